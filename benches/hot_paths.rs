@@ -0,0 +1,84 @@
+// Benchmarks for the parsing and request-building paths a scanner hits on
+// every request. Budgets are listed in README.md under "Performance".
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use serde_json::{json, Value};
+use webull_unofficial::models::{Bar, OrderAction, PlaceOrderRequest, Quote};
+use webull_unofficial::LiveWebullClient;
+
+fn quote_json() -> String {
+    json!({
+        "tickerId": 913256135, "symbol": "AAPL", "name": "Apple Inc",
+        "close": "189.50", "change": "1.25", "changeRatio": "0.0066",
+        "preClose": "188.25", "open": "188.90", "high": "190.10",
+        "low": "188.40", "volume": "52341234", "pPrice": "189.62",
+        "pChange": "0.12", "pChRatio": "0.0006", "marketValue": "2950000000000",
+        "peTtm": "29.4", "fiftyTwoWkHigh": "199.62", "fiftyTwoWkLow": "164.08",
+        "bidList": [{"price": "189.49", "volume": "300"}],
+        "askList": [{"price": "189.51", "volume": "200"}]
+    })
+    .to_string()
+}
+
+fn chart_response(rows: usize) -> Value {
+    let data: Vec<String> = (0..rows)
+        .map(|i| {
+            format!(
+                "{},189.{:02},189.{:02},190.00,188.50,189.20,{},189.41",
+                1_718_990_400 + i * 60,
+                i % 100,
+                (i + 7) % 100,
+                10_000 + i
+            )
+        })
+        .collect();
+    json!([{"tickerId": 913256135, "data": data}])
+}
+
+fn bench_quote_parsing(c: &mut Criterion) {
+    let body = quote_json();
+    c.bench_function("quote_parse", |b| {
+        b.iter(|| serde_json::from_str::<Quote>(black_box(&body)).unwrap())
+    });
+}
+
+fn bench_bar_parsing(c: &mut Criterion) {
+    let response = chart_response(1200);
+    let mut group = c.benchmark_group("bar_parse");
+    group.throughput(Throughput::Elements(1200));
+    group.bench_function("1200_bars", |b| {
+        b.iter(|| Bar::from_chart_response(black_box(&response)))
+    });
+    group.finish();
+}
+
+fn bench_order_serialization(c: &mut Criterion) {
+    let order = PlaceOrderRequest::limit(189.50)
+        .ticker_id(913256135)
+        .action(OrderAction::Buy)
+        .quantity(10.0)
+        .build()
+        .unwrap();
+    c.bench_function("order_serialize", |b| {
+        b.iter(|| {
+            let payload = LiveWebullClient::order_payload(black_box(&order)).unwrap();
+            serde_json::to_vec(&payload).unwrap()
+        })
+    });
+}
+
+fn bench_header_construction(c: &mut Criterion) {
+    let client = LiveWebullClient::new(None).unwrap();
+    c.bench_function("build_req_headers", |b| {
+        b.iter(|| client.build_req_headers(black_box(true), true, true))
+    });
+}
+
+criterion_group!(
+    hot_paths,
+    bench_quote_parsing,
+    bench_bar_parsing,
+    bench_order_serialization,
+    bench_header_construction
+);
+criterion_main!(hot_paths);
@@ -550,13 +550,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             }
                         } else {
                             if let Some(account_id) = &tester.account_id {
-                                let uuid = Uuid::new_v4();
-                                format!(
-                                    "{}{}/{}",
-                                    tester.endpoints.cancel_order(account_id),
-                                    order_id_str,
-                                    uuid
-                                )
+                                let serial = Uuid::new_v4().to_string();
+                                tester
+                                    .endpoints
+                                    .cancel_order(account_id, &order_id_str, &serial)
                             } else {
                                 continue;
                             }
@@ -26,7 +26,8 @@ async fn main() -> Result<()> {
         .login_with()
         .username(&username)
         .password(&password)
-        .await?;
+        .await?
+        .into_success()?;
 
     println!("✓ Logged in to paper account");
 
@@ -33,6 +33,7 @@ async fn main() -> Result<()> {
     match client
         .login(&username, &password, None, None, None, None)
         .await
+        .and_then(|outcome| outcome.into_success())
     {
         Ok(response) => {
             println!("Login successful!");
@@ -101,12 +102,12 @@ async fn main() -> Result<()> {
                 match client.get_quotes(&ticker.ticker_id.to_string()).await {
                     Ok(quote) => {
                         println!("Price: ${:.2}", quote.close);
-                        println!(
-                            "Change: ${:.2} ({:.2}%)",
-                            quote.change,
-                            quote.change_ratio * 100.0
-                        );
-                        println!("Volume: {}", quote.volume);
+                        if let (Some(change), Some(ratio)) = (quote.change, quote.change_ratio) {
+                            println!("Change: ${:.2} ({:.2}%)", change, ratio * 100.0);
+                        }
+                        if let Some(volume) = quote.volume {
+                            println!("Volume: {}", volume);
+                        }
                     }
                     Err(e) => eprintln!("Failed to get quotes: {}", e),
                 }
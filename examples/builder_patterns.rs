@@ -27,7 +27,8 @@ async fn main() -> Result<()> {
     println!("Logging in...");
     client
         .login(&username, &password, None, None, None, None)
-        .await?;
+        .await?
+        .into_success()?;
 
     // Find a ticker (e.g., AAPL)
     println!("\nFinding ticker AAPL...");
@@ -61,7 +62,7 @@ async fn main() -> Result<()> {
         // Example 1: Simple bars request with builder (for demonstration)
         let _bars_request = BarsRequestBuilder::new()
             .ticker_id(&ticker_id_str)
-            .interval("5m")
+            .interval(BarInterval::M5)
             .count(50)
             .build()
             .map_err(|e| WebullError::InvalidRequest(e))?;
@@ -69,7 +70,7 @@ async fn main() -> Result<()> {
         let bars = client
             .get_bars_with()
             .ticker_id(&ticker_id_str)
-            .interval("5m")
+            .interval(BarInterval::M5)
             .count(50)
             .await?;
 
@@ -85,7 +86,7 @@ async fn main() -> Result<()> {
         let daily_bars = client
             .get_bars_with()
             .ticker_id(&ticker_id_str)
-            .interval("1d")
+            .interval(BarInterval::D1)
             .count(30)
             .from_date(chrono::Utc::now() - chrono::Duration::days(30))
             .await?;
@@ -95,7 +96,7 @@ async fn main() -> Result<()> {
         // Example 3: Using the builder directly
         let custom_bars_request = BarsRequestBuilder::new()
             .ticker_id(&ticker_id_str)
-            .interval("1h")
+            .interval(BarInterval::H1)
             .count(100)
             .timestamp(1609459200) // Specific timestamp
             .build()
@@ -150,7 +151,7 @@ async fn main() -> Result<()> {
         let recent_bars = client
             .get_bars_with()
             .ticker_id(&ticker_id_str)
-            .interval("15m")
+            .interval(BarInterval::M15)
             .count(10)
             .await?;
 
@@ -28,6 +28,7 @@ async fn main() -> Result<()> {
         match client
             .login(&username, &password, None, None, None, None)
             .await
+            .and_then(|outcome| outcome.into_success())
         {
             Ok(_) => {
                 println!("✓ Logged in to live account");
@@ -119,6 +120,7 @@ async fn main() -> Result<()> {
         match client
             .login(&username, &password, None, None, None, None)
             .await
+            .and_then(|outcome| outcome.into_success())
         {
             Ok(_) => {
                 println!("✓ Logged in to paper account");
@@ -25,7 +25,8 @@ async fn main() -> Result<()> {
         .login_with()
         .username(&username)
         .password(&password)
-        .await?;
+        .await?
+        .into_success()?;
 
     // Note: You can also still use the original API:
     // client.login(&username, &password, None, None, None, None).await?;
@@ -52,7 +53,7 @@ async fn main() -> Result<()> {
         let bars = client
             .get_bars_with()
             .ticker_id(&ticker_id_str)
-            .interval("5m")
+            .interval(BarInterval::M5)
             .count(50)
             .await?; // <-- Directly await the builder!
 
@@ -69,7 +70,7 @@ async fn main() -> Result<()> {
         let daily_bars = client
             .get_bars_with()
             .ticker_id(&ticker_id_str)
-            .interval("1d")
+            .interval(BarInterval::D1)
             .count(30)
             .from_date(chrono::Utc::now() - chrono::Duration::days(30))
             .await?;
@@ -155,7 +156,7 @@ async fn main() -> Result<()> {
         let recent_bars = client
             .get_bars_with()
             .ticker_id(&ticker_id_str)
-            .interval("15m")
+            .interval(BarInterval::M15)
             .count(10)
             .await?;
 
@@ -23,7 +23,8 @@ async fn main() -> Result<()> {
     println!("Logging in...");
     client
         .login(&username, &password, None, None, None, None)
-        .await?;
+        .await?
+        .into_success()?;
 
     // Find a ticker (e.g., AAPL)
     println!("Finding ticker AAPL...");
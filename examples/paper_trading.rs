@@ -27,7 +27,8 @@ async fn main() -> Result<()> {
     println!("Logging in to paper trading account...");
     client
         .login(&username, &password, None, None, None, None)
-        .await?;
+        .await?
+        .into_success()?;
     println!("Login successful!");
 
     // Get paper account details
@@ -81,6 +82,8 @@ async fn main() -> Result<()> {
             outside_regular_trading_hour: false,
             serial_id: Some(uuid::Uuid::new_v4().to_string()),
             combo_type: None,
+            expire_date: None,
+            short_sale: false,
         };
 
         println!(
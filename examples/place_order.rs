@@ -17,7 +17,8 @@ async fn main() -> Result<()> {
     println!("Logging in...");
     client
         .login(&username, &password, None, None, None, None)
-        .await?;
+        .await?
+        .into_success()?;
     println!("Login successful!");
 
     // Get trade token (required for placing orders)
@@ -49,6 +50,8 @@ async fn main() -> Result<()> {
             outside_regular_trading_hour: false,
             serial_id: None,
             combo_type: None,
+            expire_date: None,
+            short_sale: false,
         };
 
         println!(
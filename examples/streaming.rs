@@ -20,7 +20,8 @@ async fn main() -> webull_unofficial::error::Result<()> {
     println!("Logging in...");
     let login_response = client
         .login(&username, &password, None, None, None, None)
-        .await?;
+        .await?
+        .into_success()?;
     println!("Login successful!");
 
     // Create streaming connection
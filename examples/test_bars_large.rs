@@ -1,6 +1,6 @@
 use dotenv::dotenv;
 use std::env;
-use webull_unofficial::{error::Result, WebullClient};
+use webull_unofficial::{error::Result, models::BarInterval, WebullClient};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -13,7 +13,8 @@ async fn main() -> Result<()> {
     let mut client = WebullClient::new_paper(Some(6))?;
     client
         .login(&username, &password, None, None, None, None)
-        .await?;
+        .await?
+        .into_success()?;
 
     println!("Searching for AAPL...");
     let tickers = client.find_ticker("AAPL").await?;
@@ -26,7 +27,7 @@ async fn main() -> Result<()> {
             println!("\nFetching bars with count={}...", count);
 
             match client
-                .get_bars(&ticker.ticker_id.to_string(), "d1", *count, None)
+                .get_bars(&ticker.ticker_id.to_string(), BarInterval::D1, *count, None)
                 .await
             {
                 Ok(bars) => {
@@ -14,7 +14,8 @@ async fn main() -> Result<()> {
     let mut client = WebullClient::new_live(Some(6))?;
     client
         .login(&username, &password, None, None, None, None)
-        .await?;
+        .await?
+        .into_success()?;
 
     println!("Login successful!");
 
@@ -61,34 +62,17 @@ async fn main() -> Result<()> {
     println!("\n\nFetching historical orders (last 10)...");
     match client.get_history_orders("All", 10).await {
         Ok(history) => {
-            if let Some(orders) = history.as_array() {
-                println!("Found {} historical order(s)", orders.len());
-                for order in orders.iter().take(3) {
-                    if let Some(symbol) = order
-                        .get("ticker")
-                        .and_then(|t| t.get("symbol"))
-                        .and_then(|s| s.as_str())
-                    {
-                        let action = order
-                            .get("action")
-                            .and_then(|a| a.as_str())
-                            .unwrap_or("N/A");
-                        let status = order
-                            .get("status")
-                            .and_then(|s| s.as_str())
-                            .unwrap_or("N/A");
-                        let order_type = order
-                            .get("orderType")
-                            .and_then(|o| o.as_str())
-                            .unwrap_or("N/A");
-                        println!(
-                            "  {} {} {} - Status: {}",
-                            action, symbol, order_type, status
-                        );
-                    }
-                }
-            } else {
-                println!("No historical orders found or unexpected format");
+            println!("Found {} historical order(s)", history.len());
+            for order in history.iter().take(3) {
+                let symbol = order
+                    .ticker
+                    .as_ref()
+                    .map(|t| t.symbol.as_str())
+                    .unwrap_or("N/A");
+                println!(
+                    "  {:?} {} {:?} - Status: {}",
+                    order.action, symbol, order.order_type, order.status_text
+                );
             }
         }
         Err(e) => {
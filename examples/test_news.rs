@@ -25,6 +25,7 @@ async fn main() -> Result<()> {
         .username(&username)
         .password(&password)
         .await
+        .and_then(|outcome| outcome.into_success())
     {
         Ok(_) => {
             println!("✓ Logged in to live account");
@@ -14,7 +14,8 @@ async fn main() -> Result<()> {
     let mut client = WebullClient::new_paper(Some(6))?;
     client
         .login(&username, &password, None, None, None, None)
-        .await?;
+        .await?
+        .into_success()?;
 
     println!("Login successful!");
 
@@ -59,7 +60,7 @@ async fn main() -> Result<()> {
 
     // Also try to get historical orders
     println!("\n\nFetching historical orders...");
-    match client.get_history_orders("All", 10).await {
+    match client.get_history_orders_raw("All", 10).await {
         Ok(history) => {
             println!(
                 "Historical orders response: {}",
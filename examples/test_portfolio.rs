@@ -8,7 +8,7 @@
 // Essential for portfolio management and performance tracking.
 
 use std::env;
-use webull_unofficial::{Result, WebullClient};
+use webull_unofficial::{AccountSummary, Result, RoundingPolicy, WebullClient};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -24,6 +24,7 @@ async fn main() -> Result<()> {
     match client
         .login(&username, &password, None, None, None, None)
         .await
+        .and_then(|outcome| outcome.into_success())
     {
         Ok(_) => {
             println!("✓ Logged in to live account");
@@ -40,49 +41,12 @@ async fn main() -> Result<()> {
                     println!("  Buying Power: {:?}", account.buying_power);
 
                     // Calculate portfolio metrics
-                    let total_value = account.net_liquidation.unwrap_or_else(|| {
-                        let cash = account.total_cash.unwrap_or(0.0);
-                        let market_value = account.total_market_value.unwrap_or(0.0);
-                        cash + market_value
-                    });
-
-                    let cash_percentage = if total_value > 0.0 {
-                        account
-                            .total_cash
-                            .map(|c| (c / total_value) * 100.0)
-                            .unwrap_or(0.0)
-                    } else {
-                        0.0
-                    };
-
-                    let invested_percentage = if total_value > 0.0 {
-                        account
-                            .total_market_value
-                            .map(|m| (m / total_value) * 100.0)
-                            .unwrap_or(0.0)
-                    } else {
-                        0.0
-                    };
+                    let summary = AccountSummary::from_account(&account);
+                    let policy = RoundingPolicy::default();
 
                     println!("\n  Portfolio Metrics:");
-                    println!("    Total Value: ${:.2}", total_value);
-                    println!("    Cash: {:.1}%", cash_percentage);
-                    println!("    Invested: {:.1}%", invested_percentage);
-
-                    if let Some(unrealized_pnl) = account.unrealized_profit_loss {
-                        if let Some(unrealized_pnl_rate) = account.unrealized_profit_loss_rate {
-                            let pnl_emoji = if unrealized_pnl >= 0.0 {
-                                "📈"
-                            } else {
-                                "📉"
-                            };
-                            println!(
-                                "    {} Total P&L: ${:.2} ({:.2}%)",
-                                pnl_emoji,
-                                unrealized_pnl,
-                                unrealized_pnl_rate * 100.0
-                            );
-                        }
+                    for line in summary.render(&policy).lines() {
+                        println!("    {}", line);
                     }
 
                     println!("\n✓ Portfolio analysis successful!");
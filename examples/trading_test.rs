@@ -3,9 +3,12 @@ use dotenv::dotenv;
 use log::{error, warn};
 use std::env;
 use std::io::{self, Write};
-use std::time::Duration;
-use tokio::time::sleep;
-use webull_unofficial::{error::Result, models::*, WebullClient};
+use webull_unofficial::{
+    error::{Result, WebullError},
+    models::*,
+    toolkit::Trend,
+    AccountSummary, OrderConfirmation, PortfolioAnalytics, RoundingPolicy, SmokeTest, WebullClient,
+};
 
 // Interactive trading test suite
 
@@ -58,6 +61,7 @@ async fn main() -> Result<()> {
         .username(&username)
         .password(&password)
         .await
+        .and_then(|outcome| outcome.into_success())
     {
         Ok(_) => {
             if is_paper {
@@ -200,7 +204,17 @@ fn get_password(prompt: &str) -> String {
 
 fn confirm_action(action: &str) -> bool {
     let response = get_user_input(&format!("⚠️  {} Confirm? (y/n): ", action));
-    response.to_lowercase() == "y" || response.to_lowercase() == "yes"
+    OrderConfirmation::is_affirmative(&response)
+}
+
+/// Show the order summary and its warnings, then ask to go ahead
+fn confirm_order(order: &PlaceOrderRequest, symbol: &str, last_price: f64) -> bool {
+    let confirmation = OrderConfirmation::new(order, symbol, Some(last_price));
+    println!("\n📋 Order Summary:");
+    for line in confirmation.to_string().lines() {
+        println!("  {}", line);
+    }
+    confirm_action(&confirmation.prompt())
 }
 
 async fn display_account_info(client: &WebullClient) -> Result<()> {
@@ -255,14 +269,20 @@ async fn get_quote_interactive(client: &WebullClient) -> Result<()> {
         println!("\n📊 Quote for {} - {}", ticker.symbol, ticker.name);
         println!("────────────────────────────");
         println!("Current Price: ${:.2}", quote.close);
-        println!(
-            "Change: ${:.2} ({:.2}%)",
-            quote.close - quote.pre_close,
-            ((quote.close - quote.pre_close) / quote.pre_close) * 100.0
-        );
-        println!("Volume: {}", quote.volume);
-        println!("Day Range: ${:.2} - ${:.2}", quote.low, quote.high);
-        println!("Previous Close: ${:.2}", quote.pre_close);
+        if let Some(pre_close) = quote.pre_close {
+            println!(
+                "Change: ${:.2} ({:.2}%)",
+                quote.close - pre_close,
+                ((quote.close - pre_close) / pre_close) * 100.0
+            );
+            println!("Previous Close: ${:.2}", pre_close);
+        }
+        if let Some(volume) = quote.volume {
+            println!("Volume: {}", volume);
+        }
+        if let Some((low, high)) = quote.day_range() {
+            println!("Day Range: ${:.2} - ${:.2}", low, high);
+        }
         if let Some(market_value) = quote.market_value {
             println!("Market Cap: ${:.2}M", market_value / 1_000_000.0);
         }
@@ -293,7 +313,7 @@ async fn get_historical_data_interactive(client: &WebullClient) -> Result<()> {
         let bars = client
             .get_bars_with()
             .ticker_id(&ticker.ticker_id.to_string())
-            .interval("d1")
+            .interval(BarInterval::D1)
             .count(count)
             .await?;
 
@@ -368,35 +388,20 @@ async fn place_market_order_interactive(client: &WebullClient) -> Result<()> {
     if let Some(ticker) = tickers.first() {
         let quote = client.get_quotes(&ticker.ticker_id.to_string()).await?;
 
-        let action_str = match action {
-            OrderAction::Buy => "BUY",
-            OrderAction::Sell => "SELL",
-        };
-
-        println!("\n📋 Order Summary:");
-        println!(
-            "  Action: {} {} shares of {}",
-            action_str, quantity, ticker.symbol
-        );
-        println!("  Current Price: ${:.2}", quote.close);
-        println!("  Estimated Total: ${:.2}", quote.close * quantity);
-
-        if !confirm_action(&format!(
-            "Place this MARKET order for {} shares of {}",
-            quantity, ticker.symbol
-        )) {
-            println!("❌ Order cancelled by user");
-            return Ok(());
-        }
-
-        match client
-            .place_market_order_with()
+        let order = PlaceOrderRequest::market()
             .ticker_id(ticker.ticker_id)
             .action(action)
             .quantity(quantity)
             .time_in_force(TimeInForce::Day)
-            .await
-        {
+            .build()
+            .map_err(WebullError::InvalidRequest)?;
+
+        if !confirm_order(&order, &ticker.symbol, quote.close) {
+            println!("❌ Order cancelled by user");
+            return Ok(());
+        }
+
+        match client.place_order(&order).await {
             Ok(order_id) => {
                 println!("✅ Market order placed successfully!");
                 println!("   Order ID: {}", order_id);
@@ -451,36 +456,20 @@ async fn place_limit_order_interactive(client: &WebullClient) -> Result<()> {
     if let Some(ticker) = tickers.first() {
         let quote = client.get_quotes(&ticker.ticker_id.to_string()).await?;
 
-        let action_str = match action {
-            OrderAction::Buy => "BUY",
-            OrderAction::Sell => "SELL",
-        };
-
-        println!("\n📋 Order Summary:");
-        println!(
-            "  Action: {} {} shares of {}",
-            action_str, quantity, ticker.symbol
-        );
-        println!("  Current Price: ${:.2}", quote.close);
-        println!("  Limit Price: ${:.2}", limit_price);
-        println!("  Max Total: ${:.2}", limit_price * quantity);
-
-        if !confirm_action(&format!(
-            "Place this LIMIT order for {} shares of {} at ${:.2}",
-            quantity, ticker.symbol, limit_price
-        )) {
-            println!("❌ Order cancelled by user");
-            return Ok(());
-        }
-
-        match client
-            .place_limit_order_with(limit_price)
+        let order = PlaceOrderRequest::limit(limit_price)
             .ticker_id(ticker.ticker_id)
             .action(action)
             .quantity(quantity)
             .time_in_force(TimeInForce::GoodTillCancel)
-            .await
-        {
+            .build()
+            .map_err(WebullError::InvalidRequest)?;
+
+        if !confirm_order(&order, &ticker.symbol, quote.close) {
+            println!("❌ Order cancelled by user");
+            return Ok(());
+        }
+
+        match client.place_order(&order).await {
             Ok(order_id) => {
                 println!("✅ Limit order placed successfully!");
                 println!("   Order ID: {}", order_id);
@@ -525,28 +514,20 @@ async fn place_stop_order_interactive(client: &WebullClient) -> Result<()> {
     if let Some(ticker) = tickers.first() {
         let quote = client.get_quotes(&ticker.ticker_id.to_string()).await?;
 
-        println!("\n📋 Order Summary:");
-        println!("  Action: SELL {} shares of {}", quantity, ticker.symbol);
-        println!("  Current Price: ${:.2}", quote.close);
-        println!("  Stop Price: ${:.2}", stop_price);
-        println!("  Will trigger when price drops to: ${:.2}", stop_price);
+        let order = PlaceOrderRequest::stop(stop_price)
+            .ticker_id(ticker.ticker_id)
+            .sell()
+            .quantity(quantity)
+            .time_in_force(TimeInForce::GoodTillCancel)
+            .build()
+            .map_err(WebullError::InvalidRequest)?;
 
-        if !confirm_action(&format!(
-            "Place this STOP order for {} shares of {} at ${:.2}",
-            quantity, ticker.symbol, stop_price
-        )) {
+        if !confirm_order(&order, &ticker.symbol, quote.close) {
             println!("❌ Order cancelled by user");
             return Ok(());
         }
 
-        match client
-            .place_stop_order_with(stop_price)
-            .ticker_id(ticker.ticker_id)
-            .sell()
-            .quantity(quantity)
-            .time_in_force(TimeInForce::GoodTillCancel)
-            .await
-        {
+        match client.place_order(&order).await {
             Ok(order_id) => {
                 println!("✅ Stop-loss order placed successfully!");
                 println!("   Order ID: {}", order_id);
@@ -820,66 +801,21 @@ async fn analyze_portfolio(client: &WebullClient) -> Result<()> {
 
     match client.get_account().await {
         Ok(account) => {
-            // Get total value - use net_liquidation if available, otherwise try to calculate
-            let total_value = account.net_liquidation.unwrap_or_else(|| {
-                let cash = account.total_cash.unwrap_or(0.0);
-                let market_value = account.total_market_value.unwrap_or(0.0);
-                cash + market_value
-            });
-
-            let cash_percentage = if total_value > 0.0 {
-                account
-                    .total_cash
-                    .map(|c| (c / total_value) * 100.0)
-                    .unwrap_or(0.0)
-            } else {
-                0.0
-            };
-
-            let invested_percentage = if total_value > 0.0 {
-                account
-                    .total_market_value
-                    .map(|m| (m / total_value) * 100.0)
-                    .unwrap_or(0.0)
-            } else {
-                0.0
-            };
+            let summary = AccountSummary::from_account(&account);
+            let policy = RoundingPolicy::default();
 
             println!("\nPortfolio Allocation:");
-            if total_value > 0.0 {
-                println!("  Total Value: ${:.2}", total_value);
+            for line in summary.render(&policy).lines() {
+                println!("  {}", line);
             }
-            if let Some(total_cash) = account.total_cash {
-                println!("  Cash: ${:.2} ({:.1}%)", total_cash, cash_percentage);
-            }
-            if let Some(total_market_value) = account.total_market_value {
-                println!(
-                    "  Invested: ${:.2} ({:.1}%)",
-                    total_market_value, invested_percentage
-                );
+            match summary.trend() {
+                Trend::Up => println!("  📈 Positive performance!"),
+                Trend::Down => println!("  📉 Negative performance"),
+                Trend::Flat => println!("  ➡️ Flat performance"),
             }
 
-            println!("\nPerformance Metrics:");
-            if let Some(unrealized_pl) = account.unrealized_profit_loss {
-                println!("  Unrealized P&L: ${:.2}", unrealized_pl);
-                if unrealized_pl > 0.0 {
-                    println!("  📈 Positive performance!");
-                } else if unrealized_pl < 0.0 {
-                    println!("  📉 Negative performance");
-                } else {
-                    println!("  ➡️ Flat performance");
-                }
-            }
-            if let Some(unrealized_pl_rate) = account.unrealized_profit_loss_rate {
-                println!("  Unrealized P&L Rate: {:.2}%", unrealized_pl_rate * 100.0);
-            }
-
-            println!("\nRisk Metrics:");
-            if let Some(buying_power) = account.buying_power {
-                println!("  Buying Power: ${:.2}", buying_power);
-            }
-            if let Some(unsettled_funds) = account.unsettled_funds {
-                println!("  Unsettled Funds: ${:.2}", unsettled_funds);
+            if let Some(unsettled_funds) = summary.unsettled_funds {
+                println!("  Unsettled Funds: {}", policy.money(unsettled_funds));
             }
         }
         Err(e) => {
@@ -887,32 +823,35 @@ async fn analyze_portfolio(client: &WebullClient) -> Result<()> {
         }
     }
 
-    // Display positions (if available for live trading)
-    match client.get_positions().await {
-        Ok(positions) => {
-            if !positions.is_empty() {
+    // Position breakdown (if available for live trading)
+    match client.portfolio_report(&PortfolioAnalytics::new()).await {
+        Ok(report) => {
+            if !report.positions.is_empty() {
                 println!("\nCurrent Positions:");
                 println!("──────────────────");
-                for position in positions.iter() {
-                    if let Some(ticker) = &position.ticker {
-                        println!(
-                            "  {} {} shares @ avg ${:.2}",
-                            ticker.symbol, position.quantity, position.avg_cost
-                        );
-                        println!("    Market Value: ${:.2}", position.market_value);
-
-                        if let Some(pnl) = position.unrealized_profit_loss {
-                            let emoji = if pnl > 0.0 { "📈" } else { "📉" };
-                            let pnl_rate = position.unrealized_profit_loss_rate.unwrap_or(0.0);
-                            println!(
-                                "    {} Unrealized P&L: ${:.2} ({:.2}%)",
-                                emoji,
-                                pnl,
-                                pnl_rate * 100.0
-                            );
-                        }
-                    }
+                let policy = RoundingPolicy::default();
+                for position in &report.positions {
+                    println!(
+                        "  {} {} shares, {} ({} of portfolio)",
+                        position.symbol,
+                        policy.quantity(position.quantity),
+                        policy.money(position.market_value),
+                        policy.percent(position.weight).trim_start_matches('+')
+                    );
+                    let emoji = if position.unrealized_pnl > 0.0 {
+                        "📈"
+                    } else {
+                        "📉"
+                    };
+                    println!(
+                        "    {} Unrealized P&L: {} ({})",
+                        emoji,
+                        policy.money(position.unrealized_pnl),
+                        policy.percent(position.unrealized_pnl_pct.unwrap_or(0.0))
+                    );
                 }
+                println!("\n  Realized P&L: {}", policy.money(report.realized_pnl));
+                println!("  Concentration (HHI): {:.3}", report.concentration);
             }
         }
         Err(_) => {
@@ -976,54 +915,36 @@ async fn run_automated_test_suite(client: &WebullClient) -> Result<()> {
     println!("\n🤖 Running Automated Test Suite");
     println!("════════════════════════════════");
 
-    if !confirm_action("This will place several test orders. Continue?") {
+    if !confirm_action("This will fetch account data and quotes. Continue?") {
         println!("Test suite cancelled");
         return Ok(());
     }
 
-    println!("\n1️⃣ Testing Account Info...");
-    display_account_info(client).await?;
-    sleep(Duration::from_secs(1)).await;
-
-    println!("\n2️⃣ Testing Quote Retrieval...");
-    let test_symbols = vec!["AAPL", "MSFT", "GOOGL"];
-    for symbol in test_symbols {
-        println!("\nFetching quote for {}...", symbol);
-        if let Ok(tickers) = client.find_ticker(symbol).await {
-            if let Some(ticker) = tickers.first() {
-                if let Ok(quote) = client.get_quotes(&ticker.ticker_id.to_string()).await {
-                    println!(
-                        "  {} - Price: ${:.2}, Change: {:.2}%",
-                        ticker.symbol,
-                        quote.close,
-                        quote.change_ratio * 100.0
-                    );
-                }
-            }
+    let mut smoke_test = SmokeTest::default();
+    if confirm_action("Also place (and cancel) a test LIMIT BUY for 1 share of AAPL?") {
+        if let Some(ticker) = client.find_ticker("AAPL").await?.first() {
+            let quote = client.get_quotes(&ticker.ticker_id.to_string()).await?;
+            // Far enough under the market that it won't fill before the cancel
+            let price = (quote.close * 0.8 * 100.0).round() / 100.0;
+            let order = PlaceOrderRequest::limit(price)
+                .ticker_id(ticker.ticker_id)
+                .buy()
+                .quantity(1.0)
+                .build()
+                .map_err(WebullError::InvalidRequest)?;
+            smoke_test = smoke_test.with_order(order);
         }
-        sleep(Duration::from_millis(500)).await;
     }
 
-    println!("\n3️⃣ Testing Order Placement...");
-    if confirm_action("Place a test MARKET BUY order for 1 share of AAPL?") {
-        if let Ok(tickers) = client.find_ticker("AAPL").await {
-            if let Some(ticker) = tickers.first() {
-                match client
-                    .place_market_order_with()
-                    .ticker_id(ticker.ticker_id)
-                    .buy()
-                    .quantity(1.0)
-                    .time_in_force(TimeInForce::Day)
-                    .await
-                {
-                    Ok(order_id) => println!("  ✅ Test order placed! ID: {}", order_id),
-                    Err(e) => println!("  ❌ Test order failed: {}", e),
-                }
-            }
-        }
-    }
+    let report = smoke_test.run(client).await;
+    println!();
+    print!("{}", report);
 
-    println!("\n✅ Automated test suite complete!");
+    if report.passed() {
+        println!("\n✅ Automated test suite complete!");
+    } else {
+        println!("\n❌ {} step(s) failed", report.failures().count());
+    }
 
     Ok(())
 }
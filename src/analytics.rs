@@ -0,0 +1,394 @@
+// Portfolio P&L, returns, sector exposure and concentration
+
+use crate::{
+    error::Result,
+    format::RoundingPolicy,
+    lots::{LotMethod, LotTracker, UnmatchedSell},
+    models::{HistoryOrder, Position},
+    snapshots::AccountSnapshot,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Sector reported for positions without a sector mapping
+pub const UNCLASSIFIED: &str = "Unclassified";
+
+/// One position's share of the portfolio
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionReport {
+    pub symbol: String,
+    pub quantity: f64,
+    pub market_value: f64,
+    pub cost_basis: f64,
+    pub unrealized_pnl: f64,
+    /// Unrealized P&L as a fraction of cost basis; `None` without a cost basis
+    pub unrealized_pnl_pct: Option<f64>,
+    /// Share of total gross market value, 0-1
+    pub weight: f64,
+    pub sector: String,
+}
+
+/// Change in account value from one snapshot day to the next
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyReturn {
+    pub date: NaiveDate,
+    pub value: f64,
+    /// Fractional change from the previous day's value
+    pub return_pct: f64,
+}
+
+/// Portfolio analytics built from positions, order history and snapshots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioReport {
+    pub market_value: f64,
+    pub cost_basis: f64,
+    pub unrealized_pnl: f64,
+    /// Gains realized by the sells in the order history
+    pub realized_pnl: f64,
+    /// Sold quantities with no buy in the order history, left out of
+    /// `realized_pnl`
+    pub unmatched_sells: Vec<UnmatchedSell>,
+    /// Positions, largest weight first
+    pub positions: Vec<PositionReport>,
+    /// Share of gross market value by sector, 0-1
+    pub sector_exposure: BTreeMap<String, f64>,
+    /// Herfindahl index of position weights: 1 for a single position,
+    /// approaching 0 as the portfolio spreads out
+    pub concentration: f64,
+    pub daily_returns: Vec<DailyReturn>,
+}
+
+impl PortfolioReport {
+    /// Position with the largest weight
+    pub fn largest_position(&self) -> Option<&PositionReport> {
+        self.positions.first()
+    }
+
+    /// Combined return over every day in `daily_returns`
+    pub fn cumulative_return(&self) -> Option<f64> {
+        if self.daily_returns.is_empty() {
+            return None;
+        }
+        Some(
+            self.daily_returns
+                .iter()
+                .fold(1.0, |acc, day| acc * (1.0 + day.return_pct))
+                - 1.0,
+        )
+    }
+
+    /// Plain-text summary with one line per position
+    pub fn render(&self, policy: &RoundingPolicy) -> String {
+        let mut lines = vec![
+            format!("Market value:   {}", policy.money(self.market_value)),
+            format!(
+                "Unrealized P&L: {} ({})",
+                policy.money(self.unrealized_pnl),
+                policy.percent(if self.cost_basis.abs() > 0.0 {
+                    self.unrealized_pnl / self.cost_basis.abs()
+                } else {
+                    0.0
+                })
+            ),
+            format!("Realized P&L:   {}", policy.money(self.realized_pnl)),
+        ];
+        if !self.unmatched_sells.is_empty() {
+            lines.push(format!(
+                "  excludes {} sell(s) with no matching buy",
+                self.unmatched_sells.len()
+            ));
+        }
+        for position in &self.positions {
+            lines.push(format!(
+                "  {:<8} {:>10} {:>14} {:>14} {:>9} of portfolio",
+                position.symbol,
+                policy.quantity(position.quantity),
+                policy.money(position.market_value),
+                policy.money(position.unrealized_pnl),
+                policy.percent(position.weight).trim_start_matches('+'),
+            ));
+        }
+        if let Some(total) = self.cumulative_return() {
+            lines.push(format!("Return over period: {}", policy.percent(total)));
+        }
+        lines.join("\n")
+    }
+}
+
+impl fmt::Display for PortfolioReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(&RoundingPolicy::default()))
+    }
+}
+
+/// Builds `PortfolioReport`s
+///
+/// Sectors aren't part of Webull's position data, so they're supplied by
+/// symbol; anything unmapped is grouped under `UNCLASSIFIED`.
+#[derive(Debug, Clone)]
+pub struct PortfolioAnalytics {
+    sectors: HashMap<String, String>,
+    lot_method: LotMethod,
+}
+
+impl Default for PortfolioAnalytics {
+    fn default() -> Self {
+        Self {
+            sectors: HashMap::new(),
+            lot_method: LotMethod::Fifo,
+        }
+    }
+}
+
+impl PortfolioAnalytics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Assign a symbol to a sector
+    pub fn sector(mut self, symbol: &str, sector: impl Into<String>) -> Self {
+        self.sectors
+            .insert(symbol.to_ascii_uppercase(), sector.into());
+        self
+    }
+
+    /// Assign sectors from `(symbol, sector)` pairs
+    pub fn sectors<I, S, T>(mut self, sectors: I) -> Self
+    where
+        I: IntoIterator<Item = (S, T)>,
+        S: AsRef<str>,
+        T: Into<String>,
+    {
+        for (symbol, sector) in sectors {
+            self.sectors
+                .insert(symbol.as_ref().to_ascii_uppercase(), sector.into());
+        }
+        self
+    }
+
+    /// How sells are matched to buys for realized P&L (default FIFO)
+    pub fn lot_method(mut self, method: LotMethod) -> Self {
+        self.lot_method = method;
+        self
+    }
+
+    /// Build a report
+    ///
+    /// Realized P&L comes from the filled orders in `orders`. Sells with no
+    /// matching buy (shorts, transfers in, truncated history) are left out
+    /// of it and listed in `unmatched_sells`. Daily returns use the last
+    /// snapshot of each day that has an account value.
+    pub fn report(
+        &self,
+        positions: &[Position],
+        orders: &[HistoryOrder],
+        snapshots: &[AccountSnapshot],
+    ) -> Result<PortfolioReport> {
+        let (lots, closed) = LotTracker::from_history(orders, self.lot_method);
+        let realized_pnl = closed.iter().map(|lot| lot.gain()).sum();
+
+        let mut reports: Vec<PositionReport> = positions
+            .iter()
+            .filter_map(|position| {
+                let symbol = position.ticker.as_ref()?.symbol.clone();
+                let cost_basis = position.quantity * position.avg_cost;
+                let unrealized_pnl = position
+                    .unrealized_profit_loss
+                    .unwrap_or(position.market_value - cost_basis);
+                let sector = self
+                    .sectors
+                    .get(&symbol.to_ascii_uppercase())
+                    .cloned()
+                    .unwrap_or_else(|| UNCLASSIFIED.to_string());
+                Some(PositionReport {
+                    symbol,
+                    quantity: position.quantity,
+                    market_value: position.market_value,
+                    cost_basis,
+                    unrealized_pnl,
+                    unrealized_pnl_pct: (cost_basis.abs() > 0.0)
+                        .then(|| unrealized_pnl / cost_basis.abs()),
+                    weight: 0.0,
+                    sector,
+                })
+            })
+            .collect();
+
+        // Weights use gross value so short positions count as exposure
+        let gross: f64 = reports.iter().map(|p| p.market_value.abs()).sum();
+        let mut sector_exposure = BTreeMap::new();
+        for report in &mut reports {
+            report.weight = if gross > 0.0 {
+                report.market_value.abs() / gross
+            } else {
+                0.0
+            };
+            *sector_exposure.entry(report.sector.clone()).or_insert(0.0) += report.weight;
+        }
+        reports.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+
+        Ok(PortfolioReport {
+            market_value: reports.iter().map(|p| p.market_value).sum(),
+            cost_basis: reports.iter().map(|p| p.cost_basis).sum(),
+            unrealized_pnl: reports.iter().map(|p| p.unrealized_pnl).sum(),
+            realized_pnl,
+            unmatched_sells: lots.unmatched().to_vec(),
+            concentration: reports.iter().map(|p| p.weight * p.weight).sum(),
+            positions: reports,
+            sector_exposure,
+            daily_returns: daily_returns(snapshots),
+        })
+    }
+}
+
+/// Day-over-day returns from the last snapshot of each day
+///
+/// Snapshots may be in any order; those without an account value are
+/// ignored, as is a day following a zero value.
+pub fn daily_returns(snapshots: &[AccountSnapshot]) -> Vec<DailyReturn> {
+    let mut closing: BTreeMap<NaiveDate, (chrono::DateTime<chrono::Utc>, f64)> = BTreeMap::new();
+    for snapshot in snapshots {
+        let value = match snapshot.account_value {
+            Some(value) => value,
+            None => continue,
+        };
+        let entry = closing
+            .entry(snapshot.date())
+            .or_insert((snapshot.taken_at, value));
+        if snapshot.taken_at >= entry.0 {
+            *entry = (snapshot.taken_at, value);
+        }
+    }
+
+    closing
+        .iter()
+        .zip(closing.iter().skip(1))
+        .filter(|((_, (_, previous)), _)| *previous != 0.0)
+        .map(|((_, (_, previous)), (date, (_, value)))| DailyReturn {
+            date: *date,
+            value: *value,
+            return_pct: value / previous - 1.0,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn position(symbol: &str, quantity: f64, avg_cost: f64, price: f64) -> Position {
+        serde_json::from_value(json!({
+            "ticker": {"tickerId": 1, "disSymbol": symbol, "name": symbol},
+            "position": quantity.to_string(),
+            "costPrice": avg_cost.to_string(),
+            "cost": (quantity * avg_cost).to_string(),
+            "marketValue": (quantity * price).to_string(),
+            "lastPrice": price.to_string()
+        }))
+        .unwrap()
+    }
+
+    fn fill(symbol: &str, action: &str, quantity: f64, price: f64, time: &str) -> HistoryOrder {
+        HistoryOrder::from_value(&json!({
+            "orderId": format!("{}-{}", symbol, time),
+            "action": action,
+            "orderType": "MKT",
+            "status": "Filled",
+            "timeInForce": "DAY",
+            "totalQuantity": quantity.to_string(),
+            "filledQuantity": quantity.to_string(),
+            "avgFilledPrice": price.to_string(),
+            "filledTime": time,
+            "ticker": {"tickerId": 1, "disSymbol": symbol, "name": symbol}
+        }))
+        .unwrap()
+    }
+
+    fn snapshot(taken_at: &str, value: f64) -> AccountSnapshot {
+        let account = serde_json::from_value(json!({
+            "secAccountId": 12345678,
+            "netLiquidation": value.to_string()
+        }))
+        .unwrap();
+        let mut snapshot = AccountSnapshot::new(account, Vec::new(), Vec::new());
+        snapshot.taken_at = taken_at.parse().unwrap();
+        snapshot
+    }
+
+    #[test]
+    fn test_report_pnl_exposure_and_concentration() {
+        let positions = [
+            position("AAPL", 10.0, 150.0, 180.0),
+            position("MSFT", 5.0, 450.0, 400.0),
+            position("XOM", 20.0, 60.0, 60.0),
+        ];
+        let orders = [
+            fill("AAPL", "BUY", 15.0, 150.0, "2024-05-01T14:00:00Z"),
+            fill("AAPL", "SELL", 5.0, 170.0, "2024-05-10T14:00:00Z"),
+        ];
+        let snapshots = [
+            snapshot("2024-06-03T20:00:00Z", 10_000.0),
+            snapshot("2024-06-04T15:00:00Z", 9_000.0),
+            snapshot("2024-06-04T20:00:00Z", 10_500.0),
+            snapshot("2024-06-05T20:00:00Z", 10_290.0),
+        ];
+
+        let report = PortfolioAnalytics::new()
+            .sectors([("aapl", "Technology"), ("MSFT", "Technology")])
+            .report(&positions, &orders, &snapshots)
+            .unwrap();
+
+        assert!((report.realized_pnl - 100.0).abs() < 1e-9);
+        assert!((report.market_value - 5_000.0).abs() < 1e-9);
+        assert!((report.unrealized_pnl - 50.0).abs() < 1e-9);
+
+        let largest = report.largest_position().unwrap();
+        assert_eq!(largest.symbol, "MSFT");
+        assert!((largest.weight - 0.4).abs() < 1e-9);
+        assert!((largest.unrealized_pnl_pct.unwrap() + 1.0 / 9.0).abs() < 1e-9);
+        assert!((report.sector_exposure["Technology"] - 0.76).abs() < 1e-9);
+        assert!((report.sector_exposure[UNCLASSIFIED] - 0.24).abs() < 1e-9);
+        // 0.4^2 + 0.36^2 + 0.24^2
+        assert!((report.concentration - 0.3472).abs() < 1e-9);
+
+        // The intraday 9,000 snapshot is superseded by the close
+        assert_eq!(report.daily_returns.len(), 2);
+        assert!((report.daily_returns[0].return_pct - 0.05).abs() < 1e-9);
+        assert!((report.daily_returns[1].return_pct + 0.02).abs() < 1e-9);
+        assert!((report.cumulative_return().unwrap() - 0.029).abs() < 1e-9);
+
+        let text = report.to_string();
+        assert!(text.contains("Market value:   $5,000.00"));
+        assert!(text.contains("Realized P&L:   $100.00"));
+        assert!(text.contains("Return over period: +2.90%"));
+        assert!(report.unmatched_sells.is_empty());
+    }
+
+    #[test]
+    fn test_sells_without_buys_are_reported_not_fatal() {
+        let positions = [position("AAPL", 10.0, 150.0, 180.0)];
+        let orders = [
+            fill("TSLA", "SELL", 3.0, 200.0, "2024-05-02T14:00:00Z"),
+            fill("AAPL", "BUY", 10.0, 150.0, "2024-05-03T14:00:00Z"),
+            fill("AAPL", "SELL", 4.0, 160.0, "2024-05-04T14:00:00Z"),
+        ];
+        let report = PortfolioAnalytics::new()
+            .report(&positions, &orders, &[])
+            .unwrap();
+
+        assert!((report.realized_pnl - 40.0).abs() < 1e-9);
+        assert_eq!(report.unmatched_sells.len(), 1);
+        assert_eq!(report.unmatched_sells[0].symbol, "TSLA");
+        assert_eq!(report.unmatched_sells[0].quantity, 3.0);
+        assert_eq!(report.positions.len(), 1);
+        assert!(report
+            .to_string()
+            .contains("excludes 1 sell(s) with no matching buy"));
+    }
+}
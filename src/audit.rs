@@ -0,0 +1,286 @@
+// Capture of outgoing trading payloads for auditing and diffing
+
+use crate::error::Result;
+use crate::live_client::LiveWebullClient;
+use crate::models::PlaceOrderRequest;
+use crate::paper_client::PaperWebullClient;
+use crate::rate_limit::EndpointClass;
+use crate::telemetry::{redact_body, redact_url};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::sync::Arc;
+
+/// A trading request body as it was sent, with credentials redacted
+///
+/// Trading PIN hashes (`pwd`), access and refresh tokens and device IDs
+/// are replaced in both the URL and the body before the hook sees them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedPayload {
+    pub captured_at: DateTime<Utc>,
+    pub method: String,
+    pub url: String,
+    pub class: EndpointClass,
+    /// Serialized body, including fields the client injects (serialId,
+    /// comboType, ...)
+    pub body: Value,
+}
+
+/// Callback invoked with every captured payload
+#[derive(Clone)]
+pub struct PayloadHook(Arc<dyn Fn(&CapturedPayload) + Send + Sync>);
+
+impl PayloadHook {
+    pub fn new(hook: impl Fn(&CapturedPayload) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(hook))
+    }
+
+    pub(crate) fn capture(&self, request: &reqwest::Request) {
+        let class = EndpointClass::from_url(request.url().as_str());
+        if class != EndpointClass::Trading {
+            return;
+        }
+        let body = match request
+            .body()
+            .and_then(|b| b.as_bytes())
+            .and_then(|bytes| serde_json::from_slice(bytes).ok())
+        {
+            Some(body) => redact_body(&body),
+            None => return,
+        };
+
+        (self.0)(&CapturedPayload {
+            captured_at: Utc::now(),
+            method: request.method().to_string(),
+            url: redact_url(request.url()),
+            class,
+            body,
+        });
+    }
+}
+
+impl fmt::Debug for PayloadHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PayloadHook")
+    }
+}
+
+/// One field that differs between the live and paper order bodies
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayloadDifference {
+    /// Dotted path to the field, e.g. `lmtPrice` or `legs[0].strike`
+    pub path: String,
+    /// Value in the live body, `None` if the field is only sent to paper
+    pub live: Option<Value>,
+    /// Value in the paper body, `None` if the field is only sent live
+    pub paper: Option<Value>,
+}
+
+impl fmt::Display for PayloadDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.live, &self.paper) {
+            (Some(live), Some(paper)) => {
+                write!(f, "~ {}: live {} / paper {}", self.path, live, paper)
+            }
+            (Some(live), None) => write!(f, "+ {}: {} (live only)", self.path, live),
+            (None, Some(paper)) => write!(f, "- {}: {} (paper only)", self.path, paper),
+            (None, None) => write!(f, "  {}", self.path),
+        }
+    }
+}
+
+/// The bodies an order would be sent with in live and paper mode
+///
+/// Both bodies go through the same client-side additions as a real
+/// `place_order` call (`comboType`, `lmtPrice`, `auxPrice`, the extended
+/// hours override, ...) without sending anything. A generated `serialId`
+/// is shared between the two so it doesn't show up as a difference.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderDryRun {
+    pub live: Value,
+    pub paper: Value,
+    pub differences: Vec<PayloadDifference>,
+}
+
+impl OrderDryRun {
+    pub fn new(order: &PlaceOrderRequest) -> Result<Self> {
+        let mut order = order.clone();
+        if order.serial_id.is_none() {
+            order.serial_id = Some(uuid::Uuid::new_v4().to_string());
+        }
+        let live = LiveWebullClient::order_payload(&order)?;
+        let paper = PaperWebullClient::order_payload(&order)?;
+        let mut differences = Vec::new();
+        diff_values("", Some(&live), Some(&paper), &mut differences);
+        Ok(Self {
+            live,
+            paper,
+            differences,
+        })
+    }
+
+    /// Whether both modes would send the same body
+    pub fn is_identical(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+impl fmt::Display for OrderDryRun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pretty = |value: &Value| serde_json::to_string_pretty(value).unwrap_or_default();
+        writeln!(f, "live:\n{}", pretty(&self.live))?;
+        writeln!(f, "paper:\n{}", pretty(&self.paper))?;
+        if self.differences.is_empty() {
+            return writeln!(f, "no differences");
+        }
+        writeln!(f, "differences:")?;
+        for difference in &self.differences {
+            writeln!(f, "{}", difference)?;
+        }
+        Ok(())
+    }
+}
+
+fn diff_values(
+    path: &str,
+    live: Option<&Value>,
+    paper: Option<&Value>,
+    out: &mut Vec<PayloadDifference>,
+) {
+    let child = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+    match (live, paper) {
+        (Some(Value::Object(live)), Some(Value::Object(paper))) => {
+            let mut keys: Vec<&String> = live.keys().chain(paper.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                diff_values(&child(key), live.get(key), paper.get(key), out);
+            }
+        }
+        (Some(Value::Array(live)), Some(Value::Array(paper))) => {
+            for i in 0..live.len().max(paper.len()) {
+                let path = format!("{}[{}]", path, i);
+                diff_values(&path, live.get(i), paper.get(i), out);
+            }
+        }
+        (live, paper) if live != paper => out.push(PayloadDifference {
+            path: path.to_string(),
+            live: live.cloned(),
+            paper: paper.cloned(),
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use serde_json::json;
+
+    #[test]
+    fn test_captures_trading_json_bodies_only() {
+        let captured = Arc::new(Mutex::new(Vec::new()));
+        let sink = captured.clone();
+        let hook = PayloadHook::new(move |payload| sink.lock().push(payload.clone()));
+
+        let client = reqwest::Client::new();
+        let order = client
+            .post("https://ustrade.webullfinance.com/api/trade/order/123/placeStockOrder")
+            .json(&json!({"tickerId": 913256135, "comboType": "NORMAL", "serialId": "abc"}))
+            .build()
+            .unwrap();
+        let login = client
+            .post("https://userapi.webull.com/api/passport/login/v5/account")
+            .json(&json!({"pwd": "hash"}))
+            .build()
+            .unwrap();
+        hook.capture(&order);
+        hook.capture(&login);
+
+        let captured = captured.lock();
+        assert_eq!(captured.len(), 1);
+        assert_eq!(captured[0].method, "POST");
+        assert_eq!(captured[0].body["comboType"], "NORMAL");
+    }
+
+    #[test]
+    fn test_captured_credentials_are_redacted() {
+        let captured = Arc::new(Mutex::new(Vec::new()));
+        let sink = captured.clone();
+        let hook = PayloadHook::new(move |payload| sink.lock().push(payload.clone()));
+
+        let client = reqwest::Client::new();
+        let trade_login = client
+            .post("https://trade.webullfintech.com/api/trading/v1/global/trade/login")
+            .json(&json!({"pwd": "trade-pin-hash"}))
+            .build()
+            .unwrap();
+        let order = client
+            .post("https://ustrade.webullfinance.com/api/trade/order/123/placeStockOrder?access_token=url-token")
+            .json(&json!({
+                "tickerId": 913256135,
+                "accessToken": "access-token",
+                "extInfo": {"refreshToken": "refresh-token"}
+            }))
+            .build()
+            .unwrap();
+        hook.capture(&trade_login);
+        hook.capture(&order);
+
+        let captured = captured.lock();
+        assert_eq!(captured.len(), 2);
+        let logged = serde_json::to_string(&*captured).unwrap();
+        for secret in [
+            "trade-pin-hash",
+            "access-token",
+            "refresh-token",
+            "url-token",
+        ] {
+            assert!(!logged.contains(secret), "{} reached the sink", secret);
+        }
+        assert_eq!(captured[1].body["tickerId"], 913256135);
+    }
+
+    #[test]
+    fn test_dry_run_highlights_live_only_fields() {
+        let order = PlaceOrderRequest::stop(95.0)
+            .ticker_id(913256135)
+            .action(crate::models::OrderAction::Sell)
+            .quantity(10.0)
+            .build()
+            .unwrap();
+        let dry_run = OrderDryRun::new(&order).unwrap();
+        assert_eq!(dry_run.live["serialId"], dry_run.paper["serialId"]);
+
+        let paths: Vec<&str> = dry_run
+            .differences
+            .iter()
+            .map(|d| d.path.as_str())
+            .collect();
+        assert_eq!(paths, vec!["auxPrice", "comboType"]);
+        assert_eq!(dry_run.differences[1].paper, None);
+        assert_eq!(
+            dry_run.differences[1].to_string(),
+            "+ comboType: \"NORMAL\" (live only)"
+        );
+        assert!(dry_run.to_string().contains("differences:"));
+
+        let market = PlaceOrderRequest::market()
+            .ticker_id(913256135)
+            .action(crate::models::OrderAction::Buy)
+            .quantity(1.0)
+            .combo_type("NORMAL".to_string())
+            .build()
+            .unwrap();
+        assert!(OrderDryRun::new(&market).unwrap().is_identical());
+    }
+}
@@ -0,0 +1,187 @@
+// A synchronous facade over `WebullClient`, for scripts and programs that
+// don't run an async runtime
+
+use crate::config::ClientConfig;
+use crate::error::Result;
+use crate::models::*;
+use crate::unified_client::WebullClient;
+use std::future::Future;
+use tokio::runtime::{Builder, Runtime};
+
+/// A `WebullClient` that blocks on each call, like `reqwest::blocking`
+///
+/// The client owns a single-threaded Tokio runtime and drives every request
+/// to completion on the calling thread. Methods panic if called from inside
+/// another async runtime; use `WebullClient` there instead.
+#[derive(Debug)]
+pub struct BlockingWebullClient {
+    inner: WebullClient,
+    runtime: Runtime,
+}
+
+impl BlockingWebullClient {
+    /// Create a client for live trading
+    pub fn new_live(region_code: Option<i32>) -> Result<Self> {
+        Self::from_client(WebullClient::new_live(region_code)?)
+    }
+
+    /// Create a client for paper trading
+    pub fn new_paper(region_code: Option<i32>) -> Result<Self> {
+        Self::from_client(WebullClient::new_paper(region_code)?)
+    }
+
+    /// Create a live client with custom configuration
+    pub fn new_live_with_config(region_code: Option<i32>, config: ClientConfig) -> Result<Self> {
+        Self::from_client(WebullClient::new_live_with_config(region_code, config)?)
+    }
+
+    /// Create a paper client with custom configuration
+    pub fn new_paper_with_config(region_code: Option<i32>, config: ClientConfig) -> Result<Self> {
+        Self::from_client(WebullClient::new_paper_with_config(region_code, config)?)
+    }
+
+    /// Wrap a client built with `WebullClient::builder()`
+    pub fn from_client(inner: WebullClient) -> Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        Ok(Self { inner, runtime })
+    }
+
+    /// The async client underneath
+    pub fn inner(&self) -> &WebullClient {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut WebullClient {
+        &mut self.inner
+    }
+
+    /// Unwrap the async client, dropping the runtime
+    pub fn into_inner(self) -> WebullClient {
+        self.inner
+    }
+
+    /// Run any future on this client's runtime, for calls without a
+    /// blocking wrapper
+    ///
+    /// ```rust,ignore
+    /// let news = client.block_on(client.inner().get_news("AAPL", 0, 10))?;
+    /// ```
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Check if this is a paper trading client
+    pub fn is_paper(&self) -> bool {
+        self.inner.is_paper()
+    }
+
+    /// Login to the account
+    pub fn login(
+        &mut self,
+        username: &str,
+        password: &str,
+        device_name: Option<&str>,
+        mfa: Option<&str>,
+        question_id: Option<&str>,
+        question_answer: Option<&str>,
+    ) -> Result<LoginOutcome> {
+        self.runtime.block_on(self.inner.login(
+            username,
+            password,
+            device_name,
+            mfa,
+            question_id,
+            question_answer,
+        ))
+    }
+
+    /// Logout from the account
+    pub fn logout(&mut self) -> Result<bool> {
+        self.runtime.block_on(self.inner.logout())
+    }
+
+    /// Get account ID
+    pub fn get_account_id(&mut self) -> Result<String> {
+        self.runtime.block_on(self.inner.get_account_id())
+    }
+
+    /// Get trade token
+    pub fn get_trade_token(&mut self, password: &str) -> Result<String> {
+        self.runtime.block_on(self.inner.get_trade_token(password))
+    }
+
+    /// Get account details
+    pub fn get_account(&self) -> Result<AccountDetail> {
+        self.runtime.block_on(self.inner.get_account())
+    }
+
+    /// Get positions
+    pub fn get_positions(&self) -> Result<Vec<Position>> {
+        self.runtime.block_on(self.inner.get_positions())
+    }
+
+    /// Get current orders
+    pub fn get_orders(&self, page_size: Option<i32>) -> Result<Vec<Order>> {
+        self.runtime.block_on(self.inner.get_orders(page_size))
+    }
+
+    /// Place an order
+    pub fn place_order(&self, order: &PlaceOrderRequest) -> Result<String> {
+        self.runtime.block_on(self.inner.place_order(order))
+    }
+
+    /// Cancel an order
+    pub fn cancel_order(&self, order_id: &str) -> Result<bool> {
+        self.runtime.block_on(self.inner.cancel_order(order_id))
+    }
+
+    /// Get a real-time quote
+    pub fn get_quotes(&self, ticker_id: &str) -> Result<Quote> {
+        self.runtime.block_on(self.inner.get_quotes(ticker_id))
+    }
+
+    /// Get a real-time quote by symbol
+    pub fn get_quote_by_symbol(&self, symbol: &str) -> Result<Quote> {
+        self.runtime
+            .block_on(self.inner.get_quote_by_symbol(symbol))
+    }
+
+    /// Find tickers by symbol or name
+    pub fn find_ticker(&self, keyword: &str) -> Result<Vec<Ticker>> {
+        self.runtime.block_on(self.inner.find_ticker(keyword))
+    }
+
+    /// Get historical bars
+    pub fn get_bars(
+        &self,
+        ticker_id: &str,
+        interval: BarInterval,
+        count: i32,
+        timestamp: Option<i64>,
+    ) -> Result<Vec<Bar>> {
+        self.runtime
+            .block_on(self.inner.get_bars(ticker_id, interval, count, timestamp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::WebullError;
+
+    #[test]
+    fn test_blocking_client_runs_calls_to_completion() {
+        let client = BlockingWebullClient::new_paper(Some(6)).unwrap();
+        assert!(client.is_paper());
+
+        let result = client.block_on(
+            client
+                .inner()
+                .get_options_by_expiration("AAPL", "06/21/2024"),
+        );
+        assert!(matches!(result, Err(WebullError::InvalidParameter(_))));
+
+        let rewrapped = BlockingWebullClient::from_client(client.into_inner()).unwrap();
+        assert!(rewrapped.is_paper());
+    }
+}
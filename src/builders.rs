@@ -64,7 +64,7 @@ impl<'a> LoginBuilderWithClient<'a> {
 }
 
 impl<'a> std::future::IntoFuture for LoginBuilderWithClient<'a> {
-    type Output = Result<LoginResponse>;
+    type Output = Result<LoginOutcome>;
     type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + 'a>>;
 
     fn into_future(self) -> Self::IntoFuture {
@@ -94,7 +94,7 @@ impl<'a> std::future::IntoFuture for LoginBuilderWithClient<'a> {
 pub struct BarsRequestBuilderWithClient<'a> {
     client: &'a WebullClient,
     ticker_id: Option<String>,
-    interval: Option<String>,
+    interval: Option<BarInterval>,
     count: Option<i32>,
     timestamp: Option<i64>,
 }
@@ -115,8 +115,8 @@ impl<'a> BarsRequestBuilderWithClient<'a> {
         self
     }
 
-    pub fn interval(mut self, interval: impl Into<String>) -> Self {
-        self.interval = Some(interval.into());
+    pub fn interval(mut self, interval: BarInterval) -> Self {
+        self.interval = Some(interval);
         self
     }
 
@@ -151,7 +151,7 @@ impl<'a> std::future::IntoFuture for BarsRequestBuilderWithClient<'a> {
             let count = self.count.unwrap_or(100);
 
             self.client
-                .get_bars(&ticker_id, &interval, count, self.timestamp)
+                .get_bars(&ticker_id, interval, count, self.timestamp)
                 .await
         })
     }
@@ -291,11 +291,17 @@ impl<'a> std::future::IntoFuture for OptionsRequestBuilderWithClient<'a> {
 
     fn into_future(self) -> Self::IntoFuture {
         Box::pin(async move {
-            let ticker = self
-                .ticker
-                .ok_or_else(|| WebullError::InvalidRequest("ticker is required".to_string()))?;
+            let query = OptionsRequestBuilder {
+                ticker: self.ticker,
+                expiration_date: self.expiration_date,
+                option_type: self.option_type,
+                min_strike: self.min_strike,
+                max_strike: self.max_strike,
+            }
+            .build()
+            .map_err(WebullError::InvalidRequest)?;
 
-            self.client.get_options(&ticker).await
+            self.client.get_options_chain(&query).await
         })
     }
 }
@@ -313,6 +319,9 @@ pub struct PlaceOrderBuilderWithClient<'a> {
     outside_regular_trading_hour: bool,
     serial_id: Option<String>,
     combo_type: Option<String>,
+    expire_date: Option<chrono::NaiveDate>,
+    short_sale: bool,
+    check_price_band: bool,
 }
 
 impl<'a> PlaceOrderBuilderWithClient<'a> {
@@ -330,6 +339,9 @@ impl<'a> PlaceOrderBuilderWithClient<'a> {
             outside_regular_trading_hour: false,
             serial_id: None,
             combo_type: None,
+            expire_date: None,
+            short_sale: false,
+            check_price_band: false,
         }
     }
 
@@ -364,6 +376,9 @@ impl<'a> PlaceOrderBuilderWithClient<'a> {
             outside_regular_trading_hour: false,
             serial_id: None,
             combo_type: None,
+            expire_date: None,
+            short_sale: false,
+            check_price_band: false,
         }
     }
 
@@ -387,6 +402,13 @@ impl<'a> PlaceOrderBuilderWithClient<'a> {
         self
     }
 
+    /// Sell short, opening a short position
+    pub fn sell_short(mut self) -> Self {
+        self.action = Some(OrderAction::Sell);
+        self.short_sale = true;
+        self
+    }
+
     pub fn quantity(mut self, quantity: f64) -> Self {
         self.quantity = Some(quantity);
         self
@@ -397,6 +419,13 @@ impl<'a> PlaceOrderBuilderWithClient<'a> {
         self
     }
 
+    /// Make the order good-til-date, expiring at the end of `date`
+    pub fn good_till_date(mut self, date: chrono::NaiveDate) -> Self {
+        self.time_in_force = TimeInForce::GoodTillDate;
+        self.expire_date = Some(date);
+        self
+    }
+
     /// Set limit price (for limit and stop-limit orders)
     pub fn limit(mut self, price: f64) -> Self {
         self.limit_price = Some(price);
@@ -433,6 +462,12 @@ impl<'a> PlaceOrderBuilderWithClient<'a> {
         self.combo_type = Some(combo_type);
         self
     }
+
+    /// Validate the limit price against the ticker's price band before submitting
+    pub fn check_price_band(mut self) -> Self {
+        self.check_price_band = true;
+        self
+    }
 }
 
 impl<'a> std::future::IntoFuture for PlaceOrderBuilderWithClient<'a> {
@@ -496,6 +531,18 @@ impl<'a> std::future::IntoFuture for PlaceOrderBuilderWithClient<'a> {
                 _ => {}
             }
 
+            validate_expiration(
+                &self.time_in_force,
+                self.expire_date,
+                crate::market::today(),
+            )
+            .map_err(WebullError::InvalidRequest)?;
+            if self.short_sale && action != OrderAction::Sell {
+                return Err(WebullError::InvalidRequest(
+                    "short sale orders must sell".to_string(),
+                ));
+            }
+
             let order = PlaceOrderRequest {
                 ticker_id,
                 action,
@@ -507,9 +554,15 @@ impl<'a> std::future::IntoFuture for PlaceOrderBuilderWithClient<'a> {
                 outside_regular_trading_hour: self.outside_regular_trading_hour,
                 serial_id: self.serial_id,
                 combo_type: self.combo_type,
+                expire_date: self.expire_date,
+                short_sale: self.short_sale,
             };
 
-            self.client.place_order(&order).await
+            if self.check_price_band {
+                self.client.place_order_checked(&order).await
+            } else {
+                self.client.place_order(&order).await
+            }
         })
     }
 }
@@ -546,13 +599,13 @@ mod tests {
         // Test builder pattern
         let builder = builder
             .ticker_id("913256135")
-            .interval("5m")
+            .interval(BarInterval::M5)
             .count(100)
             .timestamp(1234567890);
 
         // Verify fields are set
         assert_eq!(builder.ticker_id, Some("913256135".to_string()));
-        assert_eq!(builder.interval, Some("5m".to_string()));
+        assert_eq!(builder.interval, Some(BarInterval::M5));
         assert_eq!(builder.count, Some(100));
         assert_eq!(builder.timestamp, Some(1234567890));
     }
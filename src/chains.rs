@@ -0,0 +1,275 @@
+// Bulk option chain downloads with bounded concurrency and local storage
+
+use crate::{
+    error::Result,
+    live_client::LiveWebullClient,
+    models::{OptionContract, OptionsQuery},
+};
+use chrono::{NaiveDate, Utc};
+use futures::stream::{self, StreamExt};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Reported after each underlying finishes, successfully or not
+#[derive(Debug, Clone)]
+pub struct ChainProgress {
+    pub symbol: String,
+    pub completed: usize,
+    pub total: usize,
+    /// Contracts kept for this symbol
+    pub contracts: usize,
+    pub error: Option<String>,
+}
+
+/// Chains fetched by a bulk download, keyed by symbol
+#[derive(Debug, Clone, Default)]
+pub struct OptionChainSet {
+    pub chains: BTreeMap<String, Vec<OptionContract>>,
+    /// Symbols that still failed after retrying, with the last error
+    pub failed: BTreeMap<String, String>,
+}
+
+impl OptionChainSet {
+    /// Write each chain to `<dir>/<SYMBOL>.jsonl`, one contract per line
+    pub fn save(&self, dir: impl AsRef<Path>) -> Result<()> {
+        for (symbol, contracts) in &self.chains {
+            save_chain(dir.as_ref(), symbol, contracts)?;
+        }
+        Ok(())
+    }
+
+    /// Read every chain file in a directory written by `save`
+    pub fn load(dir: impl AsRef<Path>) -> Result<Self> {
+        let mut set = Self::default();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+                continue;
+            }
+            let symbol = match path.file_stem().and_then(|s| s.to_str()) {
+                Some(symbol) => symbol.to_string(),
+                None => continue,
+            };
+            let mut contracts = Vec::new();
+            for line in BufReader::new(File::open(&path)?).lines() {
+                let line = line?;
+                if !line.trim().is_empty() {
+                    contracts.push(serde_json::from_str(&line)?);
+                }
+            }
+            set.chains.insert(symbol, contracts);
+        }
+        Ok(set)
+    }
+
+    /// Total contracts across all chains
+    pub fn contract_count(&self) -> usize {
+        self.chains.values().map(Vec::len).sum()
+    }
+}
+
+fn save_chain(dir: &Path, symbol: &str, contracts: &[OptionContract]) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    let mut writer = BufWriter::new(File::create(
+        dir.join(format!("{}.jsonl", symbol.to_ascii_uppercase())),
+    )?);
+    for contract in contracts {
+        serde_json::to_writer(&mut writer, contract)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Whether a contract expires within `dte_range` days of `today`
+fn within_dte(
+    contract: &OptionContract,
+    today: NaiveDate,
+    dte_range: &RangeInclusive<i64>,
+) -> bool {
+    NaiveDate::parse_from_str(&contract.expiration_date, "%Y-%m-%d")
+        .is_ok_and(|expiry| dte_range.contains(&(expiry - today).num_days()))
+}
+
+type ProgressCallback = Arc<dyn Fn(&ChainProgress) + Send + Sync>;
+
+/// Fetches option chains for many underlyings
+///
+/// Created by `download_option_chains`. Up to `concurrency` chains are
+/// fetched at once; transient failures are retried with the client's retry
+/// policy. With `save_to`, each chain is written as soon as it arrives, so
+/// an interrupted download keeps what it already fetched.
+pub struct OptionChainDownload<'a> {
+    client: &'a LiveWebullClient,
+    symbols: Vec<String>,
+    dte_range: RangeInclusive<i64>,
+    concurrency: usize,
+    progress: Option<ProgressCallback>,
+    store: Option<PathBuf>,
+}
+
+impl<'a> OptionChainDownload<'a> {
+    pub(crate) fn new(
+        client: &'a LiveWebullClient,
+        symbols: Vec<String>,
+        dte_range: RangeInclusive<i64>,
+    ) -> Self {
+        Self {
+            client,
+            symbols,
+            dte_range,
+            concurrency: 4,
+            progress: None,
+            store: None,
+        }
+    }
+
+    /// Set how many chains are fetched at once (default 4)
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Call `callback` after each symbol completes
+    pub fn on_progress(
+        mut self,
+        callback: impl Fn(&ChainProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Write each chain to `<dir>/<SYMBOL>.jsonl` as it arrives
+    pub fn save_to(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.store = Some(dir.into());
+        self
+    }
+
+    /// Run the download
+    ///
+    /// Per-symbol failures are collected in `OptionChainSet::failed` rather
+    /// than aborting the batch; only local write errors are returned.
+    pub async fn run(self) -> Result<OptionChainSet> {
+        let total = self.symbols.len();
+        let today = Utc::now().date_naive();
+        let mut results = stream::iter(self.symbols.iter().cloned())
+            .map(|symbol| async {
+                let result = self.fetch_with_retry(&symbol).await;
+                (symbol, result)
+            })
+            .buffer_unordered(self.concurrency);
+
+        let mut set = OptionChainSet::default();
+        let mut completed = 0;
+        while let Some((symbol, result)) = results.next().await {
+            completed += 1;
+            let (contracts, error) = match result {
+                Ok(mut contracts) => {
+                    contracts.retain(|c| within_dte(c, today, &self.dte_range));
+                    if let Some(dir) = &self.store {
+                        save_chain(dir, &symbol, &contracts)?;
+                    }
+                    let count = contracts.len();
+                    set.chains.insert(symbol.clone(), contracts);
+                    (count, None)
+                }
+                Err(e) => {
+                    log::warn!("Option chain download for {} failed: {}", symbol, e);
+                    set.failed.insert(symbol.clone(), e.to_string());
+                    (0, Some(e.to_string()))
+                }
+            };
+
+            if let Some(progress) = &self.progress {
+                progress(&ChainProgress {
+                    symbol,
+                    completed,
+                    total,
+                    contracts,
+                    error,
+                });
+            }
+        }
+        Ok(set)
+    }
+
+    async fn fetch_with_retry(&self, symbol: &str) -> Result<Vec<OptionContract>> {
+        // The chain listing is a read-only POST, which the client itself
+        // won't retry by default
+        let policy = self.client.retry_policy();
+        let query = OptionsQuery::new(symbol);
+        let mut attempt = 0;
+        loop {
+            match self.client.get_options_chain(&query).await {
+                Err(e) if e.is_retryable() && attempt < policy.max_retries => {
+                    tokio::time::sleep(policy.delay(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl fmt::Debug for OptionChainDownload<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OptionChainDownload")
+            .field("symbols", &self.symbols)
+            .field("dte_range", &self.dte_range)
+            .field("concurrency", &self.concurrency)
+            .field("store", &self.store)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn contract(symbol: &str, expiration: &str) -> OptionContract {
+        serde_json::from_value(json!({
+            "tickerId": 1,
+            "symbol": symbol,
+            "strikePrice": 150.0,
+            "expirationDate": expiration,
+            "optionType": "CALL"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_dte_filter_and_storage_round_trip() {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+        let range = 0..=30;
+        assert!(within_dte(&contract("AAPL", "2024-06-07"), today, &range));
+        assert!(within_dte(&contract("AAPL", "2024-07-03"), today, &range));
+        assert!(!within_dte(&contract("AAPL", "2024-07-04"), today, &range));
+        assert!(!within_dte(&contract("AAPL", "2024-05-31"), today, &range));
+        assert!(!within_dte(&contract("AAPL", "not a date"), today, &range));
+
+        let mut set = OptionChainSet::default();
+        set.chains.insert(
+            "AAPL".to_string(),
+            vec![
+                contract("AAPL", "2024-06-07"),
+                contract("AAPL", "2024-06-14"),
+            ],
+        );
+        set.chains
+            .insert("SPY".to_string(), vec![contract("SPY", "2024-06-07")]);
+
+        let dir = std::env::temp_dir().join(format!("webull_chains_{}", uuid::Uuid::new_v4()));
+        set.save(&dir).unwrap();
+        let loaded = OptionChainSet::load(&dir).unwrap();
+        assert_eq!(loaded.contract_count(), 3);
+        assert_eq!(loaded.chains["AAPL"][1].expiration_date, "2024-06-14");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,271 @@
+// Circuit breakers that stop calling a failing backend for a cooldown period
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+/// State of a single circuit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally
+    Closed,
+    /// Requests are rejected until the cooldown elapses
+    Open,
+    /// One probe request is allowed through to test recovery
+    HalfOpen,
+}
+
+#[derive(Debug, Default)]
+struct Circuit {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    probe_in_flight: bool,
+}
+
+/// Failure threshold and cooldown for one circuit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BreakerSettings {
+    /// Consecutive failures that open the circuit
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a probe is allowed
+    pub cooldown: Duration,
+}
+
+impl BreakerSettings {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+        }
+    }
+}
+
+/// A set of circuit breakers keyed by backend (host, endpoint class, ...)
+///
+/// A circuit opens after `failure_threshold` consecutive failures. Once
+/// `cooldown` has passed a single probe is let through; its success closes
+/// the circuit and its failure re-opens it for another cooldown. A probe
+/// whose permit is dropped without an outcome frees the slot for another.
+#[derive(Debug)]
+pub struct CircuitBreakers<K> {
+    default: Option<BreakerSettings>,
+    settings: HashMap<K, BreakerSettings>,
+    circuits: Mutex<HashMap<K, Circuit>>,
+}
+
+impl<K: Eq + Hash + Clone> CircuitBreakers<K> {
+    /// Guard every key with the same settings
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            default: Some(BreakerSettings::new(failure_threshold, cooldown)),
+            settings: HashMap::new(),
+            circuits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Guard only the given keys, each with its own settings
+    ///
+    /// Requests for other keys are always allowed.
+    pub fn with_settings(settings: HashMap<K, BreakerSettings>) -> Self {
+        Self {
+            default: None,
+            settings,
+            circuits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn settings_for(&self, key: &K) -> Option<BreakerSettings> {
+        self.settings.get(key).copied().or(self.default)
+    }
+
+    /// Current state of the circuit for `key`
+    pub fn state(&self, key: &K) -> CircuitState {
+        self.state_at(key, Instant::now())
+    }
+
+    fn state_at(&self, key: &K, now: Instant) -> CircuitState {
+        let cooldown = match self.settings_for(key) {
+            Some(settings) => settings.cooldown,
+            None => return CircuitState::Closed,
+        };
+        match self.circuits.lock().get(key).and_then(|c| c.opened_at) {
+            None => CircuitState::Closed,
+            Some(opened) if now.saturating_duration_since(opened) >= cooldown => {
+                CircuitState::HalfOpen
+            }
+            Some(_) => CircuitState::Open,
+        }
+    }
+
+    /// Permission to send a request to `key` now, or `None` while the
+    /// circuit is open
+    ///
+    /// Report the request's outcome through the permit.
+    pub fn allow(&self, key: &K) -> Option<CircuitPermit<'_, K>> {
+        self.allow_at(key, Instant::now())
+    }
+
+    fn allow_at(&self, key: &K, now: Instant) -> Option<CircuitPermit<'_, K>> {
+        let permit = |probe| CircuitPermit {
+            breakers: self,
+            key: key.clone(),
+            probe,
+        };
+        let cooldown = match self.settings_for(key) {
+            Some(settings) => settings.cooldown,
+            None => return Some(permit(false)),
+        };
+        let mut circuits = self.circuits.lock();
+        let circuit = match circuits.get_mut(key) {
+            Some(circuit) => circuit,
+            None => return Some(permit(false)),
+        };
+        match circuit.opened_at {
+            None => Some(permit(false)),
+            Some(opened) if now.saturating_duration_since(opened) >= cooldown => {
+                // Half-open: only one probe at a time
+                if std::mem::replace(&mut circuit.probe_in_flight, true) {
+                    None
+                } else {
+                    Some(permit(true))
+                }
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// Record a successful request, closing the circuit
+    pub fn record_success(&self, key: &K) {
+        self.circuits.lock().remove(key);
+    }
+
+    /// Record a failed request, opening the circuit at the threshold
+    pub fn record_failure(&self, key: &K) {
+        self.record_failure_at(key, Instant::now());
+    }
+
+    fn record_failure_at(&self, key: &K, now: Instant) {
+        let threshold = match self.settings_for(key) {
+            Some(settings) => settings.failure_threshold,
+            None => return,
+        };
+        let mut circuits = self.circuits.lock();
+        let circuit = circuits.entry(key.clone()).or_default();
+        circuit.consecutive_failures += 1;
+        if circuit.probe_in_flight || circuit.consecutive_failures >= threshold {
+            circuit.opened_at = Some(now);
+            circuit.probe_in_flight = false;
+        }
+    }
+
+    fn release_probe(&self, key: &K) {
+        if let Some(circuit) = self.circuits.lock().get_mut(key) {
+            circuit.probe_in_flight = false;
+        }
+    }
+}
+
+/// Permission to send one request, from `CircuitBreakers::allow`
+///
+/// Call `success` or `failure` once the request completes. Dropping the
+/// permit without either, e.g. when the request future is cancelled, records
+/// nothing but lets another request probe a half-open circuit.
+#[must_use = "report the request's outcome through the permit"]
+#[derive(Debug)]
+pub struct CircuitPermit<'a, K: Eq + Hash + Clone> {
+    breakers: &'a CircuitBreakers<K>,
+    key: K,
+    // Whether this permit holds the half-open probe slot
+    probe: bool,
+}
+
+impl<K: Eq + Hash + Clone> CircuitPermit<'_, K> {
+    /// Record that the request succeeded, closing the circuit
+    pub fn success(mut self) {
+        self.probe = false;
+        self.breakers.record_success(&self.key);
+    }
+
+    /// Record that the request failed
+    pub fn failure(self) {
+        self.failure_at(Instant::now());
+    }
+
+    fn failure_at(mut self, now: Instant) {
+        self.probe = false;
+        self.breakers.record_failure_at(&self.key, now);
+    }
+}
+
+impl<K: Eq + Hash + Clone> Drop for CircuitPermit<'_, K> {
+    fn drop(&mut self) {
+        if self.probe {
+            self.breakers.release_probe(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_half_open_close() {
+        let breakers = CircuitBreakers::new(2, Duration::from_secs(10));
+        let key = "quotes-gw";
+        let start = Instant::now();
+
+        breakers.record_failure_at(&key, start);
+        assert!(breakers.allow_at(&key, start).is_some());
+        breakers.record_failure_at(&key, start);
+        assert_eq!(breakers.state_at(&key, start), CircuitState::Open);
+        assert!(breakers.allow_at(&key, start).is_none());
+
+        // After the cooldown a single probe is allowed
+        let later = start + Duration::from_secs(10);
+        assert_eq!(breakers.state_at(&key, later), CircuitState::HalfOpen);
+        let probe = breakers.allow_at(&key, later).unwrap();
+        assert!(breakers.allow_at(&key, later).is_none());
+
+        // A failed probe re-opens the circuit
+        probe.failure_at(later);
+        assert!(breakers.allow_at(&key, later + Duration::from_secs(5)).is_none());
+
+        let recovered = later + Duration::from_secs(10);
+        breakers.allow_at(&key, recovered).unwrap().success();
+        assert_eq!(breakers.state_at(&key, recovered), CircuitState::Closed);
+        assert!(breakers.allow_at(&key, recovered).is_some());
+    }
+
+    #[test]
+    fn test_dropped_probe_frees_the_slot() {
+        let breakers = CircuitBreakers::new(1, Duration::from_secs(10));
+        let key = "quotes-gw";
+        let start = Instant::now();
+        breakers.record_failure_at(&key, start);
+
+        // A probe cancelled mid-flight leaves the circuit half-open
+        let later = start + Duration::from_secs(10);
+        let probe = breakers.allow_at(&key, later).unwrap();
+        assert!(breakers.allow_at(&key, later).is_none());
+        drop(probe);
+        assert_eq!(breakers.state_at(&key, later), CircuitState::HalfOpen);
+
+        breakers.allow_at(&key, later).unwrap().success();
+        assert_eq!(breakers.state_at(&key, later), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_unconfigured_keys_pass_through() {
+        let mut settings = HashMap::new();
+        settings.insert("options", BreakerSettings::new(1, Duration::from_secs(60)));
+        let breakers = CircuitBreakers::with_settings(settings);
+
+        breakers.record_failure(&"options");
+        breakers.record_failure(&"quotes");
+        assert!(breakers.allow(&"options").is_none());
+        assert!(breakers.allow(&"quotes").is_some());
+        assert_eq!(breakers.state(&"quotes"), CircuitState::Closed);
+    }
+}
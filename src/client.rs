@@ -2,3 +2,239 @@
 pub use crate::live_client::LiveWebullClient;
 pub use crate::paper_client::PaperWebullClient;
 pub use crate::unified_client::WebullClient;
+
+use crate::{
+    config::ClientConfig,
+    did::{DidStore, FileDidStore, MemoryDidStore},
+    error::{Result, WebullError},
+    middleware::{Middleware, MiddlewareStack},
+    rate_limit::EndpointClass,
+    retry::RetryPolicy,
+};
+use reqwest::header::{HeaderName, HeaderValue};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Builder for live and paper clients
+///
+/// ```no_run
+/// use webull_unofficial::{WebullClient, RetryPolicy};
+///
+/// let client = WebullClient::builder()
+///     .region(6)
+///     .timeout(30)
+///     .user_agent("my-scanner/1.0")
+///     .header("x-request-source", "scanner")
+///     .proxy("http://proxy.internal:3128")
+///     .did_path("/var/lib/scanner")
+///     .rate_limit(5.0)
+///     .retry_policy(RetryPolicy::none())
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WebullClientBuilder {
+    paper: bool,
+    region_code: Option<i32>,
+    config: ClientConfig,
+    user_agent: Option<String>,
+    headers: Vec<(String, String)>,
+    proxy: Option<String>,
+    proxy_auth: Option<(String, String)>,
+    root_certificates: Vec<reqwest::Certificate>,
+    http_client: Option<reqwest::Client>,
+    did_store: Option<Arc<dyn DidStore>>,
+    middleware: MiddlewareStack,
+}
+
+impl WebullClientBuilder {
+    /// Live client for region 6 (US) with the default configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a paper trading client instead of a live one
+    pub fn paper(mut self) -> Self {
+        self.paper = true;
+        self
+    }
+
+    /// Webull region ID (6 = US)
+    pub fn region(mut self, region_code: i32) -> Self {
+        self.region_code = Some(region_code);
+        self
+    }
+
+    /// Start from `config`; later calls override its fields
+    pub fn config(mut self, config: ClientConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Set the request timeout in seconds
+    pub fn timeout(mut self, seconds: u64) -> Self {
+        self.config.timeout = seconds;
+        self
+    }
+
+    /// Set the request timeout in seconds for one endpoint class
+    pub fn class_timeout(mut self, class: EndpointClass, seconds: u64) -> Self {
+        self.config.class_timeouts.insert(class, seconds);
+        self
+    }
+
+    /// Send `user_agent` instead of the default browser string
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Add a header to every request, replacing a default of the same name
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Send every request through the proxy at `url`
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Authenticate to the proxy with basic auth
+    pub fn proxy_auth(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.proxy_auth = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Trust `certificate` in addition to the system roots, e.g. a
+    /// corporate proxy's or traffic inspector's CA
+    pub fn root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Trust the PEM-encoded CA certificate in `pem`
+    pub fn root_certificate_pem(self, pem: &[u8]) -> Result<Self> {
+        let certificate = reqwest::Certificate::from_pem(pem).map_err(|e| {
+            WebullError::InvalidParameter(format!("Invalid root certificate: {}", e))
+        })?;
+        Ok(self.root_certificate(certificate))
+    }
+
+    /// Send requests with `client` as configured; can't be combined with
+    /// `proxy` or `root_certificate`, which configure the client this
+    /// builder would otherwise create
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Directory holding `did.bin` (the working directory by default)
+    pub fn did_path(self, dir: impl Into<PathBuf>) -> Self {
+        self.did_store(FileDidStore::new(dir))
+    }
+
+    /// Keep the device ID in `store` instead of `did.bin`
+    pub fn did_store(mut self, store: impl DidStore + 'static) -> Self {
+        self.did_store = Some(Arc::new(store));
+        self
+    }
+
+    /// Use `did` as the device ID without touching disk
+    pub fn did(self, did: impl Into<String>) -> Self {
+        self.did_store(MemoryDidStore::with_did(did))
+    }
+
+    /// Limit requests per second for each endpoint class
+    pub fn rate_limit(mut self, requests_per_sec: f64) -> Self {
+        self.config.rate_limit = Some(requests_per_sec);
+        self
+    }
+
+    /// Set the retry policy for transient failures
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.config.retry = policy;
+        self
+    }
+
+    /// Run `middleware` on every request and response
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Build a live client, ignoring `paper`
+    pub fn build_live(self) -> Result<LiveWebullClient> {
+        let http = match self.http_client.clone() {
+            Some(http) => {
+                if self.proxy.is_some() || !self.root_certificates.is_empty() {
+                    return Err(WebullError::InvalidParameter(
+                        "proxy and root certificates can't be set with a custom HTTP client"
+                            .to_string(),
+                    ));
+                }
+                http
+            }
+            None => self.http_builder()?.build()?,
+        };
+
+        let did_store = self
+            .did_store
+            .clone()
+            .unwrap_or_else(|| Arc::new(FileDidStore::default()));
+        let mut client = LiveWebullClient::from_parts(self.region_code, did_store, http)?;
+        client.apply_config(&self.config)?;
+        client.middleware = self.middleware.clone();
+
+        if let Some(user_agent) = &self.user_agent {
+            let value = header_value(user_agent)?;
+            client.headers.insert("User-Agent", value.clone());
+            client.headers.insert("osv", value);
+        }
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                WebullError::InvalidParameter(format!("Invalid header name {}: {}", name, e))
+            })?;
+            client.headers.insert(name, header_value(value)?);
+        }
+        Ok(client)
+    }
+
+    fn http_builder(&self) -> Result<reqwest::ClientBuilder> {
+        let mut http = reqwest::Client::builder();
+        if let Some(url) = &self.proxy {
+            let mut proxy = reqwest::Proxy::all(url).map_err(|e| {
+                WebullError::InvalidParameter(format!("Invalid proxy URL {}: {}", url, e))
+            })?;
+            if let Some((username, password)) = &self.proxy_auth {
+                proxy = proxy.basic_auth(username, password);
+            }
+            http = http.proxy(proxy);
+        }
+        for certificate in &self.root_certificates {
+            http = http.add_root_certificate(certificate.clone());
+        }
+        Ok(http)
+    }
+
+    /// Build the client
+    pub fn build(self) -> Result<WebullClient> {
+        let paper = self.paper;
+        let client = self.build_live()?;
+        Ok(if paper {
+            WebullClient::Paper(PaperWebullClient {
+                base_client: client,
+                paper_account_id: None,
+            })
+        } else {
+            WebullClient::Live(client)
+        })
+    }
+}
+
+fn header_value(value: &str) -> Result<HeaderValue> {
+    HeaderValue::from_str(value).map_err(|e| {
+        WebullError::InvalidParameter(format!("Invalid header value {}: {}", value, e))
+    })
+}
@@ -0,0 +1,243 @@
+// Client-wide cap on in-flight requests with priority lanes
+
+use crate::error::{Result, WebullError};
+use crate::rate_limit::EndpointClass;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::oneshot;
+
+/// Queue lane for a request; higher lanes are always served first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RequestPriority {
+    /// Quotes, bars, option chains and everything else
+    Data,
+    /// Login, account and position queries
+    Account,
+    /// Order placement, modification and cancellation
+    Orders,
+}
+
+impl RequestPriority {
+    const LANES: usize = 3;
+
+    fn lane(self) -> usize {
+        match self {
+            RequestPriority::Orders => 0,
+            RequestPriority::Account => 1,
+            RequestPriority::Data => 2,
+        }
+    }
+}
+
+impl From<EndpointClass> for RequestPriority {
+    fn from(class: EndpointClass) -> Self {
+        match class {
+            EndpointClass::Trading => RequestPriority::Orders,
+            EndpointClass::Account => RequestPriority::Account,
+            EndpointClass::MarketData | EndpointClass::Options | EndpointClass::Other => {
+                RequestPriority::Data
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    in_flight: usize,
+    lanes: [VecDeque<oneshot::Sender<ConcurrencyPermit>>; RequestPriority::LANES],
+}
+
+#[derive(Debug)]
+struct Inner {
+    max_in_flight: usize,
+    state: Mutex<State>,
+}
+
+/// Limits how many requests are in flight at once
+///
+/// Requests over the limit wait in one FIFO queue per `RequestPriority`, and
+/// a freed slot goes to the oldest waiter in the highest non-empty lane, so
+/// an order is never stuck behind a burst of quote requests. Clones share
+/// the same slots.
+#[derive(Debug, Clone)]
+pub struct ConcurrencyLimiter {
+    inner: Arc<Inner>,
+}
+
+impl ConcurrencyLimiter {
+    /// Allow at most `max_in_flight` concurrent requests (at least one)
+    pub fn new(max_in_flight: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                max_in_flight: max_in_flight.max(1),
+                state: Mutex::new(State {
+                    in_flight: 0,
+                    lanes: Default::default(),
+                }),
+            }),
+        }
+    }
+
+    pub fn max_in_flight(&self) -> usize {
+        self.inner.max_in_flight
+    }
+
+    /// Requests currently holding a slot
+    pub fn in_flight(&self) -> usize {
+        self.inner.state.lock().in_flight
+    }
+
+    /// Requests waiting for a slot
+    pub fn queued(&self) -> usize {
+        let state = self.inner.state.lock();
+        state
+            .lanes
+            .iter()
+            .flatten()
+            .filter(|waiter| !waiter.is_closed())
+            .count()
+    }
+
+    /// Take a slot without waiting, if one is free and nobody is queued
+    pub fn try_acquire(&self) -> Option<ConcurrencyPermit> {
+        let mut state = self.inner.state.lock();
+        self.take_free_slot(&mut state)
+    }
+
+    /// Wait for a slot in `priority`'s lane
+    ///
+    /// The slot is held until the returned permit is dropped. Dropping the
+    /// future while it waits gives up its place in the queue.
+    pub async fn acquire(&self, priority: RequestPriority) -> Result<ConcurrencyPermit> {
+        let receiver = {
+            let mut state = self.inner.state.lock();
+            if let Some(permit) = self.take_free_slot(&mut state) {
+                return Ok(permit);
+            }
+            let (sender, receiver) = oneshot::channel();
+            state.lanes[priority.lane()].push_back(sender);
+            receiver
+        };
+        // Waiters are only removed from the queue by handing them a permit,
+        // so this only fails if that invariant is broken
+        receiver.await.map_err(|_| {
+            WebullError::Unknown("Concurrency limiter dropped a queued request".to_string())
+        })
+    }
+
+    fn take_free_slot(&self, state: &mut State) -> Option<ConcurrencyPermit> {
+        let queue_empty = state
+            .lanes
+            .iter()
+            .all(|lane| lane.iter().all(|waiter| waiter.is_closed()));
+        if state.in_flight < self.inner.max_in_flight && queue_empty {
+            state.in_flight += 1;
+            Some(ConcurrencyPermit {
+                inner: Some(self.inner.clone()),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// A held request slot, released on drop
+#[derive(Debug)]
+pub struct ConcurrencyPermit {
+    inner: Option<Arc<Inner>>,
+}
+
+impl Drop for ConcurrencyPermit {
+    fn drop(&mut self) {
+        let inner = match self.inner.take() {
+            Some(inner) => inner,
+            None => return,
+        };
+        loop {
+            let waiter = {
+                let mut state = inner.state.lock();
+                match state.lanes.iter_mut().find_map(|lane| lane.pop_front()) {
+                    Some(waiter) => waiter,
+                    None => {
+                        state.in_flight -= 1;
+                        return;
+                    }
+                }
+            };
+            // Hand the slot straight to the next waiter; if it gave up,
+            // defuse the returned permit and try the one after
+            let permit = ConcurrencyPermit {
+                inner: Some(inner.clone()),
+            };
+            match waiter.send(permit) {
+                Ok(()) => return,
+                Err(mut permit) => {
+                    permit.inner = None;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_orders_jump_the_data_queue() {
+        let limiter = ConcurrencyLimiter::new(1);
+        let held = limiter.acquire(RequestPriority::Data).await.unwrap();
+        assert_eq!(limiter.in_flight(), 1);
+        assert!(limiter.try_acquire().is_none());
+
+        let (order_tx, mut order) = tokio::sync::mpsc::unbounded_channel();
+        let mut tasks = Vec::new();
+        for (name, priority) in [
+            ("data-1", RequestPriority::Data),
+            ("data-2", RequestPriority::Data),
+            ("order", RequestPriority::Orders),
+        ] {
+            let limiter = limiter.clone();
+            let order_tx = order_tx.clone();
+            tasks.push(tokio::spawn(async move {
+                let _permit = limiter.acquire(priority).await.unwrap();
+                order_tx.send(name).unwrap();
+            }));
+            // Queue them in a known order
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(limiter.queued(), 3);
+
+        drop(held);
+        for task in tasks {
+            task.await.unwrap();
+        }
+        let mut served = Vec::new();
+        while let Ok(name) = order.try_recv() {
+            served.push(name);
+        }
+        assert_eq!(served, vec!["order", "data-1", "data-2"]);
+        assert_eq!(limiter.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_waiter_does_not_leak_a_slot() {
+        let limiter = ConcurrencyLimiter::new(1);
+        let held = limiter.acquire(RequestPriority::Data).await.unwrap();
+
+        let waiting = tokio::time::timeout(
+            Duration::from_millis(10),
+            limiter.acquire(RequestPriority::Data),
+        )
+        .await;
+        assert!(waiting.is_err());
+        assert_eq!(limiter.queued(), 0);
+
+        drop(held);
+        assert_eq!(limiter.in_flight(), 0);
+        assert!(limiter.try_acquire().is_some());
+        assert_eq!(limiter.in_flight(), 0);
+    }
+}
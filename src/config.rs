@@ -0,0 +1,141 @@
+// Client configuration
+
+use crate::{
+    circuit::BreakerSettings,
+    endpoints::{Endpoints, Environment},
+    rate_limit::EndpointClass,
+    retry::RetryPolicy,
+};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Tunable client behaviour
+///
+/// ```no_run
+/// use webull_unofficial::{ClientConfig, LiveWebullClient};
+///
+/// let config = ClientConfig::new().rate_limit(5.0);
+/// let client = LiveWebullClient::with_config(Some(6), config).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// Requests per second allowed per endpoint class (`None` disables limiting)
+    pub rate_limit: Option<f64>,
+    /// Burst size for the rate limiter (defaults to one second of requests)
+    pub rate_limit_burst: Option<f64>,
+    /// Maximum concurrent requests (`None` leaves them unbounded)
+    pub max_in_flight: Option<usize>,
+    /// Request timeout in seconds
+    pub timeout: u64,
+    /// Request timeouts in seconds per endpoint class, overriding `timeout`
+    pub class_timeouts: HashMap<EndpointClass, u64>,
+    /// Retry policy for transient failures
+    pub retry: RetryPolicy,
+    /// Circuit breakers per endpoint class (classes not listed are unguarded)
+    pub circuit_breakers: HashMap<EndpointClass, BreakerSettings>,
+    /// API the client talks to (production unless overridden)
+    pub environment: Environment,
+    /// Base URLs to use instead of the environment's
+    pub endpoints: Option<Endpoints>,
+    /// Use the region's own hosts and headers (see `Region::regional_profile`)
+    pub regional_hosts: bool,
+}
+
+impl ClientConfig {
+    /// Default configuration: no rate limit, a 15 second timeout and the
+    /// default retry policy
+    pub fn new() -> Self {
+        Self {
+            rate_limit: None,
+            rate_limit_burst: None,
+            max_in_flight: None,
+            timeout: 15,
+            class_timeouts: HashMap::new(),
+            retry: RetryPolicy::new(),
+            circuit_breakers: HashMap::new(),
+            environment: Environment::Production,
+            endpoints: None,
+            regional_hosts: false,
+        }
+    }
+
+    /// Limit requests per second for each endpoint class
+    pub fn rate_limit(mut self, requests_per_sec: f64) -> Self {
+        self.rate_limit = Some(requests_per_sec);
+        self
+    }
+
+    /// Set the rate limiter burst size
+    pub fn rate_limit_burst(mut self, burst: f64) -> Self {
+        self.rate_limit_burst = Some(burst);
+        self
+    }
+
+    /// Allow at most `max_in_flight` requests at once, queueing the rest
+    /// with orders ahead of account and market data requests
+    pub fn max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = Some(max_in_flight);
+        self
+    }
+
+    /// Set the request timeout in seconds
+    pub fn timeout(mut self, seconds: u64) -> Self {
+        self.timeout = seconds;
+        self
+    }
+
+    /// Set the request timeout in seconds for one endpoint class, e.g. a
+    /// short one for market data and a longer one for `Account` (login)
+    pub fn class_timeout(mut self, class: EndpointClass, seconds: u64) -> Self {
+        self.class_timeouts.insert(class, seconds);
+        self
+    }
+
+    /// Set the retry policy (use `RetryPolicy::none()` to disable retries)
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Fail fast on `class` after `failure_threshold` consecutive transient
+    /// failures, probing again once `cooldown` has passed
+    pub fn circuit_breaker(
+        mut self,
+        class: EndpointClass,
+        failure_threshold: u32,
+        cooldown: Duration,
+    ) -> Self {
+        self.circuit_breakers
+            .insert(class, BreakerSettings::new(failure_threshold, cooldown));
+        self
+    }
+
+    /// Point every endpoint at `environment`, e.g. a local sandbox
+    pub fn environment(mut self, environment: Environment) -> Self {
+        self.environment = environment;
+        self
+    }
+
+    /// Use these base URLs, e.g. to go through an API proxy; takes
+    /// precedence over `environment`
+    pub fn endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.endpoints = Some(endpoints);
+        self
+    }
+
+    /// Talk to the region's own hosts with its `lzone` and language headers
+    /// instead of the US ones
+    ///
+    /// The Hong Kong and Japan hosts are unverified; see
+    /// `Region::regional_profile`.
+    pub fn regional_hosts(mut self, enabled: bool) -> Self {
+        self.regional_hosts = enabled;
+        self
+    }
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,174 @@
+// Decimal views of prices and quantities, for arithmetic without binary
+// float rounding
+
+use crate::models::{Order, PlaceOrderRequest, PlaceOrderRequestBuilder, Position, Quote};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// The decimal a float was parsed from
+///
+/// Uses the shortest representation that round-trips, so a price that
+/// arrived as "187.35" comes back as exactly 187.35. NaN, infinities and
+/// values beyond `Decimal`'s range become zero.
+pub fn to_decimal(value: f64) -> Decimal {
+    Decimal::from_str(&value.to_string())
+        .ok()
+        .or_else(|| Decimal::from_f64(value))
+        .unwrap_or(Decimal::ZERO)
+}
+
+fn to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(0.0)
+}
+
+impl Quote {
+    pub fn close_decimal(&self) -> Decimal {
+        to_decimal(self.close)
+    }
+
+    pub fn open_decimal(&self) -> Option<Decimal> {
+        self.open.map(to_decimal)
+    }
+
+    pub fn high_decimal(&self) -> Option<Decimal> {
+        self.high.map(to_decimal)
+    }
+
+    pub fn low_decimal(&self) -> Option<Decimal> {
+        self.low.map(to_decimal)
+    }
+
+    pub fn pre_close_decimal(&self) -> Option<Decimal> {
+        self.pre_close.map(to_decimal)
+    }
+
+    pub fn change_decimal(&self) -> Option<Decimal> {
+        self.change.map(to_decimal)
+    }
+
+    pub fn bid_decimal(&self) -> Option<Decimal> {
+        self.bid.map(to_decimal)
+    }
+
+    pub fn ask_decimal(&self) -> Option<Decimal> {
+        self.ask.map(to_decimal)
+    }
+
+    /// `latest_price` as a decimal
+    pub fn latest_price_decimal(&self) -> Decimal {
+        to_decimal(self.latest_price())
+    }
+}
+
+impl Order {
+    pub fn quantity_decimal(&self) -> Decimal {
+        to_decimal(self.quantity)
+    }
+
+    pub fn filled_quantity_decimal(&self) -> Decimal {
+        to_decimal(self.filled_quantity)
+    }
+
+    pub fn avg_fill_price_decimal(&self) -> Option<Decimal> {
+        self.avg_fill_price.map(to_decimal)
+    }
+
+    pub fn limit_price_decimal(&self) -> Option<Decimal> {
+        self.limit_price.map(to_decimal)
+    }
+
+    pub fn stop_price_decimal(&self) -> Option<Decimal> {
+        self.stop_price.map(to_decimal)
+    }
+}
+
+impl Position {
+    pub fn quantity_decimal(&self) -> Decimal {
+        to_decimal(self.quantity)
+    }
+
+    pub fn avg_cost_decimal(&self) -> Decimal {
+        to_decimal(self.avg_cost)
+    }
+
+    pub fn cost_decimal(&self) -> Decimal {
+        to_decimal(self.cost)
+    }
+
+    pub fn market_value_decimal(&self) -> Decimal {
+        to_decimal(self.market_value)
+    }
+
+    pub fn last_price_decimal(&self) -> Decimal {
+        to_decimal(self.last_price)
+    }
+
+    pub fn unrealized_profit_loss_decimal(&self) -> Option<Decimal> {
+        self.unrealized_profit_loss.map(to_decimal)
+    }
+}
+
+impl PlaceOrderRequest {
+    pub fn quantity_decimal(&self) -> Decimal {
+        to_decimal(self.quantity)
+    }
+
+    pub fn limit_price_decimal(&self) -> Option<Decimal> {
+        self.limit_price.map(to_decimal)
+    }
+
+    pub fn stop_price_decimal(&self) -> Option<Decimal> {
+        self.stop_price.map(to_decimal)
+    }
+}
+
+impl PlaceOrderRequestBuilder {
+    pub fn quantity_decimal(self, quantity: Decimal) -> Self {
+        self.quantity(to_f64(quantity))
+    }
+
+    /// Set the limit price; decimals with up to 15 significant digits are
+    /// sent exactly as written
+    pub fn limit_price_decimal(self, price: Decimal) -> Self {
+        self.limit_price(to_f64(price))
+    }
+
+    pub fn stop_price_decimal(self, price: Decimal) -> Self {
+        self.stop_price(to_f64(price))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::OrderType;
+
+    #[test]
+    fn test_decimal_views_keep_quoted_digits() {
+        let quote: Quote = serde_json::from_value(serde_json::json!({
+            "close": "0.1", "change": "0.2", "changeRatio": "0.01", "preClose": "187.35",
+            "open": "1", "high": "1", "low": "1", "volume": "100", "bid": "187.34"
+        }))
+        .unwrap();
+        assert_ne!(quote.close + quote.change.unwrap(), 0.3);
+        assert_eq!(
+            quote.close_decimal() + quote.change_decimal().unwrap(),
+            Decimal::from_str("0.3").unwrap()
+        );
+        assert_eq!(quote.pre_close_decimal().unwrap().to_string(), "187.35");
+        assert_eq!(quote.bid_decimal().unwrap().to_string(), "187.34");
+        assert_eq!(quote.ask_decimal(), None);
+        assert_eq!(to_decimal(f64::NAN), Decimal::ZERO);
+
+        let order = PlaceOrderRequest::builder(OrderType::Limit)
+            .ticker_id(913256135)
+            .buy()
+            .quantity_decimal(Decimal::from(3))
+            .limit_price_decimal(Decimal::from_str("187.35").unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(order.limit_price_decimal().unwrap().to_string(), "187.35");
+        assert_eq!(order.quantity_decimal(), Decimal::from(3));
+    }
+}
@@ -0,0 +1,275 @@
+// Changed-fields-only quote and bar updates against a local checkpoint
+
+use crate::error::Result;
+use crate::models::{Bar, BarInterval, News, Quote};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+
+/// Quote fields that changed since a checkpoint
+///
+/// Keys are the quote's JSON field names (`close`, `pPrice`, ...). A field
+/// that disappeared is reported as `null`. When the caller's checkpoint
+/// doesn't match the client's, every field is sent and `full` is set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteDelta {
+    pub ticker_id: String,
+    /// Pass back as `since` on the next call (Unix milliseconds)
+    pub checkpoint: i64,
+    pub full: bool,
+    pub changed: BTreeMap<String, Value>,
+}
+
+impl QuoteDelta {
+    /// Whether nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty()
+    }
+
+    /// Merge into a locally held quote object, replacing it when `full`
+    pub fn apply(&self, quote: &mut Value) {
+        if self.full || !quote.is_object() {
+            *quote = Value::Object(Map::new());
+        }
+        if let Some(fields) = quote.as_object_mut() {
+            for (key, value) in &self.changed {
+                if value.is_null() {
+                    fields.remove(key);
+                } else {
+                    fields.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Headlines published since a checkpoint
+///
+/// News IDs only grow, so the checkpoint is the newest ID the caller has
+/// and no state is kept on the client. When there is no checkpoint, or it
+/// is too far back to page to, `full` is set and `news` is the latest page
+/// or pages rather than everything since.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewsDelta {
+    pub ticker_id: String,
+    /// Pass back as `since` on the next call; `None` until a headline is seen
+    pub checkpoint: Option<i64>,
+    pub full: bool,
+    /// Newest first
+    pub news: Vec<News>,
+}
+
+impl NewsDelta {
+    pub(crate) fn new(ticker_id: &str, since: Option<i64>, news: Vec<News>, gap: bool) -> Self {
+        let newest = news.iter().map(|n| n.id).max();
+        Self {
+            ticker_id: ticker_id.to_string(),
+            checkpoint: newest.max(since),
+            full: since.is_none() || gap,
+            news,
+        }
+    }
+
+    /// Whether nothing new was published
+    pub fn is_empty(&self) -> bool {
+        self.news.is_empty()
+    }
+}
+
+#[derive(Debug)]
+struct Checkpoint {
+    at: i64,
+    fields: Map<String, Value>,
+}
+
+/// Last quote sent per ticker, for computing deltas
+///
+/// Only the most recent checkpoint is kept per ticker, so each ticker
+/// should have one delta consumer per client; a second consumer gets a
+/// full quote whenever the other has moved the checkpoint on.
+#[derive(Debug, Default)]
+pub struct DeltaState {
+    quotes: Mutex<HashMap<String, Checkpoint>>,
+}
+
+impl DeltaState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Diff a fresh quote against the checkpoint `since` and record it as
+    /// the new checkpoint at `now` (Unix milliseconds)
+    pub fn quote_delta(
+        &self,
+        ticker_id: &str,
+        since: Option<i64>,
+        quote: &Quote,
+        now: i64,
+    ) -> Result<QuoteDelta> {
+        let fields = match serde_json::to_value(quote)? {
+            Value::Object(fields) => fields,
+            _ => Map::new(),
+        };
+
+        let mut quotes = self.quotes.lock();
+        let previous = quotes
+            .get(ticker_id)
+            .filter(|previous| Some(previous.at) == since);
+        let full = previous.is_none();
+        let mut changed = BTreeMap::new();
+        match previous {
+            Some(previous) => {
+                for (key, value) in &fields {
+                    if previous.fields.get(key) != Some(value) {
+                        changed.insert(key.clone(), value.clone());
+                    }
+                }
+                for key in previous.fields.keys() {
+                    if !fields.contains_key(key) {
+                        changed.insert(key.clone(), Value::Null);
+                    }
+                }
+            }
+            None => {
+                changed.extend(
+                    fields
+                        .iter()
+                        .filter(|(_, value)| !value.is_null())
+                        .map(|(key, value)| (key.clone(), value.clone())),
+                );
+            }
+        }
+
+        // Keep checkpoints distinct even when polled within a millisecond
+        let at = quotes
+            .get(ticker_id)
+            .map_or(now, |previous| now.max(previous.at + 1));
+        quotes.insert(ticker_id.to_string(), Checkpoint { at, fields });
+        Ok(QuoteDelta {
+            ticker_id: ticker_id.to_string(),
+            checkpoint: at,
+            full,
+            changed,
+        })
+    }
+
+    /// Forget all checkpoints
+    pub fn clear(&self) {
+        self.quotes.lock().clear();
+    }
+}
+
+/// Approximate bar length in seconds
+pub(crate) fn interval_seconds(interval: BarInterval) -> i64 {
+    match interval {
+        BarInterval::M1 => 60,
+        BarInterval::M3 => 180,
+        BarInterval::M5 => 300,
+        BarInterval::M15 => 900,
+        BarInterval::M30 => 1_800,
+        BarInterval::H1 => 3_600,
+        BarInterval::H2 => 7_200,
+        BarInterval::H4 => 14_400,
+        BarInterval::D1 => 86_400,
+        BarInterval::D5 => 432_000,
+        BarInterval::W1 => 604_800,
+        BarInterval::Month1 => 2_592_000,
+    }
+}
+
+/// Bars at or after `since` (Unix seconds), oldest first
+///
+/// The bar starting at `since` is included because it may still have been
+/// forming when it was last fetched.
+pub fn bars_since(bars: Vec<Bar>, since: i64) -> Vec<Bar> {
+    let mut bars: Vec<Bar> = bars
+        .into_iter()
+        .filter(|bar| bar.timestamp >= since)
+        .collect();
+    bars.sort_by_key(|bar| bar.timestamp);
+    bars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn quote(close: f64, p_price: Option<f64>) -> Quote {
+        serde_json::from_value(json!({
+            "tickerId": 913256135, "close": close, "change": 1.0,
+            "changeRatio": 0.01, "preClose": 100.0, "open": 100.0,
+            "high": 102.0, "low": 99.0, "volume": 1000.0, "pPrice": p_price
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_quote_delta_against_checkpoint() {
+        let state = DeltaState::new();
+        let first = state
+            .quote_delta("913256135", None, &quote(101.0, None), 1_000)
+            .unwrap();
+        assert!(first.full);
+        assert_eq!(first.changed["close"], json!(101.0));
+        assert!(!first.changed.contains_key("pPrice"));
+
+        let mut local = Value::Null;
+        first.apply(&mut local);
+
+        let second = state
+            .quote_delta(
+                "913256135",
+                Some(first.checkpoint),
+                &quote(101.5, Some(101.7)),
+                1_000,
+            )
+            .unwrap();
+        assert!(!second.full);
+        assert_eq!(second.checkpoint, 1_001);
+        assert_eq!(second.changed.len(), 2);
+        assert_eq!(second.changed["pPrice"], json!(101.7));
+        second.apply(&mut local);
+        assert_eq!(local["close"], json!(101.5));
+
+        let unchanged = state
+            .quote_delta(
+                "913256135",
+                Some(second.checkpoint),
+                &quote(101.5, Some(101.7)),
+                2_000,
+            )
+            .unwrap();
+        assert!(unchanged.is_empty());
+
+        // A stale checkpoint gets everything again
+        let stale = state
+            .quote_delta(
+                "913256135",
+                Some(first.checkpoint),
+                &quote(101.5, Some(101.7)),
+                3_000,
+            )
+            .unwrap();
+        assert!(stale.full);
+    }
+
+    #[test]
+    fn test_bars_since_keeps_forming_bar() {
+        let bar = |timestamp| Bar {
+            timestamp,
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            close: 1.0,
+            volume: 1.0,
+            vwap: 1.0,
+        };
+        let bars = bars_since(vec![bar(180), bar(60), bar(120)], 120);
+        let timestamps: Vec<i64> = bars.iter().map(|b| b.timestamp).collect();
+        assert_eq!(timestamps, vec![120, 180]);
+    }
+}
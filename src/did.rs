@@ -0,0 +1,205 @@
+// Where the device ID (DID) sent with every request is kept
+
+use crate::error::{Result, WebullError};
+use parking_lot::Mutex;
+use std::fmt::Debug;
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Persistence for the device ID
+///
+/// Webull ties logins and MFA trust to the device ID, so it should survive
+/// restarts. Implement this to keep it somewhere other than the built-in
+/// stores, e.g. a secrets manager.
+pub trait DidStore: Debug + Send + Sync {
+    /// The stored device ID, if there is one
+    fn load(&self) -> Result<Option<String>>;
+
+    fn save(&self, did: &str) -> Result<()>;
+}
+
+/// A new random device ID
+pub fn generate_did() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+/// Load the stored device ID, generating and saving one if there is none
+pub fn load_or_create(store: &dyn DidStore) -> Result<String> {
+    if let Some(did) = store.load()? {
+        return Ok(did);
+    }
+    let did = generate_did();
+    store.save(&did)?;
+    Ok(did)
+}
+
+/// Device ID in a file, bincode-encoded (`did.bin` in the working
+/// directory by default)
+#[derive(Debug, Clone)]
+pub struct FileDidStore {
+    path: PathBuf,
+}
+
+impl Default for FileDidStore {
+    fn default() -> Self {
+        Self::at(default_path())
+    }
+}
+
+#[cfg(not(test))]
+fn default_path() -> PathBuf {
+    PathBuf::from("did.bin")
+}
+
+// Unit tests build clients through the default constructors; keep their
+// device ID out of the checkout
+#[cfg(test)]
+fn default_path() -> PathBuf {
+    std::env::temp_dir()
+        .join("webull-unofficial-tests")
+        .join("did.bin")
+}
+
+impl FileDidStore {
+    /// `did.bin` in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self::at(dir.into().join("did.bin"))
+    }
+
+    /// The file at `path`
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+}
+
+impl DidStore for FileDidStore {
+    fn load(&self) -> Result<Option<String>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read(&self.path)?;
+        match bincode::deserialize::<String>(&contents) {
+            Ok(did) => Ok(Some(did)),
+            // Older files hold the raw string
+            Err(_) => String::from_utf8(contents)
+                .map(Some)
+                .map_err(|e| WebullError::DeviceIdError(e.to_string())),
+        }
+    }
+
+    fn save(&self, did: &str) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let serialized =
+            bincode::serialize(did).map_err(|e| WebullError::SerializationError(e.to_string()))?;
+        fs::write(&self.path, serialized)?;
+        Ok(())
+    }
+}
+
+/// Device ID held in memory only, for serverless functions and containers
+/// that get it from their environment
+#[derive(Debug, Default)]
+pub struct MemoryDidStore {
+    did: Mutex<Option<String>>,
+}
+
+impl MemoryDidStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A store already holding `did`
+    pub fn with_did(did: impl Into<String>) -> Self {
+        Self {
+            did: Mutex::new(Some(did.into())),
+        }
+    }
+}
+
+impl DidStore for MemoryDidStore {
+    fn load(&self) -> Result<Option<String>> {
+        Ok(self.did.lock().clone())
+    }
+
+    fn save(&self, did: &str) -> Result<()> {
+        *self.did.lock() = Some(did.to_string());
+        Ok(())
+    }
+}
+
+/// Device ID in the OS keyring (Keychain, Credential Manager or Secret
+/// Service)
+#[cfg(feature = "keyring")]
+#[derive(Debug, Clone)]
+pub struct KeyringDidStore {
+    service: String,
+    user: String,
+}
+
+#[cfg(feature = "keyring")]
+impl KeyringDidStore {
+    /// Entry `user` under `service`, e.g. the app name and the login
+    pub fn new(service: impl Into<String>, user: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+            user: user.into(),
+        }
+    }
+
+    fn entry(&self) -> Result<keyring::Entry> {
+        keyring::Entry::new(&self.service, &self.user)
+            .map_err(|e| WebullError::DeviceIdError(e.to_string()))
+    }
+}
+
+#[cfg(feature = "keyring")]
+impl DidStore for KeyringDidStore {
+    fn load(&self) -> Result<Option<String>> {
+        match self.entry()?.get_password() {
+            Ok(did) => Ok(Some(did)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(WebullError::DeviceIdError(e.to_string())),
+        }
+    }
+
+    fn save(&self, did: &str) -> Result<()> {
+        self.entry()?
+            .set_password(did)
+            .map_err(|e| WebullError::DeviceIdError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stores_create_once_and_reload() {
+        let memory = MemoryDidStore::new();
+        let did = load_or_create(&memory).unwrap();
+        assert_eq!(did.len(), 32);
+        assert_eq!(load_or_create(&memory).unwrap(), did);
+        assert_eq!(
+            load_or_create(&MemoryDidStore::with_did("abc")).unwrap(),
+            "abc"
+        );
+
+        let dir = std::env::temp_dir().join(format!("webull-did-{}", std::process::id()));
+        let file = FileDidStore::new(&dir);
+        assert!(file.load().unwrap().is_none());
+        let did = load_or_create(&file).unwrap();
+        assert_eq!(FileDidStore::new(&dir).load().unwrap(), Some(did));
+
+        // Raw strings written by hand are still read
+        fs::write(file.path(), "handwritten").unwrap();
+        assert_eq!(file.load().unwrap().as_deref(), Some("handwritten"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
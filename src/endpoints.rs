@@ -1,3 +1,40 @@
+/// Which Webull API the client talks to
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Environment {
+    /// Webull's production hosts
+    #[default]
+    Production,
+    /// A self-hosted sandbox serving every API under one base URL
+    ///
+    /// Each production base URL keeps its path and swaps its scheme and
+    /// host for this one, so `https://quotes-gw.webullfintech.com/api`
+    /// becomes `http://localhost:8080/api`. The sandbox routes on path
+    /// alone; MQTT streaming connects to `/mqtt` on the same host.
+    #[cfg(feature = "sandbox")]
+    Custom(String),
+}
+
+impl Environment {
+    /// Streaming (MQTT over WebSocket) URL for this environment
+    pub fn stream_url(&self) -> String {
+        match self {
+            Environment::Production => "wss://wspush.webullbroker.com/mqtt".to_string(),
+            #[cfg(feature = "sandbox")]
+            Environment::Custom(base_url) => {
+                let base_url = base_url.trim_end_matches('/');
+                let ws = match base_url.strip_prefix("https://") {
+                    Some(rest) => format!("wss://{}", rest),
+                    None => format!(
+                        "ws://{}",
+                        base_url.strip_prefix("http://").unwrap_or(base_url)
+                    ),
+                };
+                format!("{}/mqtt", ws)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Endpoints {
     pub base_info_url: String,
@@ -47,19 +84,524 @@ impl Endpoints {
         Self::default()
     }
 
+    /// Endpoints for `environment`
+    pub fn for_environment(environment: &Environment) -> Self {
+        match environment {
+            Environment::Production => Self::default(),
+            #[cfg(feature = "sandbox")]
+            Environment::Custom(base_url) => Self::default().rebased(base_url),
+        }
+    }
+
+    /// Start from the production URLs and override some of them
+    pub fn builder() -> EndpointsBuilder {
+        EndpointsBuilder::new()
+    }
+
+    fn base_urls_mut(&mut self) -> [&mut String; 16] {
+        [
+            &mut self.base_info_url,
+            &mut self.base_options_url,
+            &mut self.base_options_gw_url,
+            &mut self.base_paper_url,
+            &mut self.base_quote_url,
+            &mut self.base_securities_url,
+            &mut self.base_trade_url,
+            &mut self.base_user_url,
+            &mut self.base_userbroker_url,
+            &mut self.base_ustrade_url,
+            &mut self.base_paperfintech_url,
+            &mut self.base_fintech_gw_url,
+            &mut self.base_userfintech_url,
+            &mut self.base_new_trade_url,
+            &mut self.base_ustradebroker_url,
+            &mut self.base_securitiesfintech_url,
+        ]
+    }
+
+    /// Move every base URL from Webull's US domains onto `domain`, keeping
+    /// the subdomain and path (`quotes-gw.webullfintech.com` becomes
+    /// `quotes-gw.<domain>`)
+    pub(crate) fn with_domain(mut self, domain: &str) -> Self {
+        for url in self.base_urls_mut() {
+            let mut parsed = match url::Url::parse(url) {
+                Ok(parsed) => parsed,
+                Err(_) => continue,
+            };
+            let host = match parsed.host_str().and_then(|host| host.split_once('.')) {
+                Some((subdomain, _)) => format!("{}.{}", subdomain, domain),
+                None => continue,
+            };
+            if parsed.set_host(Some(&host)).is_ok() {
+                *url = parsed.as_str().trim_end_matches('/').to_string();
+            }
+        }
+        self
+    }
+
+    /// Move every base URL onto `base_url`, keeping its path
+    #[cfg(feature = "sandbox")]
+    fn rebased(mut self, base_url: &str) -> Self {
+        let base_url = base_url.trim_end_matches('/');
+        for url in self.base_urls_mut() {
+            *url = format!("{}{}", base_url, url_path(url));
+        }
+        self
+    }
+
+    /// Build the URL for an endpoint
+    pub fn url(&self, endpoint: &Endpoint) -> String {
+        use Endpoint::*;
+
+        match *endpoint {
+            Account { account_id } => UrlBuilder::new(&self.base_trade_url)
+                .path("/v3/home")
+                .segment(account_id),
+            AccountId => {
+                UrlBuilder::new(&self.base_trade_url).path("/account/getSecAccountList/v5")
+            }
+            AccountActivities { account_id } => UrlBuilder::new(&self.base_ustrade_url)
+                .path("/trade/v2/funds")
+                .segment(account_id)
+                .path("/activities"),
+            ActiveGainersLosers {
+                direction,
+                region_code,
+                rank_type,
+                num,
+            } => {
+                let ranking = match direction {
+                    "gainer" => "topGainers",
+                    "loser" => "dropGainers",
+                    _ => "topActive",
+                };
+                UrlBuilder::new(&self.base_fintech_gw_url)
+                    .path("/wlas/ranking")
+                    .segment(ranking)
+                    .query("regionId", region_code)
+                    .query("rankType", rank_type)
+                    .query("pageIndex", 1)
+                    .query("pageSize", num)
+            }
+            TopOptions {
+                rank_type,
+                region_code,
+                num,
+            } => UrlBuilder::new(&self.base_fintech_gw_url)
+                .path("/wlas/option/rank/list")
+                .query("regionId", region_code)
+                .query("rankType", rank_type)
+                .query("pageIndex", 1)
+                .query("pageSize", num),
+            AddAlert | RemoveAlert => {
+                UrlBuilder::new(&self.base_userbroker_url).path("/user/warning/v2/manage/overlap")
+            }
+            ListAlerts => {
+                UrlBuilder::new(&self.base_userbroker_url).path("/user/warning/v2/query/tickers")
+            }
+            Analysis { ticker_id } => UrlBuilder::new(&self.base_securities_url)
+                .path("/securities/ticker/v5/analysis")
+                .segment(ticker_id),
+            AnalysisShortInterest { ticker_id } => UrlBuilder::new(&self.base_securities_url)
+                .path("/securities/stock")
+                .segment(ticker_id)
+                .path("/shortInterest"),
+            AnalysisInstitutionalHolding { ticker_id } => {
+                UrlBuilder::new(&self.base_securities_url)
+                    .path("/securities/stock/v5")
+                    .segment(ticker_id)
+                    .path("/institutionalHolding")
+            }
+            AnalysisEtfHolding {
+                ticker_id,
+                has_num,
+                page_size,
+            } => UrlBuilder::new(&self.base_securities_url)
+                .path("/securities/stock/v5")
+                .segment(ticker_id)
+                .path("/belongEtf")
+                .query("hasNum", has_num)
+                .query("pageSize", page_size),
+            AnalysisCapitalFlow {
+                ticker_id,
+                show_hist,
+            } => UrlBuilder::new(&self.base_securities_url)
+                .path("/wlas/capitalflow/ticker")
+                .query("tickerId", ticker_id)
+                .query("showHis", show_hist),
+            Bars {
+                ticker_id,
+                interval,
+                count,
+                timestamp,
+            } => UrlBuilder::new(&self.base_fintech_gw_url)
+                .path("/quote/charts/query")
+                .query("tickerIds", ticker_id)
+                .query("type", interval)
+                .query("count", count)
+                .query_opt("timestamp", timestamp),
+            TradeTicks { ticker_id, count } => UrlBuilder::new(&self.base_fintech_gw_url)
+                .path("/stock/tradeTick/getTradeTick")
+                .query("tickerId", ticker_id)
+                .query("count", count),
+            IpoCalendar { region_code } => UrlBuilder::new(&self.base_fintech_gw_url)
+                .path("/bgw/ipo/listIpo")
+                .query("regionId", region_code),
+            EtfProfile { ticker_id } => UrlBuilder::new(&self.base_fintech_gw_url)
+                .path("/information/etf/profile")
+                .query("tickerId", ticker_id),
+            EtfHoldings { ticker_id } => UrlBuilder::new(&self.base_fintech_gw_url)
+                .path("/information/etf/holdings")
+                .query("tickerId", ticker_id),
+            TickerMinuteVolume { ticker_id, days } => UrlBuilder::new(&self.base_fintech_gw_url)
+                .path("/quote/ticker/minuteVolume")
+                .query("tickerId", ticker_id)
+                .query("days", days)
+                .query("interval", 30),
+            BarsCrypto { ticker_id } => UrlBuilder::new(&self.base_fintech_gw_url)
+                .path("/crypto/charts/query")
+                .query("tickerIds", ticker_id),
+            CancelOrder {
+                account_id,
+                order_id,
+                serial,
+            } => UrlBuilder::new(&self.base_ustrade_url)
+                .path("/trade/order")
+                .segment(account_id)
+                .path("/cancelStockOrder")
+                .segment(order_id)
+                .segment(serial),
+            ModifyOtocoOrders { account_id } => UrlBuilder::new(&self.base_ustrade_url)
+                .path("/trade/v2/corder/stock/modify")
+                .segment(account_id),
+            CancelOtocoOrders {
+                account_id,
+                combo_id,
+            } => UrlBuilder::new(&self.base_ustrade_url)
+                .path("/trade/v2/corder/stock/cancel")
+                .segment(account_id)
+                .segment(combo_id),
+            CheckOrder { account_id } => UrlBuilder::new(&self.base_ustrade_url)
+                .path("/trade/v2/order/stock/check")
+                .segment(account_id),
+            CheckOtocoOrders { account_id } => UrlBuilder::new(&self.base_ustrade_url)
+                .path("/trade/v2/corder/stock/check")
+                .segment(account_id),
+            PlaceOtocoOrders { account_id } => UrlBuilder::new(&self.base_ustrade_url)
+                .path("/trade/v2/corder/stock/place")
+                .segment(account_id),
+            DripSettings { account_id } => UrlBuilder::new(&self.base_ustrade_url)
+                .path("/trade/v2/account")
+                .segment(account_id)
+                .path("/drip"),
+            Dividends { account_id } => UrlBuilder::new(&self.base_trade_url)
+                .path("/v2/account")
+                .segment(account_id)
+                .path("/dividends")
+                .query("direct", "in"),
+            TickerEarnings { ticker_id } => UrlBuilder::new(&self.base_fintech_gw_url)
+                .path("/information/financial/earnings")
+                .query("tickerId", ticker_id),
+            Financials {
+                ticker_id,
+                statement,
+                report_type,
+                fiscal_period,
+                limit,
+            } => UrlBuilder::new(&self.base_fintech_gw_url)
+                .path("/information/financial")
+                .segment(statement)
+                .query("tickerId", ticker_id)
+                .query("type", report_type)
+                .query("fiscalPeriod", fiscal_period)
+                .query("limit", limit),
+            Fundamentals { ticker_id } => UrlBuilder::new(&self.base_securities_url)
+                .path("/securities/financial/index")
+                .segment(ticker_id),
+            IsTradable { ticker_id } => UrlBuilder::new(&self.base_trade_url)
+                .path("/ticker/broker/permissionV2")
+                .query("tickerId", ticker_id),
+            Login => UrlBuilder::new(&self.base_userfintech_url).path("/user/v1/login/account/v2"),
+            GetMfa => {
+                UrlBuilder::new(&self.base_user_url).path("/user/v1/verificationCode/send/v2")
+            }
+            CheckMfa => UrlBuilder::new(&self.base_userfintech_url)
+                .path("/user/v1/verificationCode/checkCode"),
+            CaptchaQuery => {
+                UrlBuilder::new(&self.base_userfintech_url).path("/user/v1/captcha/sliding/query")
+            }
+            CaptchaVerify => {
+                UrlBuilder::new(&self.base_userfintech_url).path("/user/v1/captcha/sliding/verify")
+            }
+            GetSecurity {
+                username,
+                account_type,
+                region_code,
+                event,
+                time,
+                url_type,
+            }
+            | NextSecurity {
+                username,
+                account_type,
+                region_code,
+                event,
+                time,
+                url_type,
+            } => {
+                let next = matches!(endpoint, NextSecurity { .. });
+                let question = match (next, url_type == 1) {
+                    (false, true) => "getPrivacyQuestion",
+                    (false, false) => "getSecurityQuestion",
+                    (true, true) => "nextPrivacyQuestion",
+                    (true, false) => "nextSecurityQuestion",
+                };
+                UrlBuilder::new(&self.base_user_url)
+                    .path("/user/risk")
+                    .segment(question)
+                    .query("account", username)
+                    .query("accountType", account_type)
+                    .query("regionId", region_code)
+                    .query("event", event)
+                    .query("v", time)
+            }
+            CheckSecurity => UrlBuilder::new(&self.base_user_url).path("/user/risk/checkAnswer"),
+            Logout => UrlBuilder::new(&self.base_userfintech_url).path("/user/v1/logout"),
+            News {
+                ticker_id,
+                last_id,
+                items,
+            } => UrlBuilder::new(&self.base_fintech_gw_url)
+                .path("/information/news/tickerNews")
+                .query("tickerId", ticker_id)
+                .query("currentNewsId", last_id)
+                .query("pageSize", items),
+            OptionQuotes => {
+                UrlBuilder::new(&self.base_options_gw_url).path("/quote/option/query/list")
+            }
+            Options { ticker_id } | OptionsExpDate { ticker_id } => {
+                UrlBuilder::new(&self.base_options_url)
+                    .path("/quote/option")
+                    .segment(ticker_id)
+                    .path("/list")
+            }
+            OptionsExpDateNew => {
+                UrlBuilder::new(&self.base_fintech_gw_url).path("/quote/option/strategy/list")
+            }
+            OptionsBars { derivative_id } => UrlBuilder::new(&self.base_options_gw_url)
+                .path("/quote/option/chart/query")
+                .query("derivativeId", derivative_id),
+            Orders {
+                account_id,
+                page_size,
+                status,
+                last_create_time,
+            } => UrlBuilder::new(&self.base_ustradebroker_url)
+                .path("/trade/v2/option/list")
+                .query("secAccountId", account_id)
+                .query("startTime", "1970-01-01")
+                .query("dateType", "ORDER")
+                .query("pageSize", page_size)
+                .query("status", status)
+                .query_opt("lastCreateTime0", last_create_time),
+            History { account_id } => UrlBuilder::new(&self.base_ustrade_url)
+                .path("/trading/v1/webull/order/list")
+                .query("secAccountId", account_id),
+            PaperOrders {
+                paper_account_id,
+                page_size,
+                status,
+                last_create_time,
+            } => UrlBuilder::new(&self.base_paper_url)
+                .path("/paper/1/acc")
+                .segment(paper_account_id)
+                .path("/order")
+                .query("startTime", "1970-01-01")
+                .query("dateType", "ORDER")
+                .query("pageSize", page_size)
+                .query("status", status)
+                .query_opt("lastCreateTime0", last_create_time),
+            PaperAccount { paper_account_id } => UrlBuilder::new(&self.base_paperfintech_url)
+                .path("/paper/1/acc")
+                .segment(paper_account_id),
+            PaperAccountId => UrlBuilder::new(&self.base_paperfintech_url).path("/myaccounts/true"),
+            PaperCancelOrder {
+                paper_account_id,
+                order_id,
+            } => UrlBuilder::new(&self.base_paper_url)
+                .path("/paper/1/acc")
+                .segment(paper_account_id)
+                .path("/orderop/cancel")
+                .segment(order_id),
+            PaperModifyOrder {
+                paper_account_id,
+                order_id,
+            } => UrlBuilder::new(&self.base_paper_url)
+                .path("/paper/1/acc")
+                .segment(paper_account_id)
+                .path("/orderop/modify")
+                .segment(order_id),
+            PaperPlaceOrder {
+                paper_account_id,
+                ticker_id,
+            } => UrlBuilder::new(&self.base_paper_url)
+                .path("/paper/1/acc")
+                .segment(paper_account_id)
+                .path("/orderop/place")
+                .segment(ticker_id),
+            PlaceOptionOrders { account_id } => UrlBuilder::new(&self.base_ustrade_url)
+                .path("/trade/v2/option/placeOrder")
+                .segment(account_id),
+            PlaceOrders { account_id } => UrlBuilder::new(&self.base_ustrade_url)
+                .path("/trade/order")
+                .segment(account_id)
+                .path("/placeStockOrder"),
+            ModifyOrder { account_id } => UrlBuilder::new(&self.base_ustrade_url)
+                .path("/trading/v1/webull/order/stockOrderModify")
+                .query("secAccountId", account_id),
+            Quotes { ticker_id } => self.quote_url(&self.base_options_gw_url, ticker_id),
+            QuotesExtended { ticker_id } => self
+                .quote_url(&self.base_fintech_gw_url, ticker_id)
+                .query("more", 1),
+            QuotesBatch { ticker_ids } => UrlBuilder::new(&self.base_options_gw_url)
+                .path("/bgw/quote/realtime")
+                .query("ids", ticker_ids.join(","))
+                .query("includeSecu", 1)
+                .query("includeQuote", 1)
+                .query("more", 1),
+            Rankings => {
+                UrlBuilder::new(&self.base_securities_url).path("/securities/market/v5/6/portal")
+            }
+            RefreshLogin { refresh_token } => UrlBuilder::new(&self.base_user_url)
+                .path("/passport/refreshToken")
+                .query("refreshToken", refresh_token),
+            ReplaceOptionOrders { account_id } => UrlBuilder::new(&self.base_trade_url)
+                .path("/v2/option/replaceOrder")
+                .segment(account_id),
+            StockDetail { ticker_id } => UrlBuilder::new(&self.base_fintech_gw_url)
+                .path("/stock/tickerRealTime/getQuote")
+                .query("tickerId", ticker_id)
+                .query("includeSecu", 1)
+                .query("includeQuote", 1)
+                .query("more", 1),
+            StockId {
+                keyword,
+                region_code,
+            } => UrlBuilder::new(&self.base_options_gw_url)
+                .path("/search/pc/tickers")
+                .query("keyword", keyword)
+                .query("pageIndex", 1)
+                .query("pageSize", 20)
+                .query("regionId", region_code),
+            TickerByIdentifier {
+                id_type,
+                identifier,
+                region_code,
+            } => UrlBuilder::new(&self.base_options_gw_url)
+                .path("/search/pc/tickers/identifier")
+                .query("type", id_type)
+                .query("identifier", identifier)
+                .query("regionId", region_code),
+            TradeToken => {
+                UrlBuilder::new(&self.base_new_trade_url).path("/trading/v1/global/trade/login")
+            }
+            User => UrlBuilder::new(&self.base_user_url).path("/user"),
+            FxRate { from, to, date } => UrlBuilder::new(&self.base_fintech_gw_url)
+                .path("/market/fx/history")
+                .query("fromCurrency", from)
+                .query("toCurrency", to)
+                .query("date", date),
+            SecurityEvents { page_size } => UrlBuilder::new(&self.base_user_url)
+                .path("/user/message/list")
+                .query("msgType", "SECURITY")
+                .query("pageSize", page_size),
+            Screener => UrlBuilder::new(&self.base_userbroker_url).path("/wlas/screener/ng/query"),
+            SocialPosts { topic, num } => UrlBuilder::new(&self.base_user_url)
+                .path("/social/feed/topic")
+                .segment(topic)
+                .path("/posts")
+                .query("size", num),
+            SocialHome { topic, num } => UrlBuilder::new(&self.base_user_url)
+                .path("/social/feed/topic")
+                .segment(topic)
+                .path("/home")
+                .query("size", num),
+            PortfolioLists => {
+                UrlBuilder::new(&self.base_options_gw_url).path("/personal/portfolio/v2/check")
+            }
+            CreateWatchlist => {
+                UrlBuilder::new(&self.base_options_gw_url).path("/personal/portfolio/v2/create")
+            }
+            DeleteWatchlist { watchlist_id } => UrlBuilder::new(&self.base_options_gw_url)
+                .path("/personal/portfolio/v2")
+                .segment(watchlist_id)
+                .path("/delete"),
+            AddWatchlistTickers { watchlist_id } => UrlBuilder::new(&self.base_options_gw_url)
+                .path("/personal/portfolio/v2")
+                .segment(watchlist_id)
+                .path("/tickers/add"),
+            RemoveWatchlistTickers { watchlist_id } => UrlBuilder::new(&self.base_options_gw_url)
+                .path("/personal/portfolio/v2")
+                .segment(watchlist_id)
+                .path("/tickers/remove"),
+            PressReleases {
+                ticker_id,
+                type_ids,
+                num,
+            } => UrlBuilder::new(&self.base_securitiesfintech_url)
+                .path("/securities/announcement")
+                .segment(ticker_id)
+                .path("/list")
+                .query("lastAnnouncementId", 0)
+                .query("limit", num)
+                .query_opt("typeIds", type_ids)
+                .query("options", 2),
+            CalendarEvents {
+                event,
+                region_code,
+                start_date,
+                page,
+                num,
+            } => UrlBuilder::new(&self.base_fintech_gw_url)
+                .path("/bgw/explore/calendar")
+                .segment(event)
+                .query("regionId", region_code)
+                .query("pageIndex", page)
+                .query("pageSize", num)
+                .query("startDate", start_date),
+            InstrumentList {
+                region_code,
+                user_region_code,
+                offset,
+                page_size,
+            } => UrlBuilder::new(&self.base_securitiesfintech_url)
+                .path("/securities/market/v5/card/stockActivityPc.advanced/list")
+                .query("regionId", region_code)
+                .query("userRegionId", user_region_code)
+                .query("hasNum", offset)
+                .query("pageSize", page_size),
+        }
+        .build()
+    }
+
+    fn quote_url(&self, base: &str, ticker_id: &str) -> UrlBuilder {
+        UrlBuilder::new(base)
+            .path("/quotes/ticker/getTickerRealTime")
+            .query("tickerId", ticker_id)
+            .query("includeSecu", 1)
+            .query("includeQuote", 1)
+    }
+
     pub fn account(&self, account_id: &str) -> String {
-        format!("{}/v3/home/{}", self.base_trade_url, account_id)
+        self.url(&Endpoint::Account { account_id })
     }
 
     pub fn account_id(&self) -> String {
-        format!("{}/account/getSecAccountList/v5", self.base_trade_url)
+        self.url(&Endpoint::AccountId)
     }
 
     pub fn account_activities(&self, account_id: &str) -> String {
-        format!(
-            "{}/trade/v2/funds/{}/activities",
-            self.base_ustrade_url, account_id
-        )
+        self.url(&Endpoint::AccountActivities { account_id })
     }
 
     pub fn active_gainers_losers(
@@ -69,149 +611,179 @@ impl Endpoints {
         rank_type: &str,
         num: i32,
     ) -> String {
-        let url = match direction {
-            "gainer" => "topGainers",
-            "loser" => "dropGainers",
-            _ => "topActive",
-        };
-        format!(
-            "{}/wlas/ranking/{}?regionId={}&rankType={}&pageIndex=1&pageSize={}",
-            self.base_fintech_gw_url, url, region_code, rank_type, num
-        )
+        self.url(&Endpoint::ActiveGainersLosers {
+            direction,
+            region_code,
+            rank_type,
+            num,
+        })
+    }
+
+    pub fn top_options(&self, rank_type: &str, region_code: i32, num: i32) -> String {
+        self.url(&Endpoint::TopOptions {
+            rank_type,
+            region_code,
+            num,
+        })
     }
 
     pub fn add_alert(&self) -> String {
-        format!(
-            "{}/user/warning/v2/manage/overlap",
-            self.base_userbroker_url
-        )
+        self.url(&Endpoint::AddAlert)
     }
 
     pub fn analysis(&self, stock: &str) -> String {
-        format!(
-            "{}/securities/ticker/v5/analysis/{}",
-            self.base_securities_url, stock
-        )
+        self.url(&Endpoint::Analysis { ticker_id: stock })
     }
 
     pub fn analysis_shortinterest(&self, stock: &str) -> String {
-        format!(
-            "{}/securities/stock/{}/shortInterest",
-            self.base_securities_url, stock
-        )
+        self.url(&Endpoint::AnalysisShortInterest { ticker_id: stock })
     }
 
     pub fn analysis_institutional_holding(&self, stock: &str) -> String {
-        format!(
-            "{}/securities/stock/v5/{}/institutionalHolding",
-            self.base_securities_url, stock
-        )
+        self.url(&Endpoint::AnalysisInstitutionalHolding { ticker_id: stock })
     }
 
     pub fn analysis_etf_holding(&self, stock: &str, has_num: i32, page_size: i32) -> String {
-        format!(
-            "{}/securities/stock/v5/{}/belongEtf?hasNum={}&pageSize={}",
-            self.base_securities_url, stock, has_num, page_size
-        )
+        self.url(&Endpoint::AnalysisEtfHolding {
+            ticker_id: stock,
+            has_num,
+            page_size,
+        })
     }
 
     pub fn analysis_capital_flow(&self, stock: &str, show_hist: bool) -> String {
-        format!(
-            "{}/wlas/capitalflow/ticker?tickerId={}&showHis={}",
-            self.base_securities_url, stock, show_hist
-        )
+        self.url(&Endpoint::AnalysisCapitalFlow {
+            ticker_id: stock,
+            show_hist,
+        })
     }
 
     pub fn bars(&self, stock: &str, interval: &str, count: i32, timestamp: Option<i64>) -> String {
-        let ts = timestamp
-            .map(|t| format!("&timestamp={}", t))
-            .unwrap_or_default();
-        format!(
-            "{}/quote/charts/query?tickerIds={}&type={}&count={}{}",
-            self.base_fintech_gw_url, stock, interval, count, ts
-        )
+        self.url(&Endpoint::Bars {
+            ticker_id: stock,
+            interval,
+            count,
+            timestamp,
+        })
+    }
+
+    pub fn ticker_minute_volume(&self, stock: &str, days: i32) -> String {
+        self.url(&Endpoint::TickerMinuteVolume {
+            ticker_id: stock,
+            days,
+        })
+    }
+
+    /// Recent trades (time & sales)
+    pub fn trade_ticks(&self, stock: &str, count: i32) -> String {
+        self.url(&Endpoint::TradeTicks {
+            ticker_id: stock,
+            count,
+        })
+    }
+
+    pub fn etf_profile(&self, stock: &str) -> String {
+        self.url(&Endpoint::EtfProfile { ticker_id: stock })
+    }
+
+    pub fn etf_holdings(&self, stock: &str) -> String {
+        self.url(&Endpoint::EtfHoldings { ticker_id: stock })
     }
 
     pub fn bars_crypto(&self, stock: &str) -> String {
-        format!(
-            "{}/crypto/charts/query?tickerIds={}",
-            self.base_fintech_gw_url, stock
-        )
+        self.url(&Endpoint::BarsCrypto { ticker_id: stock })
     }
 
-    pub fn cancel_order(&self, account_id: &str) -> String {
-        format!(
-            "{}/trade/order/{}/cancelStockOrder/",
-            self.base_ustrade_url, account_id
-        )
+    pub fn cancel_order(&self, account_id: &str, order_id: &str, serial: &str) -> String {
+        self.url(&Endpoint::CancelOrder {
+            account_id,
+            order_id,
+            serial,
+        })
     }
 
     pub fn modify_otoco_orders(&self, account_id: &str) -> String {
-        format!(
-            "{}/trade/v2/corder/stock/modify/{}",
-            self.base_ustrade_url, account_id
-        )
+        self.url(&Endpoint::ModifyOtocoOrders { account_id })
     }
 
     pub fn cancel_otoco_orders(&self, account_id: &str, combo_id: &str) -> String {
-        format!(
-            "{}/trade/v2/corder/stock/cancel/{}/{}",
-            self.base_ustrade_url, account_id, combo_id
-        )
+        self.url(&Endpoint::CancelOtocoOrders {
+            account_id,
+            combo_id,
+        })
+    }
+
+    pub fn check_order(&self, account_id: &str) -> String {
+        self.url(&Endpoint::CheckOrder { account_id })
     }
 
     pub fn check_otoco_orders(&self, account_id: &str) -> String {
-        format!(
-            "{}/trade/v2/corder/stock/check/{}",
-            self.base_ustrade_url, account_id
-        )
+        self.url(&Endpoint::CheckOtocoOrders { account_id })
     }
 
     pub fn place_otoco_orders(&self, account_id: &str) -> String {
-        format!(
-            "{}/trade/v2/corder/stock/place/{}",
-            self.base_ustrade_url, account_id
-        )
+        self.url(&Endpoint::PlaceOtocoOrders { account_id })
+    }
+
+    pub fn drip_settings(&self, account_id: &str) -> String {
+        self.url(&Endpoint::DripSettings { account_id })
     }
 
     pub fn dividends(&self, account_id: &str) -> String {
-        format!(
-            "{}/v2/account/{}/dividends?direct=in",
-            self.base_trade_url, account_id
-        )
+        self.url(&Endpoint::Dividends { account_id })
+    }
+
+    pub fn financials(
+        &self,
+        ticker_id: &str,
+        statement: &str,
+        report_type: i32,
+        fiscal_period: &str,
+        limit: i32,
+    ) -> String {
+        self.url(&Endpoint::Financials {
+            ticker_id,
+            statement,
+            report_type,
+            fiscal_period,
+            limit,
+        })
     }
 
     pub fn fundamentals(&self, stock: &str) -> String {
-        format!(
-            "{}/securities/financial/index/{}",
-            self.base_securities_url, stock
-        )
+        self.url(&Endpoint::Fundamentals { ticker_id: stock })
+    }
+
+    pub fn ticker_earnings(&self, stock: &str) -> String {
+        self.url(&Endpoint::TickerEarnings { ticker_id: stock })
     }
 
     pub fn is_tradable(&self, stock: &str) -> String {
-        format!(
-            "{}/ticker/broker/permissionV2?tickerId={}",
-            self.base_trade_url, stock
-        )
+        self.url(&Endpoint::IsTradable { ticker_id: stock })
     }
 
     pub fn list_alerts(&self) -> String {
-        format!("{}/user/warning/v2/query/tickers", self.base_userbroker_url)
+        self.url(&Endpoint::ListAlerts)
     }
 
     pub fn login(&self) -> String {
-        format!("{}/user/v1/login/account/v2", self.base_userfintech_url)
+        self.url(&Endpoint::Login)
     }
 
     pub fn get_mfa(&self) -> String {
-        format!("{}/user/v1/verificationCode/send/v2", self.base_user_url)
+        self.url(&Endpoint::GetMfa)
     }
 
     pub fn check_mfa(&self) -> String {
-        format!(
-            "{}/user/v1/verificationCode/checkCode",
-            self.base_userfintech_url
-        )
+        self.url(&Endpoint::CheckMfa)
+    }
+
+    pub fn captcha_query(&self) -> String {
+        self.url(&Endpoint::CaptchaQuery)
+    }
+
+    pub fn captcha_verify(&self) -> String {
+        self.url(&Endpoint::CaptchaVerify)
     }
 
     pub fn get_security(
@@ -223,15 +795,14 @@ impl Endpoints {
         time: i64,
         url_type: i32,
     ) -> String {
-        let url = if url_type == 1 {
-            "getPrivacyQuestion"
-        } else {
-            "getSecurityQuestion"
-        };
-        format!(
-            "{}/user/risk/{}?account={}&accountType={}&regionId={}&event={}&v={}",
-            self.base_user_url, url, username, account_type, region_code, event, time
-        )
+        self.url(&Endpoint::GetSecurity {
+            username,
+            account_type,
+            region_code,
+            event,
+            time,
+            url_type,
+        })
     }
 
     pub fn next_security(
@@ -243,213 +814,257 @@ impl Endpoints {
         time: i64,
         url_type: i32,
     ) -> String {
-        let url = if url_type == 1 {
-            "nextPrivacyQuestion"
-        } else {
-            "nextSecurityQuestion"
-        };
-        format!(
-            "{}/user/risk/{}?account={}&accountType={}&regionId={}&event={}&v={}",
-            self.base_user_url, url, username, account_type, region_code, event, time
-        )
+        self.url(&Endpoint::NextSecurity {
+            username,
+            account_type,
+            region_code,
+            event,
+            time,
+            url_type,
+        })
     }
 
     pub fn check_security(&self) -> String {
-        format!("{}/user/risk/checkAnswer", self.base_user_url)
+        self.url(&Endpoint::CheckSecurity)
     }
 
     pub fn logout(&self) -> String {
-        format!("{}/user/v1/logout", self.base_userfintech_url)
+        self.url(&Endpoint::Logout)
     }
 
     pub fn news(&self, stock: &str, id: i64, items: i32) -> String {
-        format!(
-            "{}/information/news/tickerNews?tickerId={}&currentNewsId={}&pageSize={}",
-            self.base_fintech_gw_url, stock, id, items
-        )
+        self.url(&Endpoint::News {
+            ticker_id: stock,
+            last_id: id,
+            items,
+        })
     }
 
     pub fn option_quotes(&self) -> String {
-        format!("{}/quote/option/query/list", self.base_options_gw_url)
+        self.url(&Endpoint::OptionQuotes)
     }
 
     pub fn options(&self, stock: &str) -> String {
-        format!("{}/quote/option/{}/list", self.base_options_url, stock)
+        self.url(&Endpoint::Options { ticker_id: stock })
     }
 
     pub fn options_exp_date(&self, stock: &str) -> String {
-        format!("{}/quote/option/{}/list", self.base_options_url, stock)
+        self.url(&Endpoint::OptionsExpDate { ticker_id: stock })
     }
 
     pub fn options_exp_date_new(&self) -> String {
-        format!("{}/quote/option/strategy/list", self.base_fintech_gw_url)
+        self.url(&Endpoint::OptionsExpDateNew)
     }
 
     pub fn options_bars(&self, derivative_id: &str) -> String {
-        format!(
-            "{}/quote/option/chart/query?derivativeId={}",
-            self.base_options_gw_url, derivative_id
-        )
+        self.url(&Endpoint::OptionsBars { derivative_id })
     }
 
     pub fn orders(&self, account_id: &str, page_size: i32) -> String {
-        format!(
-            "{}/trade/v2/option/list?secAccountId={}&startTime=1970-01-01&dateType=ORDER&pageSize={}&status=",
-            self.base_ustradebroker_url, account_id, page_size
-        )
+        self.orders_with_status(account_id, page_size, "", None)
+    }
+
+    /// Order list filtered by status, optionally paging before a creation time
+    pub fn orders_with_status(
+        &self,
+        account_id: &str,
+        page_size: i32,
+        status: &str,
+        last_create_time: Option<i64>,
+    ) -> String {
+        self.url(&Endpoint::Orders {
+            account_id,
+            page_size,
+            status,
+            last_create_time,
+        })
     }
 
     pub fn history(&self, account_id: &str) -> String {
-        format!(
-            "{}/trading/v1/webull/order/list?secAccountId={}",
-            self.base_ustrade_url, account_id
-        )
+        self.url(&Endpoint::History { account_id })
     }
 
     pub fn paper_orders(&self, paper_account_id: &str, page_size: i32) -> String {
-        format!(
-            "{}/paper/1/acc/{}/order?&startTime=1970-01-01&dateType=ORDER&pageSize={}&status=",
-            self.base_paper_url, paper_account_id, page_size
-        )
+        self.paper_orders_with_status(paper_account_id, page_size, "", None)
+    }
+
+    /// Paper order list filtered by status, optionally paging before a creation time
+    pub fn paper_orders_with_status(
+        &self,
+        paper_account_id: &str,
+        page_size: i32,
+        status: &str,
+        last_create_time: Option<i64>,
+    ) -> String {
+        self.url(&Endpoint::PaperOrders {
+            paper_account_id,
+            page_size,
+            status,
+            last_create_time,
+        })
     }
 
     pub fn paper_account(&self, paper_account_id: &str) -> String {
-        format!(
-            "{}/paper/1/acc/{}",
-            self.base_paperfintech_url, paper_account_id
-        )
+        self.url(&Endpoint::PaperAccount { paper_account_id })
     }
 
     pub fn paper_account_id(&self) -> String {
-        format!("{}/myaccounts/true", self.base_paperfintech_url)
+        self.url(&Endpoint::PaperAccountId)
     }
 
     pub fn paper_cancel_order(&self, paper_account_id: &str, order_id: &str) -> String {
-        format!(
-            "{}/paper/1/acc/{}/orderop/cancel/{}",
-            self.base_paper_url, paper_account_id, order_id
-        )
+        self.url(&Endpoint::PaperCancelOrder {
+            paper_account_id,
+            order_id,
+        })
     }
 
     pub fn paper_modify_order(&self, paper_account_id: &str, order_id: &str) -> String {
-        format!(
-            "{}/paper/1/acc/{}/orderop/modify/{}",
-            self.base_paper_url, paper_account_id, order_id
-        )
+        self.url(&Endpoint::PaperModifyOrder {
+            paper_account_id,
+            order_id,
+        })
     }
 
     pub fn paper_place_order(&self, paper_account_id: &str, stock: &str) -> String {
-        format!(
-            "{}/paper/1/acc/{}/orderop/place/{}",
-            self.base_paper_url, paper_account_id, stock
-        )
+        self.url(&Endpoint::PaperPlaceOrder {
+            paper_account_id,
+            ticker_id: stock,
+        })
     }
 
     pub fn place_option_orders(&self, account_id: &str) -> String {
-        format!(
-            "{}/trade/v2/option/placeOrder/{}",
-            self.base_ustrade_url, account_id
-        )
+        self.url(&Endpoint::PlaceOptionOrders { account_id })
     }
 
     pub fn place_orders(&self, account_id: &str) -> String {
-        format!(
-            "{}/trade/order/{}/placeStockOrder",
-            self.base_ustrade_url, account_id
-        )
+        self.url(&Endpoint::PlaceOrders { account_id })
     }
 
     pub fn modify_order(&self, account_id: &str, _order_id: &str) -> String {
-        format!(
-            "{}/trading/v1/webull/order/stockOrderModify?secAccountId={}",
-            self.base_ustrade_url, account_id
-        )
+        self.url(&Endpoint::ModifyOrder { account_id })
     }
 
     pub fn quotes(&self, stock: &str) -> String {
-        format!(
-            "{}/quotes/ticker/getTickerRealTime?tickerId={}&includeSecu=1&includeQuote=1",
-            self.base_options_gw_url, stock
-        )
+        self.url(&Endpoint::Quotes { ticker_id: stock })
+    }
+
+    /// Realtime quote with the pre/post-market fields included
+    pub fn quotes_extended(&self, stock: &str) -> String {
+        self.url(&Endpoint::QuotesExtended { ticker_id: stock })
+    }
+
+    /// Realtime quote URLs in fallback order: fintech gateway, then broker hosts
+    pub fn quotes_fallbacks(&self, stock: &str) -> Vec<String> {
+        [
+            &self.base_fintech_gw_url,
+            &self.base_options_gw_url,
+            &self.base_quote_url,
+        ]
+        .iter()
+        .map(|base| self.quote_url(base, stock).build())
+        .collect()
+    }
+
+    pub fn quotes_batch(&self, ticker_ids: &[&str]) -> String {
+        self.url(&Endpoint::QuotesBatch { ticker_ids })
     }
 
     pub fn rankings(&self) -> String {
-        format!("{}/securities/market/v5/6/portal", self.base_securities_url)
+        self.url(&Endpoint::Rankings)
     }
 
     pub fn refresh_login(&self, refresh_token: &str) -> String {
-        format!(
-            "{}/passport/refreshToken?refreshToken={}",
-            self.base_user_url, refresh_token
-        )
+        self.url(&Endpoint::RefreshLogin { refresh_token })
     }
 
     pub fn remove_alert(&self) -> String {
-        format!(
-            "{}/user/warning/v2/manage/overlap",
-            self.base_userbroker_url
-        )
+        self.url(&Endpoint::RemoveAlert)
     }
 
     pub fn replace_option_orders(&self, account_id: &str) -> String {
-        format!(
-            "{}/v2/option/replaceOrder/{}",
-            self.base_trade_url, account_id
-        )
+        self.url(&Endpoint::ReplaceOptionOrders { account_id })
     }
 
     pub fn stock_detail(&self, stock: &str) -> String {
-        format!(
-            "{}/stock/tickerRealTime/getQuote?tickerId={}&includeSecu=1&includeQuote=1&more=1",
-            self.base_fintech_gw_url, stock
-        )
+        self.url(&Endpoint::StockDetail { ticker_id: stock })
     }
 
     pub fn stock_id(&self, stock: &str, region_code: i32) -> String {
-        format!(
-            "{}/search/pc/tickers?keyword={}&pageIndex=1&pageSize=20&regionId={}",
-            self.base_options_gw_url, stock, region_code
-        )
+        self.url(&Endpoint::StockId {
+            keyword: stock,
+            region_code,
+        })
+    }
+
+    /// Search by security identifier; `id_type` is `isin` or `cusip`
+    pub fn ticker_by_identifier(
+        &self,
+        id_type: &str,
+        identifier: &str,
+        region_code: i32,
+    ) -> String {
+        self.url(&Endpoint::TickerByIdentifier {
+            id_type,
+            identifier,
+            region_code,
+        })
     }
 
     pub fn trade_token(&self) -> String {
-        format!("{}/trading/v1/global/trade/login", self.base_new_trade_url)
+        self.url(&Endpoint::TradeToken)
     }
 
     pub fn user(&self) -> String {
-        format!("{}/user", self.base_user_url)
+        self.url(&Endpoint::User)
+    }
+
+    pub fn fx_rate(&self, from: &str, to: &str, date: &str) -> String {
+        self.url(&Endpoint::FxRate { from, to, date })
+    }
+
+    pub fn security_events(&self, page_size: i32) -> String {
+        self.url(&Endpoint::SecurityEvents { page_size })
     }
 
     pub fn screener(&self) -> String {
-        format!("{}/wlas/screener/ng/query", self.base_userbroker_url)
+        self.url(&Endpoint::Screener)
     }
 
     pub fn social_posts(&self, topic: &str, num: i32) -> String {
-        format!(
-            "{}/social/feed/topic/{}/posts?size={}",
-            self.base_user_url, topic, num
-        )
+        self.url(&Endpoint::SocialPosts { topic, num })
     }
 
     pub fn social_home(&self, topic: &str, num: i32) -> String {
-        format!(
-            "{}/social/feed/topic/{}/home?size={}",
-            self.base_user_url, topic, num
-        )
+        self.url(&Endpoint::SocialHome { topic, num })
     }
 
     pub fn portfolio_lists(&self) -> String {
-        format!("{}/personal/portfolio/v2/check", self.base_options_gw_url)
+        self.url(&Endpoint::PortfolioLists)
+    }
+
+    pub fn create_watchlist(&self) -> String {
+        self.url(&Endpoint::CreateWatchlist)
+    }
+
+    pub fn delete_watchlist(&self, watchlist_id: &str) -> String {
+        self.url(&Endpoint::DeleteWatchlist { watchlist_id })
+    }
+
+    pub fn add_watchlist_tickers(&self, watchlist_id: &str) -> String {
+        self.url(&Endpoint::AddWatchlistTickers { watchlist_id })
+    }
+
+    pub fn remove_watchlist_tickers(&self, watchlist_id: &str) -> String {
+        self.url(&Endpoint::RemoveWatchlistTickers { watchlist_id })
     }
 
     pub fn press_releases(&self, stock: &str, type_ids: Option<&str>, num: i32) -> String {
-        let type_ids_string = type_ids
-            .map(|t| format!("&typeIds={}", t))
-            .unwrap_or_default();
-        format!(
-            "{}/securities/announcement/{}/list?lastAnnouncementId=0&limit={}{}&options=2",
-            self.base_securitiesfintech_url, stock, num, type_ids_string
-        )
+        self.url(&Endpoint::PressReleases {
+            ticker_id: stock,
+            type_ids,
+            num,
+        })
     }
 
     pub fn calendar_events(
@@ -460,16 +1075,694 @@ impl Endpoints {
         page: i32,
         num: i32,
     ) -> String {
-        format!(
-            "{}/bgw/explore/calendar/{}?regionId={}&pageIndex={}&pageSize={}&startDate={}",
-            self.base_fintech_gw_url, event, region_code, page, num, start_date
-        )
+        self.url(&Endpoint::CalendarEvents {
+            event,
+            region_code,
+            start_date,
+            page,
+            num,
+        })
     }
 
     pub fn get_all_tickers(&self, region_code: i32, user_region_code: i32) -> String {
-        format!(
-            "{}/securities/market/v5/card/stockActivityPc.advanced/list?regionId={}&userRegionId={}&hasNum=0&pageSize=9999",
-            self.base_securitiesfintech_url, region_code, user_region_code
-        )
+        self.url(&Endpoint::InstrumentList {
+            region_code,
+            user_region_code,
+            offset: 0,
+            page_size: 9999,
+        })
+    }
+
+    pub fn instrument_list(&self, region_code: i32, offset: usize, page_size: usize) -> String {
+        self.url(&Endpoint::InstrumentList {
+            region_code,
+            user_region_code: region_code,
+            offset,
+            page_size,
+        })
+    }
+
+    /// Upcoming and recently priced IPOs
+    pub fn ipo_calendar(&self, region_code: i32) -> String {
+        self.url(&Endpoint::IpoCalendar { region_code })
+    }
+}
+
+/// Every Webull endpoint the client calls, with its parameters
+///
+/// `Endpoints::url` turns a variant into a full URL, percent-encoding every
+/// parameter so user input (search keywords, usernames, statuses) can't
+/// break the path or inject extra query parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Endpoint<'a> {
+    Account {
+        account_id: &'a str,
+    },
+    AccountId,
+    AccountActivities {
+        account_id: &'a str,
+    },
+    ActiveGainersLosers {
+        direction: &'a str,
+        region_code: i32,
+        rank_type: &'a str,
+        num: i32,
+    },
+    TopOptions {
+        rank_type: &'a str,
+        region_code: i32,
+        num: i32,
+    },
+    AddAlert,
+    RemoveAlert,
+    ListAlerts,
+    Analysis {
+        ticker_id: &'a str,
+    },
+    AnalysisShortInterest {
+        ticker_id: &'a str,
+    },
+    AnalysisInstitutionalHolding {
+        ticker_id: &'a str,
+    },
+    AnalysisEtfHolding {
+        ticker_id: &'a str,
+        has_num: i32,
+        page_size: i32,
+    },
+    AnalysisCapitalFlow {
+        ticker_id: &'a str,
+        show_hist: bool,
+    },
+    Bars {
+        ticker_id: &'a str,
+        interval: &'a str,
+        count: i32,
+        timestamp: Option<i64>,
+    },
+    TickerMinuteVolume {
+        ticker_id: &'a str,
+        days: i32,
+    },
+    TradeTicks {
+        ticker_id: &'a str,
+        count: i32,
+    },
+    EtfProfile {
+        ticker_id: &'a str,
+    },
+    EtfHoldings {
+        ticker_id: &'a str,
+    },
+    BarsCrypto {
+        ticker_id: &'a str,
+    },
+    CancelOrder {
+        account_id: &'a str,
+        order_id: &'a str,
+        /// Fresh UUID per request, as the Python client sends
+        serial: &'a str,
+    },
+    ModifyOtocoOrders {
+        account_id: &'a str,
+    },
+    CancelOtocoOrders {
+        account_id: &'a str,
+        combo_id: &'a str,
+    },
+    CheckOrder {
+        account_id: &'a str,
+    },
+    CheckOtocoOrders {
+        account_id: &'a str,
+    },
+    PlaceOtocoOrders {
+        account_id: &'a str,
+    },
+    DripSettings {
+        account_id: &'a str,
+    },
+    Dividends {
+        account_id: &'a str,
+    },
+    Financials {
+        ticker_id: &'a str,
+        statement: &'a str,
+        report_type: i32,
+        fiscal_period: &'a str,
+        limit: i32,
+    },
+    Fundamentals {
+        ticker_id: &'a str,
+    },
+    TickerEarnings {
+        ticker_id: &'a str,
+    },
+    IsTradable {
+        ticker_id: &'a str,
+    },
+    Login,
+    GetMfa,
+    CheckMfa,
+    CaptchaQuery,
+    CaptchaVerify,
+    GetSecurity {
+        username: &'a str,
+        account_type: i32,
+        region_code: i32,
+        event: &'a str,
+        time: i64,
+        url_type: i32,
+    },
+    NextSecurity {
+        username: &'a str,
+        account_type: i32,
+        region_code: i32,
+        event: &'a str,
+        time: i64,
+        url_type: i32,
+    },
+    CheckSecurity,
+    Logout,
+    News {
+        ticker_id: &'a str,
+        last_id: i64,
+        items: i32,
+    },
+    OptionQuotes,
+    Options {
+        ticker_id: &'a str,
+    },
+    OptionsExpDate {
+        ticker_id: &'a str,
+    },
+    OptionsExpDateNew,
+    OptionsBars {
+        derivative_id: &'a str,
+    },
+    Orders {
+        account_id: &'a str,
+        page_size: i32,
+        status: &'a str,
+        last_create_time: Option<i64>,
+    },
+    History {
+        account_id: &'a str,
+    },
+    PaperOrders {
+        paper_account_id: &'a str,
+        page_size: i32,
+        status: &'a str,
+        last_create_time: Option<i64>,
+    },
+    PaperAccount {
+        paper_account_id: &'a str,
+    },
+    PaperAccountId,
+    PaperCancelOrder {
+        paper_account_id: &'a str,
+        order_id: &'a str,
+    },
+    PaperModifyOrder {
+        paper_account_id: &'a str,
+        order_id: &'a str,
+    },
+    PaperPlaceOrder {
+        paper_account_id: &'a str,
+        ticker_id: &'a str,
+    },
+    PlaceOptionOrders {
+        account_id: &'a str,
+    },
+    PlaceOrders {
+        account_id: &'a str,
+    },
+    ModifyOrder {
+        account_id: &'a str,
+    },
+    Quotes {
+        ticker_id: &'a str,
+    },
+    QuotesBatch {
+        ticker_ids: &'a [&'a str],
+    },
+    QuotesExtended {
+        ticker_id: &'a str,
+    },
+    Rankings,
+    RefreshLogin {
+        refresh_token: &'a str,
+    },
+    ReplaceOptionOrders {
+        account_id: &'a str,
+    },
+    StockDetail {
+        ticker_id: &'a str,
+    },
+    StockId {
+        keyword: &'a str,
+        region_code: i32,
+    },
+    TickerByIdentifier {
+        id_type: &'a str,
+        identifier: &'a str,
+        region_code: i32,
+    },
+    TradeToken,
+    User,
+    SecurityEvents {
+        page_size: i32,
+    },
+    FxRate {
+        from: &'a str,
+        to: &'a str,
+        date: &'a str,
+    },
+    Screener,
+    SocialPosts {
+        topic: &'a str,
+        num: i32,
+    },
+    SocialHome {
+        topic: &'a str,
+        num: i32,
+    },
+    PortfolioLists,
+    CreateWatchlist,
+    DeleteWatchlist {
+        watchlist_id: &'a str,
+    },
+    AddWatchlistTickers {
+        watchlist_id: &'a str,
+    },
+    RemoveWatchlistTickers {
+        watchlist_id: &'a str,
+    },
+    PressReleases {
+        ticker_id: &'a str,
+        type_ids: Option<&'a str>,
+        num: i32,
+    },
+    CalendarEvents {
+        event: &'a str,
+        region_code: i32,
+        start_date: &'a str,
+        page: i32,
+        num: i32,
+    },
+    InstrumentList {
+        region_code: i32,
+        user_region_code: i32,
+        offset: usize,
+        page_size: usize,
+    },
+    IpoCalendar {
+        region_code: i32,
+    },
+}
+
+/// Path of `url` without a trailing slash
+fn url_path(url: &str) -> String {
+    url::Url::parse(url)
+        .map(|parsed| parsed.path().trim_end_matches('/').to_string())
+        .unwrap_or_default()
+}
+
+/// Builder for `Endpoints` with individual base URLs overridden
+///
+/// ```
+/// use webull_unofficial::endpoints::Endpoints;
+///
+/// let endpoints = Endpoints::builder()
+///     .redirect_host("quotes-gw.webullfintech.com", "https://proxy.internal:8443")
+///     .trade_url("https://trade-proxy.internal/api/trade")
+///     .build()
+///     .unwrap();
+/// assert_eq!(endpoints.base_fintech_gw_url, "https://proxy.internal:8443/api");
+/// ```
+#[derive(Debug, Clone)]
+pub struct EndpointsBuilder {
+    endpoints: Endpoints,
+    redirects: Vec<(String, String)>,
+}
+
+impl Default for EndpointsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EndpointsBuilder {
+    pub fn new() -> Self {
+        Self::from_endpoints(Endpoints::default())
+    }
+
+    /// Start from an existing set of endpoints
+    pub fn from_endpoints(endpoints: Endpoints) -> Self {
+        Self {
+            endpoints,
+            redirects: Vec::new(),
+        }
+    }
+
+    /// Send every base URL on `host` to `base_url` instead, keeping its
+    /// path; applied after the individual overrides
+    pub fn redirect_host(mut self, host: impl Into<String>, base_url: impl Into<String>) -> Self {
+        self.redirects.push((host.into(), base_url.into()));
+        self
+    }
+
+    /// Override `base_info_url`
+    pub fn info_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.base_info_url = url.into();
+        self
+    }
+
+    /// Override `base_options_url`
+    pub fn options_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.base_options_url = url.into();
+        self
+    }
+
+    /// Override `base_options_gw_url`
+    pub fn options_gw_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.base_options_gw_url = url.into();
+        self
+    }
+
+    /// Override `base_paper_url`
+    pub fn paper_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.base_paper_url = url.into();
+        self
+    }
+
+    /// Override `base_quote_url`
+    pub fn quote_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.base_quote_url = url.into();
+        self
+    }
+
+    /// Override `base_securities_url`
+    pub fn securities_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.base_securities_url = url.into();
+        self
+    }
+
+    /// Override `base_trade_url`
+    pub fn trade_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.base_trade_url = url.into();
+        self
+    }
+
+    /// Override `base_user_url`
+    pub fn user_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.base_user_url = url.into();
+        self
+    }
+
+    /// Override `base_userbroker_url`
+    pub fn userbroker_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.base_userbroker_url = url.into();
+        self
+    }
+
+    /// Override `base_ustrade_url`
+    pub fn ustrade_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.base_ustrade_url = url.into();
+        self
+    }
+
+    /// Override `base_paperfintech_url`
+    pub fn paperfintech_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.base_paperfintech_url = url.into();
+        self
+    }
+
+    /// Override `base_fintech_gw_url`
+    pub fn fintech_gw_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.base_fintech_gw_url = url.into();
+        self
+    }
+
+    /// Override `base_userfintech_url`
+    pub fn userfintech_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.base_userfintech_url = url.into();
+        self
+    }
+
+    /// Override `base_new_trade_url`
+    pub fn new_trade_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.base_new_trade_url = url.into();
+        self
+    }
+
+    /// Override `base_ustradebroker_url`
+    pub fn ustradebroker_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.base_ustradebroker_url = url.into();
+        self
+    }
+
+    /// Override `base_securitiesfintech_url`
+    pub fn securitiesfintech_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.base_securitiesfintech_url = url.into();
+        self
+    }
+
+    /// Apply the redirects and check every base URL is an absolute URL
+    pub fn build(self) -> Result<Endpoints, String> {
+        let mut endpoints = self.endpoints;
+        for url in endpoints.base_urls_mut() {
+            let host = url::Url::parse(url)
+                .map_err(|e| format!("Invalid base URL {}: {}", url, e))?
+                .host_str()
+                .map(String::from);
+            if let Some((_, base_url)) = self
+                .redirects
+                .iter()
+                .find(|(from, _)| host.as_deref() == Some(from.as_str()))
+            {
+                *url = format!("{}{}", base_url.trim_end_matches('/'), url_path(url));
+                url::Url::parse(url).map_err(|e| format!("Invalid base URL {}: {}", url, e))?;
+            }
+        }
+        Ok(endpoints)
+    }
+}
+
+/// Builds a URL from a base, fixed path pieces, encoded path segments and
+/// encoded query parameters
+#[derive(Debug, Clone)]
+pub struct UrlBuilder {
+    url: String,
+    has_query: bool,
+}
+
+impl UrlBuilder {
+    pub fn new(base: &str) -> Self {
+        Self {
+            url: base.trim_end_matches('/').to_string(),
+            has_query: false,
+        }
+    }
+
+    /// Append a fixed path (starting with `/`), which is not encoded
+    pub fn path(mut self, path: &str) -> Self {
+        self.url.push_str(path);
+        self
+    }
+
+    /// Append one path segment, percent-encoding it
+    pub fn segment(mut self, segment: impl std::fmt::Display) -> Self {
+        self.url.push('/');
+        self.url.push_str(&encode_component(&segment.to_string()));
+        self
+    }
+
+    /// Append a query parameter, percent-encoding the value
+    pub fn query(mut self, key: &str, value: impl std::fmt::Display) -> Self {
+        self.url.push(if self.has_query { '&' } else { '?' });
+        self.has_query = true;
+        self.url.push_str(key);
+        self.url.push('=');
+        self.url.push_str(&encode_component(&value.to_string()));
+        self
+    }
+
+    /// Append a query parameter only when a value is present
+    pub fn query_opt(self, key: &str, value: Option<impl std::fmt::Display>) -> Self {
+        match value {
+            Some(value) => self.query(key, value),
+            None => self,
+        }
+    }
+
+    pub fn build(self) -> String {
+        self.url
+    }
+}
+
+/// Percent-encode everything except unreserved characters and commas (used
+/// unescaped in ID lists)
+fn encode_component(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b',' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_endpoint_url() {
+        let e = Endpoints::default();
+        let cases = vec![
+            (e.account("12345"), "https://tradeapi.webullbroker.com/api/trade/v3/home/12345"),
+            (e.account_id(), "https://tradeapi.webullbroker.com/api/trade/account/getSecAccountList/v5"),
+            (e.account_activities("1"), "https://ustrade.webullfinance.com/api/trade/v2/funds/1/activities"),
+            (e.active_gainers_losers("gainer", 6, "1d", 5), "https://quotes-gw.webullfintech.com/api/wlas/ranking/topGainers?regionId=6&rankType=1d&pageIndex=1&pageSize=5"),
+            (e.active_gainers_losers("loser", 6, "1d", 5), "https://quotes-gw.webullfintech.com/api/wlas/ranking/dropGainers?regionId=6&rankType=1d&pageIndex=1&pageSize=5"),
+            (e.active_gainers_losers("active", 6, "1d", 5), "https://quotes-gw.webullfintech.com/api/wlas/ranking/topActive?regionId=6&rankType=1d&pageIndex=1&pageSize=5"),
+            (e.top_options("volume", 6, 5), "https://quotes-gw.webullfintech.com/api/wlas/option/rank/list?regionId=6&rankType=volume&pageIndex=1&pageSize=5"),
+            (e.add_alert(), "https://userapi.webullbroker.com/api/user/warning/v2/manage/overlap"),
+            (e.remove_alert(), "https://userapi.webullbroker.com/api/user/warning/v2/manage/overlap"),
+            (e.list_alerts(), "https://userapi.webullbroker.com/api/user/warning/v2/query/tickers"),
+            (e.analysis("913256135"), "https://securitiesapi.webullbroker.com/api/securities/ticker/v5/analysis/913256135"),
+            (e.analysis_shortinterest("913256135"), "https://securitiesapi.webullbroker.com/api/securities/stock/913256135/shortInterest"),
+            (e.analysis_institutional_holding("913256135"), "https://securitiesapi.webullbroker.com/api/securities/stock/v5/913256135/institutionalHolding"),
+            (e.analysis_etf_holding("913256135", 1, 20), "https://securitiesapi.webullbroker.com/api/securities/stock/v5/913256135/belongEtf?hasNum=1&pageSize=20"),
+            (e.analysis_capital_flow("913256135", true), "https://securitiesapi.webullbroker.com/api/wlas/capitalflow/ticker?tickerId=913256135&showHis=true"),
+            (e.bars("913256135", "m1", 5, Some(1700000000)), "https://quotes-gw.webullfintech.com/api/quote/charts/query?tickerIds=913256135&type=m1&count=5&timestamp=1700000000"),
+            (e.bars("913256135", "d1", 5, None), "https://quotes-gw.webullfintech.com/api/quote/charts/query?tickerIds=913256135&type=d1&count=5"),
+            (e.ticker_minute_volume("913256135", 2), "https://quotes-gw.webullfintech.com/api/quote/ticker/minuteVolume?tickerId=913256135&days=2&interval=30"),
+            (e.trade_ticks("913256135", 50), "https://quotes-gw.webullfintech.com/api/stock/tradeTick/getTradeTick?tickerId=913256135&count=50"),
+            (e.captcha_query(), "https://u1suser.webullfintech.com/api/user/v1/captcha/sliding/query"),
+            (e.captcha_verify(), "https://u1suser.webullfintech.com/api/user/v1/captcha/sliding/verify"),
+            (e.ipo_calendar(6), "https://quotes-gw.webullfintech.com/api/bgw/ipo/listIpo?regionId=6"),
+            (e.etf_profile("913243251"), "https://quotes-gw.webullfintech.com/api/information/etf/profile?tickerId=913243251"),
+            (e.etf_holdings("913243251"), "https://quotes-gw.webullfintech.com/api/information/etf/holdings?tickerId=913243251"),
+            (e.bars_crypto("950160802"), "https://quotes-gw.webullfintech.com/api/crypto/charts/query?tickerIds=950160802"),
+            (e.drip_settings("12345"), "https://ustrade.webullfinance.com/api/trade/v2/account/12345/drip"),
+            (e.cancel_order("12345", "678", "abc"), "https://ustrade.webullfinance.com/api/trade/order/12345/cancelStockOrder/678/abc"),
+            (e.modify_otoco_orders("12345"), "https://ustrade.webullfinance.com/api/trade/v2/corder/stock/modify/12345"),
+            (e.cancel_otoco_orders("12345", "c1"), "https://ustrade.webullfinance.com/api/trade/v2/corder/stock/cancel/12345/c1"),
+            (e.check_order("12345"), "https://ustrade.webullfinance.com/api/trade/v2/order/stock/check/12345"),
+            (e.check_otoco_orders("12345"), "https://ustrade.webullfinance.com/api/trade/v2/corder/stock/check/12345"),
+            (e.place_otoco_orders("12345"), "https://ustrade.webullfinance.com/api/trade/v2/corder/stock/place/12345"),
+            (e.dividends("12345"), "https://tradeapi.webullbroker.com/api/trade/v2/account/12345/dividends?direct=in"),
+            (e.financials("913256135", "incomestatement", 101, "1,2,3,4", 8), "https://quotes-gw.webullfintech.com/api/information/financial/incomestatement?tickerId=913256135&type=101&fiscalPeriod=1,2,3,4&limit=8"),
+            (e.fundamentals("913256135"), "https://securitiesapi.webullbroker.com/api/securities/financial/index/913256135"),
+            (e.ticker_earnings("913256135"), "https://quotes-gw.webullfintech.com/api/information/financial/earnings?tickerId=913256135"),
+            (e.is_tradable("913256135"), "https://tradeapi.webullbroker.com/api/trade/ticker/broker/permissionV2?tickerId=913256135"),
+            (e.login(), "https://u1suser.webullfintech.com/api/user/v1/login/account/v2"),
+            (e.get_mfa(), "https://userapi.webull.com/api/user/v1/verificationCode/send/v2"),
+            (e.check_mfa(), "https://u1suser.webullfintech.com/api/user/v1/verificationCode/checkCode"),
+            (e.get_security("user@example.com", 2, 6, "PRODUCT_LOGIN", 1700000000, 0), "https://userapi.webull.com/api/user/risk/getSecurityQuestion?account=user%40example.com&accountType=2&regionId=6&event=PRODUCT_LOGIN&v=1700000000"),
+            (e.get_security("user@example.com", 2, 6, "PRODUCT_LOGIN", 1700000000, 1), "https://userapi.webull.com/api/user/risk/getPrivacyQuestion?account=user%40example.com&accountType=2&regionId=6&event=PRODUCT_LOGIN&v=1700000000"),
+            (e.next_security("user@example.com", 2, 6, "PRODUCT_LOGIN", 1700000000, 0), "https://userapi.webull.com/api/user/risk/nextSecurityQuestion?account=user%40example.com&accountType=2&regionId=6&event=PRODUCT_LOGIN&v=1700000000"),
+            (e.next_security("user@example.com", 2, 6, "PRODUCT_LOGIN", 1700000000, 1), "https://userapi.webull.com/api/user/risk/nextPrivacyQuestion?account=user%40example.com&accountType=2&regionId=6&event=PRODUCT_LOGIN&v=1700000000"),
+            (e.check_security(), "https://userapi.webull.com/api/user/risk/checkAnswer"),
+            (e.logout(), "https://u1suser.webullfintech.com/api/user/v1/logout"),
+            (e.news("913256135", 0, 20), "https://quotes-gw.webullfintech.com/api/information/news/tickerNews?tickerId=913256135&currentNewsId=0&pageSize=20"),
+            (e.option_quotes(), "https://quotes-gw.webullbroker.com/api/quote/option/query/list"),
+            (e.options("913256135"), "https://quoteapi.webullbroker.com/api/quote/option/913256135/list"),
+            (e.options_exp_date("913256135"), "https://quoteapi.webullbroker.com/api/quote/option/913256135/list"),
+            (e.options_exp_date_new(), "https://quotes-gw.webullfintech.com/api/quote/option/strategy/list"),
+            (e.options_bars("1040000000"), "https://quotes-gw.webullbroker.com/api/quote/option/chart/query?derivativeId=1040000000"),
+            (e.orders("12345", 20), "https://ustrade.webullbroker.com/api/trade/v2/option/list?secAccountId=12345&startTime=1970-01-01&dateType=ORDER&pageSize=20&status="),
+            (e.orders_with_status("12345", 20, "Working", Some(1700000000)), "https://ustrade.webullbroker.com/api/trade/v2/option/list?secAccountId=12345&startTime=1970-01-01&dateType=ORDER&pageSize=20&status=Working&lastCreateTime0=1700000000"),
+            (e.history("12345"), "https://ustrade.webullfinance.com/api/trading/v1/webull/order/list?secAccountId=12345"),
+            (e.paper_orders("777", 20), "https://act.webullbroker.com/webull-paper-center/api/paper/1/acc/777/order?startTime=1970-01-01&dateType=ORDER&pageSize=20&status="),
+            (e.paper_orders_with_status("777", 20, "Filled", Some(1700000000)), "https://act.webullbroker.com/webull-paper-center/api/paper/1/acc/777/order?startTime=1970-01-01&dateType=ORDER&pageSize=20&status=Filled&lastCreateTime0=1700000000"),
+            (e.paper_account("777"), "https://act.webullfintech.com/webull-paper-center/api/paper/1/acc/777"),
+            (e.paper_account_id(), "https://act.webullfintech.com/webull-paper-center/api/myaccounts/true"),
+            (e.paper_cancel_order("777", "o1"), "https://act.webullbroker.com/webull-paper-center/api/paper/1/acc/777/orderop/cancel/o1"),
+            (e.paper_modify_order("777", "o1"), "https://act.webullbroker.com/webull-paper-center/api/paper/1/acc/777/orderop/modify/o1"),
+            (e.paper_place_order("777", "913256135"), "https://act.webullbroker.com/webull-paper-center/api/paper/1/acc/777/orderop/place/913256135"),
+            (e.place_option_orders("12345"), "https://ustrade.webullfinance.com/api/trade/v2/option/placeOrder/12345"),
+            (e.place_orders("12345"), "https://ustrade.webullfinance.com/api/trade/order/12345/placeStockOrder"),
+            (e.modify_order("12345", "o1"), "https://ustrade.webullfinance.com/api/trading/v1/webull/order/stockOrderModify?secAccountId=12345"),
+            (e.quotes("913256135"), "https://quotes-gw.webullbroker.com/api/quotes/ticker/getTickerRealTime?tickerId=913256135&includeSecu=1&includeQuote=1"),
+            (e.quotes_extended("913256135"), "https://quotes-gw.webullfintech.com/api/quotes/ticker/getTickerRealTime?tickerId=913256135&includeSecu=1&includeQuote=1&more=1"),
+            (e.quotes_batch(&["913256135","913243251"]), "https://quotes-gw.webullbroker.com/api/bgw/quote/realtime?ids=913256135,913243251&includeSecu=1&includeQuote=1&more=1"),
+            (e.rankings(), "https://securitiesapi.webullbroker.com/api/securities/market/v5/6/portal"),
+            (e.refresh_login("token"), "https://userapi.webull.com/api/passport/refreshToken?refreshToken=token"),
+            (e.replace_option_orders("12345"), "https://tradeapi.webullbroker.com/api/trade/v2/option/replaceOrder/12345"),
+            (e.stock_detail("913256135"), "https://quotes-gw.webullfintech.com/api/stock/tickerRealTime/getQuote?tickerId=913256135&includeSecu=1&includeQuote=1&more=1"),
+            (e.stock_id("AAPL", 6), "https://quotes-gw.webullbroker.com/api/search/pc/tickers?keyword=AAPL&pageIndex=1&pageSize=20&regionId=6"),
+            (e.ticker_by_identifier("isin", "US0378331005", 6), "https://quotes-gw.webullbroker.com/api/search/pc/tickers/identifier?type=isin&identifier=US0378331005&regionId=6"),
+            (e.trade_token(), "https://trade.webullfintech.com/api/trading/v1/global/trade/login"),
+            (e.user(), "https://userapi.webull.com/api/user"),
+            (e.fx_rate("USD", "CAD", "2024-03-01"), "https://quotes-gw.webullfintech.com/api/market/fx/history?fromCurrency=USD&toCurrency=CAD&date=2024-03-01"),
+            (e.security_events(20), "https://userapi.webull.com/api/user/message/list?msgType=SECURITY&pageSize=20"),
+            (e.screener(), "https://userapi.webullbroker.com/api/wlas/screener/ng/query"),
+            (e.social_posts("trending", 10), "https://userapi.webull.com/api/social/feed/topic/trending/posts?size=10"),
+            (e.social_home("trending", 10), "https://userapi.webull.com/api/social/feed/topic/trending/home?size=10"),
+            (e.portfolio_lists(), "https://quotes-gw.webullbroker.com/api/personal/portfolio/v2/check"),
+            (e.create_watchlist(), "https://quotes-gw.webullbroker.com/api/personal/portfolio/v2/create"),
+            (e.delete_watchlist("w1"), "https://quotes-gw.webullbroker.com/api/personal/portfolio/v2/w1/delete"),
+            (e.add_watchlist_tickers("w1"), "https://quotes-gw.webullbroker.com/api/personal/portfolio/v2/w1/tickers/add"),
+            (e.remove_watchlist_tickers("w1"), "https://quotes-gw.webullbroker.com/api/personal/portfolio/v2/w1/tickers/remove"),
+            (e.press_releases("913256135", Some("141,142"), 10), "https://securitiesapi.webullfintech.com/api/securities/announcement/913256135/list?lastAnnouncementId=0&limit=10&typeIds=141,142&options=2"),
+            (e.press_releases("913256135", None, 10), "https://securitiesapi.webullfintech.com/api/securities/announcement/913256135/list?lastAnnouncementId=0&limit=10&options=2"),
+            (e.calendar_events("earnings", 6, "2024-01-01", 1, 50), "https://quotes-gw.webullfintech.com/api/bgw/explore/calendar/earnings?regionId=6&pageIndex=1&pageSize=50&startDate=2024-01-01"),
+            (e.get_all_tickers(6, 6), "https://securitiesapi.webullfintech.com/api/securities/market/v5/card/stockActivityPc.advanced/list?regionId=6&userRegionId=6&hasNum=0&pageSize=9999"),
+            (e.instrument_list(6, 500, 500), "https://securitiesapi.webullfintech.com/api/securities/market/v5/card/stockActivityPc.advanced/list?regionId=6&userRegionId=6&hasNum=500&pageSize=500"),
+        ];
+        for (url, expected) in cases {
+            assert_eq!(url, expected);
+        }
+
+        assert_eq!(
+            e.quotes_fallbacks("913256135"),
+            vec![
+                "https://quotes-gw.webullfintech.com/api/quotes/ticker/getTickerRealTime?tickerId=913256135&includeSecu=1&includeQuote=1",
+                "https://quotes-gw.webullbroker.com/api/quotes/ticker/getTickerRealTime?tickerId=913256135&includeSecu=1&includeQuote=1",
+                "https://quoteapi.webullbroker.com/api/quotes/ticker/getTickerRealTime?tickerId=913256135&includeSecu=1&includeQuote=1",            ]
+        );
+    }
+
+    #[test]
+    fn test_parameters_are_percent_encoded() {
+        let e = Endpoints::default();
+
+        // Keywords can't add query parameters
+        assert_eq!(
+            e.stock_id("S&P 500", 6),
+            "https://quotes-gw.webullbroker.com/api/search/pc/tickers?keyword=S%26P%20500&pageIndex=1&pageSize=20&regionId=6"
+        );
+        assert!(e
+            .get_security("a+b@example.com", 2, 6, "PRODUCT_LOGIN", 1, 0)
+            .contains("account=a%2Bb%40example.com&"));
+        assert!(e
+            .orders_with_status("12345", 20, "Working&pageSize=1", None)
+            .ends_with("&pageSize=20&status=Working%26pageSize%3D1"));
+        // Path segments can't escape their position
+        assert_eq!(
+            e.delete_watchlist("../w 1"),
+            "https://quotes-gw.webullbroker.com/api/personal/portfolio/v2/..%2Fw%201/delete"
+        );
+        assert!(e
+            .cancel_order("12345", "678/../9", "abc")
+            .ends_with("/cancelStockOrder/678%2F..%2F9/abc"));
+    }
+
+    #[test]
+    fn test_url_builder() {
+        let url = UrlBuilder::new("https://example.com/api/")
+            .path("/items")
+            .segment("a/b")
+            .query("ids", "1,2")
+            .query_opt("after", None::<i64>)
+            .query_opt("limit", Some(5))
+            .build();
+        assert_eq!(url, "https://example.com/api/items/a%2Fb?ids=1,2&limit=5");
     }
 }
@@ -1,3 +1,6 @@
+use crate::models::LoginChallenge;
+use crate::rejections::{RejectionCatalogue, RejectionReason};
+use serde_json::Value;
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, WebullError>;
@@ -22,6 +25,9 @@ pub enum WebullError {
     #[error("MFA required")]
     MfaRequired,
 
+    #[error("Captcha required: {}", .0.message)]
+    CaptchaRequired(LoginChallenge),
+
     #[error("Invalid MFA code")]
     InvalidMfaCode,
 
@@ -40,15 +46,31 @@ pub enum WebullError {
     #[error("API error: {0}")]
     ApiError(String),
 
+    #[error("API error {code}: {message}")]
+    ApiErrorCode { code: String, message: String },
+
+    #[error("Invalid trade PIN{}", .remaining_attempts.map(|n| format!(" ({} attempts remaining)", n)).unwrap_or_default())]
+    InvalidTradePin { remaining_attempts: Option<u32> },
+
     #[error("Trade token not available")]
     TradeTokenNotAvailable,
 
     #[error("Account not found")]
     AccountNotFound,
 
+    #[error("Account {0} is a managed Webull Advisors account and is read-only")]
+    ManagedAccount(String),
+
     #[error("Parse error: {0}")]
     ParseError(String),
 
+    #[error("Order rejected ({reason}): {message}")]
+    OrderRejected {
+        reason: RejectionReason,
+        code: String,
+        message: String,
+    },
+
     #[error("Order not found")]
     OrderNotFound,
 
@@ -58,6 +80,15 @@ pub enum WebullError {
     #[error("Market closed")]
     MarketClosed,
 
+    #[error("Deadline exceeded")]
+    DeadlineExceeded,
+
+    #[error("Circuit open for {0} endpoints")]
+    CircuitOpen(String),
+
+    #[error("Price {price} outside allowed range {lower} - {upper}")]
+    PriceOutOfBand { price: f64, lower: f64, upper: f64 },
+
     #[error("Symbol not found: {0}")]
     SymbolNotFound(String),
 
@@ -76,6 +107,167 @@ pub enum WebullError {
     #[error("Device ID error: {0}")]
     DeviceIdError(String),
 
+    #[error("Database error: {0}")]
+    DatabaseError(String),
+
     #[error("Unknown error: {0}")]
     Unknown(String),
 }
+
+// Kept as text so the enum has the same variants with or without the
+// `sql-sink` feature
+#[cfg(feature = "sql-sink")]
+impl From<sqlx::Error> for WebullError {
+    fn from(e: sqlx::Error) -> Self {
+        WebullError::DatabaseError(e.to_string())
+    }
+}
+
+impl WebullError {
+    /// Map a Webull error body (`code`, `msg`) to a typed error
+    ///
+    /// Returns `None` when the body does not describe a failure. Bodies
+    /// without a `code` are only treated as errors for non-2xx statuses.
+    /// Rejection codes are decoded with the process-wide catalogue.
+    pub fn from_api_body(status: u16, body: &Value) -> Option<Self> {
+        Self::from_api_body_with(status, body, &RejectionCatalogue::global())
+    }
+
+    /// `from_api_body`, decoding rejection codes with `catalogue`
+    pub fn from_api_body_with(
+        status: u16,
+        body: &Value,
+        catalogue: &RejectionCatalogue,
+    ) -> Option<Self> {
+        let text = |key: &str| match body.get(key) {
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        };
+        let code = text("code").or_else(|| text("errorCode"));
+        let message = text("msg").or_else(|| text("message")).unwrap_or_default();
+        let http_error = !(200..300).contains(&status);
+        let explicit_failure = body.get("success").and_then(|v| v.as_bool()) == Some(false);
+
+        // Some endpoints report success as code "200" alongside success: false
+        let code = match code {
+            Some(code) if matches!(code.as_str(), "200" | "0" | "ok" | "OK") => return None,
+            Some(code) => code,
+            None if http_error => status.to_string(),
+            None => return None,
+        };
+        // A 2xx body is only an error if it says so or carries no payload
+        let success = body.get("success").and_then(|v| v.as_bool()) == Some(true);
+        if !http_error
+            && !explicit_failure
+            && (success || message.is_empty() || body.get("data").is_some())
+        {
+            return None;
+        }
+
+        Some(Self::from_api_code(&code, message, body, catalogue))
+    }
+
+    fn from_api_code(
+        code: &str,
+        message: String,
+        body: &Value,
+        catalogue: &RejectionCatalogue,
+    ) -> Self {
+        let lower = code.to_ascii_lowercase();
+        if lower == "429" || lower.contains("too.many") || lower.contains("rate.limit") {
+            WebullError::RateLimitExceeded
+        } else if lower.contains("pwd.invalid") && lower.starts_with("trade") {
+            let remaining_attempts = ["remainTimes", "remainingTimes", "leftTimes"]
+                .iter()
+                .find_map(|key| {
+                    let value = body.get("data").unwrap_or(body).get(*key)?;
+                    value
+                        .as_u64()
+                        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+                })
+                .map(|n| n as u32);
+            WebullError::InvalidTradePin { remaining_attempts }
+        } else if lower.contains("token.expire")
+            || lower.contains("token.invalid")
+            || lower == "401"
+        {
+            WebullError::SessionExpired
+        } else if LoginChallenge::is_challenge_code(code) {
+            WebullError::CaptchaRequired(LoginChallenge::from_body(code, message, body))
+        } else if lower.contains("pwd") || lower.contains("phone.illegal") {
+            WebullError::InvalidCredentials
+        } else if lower.contains("insufficient") {
+            WebullError::InsufficientFunds
+        } else if lower.contains("market_closed") || lower.contains("market.closed") {
+            WebullError::MarketClosed
+        } else if lower.contains("order.not.exist") || lower.contains("order_not_exist") {
+            WebullError::OrderNotFound
+        } else if let Some(reason) = catalogue.lookup(code) {
+            WebullError::OrderRejected {
+                reason,
+                code: code.to_string(),
+                message,
+            }
+        } else {
+            WebullError::ApiErrorCode {
+                code: code.to_string(),
+                message,
+            }
+        }
+    }
+
+    /// Variant name, for grouping errors in logs and metrics
+    pub fn kind(&self) -> &'static str {
+        match self {
+            WebullError::RequestError(_) => "RequestError",
+            WebullError::JsonError(_) => "JsonError",
+            WebullError::IoError(_) => "IoError",
+            WebullError::AuthenticationError(_) => "AuthenticationError",
+            WebullError::InvalidCredentials => "InvalidCredentials",
+            WebullError::MfaRequired => "MfaRequired",
+            WebullError::CaptchaRequired(_) => "CaptchaRequired",
+            WebullError::InvalidMfaCode => "InvalidMfaCode",
+            WebullError::SessionExpired => "SessionExpired",
+            WebullError::RateLimitExceeded => "RateLimitExceeded",
+            WebullError::InvalidParameter(_) => "InvalidParameter",
+            WebullError::InvalidRequest(_) => "InvalidRequest",
+            WebullError::ApiError(_) => "ApiError",
+            WebullError::ApiErrorCode { .. } => "ApiErrorCode",
+            WebullError::InvalidTradePin { .. } => "InvalidTradePin",
+            WebullError::TradeTokenNotAvailable => "TradeTokenNotAvailable",
+            WebullError::AccountNotFound => "AccountNotFound",
+            WebullError::ManagedAccount(_) => "ManagedAccount",
+            WebullError::ParseError(_) => "ParseError",
+            WebullError::OrderRejected { .. } => "OrderRejected",
+            WebullError::OrderNotFound => "OrderNotFound",
+            WebullError::InsufficientFunds => "InsufficientFunds",
+            WebullError::MarketClosed => "MarketClosed",
+            WebullError::DeadlineExceeded => "DeadlineExceeded",
+            WebullError::CircuitOpen(_) => "CircuitOpen",
+            WebullError::PriceOutOfBand { .. } => "PriceOutOfBand",
+            WebullError::SymbolNotFound(_) => "SymbolNotFound",
+            WebullError::TickerNotFound(_) => "TickerNotFound",
+            WebullError::WebSocketError(_) => "WebSocketError",
+            WebullError::MqttError(_) => "MqttError",
+            WebullError::SerializationError(_) => "SerializationError",
+            WebullError::DeviceIdError(_) => "DeviceIdError",
+            WebullError::DatabaseError(_) => "DatabaseError",
+            WebullError::Unknown(_) => "Unknown",
+        }
+    }
+
+    /// Why an order was rejected, for errors that describe a rejection
+    ///
+    /// Covers the older typed errors for funds, market hours and price bands
+    /// as well as `OrderRejected`.
+    pub fn rejection_reason(&self) -> Option<RejectionReason> {
+        match self {
+            WebullError::OrderRejected { reason, .. } => Some(*reason),
+            WebullError::InsufficientFunds => Some(RejectionReason::InsufficientBuyingPower),
+            WebullError::MarketClosed => Some(RejectionReason::MarketClosed),
+            WebullError::PriceOutOfBand { .. } => Some(RejectionReason::PriceTooFarFromMarket),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,386 @@
+// One sequenced feed of order pushes, position changes and inbox messages
+
+use crate::{
+    client::WebullClient,
+    error::Result,
+    models::{parse_order_status, OrderStatus, Position, SecurityEvent},
+    monitor::SecurityMonitor,
+};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, BoxStream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+/// Recent event keys remembered for de-duplication
+const DEDUP_WINDOW: usize = 1024;
+
+/// An order update pushed by the stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderUpdate {
+    pub order_id: String,
+    pub status: OrderStatus,
+    pub filled_quantity: Option<f64>,
+    /// The push payload as received
+    pub raw: Value,
+}
+
+/// A position that opened, closed or changed size between two polls
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionChange {
+    pub symbol: String,
+    pub ticker_id: Option<i64>,
+    /// Zero for a newly opened position
+    pub previous_quantity: f64,
+    /// Zero for a closed position
+    pub quantity: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AccountEventKind {
+    Order(OrderUpdate),
+    Position(PositionChange),
+    /// A message from the account's notification inbox
+    Notification(SecurityEvent),
+}
+
+/// One entry in the account event feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountEvent {
+    /// Increases by one per event; store it to resume numbering after a
+    /// restart with `AccountEvents::resume_after`
+    pub sequence: u64,
+    pub at: DateTime<Utc>,
+    pub kind: AccountEventKind,
+}
+
+/// Sources, state and numbering for `WebullClient::account_events`
+///
+/// Positions and the inbox are polled; order updates arrive through the
+/// callback from `order_push_callback`, installed on a `StreamConn`
+/// subscribed to the account's orders. The first poll only records the
+/// current positions and inbox, so the feed starts with what changes after
+/// it opens. Events missed while the feed was closed are not replayed.
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use std::time::Duration;
+/// use webull_unofficial::{events::AccountEvents, StreamConn, WebullClient};
+///
+/// # async fn run(client: WebullClient, mut stream: StreamConn) {
+/// let mut feed = AccountEvents::new(Duration::from_secs(30)).resume_after(41);
+/// stream.set_order_callback(feed.order_push_callback());
+///
+/// let mut events = client.account_events(feed);
+/// while let Some(Ok(event)) = events.next().await {
+///     println!("#{} {:?}", event.sequence, event.kind);
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AccountEvents {
+    poll: Duration,
+    sequence: u64,
+    pushes: Option<mpsc::UnboundedReceiver<Value>>,
+    positions: Option<BTreeMap<String, (Option<i64>, f64)>>,
+    inbox: SecurityMonitor,
+    seen: HashSet<String>,
+    seen_order: VecDeque<String>,
+}
+
+impl AccountEvents {
+    /// Poll positions and the inbox every `poll`
+    pub fn new(poll: Duration) -> Self {
+        Self {
+            poll,
+            sequence: 0,
+            pushes: None,
+            positions: None,
+            inbox: SecurityMonitor::new(),
+            seen: HashSet::new(),
+            seen_order: VecDeque::new(),
+        }
+    }
+
+    /// Number events from `sequence + 1`
+    pub fn resume_after(mut self, sequence: u64) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
+    /// Callback for `StreamConn::set_order_callback` feeding order pushes
+    /// into this feed; calling it again replaces the previous channel
+    pub fn order_push_callback(&mut self) -> impl Fn(Value, Value) + Send + Sync + 'static {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.pushes = Some(receiver);
+        move |_topic, data| {
+            let _ = sender.send(data);
+        }
+    }
+
+    /// Last sequence number handed out
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    fn first_sighting(&mut self, key: String) -> bool {
+        if !self.seen.insert(key.clone()) {
+            return false;
+        }
+        self.seen_order.push_back(key);
+        if self.seen_order.len() > DEDUP_WINDOW {
+            if let Some(oldest) = self.seen_order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        true
+    }
+
+    fn event(&mut self, at: DateTime<Utc>, kind: AccountEventKind) -> AccountEvent {
+        self.sequence += 1;
+        AccountEvent {
+            sequence: self.sequence,
+            at,
+            kind,
+        }
+    }
+
+    /// Turn an order push into an event, unless it repeats a recent one
+    pub fn absorb_push(&mut self, data: Value, at: DateTime<Utc>) -> Option<AccountEvent> {
+        let order_id = match data.get("orderId") {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Number(n)) => n.to_string(),
+            _ => return None,
+        };
+        let status = ["statusCode", "status", "orderStatus"]
+            .iter()
+            .find_map(|key| data.get(*key).and_then(|v| v.as_str()))
+            .map(parse_order_status)?;
+        let filled_quantity =
+            ["filledQuantity", "filledQty"]
+                .iter()
+                .find_map(|key| match data.get(*key) {
+                    Some(Value::String(s)) => s.parse::<f64>().ok(),
+                    Some(Value::Number(n)) => n.as_f64(),
+                    _ => None,
+                });
+
+        let key = format!("order:{}:{:?}:{:?}", order_id, status, filled_quantity);
+        if !self.first_sighting(key) {
+            return None;
+        }
+        Some(self.event(
+            at,
+            AccountEventKind::Order(OrderUpdate {
+                order_id,
+                status,
+                filled_quantity,
+                raw: data,
+            }),
+        ))
+    }
+
+    /// Diff polled positions against the previous poll
+    pub fn absorb_positions(
+        &mut self,
+        positions: &[Position],
+        at: DateTime<Utc>,
+    ) -> Vec<AccountEvent> {
+        let current: BTreeMap<String, (Option<i64>, f64)> = positions
+            .iter()
+            .filter_map(|position| {
+                let ticker = position.ticker.as_ref()?;
+                Some((
+                    ticker.symbol.clone(),
+                    (Some(ticker.ticker_id), position.quantity),
+                ))
+            })
+            .collect();
+        let previous = match self.positions.replace(current.clone()) {
+            Some(previous) => previous,
+            None => return Vec::new(),
+        };
+
+        let mut changes = Vec::new();
+        for (symbol, (ticker_id, quantity)) in &current {
+            let before = previous.get(symbol).map_or(0.0, |(_, quantity)| *quantity);
+            if before != *quantity {
+                changes.push(PositionChange {
+                    symbol: symbol.clone(),
+                    ticker_id: *ticker_id,
+                    previous_quantity: before,
+                    quantity: *quantity,
+                });
+            }
+        }
+        for (symbol, (ticker_id, quantity)) in &previous {
+            if !current.contains_key(symbol) {
+                changes.push(PositionChange {
+                    symbol: symbol.clone(),
+                    ticker_id: *ticker_id,
+                    previous_quantity: *quantity,
+                    quantity: 0.0,
+                });
+            }
+        }
+        changes
+            .into_iter()
+            .map(|change| self.event(at, AccountEventKind::Position(change)))
+            .collect()
+    }
+
+    /// Turn new inbox messages into events, oldest first
+    pub fn absorb_notifications(
+        &mut self,
+        messages: Vec<SecurityEvent>,
+        at: DateTime<Utc>,
+    ) -> Vec<AccountEvent> {
+        let mut fresh: Vec<(DateTime<Utc>, SecurityEvent)> = self
+            .inbox
+            .observe(messages)
+            .into_iter()
+            .map(|message| {
+                let occurred = message
+                    .occurred_at
+                    .as_deref()
+                    .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+                    .map_or(at, |time| time.with_timezone(&Utc));
+                (occurred, message)
+            })
+            .collect();
+        fresh.sort_by_key(|(occurred, _)| *occurred);
+
+        let mut events = Vec::new();
+        for (occurred, message) in fresh {
+            if self.first_sighting(format!("inbox:{}", message.id)) {
+                events.push(self.event(occurred, AccountEventKind::Notification(message)));
+            }
+        }
+        events
+    }
+}
+
+impl WebullClient {
+    /// Merge order pushes, position changes and inbox messages into one
+    /// sequenced feed
+    ///
+    /// Poll failures are yielded as errors and polling continues. See
+    /// `AccountEvents` for how the sources are wired up.
+    pub fn account_events(&self, events: AccountEvents) -> BoxStream<'_, Result<AccountEvent>> {
+        let state = (events, VecDeque::new(), None::<Instant>);
+        stream::unfold(
+            state,
+            move |(mut events, mut buffer, mut next_poll)| async move {
+                loop {
+                    if let Some(event) = buffer.pop_front() {
+                        return Some((Ok(event), (events, buffer, next_poll)));
+                    }
+
+                    // Wait for a push or the next poll, whichever comes first
+                    if let Some(deadline) = next_poll {
+                        let push = match events.pushes.as_mut() {
+                            Some(pushes) => tokio::select! {
+                                push = pushes.recv() => Some(push),
+                                _ = tokio::time::sleep_until(deadline) => None,
+                            },
+                            None => {
+                                tokio::time::sleep_until(deadline).await;
+                                None
+                            }
+                        };
+                        match push {
+                            Some(Some(data)) => {
+                                buffer.extend(events.absorb_push(data, Utc::now()));
+                                continue;
+                            }
+                            // The stream side went away; keep polling
+                            Some(None) => {
+                                events.pushes = None;
+                                continue;
+                            }
+                            None => {}
+                        }
+                    }
+                    next_poll = Some(Instant::now() + events.poll);
+
+                    let positions = match self.get_positions().await {
+                        Ok(positions) => positions,
+                        Err(e) => return Some((Err(e), (events, buffer, next_poll))),
+                    };
+                    let messages = match self.get_security_events(50).await {
+                        Ok(messages) => messages,
+                        Err(e) => return Some((Err(e), (events, buffer, next_poll))),
+                    };
+                    let now = Utc::now();
+                    buffer.extend(events.absorb_notifications(messages, now));
+                    buffer.extend(events.absorb_positions(&positions, now));
+                }
+            },
+        )
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn position(symbol: &str, ticker_id: i64, quantity: f64) -> Position {
+        serde_json::from_value(json!({
+            "ticker": {"tickerId": ticker_id, "disSymbol": symbol, "name": symbol},
+            "position": quantity.to_string(),
+            "costPrice": "10", "cost": "10", "marketValue": "10", "lastPrice": "10"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sequenced_and_deduplicated() {
+        let mut events = AccountEvents::new(Duration::from_secs(30)).resume_after(41);
+        let now = Utc::now();
+
+        // The first poll only seeds
+        assert!(events
+            .absorb_positions(&[position("AAPL", 913256135, 10.0)], now)
+            .is_empty());
+
+        let push = json!({"orderId": 77, "statusCode": "Filled", "filledQuantity": "5"});
+        let filled = events.absorb_push(push.clone(), now).unwrap();
+        assert_eq!(filled.sequence, 42);
+        assert!(events.absorb_push(push, now).is_none());
+        assert!(events.absorb_push(json!({"orderId": 77}), now).is_none());
+
+        let changes = events.absorb_positions(
+            &[
+                position("AAPL", 913256135, 15.0),
+                position("MSFT", 913323997, 3.0),
+            ],
+            now,
+        );
+        let sequences: Vec<u64> = changes.iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, vec![43, 44]);
+        match &changes[0].kind {
+            AccountEventKind::Position(change) => {
+                assert_eq!(change.symbol, "AAPL");
+                assert_eq!(change.previous_quantity, 10.0);
+                assert_eq!(change.quantity, 15.0);
+            }
+            other => panic!("expected a position change, got {:?}", other),
+        }
+
+        let closed = events.absorb_positions(&[position("MSFT", 913323997, 3.0)], now);
+        assert_eq!(closed.len(), 1);
+        match &closed[0].kind {
+            AccountEventKind::Position(change) => assert_eq!(change.quantity, 0.0),
+            other => panic!("expected a position change, got {:?}", other),
+        }
+        assert_eq!(events.sequence(), 45);
+    }
+}
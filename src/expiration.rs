@@ -0,0 +1,325 @@
+// Expiration-day checks for option positions approaching the close
+
+use crate::models::{OptionContract, OrderAction, OrderType, TimeInForce};
+use crate::options::{round_to_tick, OptionStrategy, PlaceOptionSpreadRequest, SpreadLeg};
+use chrono::{NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+/// Where an expiring contract's strike sits relative to the underlying
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Moneyness {
+    /// In the money by more than the near-the-money band; likely to be
+    /// exercised or assigned
+    InTheMoney,
+    /// Strike within the near-the-money band, on either side (pin risk)
+    NearTheMoney,
+    OutOfTheMoney,
+}
+
+/// What the sweep does about a contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExpirationAction {
+    Ignore,
+    Warn,
+    /// Warn and generate an order closing the position
+    Close,
+}
+
+/// When and how the expiration sweep reacts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpirationPolicy {
+    /// Market close in exchange-local time (default 16:00)
+    pub market_close: NaiveTime,
+    /// How long before the close the sweep starts reporting (default 60 minutes)
+    pub window_minutes: i64,
+    /// Distance from the strike, as a fraction of the underlying price,
+    /// that counts as near the money (default 0.02)
+    pub near_the_money: f64,
+    pub in_the_money_action: ExpirationAction,
+    pub near_the_money_action: ExpirationAction,
+    /// Close with a limit order at the bid/ask midpoint, rounded to the
+    /// option tick, when both are quoted, instead of a market order
+    pub limit_at_mid: bool,
+}
+
+impl Default for ExpirationPolicy {
+    fn default() -> Self {
+        Self {
+            market_close: NaiveTime::from_hms_opt(16, 0, 0).unwrap_or_default(),
+            window_minutes: 60,
+            near_the_money: 0.02,
+            in_the_money_action: ExpirationAction::Close,
+            near_the_money_action: ExpirationAction::Warn,
+            limit_at_mid: true,
+        }
+    }
+}
+
+impl ExpirationPolicy {
+    pub fn window_minutes(mut self, minutes: i64) -> Self {
+        self.window_minutes = minutes.max(0);
+        self
+    }
+
+    pub fn near_the_money(mut self, fraction: f64) -> Self {
+        self.near_the_money = fraction.abs();
+        self
+    }
+
+    pub fn in_the_money_action(mut self, action: ExpirationAction) -> Self {
+        self.in_the_money_action = action;
+        self
+    }
+
+    pub fn near_the_money_action(mut self, action: ExpirationAction) -> Self {
+        self.near_the_money_action = action;
+        self
+    }
+
+    pub fn limit_at_mid(mut self, enabled: bool) -> Self {
+        self.limit_at_mid = enabled;
+        self
+    }
+}
+
+/// An option position to check
+#[derive(Debug, Clone)]
+pub struct ExpiringPosition {
+    pub contract: OptionContract,
+    /// Contracts held (negative when short)
+    pub quantity: f64,
+    pub underlying_price: f64,
+}
+
+/// A contract the sweep flagged
+#[derive(Debug, Clone)]
+pub struct ExpirationAlert {
+    pub symbol: String,
+    pub ticker_id: i64,
+    pub quantity: f64,
+    pub moneyness: Moneyness,
+    /// Intrinsic value per share as a fraction of the underlying price;
+    /// negative when out of the money
+    pub moneyness_pct: f64,
+    pub minutes_to_close: i64,
+    pub action: ExpirationAction,
+    /// Single-leg option order closing the position, for
+    /// `place_option_spread`, when the action is `Close`
+    pub order: Option<PlaceOptionSpreadRequest>,
+}
+
+/// Scans option positions on their expiration day
+#[derive(Debug, Clone, Default)]
+pub struct ExpirationSweep {
+    policy: ExpirationPolicy,
+}
+
+impl ExpirationSweep {
+    pub fn new(policy: ExpirationPolicy) -> Self {
+        Self { policy }
+    }
+
+    pub fn policy(&self) -> &ExpirationPolicy {
+        &self.policy
+    }
+
+    /// Classify a contract against the underlying price
+    pub fn moneyness(&self, contract: &OptionContract, underlying_price: f64) -> (Moneyness, f64) {
+        let intrinsic = if contract.option_type.eq_ignore_ascii_case("put") {
+            contract.strike_price - underlying_price
+        } else {
+            underlying_price - contract.strike_price
+        };
+        let pct = if underlying_price > 0.0 {
+            intrinsic / underlying_price
+        } else {
+            0.0
+        };
+        let moneyness = if pct.abs() <= self.policy.near_the_money {
+            Moneyness::NearTheMoney
+        } else if pct > 0.0 {
+            Moneyness::InTheMoney
+        } else {
+            Moneyness::OutOfTheMoney
+        };
+        (moneyness, pct)
+    }
+
+    /// Check positions at `now`, given in exchange-local time
+    ///
+    /// Only contracts expiring on `now`'s date are considered, and only
+    /// within the policy's window before the close. Out-of-the-money
+    /// contracts and actions set to `Ignore` produce no alert.
+    pub fn sweep(
+        &self,
+        positions: &[ExpiringPosition],
+        now: NaiveDateTime,
+    ) -> Vec<ExpirationAlert> {
+        let close = now.date().and_time(self.policy.market_close);
+        let minutes_to_close = (close - now).num_minutes();
+        if !(0..=self.policy.window_minutes).contains(&minutes_to_close) {
+            return Vec::new();
+        }
+        let today = now.date().format("%Y-%m-%d").to_string();
+
+        positions
+            .iter()
+            .filter(|p| p.quantity != 0.0 && p.contract.expiration_date.starts_with(&today))
+            .filter_map(|position| {
+                let (moneyness, moneyness_pct) =
+                    self.moneyness(&position.contract, position.underlying_price);
+                let action = match moneyness {
+                    Moneyness::InTheMoney => self.policy.in_the_money_action,
+                    Moneyness::NearTheMoney => self.policy.near_the_money_action,
+                    Moneyness::OutOfTheMoney => ExpirationAction::Ignore,
+                };
+                if action == ExpirationAction::Ignore {
+                    return None;
+                }
+                let order = (action == ExpirationAction::Close)
+                    .then(|| self.closing_order(position))
+                    .flatten();
+                log::warn!(
+                    "{} expires today {:?} with {} minutes to the close: {:?}",
+                    position.contract.symbol,
+                    moneyness,
+                    minutes_to_close,
+                    action
+                );
+                Some(ExpirationAlert {
+                    symbol: position.contract.symbol.clone(),
+                    ticker_id: position.contract.ticker_id,
+                    quantity: position.quantity,
+                    moneyness,
+                    moneyness_pct,
+                    minutes_to_close,
+                    action,
+                    order,
+                })
+            })
+            .collect()
+    }
+
+    fn closing_order(&self, position: &ExpiringPosition) -> Option<PlaceOptionSpreadRequest> {
+        let action = if position.quantity > 0.0 {
+            OrderAction::Sell
+        } else {
+            OrderAction::Buy
+        };
+        let contracts = position.quantity.abs().round() as u32;
+        if contracts == 0 {
+            return None;
+        }
+        let mid = match (position.contract.bid, position.contract.ask) {
+            (Some(bid), Some(ask)) if bid > 0.0 && ask > 0.0 => {
+                Some(round_to_tick((bid + ask) / 2.0))
+            }
+            _ => None,
+        };
+        let (order_type, limit_price) = match mid {
+            Some(mid) if self.policy.limit_at_mid && mid > 0.0 => (OrderType::Limit, Some(mid)),
+            _ => (OrderType::Market, None),
+        };
+        Some(PlaceOptionSpreadRequest {
+            strategy: OptionStrategy::Single,
+            legs: vec![SpreadLeg::option(&position.contract, action.clone())],
+            quantity: contracts,
+            order_type,
+            limit_price,
+            credit: action == OrderAction::Sell,
+            time_in_force: TimeInForce::Day,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use serde_json::json;
+
+    fn position(kind: &str, strike: f64, quantity: f64, expiration: &str) -> ExpiringPosition {
+        let contract = serde_json::from_value(json!({
+            "tickerId": strike as i64,
+            "symbol": format!("SPY {} {}", kind, strike),
+            "strikePrice": strike,
+            "expirationDate": expiration,
+            "optionType": kind,
+            "bid": 1.00,
+            "ask": 1.10
+        }))
+        .unwrap();
+        ExpiringPosition {
+            contract,
+            quantity,
+            underlying_price: 500.0,
+        }
+    }
+
+    fn at(hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 6, 21)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_sweep_flags_itm_and_near_contracts_before_close() {
+        let positions = vec![
+            position("CALL", 480.0, 2.0, "2024-06-21"),
+            position("PUT", 505.0, -1.0, "2024-06-21"),
+            position("PUT", 450.0, 1.0, "2024-06-21"),
+            position("CALL", 480.0, 1.0, "2024-06-28"),
+        ];
+        let sweep = ExpirationSweep::default();
+
+        // Too early in the day
+        assert!(sweep.sweep(&positions, at(14, 30)).is_empty());
+
+        let alerts = sweep.sweep(&positions, at(15, 30));
+        assert_eq!(alerts.len(), 2);
+
+        let itm = &alerts[0];
+        assert_eq!(itm.moneyness, Moneyness::InTheMoney);
+        assert_eq!(itm.minutes_to_close, 30);
+        let order = itm.order.as_ref().unwrap();
+        assert_eq!(order.strategy, OptionStrategy::Single);
+        assert_eq!(order.legs[0].action, OrderAction::Sell);
+        assert_eq!(order.legs[0].ticker_id, 480);
+        assert_eq!(order.quantity, 2);
+        assert_eq!(order.limit_price, Some(1.05));
+        let payload = order.payload().unwrap();
+        assert_eq!(payload["orders"][0]["tickerType"], "OPTION");
+        assert_eq!(payload["lmtPrice"], 1.05);
+
+        let pinned = &alerts[1];
+        assert_eq!(pinned.moneyness, Moneyness::NearTheMoney);
+        assert_eq!(pinned.action, ExpirationAction::Warn);
+        assert!(pinned.order.is_none());
+
+        // Closing the short put too, at market
+        let closing = ExpirationSweep::new(
+            ExpirationPolicy::default()
+                .near_the_money_action(ExpirationAction::Close)
+                .limit_at_mid(false),
+        );
+        let alerts = closing.sweep(&positions, at(15, 55));
+        let order = alerts[1].order.as_ref().unwrap();
+        assert_eq!(order.legs[0].action, OrderAction::Buy);
+        assert_eq!(order.order_type, OrderType::Market);
+    }
+
+    #[test]
+    fn test_mid_rounds_to_option_tick() {
+        assert_eq!(round_to_tick(1.055), 1.06);
+        assert_eq!(round_to_tick(4.37), 4.35);
+        assert_eq!(round_to_tick(4.38), 4.4);
+
+        let mut expensive = position("CALL", 480.0, 1.0, "2024-06-21");
+        expensive.contract.bid = Some(20.10);
+        expensive.contract.ask = Some(20.40);
+        let alerts = ExpirationSweep::default().sweep(&[expensive], at(15, 30));
+        assert_eq!(alerts[0].order.as_ref().unwrap().limit_price, Some(20.25));
+    }
+}
@@ -0,0 +1,265 @@
+// Record and replay of raw HTTP responses for offline tests
+
+use crate::error::{Result, WebullError};
+use crate::telemetry::{is_secret, redact_body, redact_url};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Whether fixtures are written from live traffic or served in its place
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// Send requests normally and save each response
+    Record,
+    /// Serve saved responses without touching the network
+    Replay,
+}
+
+/// A saved response, with secrets in the URL and body redacted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fixture {
+    pub method: String,
+    pub url: String,
+    pub status: u16,
+    /// Response body, kept as JSON when it parses so fixtures stay readable
+    pub body: Value,
+}
+
+/// Directory of fixtures keyed by request method, URL and body
+///
+/// Volatile request fields (`serialId`, request timestamps) and secrets
+/// (tokens, password hashes) are ignored when matching, so a replayed order
+/// finds the response recorded for it under a different session.
+#[derive(Debug, Clone)]
+pub struct FixtureStore {
+    dir: PathBuf,
+    mode: FixtureMode,
+}
+
+impl FixtureStore {
+    pub fn new(dir: impl Into<PathBuf>, mode: FixtureMode) -> Self {
+        Self {
+            dir: dir.into(),
+            mode,
+        }
+    }
+
+    pub fn mode(&self) -> FixtureMode {
+        self.mode
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Save the response to a request
+    ///
+    /// Access and refresh tokens, password hashes and device IDs are
+    /// redacted from the saved URL and body.
+    pub fn record(&self, request: &reqwest::Request, status: u16, body: &[u8]) -> Result<()> {
+        let fixture = Fixture {
+            method: request.method().to_string(),
+            url: redact_url(request.url()),
+            status,
+            body: serde_json::from_slice(body)
+                .map(|body| redact_body(&body))
+                .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(body).into_owned())),
+        };
+
+        fs::create_dir_all(&self.dir)?;
+        let json = serde_json::to_string_pretty(&fixture)?;
+        fs::write(self.path_for(request), json)?;
+        Ok(())
+    }
+
+    /// Save a live response and hand back an equivalent unread response
+    pub(crate) async fn record_response(
+        &self,
+        request: &reqwest::Request,
+        response: reqwest::Response,
+    ) -> Result<reqwest::Response> {
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .cloned();
+        let body = response.bytes().await?;
+        self.record(request, status.as_u16(), &body)?;
+
+        let mut builder = http::Response::builder().status(status);
+        if let Some(content_type) = content_type {
+            builder = builder.header(reqwest::header::CONTENT_TYPE, content_type);
+        }
+        let rebuilt = builder
+            .body(body)
+            .map_err(|e| WebullError::Unknown(e.to_string()))?;
+        Ok(reqwest::Response::from(rebuilt))
+    }
+
+    /// Load the response saved for a request
+    pub fn load(&self, request: &reqwest::Request) -> Result<Fixture> {
+        let path = self.path_for(request);
+        let contents = fs::read_to_string(&path).map_err(|_| {
+            WebullError::InvalidRequest(format!(
+                "No fixture for {} {} (expected {})",
+                request.method(),
+                request.url(),
+                path.display()
+            ))
+        })?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Build a response from a saved fixture
+    pub(crate) fn replay(&self, request: &reqwest::Request) -> Result<reqwest::Response> {
+        let fixture = self.load(request)?;
+        let body = match fixture.body {
+            Value::String(text) => text,
+            other => other.to_string(),
+        };
+        let response = http::Response::builder()
+            .status(fixture.status)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .map_err(|e| WebullError::Unknown(e.to_string()))?;
+        Ok(reqwest::Response::from(response))
+    }
+
+    fn path_for(&self, request: &reqwest::Request) -> PathBuf {
+        let url = request.url();
+        let mut query: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(key, _)| !VOLATILE_FIELDS.contains(&key.as_ref()) && !is_secret(key))
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect();
+        query.sort();
+
+        let body = request
+            .body()
+            .and_then(|b| b.as_bytes())
+            .and_then(|bytes| serde_json::from_slice::<Value>(bytes).ok())
+            .map(|body| {
+                let mut body = redact_body(&body);
+                strip_volatile(&mut body);
+                body.to_string()
+            })
+            .unwrap_or_default();
+
+        let key = format!(
+            "{} {}{} {:?} {}",
+            request.method(),
+            url.host_str().unwrap_or_default(),
+            url.path(),
+            query,
+            body
+        );
+        let slug: String = url
+            .path()
+            .trim_matches('/')
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let digest = format!("{:x}", md5::compute(key));
+
+        self.dir.join(format!(
+            "{}_{}_{}.json",
+            request.method().as_str().to_lowercase(),
+            slug,
+            &digest[..12]
+        ))
+    }
+}
+
+const VOLATILE_FIELDS: &[&str] = &["serialId", "_t", "t_time", "reqid", "timestamp"];
+
+fn strip_volatile(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|key, _| !VOLATILE_FIELDS.contains(&key.as_str()));
+            map.values_mut().for_each(strip_volatile);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_volatile),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let dir = std::env::temp_dir().join(format!("webull_fixtures_{}", uuid::Uuid::new_v4()));
+        let client = reqwest::Client::new();
+        let request = |serial: &str| {
+            client
+                .post("https://ustrade.webullfinance.com/api/trade/order/1/placeStockOrder")
+                .json(&json!({"tickerId": 913256135, "serialId": serial}))
+                .build()
+                .unwrap()
+        };
+
+        let recorder = FixtureStore::new(&dir, FixtureMode::Record);
+        recorder
+            .record(&request("first"), 200, br#"{"orderId": 42}"#)
+            .unwrap();
+
+        // A different serialId still matches the recorded fixture
+        let replayer = FixtureStore::new(&dir, FixtureMode::Replay);
+        let response = replayer.replay(&request("second")).unwrap();
+        assert_eq!(response.status(), 200);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["orderId"], 42);
+
+        let other = client
+            .get("https://quotes-gw.webullfintech.com/api/quotes/ticker/getTickerRealTime?tickerId=1")
+            .build()
+            .unwrap();
+        assert!(replayer.replay(&other).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_recorded_fixtures_are_redacted() {
+        let dir = std::env::temp_dir().join(format!("webull_fixtures_{}", uuid::Uuid::new_v4()));
+        let client = reqwest::Client::new();
+        let refresh = |token: &str| {
+            client
+                .post(format!(
+                    "https://userapi.webull.com/api/passport/refreshToken?refreshToken={}",
+                    token
+                ))
+                .build()
+                .unwrap()
+        };
+
+        let recorder = FixtureStore::new(&dir, FixtureMode::Record);
+        recorder
+            .record(
+                &refresh("old-refresh"),
+                200,
+                br#"{"accessToken": "new-access", "refreshToken": "new-refresh", "tokenExpireTime": "2024-01-01"}"#,
+            )
+            .unwrap();
+
+        let written: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| fs::read_to_string(entry.unwrap().path()).unwrap())
+            .collect();
+        assert_eq!(written.len(), 1);
+        for secret in ["old-refresh", "new-access", "new-refresh"] {
+            assert!(!written[0].contains(secret), "{} was written", secret);
+        }
+
+        // The token in the URL doesn't affect which fixture is served
+        let replayer = FixtureStore::new(&dir, FixtureMode::Replay);
+        let fixture = replayer.load(&refresh("another-refresh")).unwrap();
+        assert_eq!(fixture.body["tokenExpireTime"], "2024-01-01");
+        assert_eq!(fixture.body["accessToken"], "[redacted]");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,181 @@
+// Display formatting with the precision the Webull app uses
+
+use serde::{Deserialize, Serialize};
+
+/// Kind of instrument, for picking display precision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AssetClass {
+    Equity,
+    Option,
+    Crypto,
+}
+
+impl AssetClass {
+    /// Classify a position's `asset_type`; anything unrecognised is equity
+    pub fn from_asset_type(asset_type: Option<&str>) -> Self {
+        match asset_type.map(str::to_ascii_lowercase).as_deref() {
+            Some("option") | Some("options") => AssetClass::Option,
+            Some("crypto") | Some("cryptocurrency") => AssetClass::Crypto,
+            _ => AssetClass::Equity,
+        }
+    }
+}
+
+/// Decimal places used when displaying prices, percentages and quantities
+///
+/// The defaults follow the Webull app: stocks at 2 places, or 4 below $1;
+/// options at 2; crypto at up to 8 with trailing zeros dropped; percentages
+/// at 2; share quantities at up to 5 for fractional shares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundingPolicy {
+    pub equity_price: usize,
+    /// Used instead of `equity_price` for prices under $1
+    pub sub_dollar_price: usize,
+    pub option_price: usize,
+    /// Maximum places for crypto; trailing zeros past 2 places are dropped
+    pub crypto_price: usize,
+    pub percent: usize,
+    /// Maximum places for quantities; trailing zeros are dropped
+    pub quantity: usize,
+    /// Places for cash amounts such as market value and P&L
+    pub money: usize,
+}
+
+impl Default for RoundingPolicy {
+    fn default() -> Self {
+        Self {
+            equity_price: 2,
+            sub_dollar_price: 4,
+            option_price: 2,
+            crypto_price: 8,
+            percent: 2,
+            quantity: 5,
+            money: 2,
+        }
+    }
+}
+
+impl RoundingPolicy {
+    /// Format a price for an asset class, e.g. `"182.52"` or `"0.4521"`
+    pub fn price(&self, price: f64, class: AssetClass) -> String {
+        match class {
+            AssetClass::Equity if price.abs() < 1.0 => fixed(price, self.sub_dollar_price),
+            AssetClass::Equity => fixed(price, self.equity_price),
+            AssetClass::Option => fixed(price, self.option_price),
+            AssetClass::Crypto => trimmed(price, self.crypto_price, 2),
+        }
+    }
+
+    /// Format a fraction as a signed percentage, e.g. `0.0123` as `"+1.23%"`
+    pub fn percent(&self, ratio: f64) -> String {
+        let text = fixed(ratio * 100.0, self.percent);
+        if text.starts_with('-') || is_zero(&text) {
+            format!("{}%", text)
+        } else {
+            format!("+{}%", text)
+        }
+    }
+
+    /// Format a share or contract quantity, e.g. `"10"` or `"0.12345"`
+    pub fn quantity(&self, quantity: f64) -> String {
+        trimmed(quantity, self.quantity, 0)
+    }
+
+    /// Format a cash amount with a dollar sign and thousands separators,
+    /// e.g. `"-$1,234.50"`
+    pub fn money(&self, amount: f64) -> String {
+        let text = fixed(amount.abs(), self.money);
+        let (whole, fraction) = match text.split_once('.') {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (text.as_str(), None),
+        };
+
+        let mut grouped = String::new();
+        for (i, digit) in whole.chars().enumerate() {
+            if i > 0 && (whole.len() - i) % 3 == 0 {
+                grouped.push(',');
+            }
+            grouped.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            grouped.push('.');
+            grouped.push_str(fraction);
+        }
+
+        if amount < 0.0 && !is_zero(&text) {
+            format!("-${}", grouped)
+        } else {
+            format!("${}", grouped)
+        }
+    }
+}
+
+/// Round to exactly `places`, never showing a negative zero
+fn fixed(value: f64, places: usize) -> String {
+    let text = format!("{:.prec$}", value, prec = places);
+    match text.strip_prefix('-') {
+        Some(unsigned) if is_zero(unsigned) => unsigned.to_string(),
+        _ => text,
+    }
+}
+
+/// Round to at most `max_places`, dropping trailing zeros but keeping at
+/// least `min_places` (or `max_places`, if fewer)
+fn trimmed(value: f64, max_places: usize, min_places: usize) -> String {
+    let min_places = min_places.min(max_places);
+    let text = fixed(value, max_places);
+    let point = match text.find('.') {
+        Some(point) => point,
+        None => return text,
+    };
+    let keep = text.trim_end_matches('0').len().max(point + 1 + min_places);
+    text[..keep].trim_end_matches('.').to_string()
+}
+
+fn is_zero(text: &str) -> bool {
+    text.chars().all(|c| matches!(c, '0' | '.' | '-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prices_by_asset_class() {
+        let policy = RoundingPolicy::default();
+        assert_eq!(policy.price(182.519, AssetClass::Equity), "182.52");
+        assert_eq!(policy.price(0.45214, AssetClass::Equity), "0.4521");
+        assert_eq!(policy.price(1.5, AssetClass::Option), "1.50");
+        assert_eq!(policy.price(64_250.5, AssetClass::Crypto), "64250.50");
+        assert_eq!(policy.price(0.123456789, AssetClass::Crypto), "0.12345679");
+        let coarse = RoundingPolicy {
+            crypto_price: 1,
+            ..Default::default()
+        };
+        assert_eq!(coarse.price(1.5, AssetClass::Crypto), "1.5");
+        assert_eq!(coarse.price(2.0, AssetClass::Crypto), "2.0");
+        assert_eq!(
+            AssetClass::from_asset_type(Some("OPTION")),
+            AssetClass::Option
+        );
+        assert_eq!(AssetClass::from_asset_type(None), AssetClass::Equity);
+    }
+
+    #[test]
+    fn test_percent_quantity_and_money() {
+        let policy = RoundingPolicy::default();
+        assert_eq!(policy.percent(0.012345), "+1.23%");
+        assert_eq!(policy.percent(-0.05), "-5.00%");
+        assert_eq!(policy.percent(-0.00001), "0.00%");
+
+        assert_eq!(policy.quantity(10.0), "10");
+        assert_eq!(policy.quantity(0.123456), "0.12346");
+        assert_eq!(policy.quantity(2.5), "2.5");
+
+        assert_eq!(policy.money(1_234_567.891), "$1,234,567.89");
+        assert_eq!(policy.money(-1_234.5), "-$1,234.50");
+        assert_eq!(policy.money(-0.001), "$0.00");
+        assert_eq!(policy.money(999.0), "$999.00");
+    }
+}
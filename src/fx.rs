@@ -0,0 +1,165 @@
+// Historical FX rates and a shared cache for converting account P&L
+
+use chrono::NaiveDate;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Closing exchange rate between two currencies on a day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FxRate {
+    pub from: String,
+    pub to: String,
+    pub date: NaiveDate,
+    /// Units of `to` per unit of `from`
+    pub rate: f64,
+}
+
+impl FxRate {
+    /// Parse a rate response (`rate` at the top level, in `data`, or the
+    /// entry for `date` in a `data` array)
+    ///
+    /// A `data` array without an entry for `date` gives `None` rather than
+    /// another day's rate, which would otherwise be cached under `date`.
+    pub fn from_response(from: &str, to: &str, date: NaiveDate, value: &Value) -> Option<Self> {
+        let number = |v: &Value| {
+            ["rate", "close", "exchangeRate"]
+                .iter()
+                .find_map(|key| match v.get(*key) {
+                    Some(Value::String(s)) => s.parse::<f64>().ok(),
+                    Some(Value::Number(n)) => n.as_f64(),
+                    _ => None,
+                })
+        };
+        let day = date.format("%Y-%m-%d").to_string();
+        let rate = match value.get("data") {
+            Some(Value::Array(items)) => items
+                .iter()
+                .find(|item| {
+                    item.get("date")
+                        .and_then(|d| d.as_str())
+                        .is_some_and(|d| d.starts_with(&day))
+                })
+                .and_then(number),
+            Some(data) => number(data),
+            None => number(value),
+        }?;
+
+        (rate.is_finite() && rate > 0.0).then(|| Self {
+            from: from.to_ascii_uppercase(),
+            to: to.to_ascii_uppercase(),
+            date,
+            rate,
+        })
+    }
+}
+
+/// Daily FX rates keyed by currency pair and date
+///
+/// Historical rates never change, so entries are kept for the life of the
+/// cache. A pair is also answered from its inverse.
+#[derive(Debug, Default)]
+pub struct FxRateCache {
+    rates: Mutex<HashMap<(String, String, NaiveDate), f64>>,
+}
+
+impl FxRateCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rate from `from` to `to` on `date`, if known
+    pub fn get(&self, from: &str, to: &str, date: NaiveDate) -> Option<f64> {
+        let from = from.to_ascii_uppercase();
+        let to = to.to_ascii_uppercase();
+        if from == to {
+            return Some(1.0);
+        }
+        let rates = self.rates.lock();
+        rates
+            .get(&(from.clone(), to.clone(), date))
+            .copied()
+            .or_else(|| rates.get(&(to, from, date)).map(|rate| 1.0 / rate))
+    }
+
+    pub fn insert(&self, rate: &FxRate) {
+        self.rates.lock().insert(
+            (
+                rate.from.to_ascii_uppercase(),
+                rate.to.to_ascii_uppercase(),
+                rate.date,
+            ),
+            rate.rate,
+        );
+    }
+
+    /// Convert an amount at the rate on `date`; `None` if the rate isn't cached
+    pub fn convert(&self, amount: f64, from: &str, to: &str, date: NaiveDate) -> Option<f64> {
+        self.get(from, to, date).map(|rate| amount * rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lots::ClosedLot;
+    use serde_json::json;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_cache_rates() {
+        let rate = FxRate::from_response(
+            "usd",
+            "cad",
+            day(1),
+            &json!({"data": [
+                {"date": "2024-02-29", "rate": "1.3570"},
+                {"date": "2024-03-01", "rate": "1.3560"}
+            ]}),
+        )
+        .unwrap();
+        assert_eq!(rate.from, "USD");
+        assert_eq!(rate.rate, 1.3560);
+        assert!(FxRate::from_response("USD", "CAD", day(1), &json!({"rate": "0"})).is_none());
+        // A holiday missing from the series isn't answered with a nearby day
+        let series = json!({"data": [{"date": "2024-02-29", "rate": "1.3570"}]});
+        assert!(FxRate::from_response("USD", "CAD", day(1), &series).is_none());
+
+        let cache = FxRateCache::new();
+        cache.insert(&rate);
+        assert_eq!(cache.get("USD", "CAD", day(1)), Some(1.3560));
+        assert!((cache.get("CAD", "USD", day(1)).unwrap() - 1.0 / 1.3560).abs() < 1e-12);
+        assert_eq!(cache.get("USD", "CAD", day(2)), None);
+        assert_eq!(cache.convert(100.0, "HKD", "hkd", day(2)), Some(100.0));
+    }
+
+    #[test]
+    fn test_closed_lot_gain_uses_rates_on_trade_dates() {
+        let cache = FxRateCache::new();
+        for (d, rate) in [(1, 1.30), (15, 1.40)] {
+            cache.insert(&FxRate {
+                from: "USD".to_string(),
+                to: "CAD".to_string(),
+                date: day(d),
+                rate,
+            });
+        }
+        let lot = ClosedLot {
+            symbol: "AAPL".to_string(),
+            quantity: 10.0,
+            acquired: day(1),
+            sold: day(15),
+            cost_basis: 1000.0,
+            proceeds: 1000.0,
+        };
+        // No gain in USD, but the currency moved between purchase and sale
+        let gain = lot.gain_converted(&cache, "USD", "CAD").unwrap();
+        assert!((gain - 100.0).abs() < 1e-9);
+        assert!(lot.gain_converted(&cache, "USD", "EUR").is_none());
+    }
+}
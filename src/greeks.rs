@@ -0,0 +1,353 @@
+// Black-Scholes prices, greeks and implied volatility for option contracts
+
+use crate::models::OptionContract;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::{PI, SQRT_2};
+
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Volatility search bounds for `implied_volatility`
+const MIN_VOLATILITY: f64 = 1e-4;
+const MAX_VOLATILITY: f64 = 5.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OptionKind {
+    Call,
+    Put,
+}
+
+impl OptionKind {
+    /// From `OptionContract::option_type` ("CALL" or "PUT")
+    pub fn from_type(option_type: &str) -> Option<Self> {
+        match option_type.to_ascii_uppercase().as_str() {
+            "CALL" => Some(OptionKind::Call),
+            "PUT" => Some(OptionKind::Put),
+            _ => None,
+        }
+    }
+}
+
+/// Sensitivities in the units Webull reports them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Greeks {
+    pub delta: f64,
+    pub gamma: f64,
+    /// Per calendar day
+    pub theta: f64,
+    /// Per volatility point (0.01)
+    pub vega: f64,
+    /// Per rate point (0.01)
+    pub rho: f64,
+}
+
+/// A European option under Black-Scholes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlackScholes {
+    pub kind: OptionKind,
+    pub spot: f64,
+    pub strike: f64,
+    /// Years until expiration
+    pub years: f64,
+    /// Continuously compounded risk-free rate, e.g. 0.05
+    pub rate: f64,
+    /// Continuous dividend yield
+    pub dividend_yield: f64,
+}
+
+impl BlackScholes {
+    pub fn new(kind: OptionKind, spot: f64, strike: f64, years: f64) -> Self {
+        Self {
+            kind,
+            spot,
+            strike,
+            years,
+            rate: 0.0,
+            dividend_yield: 0.0,
+        }
+    }
+
+    pub fn rate(mut self, rate: f64) -> Self {
+        self.rate = rate;
+        self
+    }
+
+    pub fn dividend_yield(mut self, dividend_yield: f64) -> Self {
+        self.dividend_yield = dividend_yield;
+        self
+    }
+
+    /// Theoretical price; intrinsic value at expiration or zero volatility
+    pub fn price(&self, volatility: f64) -> f64 {
+        let (spot, strike) = self.discounted();
+        match self.d1_d2(volatility) {
+            Some((d1, d2)) => match self.kind {
+                OptionKind::Call => spot * norm_cdf(d1) - strike * norm_cdf(d2),
+                OptionKind::Put => strike * norm_cdf(-d2) - spot * norm_cdf(-d1),
+            },
+            None => match self.kind {
+                OptionKind::Call => (spot - strike).max(0.0),
+                OptionKind::Put => (strike - spot).max(0.0),
+            },
+        }
+    }
+
+    pub fn greeks(&self, volatility: f64) -> Greeks {
+        let (d1, d2) = match self.d1_d2(volatility) {
+            Some(d) => d,
+            None => {
+                let in_the_money = match self.kind {
+                    OptionKind::Call => self.spot > self.strike,
+                    OptionKind::Put => self.spot < self.strike,
+                };
+                let delta = match (self.kind, in_the_money) {
+                    (OptionKind::Call, true) => 1.0,
+                    (OptionKind::Put, true) => -1.0,
+                    _ => 0.0,
+                };
+                return Greeks {
+                    delta,
+                    ..Greeks::default()
+                };
+            }
+        };
+
+        let (spot, strike) = self.discounted();
+        let sqrt_t = self.years.sqrt();
+        let decay = -spot * norm_pdf(d1) * volatility / (2.0 * sqrt_t);
+        let (delta, theta, rho) = match self.kind {
+            OptionKind::Call => (
+                (-self.dividend_yield * self.years).exp() * norm_cdf(d1),
+                decay - self.rate * strike * norm_cdf(d2)
+                    + self.dividend_yield * spot * norm_cdf(d1),
+                strike * self.years * norm_cdf(d2),
+            ),
+            OptionKind::Put => (
+                -(-self.dividend_yield * self.years).exp() * norm_cdf(-d1),
+                decay + self.rate * strike * norm_cdf(-d2)
+                    - self.dividend_yield * spot * norm_cdf(-d1),
+                -strike * self.years * norm_cdf(-d2),
+            ),
+        };
+
+        Greeks {
+            delta,
+            gamma: spot * norm_pdf(d1) / (self.spot * self.spot * volatility * sqrt_t),
+            theta: theta / DAYS_PER_YEAR,
+            vega: spot * norm_pdf(d1) * sqrt_t / 100.0,
+            rho: rho / 100.0,
+        }
+    }
+
+    /// Volatility at which the model matches `price`
+    ///
+    /// `None` if the price is outside the no-arbitrage bounds or the option
+    /// has expired.
+    pub fn implied_volatility(&self, price: f64) -> Option<f64> {
+        if self.years <= 0.0 || price.is_nan() || price <= 0.0 {
+            return None;
+        }
+        let (spot, strike) = self.discounted();
+        let (lower, upper) = match self.kind {
+            OptionKind::Call => ((spot - strike).max(0.0), spot),
+            OptionKind::Put => ((strike - spot).max(0.0), strike),
+        };
+        if price <= lower || price >= upper {
+            return None;
+        }
+
+        // Newton steps, falling back to bisection when they leave the bracket
+        let (mut low, mut high) = (MIN_VOLATILITY, MAX_VOLATILITY);
+        let mut volatility = 0.3;
+        for _ in 0..100 {
+            let error = self.price(volatility) - price;
+            if error.abs() < 1e-8 {
+                return Some(volatility);
+            }
+            if error > 0.0 {
+                high = volatility;
+            } else {
+                low = volatility;
+            }
+            let vega = self.greeks(volatility).vega * 100.0;
+            let step = volatility - error / vega;
+            volatility = if vega > 1e-10 && step > low && step < high {
+                step
+            } else {
+                (low + high) / 2.0
+            };
+        }
+        ((high - low) < 1e-6).then_some(volatility)
+    }
+
+    fn d1_d2(&self, volatility: f64) -> Option<(f64, f64)> {
+        if self.years <= 0.0 || volatility <= 0.0 || self.spot <= 0.0 || self.strike <= 0.0 {
+            return None;
+        }
+        let spread = volatility * self.years.sqrt();
+        let d1 = ((self.spot / self.strike).ln()
+            + (self.rate - self.dividend_yield + volatility * volatility / 2.0) * self.years)
+            / spread;
+        Some((d1, d1 - spread))
+    }
+
+    /// Spot and strike discounted by the dividend yield and rate
+    fn discounted(&self) -> (f64, f64) {
+        (
+            self.spot * (-self.dividend_yield * self.years).exp(),
+            self.strike * (-self.rate * self.years).exp(),
+        )
+    }
+}
+
+impl OptionContract {
+    /// Fill in implied volatility and greeks the API left out
+    ///
+    /// IV is solved from the mid, or the last price without a quote. Values
+    /// already present are kept. Contracts expiring on `today` are treated as
+    /// one day out. Returns whether anything was filled in.
+    pub fn fill_greeks(&mut self, spot: f64, rate: f64, today: NaiveDate) -> bool {
+        let complete = self.implied_volatility.is_some()
+            && self.delta.is_some()
+            && self.gamma.is_some()
+            && self.theta.is_some()
+            && self.vega.is_some();
+        if complete {
+            return false;
+        }
+        let kind = match OptionKind::from_type(&self.option_type) {
+            Some(kind) => kind,
+            None => return false,
+        };
+        let days = match NaiveDate::parse_from_str(&self.expiration_date, "%Y-%m-%d") {
+            Ok(expiry) if expiry >= today => (expiry - today).num_days().max(1),
+            _ => return false,
+        };
+
+        let model = BlackScholes::new(kind, spot, self.strike_price, days as f64 / DAYS_PER_YEAR)
+            .rate(rate);
+        let volatility = match self.implied_volatility.or_else(|| {
+            self.mid()
+                .or(self.last_price)
+                .and_then(|price| model.implied_volatility(price))
+        }) {
+            Some(volatility) => volatility,
+            None => return false,
+        };
+
+        let greeks = model.greeks(volatility);
+        self.implied_volatility.get_or_insert(volatility);
+        self.delta.get_or_insert(greeks.delta);
+        self.gamma.get_or_insert(greeks.gamma);
+        self.theta.get_or_insert(greeks.theta);
+        self.vega.get_or_insert(greeks.vega);
+        true
+    }
+}
+
+/// `fill_greeks` on every contract, returning how many were filled in
+pub fn fill_chain_greeks(
+    contracts: &mut [OptionContract],
+    spot: f64,
+    rate: f64,
+    today: NaiveDate,
+) -> usize {
+    contracts
+        .iter_mut()
+        .map(|contract| contract.fill_greeks(spot, rate, today))
+        .filter(|filled| *filled)
+        .count()
+}
+
+/// Standard normal density
+pub fn norm_pdf(x: f64) -> f64 {
+    (-x * x / 2.0).exp() / (2.0 * PI).sqrt()
+}
+
+/// Standard normal cumulative distribution
+pub fn norm_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / SQRT_2)
+}
+
+/// Complementary error function, accurate to about 1.2e-7
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let result = t * poly.exp();
+    if x >= 0.0 {
+        result
+    } else {
+        2.0 - result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn close(a: f64, b: f64, tolerance: f64) -> bool {
+        (a - b).abs() < tolerance
+    }
+
+    #[test]
+    fn test_black_scholes_matches_reference_values() {
+        // Hull, Options, Futures and Other Derivatives, example 15.6
+        let call = BlackScholes::new(OptionKind::Call, 42.0, 40.0, 0.5).rate(0.1);
+        let put = BlackScholes {
+            kind: OptionKind::Put,
+            ..call
+        };
+        assert!(close(call.price(0.2), 4.76, 0.005));
+        assert!(close(put.price(0.2), 0.81, 0.005));
+
+        let greeks = call.greeks(0.2);
+        assert!(close(greeks.delta, 0.7791, 1e-3));
+        assert!(close(greeks.gamma, 0.0500, 1e-3));
+        assert!(close(greeks.vega, 0.0880, 1e-3));
+        assert!(close(greeks.theta * DAYS_PER_YEAR, -4.559, 1e-2));
+        assert!(close(put.greeks(0.2).delta, greeks.delta - 1.0, 1e-9));
+
+        let iv = call.implied_volatility(call.price(0.35)).unwrap();
+        assert!(close(iv, 0.35, 1e-6));
+        assert!(call.implied_volatility(1.0).is_none());
+        assert_eq!(
+            BlackScholes::new(OptionKind::Put, 90.0, 100.0, 0.0).price(0.3),
+            10.0
+        );
+    }
+
+    #[test]
+    fn test_fill_greeks_keeps_reported_values() {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+        let mut chain: Vec<OptionContract> = serde_json::from_value(json!([
+            {"tickerId": 1, "symbol": "AAPL240621C00190000", "strikePrice": 190.0,
+             "expirationDate": "2024-06-21", "optionType": "CALL", "bid": 4.9, "ask": 5.1},
+            {"tickerId": 2, "symbol": "AAPL240621P00190000", "strikePrice": 190.0,
+             "expirationDate": "2024-06-21", "optionType": "PUT", "bid": 4.0, "ask": 4.2,
+             "delta": -0.45},
+            {"tickerId": 3, "symbol": "AAPL240531C00190000", "strikePrice": 190.0,
+             "expirationDate": "2024-05-31", "optionType": "CALL", "bid": 1.0, "ask": 1.2}
+        ]))
+        .unwrap();
+
+        assert_eq!(fill_chain_greeks(&mut chain, 191.0, 0.05, today), 2);
+        let call = &chain[0];
+        let iv = call.implied_volatility.unwrap();
+        assert!(iv > 0.1 && iv < 0.5);
+        assert!(call.delta.unwrap() > 0.5 && call.theta.unwrap() < 0.0);
+        assert_eq!(chain[1].delta, Some(-0.45));
+        assert!(chain[1].gamma.is_some());
+        assert!(chain[2].implied_volatility.is_none());
+        assert!(!chain[0].fill_greeks(191.0, 0.05, today));
+    }
+}
@@ -1,21 +1,111 @@
+pub mod analytics;
+pub mod audit;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod builders;
+pub mod chains;
+pub mod circuit;
 pub mod client;
+pub mod concurrency;
+pub mod config;
+#[cfg(feature = "decimal")]
+pub mod decimal;
+pub mod delta;
+pub mod did;
 pub mod endpoints;
 pub mod error;
+pub mod events;
+pub mod expiration;
+pub mod fixtures;
+pub mod format;
+pub mod fx;
+#[cfg(feature = "greeks")]
+pub mod greeks;
 pub mod live_client;
+pub mod lots;
+pub mod market;
+pub mod middleware;
+#[cfg(feature = "mock")]
+pub mod mock;
 pub mod models;
+pub mod monitor;
+pub mod news;
+pub mod options;
+pub mod order_book;
+mod pagination;
 pub mod paper_client;
+pub mod quality;
+pub mod quote_history;
+pub mod rate_limit;
+pub mod reconcile;
+pub mod region;
+pub mod rejections;
+pub mod resolver;
+pub mod retry;
+pub mod risk;
+pub mod sessions;
+pub mod shutdown;
+#[cfg(feature = "sql-sink")]
+pub mod sink;
+pub mod snapshots;
 pub mod stream;
+pub mod telemetry;
+pub mod toolkit;
+pub mod trader;
 pub mod unified_client;
+pub mod universe;
 pub mod utils;
+pub mod volatility;
+pub mod watchlists;
+#[cfg(feature = "webhook")]
+pub mod webhook;
 
-pub use client::{LiveWebullClient, PaperWebullClient, WebullClient};
+pub use analytics::{PortfolioAnalytics, PortfolioReport};
+pub use audit::{CapturedPayload, OrderDryRun, PayloadDifference, PayloadHook};
+pub use chains::{ChainProgress, OptionChainDownload, OptionChainSet};
+pub use circuit::{BreakerSettings, CircuitBreakers, CircuitPermit, CircuitState};
+pub use client::{LiveWebullClient, PaperWebullClient, WebullClient, WebullClientBuilder};
+pub use concurrency::{ConcurrencyLimiter, ConcurrencyPermit, RequestPriority};
+pub use config::ClientConfig;
+pub use delta::{DeltaState, NewsDelta, QuoteDelta};
+pub use did::{DidStore, FileDidStore, MemoryDidStore};
+pub use endpoints::{Endpoints, EndpointsBuilder, Environment};
 pub use error::{Result, WebullError};
+pub use events::{AccountEvent, AccountEventKind, AccountEvents};
+pub use expiration::{ExpirationAction, ExpirationPolicy, ExpirationSweep};
+pub use fixtures::{FixtureMode, FixtureStore};
+pub use format::{AssetClass, RoundingPolicy};
+pub use fx::{FxRate, FxRateCache};
+pub use lots::{LotMethod, LotTracker};
+pub use market::{MarketCalendar, Session, TradingHours};
+pub use middleware::{LoggingMiddleware, Middleware, RequestLog};
 pub use models::{
-    BarsRequestBuilder, LoginRequestBuilder, NewsRequestBuilder, OptionsRequestBuilder,
-    PlaceOrderRequest, PlaceOrderRequestBuilder, ScreenerRequestBuilder,
+    AlertRuleBuilder, BarInterval, BarsRequestBuilder, LoginRequestBuilder, NewsRequestBuilder,
+    OptionsRequestBuilder, PlaceOrderRequest, PlaceOrderRequestBuilder, QuoteBuilder,
+    ScreenerRequestBuilder,
 };
-pub use stream::StreamConn;
+pub use monitor::{QuoteMonitor, SecurityMonitor};
+pub use news::{NewsWatcher, WatchedNews};
+pub use options::{PlaceOptionSpreadRequest, StrategyBuilder};
+pub use order_book::OrderBook;
+pub use quality::{DataQualityWarning, QuoteAnomaly, QuoteValidator};
+pub use quote_history::{QuoteHistory, QuoteRecorder, QuoteSnapshot};
+pub use rate_limit::{EndpointClass, RateLimiter};
+pub use reconcile::{OrderReconciler, StateDrift};
+pub use region::{Region, RegionProfile};
+pub use rejections::{RejectionCatalogue, RejectionReason};
+pub use resolver::SymbolResolver;
+pub use retry::RetryPolicy;
+pub use risk::{RiskPosition, Scenario, ScenarioResult, ScenarioTable};
+pub use sessions::{RefreshReport, SessionManager, SharedClient};
+pub use shutdown::{ShutdownPolicy, ShutdownReport};
+pub use snapshots::{AccountSnapshot, SnapshotStore};
+pub use stream::{BarAggregator, MarketEvent, StaleData, StreamBar, StreamConn};
+pub use toolkit::{AccountSummary, OrderConfirmation, OrderWarning, SmokeTest, SmokeTestReport};
+pub use trader::WebullTrader;
+pub use universe::InstrumentUniverse;
+pub use volatility::{HistoricalVolatility, IvRank, VolatilityRegime};
+pub use watchlists::{PortableWatchlist, WatchlistBundle, WatchlistDiff};
 
 #[cfg(test)]
 mod tests;
@@ -1,15 +1,42 @@
 use crate::{
-    endpoints::Endpoints,
+    audit::{CapturedPayload, PayloadHook},
+    chains::OptionChainDownload,
+    circuit::{CircuitBreakers, CircuitState},
+    concurrency::{ConcurrencyLimiter, RequestPriority},
+    config::ClientConfig,
+    delta::{bars_since, interval_seconds, DeltaState, NewsDelta, QuoteDelta},
+    did::{load_or_create, DidStore, FileDidStore, MemoryDidStore},
+    endpoints::{Endpoints, Environment},
     error::{Result, WebullError},
+    fixtures::{FixtureMode, FixtureStore},
+    fx::{FxRate, FxRateCache},
+    middleware::{Middleware, MiddlewareStack},
     models::*,
+    monitor::SecurityMonitor,
+    options::PlaceOptionSpreadRequest,
+    pagination::{BarPager, HistoryPager, NewsPager, BAR_PAGE_SIZE},
+    quality::QuoteValidator,
+    quote_history::QuoteRecorder,
+    rate_limit::{check_rate, EndpointClass, RateLimiter},
+    region::{Region, RegionProfile},
+    resolver::SymbolResolver,
+    retry::{is_retryable_status, RetryPolicy},
+    snapshots::SnapshotStore,
+    telemetry::RequestSpan,
+    universe::InstrumentUniverse,
     utils::*,
+    watchlists::{PortableWatchlist, WatchlistBundle, WatchlistDiff},
 };
+use futures::future;
+use futures::stream::{self, Stream, TryStreamExt};
 use reqwest::{
     header::{HeaderMap, HeaderValue, CONTENT_TYPE},
-    Client,
+    Client, RequestBuilder, Response,
 };
 use serde_json::{json, Value};
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
@@ -26,18 +53,102 @@ pub struct LiveWebullClient {
     pub(crate) refresh_token: Option<String>,
     pub(crate) token_expire: Option<i64>,
     pub(crate) uuid: Option<String>,
+    // Proof of a solved captcha, sent with the next login
+    pub(crate) captcha_ticket: Option<String>,
 
     // Configuration
     pub(crate) did: String,
+    pub(crate) did_store: Arc<dyn DidStore>,
     pub(crate) region_code: i32,
     pub(crate) zone_var: String,
+    pub(crate) region_profile: RegionProfile,
     pub(crate) timeout: u64,
+    pub(crate) class_timeouts: HashMap<EndpointClass, u64>,
+    // Set on the copies returned by `with_timeout` and `with_deadline`
+    pub(crate) timeout_override: Option<std::time::Duration>,
+    pub(crate) deadline: Option<tokio::time::Instant>,
+
+    // Caches, limiters and breakers are held in `Arc`s so clones share them
+
+    // Symbol -> ticker ID cache
+    pub(crate) resolver: Arc<SymbolResolver>,
+
+    // Opt-in local store for account snapshots
+    pub(crate) snapshot_store: Option<Arc<SnapshotStore>>,
+
+    // One limiter so the limit applies to all clones together
+    pub(crate) rate_limiter: Option<Arc<RateLimiter>>,
+
+    // Cap on concurrent requests
+    pub(crate) concurrency: Option<ConcurrencyLimiter>,
+
+    // Backoff for transient failures
+    pub(crate) retry_policy: RetryPolicy,
+
+    // Per-host breakers for the quote fallback chain
+    pub(crate) quote_breakers: Arc<CircuitBreakers<String>>,
+
+    // Opt-in breakers per endpoint class
+    pub(crate) endpoint_breakers: Option<Arc<CircuitBreakers<EndpointClass>>>,
+
+    // Receives trading request bodies as sent
+    pub(crate) payload_hook: Option<PayloadHook>,
+    pub(crate) middleware: MiddlewareStack,
+
+    // Record/replay of raw responses for offline tests
+    pub(crate) fixtures: Option<FixtureStore>,
+
+    // Flags stale, zero and crossed quotes as they are received
+    pub(crate) quote_validator: Option<Arc<QuoteValidator>>,
+
+    // Webull Advisors accounts seen in the account list; trading is refused
+    pub(crate) managed_accounts: HashSet<String>,
+
+    // Historical FX rates
+    pub(crate) fx_rates: Arc<FxRateCache>,
+
+    // Last quote handed out per ticker by `get_quote_delta`
+    pub(crate) deltas: Arc<DeltaState>,
 }
 
 impl LiveWebullClient {
     /// Create a new Webull client
+    ///
+    /// Use `WebullClientBuilder` to change the HTTP client, headers or
+    /// where the device ID is kept.
     pub fn new(region_code: Option<i32>) -> Result<Self> {
-        let did = get_did(None)?;
+        Self::from_parts(
+            region_code,
+            Arc::new(FileDidStore::default()),
+            Client::new(),
+        )
+    }
+
+    /// Create a new Webull client with a known device ID, without reading
+    /// or writing `did.bin`
+    pub fn with_did(region_code: Option<i32>, did: &str) -> Result<Self> {
+        Self::from_parts(
+            region_code,
+            Arc::new(MemoryDidStore::with_did(did)),
+            Client::new(),
+        )
+    }
+
+    /// Create a new Webull client sending requests with `client`, e.g. one
+    /// configured with a proxy or extra root certificates
+    pub fn with_http_client(region_code: Option<i32>, client: Client) -> Result<Self> {
+        Self::from_parts(region_code, Arc::new(FileDidStore::default()), client)
+    }
+
+    pub(crate) fn from_parts(
+        region_code: Option<i32>,
+        did_store: Arc<dyn DidStore>,
+        client: Client,
+    ) -> Result<Self> {
+        let did = load_or_create(did_store.as_ref())?;
+        let did_header = did_header(&did)?;
+        let region_code = region_code.unwrap_or(6);
+        let profile = Region::from_code(region_code).profile();
         let mut headers = HeaderMap::new();
 
         headers.insert("User-Agent", HeaderValue::from_static("Mozilla/5.0 (Macintosh; Intel Mac OS X 10.15; rv:99.0) Gecko/20100101 Firefox/99.0"));
@@ -45,25 +156,25 @@ impl LiveWebullClient {
         headers.insert("Accept-Encoding", HeaderValue::from_static("gzip, deflate"));
         headers.insert(
             "Accept-Language",
-            HeaderValue::from_static("en-US,en;q=0.5"),
+            HeaderValue::from_static(profile.accept_language),
         );
         headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
         headers.insert("platform", HeaderValue::from_static("web"));
-        headers.insert("hl", HeaderValue::from_static("en"));
+        headers.insert("hl", HeaderValue::from_static(profile.language));
         headers.insert("os", HeaderValue::from_static("web"));
         headers.insert("osv", HeaderValue::from_static("Mozilla/5.0 (Macintosh; Intel Mac OS X 10.15; rv:99.0) Gecko/20100101 Firefox/99.0"));
         headers.insert("app", HeaderValue::from_static("global"));
         headers.insert("appid", HeaderValue::from_static("webull-webapp"));
         headers.insert("ver", HeaderValue::from_static("3.39.18"));
-        headers.insert("lzone", HeaderValue::from_static("dc_core_r001"));
+        headers.insert("lzone", HeaderValue::from_static(profile.zone_var));
         headers.insert("ph", HeaderValue::from_static("MacOS Firefox"));
-        headers.insert("locale", HeaderValue::from_static("eng"));
+        headers.insert("locale", HeaderValue::from_static(profile.locale));
         headers.insert("device-type", HeaderValue::from_static("Web"));
-        headers.insert("did", HeaderValue::from_str(&did).unwrap());
+        headers.insert("did", did_header);
 
         Ok(Self {
-            client: Client::new(),
-            endpoints: Endpoints::new(),
+            client,
+            endpoints: profile.endpoints(&Environment::Production),
             headers,
             account_id: None,
             trade_token: None,
@@ -71,32 +182,454 @@ impl LiveWebullClient {
             refresh_token: None,
             token_expire: None,
             uuid: None,
+            captcha_ticket: None,
             did,
-            region_code: region_code.unwrap_or(6),
-            zone_var: "dc_core_r001".to_string(),
+            did_store,
+            region_code,
+            zone_var: profile.zone_var.to_string(),
+            region_profile: profile,
             timeout: 15,
+            class_timeouts: HashMap::new(),
+            timeout_override: None,
+            deadline: None,
+            resolver: Arc::new(SymbolResolver::new()),
+            snapshot_store: None,
+            rate_limiter: None,
+            concurrency: None,
+            retry_policy: RetryPolicy::new(),
+            quote_breakers: Arc::new(CircuitBreakers::new(3, std::time::Duration::from_secs(30))),
+            endpoint_breakers: None,
+            payload_hook: None,
+            middleware: MiddlewareStack::default(),
+            fixtures: None,
+            quote_validator: None,
+            managed_accounts: HashSet::new(),
+            fx_rates: Arc::new(FxRateCache::new()),
+            deltas: Arc::new(DeltaState::new()),
         })
     }
 
-    /// Set device ID
-    pub fn set_did(&mut self, did: &str, path: Option<&Path>) -> Result<()> {
-        save_did(did, path)?;
-        self.did = did.to_string();
+    /// Create a new Webull client with the given configuration
+    pub fn with_config(region_code: Option<i32>, config: ClientConfig) -> Result<Self> {
+        let mut client = Self::new(region_code)?;
+        client.apply_config(&config)?;
+        Ok(client)
+    }
+
+    pub(crate) fn apply_config(&mut self, config: &ClientConfig) -> Result<()> {
+        if let Some(rps) = config.rate_limit {
+            check_rate(rps)?;
+        }
+        if config.regional_hosts {
+            self.use_region_profile(self.region().regional_profile());
+        }
+        self.endpoints = config
+            .endpoints
+            .clone()
+            .unwrap_or_else(|| self.region_profile.endpoints(&config.environment));
+        self.timeout = config.timeout;
+        self.class_timeouts = config.class_timeouts.clone();
+        self.rate_limiter = config.rate_limit.map(|rps| {
+            let burst = config.rate_limit_burst.unwrap_or(rps.max(1.0));
+            Arc::new(RateLimiter::with_burst(rps, burst))
+        });
+        self.concurrency = config.max_in_flight.map(ConcurrencyLimiter::new);
+        self.retry_policy = config.retry.clone();
+        self.endpoint_breakers = if config.circuit_breakers.is_empty() {
+            None
+        } else {
+            Some(Arc::new(CircuitBreakers::with_settings(
+                config.circuit_breakers.clone(),
+            )))
+        };
+        Ok(())
+    }
+
+    /// Region the client was created for
+    pub fn region(&self) -> Region {
+        Region::from_code(self.region_code)
+    }
+
+    /// Hosts, headers and currency defaults the client uses
+    pub fn region_profile(&self) -> RegionProfile {
+        self.region_profile.clone()
+    }
+
+    fn use_region_profile(&mut self, profile: RegionProfile) {
+        self.headers.insert(
+            "Accept-Language",
+            HeaderValue::from_static(profile.accept_language),
+        );
+        self.headers
+            .insert("hl", HeaderValue::from_static(profile.language));
         self.headers
-            .insert("did", HeaderValue::from_str(did).unwrap());
+            .insert("lzone", HeaderValue::from_static(profile.zone_var));
+        self.headers
+            .insert("locale", HeaderValue::from_static(profile.locale));
+        self.zone_var = profile.zone_var.to_string();
+        self.region_profile = profile;
+    }
+
+    /// Use `endpoints` for every request, e.g. to go through an API proxy
+    pub fn with_endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.endpoints = endpoints;
+        self
+    }
+
+    /// Replace the base URLs requests are sent to
+    pub fn set_endpoints(&mut self, endpoints: Endpoints) {
+        self.endpoints = endpoints;
+    }
+
+    /// Set the request timeout in seconds for one endpoint class
+    pub fn set_class_timeout(&mut self, class: EndpointClass, seconds: u64) {
+        self.class_timeouts.insert(class, seconds);
+    }
+
+    /// A copy of this client whose requests all use `timeout`
+    ///
+    /// ```no_run
+    /// # async fn run(client: webull_unofficial::LiveWebullClient) -> webull_unofficial::Result<()> {
+    /// use std::time::Duration;
+    ///
+    /// let quote = client.with_timeout(Duration::from_secs(2)).get_quotes("913256135").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_timeout(&self, timeout: std::time::Duration) -> Self {
+        let mut client = self.clone();
+        client.timeout_override = Some(timeout);
+        client
+    }
+
+    /// A copy of this client that fails with `DeadlineExceeded` once
+    /// `budget` has passed, across every request it makes
+    ///
+    /// Meant for composite calls such as `get_bars_range`, where each page
+    /// is a separate request with its own timeout.
+    pub fn with_deadline(&self, budget: std::time::Duration) -> Self {
+        let deadline = tokio::time::Instant::now() + budget;
+        let mut client = self.clone();
+        client.deadline = Some(self.deadline.map_or(deadline, |d| d.min(deadline)));
+        client
+    }
+
+    /// Limit requests per second for each endpoint class
+    pub fn set_rate_limit(&mut self, requests_per_sec: f64) -> Result<()> {
+        check_rate(requests_per_sec)?;
+        self.rate_limiter = Some(Arc::new(RateLimiter::new(requests_per_sec)));
+        Ok(())
+    }
+
+    /// Allow at most `max_in_flight` requests at once across this client and
+    /// its clones, queueing the rest with orders ahead of market data
+    pub fn set_max_in_flight(&mut self, max_in_flight: usize) {
+        self.concurrency = Some(ConcurrencyLimiter::new(max_in_flight));
+    }
+
+    /// Get the concurrency limiter, if one is set
+    pub fn concurrency_limiter(&self) -> Option<&ConcurrencyLimiter> {
+        self.concurrency.as_ref()
+    }
+
+    /// Set the retry policy for transient failures
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry_policy = policy;
+    }
+
+    /// Get the retry policy
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Get the circuit state for an endpoint class
+    ///
+    /// Classes without a configured breaker are always `Closed`.
+    pub fn circuit_state(&self, class: EndpointClass) -> CircuitState {
+        self.endpoint_breakers
+            .as_ref()
+            .map_or(CircuitState::Closed, |breakers| breakers.state(&class))
+    }
+
+    /// Capture every trading request body as sent
+    ///
+    /// The hook sees the final JSON after all client-side additions such as
+    /// `serialId` and `comboType`, which makes it useful for diffing payloads
+    /// between versions or against other clients when debugging rejections.
+    /// Trading PIN hashes and tokens are redacted before the hook runs.
+    pub fn set_payload_hook(&mut self, hook: impl Fn(&CapturedPayload) + Send + Sync + 'static) {
+        self.payload_hook = Some(PayloadHook::new(hook));
+    }
+
+    /// Remove the payload hook
+    pub fn clear_payload_hook(&mut self) {
+        self.payload_hook = None;
+    }
+
+    /// Run `middleware` on every request and response, after any added
+    /// before it
+    pub fn add_middleware(&mut self, middleware: impl Middleware + 'static) {
+        self.middleware.push(Arc::new(middleware));
+    }
+
+    /// Remove all middleware
+    pub fn clear_middleware(&mut self) {
+        self.middleware.clear();
+    }
+
+    /// Validate every quote fetched by this client, publishing anomalies to
+    /// the validator's subscribers
+    pub fn set_quote_validator(&mut self, validator: Arc<QuoteValidator>) {
+        self.quote_validator = Some(validator);
+    }
+
+    /// Get the quote validator, if one is set
+    pub fn quote_validator(&self) -> Option<&Arc<QuoteValidator>> {
+        self.quote_validator.as_ref()
+    }
+
+    /// Record raw responses into `dir`, or replay them from it without
+    /// touching the network
+    ///
+    /// Recorded fixtures are plain JSON files with tokens and password hashes
+    /// redacted, so they can be checked in and used to run integration tests
+    /// offline against real response shapes.
+    pub fn set_fixture_mode(&mut self, dir: impl Into<PathBuf>, mode: FixtureMode) {
+        self.fixtures = Some(FixtureStore::new(dir, mode));
+    }
+
+    /// Stop recording or replaying fixtures
+    pub fn clear_fixture_mode(&mut self) {
+        self.fixtures = None;
+    }
+
+    /// Send a request, applying the client's circuit breakers, rate limit and
+    /// retry policy
+    ///
+    /// Timeouts, connection errors, 5xx and 429 responses are retried with
+    /// exponential backoff. Once retries are exhausted the last error or
+    /// response is returned unchanged and counts as a failure for the
+    /// endpoint class's circuit breaker.
+    ///
+    /// With the `tracing` feature each request gets a `webull_request` span
+    /// with its endpoint, reqid, status and latency.
+    pub(crate) async fn execute(&self, request: RequestBuilder) -> Result<Response> {
+        let mut request = request.build()?;
+        let class = EndpointClass::from_url(request.url().as_str());
+        *request.timeout_mut() = Some(self.request_timeout(class)?);
+        self.middleware.on_request(&mut request);
+        if let Some(hook) = &self.payload_hook {
+            hook.capture(&request);
+        }
+
+        let url = request.url().clone();
+        let result = self.dispatch(request, class).await;
+        self.middleware.on_result(&url, result)
+    }
+
+    /// Replay, or send and record, one prepared request
+    async fn dispatch(&self, request: reqwest::Request, class: EndpointClass) -> Result<Response> {
+        let recording = match &self.fixtures {
+            Some(fixtures) if fixtures.mode() == FixtureMode::Replay => {
+                return fixtures.replay(&request);
+            }
+            Some(fixtures) => request.try_clone().map(|r| (fixtures, r)),
+            None => None,
+        };
+
+        let span = RequestSpan::start(&request, class);
+        let send = async {
+            match self.deadline {
+                Some(deadline) => {
+                    tokio::time::timeout_at(deadline, self.send_guarded(request, class))
+                        .await
+                        .map_err(|_| WebullError::DeadlineExceeded)?
+                }
+                None => self.send_guarded(request, class).await,
+            }
+        };
+        let response = span.run(send).await?;
+        match recording {
+            Some((fixtures, request)) => fixtures.record_response(&request, response).await,
+            None => Ok(response),
+        }
+    }
+
+    /// Timeout for one request: the `with_timeout` override, else the
+    /// class timeout, else the default, cut short by any deadline
+    pub(crate) fn request_timeout(&self, class: EndpointClass) -> Result<std::time::Duration> {
+        let timeout = self.timeout_override.unwrap_or_else(|| {
+            std::time::Duration::from_secs(
+                self.class_timeouts
+                    .get(&class)
+                    .copied()
+                    .unwrap_or(self.timeout),
+            )
+        });
+        match self.deadline {
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+                if remaining.is_zero() {
+                    return Err(WebullError::DeadlineExceeded);
+                }
+                Ok(timeout.min(remaining))
+            }
+            None => Ok(timeout),
+        }
+    }
+
+    async fn send_guarded(
+        &self,
+        request: reqwest::Request,
+        class: EndpointClass,
+    ) -> Result<Response> {
+        let breakers = match &self.endpoint_breakers {
+            Some(breakers) => breakers,
+            None => return self.send_with_retry(request, class).await,
+        };
+        let permit = breakers
+            .allow(&class)
+            .ok_or_else(|| WebullError::CircuitOpen(format!("{:?}", class)))?;
+
+        let result = self.send_with_retry(request, class).await;
+        let failed = match &result {
+            Ok(response) => is_retryable_status(response.status()),
+            Err(e) => e.is_retryable(),
+        };
+        if failed {
+            permit.failure();
+        } else {
+            permit.success();
+        }
+        result
+    }
+
+    async fn send_with_retry(
+        &self,
+        request: reqwest::Request,
+        class: EndpointClass,
+    ) -> Result<Response> {
+        let policy = &self.retry_policy;
+        // Streaming bodies cannot be cloned and are sent only once
+        let retries = if policy.allows_method(request.method()) && request.try_clone().is_some() {
+            policy.max_retries
+        } else {
+            0
+        };
+
+        let mut attempt = 0;
+        loop {
+            // The original request is kept for the final attempt
+            let current = if attempt < retries {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            let result = {
+                // Wait for the rate limit before taking a slot, so throttled
+                // requests don't hold slots that orders are queued for
+                if let Some(limiter) = &self.rate_limiter {
+                    limiter.acquire(class).await;
+                }
+                // Held until the response headers arrive, and given back
+                // before any backoff so other requests aren't held up
+                let _permit = match &self.concurrency {
+                    Some(limiter) => Some(limiter.acquire(RequestPriority::from(class)).await?),
+                    None => None,
+                };
+
+                match current {
+                    Some(current) => self.client.execute(current).await,
+                    None => return Ok(self.client.execute(request).await?),
+                }
+            };
+            match result {
+                Ok(response) if is_retryable_status(response.status()) => {
+                    log::warn!(
+                        "Request to {} returned {}, retrying",
+                        response.url(),
+                        response.status()
+                    );
+                }
+                Ok(response) => return Ok(response),
+                Err(e) => {
+                    let error = WebullError::from(e);
+                    if !error.is_retryable() {
+                        return Err(error);
+                    }
+                    log::warn!("Request failed ({}), retrying", error);
+                }
+            }
+
+            tokio::time::sleep(policy.delay(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    /// Use `did` from now on and save it to the client's device ID store
+    pub fn replace_did(&mut self, did: &str) -> Result<()> {
+        let header = did_header(did)?;
+        self.did_store.save(did)?;
+        self.did = did.to_string();
+        self.headers.insert("did", header);
         Ok(())
     }
 
+    /// Set device ID, saving it to the client's device ID store
+    ///
+    /// With a `path`, the store becomes that file first, so later saves go
+    /// there too.
+    pub fn set_did(&mut self, did: &str, path: Option<&Path>) -> Result<()> {
+        if let Some(path) = path {
+            self.did_store = Arc::new(FileDidStore::at(path));
+        }
+        self.replace_did(did)
+    }
+
+    /// Check if this is a paper trading client
+    pub fn is_paper(&self) -> bool {
+        false
+    }
+
     /// Get device ID
     pub fn get_did(&self) -> &str {
         &self.did
     }
 
+    /// Persist the symbol cache to a JSON file, loading any entries already there
+    pub fn set_symbol_cache_path(&mut self, path: impl Into<PathBuf>) -> Result<()> {
+        self.resolver = Arc::new(SymbolResolver::with_persistence(path)?);
+        Ok(())
+    }
+
+    /// Get the symbol -> ticker ID cache
+    pub fn symbol_resolver(&self) -> &SymbolResolver {
+        &self.resolver
+    }
+
+    /// Enable local account snapshots, stored as JSON lines at `path`
+    pub fn set_snapshot_store(&mut self, path: impl Into<PathBuf>) {
+        self.snapshot_store = Some(Arc::new(SnapshotStore::open(path)));
+    }
+
     /// Get account ID
     pub fn get_account_id_str(&self) -> Option<&str> {
         self.account_id.as_deref()
     }
 
+    /// When the access token expires (Unix seconds), if the login said
+    pub fn token_expires_at(&self) -> Option<i64> {
+        // tokenExpireTime may be in seconds or milliseconds
+        self.token_expire.map(|expire| {
+            if expire > 10_000_000_000 {
+                expire / 1000
+            } else {
+                expire
+            }
+        })
+    }
+
     /// Build request headers
     pub fn build_req_headers(
         &self,
@@ -137,6 +670,9 @@ impl LiveWebullClient {
     }
 
     /// Login to Webull
+    ///
+    /// Returns the step Webull asks for next when it wants a verification
+    /// code, a security question or a captcha before handing out a session.
     pub async fn login(
         &mut self,
         username: &str,
@@ -145,7 +681,7 @@ impl LiveWebullClient {
         mfa: Option<&str>,
         question_id: Option<&str>,
         question_answer: Option<&str>,
-    ) -> Result<LoginResponse> {
+    ) -> Result<LoginOutcome> {
         if username.is_empty() || password.is_empty() {
             return Err(WebullError::InvalidParameter(
                 "Username or password is empty".to_string(),
@@ -153,11 +689,12 @@ impl LiveWebullClient {
         }
 
         let hashed_password = hash_password(password);
-        let account_type = get_account_type(username)?;
+        let identifier = AccountIdentifier::parse(username)?;
+        let account_type = identifier.account_type();
         let device_name = device_name.unwrap_or("default_string");
 
         let mut data = json!({
-            "account": username,
+            "account": identifier.account(),
             "accountType": account_type.to_string(),
             "deviceId": self.did,
             "deviceName": device_name,
@@ -176,6 +713,10 @@ impl LiveWebullClient {
             self.headers.clone()
         };
 
+        if let Some(ticket) = self.captcha_ticket.take() {
+            data["captchaTicket"] = json!(ticket);
+        }
+
         if let (Some(qid), Some(qanswer)) = (question_id, question_answer) {
             data["accessQuestions"] = json!(format!(
                 "[{{\"questionId\":\"{}\", \"answer\":\"{}\"}}]",
@@ -184,15 +725,23 @@ impl LiveWebullClient {
         }
 
         let response = self
-            .client
-            .post(&self.endpoints.login())
-            .headers(headers)
-            .json(&data)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(
+                self.client
+                    .post(&self.endpoints.login())
+                    .headers(headers)
+                    .json(&data),
+            )
             .await?;
 
-        let result: Value = response.json().await?;
+        let status = response.status().as_u16();
+        let text = response.text().await?;
+        if let Some(outcome) = serde_json::from_str::<Value>(&text)
+            .ok()
+            .and_then(|body| LoginOutcome::from_challenge(&body))
+        {
+            return Ok(outcome);
+        }
+        let result: Value = parse_json(status, text)?;
 
         if let Some(access_token) = result.get("accessToken").and_then(|v| v.as_str()) {
             self.access_token = Some(access_token.to_string());
@@ -219,7 +768,9 @@ impl LiveWebullClient {
             // Get account ID after successful login
             self.get_account_id().await?;
 
-            Ok(serde_json::from_value(result)?)
+            Ok(LoginOutcome::Success(Box::new(serde_json::from_value(
+                result,
+            )?)))
         } else {
             Err(WebullError::AuthenticationError("Login failed".to_string()))
         }
@@ -229,7 +780,7 @@ impl LiveWebullClient {
     pub async fn login_with(
         &mut self,
         builder: crate::models::LoginRequestBuilder,
-    ) -> Result<LoginResponse> {
+    ) -> Result<LoginOutcome> {
         let (username, password, device_name, mfa, question_id, question_answer) = builder
             .build()
             .map_err(|e| WebullError::InvalidRequest(e))?;
@@ -244,49 +795,127 @@ impl LiveWebullClient {
         .await
     }
 
-    /// Get MFA code
-    pub async fn get_mfa(&self, username: &str) -> Result<bool> {
-        let account_type = get_account_type(username)?;
+    /// Get MFA code, sent wherever the username points
+    pub async fn get_mfa(&self, username: &str) -> Result<MfaResponse> {
+        self.get_mfa_with(username, MfaOptions::default()).await
+    }
 
+    /// Get MFA code for a chosen purpose
+    pub async fn get_mfa_with(&self, username: &str, options: MfaOptions) -> Result<MfaResponse> {
+        let identifier = AccountIdentifier::parse(username)?;
         let data = json!({
-            "account": username,
-            "accountType": account_type.to_string(),
-            "codeType": 5
+            "account": identifier.account(),
+            "accountType": identifier.account_type().to_string(),
+            "codeType": options.code_type.code()
         });
 
         let response = self
-            .client
-            .post(&self.endpoints.get_mfa())
-            .headers(self.headers.clone())
-            .json(&data)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(
+                self.client
+                    .post(&self.endpoints.get_mfa())
+                    .headers(self.headers.clone())
+                    .json(&data),
+            )
             .await?;
 
-        Ok(response.status().is_success())
+        read_mfa_response(response).await
     }
 
     /// Check MFA code
-    pub async fn check_mfa(&self, username: &str, mfa: &str) -> Result<bool> {
-        let account_type = get_account_type(username)?;
+    pub async fn check_mfa(&self, username: &str, mfa: &str) -> Result<MfaResponse> {
+        self.check_mfa_with(username, mfa, MfaOptions::default())
+            .await
+    }
 
+    /// Check MFA code requested with `get_mfa_with`; pass the same options
+    pub async fn check_mfa_with(
+        &self,
+        username: &str,
+        mfa: &str,
+        options: MfaOptions,
+    ) -> Result<MfaResponse> {
+        let identifier = AccountIdentifier::parse(username)?;
         let data = json!({
-            "account": username,
-            "accountType": account_type.to_string(),
+            "account": identifier.account(),
+            "accountType": identifier.account_type().to_string(),
             "code": mfa,
-            "codeType": 5
+            "codeType": options.code_type.code()
         });
 
         let response = self
-            .client
-            .post(&self.endpoints.check_mfa())
-            .headers(self.headers.clone())
-            .json(&data)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(
+                self.client
+                    .post(&self.endpoints.check_mfa())
+                    .headers(self.headers.clone())
+                    .json(&data),
+            )
             .await?;
 
-        Ok(response.status().is_success())
+        read_mfa_response(response).await
+    }
+
+    /// Fetch the captcha for a login refused with `CaptchaRequired`
+    pub async fn get_captcha(
+        &self,
+        username: &str,
+        challenge: &LoginChallenge,
+    ) -> Result<CaptchaPayload> {
+        let identifier = AccountIdentifier::parse(username)?;
+
+        let data = json!({
+            "account": identifier.account(),
+            "accountType": identifier.account_type().to_string(),
+            "deviceId": self.did,
+            "token": challenge.token,
+        });
+
+        let response = self
+            .execute(
+                self.client
+                    .post(self.endpoints.captcha_query())
+                    .headers(self.headers.clone())
+                    .json(&data),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+        CaptchaPayload::from_response(&result)
+            .ok_or_else(|| WebullError::ParseError("No captcha in response".to_string()))
+    }
+
+    /// Submit a captcha solution: the horizontal offset in pixels at which
+    /// the puzzle piece fits
+    ///
+    /// On success the next `login` call carries the proof of the solved
+    /// captcha; a rejected solution is an `AuthenticationError`.
+    pub async fn submit_captcha(
+        &mut self,
+        username: &str,
+        captcha: &CaptchaPayload,
+        x: i32,
+    ) -> Result<()> {
+        let identifier = AccountIdentifier::parse(username)?;
+
+        let data = json!({
+            "account": identifier.account(),
+            "accountType": identifier.account_type().to_string(),
+            "deviceId": self.did,
+            "captchaId": captcha.captcha_id,
+            "x": x,
+        });
+
+        let response = self
+            .execute(
+                self.client
+                    .post(self.endpoints.captcha_verify())
+                    .headers(self.headers.clone())
+                    .json(&data),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+        self.captcha_ticket = Some(CaptchaPayload::ticket_from_response(&result)?);
+        Ok(())
     }
 
     /// Logout
@@ -294,11 +923,7 @@ impl LiveWebullClient {
         let headers = self.build_req_headers(false, false, true);
 
         let response = self
-            .client
-            .post(&self.endpoints.logout())
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(self.client.post(&self.endpoints.logout()).headers(headers))
             .await?;
 
         if response.status().is_success() {
@@ -316,20 +941,26 @@ impl LiveWebullClient {
 
     /// Refresh login token
     pub async fn refresh_login(&mut self) -> Result<LoginResponse> {
+        let result = self.exchange_refresh_token().await;
+        crate::telemetry::record_token_refresh(result.is_ok());
+        result
+    }
+
+    async fn exchange_refresh_token(&mut self) -> Result<LoginResponse> {
         let refresh_token = self
             .refresh_token
             .as_ref()
             .ok_or(WebullError::SessionExpired)?;
 
         let response = self
-            .client
-            .post(&self.endpoints.refresh_login(refresh_token))
-            .headers(self.headers.clone())
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(
+                self.client
+                    .post(&self.endpoints.refresh_login(refresh_token))
+                    .headers(self.headers.clone()),
+            )
             .await?;
 
-        let result: Value = response.json().await?;
+        let result: Value = read_json(response).await?;
 
         if let Some(access_token) = result.get("accessToken").and_then(|v| v.as_str()) {
             self.access_token = Some(access_token.to_string());
@@ -355,36 +986,71 @@ impl LiveWebullClient {
         }
     }
 
+    /// Get every account on the login, including Webull Advisors accounts
+    pub async fn get_accounts(&mut self) -> Result<Vec<LinkedAccount>> {
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .get(self.endpoints.account_id())
+                    .headers(headers),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+        let accounts = LinkedAccount::from_list_response(&result);
+        self.managed_accounts = accounts
+            .iter()
+            .filter(|a| a.is_managed())
+            .map(|a| a.account_id.clone())
+            .collect();
+        Ok(accounts)
+    }
+
     /// Get account ID
+    ///
+    /// Picks the first self-directed account, falling back to the first
+    /// account if every account is managed.
     pub async fn get_account_id(&mut self) -> Result<String> {
+        let accounts = self.get_accounts().await?;
+        let account = accounts
+            .iter()
+            .find(|a| !a.is_managed())
+            .or_else(|| accounts.first())
+            .ok_or(WebullError::AccountNotFound)?;
+
+        self.account_id = Some(account.account_id.clone());
+        Ok(account.account_id.clone())
+    }
+
+    /// Get the holdings of a Webull Advisors account
+    pub async fn get_managed_portfolio(&self, account_id: &str) -> Result<ManagedPortfolio> {
         let headers = self.build_req_headers(false, false, true);
 
         let response = self
-            .client
-            .get(&self.endpoints.account_id())
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(
+                self.client
+                    .get(self.endpoints.account(account_id))
+                    .headers(headers),
+            )
             .await?;
 
-        let result: Value = response.json().await?;
+        let result: Value = read_json(response).await?;
+        Ok(ManagedPortfolio::from_value(account_id, &result))
+    }
 
-        if let Some(data) = result.get("data").and_then(|v| v.as_array()) {
-            if let Some(first_account) = data.first() {
-                // Try to get secAccountId as either a string or number
-                if let Some(account_id) = first_account.get("secAccountId") {
-                    let account_id_str = match account_id {
-                        Value::String(s) => s.clone(),
-                        Value::Number(n) => n.to_string(),
-                        _ => return Err(WebullError::AccountNotFound),
-                    };
-                    self.account_id = Some(account_id_str.clone());
-                    return Ok(account_id_str);
-                }
-            }
-        }
+    /// Whether an account was listed as a Webull Advisors account
+    pub fn is_managed_account(&self, account_id: &str) -> bool {
+        self.managed_accounts.contains(account_id)
+    }
 
-        Err(WebullError::AccountNotFound)
+    /// Refuse to trade in a managed account
+    fn ensure_tradable(&self, account_id: &str) -> Result<()> {
+        if self.is_managed_account(account_id) {
+            return Err(WebullError::ManagedAccount(account_id.to_string()));
+        }
+        Ok(())
     }
 
     /// Get trade token
@@ -398,15 +1064,15 @@ impl LiveWebullClient {
         let headers = self.build_req_headers(false, false, true);
 
         let response = self
-            .client
-            .post(&self.endpoints.trade_token())
-            .headers(headers)
-            .json(&data)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(
+                self.client
+                    .post(&self.endpoints.trade_token())
+                    .headers(headers)
+                    .json(&data),
+            )
             .await?;
 
-        let result: Value = response.json().await?;
+        let result: Value = read_json(response).await?;
 
         // Debug: print response to understand the structure
         // eprintln!("Trade token response: {}", serde_json::to_string_pretty(&result).unwrap_or_default());
@@ -450,48 +1116,21 @@ impl LiveWebullClient {
         let headers = self.build_req_headers(false, false, true);
 
         let response = self
-            .client
-            .get(&self.endpoints.account(account_id))
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(
+                self.client
+                    .get(&self.endpoints.account(account_id))
+                    .headers(headers),
+            )
             .await?;
 
-        let result: Value = response.json().await?;
+        let result: Value = read_json(response).await?;
 
         // Debug: Print the response to see field names
         // eprintln!("Account response: {}", serde_json::to_string_pretty(&result).unwrap_or_default());
 
         let mut account: AccountDetail = serde_json::from_value(result)?;
 
-        // Process accountMembers to extract key financial values
-        if let Some(ref members) = account.account_members {
-            for member in members {
-                match member.key.as_str() {
-                    "totalMarketValue" => {
-                        account.total_market_value = member.value.parse::<f64>().ok();
-                    }
-                    "cashBalance" => {
-                        account.cash_balance = member.value.parse::<f64>().ok();
-                    }
-                    "dayBuyingPower" | "overnightBuyingPower" => {
-                        // Use dayBuyingPower as the primary buying power
-                        if member.key == "dayBuyingPower" {
-                            account.buying_power = member.value.parse::<f64>().ok();
-                        }
-                    }
-                    "unsettledFunds" => {
-                        account.unsettled_funds = member.value.parse::<f64>().ok();
-                    }
-                    _ => {}
-                }
-            }
-
-            // If total_cash wasn't in the members, try to use cash_balance
-            if account.total_cash.is_none() && account.cash_balance.is_some() {
-                account.total_cash = account.cash_balance;
-            }
-        }
+        account.compute_member_fields();
 
         Ok(account)
     }
@@ -508,15 +1147,9 @@ impl LiveWebullClient {
         // Use the same endpoint as get_account which includes positions
         let url = self.endpoints.account(account_id);
 
-        let response = self
-            .client
-            .get(&url)
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
-            .await?;
+        let response = self.execute(self.client.get(&url).headers(headers)).await?;
 
-        let result: Value = response.json().await?;
+        let result: Value = read_json(response).await?;
 
         if let Some(positions) = result.get("positions") {
             match serde_json::from_value::<Vec<Position>>(positions.clone()) {
@@ -535,103 +1168,210 @@ impl LiveWebullClient {
         }
     }
 
-    /// Get current open orders (from account data)
-    pub async fn get_orders(&self, _page_size: Option<i32>) -> Result<Vec<Order>> {
-        // Get account data which contains openOrders
-        let account_data = self.get_account_raw().await?;
-
-        // Extract openOrders from the account data
-        if let Some(open_orders) = account_data.get("openOrders") {
-            // Live API has both status and statusCode fields, we need to handle this
-            if let Some(orders_array) = open_orders.as_array() {
-                let mut parsed_orders = Vec::new();
+    /// Get current open orders
+    ///
+    /// Pages through the orders endpoint filtered to working orders,
+    /// `page_size` (default 100) at a time, until every open order is read.
+    pub async fn get_orders(&self, page_size: Option<i32>) -> Result<Vec<Order>> {
+        let orders: Vec<HistoryOrder> = self
+            .history_orders_paginated("Working", page_size.unwrap_or(100))
+            .try_collect()
+            .await?;
 
-                for order_value in orders_array {
-                    // Clone and modify the order value to fix field conflicts
-                    let mut order = order_value.clone();
+        Ok(orders
+            .into_iter()
+            .filter(|order| order.status.is_open())
+            .map(Order::from)
+            .collect())
+    }
 
-                    // Use statusCode if present, otherwise use status
-                    if order.get("statusCode").is_some() {
-                        if let Some(obj) = order.as_object_mut() {
-                            obj.remove("status"); // Remove the conflicting status field
-                        }
-                    }
+    /// Get historical orders
+    pub async fn get_history_orders(&self, status: &str, count: i32) -> Result<Vec<HistoryOrder>> {
+        let result = self.get_history_orders_raw(status, count).await?;
+        Ok(HistoryOrder::from_response(&result))
+    }
 
-                    // Try to parse the modified order
-                    match serde_json::from_value::<Order>(order) {
-                        Ok(parsed) => parsed_orders.push(parsed),
-                        Err(e) => {
-                            eprintln!("Failed to parse order: {}", e);
-                        }
-                    }
-                }
+    /// Get historical orders as raw JSON
+    pub async fn get_history_orders_raw(&self, status: &str, count: i32) -> Result<Value> {
+        self.get_history_orders_page(status, count, None).await
+    }
 
-                Ok(parsed_orders)
-            } else {
-                Ok(Vec::new())
-            }
-        } else {
-            Ok(Vec::new())
-        }
+    /// Stream the full order history, following pagination transparently
+    ///
+    /// Pages of `page_size` orders are fetched lazily as the stream is consumed.
+    pub fn history_orders_paginated<'a>(
+        &'a self,
+        status: &'a str,
+        page_size: i32,
+    ) -> impl Stream<Item = Result<HistoryOrder>> + Send + 'a {
+        HistoryPager::stream(page_size, move |before| {
+            self.get_history_orders_page(status, page_size, before)
+        })
     }
 
-    /// Get account data as raw JSON (for extracting openOrders)
-    async fn get_account_raw(&self) -> Result<Value> {
+    /// Fetch one page of order history created before `before` (milliseconds)
+    async fn get_history_orders_page(
+        &self,
+        status: &str,
+        count: i32,
+        before: Option<i64>,
+    ) -> Result<Value> {
         let account_id = self
             .account_id
             .as_ref()
             .ok_or(WebullError::AccountNotFound)?;
 
-        let headers = self.build_req_headers(false, false, true);
+        let headers = self.build_req_headers(true, false, true);
 
-        let response = self
-            .client
-            .get(&self.endpoints.account(account_id))
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
-            .await?;
+        let url = self
+            .endpoints
+            .orders_with_status(account_id, count, status, before);
+
+        let response = self.execute(self.client.get(&url).headers(headers)).await?;
 
-        Ok(response.json().await?)
+        read_json(response).await
     }
 
-    /// Get historical orders
-    pub async fn get_history_orders(&self, status: &str, count: i32) -> Result<Value> {
+    /// Place order
+    pub async fn place_order(&self, order: &PlaceOrderRequest) -> Result<String> {
         let account_id = self
             .account_id
             .as_ref()
             .ok_or(WebullError::AccountNotFound)?;
+        self.ensure_tradable(account_id)?;
 
-        let headers = self.build_req_headers(true, false, true);
+        if self.trade_token.is_none() {
+            return Err(WebullError::TradeTokenNotAvailable);
+        }
+
+        validate_expiration(
+            &order.time_in_force,
+            order.expire_date,
+            crate::market::today(),
+        )
+        .map_err(WebullError::InvalidRequest)?;
+        if order.short_sale {
+            self.check_short_sale(order).await?;
+        }
+
+        let headers = self.build_req_headers(true, true, true);
 
-        let url = format!("{}{}", self.endpoints.orders(account_id, count), status);
+        let order_data = Self::order_payload(order)?;
 
         let response = self
-            .client
-            .get(&url)
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(
+                self.client
+                    .post(&self.endpoints.place_orders(account_id))
+                    .headers(headers)
+                    .json(&order_data),
+            )
             .await?;
 
-        Ok(response.json().await?)
+        let result: Value = read_json(response).await?;
+        Self::placed_order_id(&result)
     }
 
-    /// Place order
-    pub async fn place_order(&self, order: &PlaceOrderRequest) -> Result<String> {
+    /// Place a multi-leg option order, returning the order ID
+    ///
+    /// Build the request by hand or with `options::StrategyBuilder`.
+    pub async fn place_option_spread(&self, order: &PlaceOptionSpreadRequest) -> Result<String> {
         let account_id = self
             .account_id
             .as_ref()
             .ok_or(WebullError::AccountNotFound)?;
+        self.ensure_tradable(account_id)?;
 
         if self.trade_token.is_none() {
             return Err(WebullError::TradeTokenNotAvailable);
         }
 
         let headers = self.build_req_headers(true, true, true);
+        let order_data = order.payload()?;
 
-        // Create order data with proper formatting
-        let mut order_data = serde_json::to_value(order)?;
+        let response = self
+            .execute(
+                self.client
+                    .post(self.endpoints.place_option_orders(account_id))
+                    .headers(headers)
+                    .json(&order_data),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+        Self::placed_order_id(&result)
+    }
+
+    /// The order ID from a place order response
+    fn placed_order_id(result: &Value) -> Result<String> {
+        // Check for orderId in data field or directly in result
+        let order_id = result
+            .get("data")
+            .and_then(|d| d.get("orderId"))
+            .or_else(|| result.get("orderId"));
+
+        if let Some(order_id_val) = order_id {
+            // Handle both string and number formats
+            let order_id_str = match order_id_val {
+                Value::String(s) => s.clone(),
+                Value::Number(n) => n.to_string(),
+                _ => return Err(WebullError::ApiError("Invalid orderId format".to_string())),
+            };
+            Ok(order_id_str)
+        } else {
+            Err(WebullError::ApiError("Failed to place order".to_string()))
+        }
+    }
+
+    /// Get borrow availability and fee rate for shorting a ticker
+    pub async fn get_shortability(&self, ticker_id: &str) -> Result<Shortability> {
+        let headers = self.build_req_headers(true, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .get(self.endpoints.is_tradable(ticker_id))
+                    .headers(headers),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+        Shortability::from_permission_response(ticker_id, &result)
+    }
+
+    /// Reject a short sale the account or the ticker can't support
+    async fn check_short_sale(&self, order: &PlaceOrderRequest) -> Result<()> {
+        if !self.get_account().await?.supports_short_selling() {
+            return Err(WebullError::InvalidRequest(
+                "Account does not support short selling".to_string(),
+            ));
+        }
+        self.check_shortable(order).await
+    }
+
+    /// Reject a short sale of a ticker that can't be borrowed
+    pub(crate) async fn check_shortable(&self, order: &PlaceOrderRequest) -> Result<()> {
+        if order.action != OrderAction::Sell {
+            return Err(WebullError::InvalidRequest(
+                "short sale orders must sell".to_string(),
+            ));
+        }
+        let shortability = self.get_shortability(&order.ticker_id.to_string()).await?;
+        if !shortability.shortable {
+            return Err(WebullError::InvalidRequest(format!(
+                "Ticker {} is not available to short",
+                order.ticker_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Build the JSON body Webull expects for a stock order
+    ///
+    /// Public only so the benchmarks can reach it; not part of the API.
+    #[doc(hidden)]
+    pub fn order_payload(order: &PlaceOrderRequest) -> Result<Value> {
+        // Create order data with proper formatting
+        let mut order_data = serde_json::to_value(order)?;
 
         // Add required fields for live trading
         order_data["comboType"] = json!("NORMAL");
@@ -671,42 +1411,59 @@ impl LiveWebullClient {
             }
         }
 
+        Ok(order_data)
+    }
+
+    /// Ask the order-check endpoint for the acceptable limit price range
+    ///
+    /// Returns `None` if the order has no limit price or the response carries no band.
+    pub async fn get_price_band(&self, order: &PlaceOrderRequest) -> Result<Option<PriceBand>> {
+        if order.limit_price.is_none() {
+            return Ok(None);
+        }
+
+        let account_id = self
+            .account_id
+            .as_ref()
+            .ok_or(WebullError::AccountNotFound)?;
+
+        let headers = self.build_req_headers(true, true, true);
+        let order_data = Self::order_payload(order)?;
+
         let response = self
-            .client
-            .post(&self.endpoints.place_orders(account_id))
-            .headers(headers)
-            .json(&order_data)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(
+                self.client
+                    .post(self.endpoints.check_order(account_id))
+                    .headers(headers)
+                    .json(&order_data),
+            )
             .await?;
 
-        let result: Value = response.json().await?;
-
-        // Check for orderId in data field or directly in result
-        let order_id = result
-            .get("data")
-            .and_then(|d| d.get("orderId"))
-            .or_else(|| result.get("orderId"));
+        let result: Value = read_json(response).await?;
+        Ok(PriceBand::from_check_response(&result))
+    }
 
-        if let Some(order_id_val) = order_id {
-            // Handle both string and number formats
-            let order_id_str = match order_id_val {
-                Value::String(s) => s.clone(),
-                Value::Number(n) => n.to_string(),
-                _ => return Err(WebullError::ApiError("Invalid orderId format".to_string())),
-            };
-            Ok(order_id_str)
-        } else {
-            Err(WebullError::ApiError("Failed to place order".to_string()))
+    /// Validate an order's limit price against the ticker's current price band
+    pub async fn validate_price_band(&self, order: &PlaceOrderRequest) -> Result<()> {
+        match (order.limit_price, self.get_price_band(order).await?) {
+            (Some(price), Some(band)) => band.check(price),
+            _ => Ok(()),
         }
     }
 
+    /// Place order after validating its limit price against the price band
+    pub async fn place_order_checked(&self, order: &PlaceOrderRequest) -> Result<String> {
+        self.validate_price_band(order).await?;
+        self.place_order(order).await
+    }
+
     /// Cancel order
     pub async fn cancel_order(&self, order_id: &str) -> Result<bool> {
         let account_id = self
             .account_id
             .as_ref()
             .ok_or(WebullError::AccountNotFound)?;
+        self.ensure_tradable(account_id)?;
 
         if self.trade_token.is_none() {
             return Err(WebullError::TradeTokenNotAvailable);
@@ -715,27 +1472,17 @@ impl LiveWebullClient {
         let headers = self.build_req_headers(true, true, true);
 
         // Python adds order_id and a UUID to the cancel URL
-        let uuid = Uuid::new_v4();
-        let url = format!(
-            "{}{}/{}",
-            self.endpoints.cancel_order(account_id),
-            order_id,
-            uuid
-        );
+        let serial = Uuid::new_v4().to_string();
+        let url = self.endpoints.cancel_order(account_id, order_id, &serial);
 
         let data = json!({});
         let response = self
-            .client
-            .post(&url)
-            .headers(headers)
-            .json(&data)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(self.client.post(&url).headers(headers).json(&data))
             .await?;
 
         // Check the response for success field
         if response.status().is_success() {
-            let result: Value = response.json().await?;
+            let result: Value = read_json(response).await?;
 
             // The API returns code "200" for success but success field is false
             // Check for code field first
@@ -753,31 +1500,168 @@ impl LiveWebullClient {
         Ok(false)
     }
 
+    /// Cancel every open order, or only those for `ticker_id`
+    ///
+    /// Cancellations are sent concurrently. Each order's outcome is returned
+    /// keyed by order ID, so one failure doesn't hide the others.
+    pub async fn cancel_all_orders(
+        &self,
+        ticker_id: Option<i64>,
+    ) -> Result<BTreeMap<String, Result<bool>>> {
+        let orders = self.get_orders(None).await?;
+        let cancels = orders
+            .into_iter()
+            .filter(|order| ticker_id.is_none_or(|id| order.ticker_id() == Some(id)))
+            .map(|order| async move {
+                let result = self.cancel_order(&order.order_id).await;
+                (order.order_id, result)
+            });
+        Ok(future::join_all(cancels).await.into_iter().collect())
+    }
+
     /// Get quotes
+    ///
+    /// Falls back from the fintech quote gateway to the broker hosts when a
+    /// host fails with a transient error. Hosts that keep failing are skipped
+    /// for 30 seconds.
     pub async fn get_quotes(&self, ticker_id: &str) -> Result<Quote> {
+        let mut last_error = None;
+        // Try each quote host in turn, skipping hosts whose breaker is open
+        for url in self.endpoints.quotes_fallbacks(ticker_id) {
+            let host = url::Url::parse(&url)
+                .ok()
+                .and_then(|u| u.host_str().map(String::from))
+                .unwrap_or_else(|| url.clone());
+            let permit = match self.quote_breakers.allow(&host) {
+                Some(permit) => permit,
+                None => continue,
+            };
+
+            match self.get_quote_from(&url).await {
+                Ok(quote) => {
+                    permit.success();
+                    if let Some(validator) = &self.quote_validator {
+                        validator.validate(&quote);
+                    }
+                    return Ok(quote);
+                }
+                Err(e) if e.is_retryable() => {
+                    log::warn!("Quote host {} failed ({}), trying next", host, e);
+                    permit.failure();
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error
+            .unwrap_or_else(|| WebullError::ApiError("All quote hosts unavailable".to_string())))
+    }
+
+    /// Record bid/ask snapshots for tickers into `dir`, one
+    /// `<ticker_id>.jsonl` file each
+    ///
+    /// Webull has no historical bid/ask endpoint, so spread-sensitive
+    /// backtests need quotes recorded as they happen. Configure the returned
+    /// recorder, then `run_until` a time; read the files back with
+    /// `QuoteHistory::load`.
+    pub fn record_quotes(
+        &self,
+        ticker_ids: &[&str],
+        dir: impl Into<std::path::PathBuf>,
+    ) -> QuoteRecorder<'_> {
+        QuoteRecorder::new(
+            self,
+            ticker_ids.iter().map(|s| s.to_string()).collect(),
+            dir.into(),
+        )
+    }
+
+    /// Get a quote by symbol, e.g. "AAPL"
+    ///
+    /// The ticker ID comes from the symbol cache, so only the first call for
+    /// a symbol costs an extra lookup.
+    pub async fn get_quote_by_symbol(&self, symbol: &str) -> Result<Quote> {
+        let ticker_id = self.resolve(symbol).await?.to_string();
+        self.get_quotes(&ticker_id).await
+    }
+
+    /// Get a quote including pre-market and after-hours trading
+    ///
+    /// Outside regular hours `Quote::close` stays at the last regular-session
+    /// price; the extended-session price is in `p_price`, and
+    /// `Quote::latest_price` picks whichever is current.
+    pub async fn get_quote_extended(&self, ticker_id: &str) -> Result<Quote> {
+        let quote = self
+            .get_quote_from(&self.endpoints.quotes_extended(ticker_id))
+            .await?;
+        if let Some(validator) = &self.quote_validator {
+            validator.validate(&quote);
+        }
+        Ok(quote)
+    }
+
+    /// Get only the quote fields that changed since `since`
+    ///
+    /// Pass `None` the first time and the returned `checkpoint` after that.
+    /// The quote is still fetched in full; the saving is in what the caller
+    /// has to store and forward, e.g. to a mobile client.
+    pub async fn get_quote_delta(&self, ticker_id: &str, since: Option<i64>) -> Result<QuoteDelta> {
+        let quote = self.get_quote_extended(ticker_id).await?;
+        self.deltas.quote_delta(
+            ticker_id,
+            since,
+            &quote,
+            chrono::Utc::now().timestamp_millis(),
+        )
+    }
+
+    async fn get_quote_from(&self, url: &str) -> Result<Quote> {
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self.execute(self.client.get(url).headers(headers)).await?;
+
+        let result: Value = read_json(response).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Get quotes for several tickers in one request
+    ///
+    /// Tickers the API has no quote for are skipped, so match results up by
+    /// `Quote::ticker_id` rather than position.
+    pub async fn get_quotes_batch(&self, ticker_ids: &[&str]) -> Result<Vec<Quote>> {
+        if ticker_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
         let headers = self.build_req_headers(false, false, true);
 
         let response = self
-            .client
-            .get(&self.endpoints.quotes(ticker_id))
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(
+                self.client
+                    .get(self.endpoints.quotes_batch(ticker_ids))
+                    .headers(headers),
+            )
             .await?;
 
-        let result: Value = response.json().await?;
-        Ok(serde_json::from_value(result)?)
+        let result: Value = read_json(response).await?;
+        let quotes = Quote::from_batch_response(&result);
+        if let Some(validator) = &self.quote_validator {
+            for quote in &quotes {
+                validator.validate(quote);
+            }
+        }
+        Ok(quotes)
     }
 
     /// Get bars/candles
     pub async fn get_bars(
         &self,
         ticker_id: &str,
-        interval: &str,
+        interval: BarInterval,
         count: i32,
         timestamp: Option<i64>,
     ) -> Result<Vec<Bar>> {
-        let interval = parse_interval(interval)?;
         let headers = self.build_req_headers(false, false, true);
 
         // Use current timestamp if not provided (like Python does)
@@ -790,74 +1674,102 @@ impl LiveWebullClient {
 
         let url = self
             .endpoints
-            .bars(ticker_id, &interval, count, Some(timestamp));
-
-        let response = self
-            .client
-            .get(&url)
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
-            .await?;
-
-        let result: Value = response.json().await?;
-
-        // Parse bars from the response
-        // The response is an array with the first element containing the data
-        if let Some(result_array) = result.as_array() {
-            if let Some(first_item) = result_array.first() {
-                if let Some(data_array) = first_item.get("data").and_then(|v| v.as_array()) {
-                    let mut bars = Vec::new();
-                    for data_str in data_array {
-                        if let Some(s) = data_str.as_str() {
-                            // Parse comma-separated values: timestamp,open,close,high,low,?,volume,vwap
-                            let parts: Vec<&str> = s.split(',').collect();
-                            if parts.len() >= 7 {
-                                let timestamp = parts[0].parse::<i64>().unwrap_or(0);
-                                let open = parts[1].parse::<f64>().unwrap_or(0.0);
-                                let close = parts[2].parse::<f64>().unwrap_or(0.0);
-                                let high = parts[3].parse::<f64>().unwrap_or(0.0);
-                                let low = parts[4].parse::<f64>().unwrap_or(0.0);
-                                let volume = parts[6].parse::<i64>().unwrap_or(0);
-                                let vwap = if parts.len() > 7 && parts[7] != "null" {
-                                    parts[7].parse::<f64>().unwrap_or(0.0)
-                                } else {
-                                    0.0
-                                };
-
-                                bars.push(Bar {
-                                    open,
-                                    high,
-                                    low,
-                                    close,
-                                    volume: volume as f64,
-                                    vwap,
-                                    timestamp,
-                                });
-                            }
-                        }
-                    }
-                    return Ok(bars);
-                }
-            }
-        }
+            .bars(ticker_id, interval.as_str(), count, Some(timestamp));
+
+        let response = self.execute(self.client.get(&url).headers(headers)).await?;
+
+        let result: Value = read_json(response).await?;
+
+        Ok(Bar::from_chart_response(&result))
+    }
 
-        Ok(Vec::new())
+    /// Get bars starting at or after `since` (Unix seconds), oldest first
+    ///
+    /// Only as many bars as could have formed since `since` are requested;
+    /// gaps longer than one request can cover are paged with
+    /// `get_bars_range`. Pass the last bar's timestamp to also get its final
+    /// values.
+    pub async fn get_bars_since(
+        &self,
+        ticker_id: &str,
+        interval: BarInterval,
+        since: i64,
+    ) -> Result<Vec<Bar>> {
+        let now = chrono::Utc::now().timestamp();
+        let count = (now - since).max(0) / interval_seconds(interval) + 2;
+        let bars = if count > BAR_PAGE_SIZE as i64 {
+            self.get_bars_range(ticker_id, interval, since, now).await?
+        } else {
+            self.get_bars(ticker_id, interval, count as i32, None)
+                .await?
+        };
+        Ok(bars_since(bars, since))
+    }
+
+    /// Get every bar between `start` and `end` (Unix seconds), oldest first
+    ///
+    /// A single `get_bars` call is capped at roughly 1200 bars; this pages
+    /// backwards from `end` so years of daily or weeks of minute data can be
+    /// fetched in one call.
+    pub async fn get_bars_range(
+        &self,
+        ticker_id: &str,
+        interval: BarInterval,
+        start: i64,
+        end: i64,
+    ) -> Result<Vec<Bar>> {
+        let mut pager = BarPager::new(start, end);
+        while !pager.done {
+            let page = self
+                .get_bars(ticker_id, interval, BAR_PAGE_SIZE, Some(pager.cursor))
+                .await?;
+            pager.absorb(page);
+        }
+        Ok(pager.into_bars())
     }
 
     /// Search ticker
     pub async fn find_ticker(&self, keyword: &str) -> Result<Vec<Ticker>> {
+        self.search_tickers(keyword, self.region_code).await
+    }
+
+    /// Find tickers matching `keyword` that pass `filter`
+    pub async fn find_ticker_with(
+        &self,
+        keyword: &str,
+        filter: &TickerFilter,
+    ) -> Result<Vec<Ticker>> {
+        let region_id = filter.region_id.unwrap_or(self.region_code);
+        let mut tickers = self.search_tickers(keyword, region_id).await?;
+        tickers.retain(|ticker| filter.matches(ticker));
+        Ok(tickers)
+    }
+
+    /// The ticker listed under exactly `symbol`, rather than every fuzzy match
+    ///
+    /// Tickers in the client's region win over listings elsewhere. The ticker
+    /// ID is added to the symbol cache.
+    pub async fn find_ticker_exact(&self, symbol: &str) -> Result<Ticker> {
+        let tickers = self.find_ticker(symbol).await?;
+        let ticker = Ticker::exact_match(&tickers, symbol, self.region_code)
+            .cloned()
+            .ok_or_else(|| WebullError::TickerNotFound(symbol.to_string()))?;
+        self.resolver.insert(symbol, ticker.ticker_id)?;
+        Ok(ticker)
+    }
+
+    async fn search_tickers(&self, keyword: &str, region_code: i32) -> Result<Vec<Ticker>> {
         let headers = self.build_req_headers(false, false, true);
 
         let response = self
-            .client
-            .get(&self.endpoints.stock_id(keyword, self.region_code))
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(
+                self.client
+                    .get(self.endpoints.stock_id(keyword, region_code))
+                    .headers(headers),
+            )
             .await?;
 
-        let result: Value = response.json().await?;
+        let result: Value = read_json(response).await?;
 
         // println!("Ticker search response: {}", serde_json::to_string_pretty(&result).unwrap_or_default());
 
@@ -868,25 +1780,293 @@ impl LiveWebullClient {
         }
     }
 
+    /// Find a ticker by its ISIN, e.g. `US0378331005`
+    pub async fn find_ticker_by_isin(&self, isin: &str) -> Result<Vec<Ticker>> {
+        if !is_valid_isin(isin) {
+            return Err(WebullError::InvalidParameter(format!(
+                "Invalid ISIN: {}",
+                isin
+            )));
+        }
+        self.find_ticker_by_identifier("isin", isin.trim()).await
+    }
+
+    /// Find a ticker by its CUSIP, e.g. `037833100`
+    pub async fn find_ticker_by_cusip(&self, cusip: &str) -> Result<Vec<Ticker>> {
+        if !is_valid_cusip(cusip) {
+            return Err(WebullError::InvalidParameter(format!(
+                "Invalid CUSIP: {}",
+                cusip
+            )));
+        }
+        self.find_ticker_by_identifier("cusip", cusip.trim()).await
+    }
+
+    /// Identifier search, falling back to a keyword search when the
+    /// identifier endpoint has no match
+    async fn find_ticker_by_identifier(
+        &self,
+        id_type: &str,
+        identifier: &str,
+    ) -> Result<Vec<Ticker>> {
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .get(
+                        self.endpoints
+                            .ticker_by_identifier(id_type, identifier, self.region_code),
+                    )
+                    .headers(headers),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+        let tickers: Vec<Ticker> = result
+            .get("data")
+            .and_then(|data| data.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|item| serde_json::from_value(item.clone()).ok())
+            .collect();
+
+        if tickers.is_empty() {
+            self.find_ticker(identifier).await
+        } else {
+            Ok(tickers)
+        }
+    }
+
+    /// Page through the instrument lists for a region and write them to `path`
+    ///
+    /// Only instruments whose type is in `types` are kept (all types when
+    /// empty). The file can be read back with `InstrumentUniverse::load` to map
+    /// symbols to ticker IDs without per-symbol searches. Returns the number
+    /// of instruments written.
+    pub async fn dump_instrument_universe(
+        &self,
+        region_code: i32,
+        types: &[InstrumentType],
+        path: impl AsRef<Path>,
+    ) -> Result<usize> {
+        const PAGE_SIZE: usize = 500;
+
+        let mut seen = HashSet::new();
+        let mut instruments = Vec::new();
+        let mut offset = 0;
+        loop {
+            let headers = self.build_req_headers(false, false, true);
+            let response = self
+                .execute(
+                    self.client
+                        .get(
+                            self.endpoints
+                                .instrument_list(region_code, offset, PAGE_SIZE),
+                        )
+                        .headers(headers),
+                )
+                .await?;
+
+            let result: Value = read_json(response).await?;
+            let page = Instrument::from_list_response(&result);
+            let page_len = page.len();
+            let mut new_entries = 0;
+            for instrument in page {
+                if seen.insert(instrument.ticker_id) {
+                    new_entries += 1;
+                    if types.is_empty() || types.contains(&instrument.instrument_type) {
+                        instruments.push(instrument);
+                    }
+                }
+            }
+
+            // Stop on a short page, or if the server ignored the offset
+            if page_len < PAGE_SIZE || new_entries == 0 {
+                break;
+            }
+            offset += page_len;
+        }
+
+        let count = instruments.len();
+        InstrumentUniverse::new(instruments).save(path)?;
+        Ok(count)
+    }
+
     /// Get option chains
     pub async fn get_options(&self, ticker: &str) -> Result<Vec<OptionContract>> {
+        self.get_options_chain(&OptionsQuery::new(ticker)).await
+    }
+
+    /// Get the option chain filtered by expiration, direction and strike range
+    pub async fn get_options_chain(&self, query: &OptionsQuery) -> Result<Vec<OptionContract>> {
+        let ticker_id = self.resolve_ticker_id(&query.ticker).await?;
+
+        let mut data = json!({
+            "count": -1,
+            "direction": query.direction(),
+            "expireCycle": [3, 2, 4],
+            "type": 0,
+            "quoteMultiplier": 100,
+            "tickerId": ticker_id
+        });
+        if let Some(date) = &query.expiration_date {
+            data["expireDate"] = json!(date);
+        }
+
+        let result = self.post_option_strategy_list(&data).await?;
+
+        let mut contracts = OptionContract::from_response(&result);
+        contracts.retain(|c| query.matches(c));
+        Ok(contracts)
+    }
+
+    /// Get the option chain for one expiration date (`YYYY-MM-DD`)
+    ///
+    /// Pair with `get_option_expirations` to walk a chain one expiration at a
+    /// time, keeping each response small.
+    pub async fn get_options_by_expiration(
+        &self,
+        ticker: &str,
+        date: &str,
+    ) -> Result<Vec<OptionContract>> {
+        if chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+            return Err(WebullError::InvalidParameter(format!(
+                "Invalid expiration date: {}",
+                date
+            )));
+        }
+        self.get_options_chain(&OptionsQuery {
+            expiration_date: Some(date.to_string()),
+            ..OptionsQuery::new(ticker)
+        })
+        .await
+    }
+
+    /// Download option chains for many underlyings
+    ///
+    /// Only contracts expiring within `dte_range` days are kept. Configure
+    /// concurrency, progress reporting and storage on the returned download,
+    /// then `run` it.
+    pub fn download_option_chains(
+        &self,
+        symbols: &[&str],
+        dte_range: std::ops::RangeInclusive<i64>,
+    ) -> OptionChainDownload<'_> {
+        OptionChainDownload::new(
+            self,
+            symbols.iter().map(|s| s.to_string()).collect(),
+            dte_range,
+        )
+    }
+
+    /// Get the available option expiration dates for a ticker, soonest first
+    pub async fn get_option_expirations(&self, ticker: &str) -> Result<Vec<OptionExpiration>> {
+        let ticker_id = self.resolve_ticker_id(ticker).await?;
+
+        let data = json!({
+            "count": -1,
+            "direction": "all",
+            "tickerId": ticker_id
+        });
+
+        let result = self.post_option_strategy_list(&data).await?;
+        Ok(OptionExpiration::from_response(&result))
+    }
+
+    /// Get the available option expiration dates for a ticker
+    #[deprecated(note = "use get_option_expirations")]
+    pub async fn get_options_expiration_dates(
+        &self,
+        ticker: &str,
+    ) -> Result<Vec<OptionExpiration>> {
+        self.get_option_expirations(ticker).await
+    }
+
+    /// Get a real-time quote for a single option contract
+    pub async fn get_option_quote(&self, option_ticker_id: &str) -> Result<OptionQuote> {
         let headers = self.build_req_headers(false, false, true);
 
         let response = self
-            .client
-            .get(&self.endpoints.options(ticker))
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(
+                self.client
+                    .get(self.endpoints.option_quotes())
+                    .headers(headers)
+                    .query(&[("derivativeIds", option_ticker_id)]),
+            )
             .await?;
 
-        let result: Value = response.json().await?;
+        let result: Value = read_json(response).await?;
 
-        if let Some(data) = result.get("data") {
-            Ok(serde_json::from_value(data.clone())?)
-        } else {
-            Ok(Vec::new())
+        let quote = match result.get("data") {
+            Some(Value::Array(items)) => items.first(),
+            Some(item) => Some(item),
+            None => result.as_array().and_then(|items| items.first()),
+        };
+
+        quote
+            .and_then(OptionQuote::from_value)
+            .ok_or_else(|| WebullError::TickerNotFound(option_ticker_id.to_string()))
+    }
+
+    /// Get the most active option contracts market-wide by volume or open interest
+    pub async fn get_top_options(
+        &self,
+        rank: TopOptionsRank,
+        count: i32,
+    ) -> Result<Vec<TopOption>> {
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .get(
+                        self.endpoints
+                            .top_options(rank.as_str(), self.region_code, count),
+                    )
+                    .headers(headers),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+        Ok(TopOption::from_response(&result))
+    }
+
+    async fn post_option_strategy_list(&self, data: &Value) -> Result<Value> {
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .post(self.endpoints.options_exp_date_new())
+                    .headers(headers)
+                    .json(data),
+            )
+            .await?;
+
+        read_json(response).await
+    }
+
+    /// Accept either a numeric ticker ID or a symbol to look up
+    async fn resolve_ticker_id(&self, ticker: &str) -> Result<i64> {
+        match ticker.parse::<i64>() {
+            Ok(id) => Ok(id),
+            Err(_) => self.resolve(ticker).await,
+        }
+    }
+
+    /// Resolve a symbol to its ticker ID, using the cache when possible
+    pub async fn resolve(&self, symbol: &str) -> Result<i64> {
+        if let Some(ticker_id) = self.resolver.get(symbol) {
+            return Ok(ticker_id);
         }
+
+        let id = self.get_ticker(symbol).await?;
+        let ticker_id = id
+            .parse::<i64>()
+            .map_err(|_| WebullError::ParseError(format!("Invalid ticker ID: {}", id)))?;
+        self.resolver.insert(symbol, ticker_id)?;
+        Ok(ticker_id)
     }
 
     /// Get ticker information including ticker ID
@@ -895,91 +2075,922 @@ impl LiveWebullClient {
 
         // Use stock_id endpoint with region 6 (US)
         let response = self
-            .client
-            .get(&self.endpoints.stock_id(symbol, 6))
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
-            .await?;
-
-        let result: Value = response.json().await?;
-
-        if let Some(data) = result.get("data").and_then(|v| v.as_array()) {
-            // Find the ticker matching the symbol
-            for ticker in data {
-                if ticker.get("disSymbol").and_then(|v| v.as_str()) == Some(symbol)
-                    || ticker.get("symbol").and_then(|v| v.as_str()) == Some(symbol)
-                {
-                    if let Some(ticker_id) = ticker.get("tickerId") {
-                        // Handle both string and number ticker IDs
-                        let ticker_id_str = match ticker_id {
-                            Value::String(s) => s.clone(),
-                            Value::Number(n) => n.to_string(),
-                            _ => continue,
-                        };
-                        return Ok(ticker_id_str);
-                    }
-                }
-            }
-        }
+            .execute(
+                self.client
+                    .get(&self.endpoints.stock_id(symbol, 6))
+                    .headers(headers),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
 
-        Err(WebullError::TickerNotFound(symbol.to_string()))
+        // Find the ticker matching the symbol
+        parse_list(&result, |ticker| {
+            if ticker.get("disSymbol").and_then(|v| v.as_str()) != Some(symbol)
+                && ticker.get("symbol").and_then(|v| v.as_str()) != Some(symbol)
+            {
+                return None;
+            }
+            // Handle both string and number ticker IDs
+            match ticker.get("tickerId")? {
+                Value::String(s) => Some(s.clone()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            }
+        })
+        .into_iter()
+        .next()
+        .ok_or_else(|| WebullError::TickerNotFound(symbol.to_string()))
     }
 
     /// Get news
     pub async fn get_news(&self, symbol: &str, last_id: i64, count: i32) -> Result<Vec<News>> {
         // First get the ticker ID
-        let ticker_id = self.get_ticker(symbol).await?;
+        let ticker_id = self.resolve(symbol).await?.to_string();
+        self.get_ticker_news(&ticker_id, last_id, count).await
+    }
 
+    /// Get news for a ticker ID without resolving a symbol
+    pub async fn get_ticker_news(
+        &self,
+        ticker_id: &str,
+        last_id: i64,
+        count: i32,
+    ) -> Result<Vec<News>> {
         let headers = self.build_req_headers(false, false, true);
 
         let response = self
-            .client
-            .get(&self.endpoints.news(&ticker_id, last_id, count))
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(
+                self.client
+                    .get(self.endpoints.news(ticker_id, last_id, count))
+                    .headers(headers),
+            )
             .await?;
 
-        let result: Vec<News> = response.json().await?;
-        Ok(result)
+        read_json(response).await
+    }
+
+    /// Get headlines for a ticker ID published after the headline `since`
+    ///
+    /// Pass `None` the first time and the returned `checkpoint` after that.
+    /// Pages of `count` are fetched until `since` is reached, up to ten
+    /// pages; beyond that the result is marked `full`.
+    pub async fn get_news_delta(
+        &self,
+        ticker_id: &str,
+        since: Option<i64>,
+        count: i32,
+    ) -> Result<NewsDelta> {
+        const MAX_PAGES: usize = 10;
+
+        let mut pager = NewsPager::new(since, count, MAX_PAGES);
+        while !pager.done {
+            let page = self.get_ticker_news(ticker_id, pager.cursor, count).await?;
+            pager.absorb(page);
+        }
+        let gap = pager.gap;
+        Ok(NewsDelta::new(ticker_id, since, pager.into_news(), gap))
     }
 
-    /// Get fundamentals
+    /// Get valuation, share count, dividend and classification data for a ticker
     pub async fn get_fundamentals(&self, ticker: &str) -> Result<Fundamental> {
         let headers = self.build_req_headers(false, false, true);
 
         let response = self
-            .client
-            .get(&self.endpoints.fundamentals(ticker))
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(
+                self.client
+                    .get(&self.endpoints.fundamentals(ticker))
+                    .headers(headers),
+            )
             .await?;
 
-        let result: Value = response.json().await?;
-        Ok(serde_json::from_value(result)?)
+        let result: Value = read_json(response).await?;
+        Ok(Fundamental::from_value(&result))
     }
 
-    /// Run screener
-    pub async fn screener(&self, request: &ScreenerRequest) -> Result<Vec<Ticker>> {
+    /// Get the exchange rate from one currency to another on a date
+    ///
+    /// Rates are cached, so repeated conversions for the same day only hit
+    /// the network once.
+    pub async fn get_fx_rate(&self, from: &str, to: &str, date: chrono::NaiveDate) -> Result<f64> {
+        if let Some(rate) = self.fx_rates.get(from, to, date) {
+            return Ok(rate);
+        }
+
         let headers = self.build_req_headers(false, false, true);
+        let day = date.format("%Y-%m-%d").to_string();
 
         let response = self
-            .client
-            .post(&self.endpoints.screener())
-            .headers(headers)
-            .json(request)
-            .timeout(std::time::Duration::from_secs(self.timeout))
-            .send()
+            .execute(
+                self.client
+                    .get(self.endpoints.fx_rate(from, to, &day))
+                    .headers(headers),
+            )
             .await?;
 
-        let result: Value = response.json().await?;
+        let result: Value = read_json(response).await?;
+        let rate = FxRate::from_response(from, to, date, &result)
+            .ok_or_else(|| WebullError::ApiError(format!("No {}/{} rate for {}", from, to, day)))?;
+        self.fx_rates.insert(&rate);
+        Ok(rate.rate)
+    }
 
-        if let Some(data) = result.get("data") {
-            Ok(serde_json::from_value(data.clone())?)
-        } else {
-            Ok(Vec::new())
-        }
+    /// Cache of FX rates fetched so far, for converting reports offline
+    pub fn fx_rates(&self) -> Arc<FxRateCache> {
+        self.fx_rates.clone()
     }
+
+    /// Get every earnings release Webull lists for a ticker, newest first
+    pub async fn get_ticker_earnings(&self, ticker_id: &str) -> Result<Vec<EarningsEvent>> {
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .get(self.endpoints.ticker_earnings(ticker_id))
+                    .headers(headers),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+        let mut events = EarningsEvent::from_list_response(&result);
+        events.sort_by(|a, b| b.release_date.cmp(&a.release_date));
+        Ok(events)
+    }
+
+    /// Get a ticker's income statement, balance sheet or cash flow
+    /// statement for the last eight quarters or years, most recent first
+    pub async fn get_financials(
+        &self,
+        ticker_id: &str,
+        statement: StatementType,
+        period: ReportPeriod,
+    ) -> Result<FinancialStatement> {
+        let headers = self.build_req_headers(false, false, true);
+        let (report_type, fiscal_period) = period.query_values();
+
+        let response = self
+            .execute(
+                self.client
+                    .get(self.endpoints.financials(
+                        ticker_id,
+                        statement.as_path(),
+                        report_type,
+                        fiscal_period,
+                        8,
+                    ))
+                    .headers(headers),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+        Ok(FinancialStatement::from_response(
+            statement, period, &result,
+        ))
+    }
+
+    /// Get the next earnings release for a ticker with whisper EPS, guidance
+    /// and the price reactions to past releases
+    ///
+    /// Returns `None` if no upcoming release is scheduled.
+    pub async fn get_ticker_earnings_whisper(
+        &self,
+        ticker_id: &str,
+    ) -> Result<Option<EarningsEvent>> {
+        let events = self.get_ticker_earnings(ticker_id).await?;
+
+        let history: Vec<PriceReaction> = events
+            .iter()
+            .filter(|e| e.is_reported())
+            .filter_map(|e| {
+                Some(PriceReaction {
+                    release_date: e.release_date.clone()?,
+                    change_pct: e.price_reaction?,
+                })
+            })
+            .collect();
+
+        // Earliest release that hasn't reported yet
+        Ok(events
+            .into_iter()
+            .rfind(|e| !e.is_reported())
+            .map(|mut next| {
+                next.reaction_history = history;
+                next
+            }))
+    }
+
+    /// Get every earnings release scheduled between two dates (inclusive),
+    /// earliest first
+    ///
+    /// Pages through the market calendar until it passes `end_date`.
+    pub async fn get_earnings_calendar(
+        &self,
+        start_date: chrono::NaiveDate,
+        end_date: chrono::NaiveDate,
+    ) -> Result<Vec<EarningsEvent>> {
+        const PAGE_SIZE: i32 = 50;
+        // Guards against a calendar that never runs out of pages
+        const MAX_PAGES: i32 = 100;
+
+        let start = start_date.format("%Y-%m-%d").to_string();
+        let mut events = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let headers = self.build_req_headers(false, false, true);
+            let response = self
+                .execute(
+                    self.client
+                        .get(self.endpoints.calendar_events(
+                            "earnings",
+                            self.region_code,
+                            &start,
+                            page,
+                            PAGE_SIZE,
+                        ))
+                        .headers(headers),
+                )
+                .await?;
+
+            let result: Value = read_json(response).await?;
+            let batch = EarningsEvent::from_list_response(&result);
+            let past_end = batch
+                .iter()
+                .filter_map(EarningsEvent::release_day)
+                .any(|day| day > end_date);
+            let last_page = (batch.len() as i32) < PAGE_SIZE;
+            events.extend(batch);
+            if past_end || last_page {
+                break;
+            }
+        }
+
+        events.retain(|e| {
+            e.release_day()
+                .is_some_and(|day| (start_date..=end_date).contains(&day))
+        });
+        events.sort_by(|a, b| a.release_date.cmp(&b.release_date));
+        Ok(events)
+    }
+
+    /// Run a screener query, returning matching tickers in the requested order
+    pub async fn screener(&self, request: &ScreenerRequest) -> Result<Vec<Ticker>> {
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .post(&self.endpoints.screener())
+                    .headers(headers)
+                    .json(request),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+        Ok(ScreenerRequest::parse_response(&result))
+    }
+
+    /// Get all watchlists with their tickers
+    pub async fn get_watchlists(&self) -> Result<Vec<Watchlist>> {
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .get(self.endpoints.portfolio_lists())
+                    .headers(headers),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+
+        if let Some(lists) = result.get("portfolioList") {
+            Ok(serde_json::from_value(lists.clone())?)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Create a new watchlist, returning its ID
+    pub async fn create_watchlist(&self, name: &str) -> Result<String> {
+        if name.is_empty() {
+            return Err(WebullError::InvalidParameter(
+                "Watchlist name is empty".to_string(),
+            ));
+        }
+
+        let headers = self.build_req_headers(false, false, true);
+        let data = json!({ "name": name });
+
+        let response = self
+            .execute(
+                self.client
+                    .post(self.endpoints.create_watchlist())
+                    .headers(headers)
+                    .json(&data),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+
+        let watchlist_id = result
+            .get("data")
+            .and_then(|d| d.get("id"))
+            .or_else(|| result.get("id"));
+
+        match watchlist_id {
+            Some(Value::String(s)) => Ok(s.clone()),
+            Some(Value::Number(n)) => Ok(n.to_string()),
+            _ => Err(WebullError::ApiError(
+                "Failed to create watchlist".to_string(),
+            )),
+        }
+    }
+
+    /// Add tickers to a watchlist
+    pub async fn add_to_watchlist(&self, watchlist_id: &str, ticker_ids: &[i64]) -> Result<bool> {
+        let headers = self.build_req_headers(false, false, true);
+        let data = json!({ "tickerIds": ticker_ids });
+
+        let response = self
+            .execute(
+                self.client
+                    .post(self.endpoints.add_watchlist_tickers(watchlist_id))
+                    .headers(headers)
+                    .json(&data),
+            )
+            .await?;
+
+        check_success(response).await
+    }
+
+    /// Remove tickers from a watchlist
+    pub async fn remove_from_watchlist(
+        &self,
+        watchlist_id: &str,
+        ticker_ids: &[i64],
+    ) -> Result<bool> {
+        let headers = self.build_req_headers(false, false, true);
+        let data = json!({ "tickerIds": ticker_ids });
+
+        let response = self
+            .execute(
+                self.client
+                    .post(self.endpoints.remove_watchlist_tickers(watchlist_id))
+                    .headers(headers)
+                    .json(&data),
+            )
+            .await?;
+
+        check_success(response).await
+    }
+
+    /// Delete a watchlist
+    pub async fn delete_watchlist(&self, watchlist_id: &str) -> Result<bool> {
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .post(self.endpoints.delete_watchlist(watchlist_id))
+                    .headers(headers),
+            )
+            .await?;
+
+        check_success(response).await
+    }
+
+    /// Export every watchlist in the portable format
+    pub async fn export_watchlists(&self) -> Result<WatchlistBundle> {
+        let watchlists = self.get_watchlists().await?;
+        Ok(WatchlistBundle::from_watchlists(&watchlists))
+    }
+
+    /// Make the named watchlist hold exactly `symbols`, in any order
+    ///
+    /// The watchlist is created if no list has that name (case-insensitively).
+    /// Returns the changes that were applied.
+    pub async fn sync_watchlist<S: AsRef<str>>(
+        &self,
+        name: &str,
+        symbols: &[S],
+    ) -> Result<WatchlistDiff> {
+        let desired = PortableWatchlist::new(name, symbols);
+        let watchlists = self.get_watchlists().await?;
+        let current = watchlists
+            .iter()
+            .find(|list| list.name.eq_ignore_ascii_case(name));
+        let mut diff = WatchlistDiff::between(current, &desired);
+        if diff.is_empty() {
+            return Ok(diff);
+        }
+
+        let watchlist_id = match diff.existing_id()? {
+            Some(id) => id.to_string(),
+            None => self.create_watchlist(name).await?,
+        };
+        diff.watchlist_id = Some(watchlist_id.clone());
+
+        if !diff.add.is_empty() {
+            let mut ticker_ids = Vec::with_capacity(diff.add.len());
+            for symbol in &diff.add {
+                ticker_ids.push(self.resolve(symbol).await?);
+            }
+            self.add_to_watchlist(&watchlist_id, &ticker_ids).await?;
+        }
+        if !diff.remove.is_empty() {
+            let ticker_ids: Vec<i64> = diff.remove.iter().map(|(id, _)| *id).collect();
+            self.remove_from_watchlist(&watchlist_id, &ticker_ids)
+                .await?;
+        }
+        Ok(diff)
+    }
+
+    /// Sync every watchlist in a bundle; lists not in the bundle are left alone
+    pub async fn import_watchlists(&self, bundle: &WatchlistBundle) -> Result<Vec<WatchlistDiff>> {
+        let mut diffs = Vec::with_capacity(bundle.watchlists.len());
+        for list in &bundle.watchlists {
+            diffs.push(self.sync_watchlist(&list.name, &list.symbols).await?);
+        }
+        Ok(diffs)
+    }
+
+    /// List price alerts
+    pub async fn list_alerts(&self) -> Result<Vec<Alert>> {
+        let result = self.list_alerts_raw().await?;
+
+        if let Some(data) = result.get("data") {
+            Ok(serde_json::from_value(data.clone())?)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    async fn list_alerts_raw(&self) -> Result<Value> {
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .get(self.endpoints.list_alerts())
+                    .headers(headers),
+            )
+            .await?;
+
+        read_json(response).await
+    }
+
+    /// Create a price alert for a ticker
+    pub async fn create_alert(&self, ticker_id: i64, rule: &AlertRule) -> Result<bool> {
+        let headers = self.build_req_headers(false, false, true);
+
+        let data = json!({
+            "tickerId": ticker_id,
+            "warningFrequency": rule.frequency,
+            "warningInterval": rule.interval,
+            "warningInput": {
+                "originTickerId": ticker_id,
+                "tickerId": ticker_id,
+                "rules": rule.rules
+            }
+        });
+
+        let response = self
+            .execute(
+                self.client
+                    .post(self.endpoints.add_alert())
+                    .headers(headers)
+                    .json(&data),
+            )
+            .await?;
+
+        check_success(response).await
+    }
+
+    /// Create a price alert using builder pattern
+    pub async fn create_alert_with(
+        &self,
+        ticker_id: i64,
+        builder: crate::models::AlertRuleBuilder,
+    ) -> Result<bool> {
+        let rule = builder.build().map_err(WebullError::InvalidRequest)?;
+        self.create_alert(ticker_id, &rule).await
+    }
+
+    /// Delete all price and smart alerts set on a ticker
+    ///
+    /// Webull returns one alert entry per ticker and removes it as a whole,
+    /// so alerts are addressed by ticker ID rather than by rule.
+    pub async fn delete_alert(&self, ticker_id: &str) -> Result<bool> {
+        let result = self.list_alerts_raw().await?;
+
+        // The remove endpoint expects the full alert object back with rules switched off
+        let mut alert = result
+            .get("data")
+            .and_then(|v| v.as_array())
+            .and_then(|alerts| {
+                alerts.iter().find(|a| match a.get("tickerId") {
+                    Some(Value::String(s)) => s == ticker_id,
+                    Some(Value::Number(n)) => n.to_string() == ticker_id,
+                    _ => false,
+                })
+            })
+            .cloned()
+            .ok_or_else(|| {
+                WebullError::InvalidParameter(format!("No alerts for ticker: {}", ticker_id))
+            })?;
+
+        if let Some(mut warning) = alert.get("tickerWarning").cloned() {
+            warning["remove"] = json!(true);
+            alert["warningInput"] = warning;
+        }
+        if let Some(event_warning) = alert.get_mut("eventWarning") {
+            event_warning["remove"] = json!(true);
+            if let Some(rules) = event_warning
+                .get_mut("rules")
+                .and_then(|r| r.as_array_mut())
+            {
+                for rule in rules {
+                    rule["active"] = json!("off");
+                }
+            }
+        }
+
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .post(self.endpoints.remove_alert())
+                    .headers(headers)
+                    .json(&alert),
+            )
+            .await?;
+
+        check_success(response).await
+    }
+
+    /// Get the intraday volume profile (30-minute buckets averaged over `days` days)
+    pub async fn get_ticker_minute_volume(
+        &self,
+        ticker_id: &str,
+        days: i32,
+    ) -> Result<MinuteVolumeProfile> {
+        if days <= 0 {
+            return Err(WebullError::InvalidParameter(
+                "days must be positive".to_string(),
+            ));
+        }
+
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .get(self.endpoints.ticker_minute_volume(ticker_id, days))
+                    .headers(headers),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+
+        // The buckets may be returned directly or wrapped in "data"
+        let buckets = if result.is_array() {
+            Some(&result)
+        } else {
+            result.get("data")
+        };
+
+        let buckets = match buckets {
+            Some(b) => serde_json::from_value(b.clone())?,
+            None => Vec::new(),
+        };
+
+        Ok(MinuteVolumeProfile {
+            ticker_id: ticker_id.to_string(),
+            days,
+            buckets,
+        })
+    }
+
+    /// Get the most recent `count` trades (time & sales), oldest first
+    pub async fn get_ticks(&self, ticker_id: &str, count: i32) -> Result<Vec<TradeTick>> {
+        if count <= 0 {
+            return Err(WebullError::InvalidParameter(
+                "count must be positive".to_string(),
+            ));
+        }
+
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .get(self.endpoints.trade_ticks(ticker_id, count))
+                    .headers(headers),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+        Ok(TradeTick::from_response(&result))
+    }
+
+    /// Get an ETF's constituents, heaviest first
+    pub async fn get_etf_holdings(&self, ticker_id: &str) -> Result<Vec<EtfHolding>> {
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .get(self.endpoints.etf_holdings(ticker_id))
+                    .headers(headers),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+        Ok(EtfHolding::from_response(&result))
+    }
+
+    /// Get an ETF's fund details: expense ratio, assets under management
+    /// and top holdings
+    pub async fn get_etf_profile(&self, ticker_id: &str) -> Result<EtfProfile> {
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .get(self.endpoints.etf_profile(ticker_id))
+                    .headers(headers),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+        Ok(EtfProfile::from_response(ticker_id, &result))
+    }
+
+    /// Get the IPO calendar for a region (6 = US), soonest first
+    pub async fn get_ipo_calendar(&self, region_code: i32) -> Result<Vec<IpoListing>> {
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .get(self.endpoints.ipo_calendar(region_code))
+                    .headers(headers),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+        Ok(IpoListing::from_response(&result))
+    }
+
+    /// Check session health with a cheap authenticated call
+    ///
+    /// Never fails; problems are reported in the returned `HealthStatus`.
+    pub async fn health_check(&self) -> HealthStatus {
+        let mut status = HealthStatus {
+            trade_token: self.trade_token.is_some(),
+            account_id: self.account_id.clone(),
+            ..Default::default()
+        };
+
+        if self.access_token.is_none() {
+            status.error = Some("Not logged in".to_string());
+            return status;
+        }
+
+        if let Some(expire_secs) = self.token_expires_at() {
+            let now = chrono::Utc::now().timestamp();
+            if expire_secs <= now {
+                status.error = Some("Session expired".to_string());
+                return status;
+            }
+        }
+
+        let headers = self.build_req_headers(false, false, true);
+        let started = std::time::Instant::now();
+
+        let response = self
+            .execute(
+                self.client
+                    .get(self.endpoints.account_id())
+                    .headers(headers),
+            )
+            .await;
+
+        status.latency_ms = Some(started.elapsed().as_millis() as u64);
+
+        // Expired or invalid tokens come back as 200 with an error code
+        match response {
+            Ok(response) => {
+                let http_status = response.status();
+                match check_success(response).await {
+                    Ok(true) => status.authenticated = true,
+                    Ok(false) => {
+                        status.error = Some(format!("Probe returned HTTP {}", http_status));
+                    }
+                    Err(e) => status.error = Some(e.to_string()),
+                }
+            }
+            Err(e) => {
+                status.error = Some(e.to_string());
+            }
+        }
+
+        status
+    }
+
+    /// Get the account's dividend reinvestment settings
+    pub async fn get_drip_settings(&self) -> Result<DripSettings> {
+        let account_id = self
+            .account_id
+            .as_ref()
+            .ok_or(WebullError::AccountNotFound)?;
+        let headers = self.build_req_headers(true, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .get(self.endpoints.drip_settings(account_id))
+                    .headers(headers),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+        DripSettings::from_value(&result)
+    }
+
+    /// Replace the account's dividend reinvestment settings
+    pub async fn update_drip_settings(&self, settings: &DripSettings) -> Result<bool> {
+        let account_id = self
+            .account_id
+            .as_ref()
+            .ok_or(WebullError::AccountNotFound)?;
+        self.ensure_tradable(account_id)?;
+        let headers = self.build_req_headers(true, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .post(self.endpoints.drip_settings(account_id))
+                    .headers(headers)
+                    .json(&settings.to_request()),
+            )
+            .await?;
+
+        check_success(response).await
+    }
+
+    /// Get account activities (trades, dividends, interest, fees, transfers)
+    ///
+    /// All pages are fetched; `start`/`end` are inclusive and `activity_type`
+    /// restricts results to one category. The category is filtered
+    /// client-side, since the endpoint takes no type parameter, so the whole
+    /// date range is downloaded either way.
+    pub async fn get_account_activities(
+        &self,
+        start: Option<chrono::NaiveDate>,
+        end: Option<chrono::NaiveDate>,
+        activity_type: Option<ActivityType>,
+    ) -> Result<Vec<Activity>> {
+        let account_id = self
+            .account_id
+            .as_ref()
+            .ok_or(WebullError::AccountNotFound)?;
+
+        const PAGE_SIZE: usize = 500;
+        // Guards against a history that never runs out of pages
+        const MAX_PAGES: usize = 100;
+
+        let mut seen_ids = HashSet::new();
+        let mut previous_page: Option<Vec<Value>> = None;
+        let mut activities = Vec::new();
+
+        for page_index in 1..=MAX_PAGES {
+            let headers = self.build_req_headers(true, false, true);
+
+            let mut data = json!({
+                "pageIndex": page_index,
+                "pageSize": PAGE_SIZE
+            });
+            if let Some(start) = start {
+                data["startDate"] = json!(start.format("%Y-%m-%d").to_string());
+            }
+            if let Some(end) = end {
+                data["endDate"] = json!(end.format("%Y-%m-%d").to_string());
+            }
+
+            let response = self
+                .execute(
+                    self.client
+                        .post(self.endpoints.account_activities(account_id))
+                        .headers(headers)
+                        .json(&data),
+                )
+                .await?;
+
+            let result: Value = read_json(response).await?;
+
+            let items = list_items(&result);
+
+            // A server that ignores `pageIndex` serves the same page again
+            if previous_page.as_deref() == Some(items) {
+                break;
+            }
+
+            // Identical rows can be genuine, so repeats are only recognised
+            // by activity ID; rows without one are all kept
+            let mut new_entries = 0;
+            for item in items {
+                let activity = Activity::from_value(item);
+                if let Some(id) = &activity.id {
+                    if !seen_ids.insert(id.clone()) {
+                        continue;
+                    }
+                }
+                new_entries += 1;
+                activities.push(activity);
+            }
+
+            if items.len() < PAGE_SIZE || new_entries == 0 {
+                break;
+            }
+            previous_page = Some(items.to_vec());
+        }
+
+        // The type is only filtered here; dates are re-checked since the
+        // endpoint may ignore the range
+        activities.retain(|a| {
+            if activity_type.is_some() && activity_type != Some(a.activity_type) {
+                return false;
+            }
+            match a.date {
+                Some(date) => start.is_none_or(|s| date >= s) && end.is_none_or(|e| date <= e),
+                None => start.is_none() && end.is_none(),
+            }
+        });
+
+        Ok(activities)
+    }
+
+    /// Get recent account security events (logins, password changes)
+    pub async fn get_security_events(&self, count: i32) -> Result<Vec<SecurityEvent>> {
+        let headers = self.build_req_headers(false, false, true);
+
+        let response = self
+            .execute(
+                self.client
+                    .get(self.endpoints.security_events(count))
+                    .headers(headers),
+            )
+            .await?;
+
+        let result: Value = read_json(response).await?;
+        Ok(SecurityEvent::from_list_response(&result))
+    }
+
+    /// Poll the security feed every `poll` and yield events as they appear
+    ///
+    /// Events that already existed when watching started are skipped. Poll
+    /// failures are yielded as errors and polling continues.
+    pub fn watch_security_events(
+        &self,
+        poll: std::time::Duration,
+    ) -> impl Stream<Item = Result<SecurityEvent>> + Send + '_ {
+        let state = (SecurityMonitor::new(), VecDeque::new(), false);
+        stream::unfold(
+            state,
+            move |(mut monitor, mut buffer, mut polled)| async move {
+                loop {
+                    if let Some(event) = buffer.pop_front() {
+                        return Some((Ok(event), (monitor, buffer, polled)));
+                    }
+                    if polled {
+                        tokio::time::sleep(poll).await;
+                    }
+                    polled = true;
+                    match self.get_security_events(50).await {
+                        Ok(events) => buffer.extend(monitor.observe(events)),
+                        Err(e) => return Some((Err(e), (monitor, buffer, polled))),
+                    }
+                }
+            },
+        )
+    }
+}
+
+/// Read a verification code response, which may have an empty body
+async fn read_mfa_response(response: Response) -> Result<MfaResponse> {
+    let status = response.status();
+    let text = response.text().await?;
+    let value = serde_json::from_str::<Value>(&text).unwrap_or(Value::Null);
+    if let Some(error) = WebullError::from_api_body(status.as_u16(), &value) {
+        return Err(error);
+    }
+    Ok(MfaResponse::from_response(status.is_success(), &value))
+}
+
+/// The `did` header for `did`, refusing IDs that can't be sent in one
+fn did_header(did: &str) -> Result<HeaderValue> {
+    HeaderValue::from_str(did)
+        .map_err(|e| WebullError::DeviceIdError(format!("Invalid device ID {:?}: {}", did, e)))
 }
@@ -0,0 +1,345 @@
+// Client-side tax lot tracking with specific lot selection for sells
+
+use crate::{
+    fx::FxRateCache,
+    market,
+    models::{HistoryOrder, OrderAction, OrderStatus},
+};
+use chrono::{DateTime, Months, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How lots are picked when a sell closes part of a position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LotMethod {
+    /// First in, first out
+    Fifo,
+    /// Last in, first out
+    Lifo,
+    /// Highest cost first
+    Hifo,
+}
+
+/// An open tax lot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Lot {
+    pub quantity: f64,
+    pub cost_per_share: f64,
+    pub acquired: NaiveDate,
+    /// Order the lot was opened in, to tell apart lots from the same day
+    #[serde(default)]
+    pub sequence: u64,
+}
+
+/// Portion of a lot closed by a sell
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClosedLot {
+    pub symbol: String,
+    pub quantity: f64,
+    pub acquired: NaiveDate,
+    pub sold: NaiveDate,
+    pub cost_basis: f64,
+    pub proceeds: f64,
+}
+
+impl ClosedLot {
+    /// Realized gain (negative for a loss)
+    pub fn gain(&self) -> f64 {
+        self.proceeds - self.cost_basis
+    }
+
+    /// Realized gain in another currency, converting the cost basis at the
+    /// rate on the purchase date and the proceeds at the rate on the sale date
+    ///
+    /// `None` if either rate is missing from the cache.
+    pub fn gain_converted(&self, rates: &FxRateCache, from: &str, to: &str) -> Option<f64> {
+        let cost_basis = rates.convert(self.cost_basis, from, to, self.acquired)?;
+        let proceeds = rates.convert(self.proceeds, from, to, self.sold)?;
+        Some(proceeds - cost_basis)
+    }
+
+    /// Held for more than one year, i.e. sold after the anniversary of the
+    /// purchase
+    pub fn is_long_term(&self) -> bool {
+        self.acquired
+            .checked_add_months(Months::new(12))
+            .is_some_and(|anniversary| self.sold > anniversary)
+    }
+}
+
+/// Part of a sell with no open lot to close
+///
+/// Short sales, shares transferred in and order history that doesn't reach
+/// back to the buy all end up here rather than failing the whole rebuild.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnmatchedSell {
+    pub symbol: String,
+    pub quantity: f64,
+    pub price: f64,
+    pub sold: NaiveDate,
+}
+
+/// Tracks open lots per symbol and realizes them on sells
+#[derive(Debug, Clone, Default)]
+pub struct LotTracker {
+    lots: HashMap<String, Vec<Lot>>,
+    unmatched: Vec<UnmatchedSell>,
+    next_sequence: u64,
+}
+
+impl LotTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuild lots from filled orders, oldest first
+    ///
+    /// Lots and sales are dated by the US Eastern date of the fill, so
+    /// after-hours fills stay on their trading day. Orders without a fill
+    /// time, ticker or fill price are skipped. Sells beyond the open lots are
+    /// recorded in `unmatched`.
+    pub fn from_history(orders: &[HistoryOrder], method: LotMethod) -> (Self, Vec<ClosedLot>) {
+        let mut fills: Vec<(DateTime<Utc>, &HistoryOrder)> = orders
+            .iter()
+            .filter(|o| matches!(o.status, OrderStatus::Filled | OrderStatus::PartialFilled))
+            .filter(|o| o.ticker.is_some() && o.avg_fill_price.is_some())
+            .filter_map(|o| {
+                let time = o.filled_time.as_deref()?;
+                let time = DateTime::parse_from_rfc3339(time).ok()?.with_timezone(&Utc);
+                Some((time, o))
+            })
+            .collect();
+        fills.sort_by_key(|(time, _)| *time);
+
+        let mut tracker = Self::new();
+        let mut closed = Vec::new();
+        for (time, order) in fills {
+            let symbol = order
+                .ticker
+                .as_ref()
+                .map(|t| t.symbol.as_str())
+                .unwrap_or_default();
+            let price = order.avg_fill_price.unwrap_or_default();
+            let date = market::to_eastern(time).date_naive();
+            match order.action {
+                OrderAction::Buy => tracker.buy(symbol, order.filled_quantity, price, date),
+                OrderAction::Sell => {
+                    closed.extend(tracker.sell(symbol, order.filled_quantity, price, date, method))
+                }
+            }
+        }
+        (tracker, closed)
+    }
+
+    /// Open lots for a symbol
+    pub fn lots(&self, symbol: &str) -> &[Lot] {
+        self.lots.get(symbol).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Total open quantity for a symbol
+    pub fn quantity(&self, symbol: &str) -> f64 {
+        self.lots(symbol).iter().map(|l| l.quantity).sum()
+    }
+
+    /// Sold quantities that had no open lot to close, in the order sold
+    pub fn unmatched(&self) -> &[UnmatchedSell] {
+        &self.unmatched
+    }
+
+    /// Record a purchase as a new lot
+    pub fn buy(&mut self, symbol: &str, quantity: f64, price: f64, date: NaiveDate) {
+        if quantity <= 0.0 {
+            return;
+        }
+        self.next_sequence += 1;
+        self.lots.entry(symbol.to_string()).or_default().push(Lot {
+            quantity,
+            cost_per_share: price,
+            acquired: date,
+            sequence: self.next_sequence,
+        });
+    }
+
+    /// Close lots for a sale, choosing them with `method`
+    ///
+    /// Any quantity beyond the open lots is recorded in `unmatched`.
+    pub fn sell(
+        &mut self,
+        symbol: &str,
+        quantity: f64,
+        price: f64,
+        date: NaiveDate,
+        method: LotMethod,
+    ) -> Vec<ClosedLot> {
+        let lots = self.lots.entry(symbol.to_string()).or_default();
+        // Lots are consumed from the front
+        let opened = |lot: &Lot| (lot.acquired, lot.sequence);
+        match method {
+            LotMethod::Fifo => lots.sort_by_key(opened),
+            LotMethod::Lifo => lots.sort_by_key(|lot| std::cmp::Reverse(opened(lot))),
+            LotMethod::Hifo => lots.sort_by(|a, b| {
+                b.cost_per_share
+                    .total_cmp(&a.cost_per_share)
+                    .then_with(|| opened(a).cmp(&opened(b)))
+            }),
+        }
+
+        let mut remaining = quantity;
+        let mut closed = Vec::new();
+        for lot in lots.iter_mut() {
+            if remaining <= f64::EPSILON {
+                break;
+            }
+            let take = remaining.min(lot.quantity);
+            closed.push(ClosedLot {
+                symbol: symbol.to_string(),
+                quantity: take,
+                acquired: lot.acquired,
+                sold: date,
+                cost_basis: take * lot.cost_per_share,
+                proceeds: take * price,
+            });
+            lot.quantity -= take;
+            remaining -= take;
+        }
+        lots.retain(|lot| lot.quantity > f64::EPSILON);
+
+        // Keep lots in acquisition order for display
+        lots.sort_by_key(opened);
+
+        if remaining > f64::EPSILON {
+            log::warn!(
+                "Sold {} {} beyond the open lots; recording it as unmatched",
+                remaining,
+                symbol
+            );
+            self.unmatched.push(UnmatchedSell {
+                symbol: symbol.to_string(),
+                quantity: remaining,
+                price,
+                sold: date,
+            });
+        }
+        closed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, d).unwrap()
+    }
+
+    fn tracker() -> LotTracker {
+        let mut tracker = LotTracker::new();
+        tracker.buy("AAPL", 10.0, 100.0, day(1));
+        tracker.buy("AAPL", 10.0, 120.0, day(2));
+        tracker.buy("AAPL", 10.0, 110.0, day(3));
+        tracker
+    }
+
+    #[test]
+    fn test_lot_methods() {
+        let mut fifo = tracker();
+        let closed = fifo.sell("AAPL", 15.0, 130.0, day(10), LotMethod::Fifo);
+        assert_eq!(closed.len(), 2);
+        assert_eq!(closed[0].acquired, day(1));
+        assert_eq!(closed[1].quantity, 5.0);
+        assert_eq!(fifo.quantity("AAPL"), 15.0);
+
+        let mut lifo = tracker();
+        let closed = lifo.sell("AAPL", 5.0, 130.0, day(10), LotMethod::Lifo);
+        assert_eq!(closed[0].acquired, day(3));
+        assert_eq!(closed[0].gain(), 100.0);
+
+        let mut hifo = tracker();
+        let closed = hifo.sell("AAPL", 10.0, 130.0, day(10), LotMethod::Hifo);
+        assert_eq!(closed[0].cost_basis, 1200.0);
+        assert_eq!(hifo.lots("AAPL")[0].acquired, day(1));
+        assert!(!closed[0].is_long_term());
+    }
+
+    #[test]
+    fn test_long_term_starts_after_the_anniversary() {
+        let closed = |acquired: &str, sold: &str| ClosedLot {
+            symbol: "AAPL".to_string(),
+            quantity: 1.0,
+            acquired: acquired.parse().unwrap(),
+            sold: sold.parse().unwrap(),
+            cost_basis: 100.0,
+            proceeds: 110.0,
+        };
+        // 366 days, across a leap day, but still on the anniversary
+        assert!(!closed("2023-03-01", "2024-03-01").is_long_term());
+        assert!(closed("2023-03-01", "2024-03-02").is_long_term());
+        assert!(!closed("2024-02-29", "2025-02-28").is_long_term());
+        assert!(closed("2024-02-29", "2025-03-01").is_long_term());
+    }
+
+    #[test]
+    fn test_after_hours_fills_keep_their_trading_day() {
+        let fill = |action: &str, filled: &str| {
+            HistoryOrder::from_value(&serde_json::json!({
+                "orderId": filled,
+                "ticker": {"tickerId": 913256135, "disSymbol": "AAPL", "name": "Apple Inc"},
+                "action": action,
+                "orderType": "LMT",
+                "status": "Filled",
+                "filledQuantity": "10",
+                "avgFilledPrice": "100",
+                "filledTime": filled
+            }))
+            .unwrap()
+        };
+        // 21:00 in New York is already the next day in UTC
+        let orders = [
+            fill("BUY", "2024-06-03T21:00:00-04:00"),
+            fill("SELL", "2024-06-04T20:30:00-04:00"),
+        ];
+        let (_, closed) = LotTracker::from_history(&orders, LotMethod::Fifo);
+        assert_eq!(closed[0].acquired, "2024-06-03".parse().unwrap());
+        assert_eq!(closed[0].sold, "2024-06-04".parse().unwrap());
+    }
+
+    #[test]
+    fn test_same_day_lots_keep_purchase_order() {
+        let mut tracker = LotTracker::new();
+        tracker.buy("AAPL", 10.0, 100.0, day(1));
+        tracker.buy("AAPL", 10.0, 105.0, day(1));
+
+        let mut fifo = tracker.clone();
+        let closed = fifo.sell("AAPL", 10.0, 110.0, day(2), LotMethod::Fifo);
+        assert_eq!(closed[0].cost_basis, 1000.0);
+        assert_eq!(fifo.lots("AAPL")[0].cost_per_share, 105.0);
+
+        let closed = tracker.sell("AAPL", 10.0, 110.0, day(2), LotMethod::Lifo);
+        assert_eq!(closed[0].cost_basis, 1050.0);
+    }
+
+    #[test]
+    fn test_oversell_recorded_as_unmatched() {
+        let mut tracker = tracker();
+        let closed = tracker.sell("AAPL", 31.0, 100.0, day(10), LotMethod::Fifo);
+        assert_eq!(closed.iter().map(|lot| lot.quantity).sum::<f64>(), 30.0);
+        assert!(tracker
+            .sell("MSFT", 1.0, 100.0, day(10), LotMethod::Fifo)
+            .is_empty());
+        assert_eq!(tracker.quantity("AAPL"), 0.0);
+
+        let unmatched = tracker.unmatched();
+        assert_eq!(unmatched.len(), 2);
+        assert_eq!(
+            (unmatched[0].symbol.as_str(), unmatched[0].quantity),
+            ("AAPL", 1.0)
+        );
+        assert_eq!(
+            (unmatched[1].symbol.as_str(), unmatched[1].quantity),
+            ("MSFT", 1.0)
+        );
+    }
+}
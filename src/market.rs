@@ -0,0 +1,418 @@
+// US equity market calendar and trading sessions
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Utc, Weekday,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Part of the trading day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Session {
+    /// 04:00 to the open
+    PreMarket,
+    Regular,
+    /// The close to 20:00
+    AfterHours,
+    Closed,
+}
+
+/// Trading hours of one day, in exchange-local (US Eastern) time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TradingHours {
+    pub date: NaiveDate,
+    pub pre_market_open: NaiveTime,
+    pub open: NaiveTime,
+    pub close: NaiveTime,
+    pub after_hours_close: NaiveTime,
+}
+
+impl TradingHours {
+    /// Whether the regular session ends early (13:00)
+    pub fn is_early_close(&self) -> bool {
+        self.close < regular_close()
+    }
+}
+
+fn time(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).unwrap_or_default()
+}
+
+fn regular_close() -> NaiveTime {
+    time(16, 0)
+}
+
+fn date(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+/// `n`th `weekday` of a month (1-based), or the last one when `n` is 0
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: u32) -> Option<NaiveDate> {
+    if n == 0 {
+        let next_month = if month == 12 {
+            date(year + 1, 1, 1)?
+        } else {
+            date(year, month + 1, 1)?
+        };
+        let mut day = next_month.pred_opt()?;
+        while day.weekday() != weekday {
+            day = day.pred_opt()?;
+        }
+        Some(day)
+    } else {
+        NaiveDate::from_weekday_of_month_opt(year, month, weekday, n as u8)
+    }
+}
+
+/// Western Easter Sunday (anonymous Gregorian algorithm)
+fn easter(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    date(year, month as u32, day as u32)
+}
+
+/// Weekend holidays move to the nearest weekday
+fn observed(day: NaiveDate) -> NaiveDate {
+    match day.weekday() {
+        Weekday::Sat => day - Duration::days(1),
+        Weekday::Sun => day + Duration::days(1),
+        _ => day,
+    }
+}
+
+/// NYSE/Nasdaq holidays and sessions, with US Eastern daylight saving
+///
+/// Holidays and early closes follow the exchanges' standing rules, computed
+/// for any year. One-off closures (national days of mourning, weather) are
+/// not predictable; add them with `add_holiday`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarketCalendar {
+    extra_holidays: BTreeSet<NaiveDate>,
+    extra_early_closes: BTreeSet<NaiveDate>,
+}
+
+impl MarketCalendar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Treat a date as a full-day closure
+    pub fn add_holiday(&mut self, date: NaiveDate) -> &mut Self {
+        self.extra_holidays.insert(date);
+        self
+    }
+
+    /// Treat a date as closing at 13:00
+    pub fn add_early_close(&mut self, date: NaiveDate) -> &mut Self {
+        self.extra_early_closes.insert(date);
+        self
+    }
+
+    /// Exchange holidays observed in a year, by date
+    pub fn holidays(&self, year: i32) -> BTreeMap<NaiveDate, &'static str> {
+        let mut holidays = BTreeMap::new();
+        let mut add = |day: Option<NaiveDate>, name: &'static str| {
+            if let Some(day) = day {
+                if day.year() == year {
+                    holidays.insert(day, name);
+                }
+            }
+        };
+
+        // A Saturday New Year's Day is not observed on the Friday before
+        let new_year = date(year, 1, 1).filter(|d| d.weekday() != Weekday::Sat);
+        add(new_year.map(observed), "New Year's Day");
+        add(
+            nth_weekday(year, 1, Weekday::Mon, 3),
+            "Martin Luther King Jr. Day",
+        );
+        add(
+            nth_weekday(year, 2, Weekday::Mon, 3),
+            "Washington's Birthday",
+        );
+        add(easter(year).map(|d| d - Duration::days(2)), "Good Friday");
+        add(nth_weekday(year, 5, Weekday::Mon, 0), "Memorial Day");
+        if year >= 2022 {
+            add(date(year, 6, 19).map(observed), "Juneteenth");
+        }
+        add(date(year, 7, 4).map(observed), "Independence Day");
+        add(nth_weekday(year, 9, Weekday::Mon, 1), "Labor Day");
+        add(nth_weekday(year, 11, Weekday::Thu, 4), "Thanksgiving Day");
+        add(date(year, 12, 25).map(observed), "Christmas Day");
+
+        for day in &self.extra_holidays {
+            if day.year() == year {
+                holidays.entry(*day).or_insert("Market closed");
+            }
+        }
+        holidays
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays(date.year()).contains_key(&date)
+    }
+
+    pub fn is_trading_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.is_holiday(date)
+    }
+
+    /// Whether the regular session closes at 13:00: July 3rd, the day after
+    /// Thanksgiving and Christmas Eve, when they are trading days
+    pub fn is_early_close(&self, day: NaiveDate) -> bool {
+        if !self.is_trading_day(day) {
+            return false;
+        }
+        let year = day.year();
+        let after_thanksgiving =
+            nth_weekday(year, 11, Weekday::Thu, 4).map(|d| d + Duration::days(1));
+        self.extra_early_closes.contains(&day)
+            || Some(day) == date(year, 7, 3)
+            || Some(day) == after_thanksgiving
+            || Some(day) == date(year, 12, 24)
+    }
+
+    /// Session times for a date, or `None` when the market is closed all day
+    pub fn hours(&self, date: NaiveDate) -> Option<TradingHours> {
+        if !self.is_trading_day(date) {
+            return None;
+        }
+        let early = self.is_early_close(date);
+        Some(TradingHours {
+            date,
+            pre_market_open: time(4, 0),
+            open: time(9, 30),
+            close: if early { time(13, 0) } else { regular_close() },
+            after_hours_close: if early { time(17, 0) } else { time(20, 0) },
+        })
+    }
+
+    /// Session in progress at an instant
+    pub fn session_at(&self, at: DateTime<Utc>) -> Session {
+        let local = to_eastern(at).naive_local();
+        let hours = match self.hours(local.date()) {
+            Some(hours) => hours,
+            None => return Session::Closed,
+        };
+        let now = local.time();
+        if now < hours.pre_market_open || now >= hours.after_hours_close {
+            Session::Closed
+        } else if now < hours.open {
+            Session::PreMarket
+        } else if now < hours.close {
+            Session::Regular
+        } else {
+            Session::AfterHours
+        }
+    }
+
+    /// Whether the regular session is open at an instant
+    pub fn is_open_at(&self, at: DateTime<Utc>) -> bool {
+        self.session_at(at) == Session::Regular
+    }
+
+    /// Next regular-session open strictly after an instant
+    pub fn next_open_after(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        self.next_boundary(at, |hours| hours.open)
+    }
+
+    /// Next regular-session close strictly after an instant
+    pub fn next_close_after(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        self.next_boundary(at, |hours| hours.close)
+    }
+
+    /// Last regular-session close at or before an instant
+    pub fn last_close_before(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let mut day = to_eastern(at).date_naive();
+        for _ in 0..15 {
+            if let Some(hours) = self.hours(day) {
+                let close = from_eastern(day.and_time(hours.close));
+                if close <= at {
+                    return close;
+                }
+            }
+            day = day.pred_opt().unwrap_or(day);
+        }
+        at
+    }
+
+    fn next_boundary(
+        &self,
+        at: DateTime<Utc>,
+        boundary: impl Fn(&TradingHours) -> NaiveTime,
+    ) -> DateTime<Utc> {
+        let mut day = to_eastern(at).date_naive();
+        // No gap between trading days is anywhere near this long
+        for _ in 0..15 {
+            if let Some(hours) = self.hours(day) {
+                let instant = from_eastern(day.and_time(boundary(&hours)));
+                if instant > at {
+                    return instant;
+                }
+            }
+            day = day.succ_opt().unwrap_or(day);
+        }
+        at
+    }
+}
+
+/// US Eastern offset at an instant: EDT from 02:00 on the second Sunday of
+/// March until 02:00 on the first Sunday of November, EST otherwise
+fn eastern_offset(at: DateTime<Utc>) -> FixedOffset {
+    let year = at.year();
+    let transition = |month, n, utc_hour| {
+        nth_weekday(year, month, Weekday::Sun, n)
+            .and_then(|d| d.and_hms_opt(utc_hour, 0, 0))
+            .map(|t| Utc.from_utc_datetime(&t))
+    };
+    // 02:00 EST is 07:00 UTC; 02:00 EDT is 06:00 UTC
+    let dst = match (transition(3, 2, 7), transition(11, 1, 6)) {
+        (Some(start), Some(end)) => at >= start && at < end,
+        _ => false,
+    };
+    let hours = if dst { -4 } else { -5 };
+    FixedOffset::east_opt(hours * 3600).unwrap_or(Utc.fix())
+}
+
+/// Convert an instant to US Eastern time
+pub fn to_eastern(at: DateTime<Utc>) -> DateTime<FixedOffset> {
+    at.with_timezone(&eastern_offset(at))
+}
+
+/// Convert US Eastern wall-clock time to an instant
+///
+/// Times skipped or repeated by a daylight saving change resolve using the
+/// offset in effect just before the change.
+pub fn from_eastern(local: NaiveDateTime) -> DateTime<Utc> {
+    // Guess with EST, then correct with the offset at that instant
+    let guess = Utc.from_utc_datetime(&(local + Duration::hours(5)));
+    let offset = eastern_offset(guess);
+    Utc.from_utc_datetime(&(local - Duration::seconds(offset.local_minus_utc() as i64)))
+}
+
+/// Today's date in US Eastern time, the date expirations and sessions
+/// are reckoned in
+pub fn today() -> NaiveDate {
+    to_eastern(Utc::now()).date_naive()
+}
+
+/// Whether the US regular session is open right now
+pub fn is_market_open() -> bool {
+    MarketCalendar::new().is_open_at(Utc::now())
+}
+
+/// Current US market session
+pub fn current_session() -> Session {
+    MarketCalendar::new().session_at(Utc::now())
+}
+
+/// Next US regular-session open
+pub fn next_open() -> DateTime<Utc> {
+    MarketCalendar::new().next_open_after(Utc::now())
+}
+
+/// Next US regular-session close
+pub fn next_close() -> DateTime<Utc> {
+    MarketCalendar::new().next_close_after(Utc::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.from_utc_datetime(&day(y, m, d).and_hms_opt(h, min, 0).unwrap())
+    }
+
+    #[test]
+    fn test_holiday_rules() {
+        let calendar = MarketCalendar::new();
+        let holidays: Vec<NaiveDate> = calendar.holidays(2024).into_keys().collect();
+        assert_eq!(
+            holidays,
+            vec![
+                day(2024, 1, 1),
+                day(2024, 1, 15),
+                day(2024, 2, 19),
+                day(2024, 3, 29),
+                day(2024, 5, 27),
+                day(2024, 6, 19),
+                day(2024, 7, 4),
+                day(2024, 9, 2),
+                day(2024, 11, 28),
+                day(2024, 12, 25),
+            ]
+        );
+
+        // Saturday New Year's Day 2022 was not observed; Sunday Christmas was
+        assert!(!calendar.is_holiday(day(2021, 12, 31)));
+        assert!(calendar.is_holiday(day(2022, 12, 26)));
+        // Independence Day 2026 falls on a Saturday
+        assert!(calendar.is_holiday(day(2026, 7, 3)));
+        assert!(!calendar.is_early_close(day(2026, 7, 3)));
+
+        assert!(calendar.is_early_close(day(2024, 11, 29)));
+        assert!(calendar.hours(day(2024, 12, 24)).unwrap().is_early_close());
+        assert!(calendar.hours(day(2024, 3, 30)).is_none());
+
+        let mut calendar = MarketCalendar::new();
+        calendar.add_holiday(day(2025, 1, 9));
+        assert!(!calendar.is_trading_day(day(2025, 1, 9)));
+    }
+
+    #[test]
+    fn test_sessions_across_daylight_saving() {
+        let calendar = MarketCalendar::new();
+        // 09:30 EST is 14:30 UTC in January, 09:30 EDT is 13:30 UTC in July
+        assert_eq!(
+            calendar.session_at(utc(2024, 1, 10, 14, 29)),
+            Session::PreMarket
+        );
+        assert!(calendar.is_open_at(utc(2024, 1, 10, 14, 30)));
+        assert!(calendar.is_open_at(utc(2024, 7, 10, 13, 30)));
+        assert_eq!(
+            calendar.session_at(utc(2024, 7, 10, 20, 30)),
+            Session::AfterHours
+        );
+        assert_eq!(calendar.session_at(utc(2024, 7, 11, 1, 0)), Session::Closed);
+
+        // Friday after the close: next open is Monday
+        let friday_evening = utc(2024, 3, 8, 22, 0);
+        assert_eq!(
+            calendar.next_open_after(friday_evening),
+            utc(2024, 3, 11, 13, 30)
+        );
+        assert_eq!(
+            calendar.next_close_after(friday_evening),
+            utc(2024, 3, 11, 20, 0)
+        );
+
+        // Thanksgiving: next close is the early close on Friday
+        let thanksgiving = utc(2024, 11, 28, 15, 0);
+        assert_eq!(
+            calendar.next_close_after(thanksgiving),
+            utc(2024, 11, 29, 18, 0)
+        );
+
+        assert_eq!(
+            from_eastern(day(2024, 7, 10).and_time(time(9, 30))),
+            utc(2024, 7, 10, 13, 30)
+        );
+    }
+}
@@ -0,0 +1,181 @@
+// Hooks that observe or change every request and response
+
+use crate::error::{Result, WebullError};
+use crate::telemetry::{redact_body, redact_headers};
+use log::debug;
+use reqwest::{Request, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::sync::Arc;
+use url::Url;
+
+/// Observe or change requests and responses, e.g. for logging, metrics or
+/// injecting test headers
+///
+/// Requests reach middleware after the client has added its headers and
+/// before retries, so `on_request` runs once per call and sees tokens in
+/// the clear. Use `RequestLog` to get a copy that is safe to write out.
+/// Responses are handed over before their body is read.
+pub trait Middleware: Send + Sync {
+    fn on_request(&self, _request: &mut Request) {}
+
+    fn on_response(&self, _response: &mut Response) {}
+
+    /// A request that failed without a response, including after retries
+    fn on_error(&self, _url: &Url, _error: &WebullError) {}
+}
+
+/// A request with `access_token`, `t_token`, password hashes and the
+/// device ID redacted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequestLog {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    /// JSON body, if there is one
+    pub body: Option<Value>,
+}
+
+impl RequestLog {
+    pub fn from_request(request: &Request) -> Self {
+        Self {
+            method: request.method().to_string(),
+            url: crate::telemetry::redact_url(request.url()),
+            headers: redact_headers(request.headers()),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .and_then(|bytes| serde_json::from_slice::<Value>(bytes).ok())
+                .map(|body| redact_body(&body)),
+        }
+    }
+}
+
+/// Logs every request and response at debug level, redacted
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoggingMiddleware;
+
+impl Middleware for LoggingMiddleware {
+    fn on_request(&self, request: &mut Request) {
+        let log = RequestLog::from_request(request);
+        debug!(
+            "{} {} headers={:?} body={}",
+            log.method,
+            log.url,
+            log.headers,
+            log.body.unwrap_or(Value::Null)
+        );
+    }
+
+    fn on_response(&self, response: &mut Response) {
+        debug!(
+            "{} from {}",
+            response.status(),
+            crate::telemetry::redact_url(response.url())
+        );
+    }
+
+    fn on_error(&self, url: &Url, error: &WebullError) {
+        debug!("{} failed: {}", crate::telemetry::redact_url(url), error);
+    }
+}
+
+/// Middleware in the order it was added
+#[derive(Clone, Default)]
+pub(crate) struct MiddlewareStack(Vec<Arc<dyn Middleware>>);
+
+impl MiddlewareStack {
+    pub(crate) fn push(&mut self, middleware: Arc<dyn Middleware>) {
+        self.0.push(middleware);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub(crate) fn on_request(&self, request: &mut Request) {
+        for middleware in &self.0 {
+            middleware.on_request(request);
+        }
+    }
+
+    /// Pass the outcome of a request through every middleware
+    pub(crate) fn on_result(&self, url: &Url, result: Result<Response>) -> Result<Response> {
+        match result {
+            Ok(mut response) => {
+                for middleware in &self.0 {
+                    middleware.on_response(&mut response);
+                }
+                Ok(response)
+            }
+            Err(e) => {
+                for middleware in &self.0 {
+                    middleware.on_error(url, &e);
+                }
+                Err(e)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for MiddlewareStack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MiddlewareStack({})", self.0.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    #[derive(Default)]
+    struct Recorder {
+        errors: Mutex<Vec<String>>,
+    }
+
+    impl Middleware for Arc<Recorder> {
+        fn on_request(&self, request: &mut Request) {
+            request
+                .headers_mut()
+                .insert("x-test", reqwest::header::HeaderValue::from_static("1"));
+        }
+
+        fn on_error(&self, url: &Url, _error: &WebullError) {
+            self.errors.lock().push(url.path().to_string());
+        }
+    }
+
+    #[test]
+    fn test_middleware_sees_requests_and_logs_are_redacted() {
+        let recorder = Arc::new(Recorder::default());
+        let mut stack = MiddlewareStack::default();
+        stack.push(Arc::new(Arc::clone(&recorder)));
+
+        let mut request = reqwest::Client::new()
+            .post("https://userapi.webull.com/api/passport/login/v5/account")
+            .header("access_token", "secret-token")
+            .header("t_token", "trade-token")
+            .json(&serde_json::json!({"account": "a@b.com", "pwd": "5f4dcc3b", "extInfo": {"tradePwd": "x"}}))
+            .build()
+            .unwrap();
+        stack.on_request(&mut request);
+        assert_eq!(request.headers()["x-test"], "1");
+
+        let log = RequestLog::from_request(&request);
+        let logged = serde_json::to_string(&log).unwrap();
+        for secret in ["secret-token", "trade-token", "5f4dcc3b", "\"x\""] {
+            assert!(!logged.contains(secret), "{} leaked", secret);
+        }
+        assert_eq!(log.body.unwrap()["account"], "a@b.com");
+
+        let url = request.url().clone();
+        let result = stack.on_result(&url, Err(WebullError::DeadlineExceeded));
+        assert!(matches!(result, Err(WebullError::DeadlineExceeded)));
+        assert_eq!(
+            *recorder.errors.lock(),
+            vec!["/api/passport/login/v5/account"]
+        );
+    }
+}
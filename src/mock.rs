@@ -0,0 +1,851 @@
+// In-memory trading backend for testing strategies without Webull servers
+
+use crate::{
+    error::{Result, WebullError},
+    models::*,
+    trader::WebullTrader,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use parking_lot::Mutex;
+use serde_json::json;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+/// Step in a scripted order lifecycle
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderEvent {
+    /// Order rests as working
+    Accept,
+    /// Fill this many shares at the order's price
+    PartialFill(f64),
+    /// Fill the remaining quantity
+    Fill,
+    /// Cancel the order
+    Cancel,
+    /// Reject the order; as the first event `place_order` returns an error
+    Reject(String),
+}
+
+/// How the mock engine prices fills
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum FillModel {
+    /// Fill market orders at the last price, ignoring the spread
+    #[default]
+    LastPrice,
+    /// Fill market orders at the bid/ask midpoint
+    Midpoint,
+    /// Buy at the ask and sell at the bid
+    CrossSpread,
+    /// Cross the spread, and fill a limit order resting at the touch (a buy
+    /// on the bid, a sell on the ask) with `fill_probability` per quote
+    /// update, as if it were waiting in the queue
+    QueuePosition { fill_probability: f64 },
+}
+
+/// Commissions and fees charged on each fill
+///
+/// The per-order charge and minimum apply to an order's first fill; the
+/// per-share charge and the sell fee apply to every fill.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CommissionSchedule {
+    pub per_order: f64,
+    pub per_share: f64,
+    pub minimum: f64,
+    /// Fraction of notional charged on sells (regulatory fees)
+    pub sell_fee_rate: f64,
+}
+
+impl CommissionSchedule {
+    /// Commission-free trading
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn per_order(mut self, amount: f64) -> Self {
+        self.per_order = amount;
+        self
+    }
+
+    pub fn per_share(mut self, amount: f64) -> Self {
+        self.per_share = amount;
+        self
+    }
+
+    pub fn minimum(mut self, amount: f64) -> Self {
+        self.minimum = amount;
+        self
+    }
+
+    pub fn sell_fee_rate(mut self, rate: f64) -> Self {
+        self.sell_fee_rate = rate;
+        self
+    }
+
+    /// Total charge for one fill
+    pub fn charge(&self, action: &OrderAction, quantity: f64, price: f64, first_fill: bool) -> f64 {
+        let mut commission = self.per_share * quantity;
+        if first_fill {
+            commission = (commission + self.per_order).max(self.minimum);
+        }
+        let fee = match action {
+            OrderAction::Sell => quantity * price * self.sell_fee_rate,
+            OrderAction::Buy => 0.0,
+        };
+        commission + fee
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MockOrder {
+    order_id: String,
+    request: PlaceOrderRequest,
+    status: OrderStatus,
+    filled_quantity: f64,
+    avg_fill_price: Option<f64>,
+    placed_time: String,
+    filled_time: Option<String>,
+    script: VecDeque<OrderEvent>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    cash: f64,
+    quotes: HashMap<i64, f64>,
+    // ticker ID -> (bid, ask), when set separately from the last price
+    spreads: HashMap<i64, (f64, f64)>,
+    fill_model: FillModel,
+    commissions: CommissionSchedule,
+    fees_paid: f64,
+    rng: u64,
+    symbols: HashMap<i64, String>,
+    // ticker ID -> (quantity, average cost)
+    positions: HashMap<i64, (f64, f64)>,
+    orders: Vec<MockOrder>,
+    scripts: VecDeque<Vec<OrderEvent>>,
+    next_order_id: u64,
+}
+
+/// Trading backend that fills orders in memory
+///
+/// Without a script, market orders and limit orders that are marketable when
+/// placed fill immediately at the current quote, and resting limit orders
+/// fill at their limit as soon as the quote crosses it. The
+/// fill model decides which side of the spread a fill uses, and the
+/// commission schedule is charged against cash on every fill. Scripts queued
+/// with `script_next_order` take over the lifecycle of the next placed order;
+/// the first event applies on placement and each `step` applies one more.
+/// Clones share the same state.
+///
+/// ```
+/// use webull_unofficial::mock::MockWebullClient;
+/// use webull_unofficial::{PlaceOrderRequest, WebullTrader};
+/// use webull_unofficial::models::OrderAction;
+///
+/// # tokio_test(async {
+/// let client = MockWebullClient::new(10_000.0);
+/// client.set_quote(913256135, 190.0);
+/// let order = PlaceOrderRequest::market()
+///     .ticker_id(913256135)
+///     .action(OrderAction::Buy)
+///     .quantity(10.0)
+///     .build()
+///     .unwrap();
+/// client.place_order(&order).await.unwrap();
+/// assert_eq!(client.get_positions().await.unwrap()[0].quantity, 10.0);
+/// # });
+/// # fn tokio_test<F: std::future::Future>(f: F) {
+/// #     tokio::runtime::Runtime::new().unwrap().block_on(f);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockWebullClient {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockWebullClient {
+    /// Create a mock account holding `cash`
+    pub fn new(cash: f64) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MockState {
+                cash,
+                next_order_id: 1,
+                rng: 0x2545_f491_4f6c_dd1d,
+                ..Default::default()
+            })),
+        }
+    }
+
+    /// Set the last price for a ticker, filling any limit orders it crosses
+    ///
+    /// The bid and ask are both set to the price.
+    pub fn set_quote(&self, ticker_id: i64, price: f64) {
+        let mut state = self.state.lock();
+        state.quotes.insert(ticker_id, price);
+        state.spreads.remove(&ticker_id);
+        Self::match_resting(&mut state, ticker_id);
+    }
+
+    /// Set the bid and ask for a ticker; the last price becomes the midpoint
+    pub fn set_quote_spread(&self, ticker_id: i64, bid: f64, ask: f64) {
+        let mut state = self.state.lock();
+        state.quotes.insert(ticker_id, (bid + ask) / 2.0);
+        state.spreads.insert(ticker_id, (bid, ask));
+        Self::match_resting(&mut state, ticker_id);
+    }
+
+    /// Choose how fills are priced
+    pub fn set_fill_model(&self, model: FillModel) {
+        self.state.lock().fill_model = model;
+    }
+
+    /// Charge commissions and fees on fills
+    pub fn set_commissions(&self, schedule: CommissionSchedule) {
+        self.state.lock().commissions = schedule;
+    }
+
+    /// Total commissions and fees charged so far
+    pub fn fees_paid(&self) -> f64 {
+        self.state.lock().fees_paid
+    }
+
+    /// Seed the generator behind `FillModel::QueuePosition` for repeatable runs
+    pub fn set_seed(&self, seed: u64) {
+        // xorshift never leaves zero
+        self.state.lock().rng = seed.max(1);
+    }
+
+    /// Set the display symbol used for a ticker in positions and orders
+    pub fn set_symbol(&self, ticker_id: i64, symbol: &str) {
+        self.state
+            .lock()
+            .symbols
+            .insert(ticker_id, symbol.to_string());
+    }
+
+    /// Set the cash balance
+    pub fn set_cash(&self, cash: f64) {
+        self.state.lock().cash = cash;
+    }
+
+    /// Get the cash balance
+    pub fn cash(&self) -> f64 {
+        self.state.lock().cash
+    }
+
+    /// Script the lifecycle of the next order placed
+    pub fn script_next_order(&self, events: Vec<OrderEvent>) {
+        self.state.lock().scripts.push_back(events);
+    }
+
+    /// Apply the next scripted event of every scripted order
+    pub fn step(&self) {
+        let mut state = self.state.lock();
+        for index in 0..state.orders.len() {
+            if let Some(event) = state.orders[index].script.pop_front() {
+                let _ = Self::apply(&mut state, index, event);
+            }
+        }
+    }
+
+    /// All orders placed so far, oldest first
+    pub fn placed_orders(&self) -> Vec<PlaceOrderRequest> {
+        self.state
+            .lock()
+            .orders
+            .iter()
+            .map(|o| o.request.clone())
+            .collect()
+    }
+
+    /// Fill resting limit orders for a ticker that the current quote reaches
+    fn match_resting(state: &mut MockState, ticker_id: i64) {
+        for index in 0..state.orders.len() {
+            let order = &state.orders[index];
+            let resting = order.script.is_empty()
+                && matches!(
+                    order.status,
+                    OrderStatus::Working | OrderStatus::PartialFilled
+                )
+                && order.request.ticker_id == ticker_id;
+            if !resting {
+                continue;
+            }
+            let request = order.request.clone();
+            let price = match Self::execution_price(state, ticker_id, &request.action) {
+                Some(price) => price,
+                None => continue,
+            };
+            let fills = Self::marketable(&request, price) || Self::fills_in_queue(state, &request);
+            if fills {
+                let order = &state.orders[index];
+                let remaining = order.request.quantity - order.filled_quantity;
+                let fill_price = order.request.limit_price.unwrap_or(price);
+                // A fill that fails for lack of cash leaves the order working
+                let _ = Self::fill(state, index, remaining, fill_price);
+            }
+        }
+    }
+
+    /// Price a market order on this side would fill at under the fill model
+    fn execution_price(state: &MockState, ticker_id: i64, action: &OrderAction) -> Option<f64> {
+        let last = state.quotes.get(&ticker_id).copied()?;
+        let (bid, ask) = state
+            .spreads
+            .get(&ticker_id)
+            .copied()
+            .unwrap_or((last, last));
+        Some(match (state.fill_model, action) {
+            (FillModel::LastPrice, _) => last,
+            (FillModel::Midpoint, _) => (bid + ask) / 2.0,
+            (_, OrderAction::Buy) => ask,
+            (_, OrderAction::Sell) => bid,
+        })
+    }
+
+    /// Whether a limit order resting at the touch gets filled this update
+    fn fills_in_queue(state: &mut MockState, request: &PlaceOrderRequest) -> bool {
+        let probability = match state.fill_model {
+            FillModel::QueuePosition { fill_probability } => fill_probability,
+            _ => return false,
+        };
+        let (limit, (bid, ask)) = match (request.limit_price, state.spreads.get(&request.ticker_id))
+        {
+            (Some(limit), Some(&spread)) => (limit, spread),
+            _ => return false,
+        };
+        let at_touch = match request.action {
+            OrderAction::Buy => limit == bid,
+            OrderAction::Sell => limit == ask,
+        };
+        at_touch && Self::next_random(state) < probability
+    }
+
+    // xorshift64, uniform in [0, 1)
+    fn next_random(state: &mut MockState) -> f64 {
+        let mut x = state.rng;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        state.rng = x;
+        (x >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn marketable(request: &PlaceOrderRequest, price: f64) -> bool {
+        match (&request.order_type, request.limit_price) {
+            (OrderType::Market, _) => true,
+            (_, Some(limit)) => match request.action {
+                OrderAction::Buy => price <= limit,
+                OrderAction::Sell => price >= limit,
+            },
+            (_, None) => false,
+        }
+    }
+
+    fn apply(state: &mut MockState, index: usize, event: OrderEvent) -> Result<()> {
+        let order = &state.orders[index];
+        let remaining = order.request.quantity - order.filled_quantity;
+        let price = order.request.limit_price.or_else(|| {
+            Self::execution_price(state, order.request.ticker_id, &order.request.action)
+        });
+
+        match event {
+            OrderEvent::Accept => {
+                state.orders[index].status = OrderStatus::Working;
+                Ok(())
+            }
+            OrderEvent::PartialFill(quantity) => {
+                let price = price.ok_or_else(|| Self::no_quote(order.request.ticker_id))?;
+                Self::fill(state, index, quantity.min(remaining), price)
+            }
+            OrderEvent::Fill => {
+                let price = price.ok_or_else(|| Self::no_quote(order.request.ticker_id))?;
+                Self::fill(state, index, remaining, price)
+            }
+            OrderEvent::Cancel => {
+                state.orders[index].status = OrderStatus::Cancelled;
+                Ok(())
+            }
+            OrderEvent::Reject(reason) => {
+                state.orders[index].status = OrderStatus::Rejected;
+                Err(WebullError::ApiErrorCode {
+                    code: "mock.rejected".to_string(),
+                    message: reason,
+                })
+            }
+        }
+    }
+
+    fn fill(state: &mut MockState, index: usize, quantity: f64, price: f64) -> Result<()> {
+        let (ticker_id, action) = {
+            let request = &state.orders[index].request;
+            (request.ticker_id, request.action.clone())
+        };
+        let notional = quantity * price;
+        let first_fill = state.orders[index].filled_quantity == 0.0;
+        let fee = state
+            .commissions
+            .charge(&action, quantity, price, first_fill);
+        let (held, cost) = state.positions.get(&ticker_id).copied().unwrap_or_default();
+
+        match action {
+            OrderAction::Buy => {
+                if notional + fee > state.cash {
+                    return Err(WebullError::InsufficientFunds);
+                }
+                let total = held + quantity;
+                state
+                    .positions
+                    .insert(ticker_id, (total, (held * cost + notional) / total));
+                state.cash -= notional + fee;
+            }
+            OrderAction::Sell => {
+                if quantity > held {
+                    return Err(WebullError::InvalidRequest(format!(
+                        "Cannot sell {} shares of {}: only {} held",
+                        quantity, ticker_id, held
+                    )));
+                }
+                if quantity < held {
+                    state.positions.insert(ticker_id, (held - quantity, cost));
+                } else {
+                    state.positions.remove(&ticker_id);
+                }
+                state.cash += notional - fee;
+            }
+        }
+        state.fees_paid += fee;
+
+        let order = &mut state.orders[index];
+        let filled = order.filled_quantity + quantity;
+        order.avg_fill_price =
+            Some((order.avg_fill_price.unwrap_or(0.0) * order.filled_quantity + notional) / filled);
+        order.filled_quantity = filled;
+        if filled >= order.request.quantity {
+            order.status = OrderStatus::Filled;
+            order.filled_time = Some(Utc::now().to_rfc3339());
+        } else {
+            order.status = OrderStatus::PartialFilled;
+        }
+        Ok(())
+    }
+
+    fn no_quote(ticker_id: i64) -> WebullError {
+        WebullError::InvalidRequest(format!("No mock quote set for ticker {}", ticker_id))
+    }
+
+    fn ticker(state: &MockState, ticker_id: i64) -> Option<Ticker> {
+        let symbol = state
+            .symbols
+            .get(&ticker_id)
+            .cloned()
+            .unwrap_or_else(|| ticker_id.to_string());
+        serde_json::from_value(json!({
+            "tickerId": ticker_id,
+            "disSymbol": symbol,
+            "name": symbol,
+        }))
+        .ok()
+    }
+
+    fn to_order(state: &MockState, order: &MockOrder) -> Order {
+        Order {
+            order_id: order.order_id.clone(),
+            combo_id: None,
+            ticker: Self::ticker(state, order.request.ticker_id),
+            action: order.request.action.clone(),
+            order_type: order.request.order_type.clone(),
+            status: order.status.clone(),
+            time_in_force: order.request.time_in_force.clone(),
+            quantity: order.request.quantity,
+            filled_quantity: order.filled_quantity,
+            avg_fill_price: order.avg_fill_price,
+            limit_price: order.request.limit_price,
+            stop_price: order.request.stop_price,
+            outside_regular_trading_hour: order.request.outside_regular_trading_hour,
+            create_time: Some(order.placed_time.clone()),
+            placed_time: Some(order.placed_time.clone()),
+            filled_time: order.filled_time.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl WebullTrader for MockWebullClient {
+    async fn login(
+        &mut self,
+        username: &str,
+        _password: &str,
+        _device_name: Option<&str>,
+        _mfa: Option<&str>,
+        _question_id: Option<&str>,
+        _question_answer: Option<&str>,
+    ) -> Result<LoginOutcome> {
+        Ok(LoginOutcome::Success(Box::new(serde_json::from_value(
+            json!({
+                "accessToken": "mock-access-token",
+                "refreshToken": "mock-refresh-token",
+                "tokenExpireTime": "2099-01-01T00:00:00.000+0000",
+                "uuid": "mock-uuid",
+                "email": username,
+            }),
+        )?)))
+    }
+
+    async fn logout(&mut self) -> Result<bool> {
+        Ok(true)
+    }
+
+    async fn get_trade_token(&mut self, _password: &str) -> Result<String> {
+        Ok("mock-trade-token".to_string())
+    }
+
+    async fn get_account(&self) -> Result<AccountDetail> {
+        let state = self.state.lock();
+        let market_value: f64 = state
+            .positions
+            .iter()
+            .map(|(id, (qty, cost))| qty * state.quotes.get(id).copied().unwrap_or(*cost))
+            .sum();
+
+        let mut account: AccountDetail = serde_json::from_value(json!({
+            "secAccountId": "mock",
+            "currency": "USD",
+        }))?;
+        account.net_liquidation = Some(state.cash + market_value);
+        account.total_market_value = Some(market_value);
+        account.cash_balance = Some(state.cash);
+        account.total_cash = Some(state.cash);
+        account.buying_power = Some(state.cash);
+        account.settled_funds = Some(state.cash);
+        Ok(account)
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        let state = self.state.lock();
+        let mut positions: Vec<Position> = state
+            .positions
+            .iter()
+            .map(|(id, (qty, cost))| {
+                let last_price = state.quotes.get(id).copied().unwrap_or(*cost);
+                Position {
+                    ticker: Self::ticker(&state, *id),
+                    quantity: *qty,
+                    avg_cost: *cost,
+                    cost: qty * cost,
+                    market_value: qty * last_price,
+                    last_price,
+                    unrealized_profit_loss: Some(qty * (last_price - cost)),
+                    unrealized_profit_loss_rate: (*cost != 0.0).then(|| (last_price - cost) / cost),
+                    asset_type: Some("stock".to_string()),
+                }
+            })
+            .collect();
+        positions.sort_by_key(|p| p.ticker.as_ref().map(|t| t.ticker_id));
+        Ok(positions)
+    }
+
+    async fn get_orders(&self, _page_size: Option<i32>) -> Result<Vec<Order>> {
+        let state = self.state.lock();
+        Ok(state
+            .orders
+            .iter()
+            .filter(|o| matches!(o.status, OrderStatus::Working | OrderStatus::PartialFilled))
+            .map(|o| Self::to_order(&state, o))
+            .collect())
+    }
+
+    async fn get_history_orders(&self, status: &str, count: i32) -> Result<Vec<HistoryOrder>> {
+        let state = self.state.lock();
+        Ok(state
+            .orders
+            .iter()
+            .rev()
+            .filter(|o| {
+                status.eq_ignore_ascii_case("all") || parse_order_status(status) == o.status
+            })
+            .take(count.max(0) as usize)
+            .map(|o| {
+                let order = Self::to_order(&state, o);
+                HistoryOrder {
+                    order_id: order.order_id,
+                    combo_id: None,
+                    ticker: order.ticker,
+                    action: order.action,
+                    order_type: order.order_type,
+                    status_text: format!("{:?}", order.status),
+                    status: order.status,
+                    time_in_force: order.time_in_force,
+                    quantity: order.quantity,
+                    filled_quantity: order.filled_quantity,
+                    avg_fill_price: order.avg_fill_price,
+                    limit_price: order.limit_price,
+                    stop_price: order.stop_price,
+                    outside_regular_trading_hour: order.outside_regular_trading_hour,
+                    placed_time: order.placed_time,
+                    filled_time: order.filled_time,
+                }
+            })
+            .collect())
+    }
+
+    async fn place_order(&self, order: &PlaceOrderRequest) -> Result<String> {
+        let mut state = self.state.lock();
+        let order_id = format!("MOCK-{}", state.next_order_id);
+        state.next_order_id += 1;
+
+        let mut script: VecDeque<OrderEvent> = state.scripts.pop_front().unwrap_or_default().into();
+        let scripted = !script.is_empty();
+        let first = script.pop_front();
+        state.orders.push(MockOrder {
+            order_id: order_id.clone(),
+            request: order.clone(),
+            status: OrderStatus::Working,
+            filled_quantity: 0.0,
+            avg_fill_price: None,
+            placed_time: Utc::now().to_rfc3339(),
+            filled_time: None,
+            script,
+        });
+        let index = state.orders.len() - 1;
+
+        let result = match first {
+            Some(event) => Self::apply(&mut state, index, event),
+            None if !scripted => {
+                let price = Self::execution_price(&state, order.ticker_id, &order.action);
+                match price {
+                    // A marketable limit order takes the quote, which is at
+                    // or better than its limit
+                    Some(price) if Self::marketable(order, price) => {
+                        Self::fill(&mut state, index, order.quantity, price)
+                    }
+                    None if order.order_type == OrderType::Market => {
+                        Err(Self::no_quote(order.ticker_id))
+                    }
+                    _ => Ok(()),
+                }
+            }
+            None => Ok(()),
+        };
+
+        if let Err(e) = result {
+            state.orders[index].status = OrderStatus::Rejected;
+            return Err(e);
+        }
+        Ok(order_id)
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<bool> {
+        let mut state = self.state.lock();
+        let order = state
+            .orders
+            .iter_mut()
+            .find(|o| o.order_id == order_id)
+            .ok_or(WebullError::OrderNotFound)?;
+        if matches!(
+            order.status,
+            OrderStatus::Working | OrderStatus::PartialFilled
+        ) {
+            order.status = OrderStatus::Cancelled;
+            order.script.clear();
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    async fn get_quotes(&self, ticker_id: &str) -> Result<Quote> {
+        let id: i64 = ticker_id.parse().map_err(|_| {
+            WebullError::InvalidParameter(format!("Invalid ticker ID: {}", ticker_id))
+        })?;
+        let state = self.state.lock();
+        let price = state
+            .quotes
+            .get(&id)
+            .copied()
+            .ok_or_else(|| Self::no_quote(id))?;
+        let (bid, ask) = state.spreads.get(&id).copied().unwrap_or((price, price));
+
+        Ok(Quote::builder(price)
+            .ticker_id(id)
+            .pre_close(price)
+            .session(price, price, price)
+            .volume(0.0)
+            .bid_ask(bid, ask)
+            .build())
+    }
+
+    fn is_paper(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AAPL: i64 = 913256135;
+
+    fn order(action: OrderAction, quantity: f64, limit: Option<f64>) -> PlaceOrderRequest {
+        let builder = match limit {
+            Some(price) => PlaceOrderRequest::limit(price),
+            None => PlaceOrderRequest::market(),
+        };
+        builder
+            .ticker_id(AAPL)
+            .action(action)
+            .quantity(quantity)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_market_and_limit_fills() {
+        let client = MockWebullClient::new(10_000.0);
+        client.set_quote(AAPL, 100.0);
+
+        client
+            .place_order(&order(OrderAction::Buy, 10.0, None))
+            .await
+            .unwrap();
+        assert_eq!(client.cash(), 9_000.0);
+
+        // Resting sell fills once the quote reaches the limit
+        let id = client
+            .place_order(&order(OrderAction::Sell, 10.0, Some(110.0)))
+            .await
+            .unwrap();
+        assert_eq!(client.get_orders(None).await.unwrap()[0].order_id, id);
+        client.set_quote(AAPL, 111.0);
+        assert!(client.get_orders(None).await.unwrap().is_empty());
+        assert!(client.get_positions().await.unwrap().is_empty());
+        assert_eq!(client.cash(), 10_100.0);
+
+        let filled = client.get_history_orders("Filled", 10).await.unwrap();
+        assert_eq!(filled.len(), 2);
+        assert_eq!(filled[0].avg_fill_price, Some(110.0));
+
+        let err = client
+            .place_order(&order(OrderAction::Buy, 1_000.0, None))
+            .await;
+        assert!(matches!(err, Err(WebullError::InsufficientFunds)));
+    }
+
+    #[tokio::test]
+    async fn test_scripted_lifecycle() {
+        let client = MockWebullClient::new(10_000.0);
+        client.script_next_order(vec![
+            OrderEvent::Accept,
+            OrderEvent::PartialFill(4.0),
+            OrderEvent::Cancel,
+        ]);
+        let id = client
+            .place_order(&order(OrderAction::Buy, 10.0, Some(50.0)))
+            .await
+            .unwrap();
+
+        client.step();
+        let open = client.get_orders(None).await.unwrap();
+        assert_eq!(open[0].status, OrderStatus::PartialFilled);
+        assert_eq!(open[0].filled_quantity, 4.0);
+
+        client.step();
+        let history = client.get_history_orders("All", 10).await.unwrap();
+        assert_eq!(history[0].order_id, id);
+        assert_eq!(history[0].status, OrderStatus::Cancelled);
+        assert_eq!(client.get_positions().await.unwrap()[0].quantity, 4.0);
+
+        client.script_next_order(vec![OrderEvent::Reject("halted".to_string())]);
+        assert!(client
+            .place_order(&order(OrderAction::Buy, 1.0, Some(50.0)))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fill_models_and_commissions() {
+        let client = MockWebullClient::new(10_000.0);
+        client.set_quote_spread(AAPL, 99.0, 101.0);
+        client.set_commissions(
+            CommissionSchedule::new()
+                .per_share(0.01)
+                .minimum(1.0)
+                .sell_fee_rate(0.001),
+        );
+
+        client.set_fill_model(FillModel::Midpoint);
+        client
+            .place_order(&order(OrderAction::Buy, 10.0, None))
+            .await
+            .unwrap();
+        // 10 shares at 100 plus the 1.00 minimum commission
+        assert!((client.cash() - 8_999.0).abs() < 1e-9);
+
+        client.set_fill_model(FillModel::CrossSpread);
+        client
+            .place_order(&order(OrderAction::Sell, 10.0, None))
+            .await
+            .unwrap();
+        // Sold at the bid: 990 less 1.00 commission and 0.99 sell fee
+        assert!((client.cash() - 9_987.01).abs() < 1e-9);
+        assert!((client.fees_paid() - 2.99).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_queue_position_fills_at_touch_by_probability() {
+        let client = MockWebullClient::new(10_000.0);
+        client.set_fill_model(FillModel::QueuePosition {
+            fill_probability: 0.0,
+        });
+        client.set_quote_spread(AAPL, 99.0, 101.0);
+
+        // Resting on the bid never fills with zero probability...
+        client
+            .place_order(&order(OrderAction::Buy, 1.0, Some(99.0)))
+            .await
+            .unwrap();
+        client.set_quote_spread(AAPL, 99.0, 100.0);
+        assert_eq!(client.get_orders(None).await.unwrap().len(), 1);
+
+        // ...but fills at the limit once the ask comes down to it
+        client.set_quote_spread(AAPL, 98.0, 99.0);
+        assert!(client.get_orders(None).await.unwrap().is_empty());
+
+        client.set_fill_model(FillModel::QueuePosition {
+            fill_probability: 1.0,
+        });
+        client
+            .place_order(&order(OrderAction::Buy, 1.0, Some(98.0)))
+            .await
+            .unwrap();
+        client.set_quote_spread(AAPL, 98.0, 99.5);
+        assert!(client.get_orders(None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_marketable_limit_fills_at_the_quote() {
+        let client = MockWebullClient::new(10_000.0);
+        client.set_fill_model(FillModel::CrossSpread);
+        client.set_quote_spread(AAPL, 99.0, 101.0);
+
+        client
+            .place_order(&order(OrderAction::Buy, 10.0, Some(105.0)))
+            .await
+            .unwrap();
+        client
+            .place_order(&order(OrderAction::Sell, 5.0, Some(90.0)))
+            .await
+            .unwrap();
+
+        let filled = client.get_history_orders("Filled", 10).await.unwrap();
+        let price_of = |action: OrderAction| {
+            filled
+                .iter()
+                .find(|o| o.action == action)
+                .and_then(|o| o.avg_fill_price)
+        };
+        assert_eq!(price_of(OrderAction::Buy), Some(101.0));
+        assert_eq!(price_of(OrderAction::Sell), Some(99.0));
+        assert_eq!(client.cash(), 10_000.0 - 1_010.0 + 495.0);
+    }
+}
@@ -1,6 +1,7 @@
 use serde::de::{self, Deserializer};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
 
 // Custom deserializer for fields that can be either string or number
 fn deserialize_optional_string_or_number<'de, D>(
@@ -18,6 +19,21 @@ where
     }
 }
 
+/// Items of a list response, which comes either as a bare array or wrapped
+/// in a `data` array
+pub(crate) fn list_items(value: &Value) -> &[Value] {
+    value
+        .as_array()
+        .or_else(|| value.get("data").and_then(|v| v.as_array()))
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// Parse each item of a list response; items `parse` rejects are skipped
+pub(crate) fn parse_list<T>(value: &Value, parse: impl FnMut(&Value) -> Option<T>) -> Vec<T> {
+    list_items(value).iter().filter_map(parse).collect()
+}
+
 // ============= Login Models =============
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -191,6 +207,457 @@ pub struct LoginResponse {
     pub user_type: Option<String>,
 }
 
+/// Result of a login attempt
+///
+/// Anything but `Success` means Webull wants another step before it hands
+/// out a session: answer it and call `login` again.
+#[derive(Debug, Clone)]
+pub enum LoginOutcome {
+    Success(Box<LoginResponse>),
+    /// A verification code is needed; request one with `get_mfa_with`
+    MfaRequired {
+        code_types: Vec<MfaCodeType>,
+    },
+    /// A security question must be answered
+    SecurityQuestionRequired {
+        question: SecurityQuestion,
+    },
+    /// A captcha must be solved; see `get_captcha`
+    CaptchaRequired(LoginChallenge),
+}
+
+impl LoginOutcome {
+    /// Recognize a login response that asks for another step
+    pub fn from_challenge(body: &Value) -> Option<Self> {
+        let code = match body.get("code") {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Number(n)) => n.to_string(),
+            _ => return None,
+        };
+        let lower = code.to_ascii_lowercase();
+        let data = body.get("data").filter(|d| d.is_object()).unwrap_or(body);
+        let message = body
+            .get("msg")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        if LoginChallenge::is_challenge_code(&code) {
+            Some(LoginOutcome::CaptchaRequired(LoginChallenge::from_body(
+                &code, message, body,
+            )))
+        } else if lower.contains("question") {
+            Some(LoginOutcome::SecurityQuestionRequired {
+                question: SecurityQuestion::from_response(data),
+            })
+        } else if lower.contains("mfa")
+            || lower.contains("verification")
+            || lower.contains("new.device")
+            || lower.contains("code.need")
+        {
+            let mut code_types: Vec<MfaCodeType> = data
+                .get("codeTypes")
+                .and_then(|v| v.as_array())
+                .map(|types| {
+                    types
+                        .iter()
+                        .filter_map(|t| {
+                            t.as_i64()
+                                .or_else(|| t.as_str().and_then(|s| s.parse().ok()))
+                        })
+                        .map(|t| MfaCodeType::from_code(t as i32))
+                        .collect()
+                })
+                .unwrap_or_default();
+            if code_types.is_empty() {
+                code_types.push(MfaCodeType::Login);
+            }
+            Some(LoginOutcome::MfaRequired { code_types })
+        } else {
+            None
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        matches!(self, LoginOutcome::Success(_))
+    }
+
+    /// The session, or the step still missing as an error
+    pub fn into_success(self) -> crate::error::Result<LoginResponse> {
+        match self {
+            LoginOutcome::Success(response) => Ok(*response),
+            LoginOutcome::MfaRequired { .. } => Err(crate::error::WebullError::MfaRequired),
+            LoginOutcome::SecurityQuestionRequired { question } => {
+                Err(crate::error::WebullError::AuthenticationError(format!(
+                    "security question required: {}",
+                    question.question
+                )))
+            }
+            LoginOutcome::CaptchaRequired(challenge) => {
+                Err(crate::error::WebullError::CaptchaRequired(challenge))
+            }
+        }
+    }
+}
+
+/// Security question asked before a login is accepted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityQuestion {
+    /// Pass back with the answer to `login`
+    pub question_id: String,
+    pub question: String,
+}
+
+impl SecurityQuestion {
+    pub fn from_response(value: &Value) -> Self {
+        let text = |keys: &[&str]| {
+            keys.iter()
+                .find_map(|key| match value.get(*key) {
+                    Some(Value::String(s)) => Some(s.clone()),
+                    Some(Value::Number(n)) => Some(n.to_string()),
+                    _ => None,
+                })
+                .unwrap_or_default()
+        };
+        Self {
+            question_id: text(&["questionId", "id"]),
+            question: text(&["questionName", "question", "questionContent"]),
+        }
+    }
+}
+
+/// A login name, classified the way Webull expects it
+///
+/// Phone numbers need a country code: "+1-2345678901", "+8613812345678",
+/// "+86 138 1234 5678" and "0044 (20) 7946 0958" all work, and are sent as
+/// "+<code>-<digits>". Emails are trimmed and lowercased.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AccountIdentifier {
+    Email(String),
+    Phone {
+        country_code: String,
+        number: String,
+    },
+    /// Anything else, which Webull treats like an email login
+    Username(String),
+}
+
+impl AccountIdentifier {
+    pub fn parse(login: &str) -> crate::error::Result<Self> {
+        let login = login.trim();
+        let invalid = |message: &str| {
+            Err(crate::error::WebullError::InvalidParameter(
+                message.to_string(),
+            ))
+        };
+
+        if login.contains('@') {
+            let email = login.to_lowercase();
+            return if crate::utils::validate_email(&email) {
+                Ok(AccountIdentifier::Email(email))
+            } else {
+                invalid("Invalid email format")
+            };
+        }
+
+        let phone_chars = |s: &str| {
+            s.chars()
+                .all(|c| c.is_ascii_digit() || matches!(c, ' ' | '-' | '.' | '(' | ')'))
+        };
+        let international = login
+            .strip_prefix('+')
+            .or_else(|| login.strip_prefix("00").filter(|rest| phone_chars(rest)));
+        let Some(rest) = international else {
+            return Ok(AccountIdentifier::Username(login.to_string()));
+        };
+
+        let mut country_code: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+        let mut number_part = &rest[country_code.len()..];
+        if number_part.is_empty() || country_code.len() > 3 {
+            // No separator after the code: split it off by its known length
+            let len = country_code_len(&country_code);
+            number_part = &rest[len.min(rest.len())..];
+            country_code.truncate(len);
+        }
+        if country_code.is_empty() || country_code.len() > 3 {
+            return invalid("Phone numbers need a 1-3 digit country code");
+        }
+        if !phone_chars(number_part) {
+            return invalid("Invalid phone number");
+        }
+        let number: String = number_part.chars().filter(|c| c.is_ascii_digit()).collect();
+        if !(4..=14).contains(&number.len()) {
+            return invalid("Invalid phone number length");
+        }
+        Ok(AccountIdentifier::Phone {
+            country_code,
+            number,
+        })
+    }
+
+    /// Webull's `accountType`: 1 for phones, 2 otherwise
+    pub fn account_type(&self) -> i32 {
+        self.mfa_channel().account_type()
+    }
+
+    /// Where Webull sends verification codes for this login
+    pub fn mfa_channel(&self) -> MfaChannel {
+        match self {
+            AccountIdentifier::Phone { .. } => MfaChannel::Sms,
+            _ => MfaChannel::Email,
+        }
+    }
+
+    /// The `account` value sent to Webull
+    pub fn account(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Length of the ITU calling code at the start of `digits`
+///
+/// Calling codes are prefix-free: 1 and 7 are the only one-digit codes and
+/// the two-digit ones are listed here; everything else has three digits.
+fn country_code_len(digits: &str) -> usize {
+    const TWO_DIGIT: &[&str] = &[
+        "20", "27", "30", "31", "32", "33", "34", "36", "39", "40", "41", "43", "44", "45", "46",
+        "47", "48", "49", "51", "52", "53", "54", "55", "56", "57", "58", "60", "61", "62", "63",
+        "64", "65", "66", "81", "82", "84", "86", "90", "91", "92", "93", "94", "95", "98",
+    ];
+    if digits.starts_with('1') || digits.starts_with('7') {
+        1
+    } else if TWO_DIGIT.iter().any(|code| digits.starts_with(code)) {
+        2
+    } else {
+        3
+    }
+}
+
+impl std::fmt::Display for AccountIdentifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccountIdentifier::Email(email) => write!(f, "{}", email),
+            AccountIdentifier::Phone {
+                country_code,
+                number,
+            } => write!(f, "+{}-{}", country_code, number),
+            AccountIdentifier::Username(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl std::str::FromStr for AccountIdentifier {
+    type Err = crate::error::WebullError;
+
+    fn from_str(login: &str) -> crate::error::Result<Self> {
+        Self::parse(login)
+    }
+}
+
+/// Where Webull sends a verification code
+///
+/// The channel follows the login: `accountType` describes the `account`
+/// value, so codes for a phone number go by SMS and everything else by
+/// email. To get a code by SMS, log in with the phone number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MfaChannel {
+    Sms,
+    Email,
+}
+
+impl MfaChannel {
+    /// The `accountType` Webull uses for this channel
+    pub fn account_type(self) -> i32 {
+        match self {
+            MfaChannel::Sms => 1,
+            MfaChannel::Email => 2,
+        }
+    }
+}
+
+/// What a verification code is for (Webull's `codeType`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MfaCodeType {
+    /// Signing in from a new device
+    #[default]
+    Login,
+    /// Any other `codeType` value
+    Other(i32),
+}
+
+impl MfaCodeType {
+    pub fn from_code(code: i32) -> Self {
+        match code {
+            5 => MfaCodeType::Login,
+            other => MfaCodeType::Other(other),
+        }
+    }
+
+    pub fn code(self) -> i32 {
+        match self {
+            MfaCodeType::Login => 5,
+            MfaCodeType::Other(code) => code,
+        }
+    }
+}
+
+/// Options for requesting and checking a verification code
+///
+/// The code goes wherever the username points; see `MfaChannel`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MfaOptions {
+    pub code_type: MfaCodeType,
+}
+
+impl MfaOptions {
+    pub fn code_type(mut self, code_type: MfaCodeType) -> Self {
+        self.code_type = code_type;
+        self
+    }
+}
+
+/// Webull's answer to a verification code request or check
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaResponse {
+    pub success: bool,
+    /// Seconds until the code expires
+    pub expires_in: Option<u64>,
+    /// Seconds before another code can be requested
+    pub retry_after: Option<u64>,
+    pub message: Option<String>,
+}
+
+impl MfaResponse {
+    /// Parse a verification code response; an empty body means success
+    pub fn from_response(success: bool, value: &Value) -> Self {
+        let data = value.get("data").filter(|d| d.is_object()).unwrap_or(value);
+        let seconds = |keys: &[&str]| {
+            value_f64(data, keys)
+                .or_else(|| value_f64(value, keys))
+                .filter(|s| *s >= 0.0)
+                .map(|s| s as u64)
+        };
+        let success = match data.get("result").or_else(|| value.get("success")) {
+            Some(Value::Bool(ok)) => *ok,
+            _ => success,
+        };
+        Self {
+            success,
+            expires_in: seconds(&["expireTime", "expiresIn", "validTime"]),
+            retry_after: seconds(&["interval", "retryAfter", "resendTime", "countDown"]),
+            message: value
+                .get("msg")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+        }
+    }
+}
+
+/// Kind of captcha Webull asks for before accepting a login
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaptchaKind {
+    /// Drag a puzzle piece to its slot in a background image
+    Slider,
+    /// Any other image verification
+    Image,
+}
+
+/// A login that was refused until a captcha is solved
+///
+/// Fetch the puzzle with `get_captcha`, submit the solution with
+/// `submit_captcha`, then call `login` again.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginChallenge {
+    pub code: String,
+    pub message: String,
+    pub kind: CaptchaKind,
+    /// Token identifying this challenge, when the response carries one
+    pub token: Option<String>,
+}
+
+impl LoginChallenge {
+    /// Whether an error code asks for a captcha
+    pub fn is_challenge_code(code: &str) -> bool {
+        let lower = code.to_ascii_lowercase();
+        lower.contains("captcha") || lower.contains("slider") || lower.contains("pic.verify")
+    }
+
+    /// Build from a login error body
+    pub fn from_body(code: &str, message: String, body: &Value) -> Self {
+        let data = body.get("data").filter(|d| d.is_object()).unwrap_or(body);
+        let token = ["captchaToken", "token", "verifyToken"]
+            .iter()
+            .find_map(|key| data.get(*key).and_then(|v| v.as_str()))
+            .map(String::from);
+        let kind = if code.to_ascii_lowercase().contains("slider") {
+            CaptchaKind::Slider
+        } else {
+            CaptchaKind::Image
+        };
+        Self {
+            code: code.to_string(),
+            message,
+            kind,
+            token,
+        }
+    }
+}
+
+/// Captcha images to show the user (or a solver)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptchaPayload {
+    pub captcha_id: String,
+    /// Base64-encoded background image
+    pub background_image: String,
+    /// Base64-encoded puzzle piece, for slider captchas
+    pub slider_image: Option<String>,
+    /// Vertical offset of the puzzle piece in the background, in pixels
+    pub slider_y: Option<i32>,
+}
+
+impl CaptchaPayload {
+    /// Parse a captcha query response; the fields may sit under `data`
+    pub fn from_response(value: &Value) -> Option<Self> {
+        let data = value.get("data").filter(|d| d.is_object()).unwrap_or(value);
+        let text = |keys: &[&str]| {
+            keys.iter().find_map(|key| {
+                data.get(*key)
+                    .and_then(|v| v.as_str())
+                    .filter(|s| !s.is_empty())
+                    .map(String::from)
+            })
+        };
+        Some(Self {
+            captcha_id: text(&["captchaId", "id", "token"])?,
+            background_image: text(&["backgroundImage", "bgImage", "originalImage"])?,
+            slider_image: text(&["sliderImage", "sliderImg", "jigsawImage"]),
+            slider_y: value_f64(data, &["sliderY", "y", "yPos"]).map(|y| y as i32),
+        })
+    }
+
+    /// The ticket proving a solved captcha, from a verification response
+    ///
+    /// A response without one means the solution wasn't accepted.
+    pub fn ticket_from_response(value: &Value) -> crate::error::Result<String> {
+        let data = value.get("data").filter(|d| d.is_object()).unwrap_or(value);
+        ["ticket", "captchaTicket", "token"]
+            .iter()
+            .find_map(|key| data.get(*key).and_then(|v| v.as_str()))
+            .filter(|ticket| !ticket.is_empty())
+            .map(String::from)
+            .ok_or_else(|| {
+                crate::error::WebullError::AuthenticationError(
+                    "Captcha verification returned no ticket".to_string(),
+                )
+            })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UserSettings {
@@ -240,6 +707,104 @@ pub struct Account {
     pub status: String,
 }
 
+/// Whether an account is self-directed or managed by Webull Advisors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccountKind {
+    Brokerage,
+    /// Managed portfolio; holdings can be read but not traded
+    Advisors,
+}
+
+/// Entry in the account list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkedAccount {
+    pub account_id: String,
+    pub account_type: Option<String>,
+    pub broker_id: Option<i64>,
+    pub broker_name: Option<String>,
+    pub status: Option<String>,
+    pub kind: AccountKind,
+}
+
+impl LinkedAccount {
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let text = |key: &str| match value.get(key) {
+            Some(Value::String(s)) if !s.is_empty() => Some(s.clone()),
+            Some(Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        };
+        let account_type = text("accountType");
+        let broker_name = text("brokerName");
+
+        let flagged = ["advisor", "isAdvisor", "managed", "isManaged"]
+            .iter()
+            .any(|key| value.get(*key).and_then(|v| v.as_bool()) == Some(true));
+        let named = [&account_type, &broker_name, &text("accountTypeName")]
+            .into_iter()
+            .flatten()
+            .any(|name| {
+                let upper = name.to_ascii_uppercase();
+                upper.contains("ADVISOR") || upper.contains("ROBO") || upper.contains("MANAGED")
+            });
+
+        Some(Self {
+            account_id: text("secAccountId")?,
+            account_type,
+            broker_id: text("brokerId").and_then(|id| id.parse().ok()),
+            broker_name,
+            status: text("status"),
+            kind: if flagged || named {
+                AccountKind::Advisors
+            } else {
+                AccountKind::Brokerage
+            },
+        })
+    }
+
+    /// Parse the account list response
+    pub fn from_list_response(value: &Value) -> Vec<Self> {
+        parse_list(value, Self::from_value)
+    }
+
+    pub fn is_managed(&self) -> bool {
+        self.kind == AccountKind::Advisors
+    }
+}
+
+/// Read-only holdings of a Webull Advisors account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedPortfolio {
+    pub account_id: String,
+    pub net_liquidation: Option<f64>,
+    pub cash_balance: Option<f64>,
+    pub positions: Vec<Position>,
+}
+
+impl ManagedPortfolio {
+    pub fn from_value(account_id: &str, value: &Value) -> Self {
+        let member = |key: &str| {
+            value
+                .get("accountMembers")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .find(|m| m.get("key").and_then(|k| k.as_str()) == Some(key))
+                .and_then(|m| value_f64(m, &["value"]))
+        };
+        Self {
+            account_id: account_id.to_string(),
+            net_liquidation: value_f64(value, &["netLiquidation"]),
+            cash_balance: member("cashBalance"),
+            positions: value
+                .get("positions")
+                .and_then(|p| serde_json::from_value(p.clone()).ok())
+                .unwrap_or_default(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountDetail {
@@ -295,10 +860,54 @@ pub struct AccountDetail {
     pub positions2: Option<Vec<Position>>,
     pub open_orders: Option<Vec<Order>>,
     pub open_orders2: Option<Vec<Order>>,
-    pub open_ipo_orders: Option<Vec<serde_json::Value>>,
+    pub open_ipo_orders: Option<Vec<IpoOrder>>,
     pub banners: Option<Vec<Banner>>,
 }
 
+impl AccountDetail {
+    /// Fill the computed balance fields from `account_members`
+    ///
+    /// Fields the account doesn't report are derived where possible: total
+    /// cash falls back to the cash balance, and settled funds to the cash
+    /// balance less unsettled funds.
+    pub fn compute_member_fields(&mut self) {
+        let members = match self.account_members.as_deref() {
+            Some(members) => members,
+            None => return,
+        };
+        let member = |keys: &[&str]| {
+            keys.iter().find_map(|key| {
+                members
+                    .iter()
+                    .find(|m| m.key == *key)
+                    .and_then(|m| m.value.parse::<f64>().ok())
+            })
+        };
+
+        self.total_market_value = member(&["totalMarketValue"]);
+        self.cash_balance = member(&["cashBalance", "usableCash"]);
+        self.buying_power = member(&[
+            "dayBuyingPower",
+            "buyingPower",
+            "usableCash",
+            "overnightBuyingPower",
+        ]);
+        self.unsettled_funds = member(&["unsettledFunds", "unsettledCash"]);
+        self.total_cash = member(&["totalCash", "totalCashValue"]).or(self.cash_balance);
+        self.settled_funds = member(&["settledFunds", "settledCash"]).or_else(|| {
+            self.cash_balance
+                .map(|cash| cash - self.unsettled_funds.unwrap_or(0.0))
+        });
+    }
+
+    /// Whether the account type allows short selling (margin accounts)
+    pub fn supports_short_selling(&self) -> bool {
+        self.account_type
+            .as_deref()
+            .is_some_and(|t| t.to_ascii_uppercase().contains("MARGIN"))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountMember {
@@ -319,6 +928,277 @@ pub struct Banner {
     pub banner_type: Option<String>,
 }
 
+/// A pending IPO subscription on the account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IpoOrder {
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
+    pub order_id: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
+    pub ticker_id: Option<String>,
+    #[serde(default, alias = "disSymbol")]
+    pub symbol: Option<String>,
+    #[serde(default, alias = "statusStr")]
+    pub status: Option<String>,
+    /// Shares subscribed for
+    #[serde(
+        default,
+        alias = "totalQuantity",
+        deserialize_with = "deserialize_f64_from_string_opt"
+    )]
+    pub quantity: Option<f64>,
+    /// Shares allotted once the IPO prices
+    #[serde(
+        default,
+        alias = "filledQuantity",
+        deserialize_with = "deserialize_f64_from_string_opt"
+    )]
+    pub allotted_quantity: Option<f64>,
+    #[serde(
+        default,
+        alias = "lmtPrice",
+        deserialize_with = "deserialize_f64_from_string_opt"
+    )]
+    pub limit_price: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
+    pub create_time: Option<String>,
+}
+
+/// Dividend reinvestment override for one holding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DripHolding {
+    pub ticker_id: i64,
+    pub symbol: Option<String>,
+    pub enabled: bool,
+}
+
+/// Dividend reinvestment (DRIP) preferences for an account
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DripSettings {
+    /// Account-wide setting, followed by holdings without an override
+    pub enabled: bool,
+    #[serde(default)]
+    pub holdings: Vec<DripHolding>,
+}
+
+impl DripSettings {
+    /// Parse a DRIP settings response, with or without a `data` wrapper
+    ///
+    /// A status this parser doesn't recognize is an error rather than
+    /// "off", as is a response with no account-wide status. Holdings
+    /// without a ticker ID or status are skipped.
+    pub fn from_value(value: &Value) -> crate::error::Result<Self> {
+        use crate::error::WebullError;
+
+        let value = value.get("data").filter(|d| d.is_object()).unwrap_or(value);
+        let flag = |v: &Value, keys: &[&str]| -> crate::error::Result<Option<bool>> {
+            let raw = match keys.iter().find_map(|key| v.get(*key)) {
+                Some(raw) => raw,
+                None => return Ok(None),
+            };
+            let enabled = match raw {
+                Value::Bool(b) => Some(*b),
+                Value::Number(n) => match n.as_i64() {
+                    Some(1) => Some(true),
+                    Some(0) => Some(false),
+                    _ => None,
+                },
+                Value::String(s) => match s.to_ascii_uppercase().as_str() {
+                    "ON" | "Y" | "YES" | "TRUE" | "1" | "OPEN" | "ENABLED" => Some(true),
+                    "OFF" | "N" | "NO" | "FALSE" | "0" | "CLOSE" | "CLOSED" | "DISABLED" => {
+                        Some(false)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+            enabled
+                .map(Some)
+                .ok_or_else(|| WebullError::ParseError(format!("Unrecognized DRIP status {}", raw)))
+        };
+        let keys = ["enabled", "dripStatus", "status"];
+
+        let mut holdings = Vec::new();
+        let items = ["holdings", "tickers", "list"]
+            .iter()
+            .find_map(|key| value.get(*key).and_then(|v| v.as_array()));
+        for item in items.into_iter().flatten() {
+            let ticker = item.get("ticker").unwrap_or(item);
+            let ticker_id = match ticker.get("tickerId") {
+                Some(Value::Number(n)) => n.as_i64(),
+                Some(Value::String(s)) => s.parse().ok(),
+                _ => None,
+            };
+            if let (Some(ticker_id), Some(enabled)) = (ticker_id, flag(item, &keys)?) {
+                holdings.push(DripHolding {
+                    ticker_id,
+                    symbol: ticker
+                        .get("disSymbol")
+                        .or_else(|| ticker.get("symbol"))
+                        .and_then(|v| v.as_str())
+                        .map(String::from),
+                    enabled,
+                });
+            }
+        }
+
+        let enabled = flag(value, &keys)?
+            .ok_or_else(|| WebullError::ParseError("DRIP settings have no status".to_string()))?;
+        Ok(Self { enabled, holdings })
+    }
+
+    /// Whether dividends from a holding are reinvested
+    pub fn is_enabled_for(&self, ticker_id: i64) -> bool {
+        self.holdings
+            .iter()
+            .find(|h| h.ticker_id == ticker_id)
+            .map_or(self.enabled, |h| h.enabled)
+    }
+
+    /// Override the account-wide setting for one holding
+    pub fn set_holding(&mut self, ticker_id: i64, enabled: bool) -> &mut Self {
+        match self.holdings.iter_mut().find(|h| h.ticker_id == ticker_id) {
+            Some(holding) => holding.enabled = enabled,
+            None => self.holdings.push(DripHolding {
+                ticker_id,
+                symbol: None,
+                enabled,
+            }),
+        }
+        self
+    }
+
+    /// Request body for updating the settings
+    pub fn to_request(&self) -> Value {
+        let status = |enabled: bool| if enabled { "ON" } else { "OFF" };
+        serde_json::json!({
+            "dripStatus": status(self.enabled),
+            "tickers": self
+                .holdings
+                .iter()
+                .map(|h| serde_json::json!({
+                    "tickerId": h.ticker_id,
+                    "dripStatus": status(h.enabled)
+                }))
+                .collect::<Vec<_>>()
+        })
+    }
+}
+
+// ============= Activity Models =============
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ActivityType {
+    Trade,
+    Dividend,
+    Interest,
+    Fee,
+    Transfer,
+    Other,
+}
+
+impl ActivityType {
+    /// Classify a raw Webull activity type string
+    pub fn from_raw(raw: &str) -> Self {
+        let raw = raw.to_uppercase();
+        if raw.contains("DIV") {
+            ActivityType::Dividend
+        } else if raw.contains("INTEREST") {
+            ActivityType::Interest
+        } else if raw.contains("FEE") || raw.contains("COMMISSION") {
+            ActivityType::Fee
+        } else if ["DEPOSIT", "WITHDRAW", "TRANSFER", "ACAT", "ACH", "WIRE"]
+            .iter()
+            .any(|k| raw.contains(k))
+        {
+            ActivityType::Transfer
+        } else if ["BUY", "SELL", "TRADE", "FILL"]
+            .iter()
+            .any(|k| raw.contains(k))
+        {
+            ActivityType::Trade
+        } else {
+            ActivityType::Other
+        }
+    }
+}
+
+/// Account activity / transaction record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Activity {
+    pub id: Option<String>,
+    pub activity_type: ActivityType,
+    /// Type string exactly as reported by the API
+    pub raw_type: String,
+    pub date: Option<chrono::NaiveDate>,
+    pub symbol: Option<String>,
+    pub description: Option<String>,
+    pub quantity: Option<f64>,
+    pub price: Option<f64>,
+    pub amount: Option<f64>,
+    pub currency: Option<String>,
+}
+
+impl Activity {
+    /// Parse an activity record from the activities endpoint
+    pub fn from_value(value: &Value) -> Self {
+        let raw_type = ["type", "activityType", "transType", "bizType"]
+            .iter()
+            .find_map(|k| value.get(*k).and_then(|v| v.as_str()))
+            .unwrap_or_default()
+            .to_string();
+
+        let id = match value.get("id").or_else(|| value.get("activityId")) {
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        };
+
+        let date = ["date", "tradeDate", "settleDate", "createTime", "time"]
+            .iter()
+            .find_map(|k| value.get(*k))
+            .and_then(|v| match v {
+                Value::Number(n) => n
+                    .as_i64()
+                    .and_then(chrono::DateTime::from_timestamp_millis)
+                    .map(|t| t.date_naive()),
+                Value::String(s) => s
+                    .get(..10)
+                    .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()),
+                _ => None,
+            });
+
+        let symbol = value
+            .get("ticker")
+            .and_then(|t| t.get("disSymbol").or_else(|| t.get("symbol")))
+            .or_else(|| value.get("symbol"))
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        Self {
+            id,
+            activity_type: ActivityType::from_raw(&raw_type),
+            raw_type,
+            date,
+            symbol,
+            description: value
+                .get("description")
+                .or_else(|| value.get("remark"))
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            quantity: value_f64(value, &["quantity", "filledQuantity"]),
+            price: value_f64(value, &["price", "avgFilledPrice"]),
+            amount: value_f64(value, &["amount", "netAmount", "value"]),
+            currency: value
+                .get("currency")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        }
+    }
+}
+
 // ============= Paper Account Models =============
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -422,27 +1302,202 @@ pub struct Ticker {
     pub shariah_flag: Option<i32>,
 }
 
-// ============= Quote Models =============
+impl Ticker {
+    /// Instrument class from the `template` field
+    pub fn instrument_type(&self) -> InstrumentType {
+        self.template
+            .as_deref()
+            .map(InstrumentType::from_template)
+            .unwrap_or(InstrumentType::Other)
+    }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+    /// Whether options are listed on this ticker
+    pub fn has_options(&self) -> bool {
+        self.derivative_support == Some(1)
+    }
+
+    /// The ticker listed under exactly `symbol`, preferring `region_id`
+    ///
+    /// Symbols are compared ignoring case and share-class separators, so
+    /// "brk.b", "BRK-B" and "BRK B" all match "BRK.B".
+    pub fn exact_match<'a>(
+        tickers: &'a [Ticker],
+        symbol: &str,
+        region_id: i32,
+    ) -> Option<&'a Ticker> {
+        let wanted = normalize_symbol(symbol);
+        let mut matches = tickers
+            .iter()
+            .filter(|t| normalize_symbol(&t.symbol) == wanted);
+        let first = matches.next()?;
+        if first.region_id == region_id {
+            return Some(first);
+        }
+        matches.find(|t| t.region_id == region_id).or(Some(first))
+    }
+}
+
+fn normalize_symbol(symbol: &str) -> String {
+    symbol
+        .chars()
+        .filter(|c| !matches!(c, '.' | '-' | '/' | ' '))
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Narrows a ticker search
+///
+/// Instrument classes are alternatives: `stocks().etfs()` keeps either.
+/// The other settings must all match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TickerFilter {
+    /// Classes to keep; empty keeps all
+    pub instrument_types: Vec<InstrumentType>,
+    /// Keep only tickers with listed options
+    pub options_enabled: bool,
+    /// Search this region instead of the client's, and keep only its tickers
+    pub region_id: Option<i32>,
+}
+
+impl TickerFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn instrument_type(mut self, instrument_type: InstrumentType) -> Self {
+        if !self.instrument_types.contains(&instrument_type) {
+            self.instrument_types.push(instrument_type);
+        }
+        self
+    }
+
+    pub fn stocks(self) -> Self {
+        self.instrument_type(InstrumentType::Stock)
+    }
+
+    pub fn etfs(self) -> Self {
+        self.instrument_type(InstrumentType::Etf)
+    }
+
+    pub fn crypto(self) -> Self {
+        self.instrument_type(InstrumentType::Crypto)
+    }
+
+    pub fn options_enabled(mut self) -> Self {
+        self.options_enabled = true;
+        self
+    }
+
+    pub fn region(mut self, region_id: i32) -> Self {
+        self.region_id = Some(region_id);
+        self
+    }
+
+    pub fn matches(&self, ticker: &Ticker) -> bool {
+        (self.instrument_types.is_empty()
+            || self.instrument_types.contains(&ticker.instrument_type()))
+            && (!self.options_enabled || ticker.has_options())
+            && self.region_id.is_none_or(|id| ticker.region_id == id)
+    }
+}
+
+/// Instrument class used to filter the instrument universe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InstrumentType {
+    Stock,
+    Etf,
+    Crypto,
+    Other,
+}
+
+impl InstrumentType {
+    /// Classify from Webull's `template` field ("stock", "etf", ...)
+    pub fn from_template(template: &str) -> Self {
+        match template.to_ascii_lowercase().as_str() {
+            "stock" | "adr" => InstrumentType::Stock,
+            "etf" | "etn" => InstrumentType::Etf,
+            "crypto" => InstrumentType::Crypto,
+            _ => InstrumentType::Other,
+        }
+    }
+}
+
+/// Static data for one tradable instrument
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Quote {
+pub struct Instrument {
+    pub ticker_id: i64,
+    pub symbol: String,
+    pub name: String,
+    pub exchange_code: Option<String>,
+    pub instrument_type: InstrumentType,
+}
+
+impl Instrument {
+    /// Parse an instrument from a list entry, either flat or wrapped in `ticker`
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let ticker = value.get("ticker").unwrap_or(value);
+        let ticker_id = match ticker.get("tickerId")? {
+            Value::Number(n) => n.as_i64()?,
+            Value::String(s) => s.parse().ok()?,
+            _ => return None,
+        };
+        let symbol = ticker
+            .get("disSymbol")
+            .or_else(|| ticker.get("symbol"))
+            .and_then(|v| v.as_str())?
+            .to_string();
+        let text = |key: &str| ticker.get(key).and_then(|v| v.as_str()).map(String::from);
+
+        Some(Self {
+            ticker_id,
+            symbol,
+            name: text("name").unwrap_or_default(),
+            exchange_code: text("disExchangeCode").or_else(|| text("exchangeCode")),
+            instrument_type: text("template")
+                .map(|t| InstrumentType::from_template(&t))
+                .unwrap_or(InstrumentType::Other),
+        })
+    }
+
+    /// Parse every instrument in a list response
+    pub fn from_list_response(value: &Value) -> Vec<Self> {
+        parse_list(value, Self::from_value)
+    }
+}
+
+// ============= Quote Models =============
+
+/// A quote snapshot
+///
+/// Only `close` is always present; pre-market and for newly listed tickers
+/// Webull leaves out fields such as `open`, `high` and `low`. The response
+/// itself is kept in `raw` for fields not modelled here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self", rename_all = "camelCase")]
+pub struct Quote {
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
+    pub ticker_id: Option<String>,
     #[serde(deserialize_with = "deserialize_f64_from_string")]
     pub close: f64,
-    #[serde(deserialize_with = "deserialize_f64_from_string")]
-    pub change: f64,
-    #[serde(deserialize_with = "deserialize_f64_from_string")]
-    pub change_ratio: f64,
-    #[serde(rename = "preClose", deserialize_with = "deserialize_f64_from_string")]
-    pub pre_close: f64,
-    #[serde(deserialize_with = "deserialize_f64_from_string")]
-    pub open: f64,
-    #[serde(deserialize_with = "deserialize_f64_from_string")]
-    pub high: f64,
-    #[serde(deserialize_with = "deserialize_f64_from_string")]
-    pub low: f64,
-    #[serde(deserialize_with = "deserialize_f64_from_string")]
-    pub volume: f64,
+    #[serde(deserialize_with = "deserialize_f64_from_string_opt", default)]
+    pub change: Option<f64>,
+    #[serde(deserialize_with = "deserialize_f64_from_string_opt", default)]
+    pub change_ratio: Option<f64>,
+    #[serde(
+        rename = "preClose",
+        deserialize_with = "deserialize_f64_from_string_opt",
+        default
+    )]
+    pub pre_close: Option<f64>,
+    #[serde(deserialize_with = "deserialize_f64_from_string_opt", default)]
+    pub open: Option<f64>,
+    #[serde(deserialize_with = "deserialize_f64_from_string_opt", default)]
+    pub high: Option<f64>,
+    #[serde(deserialize_with = "deserialize_f64_from_string_opt", default)]
+    pub low: Option<f64>,
+    #[serde(deserialize_with = "deserialize_f64_from_string_opt", default)]
+    pub volume: Option<f64>,
     #[serde(deserialize_with = "deserialize_f64_from_string_opt", default)]
     pub avg_vol10_d: Option<f64>,
     #[serde(
@@ -469,8 +1524,195 @@ pub struct Quote {
     pub bid_size: Option<f64>,
     pub currency_code: Option<String>,
     pub currency_id: Option<i32>,
+    /// Time of the last trade, e.g. "2024-06-03T19:59:59.000+0000"
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
+    pub trade_time: Option<String>,
+    /// Last pre- or post-market price, when trading outside regular hours
+    #[serde(
+        rename = "pPrice",
+        deserialize_with = "deserialize_f64_from_string_opt",
+        default
+    )]
+    pub p_price: Option<f64>,
+    /// Pre/post-market change from the regular-session close
+    #[serde(
+        rename = "pChange",
+        deserialize_with = "deserialize_f64_from_string_opt",
+        default
+    )]
+    pub p_change: Option<f64>,
+    #[serde(
+        rename = "pChRatio",
+        deserialize_with = "deserialize_f64_from_string_opt",
+        default
+    )]
+    pub p_change_ratio: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub depth: Option<Depth>,
+    /// The quote object as received; not serialized
+    #[serde(skip)]
+    pub raw: Value,
+}
+
+impl<'de> Deserialize<'de> for Quote {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = Value::deserialize(deserializer)?;
+        let mut quote = Quote::deserialize(&raw).map_err(de::Error::custom)?;
+        quote.raw = raw;
+        Ok(quote)
+    }
+}
+
+impl Serialize for Quote {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Quote::serialize(self, serializer)
+    }
+}
+
+impl Quote {
+    pub fn builder(close: f64) -> QuoteBuilder {
+        QuoteBuilder::new(close)
+    }
+
+    /// A field of the response as received, including ones not modelled
+    /// here
+    pub fn get(&self, field: &str) -> Option<&Value> {
+        self.raw.get(field)
+    }
+
+    /// A numeric field of the response, parsed from a string if needed
+    pub fn get_f64(&self, field: &str) -> Option<f64> {
+        match self.get(field)? {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Session low and high, when both are known
+    pub fn day_range(&self) -> Option<(f64, f64)> {
+        self.low.zip(self.high)
+    }
+
+    /// Most recent trade price, including pre/post-market trading
+    pub fn latest_price(&self) -> f64 {
+        self.p_price.filter(|p| *p > 0.0).unwrap_or(self.close)
+    }
+
+    /// Parse a multi-ticker quote response, skipping entries that fail to parse
+    pub fn from_batch_response(value: &Value) -> Vec<Self> {
+        parse_list(value, |item| {
+            match serde_json::from_value::<Quote>(item.clone()) {
+                Ok(quote) => Some(quote),
+                Err(e) => {
+                    log::warn!("Skipping unparseable quote: {}", e);
+                    None
+                }
+            }
+        })
+    }
+}
+
+/// Builds a `Quote` by hand, e.g. for mocks and tests
+#[derive(Debug, Clone)]
+pub struct QuoteBuilder {
+    quote: Quote,
+}
+
+impl QuoteBuilder {
+    pub fn new(close: f64) -> Self {
+        Self {
+            quote: Quote {
+                ticker_id: None,
+                close,
+                change: None,
+                change_ratio: None,
+                pre_close: None,
+                open: None,
+                high: None,
+                low: None,
+                volume: None,
+                avg_vol10_d: None,
+                avg_vol3_m: None,
+                market_value: None,
+                pe: None,
+                forward_pe: None,
+                bps: None,
+                ask: None,
+                bid: None,
+                ask_size: None,
+                bid_size: None,
+                currency_code: None,
+                currency_id: None,
+                trade_time: None,
+                p_price: None,
+                p_change: None,
+                p_change_ratio: None,
+                depth: None,
+                raw: Value::Null,
+            },
+        }
+    }
+
+    pub fn ticker_id(mut self, ticker_id: impl ToString) -> Self {
+        self.quote.ticker_id = Some(ticker_id.to_string());
+        self
+    }
+
+    /// Set `pre_close`, and `change` and `change_ratio` from it
+    pub fn pre_close(mut self, pre_close: f64) -> Self {
+        self.quote.pre_close = Some(pre_close);
+        self.quote.change = Some(self.quote.close - pre_close);
+        self.quote.change_ratio = Some(if pre_close == 0.0 {
+            0.0
+        } else {
+            (self.quote.close - pre_close) / pre_close
+        });
+        self
+    }
+
+    /// Set open, high and low
+    pub fn session(mut self, open: f64, high: f64, low: f64) -> Self {
+        self.quote.open = Some(open);
+        self.quote.high = Some(high);
+        self.quote.low = Some(low);
+        self
+    }
+
+    pub fn volume(mut self, volume: f64) -> Self {
+        self.quote.volume = Some(volume);
+        self
+    }
+
+    pub fn bid_ask(mut self, bid: f64, ask: f64) -> Self {
+        self.quote.bid = Some(bid);
+        self.quote.ask = Some(ask);
+        self
+    }
+
+    /// Last pre- or post-market price
+    pub fn extended_price(mut self, price: f64) -> Self {
+        self.quote.p_price = Some(price);
+        self
+    }
+
+    pub fn trade_time(mut self, trade_time: impl Into<String>) -> Self {
+        self.quote.trade_time = Some(trade_time.into());
+        self
+    }
+
+    /// The quote, with `raw` holding its serialized fields
+    pub fn build(self) -> Quote {
+        let mut quote = self.quote;
+        quote.raw = serde_json::to_value(&quote).unwrap_or(Value::Null);
+        quote
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -489,6 +1731,251 @@ pub struct PriceLevel {
     pub volume: f64,
 }
 
+/// One trade from the time & sales feed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeTick {
+    pub price: f64,
+    /// Shares traded
+    pub size: f64,
+    /// Unix milliseconds
+    pub time: i64,
+    /// Exchange code, when reported
+    pub exchange: Option<String>,
+    /// Trade condition code, when reported
+    pub condition: Option<String>,
+}
+
+impl TradeTick {
+    /// Parse a trade tick response into ticks, oldest first
+    ///
+    /// Accepts the ticks as a bare array, under `data`, or under `data` of
+    /// the first element of an array; entries without a price or time are
+    /// skipped.
+    pub fn from_response(value: &Value) -> Vec<Self> {
+        let items = match value {
+            Value::Array(items) => match items.first().and_then(|first| first.get("data")) {
+                Some(Value::Array(nested)) => nested,
+                _ => items,
+            },
+            _ => match value.get("data") {
+                Some(Value::Array(items)) => items,
+                _ => return Vec::new(),
+            },
+        };
+        let text = |item: &Value, keys: &[&str]| {
+            keys.iter().find_map(|key| match item.get(*key) {
+                Some(Value::String(s)) if !s.is_empty() => Some(s.clone()),
+                Some(Value::Number(n)) => Some(n.to_string()),
+                _ => None,
+            })
+        };
+
+        let mut ticks: Vec<Self> = items
+            .iter()
+            .filter_map(|item| {
+                Some(Self {
+                    price: value_f64(item, &["price", "deal", "tradePrice"])?,
+                    size: value_f64(item, &["volume", "size", "tradeVolume"]).unwrap_or(0.0),
+                    time: value_f64(item, &["tradeTime", "tradeStamp", "time"])? as i64,
+                    exchange: text(item, &["exchange", "trdEx", "exchangeCode"]),
+                    condition: text(item, &["tradeCondition", "condition", "trdCond"]),
+                })
+            })
+            .collect();
+        ticks.sort_by_key(|tick| tick.time);
+        ticks
+    }
+}
+
+/// One constituent of an ETF
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EtfHolding {
+    pub symbol: String,
+    pub name: Option<String>,
+    pub ticker_id: Option<i64>,
+    /// Share of the fund's assets, as a fraction (0.07 = 7%)
+    pub weight: f64,
+    pub shares: Option<f64>,
+    pub market_value: Option<f64>,
+}
+
+impl EtfHolding {
+    /// Parse a holdings response, heaviest first
+    ///
+    /// Accepts the holdings as a bare array or under `data`, `holdings` or
+    /// `list`. Weights are reported in percent and converted to fractions.
+    pub fn from_response(value: &Value) -> Vec<Self> {
+        let items = match value {
+            Value::Array(items) => items,
+            _ => match ["data", "holdings", "list"]
+                .iter()
+                .find_map(|key| value.get(*key).and_then(|v| v.as_array()))
+            {
+                Some(items) => items,
+                None => return Vec::new(),
+            },
+        };
+
+        let mut holdings: Vec<Self> = items
+            .iter()
+            .filter_map(|item| {
+                let symbol = ["symbol", "disSymbol", "tickerSymbol"]
+                    .iter()
+                    .find_map(|key| item.get(*key).and_then(|v| v.as_str()))?;
+                let weight = value_f64(item, &["ratio", "weight", "holdingRatio"])?;
+                Some(Self {
+                    symbol: symbol.to_string(),
+                    name: item.get("name").and_then(|v| v.as_str()).map(String::from),
+                    ticker_id: value_f64(item, &["tickerId"]).map(|id| id as i64),
+                    weight: weight / 100.0,
+                    shares: value_f64(item, &["shares", "holdingShares", "position"]),
+                    market_value: value_f64(item, &["marketValue", "holdingValue"]),
+                })
+            })
+            .collect();
+        holdings.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+        holdings
+    }
+}
+
+/// Fund-level details of an ETF
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EtfProfile {
+    pub ticker_id: String,
+    pub name: Option<String>,
+    pub issuer: Option<String>,
+    /// Annual expense ratio, as a fraction (0.0009 = 0.09%)
+    pub expense_ratio: Option<f64>,
+    /// Assets under management
+    pub aum: Option<f64>,
+    pub inception_date: Option<String>,
+    pub holdings_count: Option<i64>,
+    /// Largest holdings, heaviest first
+    pub top_holdings: Vec<EtfHolding>,
+}
+
+impl EtfProfile {
+    /// Parse a profile response; the fields may sit at the top level or
+    /// under `data`. The expense ratio is reported in percent.
+    pub fn from_response(ticker_id: &str, value: &Value) -> Self {
+        let data = value.get("data").filter(|d| d.is_object()).unwrap_or(value);
+        let text = |keys: &[&str]| {
+            keys.iter().find_map(|key| {
+                data.get(*key)
+                    .and_then(|v| v.as_str())
+                    .filter(|s| !s.is_empty())
+                    .map(String::from)
+            })
+        };
+        let top_holdings = ["topHoldings", "holdings"]
+            .iter()
+            .find_map(|key| data.get(*key))
+            .map(EtfHolding::from_response)
+            .unwrap_or_default();
+
+        Self {
+            ticker_id: ticker_id.to_string(),
+            name: text(&["name", "fundName"]),
+            issuer: text(&["issuer", "fundCompany", "sponsor"]),
+            expense_ratio: value_f64(data, &["expenseRatio", "netExpenseRatio", "managementFee"])
+                .map(|ratio| ratio / 100.0),
+            aum: value_f64(data, &["aum", "totalAssets", "netAssets"]),
+            inception_date: text(&["inceptionDate", "establishDate"]),
+            holdings_count: value_f64(data, &["holdingsCount", "holdingCount", "totalHoldings"])
+                .map(|count| count as i64),
+            top_holdings,
+        }
+    }
+}
+
+/// An upcoming or recently priced IPO
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IpoListing {
+    pub ticker_id: Option<i64>,
+    pub symbol: String,
+    pub name: Option<String>,
+    pub exchange: Option<String>,
+    /// Low end of the expected price range
+    pub price_low: Option<f64>,
+    /// High end of the expected price range; equals `price_low` once priced
+    pub price_high: Option<f64>,
+    /// Shares offered
+    pub shares: Option<f64>,
+    pub expected_date: Option<chrono::NaiveDate>,
+    pub status: Option<String>,
+}
+
+impl IpoListing {
+    /// Parse an IPO calendar response, soonest first
+    ///
+    /// Accepts the listings as a bare array or under `data` or `list`, and
+    /// the price range as separate bounds, a single price or a `"14-16"`
+    /// string. Listings without a date sort last.
+    pub fn from_response(value: &Value) -> Vec<Self> {
+        let items = match value {
+            Value::Array(items) => items,
+            _ => match ["data", "list"]
+                .iter()
+                .find_map(|key| value.get(*key).and_then(|v| v.as_array()))
+            {
+                Some(items) => items,
+                None => return Vec::new(),
+            },
+        };
+        let text = |item: &Value, keys: &[&str]| {
+            keys.iter().find_map(|key| {
+                item.get(*key)
+                    .and_then(|v| v.as_str())
+                    .filter(|s| !s.is_empty())
+                    .map(String::from)
+            })
+        };
+
+        let mut listings: Vec<Self> = items
+            .iter()
+            .filter_map(|item| {
+                let symbol = text(item, &["symbol", "disSymbol"])?;
+                let (mut price_low, mut price_high) = (
+                    value_f64(item, &["priceLow", "minPrice", "priceFrom"]),
+                    value_f64(item, &["priceHigh", "maxPrice", "priceTo"]),
+                );
+                if let Some(range) = text(item, &["priceRange", "ipoPrice", "price"]) {
+                    let mut bounds = range.split(['-', '~']).filter_map(|bound| {
+                        bound.trim().trim_start_matches('$').parse::<f64>().ok()
+                    });
+                    let low = bounds.next();
+                    price_low = price_low.or(low);
+                    price_high = price_high.or(bounds.next()).or(low);
+                } else if let Some(price) = value_f64(item, &["ipoPrice", "price"]) {
+                    price_low = price_low.or(Some(price));
+                    price_high = price_high.or(Some(price));
+                }
+                let expected_date = text(item, &["ipoDate", "expectedDate", "listDate"])
+                    .and_then(|date| date.get(..10).map(String::from))
+                    .and_then(|date| chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok());
+
+                Some(Self {
+                    ticker_id: value_f64(item, &["tickerId"]).map(|id| id as i64),
+                    symbol,
+                    name: text(item, &["name", "companyName"]),
+                    exchange: text(item, &["exchange", "disExchangeCode", "exchangeCode"]),
+                    price_low,
+                    price_high,
+                    shares: value_f64(item, &["shares", "sharesOffered", "issueShares"]),
+                    expected_date,
+                    status: text(item, &["status", "ipoStatus"]),
+                })
+            })
+            .collect();
+        listings.sort_by_key(|listing| (listing.expected_date.is_none(), listing.expected_date));
+        listings
+    }
+}
+
 // ============= Order Models =============
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -576,6 +2063,29 @@ pub enum OrderStatus {
     Failed,
     #[serde(rename = "Rejected")]
     Rejected,
+    /// A status this client doesn't recognize (e.g. "Pending Cancel"), as
+    /// reported; never treated as open
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+impl Order {
+    pub fn ticker_id(&self) -> Option<i64> {
+        self.ticker.as_ref().map(|t| t.ticker_id)
+    }
+}
+
+impl OrderStatus {
+    /// Whether the order can still fill or be cancelled
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self,
+            OrderStatus::Working
+                | OrderStatus::Pending
+                | OrderStatus::Submitted
+                | OrderStatus::PartialFilled
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -589,6 +2099,236 @@ pub enum TimeInForce {
     ImmediateOrCancel,
     #[serde(rename = "FOK")]
     FillOrKill,
+    /// Good-til-date; requires an expiration date on the order
+    #[serde(rename = "GTD")]
+    GoodTillDate,
+}
+
+/// Furthest a good-til-date order may be set to expire, in calendar days
+pub const GTD_MAX_DAYS: i64 = 90;
+
+/// Check that an expiration date is consistent with the time in force
+///
+/// GTD orders need a date after `today` and no more than `GTD_MAX_DAYS` out;
+/// other time-in-force values must not carry one. `today` is the exchange's
+/// date (`market::today()`), which runs behind UTC in the evening.
+pub fn validate_expiration(
+    time_in_force: &TimeInForce,
+    expire_date: Option<chrono::NaiveDate>,
+    today: chrono::NaiveDate,
+) -> Result<(), String> {
+    match (time_in_force, expire_date) {
+        (TimeInForce::GoodTillDate, None) => Err("GTD order requires expire_date".to_string()),
+        (TimeInForce::GoodTillDate, Some(date)) => {
+            if date <= today {
+                return Err(format!("GTD expire_date {} must be after {}", date, today));
+            }
+            let latest = today + chrono::Duration::days(GTD_MAX_DAYS);
+            if date > latest {
+                return Err(format!(
+                    "GTD expire_date {} is beyond the {} day limit ({})",
+                    date, GTD_MAX_DAYS, latest
+                ));
+            }
+            Ok(())
+        }
+        (_, Some(_)) => Err(format!(
+            "expire_date is only valid for GTD orders, not {:?}",
+            time_in_force
+        )),
+        (_, None) => Ok(()),
+    }
+}
+
+/// Order from the order history endpoints, normalized across live and paper schemas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryOrder {
+    pub order_id: String,
+    pub combo_id: Option<String>,
+    pub ticker: Option<Ticker>,
+    pub action: OrderAction,
+    pub order_type: OrderType,
+    pub status: OrderStatus,
+    /// Status exactly as reported by the API (e.g. "Partial Filled", "Expired")
+    pub status_text: String,
+    pub time_in_force: TimeInForce,
+    pub quantity: f64,
+    pub filled_quantity: f64,
+    pub avg_fill_price: Option<f64>,
+    pub limit_price: Option<f64>,
+    pub stop_price: Option<f64>,
+    pub outside_regular_trading_hour: bool,
+    pub placed_time: Option<String>,
+    pub filled_time: Option<String>,
+}
+
+impl HistoryOrder {
+    /// Parse a single history order from either the live or paper schema
+    pub fn from_value(order_val: &Value) -> crate::error::Result<Self> {
+        use crate::error::WebullError;
+
+        let order_id = match order_val.get("orderId") {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Number(n)) => n.to_string(),
+            _ => return Err(WebullError::ParseError("Missing orderId".to_string())),
+        };
+
+        let ticker = match order_val.get("ticker") {
+            Some(t) => Some(serde_json::from_value::<Ticker>(t.clone())?),
+            None => None,
+        };
+
+        let action = match order_val.get("action").and_then(|v| v.as_str()) {
+            Some("BUY") => OrderAction::Buy,
+            Some("SELL") => OrderAction::Sell,
+            _ => return Err(WebullError::ParseError("Invalid action".to_string())),
+        };
+
+        let order_type = match order_val.get("orderType").and_then(|v| v.as_str()) {
+            Some("MKT") => OrderType::Market,
+            Some("LMT") => OrderType::Limit,
+            Some("STP") => OrderType::Stop,
+            Some("STP LMT") | Some("STP_LMT") => OrderType::StopLimit,
+            _ => return Err(WebullError::ParseError("Invalid order type".to_string())),
+        };
+
+        // Live orders carry both a display "status" and a "statusCode"; prefer the code
+        let status_text = order_val
+            .get("statusCode")
+            .or_else(|| order_val.get("status"))
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let status = parse_order_status(&status_text);
+
+        let time_in_force = match order_val.get("timeInForce").and_then(|v| v.as_str()) {
+            Some("GTC") => TimeInForce::GoodTillCancel,
+            Some("IOC") => TimeInForce::ImmediateOrCancel,
+            Some("FOK") => TimeInForce::FillOrKill,
+            Some("GTD") => TimeInForce::GoodTillDate,
+            _ => TimeInForce::Day,
+        };
+
+        Ok(Self {
+            order_id,
+            combo_id: value_id(order_val, "comboId"),
+            ticker,
+            action,
+            order_type,
+            status,
+            status_text,
+            time_in_force,
+            quantity: value_f64(order_val, &["totalQuantity", "quantity"]).unwrap_or(0.0),
+            filled_quantity: value_f64(order_val, &["filledQuantity"]).unwrap_or(0.0),
+            avg_fill_price: value_f64(order_val, &["avgFilledPrice"]),
+            limit_price: value_f64(order_val, &["lmtPrice"]),
+            stop_price: value_f64(order_val, &["auxPrice", "stopPrice"]),
+            outside_regular_trading_hour: order_val
+                .get("outsideRegularTradingHour")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            placed_time: value_time(order_val, "createTime0", "createTime"),
+            filled_time: value_time(order_val, "filledTime0", "filledTime"),
+        })
+    }
+
+    /// Parse a history response, flattening live combo groups into individual orders
+    ///
+    /// Orders that cannot be parsed are skipped.
+    pub fn from_response(result: &Value) -> Vec<Self> {
+        let mut orders = Vec::new();
+        for item in list_items(result) {
+            // Live history groups orders by combo: {"comboId": ..., "orders": [...]}
+            if let Some(inner) = item.get("orders").and_then(|v| v.as_array()) {
+                for order_val in inner {
+                    match Self::from_value(order_val) {
+                        Ok(mut order) => {
+                            if order.combo_id.is_none() {
+                                order.combo_id = value_id(item, "comboId");
+                            }
+                            orders.push(order);
+                        }
+                        Err(e) => log::warn!("Failed to parse history order: {}", e),
+                    }
+                }
+            } else {
+                match Self::from_value(item) {
+                    Ok(order) => orders.push(order),
+                    Err(e) => log::warn!("Failed to parse history order: {}", e),
+                }
+            }
+        }
+        orders
+    }
+}
+
+impl From<HistoryOrder> for Order {
+    fn from(order: HistoryOrder) -> Self {
+        Order {
+            order_id: order.order_id,
+            combo_id: order.combo_id,
+            ticker: order.ticker,
+            action: order.action,
+            order_type: order.order_type,
+            status: order.status,
+            time_in_force: order.time_in_force,
+            quantity: order.quantity,
+            filled_quantity: order.filled_quantity,
+            avg_fill_price: order.avg_fill_price,
+            limit_price: order.limit_price,
+            stop_price: order.stop_price,
+            outside_regular_trading_hour: order.outside_regular_trading_hour,
+            create_time: order.placed_time.clone(),
+            placed_time: order.placed_time,
+            filled_time: order.filled_time,
+        }
+    }
+}
+
+pub(crate) fn parse_order_status(status: &str) -> OrderStatus {
+    match status.replace(' ', "").to_lowercase().as_str() {
+        "working" => OrderStatus::Working,
+        "pending" => OrderStatus::Pending,
+        "submitted" => OrderStatus::Submitted,
+        "partialfilled" | "partiallyfilled" => OrderStatus::PartialFilled,
+        "filled" => OrderStatus::Filled,
+        "canceled" | "cancelled" | "expired" => OrderStatus::Cancelled,
+        "failed" => OrderStatus::Failed,
+        "rejected" => OrderStatus::Rejected,
+        _ => OrderStatus::Unknown(status.to_string()),
+    }
+}
+
+fn value_f64(value: &Value, keys: &[&str]) -> Option<f64> {
+    keys.iter().find_map(|key| match value.get(*key) {
+        Some(Value::String(s)) => s.parse::<f64>().ok(),
+        Some(Value::Number(n)) => n.as_f64(),
+        _ => None,
+    })
+}
+
+/// An ID sent as either a string or a number
+fn value_id(value: &Value, key: &str) -> Option<String> {
+    match value.get(key) {
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(Value::Number(n)) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn value_time(value: &Value, millis_key: &str, text_key: &str) -> Option<String> {
+    value
+        .get(millis_key)
+        .and_then(|v| v.as_i64())
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|t| t.to_rfc3339())
+        .or_else(|| {
+            value
+                .get(text_key)
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        })
 }
 
 // ============= Place Order Models =============
@@ -610,6 +2350,16 @@ pub struct PlaceOrderRequest {
     pub serial_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub combo_type: Option<String>,
+    /// Expiration date for GTD orders, sent as `YYYY-MM-DD`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expire_date: Option<chrono::NaiveDate>,
+    /// Sell shares not held, opening a short position (margin accounts only)
+    #[serde(
+        rename = "shortSupport",
+        default,
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub short_sale: bool,
 }
 
 impl PlaceOrderRequest {
@@ -654,6 +2404,8 @@ pub struct PlaceOrderRequestBuilder {
     outside_regular_trading_hour: bool,
     serial_id: Option<String>,
     combo_type: Option<String>,
+    expire_date: Option<chrono::NaiveDate>,
+    short_sale: bool,
 }
 
 impl PlaceOrderRequestBuilder {
@@ -670,6 +2422,8 @@ impl PlaceOrderRequestBuilder {
             outside_regular_trading_hour: false,
             serial_id: None,
             combo_type: None,
+            expire_date: None,
+            short_sale: false,
         }
     }
 
@@ -705,6 +2459,13 @@ impl PlaceOrderRequestBuilder {
         self
     }
 
+    /// Sell short, opening a short position
+    pub fn sell_short(mut self) -> Self {
+        self.action = Some(OrderAction::Sell);
+        self.short_sale = true;
+        self
+    }
+
     /// Set the quantity
     pub fn quantity(mut self, quantity: f64) -> Self {
         self.quantity = Some(quantity);
@@ -717,6 +2478,13 @@ impl PlaceOrderRequestBuilder {
         self
     }
 
+    /// Make the order good-til-date, expiring at the end of `date`
+    pub fn good_till_date(mut self, date: chrono::NaiveDate) -> Self {
+        self.time_in_force = TimeInForce::GoodTillDate;
+        self.expire_date = Some(date);
+        self
+    }
+
     /// Set the limit price (for limit and stop-limit orders)
     pub fn limit_price(mut self, price: f64) -> Self {
         self.limit_price = Some(price);
@@ -789,6 +2557,15 @@ impl PlaceOrderRequestBuilder {
             _ => {}
         }
 
+        validate_expiration(
+            &self.time_in_force,
+            self.expire_date,
+            crate::market::today(),
+        )?;
+        if self.short_sale && action != OrderAction::Sell {
+            return Err("short sale orders must sell".to_string());
+        }
+
         Ok(PlaceOrderRequest {
             ticker_id,
             action,
@@ -800,6 +2577,121 @@ impl PlaceOrderRequestBuilder {
             outside_regular_trading_hour: self.outside_regular_trading_hour,
             serial_id: self.serial_id,
             combo_type: self.combo_type,
+            expire_date: self.expire_date,
+            short_sale: self.short_sale,
+        })
+    }
+}
+
+/// Borrow availability for shorting a ticker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Shortability {
+    pub ticker_id: String,
+    pub shortable: bool,
+    /// Asset class reported by the broker (e.g. "STOCK", "ETF")
+    pub asset_type: Option<String>,
+    pub easy_to_borrow: Option<bool>,
+    /// Shares available to borrow, when reported
+    pub available_shares: Option<f64>,
+    /// Annualized borrow fee rate as a fraction (0.05 = 5%)
+    pub fee_rate: Option<f64>,
+}
+
+impl Shortability {
+    /// Parse a ticker permission response
+    ///
+    /// A response that doesn't say whether the ticker can be shorted is an
+    /// error rather than "not shortable", so a changed response shape shows
+    /// up instead of silently blocking or allowing shorts.
+    pub fn from_permission_response(ticker_id: &str, value: &Value) -> crate::error::Result<Self> {
+        let data = value.get("data").unwrap_or(value);
+        let flag = |keys: &[&str]| {
+            keys.iter().find_map(|key| match data.get(*key) {
+                Some(Value::Bool(b)) => Some(*b),
+                Some(Value::Number(n)) => n.as_i64().map(|n| n != 0),
+                Some(Value::String(s)) => match s.to_ascii_lowercase().as_str() {
+                    "true" | "1" | "y" | "yes" => Some(true),
+                    "false" | "0" | "n" | "no" => Some(false),
+                    _ => None,
+                },
+                _ => None,
+            })
+        };
+
+        let available_shares = value_f64(
+            data,
+            &["shortableShares", "borrowShares", "availableShares"],
+        );
+        let shortable = flag(&["shortable", "isShortable", "canShort", "shortSupport"])
+            .ok_or_else(|| {
+                crate::error::WebullError::ParseError(format!(
+                    "Permission response for {} has no shortable flag",
+                    ticker_id
+                ))
+            })?;
+        Ok(Self {
+            ticker_id: ticker_id.to_string(),
+            shortable: shortable && available_shares != Some(0.0),
+            asset_type: data
+                .get("assetType")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            easy_to_borrow: flag(&["easyToBorrow", "etb"]),
+            available_shares,
+            fee_rate: value_f64(data, &["borrowFeeRate", "shortFeeRate", "feeRate"]),
+        })
+    }
+}
+
+/// Acceptable limit price range for a ticker (LULD band or broker collar)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PriceBand {
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl PriceBand {
+    /// Check if a price falls within the band (inclusive)
+    pub fn contains(&self, price: f64) -> bool {
+        price >= self.lower && price <= self.upper
+    }
+
+    /// Return `PriceOutOfBand` if the price falls outside the band
+    pub fn check(&self, price: f64) -> crate::error::Result<()> {
+        if self.contains(price) {
+            Ok(())
+        } else {
+            Err(crate::error::WebullError::PriceOutOfBand {
+                price,
+                lower: self.lower,
+                upper: self.upper,
+            })
+        }
+    }
+
+    /// Extract the band from an order-check response
+    pub fn from_check_response(value: &Value) -> Option<Self> {
+        const KEYS: [(&str, &str); 4] = [
+            ("minPrice", "maxPrice"),
+            ("lowerLimit", "upperLimit"),
+            ("priceLow", "priceHigh"),
+            ("luldLower", "luldUpper"),
+        ];
+
+        let data = value.get("data").unwrap_or(value);
+        let mut candidates = vec![value, data];
+        if let Some(list) = data.get("checkResultList").and_then(|v| v.as_array()) {
+            candidates.extend(list.iter());
+        }
+
+        candidates.into_iter().find_map(|candidate| {
+            KEYS.iter().find_map(|(low, high)| {
+                Some(Self {
+                    lower: value_f64(candidate, &[low])?,
+                    upper: value_f64(candidate, &[high])?,
+                })
+            })
         })
     }
 }
@@ -817,17 +2709,165 @@ pub struct Bar {
     pub vwap: f64,
 }
 
-/// Request builder for fetching bars/candles
-#[derive(Debug, Clone)]
-pub struct BarsRequestBuilder {
-    ticker_id: Option<String>,
-    interval: Option<String>,
-    count: Option<i32>,
-    timestamp: Option<i64>,
+impl Bar {
+    /// Parse a chart response: an array whose first element holds `data`,
+    /// a list of comma-separated `timestamp,open,close,high,low,?,volume,vwap`
+    /// rows
+    pub fn from_chart_response(value: &Value) -> Vec<Self> {
+        let rows = match value
+            .as_array()
+            .and_then(|items| items.first())
+            .and_then(|first| first.get("data"))
+            .and_then(|data| data.as_array())
+        {
+            Some(rows) => rows,
+            None => return Vec::new(),
+        };
+
+        let mut bars = Vec::with_capacity(rows.len());
+        for row in rows.iter().filter_map(|row| row.as_str()) {
+            let parts: Vec<&str> = row.split(',').collect();
+            if parts.len() < 7 {
+                continue;
+            }
+            let vwap = if parts.len() > 7 && parts[7] != "null" {
+                parts[7].parse::<f64>().unwrap_or(0.0)
+            } else {
+                0.0
+            };
+            bars.push(Bar {
+                timestamp: parts[0].parse::<i64>().unwrap_or(0),
+                open: parts[1].parse::<f64>().unwrap_or(0.0),
+                close: parts[2].parse::<f64>().unwrap_or(0.0),
+                high: parts[3].parse::<f64>().unwrap_or(0.0),
+                low: parts[4].parse::<f64>().unwrap_or(0.0),
+                volume: parts[6].parse::<i64>().unwrap_or(0) as f64,
+                vwap,
+            });
+        }
+        bars
+    }
 }
 
-impl BarsRequestBuilder {
-    /// Create a new bars request builder
+/// Bar/candle interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BarInterval {
+    M1,
+    M3,
+    M5,
+    M15,
+    M30,
+    H1,
+    H2,
+    H4,
+    D1,
+    /// Five trading days per bar
+    D5,
+    W1,
+    Month1,
+}
+
+impl BarInterval {
+    /// Interval code used by the chart API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BarInterval::M1 => "m1",
+            BarInterval::M3 => "m3",
+            BarInterval::M5 => "m5",
+            BarInterval::M15 => "m15",
+            BarInterval::M30 => "m30",
+            BarInterval::H1 => "h1",
+            BarInterval::H2 => "h2",
+            BarInterval::H4 => "h4",
+            BarInterval::D1 => "d1",
+            BarInterval::D5 => "d5",
+            BarInterval::W1 => "w1",
+            BarInterval::Month1 => "mo1",
+        }
+    }
+}
+
+impl std::fmt::Display for BarInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for BarInterval {
+    type Err = crate::error::WebullError;
+
+    /// Accepts both API codes ("m5", "d1") and short forms ("5m", "1d"),
+    /// including hours written in minutes ("m120", "240m")
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "m1" | "1m" => Ok(BarInterval::M1),
+            "m3" | "3m" => Ok(BarInterval::M3),
+            "m5" | "5m" => Ok(BarInterval::M5),
+            "m15" | "15m" => Ok(BarInterval::M15),
+            "m30" | "30m" => Ok(BarInterval::M30),
+            "h1" | "1h" | "m60" | "60m" => Ok(BarInterval::H1),
+            "h2" | "2h" | "m120" | "120m" => Ok(BarInterval::H2),
+            "h4" | "4h" | "m240" | "240m" => Ok(BarInterval::H4),
+            "d1" | "1d" => Ok(BarInterval::D1),
+            "d5" => Ok(BarInterval::D5),
+            "w1" | "1w" => Ok(BarInterval::W1),
+            "mo1" | "1M" => Ok(BarInterval::Month1),
+            _ => Err(crate::error::WebullError::InvalidParameter(format!(
+                "Invalid interval: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Average traded volume for one intraday time bucket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeBucket {
+    /// Bucket start time in exchange local time (e.g. "09:30")
+    #[serde(alias = "tradeTime")]
+    pub time: String,
+    #[serde(alias = "volume", deserialize_with = "deserialize_f64_from_string")]
+    pub avg_volume: f64,
+}
+
+/// Intraday volume distribution averaged over a number of days
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinuteVolumeProfile {
+    pub ticker_id: String,
+    pub days: i32,
+    pub buckets: Vec<VolumeBucket>,
+}
+
+impl MinuteVolumeProfile {
+    /// Total average daily volume across all buckets
+    pub fn total_volume(&self) -> f64 {
+        self.buckets.iter().map(|b| b.avg_volume).sum()
+    }
+
+    /// Fraction of daily volume traded in each bucket (sums to 1.0)
+    ///
+    /// Useful as a schedule for VWAP-style order slicing.
+    pub fn weights(&self) -> Vec<f64> {
+        let total = self.total_volume();
+        if total <= 0.0 {
+            return vec![0.0; self.buckets.len()];
+        }
+        self.buckets.iter().map(|b| b.avg_volume / total).collect()
+    }
+}
+
+/// Request builder for fetching bars/candles
+#[derive(Debug, Clone)]
+pub struct BarsRequestBuilder {
+    ticker_id: Option<String>,
+    interval: Option<BarInterval>,
+    count: Option<i32>,
+    timestamp: Option<i64>,
+}
+
+impl BarsRequestBuilder {
+    /// Create a new bars request builder
     pub fn new() -> Self {
         Self {
             ticker_id: None,
@@ -843,9 +2883,9 @@ impl BarsRequestBuilder {
         self
     }
 
-    /// Set the interval (e.g., "1m", "5m", "1d")
-    pub fn interval(mut self, interval: impl Into<String>) -> Self {
-        self.interval = Some(interval.into());
+    /// Set the interval
+    pub fn interval(mut self, interval: BarInterval) -> Self {
+        self.interval = Some(interval);
         self
     }
 
@@ -868,7 +2908,7 @@ impl BarsRequestBuilder {
     }
 
     /// Build the request parameters
-    pub fn build(self) -> Result<(String, String, i32, Option<i64>), String> {
+    pub fn build(self) -> Result<(String, BarInterval, i32, Option<i64>), String> {
         let ticker_id = self
             .ticker_id
             .ok_or_else(|| "ticker_id is required".to_string())?;
@@ -988,16 +3028,314 @@ pub struct OptionContract {
     pub strike_price: f64,
     pub expiration_date: String,
     pub option_type: String, // CALL or PUT
+    #[serde(default)]
+    pub bid: Option<f64>,
+    #[serde(default)]
+    pub ask: Option<f64>,
+    #[serde(default)]
+    pub last_price: Option<f64>,
+    #[serde(default)]
+    pub volume: Option<f64>,
+    #[serde(default)]
+    pub open_interest: Option<f64>,
+    #[serde(default)]
+    pub implied_volatility: Option<f64>,
+    #[serde(default)]
+    pub delta: Option<f64>,
+    #[serde(default)]
+    pub gamma: Option<f64>,
+    #[serde(default)]
+    pub theta: Option<f64>,
+    #[serde(default)]
+    pub vega: Option<f64>,
+}
+
+impl OptionContract {
+    /// Parse a contract from the option strategy list endpoint
+    ///
+    /// `expiration_date` is used when the contract itself doesn't carry one.
+    pub fn from_value(value: &Value, expiration_date: Option<&str>) -> Option<Self> {
+        let ticker_id = match value.get("tickerId")? {
+            Value::Number(n) => n.as_i64()?,
+            Value::String(s) => s.parse().ok()?,
+            _ => return None,
+        };
+
+        let top_of_book = |key: &str| {
+            value
+                .get(key)
+                .and_then(|v| v.as_array())
+                .and_then(|levels| levels.first())
+                .and_then(|level| value_f64(level, &["price"]))
+        };
+
+        Some(Self {
+            ticker_id,
+            symbol: value
+                .get("symbol")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            strike_price: value_f64(value, &["strikePrice"])?,
+            expiration_date: value
+                .get("expireDate")
+                .and_then(|v| v.as_str())
+                .or(expiration_date)
+                .unwrap_or_default()
+                .to_string(),
+            option_type: value
+                .get("direction")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_uppercase(),
+            bid: top_of_book("bidList"),
+            ask: top_of_book("askList"),
+            last_price: value_f64(value, &["close", "price"]),
+            volume: value_f64(value, &["volume"]),
+            open_interest: value_f64(value, &["openInterest"]),
+            implied_volatility: value_f64(value, &["impVol"]),
+            delta: value_f64(value, &["delta"]),
+            gamma: value_f64(value, &["gamma"]),
+            theta: value_f64(value, &["theta"]),
+            vega: value_f64(value, &["vega"]),
+        })
+    }
+
+    /// Get the bid/ask midpoint
+    pub fn mid(&self) -> Option<f64> {
+        match (self.bid, self.ask) {
+            (Some(bid), Some(ask)) => Some((bid + ask) / 2.0),
+            _ => None,
+        }
+    }
+
+    /// Parse every contract in an option strategy list response
+    ///
+    /// Handles both flat contracts and strike rows with `call`/`put` children.
+    pub fn from_response(value: &Value) -> Vec<Self> {
+        let mut contracts = Vec::new();
+        for group in value
+            .get("expireDateList")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+        {
+            let date = group
+                .get("from")
+                .and_then(|f| f.get("date"))
+                .and_then(|v| v.as_str());
+
+            for row in group
+                .get("data")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+            {
+                if row.get("call").is_some() || row.get("put").is_some() {
+                    for (key, direction) in [("call", "CALL"), ("put", "PUT")] {
+                        if let Some(mut contract) =
+                            row.get(key).and_then(|c| Self::from_value(c, date))
+                        {
+                            contract.option_type = direction.to_string();
+                            contracts.push(contract);
+                        }
+                    }
+                } else if let Some(contract) = Self::from_value(row, date) {
+                    contracts.push(contract);
+                }
+            }
+        }
+        contracts
+    }
+}
+
+/// Real-time quote for a single option contract
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionQuote {
+    /// Contract details with top-of-book, volume and greeks
+    pub contract: OptionContract,
+    pub bid_size: Option<f64>,
+    pub ask_size: Option<f64>,
+    pub change: Option<f64>,
+    pub change_ratio: Option<f64>,
+    pub rho: Option<f64>,
+    pub underlying_price: Option<f64>,
+}
+
+impl OptionQuote {
+    /// Parse an option quote from the option quotes endpoint
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let top_size = |key: &str| {
+            value
+                .get(key)
+                .and_then(|v| v.as_array())
+                .and_then(|levels| levels.first())
+                .and_then(|level| value_f64(level, &["volume"]))
+        };
+
+        Some(Self {
+            contract: OptionContract::from_value(value, None)?,
+            bid_size: top_size("bidList"),
+            ask_size: top_size("askList"),
+            change: value_f64(value, &["change"]),
+            change_ratio: value_f64(value, &["changeRatio"]),
+            rho: value_f64(value, &["rho"]),
+            underlying_price: value_f64(value, &["underlyingPrice", "unPrice"]),
+        })
+    }
+
+    /// Get the bid/ask midpoint
+    pub fn mid(&self) -> Option<f64> {
+        self.contract.mid()
+    }
+}
+
+/// Ranking used for market-wide top options
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopOptionsRank {
+    Volume,
+    OpenInterest,
+}
+
+impl TopOptionsRank {
+    /// Rank type value expected by the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TopOptionsRank::Volume => "volume",
+            TopOptionsRank::OpenInterest => "position",
+        }
+    }
+}
+
+/// Option contract from the market-wide top options ranking
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopOption {
+    pub underlying_ticker_id: Option<i64>,
+    pub underlying_symbol: Option<String>,
+    pub contract: OptionContract,
+}
+
+impl TopOption {
+    /// Parse the ranking list; entries nest the contract under `derivative`
+    pub fn from_response(value: &Value) -> Vec<Self> {
+        parse_list(value, |item| {
+            let contract =
+                OptionContract::from_value(item.get("derivative").unwrap_or(item), None)?;
+            let underlying = item.get("belongTicker").or_else(|| item.get("ticker"));
+            Some(Self {
+                underlying_ticker_id: underlying.and_then(|u| u.get("tickerId")).and_then(|v| {
+                    match v {
+                        Value::Number(n) => n.as_i64(),
+                        Value::String(s) => s.parse().ok(),
+                        _ => None,
+                    }
+                }),
+                underlying_symbol: underlying
+                    .and_then(|u| u.get("disSymbol").or_else(|| u.get("symbol")))
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+                contract,
+            })
+        })
+    }
+}
+
+/// Option expiration date available for an underlying
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionExpiration {
+    pub date: String,
+    /// Calendar days until expiration
+    pub days: Option<i32>,
+    pub weekly: bool,
+}
+
+impl OptionExpiration {
+    /// Parse the expiration list from an option strategy list response,
+    /// soonest first and without repeats
+    pub fn from_response(value: &Value) -> Vec<Self> {
+        let mut expirations: Vec<Self> = value
+            .get("expireDateList")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|group| {
+                let from = group.get("from")?;
+                Some(Self {
+                    date: from.get("date")?.as_str()?.to_string(),
+                    days: from.get("days").and_then(|v| v.as_i64()).map(|d| d as i32),
+                    weekly: matches!(from.get("weekly"), Some(Value::Bool(true)))
+                        || from.get("weekly").and_then(|v| v.as_i64()) == Some(1),
+                })
+            })
+            .collect();
+        // ISO dates sort chronologically as strings
+        expirations.sort_by(|a, b| a.date.cmp(&b.date));
+        expirations.dedup_by(|a, b| a.date == b.date);
+        expirations
+    }
+}
+
+/// Options chain query parameters produced by `OptionsRequestBuilder`
+#[derive(Debug, Clone, Default)]
+pub struct OptionsQuery {
+    /// Ticker symbol or ticker ID of the underlying
+    pub ticker: String,
+    /// Expiration date (format: YYYY-MM-DD); all expirations when `None`
+    pub expiration_date: Option<String>,
+    /// CALL or PUT; both when `None`
+    pub option_type: Option<String>,
+    pub min_strike: Option<f64>,
+    pub max_strike: Option<f64>,
+}
+
+impl OptionsQuery {
+    /// Query the full chain for a ticker
+    pub fn new(ticker: impl Into<String>) -> Self {
+        Self {
+            ticker: ticker.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Check if a contract passes the expiration, direction and strike filters
+    pub fn matches(&self, contract: &OptionContract) -> bool {
+        if let Some(date) = &self.expiration_date {
+            if &contract.expiration_date != date {
+                return false;
+            }
+        }
+        if let Some(option_type) = &self.option_type {
+            if !contract.option_type.eq_ignore_ascii_case(option_type) {
+                return false;
+            }
+        }
+        self.min_strike
+            .is_none_or(|min| contract.strike_price >= min)
+            && self
+                .max_strike
+                .is_none_or(|max| contract.strike_price <= max)
+    }
+
+    /// Direction value expected by the API
+    pub(crate) fn direction(&self) -> String {
+        self.option_type
+            .as_deref()
+            .map(str::to_lowercase)
+            .unwrap_or_else(|| "all".to_string())
+    }
 }
 
 /// Builder for requesting options data
 #[derive(Debug, Clone)]
 pub struct OptionsRequestBuilder {
-    ticker: Option<String>,
-    expiration_date: Option<String>,
-    option_type: Option<String>,
-    min_strike: Option<f64>,
-    max_strike: Option<f64>,
+    pub(crate) ticker: Option<String>,
+    pub(crate) expiration_date: Option<String>,
+    pub(crate) option_type: Option<String>,
+    pub(crate) min_strike: Option<f64>,
+    pub(crate) max_strike: Option<f64>,
 }
 
 impl OptionsRequestBuilder {
@@ -1063,15 +3401,25 @@ impl OptionsRequestBuilder {
         self
     }
 
-    /// Build the options request parameters
-    pub fn build(self) -> Result<String, String> {
+    /// Build the options query
+    pub fn build(self) -> Result<OptionsQuery, String> {
         let ticker = self
             .ticker
             .ok_or_else(|| "ticker is required".to_string())?;
 
-        // In a real implementation, this would build appropriate query parameters
-        // For now, we just return the ticker as the main required parameter
-        Ok(ticker)
+        if let (Some(min), Some(max)) = (self.min_strike, self.max_strike) {
+            if min > max {
+                return Err(format!("min_strike {} is above max_strike {}", min, max));
+            }
+        }
+
+        Ok(OptionsQuery {
+            ticker,
+            expiration_date: self.expiration_date,
+            option_type: self.option_type,
+            min_strike: self.min_strike,
+            max_strike: self.max_strike,
+        })
     }
 }
 
@@ -1083,97 +3431,657 @@ impl Default for OptionsRequestBuilder {
 
 // ============= Fundamentals Models =============
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Fundamental {
     pub ticker_id: Option<i64>,
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+    pub sector: Option<String>,
+    pub industry: Option<String>,
     pub market_cap: Option<f64>,
     pub pe_ratio: Option<f64>,
+    pub forward_pe: Option<f64>,
     pub eps: Option<f64>,
+    pub beta: Option<f64>,
+    pub fifty_two_week_high: Option<f64>,
+    pub fifty_two_week_low: Option<f64>,
+    pub shares_outstanding: Option<f64>,
+    /// Shares available for public trading
+    pub float_shares: Option<f64>,
+    /// Annual dividend per share
+    pub dividend: Option<f64>,
+    /// Dividend yield as a fraction (0.005 = 0.5%)
     pub dividend_yield: Option<f64>,
+    /// `YYYY-MM-DD`
+    pub ex_dividend_date: Option<String>,
+    /// `YYYY-MM-DD`
+    pub dividend_pay_date: Option<String>,
+    /// `YYYY-MM-DD`
+    pub next_earnings_date: Option<String>,
+}
+
+impl Fundamental {
+    /// Parse a fundamentals response
+    ///
+    /// Fields are looked up on the top-level object and on the `data`,
+    /// `ticker`, `tickerInfo`, `quote` and `statistics` objects it wraps,
+    /// under every name Webull uses for them. Anything not found is `None`.
+    pub fn from_value(value: &Value) -> Self {
+        let root = value.get("data").filter(|d| d.is_object()).unwrap_or(value);
+        let sources: Vec<&Value> = std::iter::once(root)
+            .chain(
+                ["ticker", "tickerInfo", "quote", "statistics", "dividend"]
+                    .iter()
+                    .filter_map(|key| root.get(*key))
+                    .filter(|v| v.is_object()),
+            )
+            .collect();
+        let number = |keys: &[&str]| sources.iter().find_map(|v| value_f64(v, keys));
+        let text = |keys: &[&str]| {
+            sources.iter().find_map(|v| {
+                keys.iter().find_map(|key| match v.get(*key) {
+                    Some(Value::String(s)) if !s.is_empty() => Some(s.clone()),
+                    Some(Value::Object(o)) => {
+                        o.get("name").and_then(|n| n.as_str()).map(String::from)
+                    }
+                    _ => None,
+                })
+            })
+        };
+        let date = |keys: &[&str]| text(keys).map(|d| d.chars().take(10).collect());
+
+        Self {
+            ticker_id: number(&["tickerId"]).map(|id| id as i64),
+            symbol: text(&["disSymbol", "symbol"]),
+            name: text(&["name", "tickerName"]),
+            sector: text(&["sector", "sectorName"]),
+            industry: text(&["industry", "industryName"]),
+            market_cap: number(&["marketCap", "marketValue", "totalMarketValue"]),
+            pe_ratio: number(&["peRatio", "peTtm", "pe"]),
+            forward_pe: number(&["forwardPe"]),
+            eps: number(&["eps", "epsTtm"]),
+            beta: number(&["beta", "beta3Y"]),
+            fifty_two_week_high: number(&["fiftyTwoWkHigh", "high52w", "week52High"]),
+            fifty_two_week_low: number(&["fiftyTwoWkLow", "low52w", "week52Low"]),
+            shares_outstanding: number(&["outstandingShares", "totalShares", "sharesOutstanding"]),
+            float_shares: number(&["floatShares", "float", "freeFloat"]),
+            dividend: number(&["dividend", "dividendPerShare", "annualDividend"]),
+            dividend_yield: number(&["dividendYield", "yield"]),
+            ex_dividend_date: date(&["exDividendDate", "exDate"]),
+            dividend_pay_date: date(&["dividendPayDate", "payDate"]),
+            next_earnings_date: date(&["nextEarningDay", "nextEarningsDate", "latestEarningsDate"]),
+        }
+    }
+
+    /// Share of the 52-week range the price sits at (0 = low, 1 = high)
+    pub fn range_position(&self, price: f64) -> Option<f64> {
+        let (low, high) = (self.fifty_two_week_low?, self.fifty_two_week_high?);
+        (high > low).then(|| (price - low) / (high - low))
+    }
+}
+
+// ============= Financial Statement Models =============
+
+/// Which financial statement to fetch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatementType {
+    Income,
+    BalanceSheet,
+    CashFlow,
+}
+
+impl StatementType {
+    /// Path segment of the statement endpoint
+    pub fn as_path(&self) -> &'static str {
+        match self {
+            StatementType::Income => "incomestatement",
+            StatementType::BalanceSheet => "balancesheet",
+            StatementType::CashFlow => "cashflow",
+        }
+    }
+}
+
+/// Reporting frequency of a statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReportPeriod {
+    Quarterly,
+    Annual,
+}
+
+impl ReportPeriod {
+    /// `type` and `fiscalPeriod` query values for the statement endpoint
+    pub fn query_values(&self) -> (i32, &'static str) {
+        match self {
+            ReportPeriod::Quarterly => (101, "1,2,3,4"),
+            ReportPeriod::Annual => (102, "0"),
+        }
+    }
+}
+
+/// One reporting period of a financial statement
+///
+/// Line items are keyed by their name lowercased with spaces and
+/// punctuation removed, so "Total Revenue" and `totalRevenue` are both
+/// `totalrevenue`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementPeriod {
+    pub fiscal_year: Option<i32>,
+    /// Fiscal quarter (1-4), or `None`/0 for annual reports
+    pub fiscal_period: Option<i32>,
+    /// Period end date, `YYYY-MM-DD`
+    pub end_date: Option<String>,
+    pub items: BTreeMap<String, f64>,
+}
+
+impl StatementPeriod {
+    /// Parse one period, with line items in an `items` array of
+    /// `{title, value}` or as flat fields
+    pub fn from_value(value: &Value) -> Option<Self> {
+        const METADATA: &[&str] = &[
+            "fiscalyear",
+            "fiscalperiod",
+            "year",
+            "quarter",
+            "reportenddate",
+            "enddate",
+            "periodenddate",
+            "publishdate",
+            "currency",
+            "type",
+            "tickerid",
+        ];
+        let object = value.as_object()?;
+        let mut items = BTreeMap::new();
+        let rows = ["items", "list", "rows"]
+            .iter()
+            .find_map(|key| value.get(*key).and_then(|v| v.as_array()));
+        match rows {
+            Some(rows) => {
+                for row in rows {
+                    let name = ["title", "name", "key"]
+                        .iter()
+                        .find_map(|key| row.get(*key).and_then(|v| v.as_str()));
+                    if let (Some(name), Some(amount)) = (name, value_f64(row, &["value"])) {
+                        items.insert(statement_key(name), amount);
+                    }
+                }
+            }
+            None => {
+                for (key, field) in object {
+                    let key = statement_key(key);
+                    if METADATA.contains(&key.as_str()) {
+                        continue;
+                    }
+                    let amount = match field {
+                        Value::Number(n) => n.as_f64(),
+                        Value::String(s) => s.parse().ok(),
+                        _ => None,
+                    };
+                    if let Some(amount) = amount {
+                        items.insert(key, amount);
+                    }
+                }
+            }
+        }
+
+        let int = |keys: &[&str]| value_f64(value, keys).map(|v| v as i32);
+        let end_date = ["reportEndDate", "endDate", "periodEndDate"]
+            .iter()
+            .find_map(|key| value.get(*key).and_then(|v| v.as_str()))
+            .map(|d| d.chars().take(10).collect());
+        Some(Self {
+            fiscal_year: int(&["fiscalYear", "year"]),
+            fiscal_period: int(&["fiscalPeriod", "quarter"]),
+            end_date,
+            items,
+        })
+    }
+
+    /// A line item by name, in any capitalisation or spacing
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.items.get(&statement_key(name)).copied()
+    }
+
+    fn first(&self, names: &[&str]) -> Option<f64> {
+        names.iter().find_map(|name| self.get(name))
+    }
+
+    pub fn revenue(&self) -> Option<f64> {
+        self.first(&["totalRevenue", "revenue", "operatingRevenue"])
+    }
+
+    pub fn net_income(&self) -> Option<f64> {
+        self.first(&["netIncome", "netIncomeCommonStockholders", "netIncomeLoss"])
+    }
+
+    pub fn diluted_eps(&self) -> Option<f64> {
+        self.first(&["dilutedEps", "eps", "basicEps"])
+    }
+
+    pub fn total_assets(&self) -> Option<f64> {
+        self.get("totalAssets")
+    }
+
+    pub fn total_liabilities(&self) -> Option<f64> {
+        self.first(&["totalLiabilities", "totalLiab"])
+    }
+
+    pub fn total_equity(&self) -> Option<f64> {
+        self.first(&[
+            "totalEquity",
+            "totalStockholdersEquity",
+            "shareholdersEquity",
+        ])
+    }
+
+    pub fn operating_cash_flow(&self) -> Option<f64> {
+        self.first(&[
+            "operatingCashFlow",
+            "cashFromOperatingActivities",
+            "netOperatingCashFlow",
+        ])
+    }
+
+    pub fn capital_expenditure(&self) -> Option<f64> {
+        self.first(&["capitalExpenditure", "capex"])
+    }
+
+    /// Operating cash flow less capital expenditure, unless reported directly
+    pub fn free_cash_flow(&self) -> Option<f64> {
+        self.get("freeCashFlow").or_else(|| {
+            // Capex is usually reported as a negative outflow
+            Some(self.operating_cash_flow()? - self.capital_expenditure()?.abs())
+        })
+    }
+}
+
+fn statement_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// A financial statement across reporting periods, most recent first
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinancialStatement {
+    pub statement_type: StatementType,
+    pub period: ReportPeriod,
+    pub periods: Vec<StatementPeriod>,
+}
+
+impl FinancialStatement {
+    /// Parse a statement response
+    pub fn from_response(
+        statement_type: StatementType,
+        period: ReportPeriod,
+        value: &Value,
+    ) -> Self {
+        let mut periods = parse_list(value, StatementPeriod::from_value);
+        periods.sort_by(|a, b| {
+            (b.fiscal_year, b.fiscal_period, &b.end_date).cmp(&(
+                a.fiscal_year,
+                a.fiscal_period,
+                &a.end_date,
+            ))
+        });
+        Self {
+            statement_type,
+            period,
+            periods,
+        }
+    }
+
+    /// Most recent period
+    pub fn latest(&self) -> Option<&StatementPeriod> {
+        self.periods.first()
+    }
+}
+
+// ============= Earnings Models =============
+
+/// Company guidance issued alongside an earnings release
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EarningsGuidance {
+    /// Period the guidance covers (e.g. "Q3 2024", "FY2024")
+    pub period: Option<String>,
+    pub eps_low: Option<f64>,
+    pub eps_high: Option<f64>,
+    pub revenue_low: Option<f64>,
+    pub revenue_high: Option<f64>,
+}
+
+/// Price move following a past earnings release
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceReaction {
+    pub release_date: String,
+    /// Percent change from the close before the release to the close after
+    pub change_pct: f64,
+}
+
+/// A scheduled or reported earnings release
+///
+/// Whisper, guidance and reaction fields are only filled in where Webull
+/// exposes them for the ticker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EarningsEvent {
+    pub ticker_id: Option<String>,
+    pub symbol: Option<String>,
+    /// Release date, `YYYY-MM-DD`
+    pub release_date: Option<String>,
+    pub fiscal_period: Option<String>,
+    pub eps_estimate: Option<f64>,
+    pub eps_actual: Option<f64>,
+    pub revenue_estimate: Option<f64>,
+    pub revenue_actual: Option<f64>,
+    /// Unofficial consensus EPS
+    pub whisper_eps: Option<f64>,
+    pub guidance: Option<EarningsGuidance>,
+    /// Price reaction after this release, once reported
+    pub price_reaction: Option<f64>,
+    /// Reactions to earlier releases, most recent first
+    #[serde(default)]
+    pub reaction_history: Vec<PriceReaction>,
+}
+
+impl EarningsEvent {
+    /// Parse one earnings entry, flat or wrapped in `ticker`/`values`
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let ticker = value.get("ticker").unwrap_or(value);
+        let values = value.get("values").unwrap_or(value);
+        let text = |v: &Value, keys: &[&str]| {
+            keys.iter().find_map(|key| match v.get(*key) {
+                Some(Value::String(s)) if !s.is_empty() => Some(s.clone()),
+                Some(Value::Number(n)) => Some(n.to_string()),
+                _ => None,
+            })
+        };
+
+        let release_date = text(values, &["releaseDate", "reportDate", "publishDate"])
+            .map(|d| d.chars().take(10).collect());
+        let guidance = value
+            .get("guidance")
+            .map(|g| EarningsGuidance {
+                period: text(g, &["period", "fiscalPeriod"]),
+                eps_low: value_f64(g, &["epsLow", "epsMin"]),
+                eps_high: value_f64(g, &["epsHigh", "epsMax"]),
+                revenue_low: value_f64(g, &["revenueLow", "revenueMin"]),
+                revenue_high: value_f64(g, &["revenueHigh", "revenueMax"]),
+            })
+            .filter(|g| {
+                g.eps_low.is_some()
+                    || g.eps_high.is_some()
+                    || g.revenue_low.is_some()
+                    || g.revenue_high.is_some()
+            });
+
+        let event = Self {
+            ticker_id: text(ticker, &["tickerId"]),
+            symbol: text(ticker, &["disSymbol", "symbol"]),
+            release_date,
+            fiscal_period: text(values, &["fiscalPeriod", "quarter", "period"]),
+            eps_estimate: value_f64(values, &["epsEstimate", "epsForecast", "estimateEps"]),
+            eps_actual: value_f64(values, &["epsActual", "eps", "actualEps"]),
+            revenue_estimate: value_f64(values, &["revenueEstimate", "revenueForecast"]),
+            revenue_actual: value_f64(values, &["revenueActual", "revenue"]),
+            whisper_eps: value_f64(values, &["whisperEps", "epsWhisper", "whisper"]),
+            guidance,
+            price_reaction: value_f64(values, &["priceReaction", "postEarningsChangeRatio"]),
+            reaction_history: Vec::new(),
+        };
+        (event.release_date.is_some() || event.ticker_id.is_some()).then_some(event)
+    }
+
+    /// Parse every entry in an earnings response
+    pub fn from_list_response(value: &Value) -> Vec<Self> {
+        parse_list(value, Self::from_value)
+    }
+
+    /// Whether the release has happened and reported EPS
+    pub fn is_reported(&self) -> bool {
+        self.eps_actual.is_some()
+    }
+
+    /// Release date as a calendar date
+    pub fn release_day(&self) -> Option<chrono::NaiveDate> {
+        chrono::NaiveDate::parse_from_str(self.release_date.as_deref()?, "%Y-%m-%d").ok()
+    }
 }
 
 // ============= Screener Models =============
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Field a screener rule filters or sorts on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ScreenerField {
+    Price,
+    MarketCap,
+    Volume,
+    /// Day change as a fraction (0.05 = up 5%)
+    ChangeRatio,
+    PeRatio,
+    Sector,
+}
+
+impl ScreenerField {
+    /// Rule key in Webull's screener payload
+    pub fn rule(&self) -> &'static str {
+        match self {
+            ScreenerField::Price => "wlas.screener.rule.price",
+            ScreenerField::MarketCap => "wlas.screener.rule.marketvalue",
+            ScreenerField::Volume => "wlas.screener.rule.volume",
+            ScreenerField::ChangeRatio => "wlas.screener.rule.changeRatio",
+            ScreenerField::PeRatio => "wlas.screener.rule.peTtm",
+            ScreenerField::Sector => "wlas.screener.rule.sector",
+        }
+    }
+}
+
+/// Sort order for screener results
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScreenerSort {
+    pub rule: String,
+    pub desc: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct ScreenerAttach {
+    pub hkex_privilege: bool,
+}
+
+/// Body of a screener query, in the shape the screener endpoint expects
+///
+/// Range rules are encoded as `gte=<min>&lte=<max>` with either bound
+/// optional; use `ScreenerRequestBuilder` to build them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ScreenerRequest {
-    pub region_id: i32,
-    pub plate_id: i32,
-    pub rank_id: i32,
+    /// Maximum number of results
+    pub fetch: u32,
+    pub rules: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<ScreenerSort>,
+    pub attach: ScreenerAttach,
+}
+
+impl ScreenerRequest {
+    /// Tickers from a screener response (`data.items[].ticker`, or a bare
+    /// ticker list)
+    pub fn parse_response(value: &Value) -> Vec<Ticker> {
+        let data = value.get("data").unwrap_or(value);
+        data.get("items")
+            .and_then(|v| v.as_array())
+            .or_else(|| data.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|item| {
+                let ticker = item.get("ticker").unwrap_or(item);
+                serde_json::from_value(ticker.clone()).ok()
+            })
+            .collect()
+    }
 }
 
 /// Builder for ScreenerRequest
+///
+/// ```
+/// use webull_unofficial::models::ScreenerField;
+/// use webull_unofficial::ScreenerRequestBuilder;
+///
+/// let request = ScreenerRequestBuilder::new()
+///     .price(Some(5.0), Some(50.0))
+///     .market_cap(Some(2e9), None)
+///     .change_percent(Some(3.0), None)
+///     .sort_by(ScreenerField::Volume, true)
+///     .build()
+///     .unwrap();
+/// assert_eq!(request.rules["wlas.screener.rule.price"], "gte=5&lte=50");
+/// ```
 #[derive(Debug, Clone)]
 pub struct ScreenerRequestBuilder {
-    region_id: Option<i32>,
-    plate_id: Option<i32>,
-    rank_id: Option<i32>,
+    region_id: i32,
+    fetch: u32,
+    // Keyed by rule so the payload has a stable order
+    ranges: BTreeMap<&'static str, (Option<f64>, Option<f64>)>,
+    sectors: Vec<String>,
+    sort: Option<(ScreenerField, bool)>,
 }
 
 impl ScreenerRequestBuilder {
     /// Create a new screener request builder
     pub fn new() -> Self {
         Self {
-            region_id: Some(6), // Default to US region
-            plate_id: None,
-            rank_id: None,
+            region_id: 6, // Default to US region
+            fetch: 200,
+            ranges: BTreeMap::new(),
+            sectors: Vec::new(),
+            sort: None,
         }
     }
 
     /// Set the region ID (default is 6 for US)
     pub fn region(mut self, region_id: i32) -> Self {
-        self.region_id = Some(region_id);
+        self.region_id = region_id;
         self
     }
 
-    /// Set the plate/category ID
-    pub fn plate(mut self, plate_id: i32) -> Self {
-        self.plate_id = Some(plate_id);
+    /// Set the maximum number of results (default 200)
+    pub fn limit(mut self, fetch: u32) -> Self {
+        self.fetch = fetch;
         self
     }
 
-    /// Set the rank/sort ID
-    pub fn rank(mut self, rank_id: i32) -> Self {
-        self.rank_id = Some(rank_id);
+    /// Filter a numeric field to a range; either bound may be open
+    pub fn filter(mut self, field: ScreenerField, min: Option<f64>, max: Option<f64>) -> Self {
+        self.ranges.insert(field.rule(), (min, max));
         self
     }
 
-    /// Use preset for top gainers
-    pub fn top_gainers(mut self) -> Self {
-        self.plate_id = Some(1); // Typical ID for gainers
-        self.rank_id = Some(1); // Sort by gain percentage
+    /// Filter by last price
+    pub fn price(self, min: Option<f64>, max: Option<f64>) -> Self {
+        self.filter(ScreenerField::Price, min, max)
+    }
+
+    /// Filter by market capitalisation in dollars
+    pub fn market_cap(self, min: Option<f64>, max: Option<f64>) -> Self {
+        self.filter(ScreenerField::MarketCap, min, max)
+    }
+
+    /// Filter by day volume in shares
+    pub fn volume(self, min: Option<f64>, max: Option<f64>) -> Self {
+        self.filter(ScreenerField::Volume, min, max)
+    }
+
+    /// Filter by day change in percent (3.0 = up 3%)
+    pub fn change_percent(self, min: Option<f64>, max: Option<f64>) -> Self {
+        self.filter(
+            ScreenerField::ChangeRatio,
+            min.map(|p| p / 100.0),
+            max.map(|p| p / 100.0),
+        )
+    }
+
+    /// Filter by trailing P/E ratio
+    pub fn pe_ratio(self, min: Option<f64>, max: Option<f64>) -> Self {
+        self.filter(ScreenerField::PeRatio, min, max)
+    }
+
+    /// Only include tickers in these sectors
+    pub fn sectors<S: AsRef<str>>(mut self, sectors: &[S]) -> Self {
+        self.sectors = sectors.iter().map(|s| s.as_ref().to_string()).collect();
         self
     }
 
-    /// Use preset for top losers
-    pub fn top_losers(mut self) -> Self {
-        self.plate_id = Some(2); // Typical ID for losers
-        self.rank_id = Some(2); // Sort by loss percentage
+    /// Sort results by a field
+    pub fn sort_by(mut self, field: ScreenerField, descending: bool) -> Self {
+        self.sort = Some((field, descending));
         self
     }
 
+    /// Use preset for top gainers
+    pub fn top_gainers(self) -> Self {
+        self.change_percent(Some(0.0), None)
+            .sort_by(ScreenerField::ChangeRatio, true)
+    }
+
+    /// Use preset for top losers
+    pub fn top_losers(self) -> Self {
+        self.change_percent(None, Some(0.0))
+            .sort_by(ScreenerField::ChangeRatio, false)
+    }
+
     /// Use preset for most active (by volume)
-    pub fn most_active(mut self) -> Self {
-        self.plate_id = Some(3); // Typical ID for active stocks
-        self.rank_id = Some(3); // Sort by volume
-        self
+    pub fn most_active(self) -> Self {
+        self.sort_by(ScreenerField::Volume, true)
     }
 
     /// Build the screener request
     pub fn build(self) -> Result<ScreenerRequest, String> {
-        let region_id = self.region_id.unwrap_or(6);
-        let plate_id = self
-            .plate_id
-            .ok_or_else(|| "plate_id is required".to_string())?;
-        let rank_id = self
-            .rank_id
-            .ok_or_else(|| "rank_id is required".to_string())?;
+        if self.fetch == 0 {
+            return Err("limit must be at least 1".to_string());
+        }
+
+        let mut rules = BTreeMap::new();
+        rules.insert(
+            "wlas.screener.rule.region".to_string(),
+            format!("securities.region.name.{}", self.region_id),
+        );
+        for (rule, (min, max)) in self.ranges {
+            if let (Some(min), Some(max)) = (min, max) {
+                if min > max {
+                    return Err(format!("{} minimum {} is above maximum {}", rule, min, max));
+                }
+            }
+            let bounds: Vec<String> = [("gte", min), ("lte", max)]
+                .iter()
+                .filter_map(|(op, bound)| bound.map(|b| format!("{}={}", op, b)))
+                .collect();
+            if !bounds.is_empty() {
+                rules.insert(rule.to_string(), bounds.join("&"));
+            }
+        }
+        if !self.sectors.is_empty() {
+            rules.insert(
+                ScreenerField::Sector.rule().to_string(),
+                self.sectors.join(","),
+            );
+        }
 
         Ok(ScreenerRequest {
-            region_id,
-            plate_id,
-            rank_id,
+            fetch: self.fetch,
+            rules,
+            sort: self.sort.map(|(field, desc)| ScreenerSort {
+                rule: field.rule().to_string(),
+                desc,
+            }),
+            attach: ScreenerAttach {
+                hkex_privilege: false,
+            },
         })
     }
 }
@@ -1184,6 +4092,305 @@ impl Default for ScreenerRequestBuilder {
     }
 }
 
+// ============= Watchlist Models =============
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Watchlist {
+    #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(alias = "tickerList", default)]
+    pub tickers: Vec<WatchlistItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchlistItem {
+    pub ticker_id: i64,
+    #[serde(rename = "disSymbol", alias = "symbol")]
+    pub symbol: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub exchange_code: Option<String>,
+    #[serde(default)]
+    pub template: Option<String>,
+}
+
+// ============= Alert Models =============
+
+/// A single alert condition in Webull's rule format
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertCondition {
+    pub field: String,
+    #[serde(rename = "type")]
+    pub rule_type: String,
+    #[serde(deserialize_with = "deserialize_string_from_any")]
+    pub value: String,
+    #[serde(default = "default_alert_active")]
+    pub active: String,
+    #[serde(default = "default_alert_remark")]
+    pub remark: String,
+}
+
+fn default_alert_active() -> String {
+    "on".to_string()
+}
+
+fn default_alert_remark() -> String {
+    "none".to_string()
+}
+
+impl AlertCondition {
+    fn new(field: &str, rule_type: &str, value: f64) -> Self {
+        Self {
+            field: field.to_string(),
+            rule_type: rule_type.to_string(),
+            value: value.to_string(),
+            active: default_alert_active(),
+            remark: default_alert_remark(),
+        }
+    }
+}
+
+/// Price alert rules for a ticker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertRule {
+    pub rules: Vec<AlertCondition>,
+    /// Trigger frequency (1 = once, 2 = once a day, 3 = every time)
+    pub frequency: i32,
+    /// Minimum interval between triggers
+    pub interval: i32,
+}
+
+/// Builder for AlertRule
+#[derive(Debug, Clone)]
+pub struct AlertRuleBuilder {
+    rules: Vec<AlertCondition>,
+    frequency: i32,
+    interval: i32,
+}
+
+impl AlertRuleBuilder {
+    /// Create a new alert rule builder
+    pub fn new() -> Self {
+        Self {
+            rules: Vec::new(),
+            frequency: 1, // Default to trigger once
+            interval: 1,
+        }
+    }
+
+    /// Trigger when the price rises above a value
+    pub fn price_above(mut self, price: f64) -> Self {
+        self.rules
+            .push(AlertCondition::new("price", "above", price));
+        self
+    }
+
+    /// Trigger when the price falls below a value
+    pub fn price_below(mut self, price: f64) -> Self {
+        self.rules
+            .push(AlertCondition::new("price", "below", price));
+        self
+    }
+
+    /// Trigger when the day's change rises above a percentage
+    pub fn percent_change_up(mut self, percent: f64) -> Self {
+        self.rules
+            .push(AlertCondition::new("changeRatio", "above", percent));
+        self
+    }
+
+    /// Trigger when the day's change falls below a negative percentage
+    pub fn percent_change_down(mut self, percent: f64) -> Self {
+        self.rules
+            .push(AlertCondition::new("changeRatio", "below", -percent.abs()));
+        self
+    }
+
+    /// Trigger when the day's volume rises above a value
+    pub fn volume_above(mut self, volume: f64) -> Self {
+        self.rules
+            .push(AlertCondition::new("volume", "above", volume));
+        self
+    }
+
+    /// Set the trigger frequency (1 = once, 2 = once a day, 3 = every time)
+    pub fn frequency(mut self, frequency: i32) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    /// Set the minimum interval between triggers
+    pub fn interval(mut self, interval: i32) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Build the alert rule
+    pub fn build(self) -> Result<AlertRule, String> {
+        if self.rules.is_empty() {
+            return Err("at least one alert condition is required".to_string());
+        }
+        if !(1..=3).contains(&self.frequency) {
+            return Err(format!("invalid alert frequency: {}", self.frequency));
+        }
+
+        Ok(AlertRule {
+            rules: self.rules,
+            frequency: self.frequency,
+            interval: self.interval,
+        })
+    }
+}
+
+impl Default for AlertRuleBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Alert {
+    pub ticker_id: i64,
+    #[serde(default)]
+    pub ticker_symbol: Option<String>,
+    #[serde(default)]
+    pub ticker_name: Option<String>,
+    #[serde(default)]
+    pub ticker_warning: Option<TickerWarning>,
+    #[serde(default)]
+    pub event_warning: Option<serde_json::Value>,
+    #[serde(default)]
+    pub warning_frequency: Option<i32>,
+    #[serde(default)]
+    pub warning_interval: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TickerWarning {
+    #[serde(default)]
+    pub rules: Vec<AlertCondition>,
+}
+
+// ============= Security Event Models =============
+
+/// Kind of account security event
+///
+/// The feed's event types are undocumented; the named kinds are recognized
+/// from keywords in the event's `type` (or its title when there is none).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SecurityEventKind {
+    /// Login from a device not seen before
+    NewDeviceLogin,
+    Login,
+    PasswordChange,
+    TradePinChange,
+    /// An event type not recognized, with the raw value as sent
+    Unknown(String),
+}
+
+impl SecurityEventKind {
+    fn from_code(code: &str) -> Self {
+        let normalized = code.to_ascii_lowercase().replace(['-', ' '], "_");
+        if normalized.contains("device") {
+            SecurityEventKind::NewDeviceLogin
+        } else if normalized.contains("trade_pwd")
+            || normalized.contains("trade_password")
+            || normalized.contains("pin")
+        {
+            SecurityEventKind::TradePinChange
+        } else if normalized.contains("pwd") || normalized.contains("password") {
+            SecurityEventKind::PasswordChange
+        } else if normalized.contains("login") {
+            SecurityEventKind::Login
+        } else {
+            SecurityEventKind::Unknown(code.to_string())
+        }
+    }
+}
+
+/// Entry from the account security feed (logins, password changes)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityEvent {
+    pub id: String,
+    pub kind: SecurityEventKind,
+    pub title: String,
+    pub content: Option<String>,
+    pub device_name: Option<String>,
+    pub ip: Option<String>,
+    /// RFC 3339 time of the event
+    pub occurred_at: Option<String>,
+}
+
+impl SecurityEvent {
+    /// Parse one message from the security feed
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let id = match value.get("id").or_else(|| value.get("messageId"))? {
+            Value::String(s) => s.clone(),
+            Value::Number(n) => n.to_string(),
+            _ => return None,
+        };
+        let text = |key: &str| value.get(key).and_then(|v| v.as_str()).map(String::from);
+        let title = text("title").unwrap_or_default();
+        // `msgType` is the feed category (SECURITY), not the kind of event
+        let kind = SecurityEventKind::from_code(&text("type").unwrap_or_else(|| title.clone()));
+
+        Some(Self {
+            id,
+            kind,
+            title,
+            content: text("content").or_else(|| text("body")),
+            device_name: text("deviceName"),
+            ip: text("ip").or_else(|| text("loginIp")),
+            occurred_at: value_time(value, "createTime", "createTimeStr"),
+        })
+    }
+
+    /// Parse every event in a feed response
+    pub fn from_list_response(value: &Value) -> Vec<Self> {
+        parse_list(value, Self::from_value)
+    }
+}
+
+// ============= Health Models =============
+
+/// Structured client health status, suitable for readiness probes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthStatus {
+    /// Session token is present, unexpired, and accepted by the API
+    pub authenticated: bool,
+    /// A trade token is held; it isn't checked against the API, so it may
+    /// have expired
+    pub trade_token: bool,
+    /// Account ID resolved for this session
+    pub account_id: Option<String>,
+    /// Round-trip latency of the authenticated probe call in milliseconds
+    pub latency_ms: Option<u64>,
+    /// Error message if the probe failed
+    pub error: Option<String>,
+}
+
+impl HealthStatus {
+    /// Ready for market data and account calls
+    pub fn is_ready(&self) -> bool {
+        self.authenticated && self.account_id.is_some() && self.error.is_none()
+    }
+
+    /// Ready for order placement
+    pub fn is_trading_ready(&self) -> bool {
+        self.is_ready() && self.trade_token
+    }
+}
+
 // ============= Helper Functions =============
 
 /// Custom deserializer for f64 from string
@@ -1201,6 +4408,19 @@ where
     }
 }
 
+/// Custom deserializer for a string from a string or number
+pub fn deserialize_string_from_any<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: serde_json::Value = Deserialize::deserialize(deserializer)?;
+    match s {
+        serde_json::Value::String(s) => Ok(s),
+        serde_json::Value::Number(n) => Ok(n.to_string()),
+        _ => Err(de::Error::custom("Expected string or number")),
+    }
+}
+
 /// Custom deserializer for optional f64 from string
 pub fn deserialize_f64_from_string_opt<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
 where
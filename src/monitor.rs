@@ -0,0 +1,295 @@
+// Computed quote alarms evaluated client-side from streamed or polled prices
+
+use crate::models::SecurityEvent;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
+
+/// Direction of a triggered move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+}
+
+/// Fired when a ticker moves at least the registered percentage within its window
+#[derive(Debug, Clone)]
+pub struct MoveEvent {
+    pub rule_id: usize,
+    pub ticker_id: String,
+    pub direction: MoveDirection,
+    /// Signed percent change from `from_price` to `to_price`
+    pub change_pct: f64,
+    pub from_price: f64,
+    pub to_price: f64,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+struct MoveRule {
+    id: usize,
+    ticker_id: String,
+    threshold_pct: f64,
+    window: chrono::Duration,
+    // Cleared after firing; set again once the move falls back under the threshold
+    armed: bool,
+}
+
+#[derive(Debug, Default)]
+struct PriceHistory {
+    first_seen: Option<DateTime<Utc>>,
+    samples: VecDeque<(DateTime<Utc>, f64)>,
+}
+
+/// Evaluates registered move alarms against incoming prices
+///
+/// A rule stays quiet until it has seen a full window of prices (warm-up), and
+/// fires once per crossing: it re-arms only after the move drops back below the
+/// threshold.
+#[derive(Debug, Default)]
+pub struct QuoteMonitor {
+    rules: Vec<MoveRule>,
+    history: HashMap<String, PriceHistory>,
+    next_id: usize,
+}
+
+impl QuoteMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fire when `ticker_id` moves `pct` percent (either direction) within `window`
+    ///
+    /// Returns a rule ID that can be passed to `remove`.
+    pub fn on_move(&mut self, ticker_id: impl Into<String>, pct: f64, window: Duration) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.rules.push(MoveRule {
+            id,
+            ticker_id: ticker_id.into(),
+            threshold_pct: pct.abs(),
+            window: chrono::Duration::from_std(window).unwrap_or(chrono::Duration::MAX),
+            armed: true,
+        });
+        id
+    }
+
+    /// Remove a rule; returns false if it was not registered
+    pub fn remove(&mut self, rule_id: usize) -> bool {
+        let before = self.rules.len();
+        self.rules.retain(|r| r.id != rule_id);
+        self.rules.len() != before
+    }
+
+    /// Number of registered rules
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Check if no rules are registered
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Record a price and return any alarms it triggers
+    pub fn observe(&mut self, ticker_id: &str, price: f64, at: DateTime<Utc>) -> Vec<MoveEvent> {
+        let max_window = match self
+            .rules
+            .iter()
+            .filter(|r| r.ticker_id == ticker_id)
+            .map(|r| r.window)
+            .max()
+        {
+            Some(window) => window,
+            None => return Vec::new(),
+        };
+
+        let history = self.history.entry(ticker_id.to_string()).or_default();
+        history.first_seen.get_or_insert(at);
+        history.samples.push_back((at, price));
+        // Keep the newest sample at or before the oldest window start as its reference
+        while history.samples.len() > 1
+            && at.signed_duration_since(history.samples[1].0) >= max_window
+        {
+            history.samples.pop_front();
+        }
+
+        let mut events = Vec::new();
+        for rule in self.rules.iter_mut().filter(|r| r.ticker_id == ticker_id) {
+            // Warm-up: wait until the history covers the whole window
+            if history
+                .first_seen
+                .is_none_or(|first| at.signed_duration_since(first) < rule.window)
+            {
+                continue;
+            }
+
+            // Price as of the start of the window
+            let start = at - rule.window;
+            let from_price = match history
+                .samples
+                .iter()
+                .take_while(|(time, _)| *time <= start)
+                .last()
+            {
+                Some((_, p)) if *p > 0.0 => *p,
+                _ => continue,
+            };
+
+            let change_pct = (price - from_price) / from_price * 100.0;
+            if change_pct.abs() < rule.threshold_pct {
+                rule.armed = true;
+                continue;
+            }
+            if !rule.armed {
+                continue;
+            }
+
+            rule.armed = false;
+            events.push(MoveEvent {
+                rule_id: rule.id,
+                ticker_id: ticker_id.to_string(),
+                direction: if change_pct >= 0.0 {
+                    MoveDirection::Up
+                } else {
+                    MoveDirection::Down
+                },
+                change_pct,
+                from_price,
+                to_price: price,
+                at,
+            });
+        }
+        events
+    }
+
+    /// Record a push message from the price stream (see `StreamConn::set_price_callback`)
+    pub fn observe_push(&mut self, topic: &Value, data: &Value) -> Vec<MoveEvent> {
+        let ticker_id = match topic.get("tickerId") {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Number(n)) => n.to_string(),
+            _ => return Vec::new(),
+        };
+
+        let price = ["price", "close", "pPrice"]
+            .iter()
+            .find_map(|key| match data.get(*key) {
+                Some(Value::String(s)) => s.parse::<f64>().ok(),
+                Some(Value::Number(n)) => n.as_f64(),
+                _ => None,
+            });
+
+        match price {
+            Some(price) => self.observe(&ticker_id, price, Utc::now()),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Picks out security events that have not been seen before
+///
+/// The first batch only seeds the seen set, so events that predate the
+/// monitor are not reported as new.
+#[derive(Debug, Default)]
+pub struct SecurityMonitor {
+    seen: HashSet<String>,
+    primed: bool,
+}
+
+impl SecurityMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a polled batch of events, returning the new ones
+    pub fn observe(&mut self, events: Vec<SecurityEvent>) -> Vec<SecurityEvent> {
+        let fresh: Vec<SecurityEvent> = events
+            .into_iter()
+            .filter(|e| self.seen.insert(e.id.clone()))
+            .collect();
+        if std::mem::replace(&mut self.primed, true) {
+            fresh
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_000 + secs, 0).unwrap()
+    }
+
+    #[test]
+    fn test_warm_up_and_trigger() {
+        let mut monitor = QuoteMonitor::new();
+        let rule = monitor.on_move("913256135", 2.0, Duration::from_secs(300));
+
+        // Large move before a full window of history is ignored
+        assert!(monitor.observe("913256135", 100.0, at(0)).is_empty());
+        assert!(monitor.observe("913256135", 105.0, at(60)).is_empty());
+
+        // Window is now covered; 100 -> 103 over the last 5 minutes is +3%
+        let events = monitor.observe("913256135", 103.0, at(300));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].rule_id, rule);
+        assert_eq!(events[0].direction, MoveDirection::Up);
+        assert_eq!(events[0].from_price, 100.0);
+
+        // Unrelated tickers are ignored
+        assert!(monitor.observe("1", 1.0, at(300)).is_empty());
+    }
+
+    #[test]
+    fn test_debounce_and_rearm() {
+        let mut monitor = QuoteMonitor::new();
+        monitor.on_move("1", 1.0, Duration::from_secs(60));
+
+        monitor.observe("1", 50.0, at(0));
+        assert_eq!(monitor.observe("1", 49.0, at(60)).len(), 1);
+
+        // Still beyond the threshold: no repeat event
+        assert!(monitor.observe("1", 48.9, at(70)).is_empty());
+
+        // Falls back under the threshold, then crosses again
+        assert!(monitor.observe("1", 48.95, at(125)).is_empty());
+        let events = monitor.observe("1", 48.0, at(140));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].direction, MoveDirection::Down);
+    }
+
+    #[test]
+    fn test_observe_push_and_remove() {
+        let mut monitor = QuoteMonitor::new();
+        let rule = monitor.on_move("42", 5.0, Duration::ZERO);
+
+        let events = monitor.observe_push(&json!({"tickerId": 42}), &json!({"price": "10.0"}));
+        assert!(events.is_empty());
+
+        assert!(monitor.remove(rule));
+        assert!(!monitor.remove(rule));
+        assert!(monitor.is_empty());
+    }
+
+    #[test]
+    fn test_security_monitor_reports_only_new_events() {
+        let event = |id: &str| {
+            SecurityEvent::from_value(&json!({"id": id, "type": "NEW_DEVICE_LOGIN"})).unwrap()
+        };
+        let mut monitor = SecurityMonitor::new();
+
+        // Existing history is not reported
+        assert!(monitor.observe(vec![event("1"), event("2")]).is_empty());
+
+        let fresh = monitor.observe(vec![event("3"), event("2")]);
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].id, "3");
+        assert!(monitor.observe(vec![event("3")]).is_empty());
+    }
+}
@@ -0,0 +1,326 @@
+// Round-robin news polling across a watchlist with a persistent dedupe store
+
+use crate::{client::WebullClient, error::Result, models::News};
+use futures::stream::{self, BoxStream, StreamExt};
+use std::collections::{HashSet, VecDeque};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// A headline not seen before, with the ticker it was fetched for
+#[derive(Debug, Clone)]
+pub struct WatchedNews {
+    pub ticker_id: String,
+    pub news: News,
+}
+
+/// Polls news for a set of tickers, one request at a time
+///
+/// Tickers are polled in turn with at least `interval` between any two
+/// requests, so a watchlist of any size stays within one request budget.
+/// The first fetch for a ticker with no recorded history only seeds the
+/// seen set; after that only headlines never seen before, for any ticker,
+/// are emitted. The newest `max_seen` IDs are remembered. With `persist_to`,
+/// they survive restarts.
+#[derive(Debug)]
+pub struct NewsWatcher {
+    tickers: Vec<String>,
+    interval: Duration,
+    page_size: i32,
+    next: usize,
+    last_request: Option<Instant>,
+    seen: HashSet<i64>,
+    // Seen IDs oldest first, with the ticker each was fetched for
+    history: VecDeque<(String, i64)>,
+    seeded: HashSet<String>,
+    max_seen: usize,
+    store: Option<PathBuf>,
+    store_lines: usize,
+}
+
+impl NewsWatcher {
+    /// Watch the given ticker IDs, spacing requests `interval` apart
+    pub fn for_watchlist<I, S>(ticker_ids: I, interval: Duration) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            tickers: ticker_ids.into_iter().map(Into::into).collect(),
+            interval,
+            page_size: 20,
+            next: 0,
+            last_request: None,
+            seen: HashSet::new(),
+            history: VecDeque::new(),
+            seeded: HashSet::new(),
+            max_seen: 10_000,
+            store: None,
+            store_lines: 0,
+        }
+    }
+
+    /// Set how many headlines to request per poll
+    pub fn page_size(mut self, page_size: i32) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Remember at most `max_seen` news IDs, forgetting the oldest first
+    /// (10,000 by default)
+    pub fn max_seen(mut self, max_seen: usize) -> Self {
+        self.max_seen = max_seen.max(1);
+        self
+    }
+
+    /// Load and append seen news IDs to `path` (one `ticker_id<TAB>news_id`
+    /// per line)
+    ///
+    /// The file is rewritten with only the remembered IDs once it holds
+    /// twice `max_seen` lines.
+    pub fn persist_to(mut self, path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if path.exists() {
+            for line in BufReader::new(fs::File::open(&path)?).lines() {
+                let line = line?;
+                self.store_lines += 1;
+                if let Some((ticker_id, id)) = line.split_once('\t') {
+                    // A ticker polled with nothing new has an empty ID
+                    self.seeded.insert(ticker_id.to_string());
+                    if let Ok(id) = id.trim().parse() {
+                        if self.seen.insert(id) {
+                            self.history.push_back((ticker_id.to_string(), id));
+                        }
+                    }
+                }
+            }
+        }
+        self.forget_oldest();
+        if self.store_lines > 2 * self.max_seen {
+            self.compact(&path)?;
+        }
+        self.store = Some(path);
+        Ok(self)
+    }
+
+    pub fn tickers(&self) -> &[String] {
+        &self.tickers
+    }
+
+    /// Record fetched headlines for a ticker, returning the new ones
+    pub fn absorb(&mut self, ticker_id: &str, news: Vec<News>) -> Result<Vec<WatchedNews>> {
+        let seeding = self.seeded.insert(ticker_id.to_string());
+        let fresh: Vec<News> = news
+            .into_iter()
+            .filter(|n| self.seen.insert(n.id))
+            .collect();
+        self.history
+            .extend(fresh.iter().map(|n| (ticker_id.to_string(), n.id)));
+        self.forget_oldest();
+
+        if let Some(path) = self.store.clone() {
+            let mut lines: Vec<String> = fresh
+                .iter()
+                .map(|n| format!("{}\t{}", ticker_id, n.id))
+                .collect();
+            if seeding && fresh.is_empty() {
+                lines.push(format!("{}\t", ticker_id));
+            }
+            if self.store_lines + lines.len() > 2 * self.max_seen {
+                self.compact(&path)?;
+            } else if !lines.is_empty() {
+                if let Some(parent) = path.parent() {
+                    if !parent.as_os_str().is_empty() {
+                        fs::create_dir_all(parent)?;
+                    }
+                }
+                let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+                for line in &lines {
+                    writeln!(file, "{}", line)?;
+                }
+                self.store_lines += lines.len();
+            }
+        }
+
+        if seeding {
+            return Ok(Vec::new());
+        }
+        Ok(fresh
+            .into_iter()
+            .map(|news| WatchedNews {
+                ticker_id: ticker_id.to_string(),
+                news,
+            })
+            .collect())
+    }
+
+    fn forget_oldest(&mut self) {
+        while self.history.len() > self.max_seen {
+            if let Some((_, id)) = self.history.pop_front() {
+                self.seen.remove(&id);
+            }
+        }
+    }
+
+    /// Rewrite the store with only the remembered IDs and seeded tickers
+    fn compact(&mut self, path: &Path) -> Result<()> {
+        let with_ids: HashSet<&str> = self.history.iter().map(|(t, _)| t.as_str()).collect();
+        let mut lines: Vec<String> = self
+            .seeded
+            .iter()
+            .filter(|t| !with_ids.contains(t.as_str()))
+            .map(|t| format!("{}\t", t))
+            .collect();
+        lines.extend(self.history.iter().map(|(t, id)| format!("{}\t{}", t, id)));
+
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        let tmp = path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp)?;
+        for line in &lines {
+            writeln!(file, "{}", line)?;
+        }
+        drop(file);
+        fs::rename(&tmp, path)?;
+        self.store_lines = lines.len();
+        Ok(())
+    }
+
+    /// Wait for the rate budget, poll the next ticker and return its new headlines
+    pub async fn poll_next(&mut self, client: &WebullClient) -> Result<Vec<WatchedNews>> {
+        if self.tickers.is_empty() {
+            return Ok(Vec::new());
+        }
+        if let Some(last) = self.last_request {
+            tokio::time::sleep_until(last + self.interval).await;
+        }
+        let ticker_id = self.tickers[self.next % self.tickers.len()].clone();
+        self.next = (self.next + 1) % self.tickers.len();
+        self.last_request = Some(Instant::now());
+
+        let news = client
+            .get_ticker_news(&ticker_id, 0, self.page_size)
+            .await?;
+        self.absorb(&ticker_id, news)
+    }
+
+    /// Poll forever, yielding new headlines as they appear
+    ///
+    /// Poll failures are yielded as errors and polling continues.
+    pub fn stream(self, client: &WebullClient) -> BoxStream<'_, Result<WatchedNews>> {
+        stream::unfold(
+            (self, VecDeque::new()),
+            move |(mut watcher, mut buffer)| async move {
+                loop {
+                    if let Some(news) = buffer.pop_front() {
+                        return Some((Ok(news), (watcher, buffer)));
+                    }
+                    if watcher.tickers.is_empty() {
+                        return None;
+                    }
+                    match watcher.poll_next(client).await {
+                        Ok(fresh) => buffer.extend(fresh),
+                        Err(e) => return Some((Err(e), (watcher, buffer))),
+                    }
+                }
+            },
+        )
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn news(id: i64) -> News {
+        serde_json::from_value(json!({
+            "id": id,
+            "title": format!("Headline {}", id),
+            "newsTime": "2024-06-01T12:00:00.000+0000"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_seeds_then_emits_new_headlines_across_restarts() {
+        let dir = std::env::temp_dir().join(format!("webull_news_{}", uuid::Uuid::new_v4()));
+        let path = dir.join("seen.tsv");
+
+        let mut watcher = NewsWatcher::for_watchlist(["913256135", "913243251"], Duration::ZERO)
+            .persist_to(&path)
+            .unwrap();
+        assert!(watcher
+            .absorb("913256135", vec![news(1), news(2)])
+            .unwrap()
+            .is_empty());
+        let fresh = watcher.absorb("913256135", vec![news(3), news(2)]).unwrap();
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].news.id, 3);
+
+        // A restarted watcher remembers what was already emitted
+        let mut restarted = NewsWatcher::for_watchlist(["913256135"], Duration::ZERO)
+            .persist_to(&path)
+            .unwrap();
+        let fresh = restarted
+            .absorb("913256135", vec![news(4), news(3)])
+            .unwrap();
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].news.id, 4);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_headline_for_two_tickers_is_emitted_once() {
+        let mut watcher = NewsWatcher::for_watchlist(["913256135", "913243251"], Duration::ZERO);
+        watcher.absorb("913256135", vec![news(1)]).unwrap();
+        watcher.absorb("913243251", vec![news(2)]).unwrap();
+
+        let fresh = watcher.absorb("913256135", vec![news(3)]).unwrap();
+        assert_eq!(fresh.len(), 1);
+        let fresh = watcher.absorb("913243251", vec![news(4), news(3)]).unwrap();
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].news.id, 4);
+    }
+
+    #[test]
+    fn test_seen_ids_and_store_are_capped() {
+        let dir = std::env::temp_dir().join(format!("webull_news_{}", uuid::Uuid::new_v4()));
+        let path = dir.join("seen.tsv");
+
+        let mut watcher = NewsWatcher::for_watchlist(["913256135"], Duration::ZERO)
+            .max_seen(3)
+            .persist_to(&path)
+            .unwrap();
+        watcher.absorb("913256135", vec![news(1)]).unwrap();
+        for id in 2..=10 {
+            watcher.absorb("913256135", vec![news(id)]).unwrap();
+        }
+        assert_eq!(watcher.seen.len(), 3);
+        let lines = fs::read_to_string(&path).unwrap().lines().count();
+        assert!(lines <= 6, "store has {} lines", lines);
+
+        // The oldest IDs are forgotten, the newest survive a restart
+        let mut restarted = NewsWatcher::for_watchlist(["913256135"], Duration::ZERO)
+            .max_seen(3)
+            .persist_to(&path)
+            .unwrap();
+        assert!(restarted
+            .absorb("913256135", vec![news(10)])
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            restarted.absorb("913256135", vec![news(1)]).unwrap().len(),
+            1
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
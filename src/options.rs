@@ -0,0 +1,649 @@
+// Multi-leg option orders and presets that pick their contracts from a chain
+
+use crate::{
+    error::{Result, WebullError},
+    live_client::LiveWebullClient,
+    market,
+    models::{OptionContract, OrderAction, OrderType, TimeInForce},
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Strategy tag sent with a spread order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OptionStrategy {
+    Single,
+    CoveredCall,
+    Vertical,
+    IronCondor,
+}
+
+/// Instrument a leg trades
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum LegKind {
+    Option,
+    #[serde(rename = "EQUITY")]
+    Stock,
+}
+
+/// One leg of a spread
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpreadLeg {
+    pub ticker_id: i64,
+    pub kind: LegKind,
+    pub action: OrderAction,
+    /// Units of this leg per spread, e.g. 100 shares against one call
+    pub ratio: u32,
+    /// Contract symbol, or empty for the stock leg
+    pub symbol: String,
+    pub strike_price: Option<f64>,
+}
+
+impl SpreadLeg {
+    pub fn option(contract: &OptionContract, action: OrderAction) -> Self {
+        Self {
+            ticker_id: contract.ticker_id,
+            kind: LegKind::Option,
+            action,
+            ratio: 1,
+            symbol: contract.symbol.clone(),
+            strike_price: Some(contract.strike_price),
+        }
+    }
+
+    pub fn stock(ticker_id: i64, action: OrderAction, shares: u32) -> Self {
+        Self {
+            ticker_id,
+            kind: LegKind::Stock,
+            action,
+            ratio: shares,
+            symbol: String::new(),
+            strike_price: None,
+        }
+    }
+}
+
+/// A multi-leg option order
+///
+/// `limit_price` is the net price per spread as a positive number; `credit`
+/// says whether it is received or paid. The payload carries the direction
+/// of multi-leg orders in the sign of `lmtPrice`, as Webull's combo ticket
+/// does: positive is a debit, negative a credit. `Single` orders carry it
+/// on the leg's action and always send a positive price.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceOptionSpreadRequest {
+    pub strategy: OptionStrategy,
+    pub legs: Vec<SpreadLeg>,
+    /// Number of spreads
+    pub quantity: u32,
+    pub order_type: OrderType,
+    pub limit_price: Option<f64>,
+    pub credit: bool,
+    pub time_in_force: TimeInForce,
+}
+
+impl PlaceOptionSpreadRequest {
+    /// Body for the option order endpoint
+    pub fn payload(&self) -> Result<Value> {
+        if self.legs.is_empty() || self.quantity == 0 {
+            return Err(WebullError::InvalidRequest(
+                "A spread needs at least one leg and a quantity".to_string(),
+            ));
+        }
+        if !matches!(
+            self.time_in_force,
+            TimeInForce::Day | TimeInForce::GoodTillCancel
+        ) {
+            return Err(WebullError::InvalidRequest(
+                "Option spreads are DAY or GTC only".to_string(),
+            ));
+        }
+
+        let orders: Vec<Value> = self
+            .legs
+            .iter()
+            .map(|leg| {
+                json!({
+                    "action": leg.action,
+                    "quantity": leg.ratio * self.quantity,
+                    "tickerId": leg.ticker_id,
+                    "tickerType": leg.kind,
+                })
+            })
+            .collect();
+        let mut payload = json!({
+            "orderType": self.order_type,
+            "timeInForce": self.time_in_force,
+            "serialId": uuid::Uuid::new_v4().to_string(),
+            "quantity": self.quantity,
+            "optionStrategy": self.strategy,
+            "orders": orders,
+        });
+        match (&self.order_type, self.limit_price) {
+            (OrderType::Market, _) => {}
+            (OrderType::Limit, Some(price)) if price > 0.0 => {
+                let signed = self.credit && self.strategy != OptionStrategy::Single;
+                payload["lmtPrice"] = json!(if signed { -price } else { price });
+            }
+            (OrderType::Limit, _) => {
+                return Err(WebullError::InvalidRequest(
+                    "Limit spreads need a positive net price".to_string(),
+                ))
+            }
+            (order_type, _) => {
+                return Err(WebullError::InvalidRequest(format!(
+                    "{:?} orders are not supported for spreads",
+                    order_type
+                )))
+            }
+        }
+        Ok(payload)
+    }
+}
+
+/// Direction of a vertical spread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vertical {
+    /// Buy a call, sell a higher strike call (debit)
+    BullCall,
+    /// Sell a call, buy a higher strike call (credit)
+    BearCall,
+    /// Sell a put, buy a lower strike put (credit)
+    BullPut,
+    /// Buy a put, sell a lower strike put (debit)
+    BearPut,
+}
+
+/// Builds spread orders by picking contracts from an option chain
+///
+/// The leg nearest the target delta anchors the spread and the other leg
+/// sits `width` away in strike price, using the closest listed strike. Without an
+/// explicit expiration the one closest to `dte` days out is used, or the
+/// soonest in the chain. Limit prices default to the net mid of the legs.
+///
+/// Contracts the API returned without a delta can't be matched against the
+/// target; with the `greeks` feature they are filled from `underlying_price`,
+/// otherwise building fails rather than skipping them.
+///
+/// ```no_run
+/// use webull_unofficial::options::{StrategyBuilder, Vertical};
+/// use webull_unofficial::LiveWebullClient;
+///
+/// # async fn run(client: LiveWebullClient) -> webull_unofficial::error::Result<()> {
+/// let builder = StrategyBuilder::new(913256135).delta(0.25).width(5.0).dte(30);
+/// let chain = builder.fetch_chain(&client).await?;
+/// let order = builder.vertical(&chain, Vertical::BullPut)?;
+/// client.place_option_spread(&order).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct StrategyBuilder {
+    underlying_ticker_id: i64,
+    expiration: Option<String>,
+    dte: Option<i64>,
+    delta: f64,
+    width: f64,
+    quantity: u32,
+    limit_price: Option<f64>,
+    time_in_force: TimeInForce,
+    #[cfg(feature = "greeks")]
+    underlying_price: Option<f64>,
+    #[cfg(feature = "greeks")]
+    rate: f64,
+}
+
+impl StrategyBuilder {
+    pub fn new(underlying_ticker_id: i64) -> Self {
+        Self {
+            underlying_ticker_id,
+            expiration: None,
+            dte: None,
+            delta: 0.30,
+            width: 5.0,
+            quantity: 1,
+            limit_price: None,
+            time_in_force: TimeInForce::Day,
+            #[cfg(feature = "greeks")]
+            underlying_price: None,
+            #[cfg(feature = "greeks")]
+            rate: 0.05,
+        }
+    }
+
+    /// Expiration date (`YYYY-MM-DD`)
+    pub fn expiration(mut self, date: impl Into<String>) -> Self {
+        self.expiration = Some(date.into());
+        self
+    }
+
+    /// Use the expiration closest to this many days out
+    pub fn dte(mut self, days: i64) -> Self {
+        self.dte = Some(days);
+        self
+    }
+
+    /// Target delta of the anchoring leg, as a positive number (0.30 by default)
+    pub fn delta(mut self, delta: f64) -> Self {
+        self.delta = delta.abs();
+        self
+    }
+
+    /// Strike distance between the legs of each vertical (5.0 by default)
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = width.abs();
+        self
+    }
+
+    pub fn quantity(mut self, quantity: u32) -> Self {
+        self.quantity = quantity;
+        self
+    }
+
+    /// Net price per spread, instead of the net mid
+    pub fn limit_price(mut self, price: f64) -> Self {
+        self.limit_price = Some(price);
+        self
+    }
+
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = time_in_force;
+        self
+    }
+
+    /// Price of the underlying, used to fill deltas the chain is missing
+    #[cfg(feature = "greeks")]
+    pub fn underlying_price(mut self, price: f64) -> Self {
+        self.underlying_price = Some(price);
+        self
+    }
+
+    /// Risk-free rate for filling missing deltas (0.05 by default)
+    #[cfg(feature = "greeks")]
+    pub fn rate(mut self, rate: f64) -> Self {
+        self.rate = rate;
+        self
+    }
+
+    /// Fetch the chain for the configured expiration
+    pub async fn fetch_chain(&self, client: &LiveWebullClient) -> Result<Vec<OptionContract>> {
+        let ticker = self.underlying_ticker_id.to_string();
+        let date = match &self.expiration {
+            Some(date) => date.clone(),
+            None => {
+                let dates: Vec<String> = client
+                    .get_option_expirations(&ticker)
+                    .await?
+                    .into_iter()
+                    .map(|expiration| expiration.date)
+                    .collect();
+                self.pick_expiration(&dates, market::today())
+                    .ok_or_else(|| {
+                        WebullError::InvalidRequest(format!("No option expirations for {}", ticker))
+                    })?
+            }
+        };
+        client.get_options_by_expiration(&ticker, &date).await
+    }
+
+    /// Buy 100 shares per contract and sell a call at the target delta
+    ///
+    /// The net price is per share: the stock price less the call premium.
+    pub fn covered_call(
+        &self,
+        chain: &[OptionContract],
+        stock_price: f64,
+    ) -> Result<PlaceOptionSpreadRequest> {
+        let contracts = self.expiring(chain, Some(stock_price))?;
+        let call = nearest_delta(&contracts, "CALL", self.delta)?;
+        let legs = vec![
+            SpreadLeg::stock(self.underlying_ticker_id, OrderAction::Buy, 100),
+            SpreadLeg::option(call, OrderAction::Sell),
+        ];
+        let net = call.mid().map(|premium| stock_price - premium);
+        self.request(OptionStrategy::CoveredCall, legs, net, false)
+    }
+
+    /// Two legs of the same type `width` apart
+    pub fn vertical(
+        &self,
+        chain: &[OptionContract],
+        direction: Vertical,
+    ) -> Result<PlaceOptionSpreadRequest> {
+        let contracts = self.expiring(chain, None)?;
+        let (option_type, anchor_action, offset) = match direction {
+            Vertical::BullCall => ("CALL", OrderAction::Buy, self.width),
+            Vertical::BearCall => ("CALL", OrderAction::Sell, self.width),
+            Vertical::BullPut => ("PUT", OrderAction::Sell, -self.width),
+            Vertical::BearPut => ("PUT", OrderAction::Buy, -self.width),
+        };
+        let credit = anchor_action == OrderAction::Sell;
+        let anchor = nearest_delta(&contracts, option_type, self.delta)?;
+        let wing = wing(&contracts, anchor, offset)?;
+        let wing_action = match anchor_action {
+            OrderAction::Buy => OrderAction::Sell,
+            OrderAction::Sell => OrderAction::Buy,
+        };
+        let net = net_mid(&[(anchor, &anchor_action), (wing, &wing_action)]);
+        let legs = vec![
+            SpreadLeg::option(anchor, anchor_action),
+            SpreadLeg::option(wing, wing_action),
+        ];
+        self.request(OptionStrategy::Vertical, legs, net, credit)
+    }
+
+    /// A bull put and a bear call spread around the current price
+    pub fn iron_condor(&self, chain: &[OptionContract]) -> Result<PlaceOptionSpreadRequest> {
+        let contracts = self.expiring(chain, None)?;
+        let short_put = nearest_delta(&contracts, "PUT", self.delta)?;
+        let short_call = nearest_delta(&contracts, "CALL", self.delta)?;
+        if short_put.strike_price >= short_call.strike_price {
+            return Err(WebullError::InvalidRequest(
+                "Short put and call strikes overlap; lower the delta".to_string(),
+            ));
+        }
+        let long_put = wing(&contracts, short_put, -self.width)?;
+        let long_call = wing(&contracts, short_call, self.width)?;
+        let (buy, sell) = (OrderAction::Buy, OrderAction::Sell);
+        let net = net_mid(&[
+            (long_put, &buy),
+            (short_put, &sell),
+            (short_call, &sell),
+            (long_call, &buy),
+        ]);
+        let legs = vec![
+            SpreadLeg::option(long_put, OrderAction::Buy),
+            SpreadLeg::option(short_put, OrderAction::Sell),
+            SpreadLeg::option(short_call, OrderAction::Sell),
+            SpreadLeg::option(long_call, OrderAction::Buy),
+        ];
+        self.request(OptionStrategy::IronCondor, legs, net, true)
+    }
+
+    /// Contracts for the configured expiration, with missing deltas filled
+    /// in from the underlying price when the `greeks` feature is on
+    fn expiring(
+        &self,
+        chain: &[OptionContract],
+        stock_price: Option<f64>,
+    ) -> Result<Vec<OptionContract>> {
+        let today = market::today();
+        let date = match &self.expiration {
+            Some(date) => Some(date.clone()),
+            None => {
+                let mut dates: Vec<String> =
+                    chain.iter().map(|c| c.expiration_date.clone()).collect();
+                dates.sort();
+                dates.dedup();
+                self.pick_expiration(&dates, today)
+            }
+        };
+        let contracts: Vec<OptionContract> = chain
+            .iter()
+            .filter(|c| Some(&c.expiration_date) == date.as_ref())
+            .cloned()
+            .collect();
+        if contracts.is_empty() {
+            return Err(WebullError::InvalidRequest(format!(
+                "No contracts expiring {}",
+                date.unwrap_or_default()
+            )));
+        }
+        #[cfg(feature = "greeks")]
+        let contracts = {
+            let mut contracts = contracts;
+            if let Some(spot) = self.underlying_price.or(stock_price) {
+                crate::greeks::fill_chain_greeks(&mut contracts, spot, self.rate, today);
+            }
+            contracts
+        };
+        #[cfg(not(feature = "greeks"))]
+        let _ = stock_price;
+        Ok(contracts)
+    }
+
+    /// The expiration closest to `dte` days after `today`, or the soonest
+    fn pick_expiration(&self, dates: &[String], today: NaiveDate) -> Option<String> {
+        let days = |date: &String| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .ok()
+                .map(|expiry| (expiry - today).num_days())
+        };
+        let upcoming = dates
+            .iter()
+            .filter(|date| days(date).is_some_and(|d| d >= 0));
+        match self.dte {
+            Some(target) => upcoming.min_by_key(|date| days(date).map(|d| (d - target).abs())),
+            None => upcoming.min_by_key(|date| days(date)),
+        }
+        .cloned()
+    }
+
+    fn request(
+        &self,
+        strategy: OptionStrategy,
+        legs: Vec<SpreadLeg>,
+        net_mid: Option<f64>,
+        credit: bool,
+    ) -> Result<PlaceOptionSpreadRequest> {
+        let price = self.limit_price.or(net_mid.map(f64::abs)).ok_or_else(|| {
+            WebullError::InvalidRequest(
+                "No quotes to price the spread; set a limit price".to_string(),
+            )
+        })?;
+        Ok(PlaceOptionSpreadRequest {
+            strategy,
+            legs,
+            quantity: self.quantity,
+            order_type: OrderType::Limit,
+            limit_price: Some(round_cents(price)),
+            credit,
+            time_in_force: self.time_in_force.clone(),
+        })
+    }
+}
+
+/// Net mid per spread; positive is a debit
+fn net_mid(legs: &[(&OptionContract, &OrderAction)]) -> Option<f64> {
+    legs.iter()
+        .map(|(contract, action)| {
+            contract.mid().map(|mid| match action {
+                OrderAction::Buy => mid,
+                OrderAction::Sell => -mid,
+            })
+        })
+        .sum()
+}
+
+fn round_cents(price: f64) -> f64 {
+    (price * 100.0).round() / 100.0
+}
+
+/// Round a single-leg premium to its listed increment
+///
+/// Uses the Penny Interval Program ticks: $0.01 under $3.00 and $0.05 from
+/// there up. Classes outside the program quote in $0.05 and $0.10.
+pub fn round_to_tick(price: f64) -> f64 {
+    if price < 3.0 {
+        round_cents(price)
+    } else {
+        (price * 20.0).round() / 20.0
+    }
+}
+
+/// The contract of `option_type` whose delta is closest to `target`
+///
+/// Fails if any candidate has no delta, since it could be the closest.
+fn nearest_delta<'a>(
+    contracts: &'a [OptionContract],
+    option_type: &str,
+    target: f64,
+) -> Result<&'a OptionContract> {
+    let candidates: Vec<&OptionContract> = contracts
+        .iter()
+        .filter(|c| c.option_type == option_type)
+        .collect();
+    let missing = candidates.iter().filter(|c| c.delta.is_none()).count();
+    if missing > 0 {
+        return Err(WebullError::InvalidRequest(format!(
+            "{} {} contracts have no delta; fill them with greeks::fill_chain_greeks",
+            missing, option_type
+        )));
+    }
+    candidates
+        .into_iter()
+        .filter_map(|c| c.delta.map(|delta| (c, (delta.abs() - target).abs())))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(contract, _)| contract)
+        .ok_or_else(|| WebullError::InvalidRequest(format!("No {} in the chain", option_type)))
+}
+
+/// The contract of the anchor's type closest to `offset` strikes away, on
+/// that side of the anchor
+fn wing<'a>(
+    contracts: &'a [OptionContract],
+    anchor: &OptionContract,
+    offset: f64,
+) -> Result<&'a OptionContract> {
+    let strike = anchor.strike_price + offset;
+    contracts
+        .iter()
+        .filter(|c| {
+            c.option_type == anchor.option_type
+                && (c.strike_price - anchor.strike_price) * offset.signum() > 0.0
+        })
+        .min_by(|a, b| {
+            (a.strike_price - strike)
+                .abs()
+                .total_cmp(&(b.strike_price - strike).abs())
+        })
+        .ok_or_else(|| {
+            WebullError::InvalidRequest(format!(
+                "No {} strike beyond {}",
+                anchor.option_type, anchor.strike_price
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contract(
+        ticker_id: i64,
+        option_type: &str,
+        strike: f64,
+        delta: f64,
+        mid: f64,
+    ) -> OptionContract {
+        serde_json::from_value(json!({
+            "tickerId": ticker_id,
+            "symbol": format!("AAPL240621{}{}", &option_type[..1], strike),
+            "strikePrice": strike,
+            "expirationDate": "2024-06-21",
+            "optionType": option_type,
+            "bid": mid - 0.05,
+            "ask": mid + 0.05,
+            "delta": delta,
+        }))
+        .unwrap()
+    }
+
+    fn chain() -> Vec<OptionContract> {
+        vec![
+            contract(1, "PUT", 170.0, -0.10, 0.60),
+            contract(2, "PUT", 175.0, -0.20, 1.20),
+            contract(3, "PUT", 180.0, -0.31, 2.10),
+            contract(4, "CALL", 195.0, 0.33, 2.40),
+            contract(5, "CALL", 200.0, 0.21, 1.30),
+            contract(6, "CALL", 205.0, 0.12, 0.70),
+        ]
+    }
+
+    #[test]
+    fn test_presets_pick_legs_by_delta_and_width() {
+        let builder = StrategyBuilder::new(913256135)
+            .expiration("2024-06-21")
+            .quantity(2);
+
+        let bull_put = builder.vertical(&chain(), Vertical::BullPut).unwrap();
+        let legs: Vec<_> = bull_put
+            .legs
+            .iter()
+            .map(|l| (l.ticker_id, l.action.clone()))
+            .collect();
+        assert_eq!(legs, vec![(3, OrderAction::Sell), (2, OrderAction::Buy)]);
+        assert!(bull_put.credit);
+        assert_eq!(bull_put.limit_price, Some(0.9));
+
+        let condor = builder.clone().delta(0.2).iron_condor(&chain()).unwrap();
+        let ids: Vec<_> = condor.legs.iter().map(|l| l.ticker_id).collect();
+        assert_eq!(ids, vec![1, 2, 5, 6]);
+        assert_eq!(condor.limit_price, Some(1.2));
+
+        let covered = builder.covered_call(&chain(), 190.0).unwrap();
+        assert_eq!(covered.legs[0].kind, LegKind::Stock);
+        assert_eq!(covered.legs[1].ticker_id, 4);
+        assert_eq!(covered.limit_price, Some(187.6));
+
+        let payload = covered.payload().unwrap();
+        assert_eq!(payload["optionStrategy"], "CoveredCall");
+        assert_eq!(payload["lmtPrice"], 187.6);
+        assert_eq!(payload["orders"][0]["quantity"], 200);
+        assert_eq!(payload["orders"][0]["tickerType"], "EQUITY");
+        assert_eq!(payload["orders"][1]["action"], "SELL");
+
+        let wide = builder.clone().width(50.0);
+        let bear_put = wide.vertical(&chain(), Vertical::BearPut).unwrap();
+        assert_eq!(bear_put.legs[1].ticker_id, 1);
+        assert!(!bear_put.credit);
+
+        // Only expired contracts and no date given
+        assert!(StrategyBuilder::new(913256135)
+            .vertical(&chain(), Vertical::BullCall)
+            .is_err());
+    }
+
+    #[test]
+    fn test_payload_signs_net_price_by_direction() {
+        let builder = StrategyBuilder::new(913256135).expiration("2024-06-21");
+
+        let condor = builder.clone().delta(0.2).iron_condor(&chain()).unwrap();
+        assert_eq!(condor.payload().unwrap()["lmtPrice"], -1.2);
+
+        let bull_put = builder.vertical(&chain(), Vertical::BullPut).unwrap();
+        assert_eq!(bull_put.payload().unwrap()["lmtPrice"], -0.9);
+
+        let bull_call = builder.vertical(&chain(), Vertical::BullCall).unwrap();
+        assert!(!bull_call.credit);
+        assert_eq!(bull_call.payload().unwrap()["lmtPrice"], 1.1);
+
+        // A put without a delta could be the nearest one, so don't guess
+        let mut gappy = chain();
+        gappy[0].delta = None;
+        let err = builder.vertical(&gappy, Vertical::BullPut).unwrap_err();
+        assert!(err.to_string().contains("1 PUT contracts have no delta"));
+    }
+
+    #[test]
+    fn test_expiration_closest_to_dte() {
+        let dates: Vec<String> = ["2024-06-07", "2024-06-14", "2024-06-21", "2024-07-19"]
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+        let today = NaiveDate::from_ymd_opt(2024, 6, 10).unwrap();
+        let builder = StrategyBuilder::new(1);
+        assert_eq!(
+            builder.pick_expiration(&dates, today).as_deref(),
+            Some("2024-06-14")
+        );
+        assert_eq!(
+            builder.dte(30).pick_expiration(&dates, today).as_deref(),
+            Some("2024-07-19")
+        );
+    }
+}
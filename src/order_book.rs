@@ -0,0 +1,383 @@
+use crate::{
+    error::{Result, WebullError},
+    live_client::LiveWebullClient,
+    models::{Depth, PriceLevel},
+};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+// Prices are keyed as integer micro-units so levels can live in an ordered map
+const PRICE_SCALE: f64 = 1_000_000.0;
+
+fn price_key(price: f64) -> i64 {
+    (price * PRICE_SCALE).round() as i64
+}
+
+fn key_price(key: i64) -> f64 {
+    key as f64 / PRICE_SCALE
+}
+
+/// Incremental depth-of-book update received from the stream
+#[derive(Debug, Clone, Default)]
+pub struct DepthUpdate {
+    pub sequence: Option<u64>,
+    pub bids: Vec<PriceLevel>,
+    pub asks: Vec<PriceLevel>,
+    /// True when the update replaces the whole book rather than patching it
+    pub snapshot: bool,
+}
+
+impl DepthUpdate {
+    /// Parse a depth update from a push payload (`TopicTypes::TICKER_BOOK`)
+    pub fn from_payload(payload: &Value) -> Option<Self> {
+        let depth = payload.get("depth")?;
+
+        let sequence = ["seq", "sequence", "seqNum"]
+            .iter()
+            .find_map(|key| payload.get(*key).or_else(|| depth.get(*key)))
+            .and_then(|v| match v {
+                Value::Number(n) => n.as_u64(),
+                Value::String(s) => s.parse::<u64>().ok(),
+                _ => None,
+            });
+
+        let snapshot = payload
+            .get("snapshot")
+            .or_else(|| depth.get("snapshot"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        Some(Self {
+            sequence,
+            bids: parse_levels(depth.get("ntvAggBidList")),
+            asks: parse_levels(depth.get("ntvAggAskList")),
+            snapshot,
+        })
+    }
+}
+
+fn parse_levels(levels: Option<&Value>) -> Vec<PriceLevel> {
+    levels
+        .and_then(|v| serde_json::from_value::<Vec<PriceLevel>>(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Outcome of applying an update to an `OrderBook`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApplyOutcome {
+    /// The update was applied to the book
+    Applied,
+    /// The update was older than the current book state and was ignored
+    Stale,
+    /// A sequence gap was detected; the book needs a REST re-sync
+    GapDetected { expected: u64, received: u64 },
+    /// The book is waiting for a snapshot or re-sync and the delta was skipped
+    ResyncPending,
+}
+
+/// Locally maintained order book built from a REST snapshot and stream deltas
+#[derive(Debug, Clone)]
+pub struct OrderBook {
+    ticker_id: String,
+    bids: BTreeMap<i64, f64>,
+    asks: BTreeMap<i64, f64>,
+    last_sequence: Option<u64>,
+    // Highest sequence received, kept across snapshots without a sequence so
+    // deltas from before the snapshot are still recognised as stale
+    highest_sequence: Option<u64>,
+    needs_resync: bool,
+}
+
+impl OrderBook {
+    /// Create an empty book for a ticker (requires a snapshot or re-sync before use)
+    pub fn new(ticker_id: impl Into<String>) -> Self {
+        Self {
+            ticker_id: ticker_id.into(),
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            last_sequence: None,
+            highest_sequence: None,
+            needs_resync: true,
+        }
+    }
+
+    /// Get the ticker ID this book tracks
+    pub fn ticker_id(&self) -> &str {
+        &self.ticker_id
+    }
+
+    /// Check if the book is out of sync and should be re-fetched over REST
+    pub fn needs_resync(&self) -> bool {
+        self.needs_resync
+    }
+
+    /// Get the sequence number of the last applied update
+    pub fn last_sequence(&self) -> Option<u64> {
+        self.last_sequence
+    }
+
+    /// Replace the book contents with a full depth snapshot
+    ///
+    /// Without a `sequence`, deltas at or below the highest sequence already
+    /// received are treated as stale and the next newer one is accepted.
+    pub fn apply_snapshot(&mut self, depth: &Depth, sequence: Option<u64>) {
+        self.bids.clear();
+        self.asks.clear();
+        for level in depth.ntv_agg_bid_list.iter().flatten() {
+            Self::set_level(&mut self.bids, level);
+        }
+        for level in depth.ntv_agg_ask_list.iter().flatten() {
+            Self::set_level(&mut self.asks, level);
+        }
+        self.last_sequence = sequence;
+        self.highest_sequence = self.highest_sequence.max(sequence);
+        self.needs_resync = false;
+    }
+
+    /// Apply an incremental update, detecting sequence gaps
+    ///
+    /// Levels with zero volume are removed from the book. Deltas are skipped
+    /// while a re-sync is pending; only a snapshot update clears it.
+    pub fn apply_update(&mut self, update: &DepthUpdate) -> ApplyOutcome {
+        let highest = self.highest_sequence;
+        self.highest_sequence = highest.max(update.sequence);
+        if update.snapshot {
+            self.bids.clear();
+            self.asks.clear();
+        } else if self.needs_resync {
+            return ApplyOutcome::ResyncPending;
+        } else if let Some(received) = update.sequence {
+            match self.last_sequence {
+                Some(last) if received <= last => return ApplyOutcome::Stale,
+                Some(last) if received != last + 1 => {
+                    self.needs_resync = true;
+                    return ApplyOutcome::GapDetected {
+                        expected: last + 1,
+                        received,
+                    };
+                }
+                Some(_) => {}
+                // After a snapshot without a sequence only newer deltas apply
+                None if highest.is_some_and(|h| received <= h) => return ApplyOutcome::Stale,
+                None => {}
+            }
+        }
+
+        for level in &update.bids {
+            Self::set_level(&mut self.bids, level);
+        }
+        for level in &update.asks {
+            Self::set_level(&mut self.asks, level);
+        }
+
+        if update.sequence.is_some() {
+            self.last_sequence = update.sequence;
+        }
+        if update.snapshot {
+            self.needs_resync = false;
+        }
+        ApplyOutcome::Applied
+    }
+
+    /// Re-sync the book from the REST quote endpoint
+    ///
+    /// The quote carries no sequence number, so deltas up to the highest one
+    /// already received are skipped as stale afterwards.
+    pub async fn resync(&mut self, client: &LiveWebullClient) -> Result<()> {
+        let quote = client.get_quotes(&self.ticker_id).await?;
+        let depth = quote.depth.ok_or_else(|| {
+            WebullError::ApiError(format!("No depth data for ticker {}", self.ticker_id))
+        })?;
+        self.apply_snapshot(&depth, None);
+        Ok(())
+    }
+
+    /// Get the best N bid levels (highest price first)
+    pub fn best_bids(&self, n: usize) -> Vec<PriceLevel> {
+        self.bids
+            .iter()
+            .rev()
+            .take(n)
+            .map(|(k, v)| PriceLevel {
+                price: key_price(*k),
+                volume: *v,
+            })
+            .collect()
+    }
+
+    /// Get the best N ask levels (lowest price first)
+    pub fn best_asks(&self, n: usize) -> Vec<PriceLevel> {
+        self.asks
+            .iter()
+            .take(n)
+            .map(|(k, v)| PriceLevel {
+                price: key_price(*k),
+                volume: *v,
+            })
+            .collect()
+    }
+
+    /// Get the best bid price
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.keys().next_back().map(|k| key_price(*k))
+    }
+
+    /// Get the best ask price
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.keys().next().map(|k| key_price(*k))
+    }
+
+    /// Get the bid/ask spread
+    pub fn spread(&self) -> Option<f64> {
+        match (self.best_bid(), self.best_ask()) {
+            (Some(bid), Some(ask)) => Some(ask - bid),
+            _ => None,
+        }
+    }
+
+    fn set_level(side: &mut BTreeMap<i64, f64>, level: &PriceLevel) {
+        let key = price_key(level.price);
+        if level.volume <= 0.0 {
+            side.remove(&key);
+        } else {
+            side.insert(key, level.volume);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn level(price: f64, volume: f64) -> PriceLevel {
+        PriceLevel { price, volume }
+    }
+
+    #[test]
+    fn test_snapshot_and_best_levels() {
+        let mut book = OrderBook::new("913256135");
+        assert!(book.needs_resync());
+
+        let depth = Depth {
+            ntv_agg_bid_list: Some(vec![level(99.0, 100.0), level(99.5, 50.0)]),
+            ntv_agg_ask_list: Some(vec![level(100.5, 20.0), level(100.0, 10.0)]),
+        };
+        book.apply_snapshot(&depth, Some(10));
+
+        assert!(!book.needs_resync());
+        assert_eq!(book.best_bid(), Some(99.5));
+        assert_eq!(book.best_ask(), Some(100.0));
+        assert_eq!(book.spread(), Some(0.5));
+        assert_eq!(book.best_bids(1)[0].volume, 50.0);
+        assert_eq!(book.best_asks(2)[1].price, 100.5);
+    }
+
+    #[test]
+    fn test_delta_updates_and_gap_detection() {
+        let mut book = OrderBook::new("1");
+        book.apply_snapshot(
+            &Depth {
+                ntv_agg_bid_list: Some(vec![level(10.0, 5.0)]),
+                ntv_agg_ask_list: Some(vec![level(10.1, 5.0)]),
+            },
+            Some(1),
+        );
+
+        // Zero volume removes a level
+        let outcome = book.apply_update(&DepthUpdate {
+            sequence: Some(2),
+            bids: vec![level(10.0, 0.0), level(9.9, 3.0)],
+            asks: vec![],
+            snapshot: false,
+        });
+        assert_eq!(outcome, ApplyOutcome::Applied);
+        assert_eq!(book.best_bid(), Some(9.9));
+
+        // Replayed sequence is ignored
+        let outcome = book.apply_update(&DepthUpdate {
+            sequence: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(outcome, ApplyOutcome::Stale);
+
+        // Skipped sequence flags a re-sync
+        let outcome = book.apply_update(&DepthUpdate {
+            sequence: Some(5),
+            ..Default::default()
+        });
+        assert_eq!(
+            outcome,
+            ApplyOutcome::GapDetected {
+                expected: 3,
+                received: 5
+            }
+        );
+        assert!(book.needs_resync());
+    }
+
+    #[test]
+    fn test_resync_skips_stale_deltas() {
+        let mut book = OrderBook::new("1");
+        book.apply_snapshot(
+            &Depth {
+                ntv_agg_bid_list: Some(vec![level(10.0, 5.0)]),
+                ntv_agg_ask_list: Some(vec![level(10.1, 5.0)]),
+            },
+            Some(1),
+        );
+        let delta = |sequence: u64, price: f64| DepthUpdate {
+            sequence: Some(sequence),
+            bids: vec![level(price, 1.0)],
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            book.apply_update(&delta(4, 9.0)),
+            ApplyOutcome::GapDetected { .. }
+        ));
+        // Deltas are held back until the book is re-synced
+        assert_eq!(
+            book.apply_update(&delta(5, 9.1)),
+            ApplyOutcome::ResyncPending
+        );
+        assert_eq!(book.best_bid(), Some(10.0));
+
+        // What `resync` applies: a REST snapshot without a sequence
+        book.apply_snapshot(
+            &Depth {
+                ntv_agg_bid_list: Some(vec![level(9.5, 5.0)]),
+                ntv_agg_ask_list: Some(vec![level(9.6, 5.0)]),
+            },
+            None,
+        );
+        assert_eq!(book.apply_update(&delta(3, 9.2)), ApplyOutcome::Stale);
+        assert_eq!(book.apply_update(&delta(5, 9.3)), ApplyOutcome::Stale);
+        assert_eq!(book.best_bid(), Some(9.5));
+
+        assert_eq!(book.apply_update(&delta(6, 9.55)), ApplyOutcome::Applied);
+        assert_eq!(book.last_sequence(), Some(6));
+        assert_eq!(book.best_bid(), Some(9.55));
+        assert!(matches!(
+            book.apply_update(&delta(8, 9.4)),
+            ApplyOutcome::GapDetected { expected: 7, .. }
+        ));
+    }
+
+    #[test]
+    fn test_depth_update_from_payload() {
+        let payload = json!({
+            "seq": "42",
+            "depth": {
+                "ntvAggBidList": [{"price": "150.10", "volume": "300"}],
+                "ntvAggAskList": [{"price": "150.12", "volume": "100"}]
+            }
+        });
+
+        let update = DepthUpdate::from_payload(&payload).unwrap();
+        assert_eq!(update.sequence, Some(42));
+        assert_eq!(update.bids[0].price, 150.10);
+        assert_eq!(update.asks[0].volume, 100.0);
+        assert!(DepthUpdate::from_payload(&json!({"close": "1.0"})).is_none());
+    }
+}
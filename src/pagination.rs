@@ -0,0 +1,390 @@
+use crate::error::Result;
+use crate::models::{list_items, Bar, HistoryOrder, News};
+use futures::stream::{self, Stream};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::future::Future;
+
+/// Cursor state for walking order history pages backwards in time
+///
+/// Webull pages order history by creation time: each request returns orders created
+/// before `lastCreateTime0`, so the oldest order of one page seeds the next request.
+#[derive(Debug, Default)]
+pub(crate) struct HistoryPager {
+    pub(crate) before: Option<i64>,
+    pub(crate) buffer: VecDeque<HistoryOrder>,
+    pub(crate) done: bool,
+    page_size: usize,
+    seen: HashSet<String>,
+}
+
+impl HistoryPager {
+    pub(crate) fn new(page_size: i32) -> Self {
+        Self {
+            page_size: page_size.max(1) as usize,
+            ..Default::default()
+        }
+    }
+
+    /// Buffer a fetched page and advance the cursor
+    ///
+    /// The page is sized and dated from its raw orders, so one this client
+    /// can't parse (e.g. a trailing stop) doesn't make a full page look short.
+    pub(crate) fn absorb(&mut self, page: &Value) {
+        let raw = raw_orders(page);
+        let oldest = raw.iter().filter_map(|o| created_millis(o)).min();
+
+        // Orders sharing the boundary timestamp may be returned twice
+        for order in HistoryOrder::from_response(page) {
+            if self.seen.insert(order.order_id.clone()) {
+                self.buffer.push_back(order);
+            }
+        }
+
+        match oldest {
+            Some(ts) if raw.len() >= self.page_size && Some(ts) != self.before => {
+                self.before = Some(ts);
+            }
+            _ => self.done = true,
+        }
+    }
+
+    /// Stream every order, calling `fetch` with the cursor for each page as
+    /// the stream is consumed
+    ///
+    /// The stream ends after yielding the first error.
+    pub(crate) fn stream<'a, F, Fut>(
+        page_size: i32,
+        fetch: F,
+    ) -> impl Stream<Item = Result<HistoryOrder>> + Send + 'a
+    where
+        F: Fn(Option<i64>) -> Fut + Send + 'a,
+        Fut: Future<Output = Result<Value>> + Send + 'a,
+    {
+        stream::unfold(
+            (Self::new(page_size), fetch),
+            |(mut pager, fetch)| async move {
+                loop {
+                    if let Some(order) = pager.buffer.pop_front() {
+                        return Some((Ok(order), (pager, fetch)));
+                    }
+                    if pager.done {
+                        return None;
+                    }
+                    match fetch(pager.before).await {
+                        Ok(page) => pager.absorb(&page),
+                        Err(e) => {
+                            pager.done = true;
+                            return Some((Err(e), (pager, fetch)));
+                        }
+                    }
+                }
+            },
+        )
+    }
+}
+
+/// Every order in a history page, with live combo groups flattened
+fn raw_orders(page: &Value) -> Vec<&Value> {
+    list_items(page)
+        .iter()
+        .flat_map(|item| match item.get("orders").and_then(|v| v.as_array()) {
+            Some(inner) => inner.iter().collect(),
+            None => vec![item],
+        })
+        .collect()
+}
+
+/// When an order was created, in milliseconds
+fn created_millis(order: &Value) -> Option<i64> {
+    order
+        .get("createTime0")
+        .and_then(|v| v.as_i64())
+        .or_else(|| {
+            let text = order.get("createTime")?.as_str()?;
+            chrono::DateTime::parse_from_rfc3339(text)
+                .ok()
+                .map(|t| t.timestamp_millis())
+        })
+}
+
+/// Most bars the chart endpoint returns for one request
+pub(crate) const BAR_PAGE_SIZE: i32 = 1200;
+
+/// Cursor state for collecting headlines newer than a known ID
+///
+/// The news endpoint pages backwards: each request returns headlines older
+/// than `lastId`, so the oldest ID of one page seeds the next request. Paging
+/// stops at the first page that reaches `since`, or after `max_pages`.
+#[derive(Debug)]
+pub(crate) struct NewsPager {
+    since: Option<i64>,
+    page_size: usize,
+    pages_left: usize,
+    pub(crate) cursor: i64,
+    pub(crate) done: bool,
+    /// Whether paging stopped before reaching `since`
+    pub(crate) gap: bool,
+    news: BTreeMap<i64, News>,
+}
+
+impl NewsPager {
+    pub(crate) fn new(since: Option<i64>, page_size: i32, max_pages: usize) -> Self {
+        Self {
+            since,
+            page_size: page_size.max(1) as usize,
+            pages_left: max_pages.max(1),
+            cursor: 0,
+            done: false,
+            gap: false,
+            news: BTreeMap::new(),
+        }
+    }
+
+    /// Keep the headlines newer than `since` and move the cursor back
+    pub(crate) fn absorb(&mut self, page: Vec<News>) {
+        let raw_count = page.len();
+        let oldest = page.iter().map(|n| n.id).min();
+        let reached = match (self.since, oldest) {
+            (Some(since), Some(oldest)) => oldest <= since,
+            // Without a checkpoint one page is the whole answer
+            (None, _) => true,
+            (Some(_), None) => false,
+        };
+        for news in page {
+            if !matches!(self.since, Some(since) if news.id <= since) {
+                self.news.insert(news.id, news);
+            }
+        }
+
+        self.pages_left -= 1;
+        match oldest {
+            _ if reached || raw_count < self.page_size => self.done = true,
+            Some(oldest) if self.pages_left > 0 && (self.cursor == 0 || oldest < self.cursor) => {
+                self.cursor = oldest;
+            }
+            _ => {
+                self.done = true;
+                self.gap = true;
+            }
+        }
+    }
+
+    /// Collected headlines, newest first
+    pub(crate) fn into_news(self) -> Vec<News> {
+        self.news.into_values().rev().collect()
+    }
+}
+
+/// Cursor state for assembling bars over a time range
+///
+/// The chart endpoint returns up to `count` bars ending at `timestamp`, so the
+/// oldest bar of one page becomes the end of the next request.
+#[derive(Debug)]
+pub(crate) struct BarPager {
+    start: i64,
+    end: i64,
+    pub(crate) cursor: i64,
+    pub(crate) done: bool,
+    bars: BTreeMap<i64, Bar>,
+}
+
+impl BarPager {
+    pub(crate) fn new(start: i64, end: i64) -> Self {
+        Self {
+            start,
+            end,
+            cursor: end,
+            done: start > end,
+            bars: BTreeMap::new(),
+        }
+    }
+
+    /// Keep the bars inside the range and move the cursor to the oldest bar
+    pub(crate) fn absorb(&mut self, page: Vec<Bar>) {
+        let oldest = page.iter().map(|b| b.timestamp).min();
+
+        for bar in page {
+            if (self.start..=self.end).contains(&bar.timestamp) {
+                self.bars.insert(bar.timestamp, bar);
+            }
+        }
+
+        match oldest {
+            Some(ts) if ts > self.start && ts < self.cursor => self.cursor = ts,
+            _ => self.done = true,
+        }
+    }
+
+    /// Collected bars, oldest first
+    pub(crate) fn into_bars(self) -> Vec<Bar> {
+        self.bars.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn order(id: &str, created: i64) -> Value {
+        json!({
+            "orderId": id,
+            "action": "BUY",
+            "orderType": "MKT",
+            "status": "Filled",
+            "totalQuantity": "1",
+            "filledQuantity": "1",
+            "createTime0": created
+        })
+    }
+
+    #[test]
+    fn test_pager_advances_and_dedupes() {
+        let mut pager = HistoryPager::new(2);
+
+        pager.absorb(&json!([order("a", 3000), order("b", 2000)]));
+        assert_eq!(pager.before, Some(2000));
+        assert!(!pager.done);
+        assert_eq!(pager.buffer.len(), 2);
+
+        // "b" repeats at the boundary and is dropped
+        pager.absorb(&json!([order("b", 2000), order("c", 1000)]));
+        assert_eq!(pager.before, Some(1000));
+        assert_eq!(pager.buffer.len(), 3);
+
+        // A short page ends pagination
+        pager.absorb(&json!([order("d", 500)]));
+        assert!(pager.done);
+        assert_eq!(pager.buffer.len(), 4);
+    }
+
+    #[test]
+    fn test_pager_counts_orders_it_cannot_parse() {
+        let mut trailing = order("b", 2000);
+        trailing["orderType"] = json!("TRAILING_STOP");
+
+        let mut pager = HistoryPager::new(3);
+        pager.absorb(&json!({"data": [
+            order("a", 3000),
+            {"comboId": 7, "orders": [trailing, order("c", 1500)]}
+        ]}));
+        assert!(!pager.done);
+        assert_eq!(pager.before, Some(1500));
+        assert_eq!(pager.buffer.len(), 2);
+    }
+
+    #[test]
+    fn test_pager_stops_on_empty_page() {
+        let mut pager = HistoryPager::new(10);
+        pager.absorb(&json!([]));
+        assert!(pager.done);
+        assert!(pager.buffer.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_history_stream_follows_cursor_and_stops_on_error() {
+        use crate::error::WebullError;
+        use futures::{StreamExt, TryStreamExt};
+
+        let cursors = std::sync::Mutex::new(Vec::new());
+        let orders: Vec<HistoryOrder> = HistoryPager::stream(2, |before| {
+            cursors.lock().unwrap().push(before);
+            let page = match before {
+                None => json!([order("a", 3000), order("b", 2000)]),
+                _ => json!([order("c", 1000)]),
+            };
+            async move { Ok(page) }
+        })
+        .try_collect()
+        .await
+        .unwrap();
+        let ids: Vec<&str> = orders.iter().map(|o| o.order_id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        assert_eq!(*cursors.lock().unwrap(), vec![None, Some(2000)]);
+
+        let results: Vec<Result<HistoryOrder>> =
+            HistoryPager::stream(2, |_| async { Err(WebullError::SessionExpired) })
+                .collect()
+                .await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+
+    fn bars(timestamps: &[i64]) -> Vec<Bar> {
+        timestamps
+            .iter()
+            .map(|&timestamp| Bar {
+                timestamp,
+                open: 1.0,
+                high: 1.0,
+                low: 1.0,
+                close: 1.0,
+                volume: 1.0,
+                vwap: 1.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_news_pager_stops_at_checkpoint() {
+        let page = |ids: &[i64]| -> Vec<News> {
+            ids.iter()
+                .map(|id| {
+                    serde_json::from_value(json!({
+                        "id": id, "title": "Headline", "newsTime": "2024-06-01T12:00:00.000+0000"
+                    }))
+                    .unwrap()
+                })
+                .collect()
+        };
+
+        let mut pager = NewsPager::new(Some(100), 2, 5);
+        pager.absorb(page(&[105, 104]));
+        assert!(!pager.done);
+        assert_eq!(pager.cursor, 104);
+        pager.absorb(page(&[103, 100]));
+        assert!(pager.done && !pager.gap);
+        let ids: Vec<i64> = pager.into_news().iter().map(|n| n.id).collect();
+        assert_eq!(ids, vec![105, 104, 103]);
+
+        // Out of pages before reaching the checkpoint
+        let mut pager = NewsPager::new(Some(1), 2, 1);
+        pager.absorb(page(&[9, 8]));
+        assert!(pager.done && pager.gap);
+
+        let mut pager = NewsPager::new(None, 2, 5);
+        pager.absorb(page(&[9, 8]));
+        assert!(pager.done && !pager.gap);
+    }
+
+    #[test]
+    fn test_bar_pager_walks_back_to_start() {
+        let mut pager = BarPager::new(150, 1000);
+
+        // Newest first, overlapping at the page boundary
+        pager.absorb(bars(&[1000, 900, 800]));
+        assert_eq!(pager.cursor, 800);
+        pager.absorb(bars(&[800, 700, 600]));
+        assert_eq!(pager.cursor, 600);
+        assert!(!pager.done);
+
+        // Reaching the start ends pagination and drops out-of-range bars
+        pager.absorb(bars(&[600, 200, 100]));
+        assert!(pager.done);
+
+        let timestamps: Vec<i64> = pager.into_bars().iter().map(|b| b.timestamp).collect();
+        assert_eq!(timestamps, vec![200, 600, 700, 800, 900, 1000]);
+    }
+
+    #[test]
+    fn test_bar_pager_stops_without_progress() {
+        let mut pager = BarPager::new(0, 1000);
+        pager.absorb(bars(&[1000]));
+        assert!(pager.done);
+
+        let mut pager = BarPager::new(0, 1000);
+        pager.absorb(Vec::new());
+        assert!(pager.done);
+    }
+}
@@ -1,9 +1,19 @@
 use crate::{
+    chains::OptionChainDownload,
+    config::ClientConfig,
+    delta::{NewsDelta, QuoteDelta},
     error::{Result, WebullError},
+    format::AssetClass,
     live_client::LiveWebullClient,
     models::{AccountDetail, AccountMember, *},
+    pagination::HistoryPager,
+    quote_history::QuoteRecorder,
+    utils::{check_success, read_json},
 };
+use futures::future;
+use futures::stream::{Stream, TryStreamExt};
 use serde_json::Value;
+use std::collections::BTreeMap;
 
 /// Paper trading client
 #[derive(Debug, Clone)]
@@ -21,6 +31,14 @@ impl PaperWebullClient {
         })
     }
 
+    /// Create a new paper trading client with the given configuration
+    pub fn with_config(region_code: Option<i32>, config: ClientConfig) -> Result<Self> {
+        Ok(Self {
+            base_client: LiveWebullClient::with_config(region_code, config)?,
+            paper_account_id: None,
+        })
+    }
+
     /// Login (delegates to base client)
     pub async fn login(
         &mut self,
@@ -30,7 +48,7 @@ impl PaperWebullClient {
         mfa: Option<&str>,
         question_id: Option<&str>,
         question_answer: Option<&str>,
-    ) -> Result<LoginResponse> {
+    ) -> Result<LoginOutcome> {
         let result = self
             .base_client
             .login(
@@ -42,7 +60,9 @@ impl PaperWebullClient {
                 question_answer,
             )
             .await?;
-        self.get_paper_account_id().await?;
+        if result.is_success() {
+            self.get_paper_account_id().await?;
+        }
         Ok(result)
     }
 
@@ -52,45 +72,31 @@ impl PaperWebullClient {
 
         let response = self
             .base_client
-            .client
-            .get(&self.base_client.endpoints.paper_account_id())
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.base_client.timeout))
-            .send()
+            .execute(
+                self.base_client
+                    .client
+                    .get(&self.base_client.endpoints.paper_account_id())
+                    .headers(headers),
+            )
             .await?;
 
-        let result: Value = response.json().await?;
+        let result: Value = read_json(response).await?;
 
-        // The response can be either an array directly or wrapped in "data"
-        let accounts = if result.is_array() {
-            result.as_array()
-        } else {
-            result.get("data").and_then(|v| v.as_array())
+        // Try id as string or number (matches Python implementation)
+        let paper_id = match list_items(&result).first().and_then(|a| a.get("id")) {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Number(n)) => n.to_string(),
+            _ => return Err(WebullError::AccountNotFound),
         };
-
-        if let Some(accounts_array) = accounts {
-            if let Some(first_account) = accounts_array.first() {
-                // Try id as string or number (matches Python implementation)
-                if let Some(paper_id) = first_account.get("id") {
-                    let paper_id_str = match paper_id {
-                        Value::String(s) => s.clone(),
-                        Value::Number(n) => n.to_string(),
-                        _ => return Err(WebullError::AccountNotFound),
-                    };
-                    self.paper_account_id = Some(paper_id_str.clone());
-                    return Ok(paper_id_str);
-                }
-            }
-        }
-
-        Err(WebullError::AccountNotFound)
+        self.paper_account_id = Some(paper_id.clone());
+        Ok(paper_id)
     }
 
     /// Login using builder pattern
     pub async fn login_with(
         &mut self,
         builder: crate::models::LoginRequestBuilder,
-    ) -> Result<LoginResponse> {
+    ) -> Result<LoginOutcome> {
         let (username, password, device_name, mfa, question_id, question_answer) = builder
             .build()
             .map_err(|e| WebullError::InvalidRequest(e))?;
@@ -116,14 +122,15 @@ impl PaperWebullClient {
 
         let response = self
             .base_client
-            .client
-            .get(&self.base_client.endpoints.paper_account(paper_account_id))
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.base_client.timeout))
-            .send()
+            .execute(
+                self.base_client
+                    .client
+                    .get(&self.base_client.endpoints.paper_account(paper_account_id))
+                    .headers(headers),
+            )
             .await?;
 
-        let result: Value = response.json().await?;
+        let result: Value = read_json(response).await?;
         // Debug: Remove or comment out in production
         // println!("Paper account raw response: {:?}", result);
 
@@ -200,46 +207,18 @@ impl PaperWebullClient {
             }
         }
 
-        // Process accountMembers to extract key financial values
-        if let Some(ref members) = account.account_members {
-            for member in members {
-                match member.key.as_str() {
-                    "totalMarketValue" => {
-                        account.total_market_value = member.value.parse::<f64>().ok();
-                    }
-                    "usableCash" => {
-                        // Paper trading uses "usableCash" instead of "cashBalance"
-                        let usable_cash = member.value.parse::<f64>().ok();
-                        account.cash_balance = usable_cash;
-                        account.buying_power = usable_cash; // Use usable cash as buying power
-                    }
-                    "dayProfitLoss" => {
-                        // We could add a day_profit_loss field if needed
-                        // For now, this is informational only
-                    }
-                    "cashBalance" => {
-                        // Fall back to cashBalance if available
-                        if account.cash_balance.is_none() {
-                            account.cash_balance = member.value.parse::<f64>().ok();
-                        }
-                    }
-                    "dayBuyingPower" | "overnightBuyingPower" => {
-                        // Use dayBuyingPower as the primary buying power if not already set
-                        if member.key == "dayBuyingPower" && account.buying_power.is_none() {
-                            account.buying_power = member.value.parse::<f64>().ok();
-                        }
-                    }
-                    "unsettledFunds" => {
-                        account.unsettled_funds = member.value.parse::<f64>().ok();
-                    }
-                    _ => {}
-                }
-            }
-
-            // If total_cash wasn't in the members, try to use cash_balance
-            if account.total_cash.is_none() && account.cash_balance.is_some() {
-                account.total_cash = account.cash_balance;
-            }
+        account.compute_member_fields();
+        // Paper accounts report spendable cash as "usableCash"
+        let usable_cash = account
+            .account_members
+            .iter()
+            .flatten()
+            .find(|m| m.key == "usableCash")
+            .and_then(|m| m.value.parse::<f64>().ok());
+        if usable_cash.is_some() {
+            account.cash_balance = usable_cash;
+            account.buying_power = usable_cash;
+            account.total_cash = account.total_cash.or(usable_cash);
         }
 
         // Extract positions array - paper trading returns this directly in the response
@@ -270,44 +249,40 @@ impl PaperWebullClient {
             .as_ref()
             .ok_or(WebullError::AccountNotFound)?;
 
-        // Paper orders need trade token and time headers
-        let headers = self.base_client.build_req_headers(true, true, true);
-
-        // Create a modified order with serialId if not present and handle market orders
-        let mut order_data = serde_json::to_value(order)?;
-
-        // Add serialId if not present
-        if order_data.get("serialId").is_none() {
-            let uuid = uuid::Uuid::new_v4().to_string();
-            order_data["serialId"] = serde_json::Value::String(uuid);
+        validate_expiration(
+            &order.time_in_force,
+            order.expire_date,
+            crate::market::today(),
+        )
+        .map_err(WebullError::InvalidRequest)?;
+        // The paper account's margin settings aren't reported, so only the
+        // ticker is checked
+        if order.short_sale {
+            self.base_client.check_shortable(order).await?;
         }
 
-        // For market orders, force outsideRegularTradingHour to false
-        if matches!(order.order_type, OrderType::Market) {
-            order_data["outsideRegularTradingHour"] = serde_json::Value::Bool(false);
-        }
+        // Paper orders need trade token and time headers
+        let headers = self.base_client.build_req_headers(true, true, true);
 
-        // Add lmtPrice for limit orders
-        if let Some(limit_price) = order.limit_price {
-            order_data["lmtPrice"] = serde_json::Value::from(limit_price);
-        }
+        let order_data = Self::order_payload(order)?;
 
         let response = self
             .base_client
-            .client
-            .post(
-                &self
-                    .base_client
-                    .endpoints
-                    .paper_place_order(paper_account_id, &order.ticker_id.to_string()),
+            .execute(
+                self.base_client
+                    .client
+                    .post(
+                        &self
+                            .base_client
+                            .endpoints
+                            .paper_place_order(paper_account_id, &order.ticker_id.to_string()),
+                    )
+                    .headers(headers)
+                    .json(&order_data),
             )
-            .headers(headers)
-            .json(&order_data)
-            .timeout(std::time::Duration::from_secs(self.base_client.timeout))
-            .send()
             .await?;
 
-        let result: Value = response.json().await?;
+        let result: Value = read_json(response).await?;
 
         // Check for orderId directly in result or in data field
         let order_id = result
@@ -329,6 +304,30 @@ impl PaperWebullClient {
         }
     }
 
+    /// Build the JSON body sent for a paper order
+    pub(crate) fn order_payload(order: &PlaceOrderRequest) -> Result<Value> {
+        // Create a modified order with serialId if not present and handle market orders
+        let mut order_data = serde_json::to_value(order)?;
+
+        // Add serialId if not present
+        if order_data.get("serialId").is_none() {
+            let uuid = uuid::Uuid::new_v4().to_string();
+            order_data["serialId"] = serde_json::Value::String(uuid);
+        }
+
+        // For market orders, force outsideRegularTradingHour to false
+        if matches!(order.order_type, OrderType::Market) {
+            order_data["outsideRegularTradingHour"] = serde_json::Value::Bool(false);
+        }
+
+        // Add lmtPrice for limit orders
+        if let Some(limit_price) = order.limit_price {
+            order_data["lmtPrice"] = serde_json::Value::from(limit_price);
+        }
+
+        Ok(order_data)
+    }
+
     /// Cancel paper order
     pub async fn cancel_order(&self, order_id: &str) -> Result<bool> {
         let paper_account_id = self
@@ -340,171 +339,67 @@ impl PaperWebullClient {
 
         let response = self
             .base_client
-            .client
-            .post(
-                &self
-                    .base_client
-                    .endpoints
-                    .paper_cancel_order(paper_account_id, order_id),
+            .execute(
+                self.base_client
+                    .client
+                    .post(
+                        &self
+                            .base_client
+                            .endpoints
+                            .paper_cancel_order(paper_account_id, order_id),
+                    )
+                    .headers(headers),
             )
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.base_client.timeout))
-            .send()
             .await?;
 
-        Ok(response.status().is_success())
+        check_success(response).await
     }
 
     /// Get paper orders (current open orders)
     pub async fn get_orders(&self, page_size: Option<i32>) -> Result<Vec<Order>> {
         // Paper trading doesn't return openOrders in account data like live trading does
-        // Instead, we need to get all orders and filter for "Working" status
-        let history = self
-            .get_history_orders("All", page_size.unwrap_or(100))
+        // Instead, we page through the order history and keep the open orders
+        let orders: Vec<HistoryOrder> = self
+            .history_orders_paginated("Working", page_size.unwrap_or(100))
+            .try_collect()
             .await?;
 
-        // Parse the response and filter for Working orders
-        if let Some(orders_array) = history.as_array() {
-            let mut working_orders = Vec::new();
-
-            for order_val in orders_array {
-                // Check if status is "Working"
-                if let Some(status) = order_val.get("status").and_then(|s| s.as_str()) {
-                    if status == "Working" {
-                        // Try to parse this into our Order struct
-                        // For now, we'll need to manually construct it since the format is different
-                        if let Ok(order) = self.parse_paper_order(order_val) {
-                            working_orders.push(order);
-                        }
-                    }
-                }
-            }
-            Ok(working_orders)
-        } else {
-            Ok(Vec::new())
-        }
+        Ok(orders
+            .into_iter()
+            .filter(|order| order.status.is_open())
+            .map(Order::from)
+            .collect())
     }
 
-    /// Helper to parse paper order from JSON
-    fn parse_paper_order(&self, order_val: &Value) -> Result<Order> {
-        use chrono::{DateTime, Utc};
-
-        let order_id = order_val
-            .get("orderId")
-            .and_then(|v| v.as_i64())
-            .map(|id| id.to_string())
-            .ok_or(WebullError::ParseError("Missing orderId".to_string()))?;
-
-        let ticker_data = order_val
-            .get("ticker")
-            .ok_or(WebullError::ParseError("Missing ticker".to_string()))?;
-
-        let ticker = serde_json::from_value::<Ticker>(ticker_data.clone())?;
-
-        let action = match order_val.get("action").and_then(|v| v.as_str()) {
-            Some("BUY") => OrderAction::Buy,
-            Some("SELL") => OrderAction::Sell,
-            _ => return Err(WebullError::ParseError("Invalid action".to_string())),
-        };
-
-        let order_type = match order_val.get("orderType").and_then(|v| v.as_str()) {
-            Some("MKT") => OrderType::Market,
-            Some("LMT") => OrderType::Limit,
-            Some("STP") => OrderType::Stop,
-            Some("STP LMT") => OrderType::StopLimit,
-            _ => return Err(WebullError::ParseError("Invalid order type".to_string())),
-        };
-
-        let status = match order_val.get("status").and_then(|v| v.as_str()) {
-            Some("Working") => OrderStatus::Working,
-            Some("Filled") => OrderStatus::Filled,
-            Some("Canceled") | Some("Cancelled") => OrderStatus::Cancelled,
-            Some("PartiallyFilled") | Some("Partial Filled") => OrderStatus::PartialFilled,
-            Some("Pending") => OrderStatus::Pending,
-            Some("Failed") => OrderStatus::Failed,
-            _ => OrderStatus::Working,
-        };
-
-        let time_in_force = match order_val.get("timeInForce").and_then(|v| v.as_str()) {
-            Some("DAY") => TimeInForce::Day,
-            Some("GTC") => TimeInForce::GoodTillCancel,
-            Some("IOC") => TimeInForce::ImmediateOrCancel,
-            Some("FOK") => TimeInForce::FillOrKill,
-            _ => TimeInForce::Day,
-        };
-
-        let quantity = order_val
-            .get("totalQuantity")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<f64>().ok())
-            .unwrap_or(0.0);
-
-        let filled_quantity = order_val
-            .get("filledQuantity")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<f64>().ok())
-            .unwrap_or(0.0);
-
-        let limit_price = order_val
-            .get("lmtPrice")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<f64>().ok());
-
-        let stop_price = order_val
-            .get("auxPrice")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<f64>().ok());
-
-        let avg_fill_price = order_val
-            .get("avgFilledPrice")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<f64>().ok());
-
-        // Parse placed time
-        let placed_time =
-            if let Some(timestamp) = order_val.get("createTime0").and_then(|v| v.as_i64()) {
-                DateTime::from_timestamp_millis(timestamp).unwrap_or(Utc::now())
-            } else {
-                Utc::now()
-            };
-
-        let filled_time =
-            if let Some(timestamp) = order_val.get("filledTime0").and_then(|v| v.as_i64()) {
-                Some(DateTime::from_timestamp_millis(timestamp).unwrap_or(Utc::now()))
-            } else {
-                None
-            };
+    /// Get historical paper orders
+    pub async fn get_history_orders(&self, status: &str, count: i32) -> Result<Vec<HistoryOrder>> {
+        let result = self.get_history_orders_raw(status, count).await?;
+        Ok(HistoryOrder::from_response(&result))
+    }
 
-        let outside_regular_trading_hour = order_val
-            .get("outsideRegularTradingHour")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+    /// Get historical paper orders as raw JSON
+    pub async fn get_history_orders_raw(&self, status: &str, count: i32) -> Result<Value> {
+        self.get_history_orders_page(status, count, None).await
+    }
 
-        Ok(Order {
-            order_id,
-            combo_id: order_val
-                .get("comboId")
-                .and_then(|v| v.as_str())
-                .map(String::from),
-            ticker: Some(ticker),
-            action,
-            order_type,
-            status,
-            time_in_force,
-            quantity,
-            filled_quantity,
-            avg_fill_price,
-            limit_price,
-            stop_price,
-            create_time: None,
-            placed_time: Some(placed_time.to_rfc3339()),
-            filled_time: filled_time.map(|t| t.to_rfc3339()),
-            outside_regular_trading_hour,
+    /// Stream the full paper order history, following pagination transparently
+    pub fn history_orders_paginated<'a>(
+        &'a self,
+        status: &'a str,
+        page_size: i32,
+    ) -> impl Stream<Item = Result<HistoryOrder>> + Send + 'a {
+        HistoryPager::stream(page_size, move |before| {
+            self.get_history_orders_page(status, page_size, before)
         })
     }
 
-    /// Get historical paper orders
-    pub async fn get_history_orders(&self, status: &str, count: i32) -> Result<Value> {
+    /// Fetch one page of paper order history created before `before` (milliseconds)
+    async fn get_history_orders_page(
+        &self,
+        status: &str,
+        count: i32,
+        before: Option<i64>,
+    ) -> Result<Value> {
         let paper_account_id = self
             .paper_account_id
             .as_ref()
@@ -512,24 +407,19 @@ impl PaperWebullClient {
 
         let headers = self.base_client.build_req_headers(true, false, true);
 
-        let url = format!(
-            "{}{}",
-            self.base_client
-                .endpoints
-                .paper_orders(paper_account_id, count),
-            status
+        let url = self.base_client.endpoints.paper_orders_with_status(
+            paper_account_id,
+            count,
+            status,
+            before,
         );
 
         let response = self
             .base_client
-            .client
-            .get(&url)
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(self.base_client.timeout))
-            .send()
+            .execute(self.base_client.client.get(&url).headers(headers))
             .await?;
 
-        Ok(response.json().await?)
+        read_json(response).await
     }
 
     /// Delegate other methods to base client
@@ -537,10 +427,48 @@ impl PaperWebullClient {
         self.base_client.get_quotes(ticker_id).await
     }
 
+    pub fn record_quotes(
+        &self,
+        ticker_ids: &[&str],
+        dir: impl Into<std::path::PathBuf>,
+    ) -> QuoteRecorder<'_> {
+        self.base_client.record_quotes(ticker_ids, dir)
+    }
+
+    pub async fn get_quote_extended(&self, ticker_id: &str) -> Result<Quote> {
+        self.base_client.get_quote_extended(ticker_id).await
+    }
+
+    pub async fn get_quote_delta(&self, ticker_id: &str, since: Option<i64>) -> Result<QuoteDelta> {
+        self.base_client.get_quote_delta(ticker_id, since).await
+    }
+
+    pub async fn get_news_delta(
+        &self,
+        ticker_id: &str,
+        since: Option<i64>,
+        count: i32,
+    ) -> Result<NewsDelta> {
+        self.base_client
+            .get_news_delta(ticker_id, since, count)
+            .await
+    }
+
+    pub async fn get_bars_since(
+        &self,
+        ticker_id: &str,
+        interval: BarInterval,
+        since: i64,
+    ) -> Result<Vec<Bar>> {
+        self.base_client
+            .get_bars_since(ticker_id, interval, since)
+            .await
+    }
+
     pub async fn get_bars(
         &self,
         ticker_id: &str,
-        interval: &str,
+        interval: BarInterval,
         count: i32,
         timestamp: Option<i64>,
     ) -> Result<Vec<Bar>> {
@@ -549,18 +477,115 @@ impl PaperWebullClient {
             .await
     }
 
+    pub async fn get_bars_range(
+        &self,
+        ticker_id: &str,
+        interval: BarInterval,
+        start: i64,
+        end: i64,
+    ) -> Result<Vec<Bar>> {
+        self.base_client
+            .get_bars_range(ticker_id, interval, start, end)
+            .await
+    }
+
     pub async fn find_ticker(&self, keyword: &str) -> Result<Vec<Ticker>> {
         self.base_client.find_ticker(keyword).await
     }
 
+    pub async fn find_ticker_with(
+        &self,
+        keyword: &str,
+        filter: &TickerFilter,
+    ) -> Result<Vec<Ticker>> {
+        self.base_client.find_ticker_with(keyword, filter).await
+    }
+
+    pub async fn find_ticker_exact(&self, symbol: &str) -> Result<Ticker> {
+        self.base_client.find_ticker_exact(symbol).await
+    }
+
+    pub async fn find_ticker_by_isin(&self, isin: &str) -> Result<Vec<Ticker>> {
+        self.base_client.find_ticker_by_isin(isin).await
+    }
+
+    pub async fn find_ticker_by_cusip(&self, cusip: &str) -> Result<Vec<Ticker>> {
+        self.base_client.find_ticker_by_cusip(cusip).await
+    }
+
     pub async fn get_news(&self, ticker: &str, last_id: i64, count: i32) -> Result<Vec<News>> {
         self.base_client.get_news(ticker, last_id, count).await
     }
 
+    pub async fn get_ticker_news(
+        &self,
+        ticker_id: &str,
+        last_id: i64,
+        count: i32,
+    ) -> Result<Vec<News>> {
+        self.base_client
+            .get_ticker_news(ticker_id, last_id, count)
+            .await
+    }
+
     pub async fn get_fundamentals(&self, ticker: &str) -> Result<Fundamental> {
         self.base_client.get_fundamentals(ticker).await
     }
 
+    pub async fn get_ticker_earnings(&self, ticker_id: &str) -> Result<Vec<EarningsEvent>> {
+        self.base_client.get_ticker_earnings(ticker_id).await
+    }
+
+    pub async fn get_financials(
+        &self,
+        ticker_id: &str,
+        statement: StatementType,
+        period: ReportPeriod,
+    ) -> Result<FinancialStatement> {
+        self.base_client
+            .get_financials(ticker_id, statement, period)
+            .await
+    }
+
+    pub async fn get_earnings_calendar(
+        &self,
+        start_date: chrono::NaiveDate,
+        end_date: chrono::NaiveDate,
+    ) -> Result<Vec<EarningsEvent>> {
+        self.base_client
+            .get_earnings_calendar(start_date, end_date)
+            .await
+    }
+
+    pub async fn get_ticker_earnings_whisper(
+        &self,
+        ticker_id: &str,
+    ) -> Result<Option<EarningsEvent>> {
+        self.base_client
+            .get_ticker_earnings_whisper(ticker_id)
+            .await
+    }
+
+    pub async fn get_accounts(&mut self) -> Result<Vec<LinkedAccount>> {
+        self.base_client.get_accounts().await
+    }
+
+    pub async fn get_managed_portfolio(&self, account_id: &str) -> Result<ManagedPortfolio> {
+        self.base_client.get_managed_portfolio(account_id).await
+    }
+
+    pub async fn get_fx_rate(&self, from: &str, to: &str, date: chrono::NaiveDate) -> Result<f64> {
+        self.base_client.get_fx_rate(from, to, date).await
+    }
+
+    pub fn download_option_chains(
+        &self,
+        symbols: &[&str],
+        dte_range: std::ops::RangeInclusive<i64>,
+    ) -> OptionChainDownload<'_> {
+        self.base_client.download_option_chains(symbols, dte_range)
+    }
+
     pub async fn logout(&mut self) -> Result<bool> {
         self.base_client.logout().await
     }
@@ -569,6 +594,11 @@ impl PaperWebullClient {
         self.base_client.get_trade_token(password).await
     }
 
+    /// Check if this is a paper trading client
+    pub fn is_paper(&self) -> bool {
+        true
+    }
+
     pub fn get_did(&self) -> &str {
         self.base_client.get_did()
     }
@@ -585,4 +615,96 @@ impl PaperWebullClient {
         // Return positions from account details, defaulting to empty vec if None
         Ok(account.positions.unwrap_or_default())
     }
+
+    /// Cancel every working paper order, or only those for `ticker_id`
+    ///
+    /// Cancellations are sent concurrently; each order's outcome is returned
+    /// keyed by order ID.
+    pub async fn cancel_all_orders(
+        &self,
+        ticker_id: Option<i64>,
+    ) -> Result<BTreeMap<String, Result<bool>>> {
+        let orders = self.get_orders(None).await?;
+        let cancels = orders
+            .into_iter()
+            .filter(|order| ticker_id.is_none_or(|id| order.ticker_id() == Some(id)))
+            .map(|order| async move {
+                let result = self.cancel_order(&order.order_id).await;
+                (order.order_id, result)
+            });
+        Ok(future::join_all(cancels).await.into_iter().collect())
+    }
+
+    /// Return the paper account to a clean state between test runs
+    ///
+    /// Cancels every open order, however far back in the history, and, when
+    /// `flatten` is set, closes every stock position with a market order.
+    /// Market orders only fill during regular trading hours. Option and
+    /// crypto positions can't be closed with a stock order and are left open.
+    pub async fn reset_day(&self, flatten: bool) -> Result<DayReset> {
+        let mut cancelled = Vec::new();
+        let mut failed = Vec::new();
+        for (order_id, result) in self.cancel_all_orders(None).await? {
+            match result {
+                Ok(true) => cancelled.push(order_id),
+                Ok(false) => failed.push(order_id),
+                Err(e) => failed.push(format!("{} ({})", order_id, e)),
+            }
+        }
+        if !failed.is_empty() {
+            return Err(WebullError::ApiError(format!(
+                "Failed to cancel paper orders: {}",
+                failed.join(", ")
+            )));
+        }
+
+        let mut reset = DayReset {
+            cancelled_orders: cancelled,
+            ..Default::default()
+        };
+        if !flatten {
+            return Ok(reset);
+        }
+
+        for position in self.get_positions().await? {
+            let ticker_id = match &position.ticker {
+                Some(ticker) if position.quantity != 0.0 => ticker.ticker_id,
+                _ => continue,
+            };
+            if AssetClass::from_asset_type(position.asset_type.as_deref()) != AssetClass::Equity {
+                reset.skipped_positions.push(ticker_id);
+                continue;
+            }
+            let action = if position.quantity > 0.0 {
+                OrderAction::Sell
+            } else {
+                OrderAction::Buy
+            };
+            let order = PlaceOrderRequest::market()
+                .ticker_id(ticker_id)
+                .action(action)
+                .quantity(position.quantity.abs())
+                .build()
+                .map_err(WebullError::InvalidRequest)?;
+            reset.closing_orders.push(self.place_order(&order).await?);
+        }
+        Ok(reset)
+    }
+
+    /// Check session health, reporting the paper account ID
+    pub async fn health_check(&self) -> HealthStatus {
+        let mut status = self.base_client.health_check().await;
+        status.account_id = self.paper_account_id.clone();
+        status
+    }
+}
+
+/// Orders affected by `PaperWebullClient::reset_day`
+#[derive(Debug, Clone, Default)]
+pub struct DayReset {
+    pub cancelled_orders: Vec<String>,
+    /// Market orders placed to close positions
+    pub closing_orders: Vec<String>,
+    /// Ticker IDs of option and crypto positions left open
+    pub skipped_positions: Vec<i64>,
 }
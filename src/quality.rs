@@ -0,0 +1,268 @@
+// Sanity checks on received quotes so strategies can pause on bad data
+
+use crate::market::MarketCalendar;
+use crate::models::Quote;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use tokio::sync::broadcast;
+
+/// Problem found in a quote
+#[derive(Debug, Clone, PartialEq)]
+pub enum QuoteAnomaly {
+    /// Last trade is older than the validator's maximum age
+    Stale { age: chrono::Duration },
+    /// Last price is zero or negative
+    ZeroPrice,
+    /// Bid is above the ask
+    CrossedMarket { bid: f64, ask: f64 },
+}
+
+/// Warning published when a quote fails validation
+#[derive(Debug, Clone)]
+pub struct DataQualityWarning {
+    pub ticker_id: Option<String>,
+    pub anomaly: QuoteAnomaly,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Flags stale, zero and crossed quotes and publishes warnings
+///
+/// Staleness is judged against the regular session: outside it, a quote is
+/// stale only if its last trade came more than the maximum age before the
+/// last close, so quiet evenings and weekends don't flag every ticker.
+/// Quotes without a trade time are never reported stale. Subscribers that
+/// fall behind lose the oldest warnings rather than blocking quote handling.
+#[derive(Debug)]
+pub struct QuoteValidator {
+    max_age: chrono::Duration,
+    calendar: MarketCalendar,
+    sender: broadcast::Sender<DataQualityWarning>,
+}
+
+impl QuoteValidator {
+    /// Treat quotes whose last trade is older than `max_age` as stale
+    pub fn new(max_age: std::time::Duration) -> Self {
+        let (sender, _) = broadcast::channel(256);
+        Self {
+            max_age: chrono::Duration::from_std(max_age).unwrap_or(chrono::Duration::MAX),
+            calendar: MarketCalendar::new(),
+            sender,
+        }
+    }
+
+    /// Judge sessions with `calendar`, e.g. one with extra closures added
+    pub fn with_calendar(mut self, calendar: MarketCalendar) -> Self {
+        self.calendar = calendar;
+        self
+    }
+
+    /// Receive every warning published from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<DataQualityWarning> {
+        self.sender.subscribe()
+    }
+
+    /// Anomalies in a quote, without publishing them
+    pub fn check(&self, quote: &Quote) -> Vec<QuoteAnomaly> {
+        let trade_time = quote.trade_time.as_deref().and_then(parse_trade_time);
+        self.check_fields(quote.close, quote.bid, quote.ask, trade_time, Utc::now())
+    }
+
+    fn check_fields(
+        &self,
+        price: f64,
+        bid: Option<f64>,
+        ask: Option<f64>,
+        trade_time: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Vec<QuoteAnomaly> {
+        let mut anomalies = Vec::new();
+        if let Some(traded) = trade_time {
+            let reference = if self.calendar.is_open_at(now) {
+                now
+            } else {
+                self.calendar.last_close_before(now)
+            };
+            if reference - traded > self.max_age {
+                anomalies.push(QuoteAnomaly::Stale { age: now - traded });
+            }
+        }
+        if price <= 0.0 {
+            anomalies.push(QuoteAnomaly::ZeroPrice);
+        }
+        // A zero side just means no quote, not a crossed market
+        if let (Some(bid), Some(ask)) = (bid, ask) {
+            if bid > 0.0 && ask > 0.0 && bid > ask {
+                anomalies.push(QuoteAnomaly::CrossedMarket { bid, ask });
+            }
+        }
+        anomalies
+    }
+
+    /// Check a quote and publish a warning for each anomaly
+    ///
+    /// Returns `true` if the quote is clean.
+    pub fn validate(&self, quote: &Quote) -> bool {
+        let anomalies = self.check(quote);
+        self.publish(quote.ticker_id.clone(), anomalies)
+    }
+
+    /// Check a push message from the price stream (see `StreamConn::set_price_callback`)
+    pub fn validate_push(&self, topic: &Value, data: &Value) -> bool {
+        let number = |keys: &[&str]| {
+            keys.iter().find_map(|key| match data.get(*key) {
+                Some(Value::String(s)) => s.parse::<f64>().ok(),
+                Some(Value::Number(n)) => n.as_f64(),
+                _ => None,
+            })
+        };
+        let price = match number(&["price", "close", "pPrice"]) {
+            Some(price) => price,
+            // Depth-only or status pushes carry no price to check
+            None => return true,
+        };
+        let trade_time = match data.get("tradeTime") {
+            Some(Value::String(s)) => parse_trade_time(s),
+            Some(Value::Number(n)) => n.as_i64().and_then(DateTime::from_timestamp_millis),
+            _ => None,
+        };
+        let ticker_id = match topic.get("tickerId") {
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        };
+
+        let anomalies = self.check_fields(
+            price,
+            number(&["bid", "bidPrice"]),
+            number(&["ask", "askPrice"]),
+            trade_time,
+            Utc::now(),
+        );
+        self.publish(ticker_id, anomalies)
+    }
+
+    fn publish(&self, ticker_id: Option<String>, anomalies: Vec<QuoteAnomaly>) -> bool {
+        let clean = anomalies.is_empty();
+        let detected_at = Utc::now();
+        for anomaly in anomalies {
+            log::warn!("Quote anomaly for {:?}: {:?}", ticker_id, anomaly);
+            // No subscribers is fine
+            let _ = self.sender.send(DataQualityWarning {
+                ticker_id: ticker_id.clone(),
+                anomaly,
+                detected_at,
+            });
+        }
+        clean
+    }
+}
+
+/// Parse a quote trade time in Webull's format, RFC 3339 or epoch milliseconds
+fn parse_trade_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z")
+        .or_else(|_| DateTime::parse_from_rfc3339(value))
+        .map(|t| t.with_timezone(&Utc))
+        .ok()
+        .or_else(|| value.parse().ok().and_then(DateTime::from_timestamp_millis))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn utc(d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 6, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_flags_stale_zero_and_crossed() {
+        let validator = QuoteValidator::new(Duration::from_secs(60));
+        // Tuesday 11:00 EDT
+        let now = utc(4, 15, 0);
+
+        assert!(validator
+            .check_fields(150.0, Some(149.9), Some(150.1), Some(now), now)
+            .is_empty());
+
+        let anomalies = validator.check_fields(
+            0.0,
+            Some(150.2),
+            Some(150.1),
+            Some(now - chrono::Duration::minutes(5)),
+            now,
+        );
+        assert_eq!(anomalies.len(), 3);
+        assert!(matches!(anomalies[0], QuoteAnomaly::Stale { .. }));
+        assert_eq!(anomalies[1], QuoteAnomaly::ZeroPrice);
+        assert_eq!(
+            anomalies[2],
+            QuoteAnomaly::CrossedMarket {
+                bid: 150.2,
+                ask: 150.1
+            }
+        );
+
+        // One-sided quotes are not crossed
+        assert!(validator
+            .check_fields(150.0, Some(150.2), Some(0.0), None, now)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_staleness_follows_the_session() {
+        let validator = QuoteValidator::new(Duration::from_secs(60));
+        let last_trade = Some(utc(4, 19, 59));
+
+        // 18:00 EDT, two hours after the close
+        let evening = utc(4, 22, 0);
+        assert!(validator
+            .check_fields(150.0, None, None, last_trade, evening)
+            .is_empty());
+        // Saturday, after a trade just before Friday's close
+        assert!(validator
+            .check_fields(150.0, None, None, Some(utc(7, 19, 59)), utc(8, 15, 0))
+            .is_empty());
+
+        // Nothing traded in the last hour of the session
+        let anomalies = validator.check_fields(150.0, None, None, Some(utc(4, 18, 30)), evening);
+        assert!(matches!(anomalies[0], QuoteAnomaly::Stale { .. }));
+
+        let closes = MarketCalendar::new().last_close_before(utc(8, 15, 0));
+        assert_eq!(closes, utc(7, 20, 0));
+    }
+
+    #[test]
+    fn test_publishes_warnings() {
+        let validator = QuoteValidator::new(Duration::from_secs(60));
+        let mut warnings = validator.subscribe();
+
+        let quote: Quote = serde_json::from_value(json!({
+            "tickerId": 913256135,
+            "close": "150.00", "change": "0", "changeRatio": "0", "preClose": "150.00",
+            "open": "150.00", "high": "150.00", "low": "150.00", "volume": "100",
+            "bid": "150.05", "ask": "149.95",
+            "tradeTime": "2024-06-03T19:59:59.000+0000"
+        }))
+        .unwrap();
+        assert!(!validator.validate(&quote));
+
+        let stale = warnings.try_recv().unwrap();
+        assert_eq!(stale.ticker_id.as_deref(), Some("913256135"));
+        assert!(matches!(stale.anomaly, QuoteAnomaly::Stale { .. }));
+        let crossed = warnings.try_recv().unwrap();
+        assert!(matches!(
+            crossed.anomaly,
+            QuoteAnomaly::CrossedMarket { .. }
+        ));
+
+        assert!(validator.validate_push(&json!({"tickerId": 1}), &json!({"status": "T"})));
+        assert!(!validator.validate_push(&json!({"tickerId": 1}), &json!({"price": "0"})));
+        assert_eq!(
+            warnings.try_recv().unwrap().anomaly,
+            QuoteAnomaly::ZeroPrice
+        );
+    }
+}
@@ -0,0 +1,293 @@
+// Recorded bid/ask snapshots for spread-aware backtests
+//
+// Webull serves historical bars but no historical bid/ask, so quote history
+// has to be captured as it happens: `QuoteRecorder` polls quotes during the
+// session and appends them to per-ticker files that `QuoteHistory` reads back.
+
+use crate::{
+    error::Result,
+    live_client::LiveWebullClient,
+    market::{MarketCalendar, Session},
+    models::Quote,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Top of book for one ticker at one instant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteSnapshot {
+    pub ticker_id: String,
+    /// When the quote was received
+    pub time: DateTime<Utc>,
+    pub bid: Option<f64>,
+    pub ask: Option<f64>,
+    pub bid_size: Option<f64>,
+    pub ask_size: Option<f64>,
+    /// Last trade price
+    pub last: f64,
+}
+
+impl QuoteSnapshot {
+    /// Snapshot a quote received at `time`; `None` without a ticker ID
+    pub fn from_quote(quote: &Quote, time: DateTime<Utc>) -> Option<Self> {
+        Some(Self {
+            ticker_id: quote.ticker_id.clone()?,
+            time,
+            bid: quote.bid,
+            ask: quote.ask,
+            bid_size: quote.bid_size,
+            ask_size: quote.ask_size,
+            last: quote.close,
+        })
+    }
+
+    /// Bid and ask, when both sides are quoted
+    fn sides(&self) -> Option<(f64, f64)> {
+        match (self.bid, self.ask) {
+            (Some(bid), Some(ask)) if bid > 0.0 && ask > 0.0 => Some((bid, ask)),
+            _ => None,
+        }
+    }
+
+    /// Midpoint of the bid and ask
+    pub fn mid(&self) -> Option<f64> {
+        self.sides().map(|(bid, ask)| (bid + ask) / 2.0)
+    }
+
+    /// Ask minus bid
+    pub fn spread(&self) -> Option<f64> {
+        self.sides().map(|(bid, ask)| ask - bid)
+    }
+}
+
+/// Recorded snapshots by ticker ID, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct QuoteHistory {
+    pub snapshots: BTreeMap<String, Vec<QuoteSnapshot>>,
+}
+
+impl QuoteHistory {
+    /// Read every `<ticker_id>.jsonl` file in a directory written by a
+    /// `QuoteRecorder`
+    pub fn load(dir: impl AsRef<Path>) -> Result<Self> {
+        let mut history = Self::default();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+                continue;
+            }
+            for line in BufReader::new(File::open(&path)?).lines() {
+                let line = line?;
+                if !line.trim().is_empty() {
+                    history.insert(serde_json::from_str(&line)?);
+                }
+            }
+        }
+        for snapshots in history.snapshots.values_mut() {
+            snapshots.sort_by_key(|s| s.time);
+        }
+        Ok(history)
+    }
+
+    fn insert(&mut self, snapshot: QuoteSnapshot) {
+        self.snapshots
+            .entry(snapshot.ticker_id.clone())
+            .or_default()
+            .push(snapshot);
+    }
+
+    /// The quote in force at `time`: the latest snapshot at or before it
+    pub fn at(&self, ticker_id: &str, time: DateTime<Utc>) -> Option<&QuoteSnapshot> {
+        let snapshots = self.snapshots.get(ticker_id)?;
+        let after = snapshots.partition_point(|s| s.time <= time);
+        after.checked_sub(1).map(|i| &snapshots[i])
+    }
+
+    /// Snapshots from `start` up to but excluding `end`
+    pub fn between(
+        &self,
+        ticker_id: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> &[QuoteSnapshot] {
+        let snapshots = match self.snapshots.get(ticker_id) {
+            Some(snapshots) => snapshots,
+            None => return &[],
+        };
+        let from = snapshots.partition_point(|s| s.time < start);
+        let to = snapshots.partition_point(|s| s.time < end).max(from);
+        &snapshots[from..to]
+    }
+}
+
+/// Append snapshots to `<dir>/<ticker_id>.jsonl`
+fn append_snapshots(dir: &Path, snapshots: &[QuoteSnapshot]) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    let mut by_ticker: BTreeMap<&str, Vec<&QuoteSnapshot>> = BTreeMap::new();
+    for snapshot in snapshots {
+        by_ticker
+            .entry(&snapshot.ticker_id)
+            .or_default()
+            .push(snapshot);
+    }
+    for (ticker_id, snapshots) in by_ticker {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(format!("{}.jsonl", ticker_id)))?;
+        let mut writer = BufWriter::new(file);
+        for snapshot in snapshots {
+            serde_json::to_writer(&mut writer, snapshot)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+/// Polls quotes for a set of tickers and appends them to disk
+///
+/// Created by `record_quotes`. Polls happen every `interval` (default 5
+/// seconds) during the regular session, or from pre-market to after-hours
+/// with `extended_hours`. A failed poll is logged and skipped; only local
+/// write errors stop the recorder.
+#[derive(Debug)]
+pub struct QuoteRecorder<'a> {
+    client: &'a LiveWebullClient,
+    ticker_ids: Vec<String>,
+    dir: PathBuf,
+    interval: Duration,
+    extended_hours: bool,
+    calendar: MarketCalendar,
+}
+
+impl<'a> QuoteRecorder<'a> {
+    pub(crate) fn new(client: &'a LiveWebullClient, ticker_ids: Vec<String>, dir: PathBuf) -> Self {
+        Self {
+            client,
+            ticker_ids,
+            dir,
+            interval: Duration::from_secs(5),
+            extended_hours: false,
+            calendar: MarketCalendar::new(),
+        }
+    }
+
+    /// Set how often quotes are polled
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Also record pre-market and after-hours quotes
+    pub fn extended_hours(mut self, enabled: bool) -> Self {
+        self.extended_hours = enabled;
+        self
+    }
+
+    /// Judge sessions with `calendar`, e.g. one with extra closures added
+    pub fn calendar(mut self, calendar: MarketCalendar) -> Self {
+        self.calendar = calendar;
+        self
+    }
+
+    fn is_recording(&self, at: DateTime<Utc>) -> bool {
+        match self.calendar.session_at(at) {
+            Session::Regular => true,
+            Session::PreMarket | Session::AfterHours => self.extended_hours,
+            Session::Closed => false,
+        }
+    }
+
+    async fn poll(&self) -> Result<Vec<QuoteSnapshot>> {
+        let ticker_ids: Vec<&str> = self.ticker_ids.iter().map(String::as_str).collect();
+        let quotes = self.client.get_quotes_batch(&ticker_ids).await?;
+        let now = Utc::now();
+        Ok(quotes
+            .iter()
+            .filter_map(|quote| QuoteSnapshot::from_quote(quote, now))
+            .collect())
+    }
+
+    /// Poll once and append the snapshots, whatever the session
+    pub async fn record_once(&self) -> Result<Vec<QuoteSnapshot>> {
+        let snapshots = self.poll().await?;
+        append_snapshots(&self.dir, &snapshots)?;
+        Ok(snapshots)
+    }
+
+    /// Record until `end`, returning the number of snapshots written
+    pub async fn run_until(self, end: DateTime<Utc>) -> Result<usize> {
+        let mut ticker = tokio::time::interval(self.interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut written = 0;
+        loop {
+            ticker.tick().await;
+            let now = Utc::now();
+            if now >= end {
+                return Ok(written);
+            }
+            if !self.is_recording(now) {
+                continue;
+            }
+            match self.poll().await {
+                Ok(snapshots) => {
+                    append_snapshots(&self.dir, &snapshots)?;
+                    written += snapshots.len();
+                }
+                Err(e) => log::warn!("Quote snapshot poll failed: {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn at(minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 6, 4, 15, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_snapshots_round_trip_and_lookup() {
+        let quote: Quote = serde_json::from_value(json!({
+            "tickerId": 913256135,
+            "close": "150.00", "change": "0", "changeRatio": "0", "preClose": "150.00",
+            "open": "150.00", "high": "150.00", "low": "150.00", "volume": "100",
+            "bid": "149.98", "ask": "150.02", "bidSize": "300", "askSize": "100"
+        }))
+        .unwrap();
+        let first = QuoteSnapshot::from_quote(&quote, at(0)).unwrap();
+        assert_eq!(first.mid(), Some(150.0));
+        assert!((first.spread().unwrap() - 0.04).abs() < 1e-9);
+        let mut second = first.clone();
+        second.time = at(5);
+        second.ask = Some(0.0);
+        assert_eq!(second.spread(), None);
+
+        let dir = std::env::temp_dir().join(format!("webull_quotes_{}", std::process::id()));
+        append_snapshots(&dir, std::slice::from_ref(&first)).unwrap();
+        append_snapshots(&dir, std::slice::from_ref(&second)).unwrap();
+        let history = QuoteHistory::load(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(history.snapshots["913256135"].len(), 2);
+        assert_eq!(history.at("913256135", at(3)), Some(&first));
+        assert_eq!(history.at("913256135", at(5)), Some(&second));
+        assert_eq!(
+            history.at("913256135", at(0) - chrono::Duration::seconds(1)),
+            None
+        );
+        assert_eq!(history.at("1", at(3)), None);
+        assert_eq!(history.between("913256135", at(0), at(5)), &[first]);
+    }
+}
@@ -0,0 +1,180 @@
+// Client-side token bucket rate limiting per endpoint class
+
+use crate::error::{Result, WebullError};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Group of endpoints that share a rate limit bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EndpointClass {
+    /// Quotes, bars and other market data
+    MarketData,
+    /// Option chains, option quotes and option rankings
+    Options,
+    /// Order placement, cancellation and trade history
+    Trading,
+    /// Login, account and user endpoints
+    Account,
+    Other,
+}
+
+impl EndpointClass {
+    /// Classify a request URL by host and path
+    pub fn from_url(url: &str) -> Self {
+        if url.contains("/trade/") || url.contains("/paper/") || url.contains("ustrade") {
+            EndpointClass::Trading
+        } else if url.contains("option") {
+            EndpointClass::Options
+        } else if url.contains("quote") || url.contains("securities") || url.contains("wlas") {
+            EndpointClass::MarketData
+        } else if url.contains("userapi")
+            || url.contains("/user/")
+            || url.contains("passport")
+            || url.contains("/account")
+        {
+            EndpointClass::Account
+        } else {
+            EndpointClass::Other
+        }
+    }
+}
+
+/// Refuse a refill rate that isn't a positive, finite number of requests
+/// per second
+pub(crate) fn check_rate(requests_per_sec: f64) -> Result<()> {
+    if requests_per_sec.is_finite() && requests_per_sec > 0.0 {
+        Ok(())
+    } else {
+        Err(WebullError::InvalidParameter(format!(
+            "Rate limit must be a positive number of requests per second, got {}",
+            requests_per_sec
+        )))
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token bucket limiter keyed by `EndpointClass`
+///
+/// Each class refills at `requests_per_sec` and can burst up to `burst`
+/// requests. Cloned clients share one limiter through an `Arc`.
+#[derive(Debug)]
+pub struct RateLimiter {
+    requests_per_sec: f64,
+    burst: f64,
+    buckets: Mutex<HashMap<EndpointClass, TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Create a limiter allowing `requests_per_sec` per endpoint class
+    ///
+    /// The burst size defaults to one second's worth of requests.
+    pub fn new(requests_per_sec: f64) -> Self {
+        Self::with_burst(requests_per_sec, requests_per_sec.max(1.0))
+    }
+
+    /// Create a limiter with an explicit burst size
+    pub fn with_burst(requests_per_sec: f64, burst: f64) -> Self {
+        Self {
+            requests_per_sec: requests_per_sec.max(f64::MIN_POSITIVE),
+            burst: burst.max(1.0),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the configured refill rate
+    pub fn requests_per_sec(&self) -> f64 {
+        self.requests_per_sec
+    }
+
+    /// Take a token, returning how long to wait before one is available
+    fn try_acquire(&self, class: EndpointClass, now: Instant) -> Option<Duration> {
+        let mut buckets = self.buckets.lock();
+        let bucket = buckets.entry(class).or_insert(TokenBucket {
+            tokens: self.burst,
+            last_refill: now,
+        });
+
+        let elapsed = now
+            .saturating_duration_since(bucket.last_refill)
+            .as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.requests_per_sec).min(self.burst);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            None
+        } else {
+            let missing = 1.0 - bucket.tokens;
+            Some(Duration::from_secs_f64(missing / self.requests_per_sec))
+        }
+    }
+
+    /// Wait until a request in `class` is allowed
+    pub async fn acquire(&self, class: EndpointClass) {
+        while let Some(wait) = self.try_acquire(class, Instant::now()) {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_classification() {
+        assert_eq!(
+            EndpointClass::from_url("https://ustrade.webullbroker.com/api/trade/v2/option/list"),
+            EndpointClass::Trading
+        );
+        assert_eq!(
+            EndpointClass::from_url(
+                "https://quotes-gw.webullbroker.com/api/quotes/ticker/getTickerRealTime"
+            ),
+            EndpointClass::MarketData
+        );
+        assert_eq!(
+            EndpointClass::from_url(
+                "https://quotes-gw.webullbroker.com/api/quote/option/strategy/list"
+            ),
+            EndpointClass::Options
+        );
+        assert_eq!(
+            EndpointClass::from_url("https://userapi.webull.com/api/passport/login/v5/account"),
+            EndpointClass::Account
+        );
+    }
+
+    #[test]
+    fn test_bucket_burst_and_refill() {
+        let limiter = RateLimiter::with_burst(2.0, 2.0);
+        let start = Instant::now();
+
+        assert!(limiter
+            .try_acquire(EndpointClass::MarketData, start)
+            .is_none());
+        assert!(limiter
+            .try_acquire(EndpointClass::MarketData, start)
+            .is_none());
+        let wait = limiter
+            .try_acquire(EndpointClass::MarketData, start)
+            .unwrap();
+        assert_eq!(wait, Duration::from_millis(500));
+
+        // Other classes have their own bucket
+        assert!(limiter.try_acquire(EndpointClass::Trading, start).is_none());
+
+        // Half a second refills one token
+        let later = start + Duration::from_millis(500);
+        assert!(limiter
+            .try_acquire(EndpointClass::MarketData, later)
+            .is_none());
+    }
+}
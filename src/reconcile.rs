@@ -0,0 +1,273 @@
+// Reconciles order state built from push events against the REST order history
+
+use crate::{
+    error::Result,
+    models::{parse_order_status, HistoryOrder, OrderStatus},
+    WebullClient,
+};
+use log::warn;
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Locally tracked state of a single order
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackedOrder {
+    pub status: OrderStatus,
+    pub filled_quantity: f64,
+}
+
+/// Difference between local order state and the REST history
+///
+/// Each drift has already been corrected locally when it is reported.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StateDrift {
+    /// REST knows an order that never arrived over push since the first
+    /// reconciliation
+    Missing {
+        order_id: String,
+        rest: TrackedOrder,
+    },
+    /// Local and REST status disagree
+    StatusMismatch {
+        order_id: String,
+        local: OrderStatus,
+        rest: OrderStatus,
+    },
+    /// Local and REST filled quantity disagree
+    FillMismatch {
+        order_id: String,
+        local: f64,
+        rest: f64,
+    },
+}
+
+/// Order state fed by push events and periodically checked against REST
+///
+/// Share it behind an `Arc` so the stream's order callback and the
+/// reconciliation loop can both reach it.
+#[derive(Debug, Default)]
+pub struct OrderReconciler {
+    orders: Mutex<HashMap<String, TrackedOrder>>,
+    baselined: AtomicBool,
+}
+
+impl OrderReconciler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the local state of an order
+    pub fn get(&self, order_id: &str) -> Option<TrackedOrder> {
+        self.orders.lock().get(order_id).cloned()
+    }
+
+    /// Number of tracked orders
+    pub fn len(&self) -> usize {
+        self.orders.lock().len()
+    }
+
+    /// Check if no orders are tracked
+    pub fn is_empty(&self) -> bool {
+        self.orders.lock().is_empty()
+    }
+
+    /// Apply an order push payload (see `StreamConn::set_order_callback`)
+    ///
+    /// Returns false if the payload carries no order ID or status.
+    pub fn apply_push(&self, data: &Value) -> bool {
+        let order_id = match data.get("orderId") {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Number(n)) => n.to_string(),
+            _ => return false,
+        };
+
+        let status = match ["statusCode", "status", "orderStatus"]
+            .iter()
+            .find_map(|key| data.get(*key).and_then(|v| v.as_str()))
+        {
+            Some(status) => parse_order_status(status),
+            None => return false,
+        };
+
+        let filled_quantity =
+            ["filledQuantity", "filledQty"]
+                .iter()
+                .find_map(|key| match data.get(*key) {
+                    Some(Value::String(s)) => s.parse::<f64>().ok(),
+                    Some(Value::Number(n)) => n.as_f64(),
+                    _ => None,
+                });
+
+        let mut orders = self.orders.lock();
+        let entry = orders.entry(order_id).or_insert(TrackedOrder {
+            status: status.clone(),
+            filled_quantity: 0.0,
+        });
+        entry.status = status;
+        if let Some(filled) = filled_quantity {
+            entry.filled_quantity = filled;
+        }
+        true
+    }
+
+    /// Compare local state against REST orders, correcting any drift
+    ///
+    /// The first pass is a baseline: orders placed before the reconciler
+    /// started are adopted rather than reported `Missing`. Orders already
+    /// tracked from push events are still checked.
+    pub fn reconcile(&self, rest_orders: &[HistoryOrder]) -> Vec<StateDrift> {
+        let mut orders = self.orders.lock();
+        let baseline = !self.baselined.swap(true, Ordering::SeqCst);
+        let mut drifts = Vec::new();
+
+        for order in rest_orders {
+            let rest = TrackedOrder {
+                status: order.status.clone(),
+                filled_quantity: order.filled_quantity,
+            };
+
+            match orders.get(&order.order_id) {
+                None if baseline => {}
+                None => drifts.push(StateDrift::Missing {
+                    order_id: order.order_id.clone(),
+                    rest: rest.clone(),
+                }),
+                Some(local) => {
+                    if local.status != rest.status {
+                        drifts.push(StateDrift::StatusMismatch {
+                            order_id: order.order_id.clone(),
+                            local: local.status.clone(),
+                            rest: rest.status.clone(),
+                        });
+                    }
+                    if (local.filled_quantity - rest.filled_quantity).abs() > f64::EPSILON {
+                        drifts.push(StateDrift::FillMismatch {
+                            order_id: order.order_id.clone(),
+                            local: local.filled_quantity,
+                            rest: rest.filled_quantity,
+                        });
+                    }
+                }
+            }
+
+            orders.insert(order.order_id.clone(), rest);
+        }
+
+        drifts
+    }
+
+    /// Fetch recent order history and reconcile against it
+    pub async fn reconcile_with(
+        &self,
+        client: &WebullClient,
+        count: i32,
+    ) -> Result<Vec<StateDrift>> {
+        let rest_orders = client.get_history_orders("All", count).await?;
+        Ok(self.reconcile(&rest_orders))
+    }
+
+    /// Reconcile every `interval`, passing each drift to `on_drift`
+    ///
+    /// Runs until the future is dropped; fetch errors are logged and retried
+    /// on the next tick.
+    pub async fn run<F>(
+        &self,
+        client: &WebullClient,
+        interval: Duration,
+        count: i32,
+        mut on_drift: F,
+    ) where
+        F: FnMut(StateDrift),
+    {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match self.reconcile_with(client, count).await {
+                Ok(drifts) => drifts.into_iter().for_each(&mut on_drift),
+                Err(e) => warn!("Order reconciliation failed: {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rest_order(order_id: &str, status: &str, filled: f64) -> HistoryOrder {
+        HistoryOrder::from_value(&json!({
+            "orderId": order_id,
+            "action": "BUY",
+            "orderType": "LMT",
+            "status": status,
+            "totalQuantity": "10",
+            "filledQuantity": filled.to_string()
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_push_updates_local_state() {
+        let reconciler = OrderReconciler::new();
+        assert!(reconciler.apply_push(&json!({"orderId": 1, "status": "Working"})));
+        assert!(reconciler.apply_push(&json!({
+            "orderId": "1",
+            "status": "Partial Filled",
+            "filledQuantity": "4"
+        })));
+        assert!(!reconciler.apply_push(&json!({"status": "Filled"})));
+
+        let order = reconciler.get("1").unwrap();
+        assert_eq!(order.status, OrderStatus::PartialFilled);
+        assert_eq!(order.filled_quantity, 4.0);
+    }
+
+    #[test]
+    fn test_first_pass_is_a_baseline() {
+        let reconciler = OrderReconciler::new();
+        let history = [
+            rest_order("1", "Filled", 10.0),
+            rest_order("2", "Cancelled", 0.0),
+        ];
+        assert!(reconciler.reconcile(&history).is_empty());
+        assert_eq!(reconciler.len(), 2);
+
+        // Later passes report orders that never arrived over push
+        let drifts = reconciler.reconcile(&[rest_order("3", "Working", 0.0)]);
+        assert!(matches!(&drifts[..], [StateDrift::Missing { order_id, .. }] if order_id == "3"));
+    }
+
+    #[test]
+    fn test_reconcile_reports_and_corrects_drift() {
+        let reconciler = OrderReconciler::new();
+        assert!(reconciler.reconcile(&[]).is_empty());
+        reconciler.apply_push(&json!({"orderId": "1", "status": "Working"}));
+        reconciler.apply_push(&json!({"orderId": "2", "status": "Filled", "filledQuantity": 10}));
+
+        let drifts = reconciler.reconcile(&[
+            rest_order("1", "Filled", 10.0),
+            rest_order("2", "Filled", 10.0),
+            rest_order("3", "Cancelled", 0.0),
+        ]);
+
+        assert_eq!(drifts.len(), 3);
+        assert!(matches!(
+            &drifts[0],
+            StateDrift::StatusMismatch { order_id, local: OrderStatus::Working, rest: OrderStatus::Filled }
+                if order_id == "1"
+        ));
+        assert!(matches!(&drifts[1], StateDrift::FillMismatch { local, .. } if *local == 0.0));
+        assert!(matches!(&drifts[2], StateDrift::Missing { order_id, .. } if order_id == "3"));
+
+        // Local state now matches REST, so a second pass is clean
+        assert_eq!(reconciler.get("1").unwrap().status, OrderStatus::Filled);
+        assert!(reconciler
+            .reconcile(&[rest_order("1", "Filled", 10.0)])
+            .is_empty());
+        assert_eq!(reconciler.len(), 3);
+    }
+}
@@ -0,0 +1,153 @@
+// Per-region hosts, headers and defaults picked from the client's region code
+
+use crate::endpoints::{Endpoints, Environment};
+use serde::{Deserialize, Serialize};
+
+/// Webull market region, selected by region ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Region {
+    /// Region 6
+    Us,
+    /// Region 2
+    HongKong,
+    /// Region 8
+    Japan,
+    /// Any other region ID; uses the global hosts with English defaults
+    Global,
+}
+
+impl Region {
+    /// Region for a Webull region ID
+    pub fn from_code(region_code: i32) -> Self {
+        match region_code {
+            6 => Region::Us,
+            2 => Region::HongKong,
+            8 => Region::Japan,
+            _ => Region::Global,
+        }
+    }
+
+    /// Default hosts, headers and currency for this region
+    ///
+    /// Every region talks to Webull's US hosts in the `dc_core_r001` zone
+    /// with English headers; only the currency follows the region. See
+    /// `regional_profile` for region-specific hosts.
+    pub fn profile(self) -> RegionProfile {
+        RegionProfile {
+            region: self,
+            domain: None,
+            zone_var: "dc_core_r001",
+            language: "en",
+            locale: "eng",
+            accept_language: "en-US,en;q=0.5",
+            currency: self.currency(),
+        }
+    }
+
+    /// Region-specific hosts and headers, used with
+    /// `ClientConfig::regional_hosts`
+    ///
+    /// The Hong Kong (`webull.hk`, `dc_core_r002`) and Japan (`webull.co.jp`,
+    /// `dc_core_r003`) hosts and zones have not been checked against live
+    /// traffic. Other regions get the default profile.
+    pub fn regional_profile(self) -> RegionProfile {
+        match self {
+            Region::HongKong => RegionProfile {
+                region: self,
+                domain: Some("webull.hk"),
+                zone_var: "dc_core_r002",
+                language: "zh-hant",
+                locale: "zht",
+                accept_language: "zh-HK,zh;q=0.8,en;q=0.5",
+                currency: self.currency(),
+            },
+            Region::Japan => RegionProfile {
+                region: self,
+                domain: Some("webull.co.jp"),
+                zone_var: "dc_core_r003",
+                language: "ja",
+                locale: "jpn",
+                accept_language: "ja-JP,ja;q=0.8,en;q=0.5",
+                currency: self.currency(),
+            },
+            Region::Us | Region::Global => self.profile(),
+        }
+    }
+
+    fn currency(self) -> &'static str {
+        match self {
+            Region::HongKong => "HKD",
+            Region::Japan => "JPY",
+            Region::Us | Region::Global => "USD",
+        }
+    }
+}
+
+/// What a client sends and where, for one region
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionProfile {
+    pub region: Region,
+    /// Domain replacing Webull's US domains (`None` keeps them)
+    pub domain: Option<&'static str>,
+    /// `lzone` header: the data center serving the region
+    pub zone_var: &'static str,
+    /// `hl` header
+    pub language: &'static str,
+    /// `locale` header
+    pub locale: &'static str,
+    /// `Accept-Language` header
+    pub accept_language: &'static str,
+    /// Currency amounts are reported in unless an account says otherwise
+    pub currency: &'static str,
+}
+
+impl RegionProfile {
+    /// Endpoints for this region in `environment`
+    ///
+    /// A custom environment keeps its own host for every region.
+    pub fn endpoints(&self, environment: &Environment) -> Endpoints {
+        let endpoints = Endpoints::for_environment(environment);
+        match (environment, self.domain) {
+            (Environment::Production, Some(domain)) => endpoints.with_domain(domain),
+            _ => endpoints,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_region_profiles_pick_hosts_and_headers() {
+        let us = Region::from_code(6).profile();
+        let us_endpoints = us.endpoints(&Environment::Production);
+        assert_eq!(
+            us_endpoints.base_fintech_gw_url,
+            "https://quotes-gw.webullfintech.com/api"
+        );
+        assert_eq!(us.currency, "USD");
+
+        // Regional hosts are opt-in
+        let hk_default = Region::from_code(2).profile();
+        assert_eq!(hk_default.domain, None);
+        assert_eq!(hk_default.zone_var, "dc_core_r001");
+        assert_eq!(hk_default.currency, "HKD");
+
+        let hk = Region::from_code(2).regional_profile();
+        let hk_endpoints = hk.endpoints(&Environment::Production);
+        assert_eq!(
+            hk_endpoints.base_fintech_gw_url,
+            "https://quotes-gw.webull.hk/api"
+        );
+        assert_eq!(
+            hk_endpoints.base_paper_url,
+            "https://act.webull.hk/webull-paper-center/api"
+        );
+        assert_eq!(hk.zone_var, "dc_core_r002");
+        assert_eq!(Region::Us.regional_profile(), us);
+
+        assert_eq!(Region::from_code(8).profile().currency, "JPY");
+        assert_eq!(Region::from_code(14), Region::Global);
+    }
+}
@@ -0,0 +1,242 @@
+// Decoding of Webull order rejection codes into documented reasons
+
+use once_cell::sync::Lazy;
+use parking_lot::{RwLock, RwLockReadGuard};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Why Webull rejected an order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RejectionReason {
+    InsufficientBuyingPower,
+    /// Selling more than the position holds
+    InsufficientPosition,
+    /// Limit or stop price outside the allowed band around the market
+    PriceTooFarFromMarket,
+    /// Price not a multiple of the instrument's tick size
+    InvalidTickSize,
+    InvalidQuantity,
+    MarketClosed,
+    /// Account is restricted, frozen or closing-only
+    AccountRestricted,
+    /// Order would breach pattern day trader rules
+    PatternDayTrader,
+    /// Instrument can't be traded through this account right now
+    SymbolNotTradable,
+    /// Order type or time in force not allowed for this instrument or session
+    UnsupportedOrderType,
+    DuplicateOrder,
+}
+
+impl RejectionReason {
+    pub fn description(&self) -> &'static str {
+        match self {
+            RejectionReason::InsufficientBuyingPower => "insufficient buying power",
+            RejectionReason::InsufficientPosition => "insufficient position to sell",
+            RejectionReason::PriceTooFarFromMarket => "price too far from market",
+            RejectionReason::InvalidTickSize => "price does not match tick size",
+            RejectionReason::InvalidQuantity => "invalid quantity",
+            RejectionReason::MarketClosed => "market closed",
+            RejectionReason::AccountRestricted => "account restricted",
+            RejectionReason::PatternDayTrader => "pattern day trader restriction",
+            RejectionReason::SymbolNotTradable => "symbol not tradable",
+            RejectionReason::UnsupportedOrderType => "order type not supported",
+            RejectionReason::DuplicateOrder => "duplicate order",
+        }
+    }
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+/// Starting set of likely codes
+///
+/// None has been checked against a recorded response, so decoding is best
+/// effort; add or correct codes with `register`.
+const KNOWN_CODES: &[(&str, RejectionReason)] = &[
+    (
+        "INSUFFICIENT_BUYING_POWER",
+        RejectionReason::InsufficientBuyingPower,
+    ),
+    (
+        "trade.webull.INSUFFICIENT_BUYING_POWER",
+        RejectionReason::InsufficientBuyingPower,
+    ),
+    (
+        "OAUTH_INSUFFICIENT_BUYING_POWER",
+        RejectionReason::InsufficientBuyingPower,
+    ),
+    (
+        "INSUFFICIENT_POSITION",
+        RejectionReason::InsufficientPosition,
+    ),
+    (
+        "trade.webull.POSITION_NOT_ENOUGH",
+        RejectionReason::InsufficientPosition,
+    ),
+    (
+        "OAUTH_PRICE_OUT_OF_RANGE",
+        RejectionReason::PriceTooFarFromMarket,
+    ),
+    (
+        "trade.webull.PRICE_OUT_OF_RANGE",
+        RejectionReason::PriceTooFarFromMarket,
+    ),
+    (
+        "order.price.far.from.market",
+        RejectionReason::PriceTooFarFromMarket,
+    ),
+    ("order.price.tick", RejectionReason::InvalidTickSize),
+    (
+        "trade.webull.PRICE_TICK_ERROR",
+        RejectionReason::InvalidTickSize,
+    ),
+    ("order.quantity.invalid", RejectionReason::InvalidQuantity),
+    (
+        "trade.webull.QUANTITY_ERROR",
+        RejectionReason::InvalidQuantity,
+    ),
+    ("trade.webull.MARKET_CLOSED", RejectionReason::MarketClosed),
+    (
+        "trade.webull.ACCOUNT_RESTRICTED",
+        RejectionReason::AccountRestricted,
+    ),
+    (
+        "trade.webull.ACCOUNT_CLOSING_ONLY",
+        RejectionReason::AccountRestricted,
+    ),
+    ("trade.account.frozen", RejectionReason::AccountRestricted),
+    ("trade.webull.PDT_LIMIT", RejectionReason::PatternDayTrader),
+    (
+        "trade.webull.DAY_TRADE_LIMIT",
+        RejectionReason::PatternDayTrader,
+    ),
+    (
+        "trade.webull.TICKER_NOT_TRADABLE",
+        RejectionReason::SymbolNotTradable,
+    ),
+    ("trade.ticker.halted", RejectionReason::SymbolNotTradable),
+    (
+        "trade.webull.ORDER_TYPE_NOT_SUPPORT",
+        RejectionReason::UnsupportedOrderType,
+    ),
+    (
+        "trade.webull.TIF_NOT_SUPPORT",
+        RejectionReason::UnsupportedOrderType,
+    ),
+    (
+        "trade.webull.DUPLICATE_ORDER",
+        RejectionReason::DuplicateOrder,
+    ),
+    ("trade.serial.id.repeat", RejectionReason::DuplicateOrder),
+];
+
+static GLOBAL: Lazy<RwLock<RejectionCatalogue>> =
+    Lazy::new(|| RwLock::new(RejectionCatalogue::default()));
+
+/// Lookup table from rejection codes to reasons
+///
+/// Codes match case-insensitively, either in full or by their last dotted
+/// segment, so `trade.webull.INSUFFICIENT_BUYING_POWER` and
+/// `INSUFFICIENT_BUYING_POWER` decode the same way. The default catalogue
+/// holds a starting set of likely codes; `register` adds more to the
+/// process-wide table that error decoding uses.
+#[derive(Debug, Clone)]
+pub struct RejectionCatalogue {
+    codes: HashMap<String, RejectionReason>,
+}
+
+impl Default for RejectionCatalogue {
+    fn default() -> Self {
+        let mut catalogue = Self::empty();
+        catalogue.extend(KNOWN_CODES.iter().copied());
+        catalogue
+    }
+}
+
+impl RejectionCatalogue {
+    /// A catalogue with no codes
+    pub fn empty() -> Self {
+        Self {
+            codes: HashMap::new(),
+        }
+    }
+
+    /// Map a code to a reason, replacing any existing mapping
+    pub fn insert(&mut self, code: impl AsRef<str>, reason: RejectionReason) {
+        self.codes
+            .insert(code.as_ref().to_ascii_lowercase(), reason);
+    }
+
+    /// Add every mapping from a table, e.g. one deserialized from JSON
+    pub fn extend<I, S>(&mut self, codes: I)
+    where
+        I: IntoIterator<Item = (S, RejectionReason)>,
+        S: AsRef<str>,
+    {
+        for (code, reason) in codes {
+            self.insert(code, reason);
+        }
+    }
+
+    /// Reason for a code, if the catalogue knows it
+    pub fn lookup(&self, code: &str) -> Option<RejectionReason> {
+        let code = code.trim().to_ascii_lowercase();
+        self.codes.get(&code).copied().or_else(|| {
+            let (_, last) = code.rsplit_once('.')?;
+            self.codes.get(last).copied()
+        })
+    }
+
+    /// Look a code up in the process-wide catalogue
+    pub fn decode(code: &str) -> Option<RejectionReason> {
+        GLOBAL.read().lookup(code)
+    }
+
+    /// The process-wide catalogue, locked for reading
+    pub(crate) fn global() -> RwLockReadGuard<'static, RejectionCatalogue> {
+        GLOBAL.read()
+    }
+
+    /// Add a code to the process-wide catalogue
+    pub fn register(code: impl AsRef<str>, reason: RejectionReason) {
+        GLOBAL.write().insert(code, reason);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_by_full_code_and_last_segment() {
+        let catalogue = RejectionCatalogue::default();
+        assert_eq!(
+            catalogue.lookup("trade.webull.INSUFFICIENT_BUYING_POWER"),
+            Some(RejectionReason::InsufficientBuyingPower)
+        );
+        assert_eq!(
+            catalogue.lookup("trade.us.insufficient_buying_power"),
+            Some(RejectionReason::InsufficientBuyingPower)
+        );
+        assert_eq!(
+            catalogue.lookup("ORDER.PRICE.TICK"),
+            Some(RejectionReason::InvalidTickSize)
+        );
+        assert_eq!(catalogue.lookup("trade.webull.SOMETHING_NEW"), None);
+
+        let mut custom = RejectionCatalogue::empty();
+        let table: HashMap<String, RejectionReason> =
+            serde_json::from_str(r#"{"4021": "AccountRestricted"}"#).unwrap();
+        custom.extend(table);
+        assert_eq!(
+            custom.lookup("4021"),
+            Some(RejectionReason::AccountRestricted)
+        );
+        assert_eq!(custom.lookup("order.price.tick"), None);
+    }
+}
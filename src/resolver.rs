@@ -0,0 +1,137 @@
+// Symbol -> ticker ID cache with optional on-disk persistence
+
+use crate::error::{Result, WebullError};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Caches symbol to ticker ID lookups
+///
+/// Symbols are matched case-insensitively. When a persistence path is set the
+/// cache is loaded from it on creation and rewritten after each new entry.
+#[derive(Debug, Default)]
+pub struct SymbolResolver {
+    cache: RwLock<HashMap<String, i64>>,
+    path: Option<PathBuf>,
+}
+
+impl SymbolResolver {
+    /// Create an in-memory resolver
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a resolver persisted to a JSON file, loading any existing entries
+    pub fn with_persistence(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let cache = if path.exists() {
+            let contents = fs::read_to_string(&path)?;
+            serde_json::from_str(&contents)?
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self {
+            cache: RwLock::new(cache),
+            path: Some(path),
+        })
+    }
+
+    /// Get the persistence path, if any
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Look up a cached ticker ID
+    pub fn get(&self, symbol: &str) -> Option<i64> {
+        self.cache.read().get(&symbol.to_uppercase()).copied()
+    }
+
+    /// Cache a ticker ID, writing through to disk when persistence is enabled
+    pub fn insert(&self, symbol: &str, ticker_id: i64) -> Result<()> {
+        let previous = self.cache.write().insert(symbol.to_uppercase(), ticker_id);
+        if previous != Some(ticker_id) {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Cache many ticker IDs at once, writing to disk a single time
+    pub fn extend<'a>(&self, entries: impl IntoIterator<Item = (&'a str, i64)>) -> Result<()> {
+        self.cache.write().extend(
+            entries
+                .into_iter()
+                .map(|(symbol, ticker_id)| (symbol.to_uppercase(), ticker_id)),
+        );
+        self.save()
+    }
+
+    /// Number of cached symbols
+    pub fn len(&self) -> usize {
+        self.cache.read().len()
+    }
+
+    /// Check if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.cache.read().is_empty()
+    }
+
+    /// Drop all cached entries (and the persisted file contents)
+    pub fn clear(&self) -> Result<()> {
+        self.cache.write().clear();
+        self.save()
+    }
+
+    /// Write the cache to disk; a no-op without a persistence path
+    pub fn save(&self) -> Result<()> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        let contents = serde_json::to_string_pretty(&*self.cache.read())
+            .map_err(|e| WebullError::SerializationError(e.to_string()))?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_memory_cache() {
+        let resolver = SymbolResolver::new();
+        assert!(resolver.get("AAPL").is_none());
+
+        resolver.insert("aapl", 913256135).unwrap();
+        assert_eq!(resolver.get("AAPL"), Some(913256135));
+        assert_eq!(resolver.len(), 1);
+
+        resolver.clear().unwrap();
+        assert!(resolver.is_empty());
+    }
+
+    #[test]
+    fn test_persistence_round_trip() {
+        let dir = std::env::temp_dir().join(format!("webull_resolver_{}", uuid::Uuid::new_v4()));
+        let path = dir.join("symbols.json");
+
+        let resolver = SymbolResolver::with_persistence(&path).unwrap();
+        resolver.insert("TSLA", 913255598).unwrap();
+        assert!(path.exists());
+
+        let reloaded = SymbolResolver::with_persistence(&path).unwrap();
+        assert_eq!(reloaded.get("tsla"), Some(913255598));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,213 @@
+// Retry policy for transient request failures
+
+use crate::error::WebullError;
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+
+/// Exponential backoff settings for retrying transient failures
+///
+/// Only idempotent requests (GET, HEAD, PUT, DELETE, OPTIONS) are retried
+/// unless `retry_non_idempotent` is set, so an order POST that timed out
+/// after reaching the server is never submitted twice by default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 disables retrying)
+    pub max_retries: u32,
+    /// Delay before the first retry
+    pub base_delay: Duration,
+    /// Upper bound for any single delay
+    pub max_delay: Duration,
+    /// Randomize each delay between zero and the computed backoff
+    pub jitter: bool,
+    /// Also retry POST and PATCH requests
+    pub retry_non_idempotent: bool,
+}
+
+impl RetryPolicy {
+    /// Default policy: 3 retries starting at 200ms, capped at 5s, with jitter
+    pub fn new() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+            jitter: true,
+            retry_non_idempotent: false,
+        }
+    }
+
+    /// Policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::new()
+        }
+    }
+
+    /// Set the number of retries
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Set the delay before the first retry
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Set the maximum delay between retries
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Enable or disable jitter
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Allow retrying POST and PATCH requests
+    pub fn retry_non_idempotent(mut self, retry: bool) -> Self {
+        self.retry_non_idempotent = retry;
+        self
+    }
+
+    /// Whether requests with this method may be retried
+    pub fn allows_method(&self, method: &Method) -> bool {
+        self.retry_non_idempotent
+            || matches!(
+                *method,
+                Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+            )
+    }
+
+    /// Backoff before retry number `attempt` (starting at 0), without jitter
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt);
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    /// Delay to sleep before retry number `attempt`, with jitter applied
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let backoff = self.backoff(attempt);
+        if self.jitter {
+            backoff.mul_f64(random_fraction())
+        } else {
+            backoff
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether a response status indicates a transient server-side failure
+pub fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+impl WebullError {
+    /// Whether the error is transient and the request may succeed if retried
+    ///
+    /// Timeouts, connection failures, 5xx responses and rate limiting are
+    /// retryable. Authentication, validation and API business errors are not.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            WebullError::RequestError(e) => {
+                e.is_timeout()
+                    || e.is_connect()
+                    || (e.is_request() && e.status().is_none())
+                    || e.status().is_some_and(is_retryable_status)
+            }
+            WebullError::IoError(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::Interrupted
+            ),
+            WebullError::ApiErrorCode { code, .. } => code
+                .parse::<u16>()
+                .ok()
+                .and_then(|status| StatusCode::from_u16(status).ok())
+                .is_some_and(is_retryable_status),
+            WebullError::RateLimitExceeded => true,
+            _ => false,
+        }
+    }
+}
+
+// Uniform value in [0, 1) from the v4 UUID generator, avoiding a rand dependency.
+// A v4 UUID's version and variant bits are fixed and sit above its low 62
+// bits, which are all random; 53 of those fill an f64 mantissa.
+fn random_fraction() -> f64 {
+    let bits = uuid::Uuid::new_v4().as_u128() as u64 & ((1u64 << 53) - 1);
+    bits as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_fraction_spans_the_unit_interval() {
+        let samples: Vec<f64> = (0..2000).map(|_| random_fraction()).collect();
+        assert!(samples.iter().all(|x| (0.0..1.0).contains(x)));
+        let low = samples.iter().filter(|x| **x < 0.25).count();
+        let high = samples.iter().filter(|x| **x >= 0.75).count();
+        assert!(low > 300 && high > 300, "low {}, high {}", low, high);
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let policy = RetryPolicy::new()
+            .base_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_millis(500))
+            .jitter(false);
+
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(1), Duration::from_millis(200));
+        assert_eq!(policy.delay(2), Duration::from_millis(400));
+        assert_eq!(policy.delay(3), Duration::from_millis(500));
+        assert_eq!(policy.delay(40), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_backoff() {
+        let policy = RetryPolicy::new().base_delay(Duration::from_millis(100));
+        for attempt in 0..5 {
+            assert!(policy.delay(attempt) <= policy.backoff(attempt));
+        }
+    }
+
+    #[test]
+    fn test_retryable_classification() {
+        assert!(WebullError::RateLimitExceeded.is_retryable());
+        assert!(WebullError::IoError(std::io::ErrorKind::ConnectionReset.into()).is_retryable());
+        assert!(!WebullError::InvalidCredentials.is_retryable());
+        assert!(!WebullError::ApiError("rejected".to_string()).is_retryable());
+        assert!(WebullError::ApiErrorCode {
+            code: "503".to_string(),
+            message: String::new()
+        }
+        .is_retryable());
+
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+
+        let policy = RetryPolicy::new();
+        assert!(policy.allows_method(&Method::GET));
+        assert!(!policy.allows_method(&Method::POST));
+        assert!(policy
+            .retry_non_idempotent(true)
+            .allows_method(&Method::POST));
+    }
+}
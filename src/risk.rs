@@ -0,0 +1,315 @@
+// Scenario P&L for pre-trade risk checks
+
+use crate::models::{OptionContract, Position};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Contract multiplier for US equity options
+const OPTION_MULTIPLIER: f64 = 100.0;
+
+/// A position as the scenario engine sees it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RiskPosition {
+    /// Shares; value moves one-for-one with the price
+    Equity {
+        symbol: String,
+        quantity: f64,
+        price: f64,
+    },
+    /// Option contracts, repriced from their greeks
+    Option {
+        /// Option contract symbol
+        symbol: String,
+        /// Symbol of the underlying, used to pick the price shock
+        underlying: String,
+        /// Contracts held (negative when short)
+        quantity: f64,
+        price: f64,
+        underlying_price: f64,
+        delta: f64,
+        gamma: f64,
+        /// Price change per one point (0.01) of implied volatility
+        vega: f64,
+    },
+}
+
+impl RiskPosition {
+    /// Equity position from an account position; `None` for options or
+    /// positions without a ticker
+    pub fn from_position(position: &Position) -> Option<Self> {
+        let ticker = position.ticker.as_ref()?;
+        if position
+            .asset_type
+            .as_deref()
+            .is_some_and(|t| t.eq_ignore_ascii_case("option"))
+        {
+            return None;
+        }
+        Some(RiskPosition::Equity {
+            symbol: ticker.symbol.clone(),
+            quantity: position.quantity,
+            price: position.last_price,
+        })
+    }
+
+    /// Option position from a contract quote with greeks
+    ///
+    /// Missing greeks count as zero, so the contract only contributes its
+    /// current value.
+    pub fn option(
+        contract: &OptionContract,
+        underlying: impl Into<String>,
+        underlying_price: f64,
+        quantity: f64,
+    ) -> Self {
+        let price = match (contract.bid, contract.ask) {
+            (Some(bid), Some(ask)) if bid > 0.0 && ask > 0.0 => (bid + ask) / 2.0,
+            _ => contract.last_price.unwrap_or_default(),
+        };
+        RiskPosition::Option {
+            symbol: contract.symbol.clone(),
+            underlying: underlying.into(),
+            quantity,
+            price,
+            underlying_price,
+            delta: contract.delta.unwrap_or_default(),
+            gamma: contract.gamma.unwrap_or_default(),
+            vega: contract.vega.unwrap_or_default(),
+        }
+    }
+
+    pub fn symbol(&self) -> &str {
+        match self {
+            RiskPosition::Equity { symbol, .. } | RiskPosition::Option { symbol, .. } => symbol,
+        }
+    }
+
+    /// Current market value
+    pub fn value(&self) -> f64 {
+        match self {
+            RiskPosition::Equity {
+                quantity, price, ..
+            } => quantity * price,
+            RiskPosition::Option {
+                quantity, price, ..
+            } => quantity * price * OPTION_MULTIPLIER,
+        }
+    }
+
+    /// Change in value under a scenario
+    ///
+    /// Options use a second-order delta/gamma approximation plus vega, so
+    /// large shocks are less accurate than a full reprice. An option's value
+    /// is never taken below zero.
+    pub fn pnl(&self, scenario: &Scenario) -> f64 {
+        match self {
+            RiskPosition::Equity {
+                symbol,
+                quantity,
+                price,
+            } => quantity * price * scenario.price_shock_for(symbol),
+            RiskPosition::Option {
+                underlying,
+                quantity,
+                price,
+                underlying_price,
+                delta,
+                gamma,
+                vega,
+                ..
+            } => {
+                let shift = underlying_price * scenario.price_shock_for(underlying);
+                let change =
+                    delta * shift + 0.5 * gamma * shift * shift + vega * scenario.vol_shock * 100.0;
+                let change = change.max(-price);
+                quantity * change * OPTION_MULTIPLIER
+            }
+        }
+    }
+}
+
+/// Shocks applied together in one scenario
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Scenario {
+    pub name: String,
+    /// Relative price move for every underlying (-0.1 = down 10%)
+    pub price_shock: f64,
+    /// Absolute change in implied volatility (0.05 = up 5 vol points)
+    pub vol_shock: f64,
+    /// Price moves that replace `price_shock` for specific underlyings
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub symbol_shocks: BTreeMap<String, f64>,
+}
+
+impl Scenario {
+    pub fn new(price_shock: f64, vol_shock: f64) -> Self {
+        Self {
+            name: format!(
+                "price {:+.1}% / vol {:+.1}",
+                price_shock * 100.0,
+                vol_shock * 100.0
+            ),
+            price_shock,
+            vol_shock,
+            symbol_shocks: BTreeMap::new(),
+        }
+    }
+
+    /// Rename the scenario
+    pub fn named(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Shock one underlying by a different amount
+    pub fn symbol_shock(mut self, symbol: &str, price_shock: f64) -> Self {
+        self.symbol_shocks
+            .insert(symbol.to_ascii_uppercase(), price_shock);
+        self
+    }
+
+    fn price_shock_for(&self, symbol: &str) -> f64 {
+        self.symbol_shocks
+            .get(&symbol.to_ascii_uppercase())
+            .copied()
+            .unwrap_or(self.price_shock)
+    }
+
+    /// Every combination of the given price and volatility shocks
+    pub fn grid(price_shocks: &[f64], vol_shocks: &[f64]) -> Vec<Self> {
+        price_shocks
+            .iter()
+            .flat_map(|&price| vol_shocks.iter().map(move |&vol| Self::new(price, vol)))
+            .collect()
+    }
+}
+
+/// P&L of the portfolio under one scenario
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioResult {
+    pub scenario: Scenario,
+    pub value: f64,
+    pub pnl: f64,
+    /// P&L by position symbol
+    pub by_position: BTreeMap<String, f64>,
+}
+
+/// Scenario P&L table for a portfolio
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioTable {
+    pub base_value: f64,
+    pub rows: Vec<ScenarioResult>,
+}
+
+impl ScenarioTable {
+    /// Run every scenario against the positions
+    pub fn simulate(positions: &[RiskPosition], scenarios: &[Scenario]) -> Self {
+        let base_value = positions.iter().map(RiskPosition::value).sum();
+        let rows = scenarios
+            .iter()
+            .map(|scenario| {
+                let mut by_position = BTreeMap::new();
+                for position in positions {
+                    *by_position
+                        .entry(position.symbol().to_string())
+                        .or_insert(0.0) += position.pnl(scenario);
+                }
+                let pnl = by_position.values().sum();
+                ScenarioResult {
+                    scenario: scenario.clone(),
+                    value: base_value + pnl,
+                    pnl,
+                    by_position,
+                }
+            })
+            .collect();
+        Self { base_value, rows }
+    }
+
+    /// Scenario with the largest loss
+    pub fn worst(&self) -> Option<&ScenarioResult> {
+        self.rows.iter().min_by(|a, b| a.pnl.total_cmp(&b.pnl))
+    }
+
+    /// Whether no scenario loses more than `max_loss`
+    pub fn within_loss_limit(&self, max_loss: f64) -> bool {
+        self.worst()
+            .is_none_or(|worst| worst.pnl >= -max_loss.abs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(delta: f64, gamma: f64, vega: f64) -> RiskPosition {
+        RiskPosition::Option {
+            symbol: "AAPL240719C00150000".to_string(),
+            underlying: "AAPL".to_string(),
+            quantity: 2.0,
+            price: 5.0,
+            underlying_price: 150.0,
+            delta,
+            gamma,
+            vega,
+        }
+    }
+
+    #[test]
+    fn test_equity_and_option_scenario_pnl() {
+        let positions = vec![
+            RiskPosition::Equity {
+                symbol: "AAPL".to_string(),
+                quantity: 100.0,
+                price: 150.0,
+            },
+            call(0.5, 0.02, 0.15),
+        ];
+        let scenarios = Scenario::grid(&[-0.1, 0.0], &[0.0, 0.05]);
+        let table = ScenarioTable::simulate(&positions, &scenarios);
+        assert_eq!(table.rows.len(), 4);
+        assert_eq!(table.base_value, 15_000.0 + 1_000.0);
+
+        // Down 10%, unchanged vol: equity -1500; option move -15 gives
+        // 0.5*-15 + 0.5*0.02*225 = -5.25, floored at -5 per contract
+        let down = &table.rows[0];
+        assert!((down.by_position["AAPL"] + 1500.0).abs() < 1e-9);
+        assert!((down.by_position["AAPL240719C00150000"] + 1000.0).abs() < 1e-9);
+        assert_eq!(table.worst().unwrap().scenario, down.scenario);
+
+        // Flat price, vol up 5 points: 0.15 * 5 * 2 contracts * 100
+        let vol_up = &table.rows[3];
+        assert!((vol_up.pnl - 150.0).abs() < 1e-9);
+
+        assert!(table.within_loss_limit(2500.0));
+        assert!(!table.within_loss_limit(2000.0));
+    }
+
+    #[test]
+    fn test_symbol_shock_overrides_global() {
+        let positions = vec![
+            RiskPosition::Equity {
+                symbol: "AAPL".to_string(),
+                quantity: 10.0,
+                price: 100.0,
+            },
+            RiskPosition::Equity {
+                symbol: "SPY".to_string(),
+                quantity: 10.0,
+                price: 100.0,
+            },
+        ];
+        let scenario = Scenario::new(-0.05, 0.0)
+            .symbol_shock("aapl", -0.2)
+            .named("AAPL earnings miss");
+        let table = ScenarioTable::simulate(&positions, &[scenario]);
+        let row = &table.rows[0];
+        assert!((row.by_position["AAPL"] + 200.0).abs() < 1e-9);
+        assert!((row.by_position["SPY"] + 50.0).abs() < 1e-9);
+        assert_eq!(row.scenario.name, "AAPL earnings miss");
+    }
+}
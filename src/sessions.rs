@@ -0,0 +1,268 @@
+// Many logged-in clients behind one lookup, with central token refresh
+
+use crate::{
+    client::WebullClient,
+    error::{Result, WebullError},
+};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A client shared between tasks; refreshing the token needs the write lock
+pub type SharedClient = Arc<tokio::sync::RwLock<WebullClient>>;
+
+#[derive(Debug)]
+struct Session {
+    client: SharedClient,
+    account_id: Option<String>,
+}
+
+/// Outcome of a `SessionManager::refresh_expiring` pass
+#[derive(Debug, Default)]
+pub struct RefreshReport {
+    /// Tenants whose token was refreshed
+    pub refreshed: Vec<String>,
+    /// Tenants whose refresh failed; they need to log in again
+    pub failed: Vec<(String, WebullError)>,
+}
+
+/// Authenticated clients for several users, keyed by a tenant name
+///
+/// Each tenant gets its own client and so its own rate limiter: one busy
+/// account can't use up another's request budget. Tokens close to expiry
+/// are refreshed by calling `refresh_expiring` periodically.
+///
+/// ```no_run
+/// use webull_unofficial::{sessions::SessionManager, WebullClient};
+///
+/// # async fn run() -> webull_unofficial::Result<()> {
+/// let sessions = SessionManager::new().tenant_rate_limit(2.0);
+/// let mut client = WebullClient::new_live(None)?;
+/// client
+///     .login("alice@example.com", "password", None, None, None, None)
+///     .await?
+///     .into_success()?;
+/// client.get_account_id().await?;
+/// sessions.insert("alice", client)?;
+///
+/// if let Some(client) = sessions.get("alice") {
+///     let account = client.read().await.get_account().await?;
+///     println!("{:?}", account.net_liquidation);
+/// }
+/// sessions.refresh_expiring().await;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SessionManager {
+    sessions: RwLock<HashMap<String, Session>>,
+    refresh_before: Duration,
+    rate_limit: Option<f64>,
+}
+
+impl Default for SessionManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionManager {
+    /// Refresh tokens within 10 minutes of expiry; no rate limit
+    pub fn new() -> Self {
+        Self {
+            sessions: RwLock::new(HashMap::new()),
+            refresh_before: Duration::from_secs(600),
+            rate_limit: None,
+        }
+    }
+
+    /// Limit each tenant's client to `requests_per_sec` per endpoint class
+    pub fn tenant_rate_limit(mut self, requests_per_sec: f64) -> Self {
+        self.rate_limit = Some(requests_per_sec);
+        self
+    }
+
+    /// Refresh tokens this long before they expire
+    pub fn refresh_before(mut self, margin: Duration) -> Self {
+        self.refresh_before = margin;
+        self
+    }
+
+    /// Add or replace a tenant's client
+    ///
+    /// The client is indexed by its account ID if it already has one (see
+    /// `WebullClient::get_account_id`); otherwise call `index_accounts`
+    /// once it does. Fails if the tenant rate limit isn't a positive number.
+    pub fn insert(
+        &self,
+        tenant: impl Into<String>,
+        mut client: WebullClient,
+    ) -> Result<SharedClient> {
+        if let Some(requests_per_sec) = self.rate_limit {
+            client.set_rate_limit(requests_per_sec)?;
+        }
+        let account_id = client.get_account_id_str();
+        let client = Arc::new(tokio::sync::RwLock::new(client));
+        self.sessions.write().insert(
+            tenant.into(),
+            Session {
+                client: client.clone(),
+                account_id,
+            },
+        );
+        Ok(client)
+    }
+
+    pub fn remove(&self, tenant: &str) -> Option<SharedClient> {
+        self.sessions
+            .write()
+            .remove(tenant)
+            .map(|session| session.client)
+    }
+
+    pub fn get(&self, tenant: &str) -> Option<SharedClient> {
+        self.sessions
+            .read()
+            .get(tenant)
+            .map(|session| session.client.clone())
+    }
+
+    /// Tenant holding `account_id`
+    pub fn tenant_for_account(&self, account_id: &str) -> Option<String> {
+        self.sessions
+            .read()
+            .iter()
+            .find(|(_, session)| session.account_id.as_deref() == Some(account_id))
+            .map(|(tenant, _)| tenant.clone())
+    }
+
+    /// Client for the tenant holding `account_id`
+    pub fn by_account(&self, account_id: &str) -> Option<SharedClient> {
+        self.sessions
+            .read()
+            .values()
+            .find(|session| session.account_id.as_deref() == Some(account_id))
+            .map(|session| session.client.clone())
+    }
+
+    /// Tenant names, sorted
+    pub fn tenants(&self) -> Vec<String> {
+        let mut tenants: Vec<String> = self.sessions.read().keys().cloned().collect();
+        tenants.sort();
+        tenants
+    }
+
+    pub fn len(&self) -> usize {
+        self.sessions.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sessions.read().is_empty()
+    }
+
+    /// Re-read each client's account ID into the lookup index
+    pub async fn index_accounts(&self) {
+        for (tenant, client) in self.clients() {
+            let account_id = client.read().await.get_account_id_str();
+            if let Some(session) = self.sessions.write().get_mut(&tenant) {
+                session.account_id = account_id;
+            }
+        }
+    }
+
+    /// Tenants whose token expires within the refresh margin of `now`
+    /// (Unix seconds), sorted; sessions without a known expiry are skipped
+    pub async fn due_for_refresh(&self, now: i64) -> Vec<String> {
+        let deadline = now + self.refresh_before.as_secs() as i64;
+        let mut due = Vec::new();
+        for (tenant, client) in self.clients() {
+            let expires_at = client.read().await.token_expires_at();
+            if expires_at.is_some_and(|expires_at| expires_at <= deadline) {
+                due.push(tenant);
+            }
+        }
+        due.sort();
+        due
+    }
+
+    /// Refresh every token close to expiry
+    ///
+    /// Tenants are refreshed one at a time, each holding only its own
+    /// client's write lock.
+    pub async fn refresh_expiring(&self) -> RefreshReport {
+        let mut report = RefreshReport::default();
+        for tenant in self.due_for_refresh(chrono::Utc::now().timestamp()).await {
+            let client = match self.get(&tenant) {
+                Some(client) => client,
+                None => continue,
+            };
+            let result = client.write().await.refresh_login().await;
+            match result {
+                Ok(_) => report.refreshed.push(tenant),
+                Err(e) => {
+                    log::warn!("Token refresh failed for tenant {}: {}", tenant, e);
+                    report.failed.push((tenant, e));
+                }
+            }
+        }
+        report
+    }
+
+    fn clients(&self) -> Vec<(String, SharedClient)> {
+        self.sessions
+            .read()
+            .iter()
+            .map(|(tenant, session)| (tenant.clone(), session.client.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(account_id: Option<&str>, token_expire: Option<i64>) -> WebullClient {
+        let mut client = WebullClient::new_live(None).unwrap();
+        if let WebullClient::Live(live) = &mut client {
+            live.account_id = account_id.map(String::from);
+            live.token_expire = token_expire;
+        }
+        client
+    }
+
+    #[tokio::test]
+    async fn test_lookup_and_refresh_schedule() {
+        let sessions = SessionManager::new()
+            .tenant_rate_limit(2.0)
+            .refresh_before(Duration::from_secs(300));
+        sessions
+            .insert("alice", client(Some("111"), Some(1_000_000)))
+            .unwrap();
+        // Expires in 2033
+        sessions
+            .insert("bob", client(Some("222"), Some(2_000_000_000)))
+            .unwrap();
+        let carol = sessions.insert("carol", client(None, None)).unwrap();
+
+        assert_eq!(sessions.tenants(), vec!["alice", "bob", "carol"]);
+        assert_eq!(sessions.tenant_for_account("222").as_deref(), Some("bob"));
+        assert!(sessions.by_account("333").is_none());
+        match &*carol.read().await {
+            WebullClient::Live(live) => assert!(live.rate_limiter.is_some()),
+            WebullClient::Paper(_) => unreachable!(),
+        }
+
+        if let WebullClient::Live(live) = &mut *carol.write().await {
+            live.account_id = Some("333".to_string());
+        }
+        sessions.index_accounts().await;
+        assert!(sessions.by_account("333").is_some());
+
+        assert_eq!(sessions.due_for_refresh(999_800).await, vec!["alice"]);
+        assert!(sessions.due_for_refresh(999_600).await.is_empty());
+
+        assert!(sessions.remove("alice").is_some());
+        assert_eq!(sessions.len(), 2);
+    }
+}
@@ -0,0 +1,68 @@
+// Graceful shutdown support for services built on the client
+
+/// What to do when shutting a client down
+#[derive(Debug, Clone)]
+pub struct ShutdownPolicy {
+    pub cancel_open_orders: bool,
+    pub close_streams: bool,
+    pub logout: bool,
+}
+
+impl ShutdownPolicy {
+    /// Close streams and log out, leaving open orders in place
+    pub fn new() -> Self {
+        Self {
+            cancel_open_orders: false,
+            close_streams: true,
+            logout: true,
+        }
+    }
+
+    /// Close streams, cancel every open order, and log out
+    pub fn flatten() -> Self {
+        Self::new().cancel_open_orders(true)
+    }
+
+    /// Cancel all open orders before logging out
+    pub fn cancel_open_orders(mut self, enabled: bool) -> Self {
+        self.cancel_open_orders = enabled;
+        self
+    }
+
+    /// Disconnect stream connections
+    pub fn close_streams(mut self, enabled: bool) -> Self {
+        self.close_streams = enabled;
+        self
+    }
+
+    /// Revoke the session
+    pub fn logout(mut self, enabled: bool) -> Self {
+        self.logout = enabled;
+        self
+    }
+}
+
+impl Default for ShutdownPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Summary of the actions taken during shutdown
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownReport {
+    pub streams_closed: usize,
+    pub cancelled_orders: Vec<String>,
+    /// Orders that could not be cancelled, with the reason
+    pub failed_cancellations: Vec<(String, String)>,
+    pub logged_out: bool,
+    /// Non-fatal errors encountered along the way
+    pub errors: Vec<String>,
+}
+
+impl ShutdownReport {
+    /// True if every requested step completed
+    pub fn is_clean(&self) -> bool {
+        self.failed_cancellations.is_empty() && self.errors.is_empty()
+    }
+}
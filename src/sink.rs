@@ -0,0 +1,482 @@
+// Batched PostgreSQL/TimescaleDB persistence for streamed quotes and trades
+
+pub use crate::stream::{MarketEvent, QuoteEvent, TradeEvent};
+
+use crate::error::{Result, WebullError};
+use chrono::Utc;
+use serde_json::Value;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use sqlx::{Postgres, QueryBuilder};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Postgres allows at most this many bind parameters per statement
+const MAX_BINDS: usize = 65_535;
+
+/// Where and how the sink writes
+///
+/// ```no_run
+/// use webull_unofficial::sink::{SqlSink, SqlSinkConfig};
+///
+/// # async fn run() -> webull_unofficial::Result<()> {
+/// let config = SqlSinkConfig::new("postgres://localhost/market")
+///     .timescale(true)
+///     .batch_size(1000);
+/// let sink = SqlSink::connect(config).await?;
+/// sink.migrate().await?;
+/// let handle = sink.spawn();
+/// // stream.set_price_callback(handle.price_callback());
+/// # handle.close().await;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlSinkConfig {
+    pub database_url: String,
+    /// Schema holding both tables (default `public`)
+    pub schema: String,
+    /// Default `webull_quotes`
+    pub quotes_table: String,
+    /// Default `webull_trades`
+    pub trades_table: String,
+    /// Rows per insert statement (default 500)
+    pub batch_size: usize,
+    /// Longest an event waits in the buffer (default 1 second)
+    pub flush_interval: Duration,
+    /// Events the background writer queues before dropping new ones
+    /// (default 10,000)
+    pub channel_capacity: usize,
+    /// Make both tables TimescaleDB hypertables on `time`
+    pub timescale: bool,
+    pub max_connections: u32,
+}
+
+impl SqlSinkConfig {
+    pub fn new(database_url: impl Into<String>) -> Self {
+        Self {
+            database_url: database_url.into(),
+            schema: "public".to_string(),
+            quotes_table: "webull_quotes".to_string(),
+            trades_table: "webull_trades".to_string(),
+            batch_size: 500,
+            flush_interval: Duration::from_secs(1),
+            channel_capacity: 10_000,
+            timescale: false,
+            max_connections: 2,
+        }
+    }
+
+    pub fn schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = schema.into();
+        self
+    }
+
+    pub fn tables(mut self, quotes: impl Into<String>, trades: impl Into<String>) -> Self {
+        self.quotes_table = quotes.into();
+        self.trades_table = trades.into();
+        self
+    }
+
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn flush_interval(mut self, interval: Duration) -> Self {
+        self.flush_interval = interval;
+        self
+    }
+
+    pub fn channel_capacity(mut self, capacity: usize) -> Self {
+        self.channel_capacity = capacity.max(1);
+        self
+    }
+
+    pub fn timescale(mut self, enabled: bool) -> Self {
+        self.timescale = enabled;
+        self
+    }
+
+    pub fn max_connections(mut self, max: u32) -> Self {
+        self.max_connections = max.max(1);
+        self
+    }
+
+    /// Check that schema and table names are plain identifiers, since they
+    /// are spliced into SQL
+    pub fn validate(&self) -> Result<()> {
+        for name in [&self.schema, &self.quotes_table, &self.trades_table] {
+            let mut chars = name.chars();
+            let valid = chars
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                return Err(WebullError::InvalidParameter(format!(
+                    "'{}' is not a valid SQL identifier",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn quotes(&self) -> String {
+        format!("{}.{}", self.schema, self.quotes_table)
+    }
+
+    fn trades(&self) -> String {
+        format!("{}.{}", self.schema, self.trades_table)
+    }
+
+    /// Statements creating the schema, tables and indexes; all idempotent
+    pub fn migrations(&self) -> Result<Vec<String>> {
+        self.validate()?;
+        let mut statements = vec![
+            format!("CREATE SCHEMA IF NOT EXISTS {}", self.schema),
+            format!(
+                "CREATE TABLE IF NOT EXISTS {} (\
+                 ticker_id BIGINT NOT NULL, \
+                 time TIMESTAMPTZ NOT NULL, \
+                 price DOUBLE PRECISION NOT NULL, \
+                 bid DOUBLE PRECISION, \
+                 ask DOUBLE PRECISION, \
+                 volume DOUBLE PRECISION)",
+                self.quotes()
+            ),
+            format!(
+                "CREATE TABLE IF NOT EXISTS {} (\
+                 ticker_id BIGINT NOT NULL, \
+                 time TIMESTAMPTZ NOT NULL, \
+                 price DOUBLE PRECISION NOT NULL, \
+                 size DOUBLE PRECISION NOT NULL, \
+                 exchange TEXT, \
+                 condition TEXT)",
+                self.trades()
+            ),
+            format!(
+                "CREATE INDEX IF NOT EXISTS {0}_ticker_time_idx ON {1} (ticker_id, time DESC)",
+                self.quotes_table,
+                self.quotes()
+            ),
+            format!(
+                "CREATE INDEX IF NOT EXISTS {0}_ticker_time_idx ON {1} (ticker_id, time DESC)",
+                self.trades_table,
+                self.trades()
+            ),
+        ];
+        if self.timescale {
+            statements.push("CREATE EXTENSION IF NOT EXISTS timescaledb".to_string());
+            for table in [self.quotes(), self.trades()] {
+                statements.push(format!(
+                    "SELECT create_hypertable('{}', 'time', if_not_exists => TRUE, migrate_data => TRUE)",
+                    table
+                ));
+            }
+        }
+        Ok(statements)
+    }
+}
+
+/// Buffers market events and writes them in batches
+#[derive(Debug)]
+pub struct SqlSink {
+    pool: PgPool,
+    config: SqlSinkConfig,
+    quotes: Vec<QuoteEvent>,
+    trades: Vec<TradeEvent>,
+}
+
+impl SqlSink {
+    pub async fn connect(config: SqlSinkConfig) -> Result<Self> {
+        config.validate()?;
+        let pool = PgPoolOptions::new()
+            .max_connections(config.max_connections)
+            .connect(&config.database_url)
+            .await?;
+        Self::with_pool(pool, config)
+    }
+
+    /// Use an existing connection pool
+    pub fn with_pool(pool: PgPool, config: SqlSinkConfig) -> Result<Self> {
+        config.validate()?;
+        Ok(Self {
+            pool,
+            config,
+            quotes: Vec::new(),
+            trades: Vec::new(),
+        })
+    }
+
+    /// Create the tables if they don't exist yet
+    pub async fn migrate(&self) -> Result<()> {
+        for statement in self.config.migrations()? {
+            sqlx::query(&statement).execute(&self.pool).await?;
+        }
+        Ok(())
+    }
+
+    /// Events waiting to be written
+    pub fn buffered(&self) -> usize {
+        self.quotes.len() + self.trades.len()
+    }
+
+    /// Buffer an event, writing the buffer once it reaches the batch size
+    pub async fn push(&mut self, event: MarketEvent) -> Result<()> {
+        match event {
+            MarketEvent::Quote(quote) => self.quotes.push(quote),
+            MarketEvent::Trade(trade) => self.trades.push(trade),
+            MarketEvent::StaleData(_) => return Ok(()),
+        }
+        if self.buffered() >= self.config.batch_size {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Write everything buffered, returning the number of rows
+    ///
+    /// Quotes and trades are written separately. Rows from a batch that
+    /// fails to insert stay buffered for the next flush, up to
+    /// `channel_capacity` per table with the oldest dropped beyond that; the
+    /// first error is returned.
+    pub async fn flush(&mut self) -> Result<usize> {
+        let quotes = self.flush_quotes().await;
+        let trades = self.flush_trades().await;
+        Ok(quotes? + trades?)
+    }
+
+    async fn flush_quotes(&mut self) -> Result<usize> {
+        let mut quotes = std::mem::take(&mut self.quotes);
+        let mut written = 0;
+        let mut failure = None;
+
+        for chunk in quotes.chunks(MAX_BINDS / 6) {
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(format!(
+                "INSERT INTO {} (ticker_id, time, price, bid, ask, volume) ",
+                self.config.quotes()
+            ));
+            query.push_values(chunk, |mut row, quote| {
+                row.push_bind(quote.ticker_id)
+                    .push_bind(quote.time)
+                    .push_bind(quote.price)
+                    .push_bind(quote.bid)
+                    .push_bind(quote.ask)
+                    .push_bind(quote.volume);
+            });
+            if let Err(e) = query.build().execute(&self.pool).await {
+                failure = Some(e);
+                break;
+            }
+            written += chunk.len();
+        }
+
+        match failure {
+            Some(e) => {
+                self.quotes = quotes.split_off(written);
+                keep_newest(&mut self.quotes, self.config.channel_capacity);
+                Err(e.into())
+            }
+            None => Ok(written),
+        }
+    }
+
+    async fn flush_trades(&mut self) -> Result<usize> {
+        let mut trades = std::mem::take(&mut self.trades);
+        let mut written = 0;
+        let mut failure = None;
+
+        for chunk in trades.chunks(MAX_BINDS / 6) {
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(format!(
+                "INSERT INTO {} (ticker_id, time, price, size, exchange, condition) ",
+                self.config.trades()
+            ));
+            query.push_values(chunk, |mut row, trade| {
+                row.push_bind(trade.ticker_id)
+                    .push_bind(trade.time)
+                    .push_bind(trade.price)
+                    .push_bind(trade.size)
+                    .push_bind(trade.exchange.as_deref())
+                    .push_bind(trade.condition.as_deref());
+            });
+            if let Err(e) = query.build().execute(&self.pool).await {
+                failure = Some(e);
+                break;
+            }
+            written += chunk.len();
+        }
+
+        match failure {
+            Some(e) => {
+                self.trades = trades.split_off(written);
+                keep_newest(&mut self.trades, self.config.channel_capacity);
+                Err(e.into())
+            }
+            None => Ok(written),
+        }
+    }
+
+    /// Move the sink onto a background task fed through a channel
+    ///
+    /// The buffer is written whenever it fills and at every flush interval.
+    /// Write errors are logged and the writer carries on.
+    pub fn spawn(mut self) -> SinkHandle {
+        let (sender, mut receiver) = mpsc::channel(self.config.channel_capacity);
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.config.flush_interval);
+            loop {
+                tokio::select! {
+                    event = receiver.recv() => match event {
+                        Some(event) => {
+                            if let Err(e) = self.push(event).await {
+                                log::error!("Market data sink write failed: {}", e);
+                            }
+                        }
+                        None => break,
+                    },
+                    _ = ticker.tick() => {
+                        if let Err(e) = self.flush().await {
+                            log::error!("Market data sink write failed: {}", e);
+                        }
+                    }
+                }
+            }
+            if let Err(e) = self.flush().await {
+                log::error!("Market data sink final write failed: {}", e);
+            }
+        });
+        SinkHandle { sender, task }
+    }
+}
+
+/// Drop the oldest rows so that at most `limit` stay buffered
+fn keep_newest<T>(rows: &mut Vec<T>, limit: usize) {
+    if rows.len() > limit {
+        let excess = rows.len() - limit;
+        log::warn!("Market data sink backlog full, dropping {} rows", excess);
+        rows.drain(..excess);
+    }
+}
+
+/// Sends events to a spawned `SqlSink`
+#[derive(Debug)]
+pub struct SinkHandle {
+    sender: mpsc::Sender<MarketEvent>,
+    task: JoinHandle<()>,
+}
+
+impl SinkHandle {
+    /// Queue an event without waiting; returns `false` if the queue is full
+    /// and the event was dropped
+    pub fn send(&self, event: MarketEvent) -> bool {
+        match self.sender.try_send(event) {
+            Ok(()) => true,
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                log::warn!("Market data sink queue full, dropping event");
+                false
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        }
+    }
+
+    /// A price callback for `StreamConn::set_price_callback` feeding this sink
+    pub fn price_callback(&self) -> impl Fn(Value, Value) + Send + Sync + 'static {
+        let sender = self.sender.clone();
+        move |topic, data| {
+            if let Some(event) = MarketEvent::from_push(&topic, &data, Utc::now()) {
+                let _ = sender.try_send(event);
+            }
+        }
+    }
+
+    /// Stop accepting events and wait for the last batch to be written
+    ///
+    /// Callbacks from `price_callback` keep the writer alive until they are
+    /// dropped too.
+    pub async fn close(self) {
+        drop(self.sender);
+        let _ = self.task.await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+    use serde_json::json;
+
+    #[test]
+    fn test_push_normalization() {
+        let received = DateTime::from_timestamp(1_718_990_000, 0).unwrap();
+        let quote = MarketEvent::from_push(
+            &json!({"tickerId": "913256135", "type": 102}),
+            &json!({"price": "189.50", "volume": "1000",
+                    "bidList": [{"price": "189.49", "volume": "3"}],
+                    "askList": [{"price": "189.52", "volume": "1"}]}),
+            received,
+        )
+        .unwrap();
+        assert_eq!(
+            quote,
+            MarketEvent::Quote(QuoteEvent {
+                ticker_id: 913256135,
+                time: received,
+                price: 189.5,
+                bid: Some(189.49),
+                ask: Some(189.52),
+                volume: Some(1000.0),
+            })
+        );
+
+        let trade = MarketEvent::from_push(
+            &json!({"tickerId": 913256135, "type": 103}),
+            &json!({"deal": 189.51, "volume": 200, "tradeTime": 1718990001500i64, "trdEx": "NSDQ"}),
+            received,
+        )
+        .unwrap();
+        match trade {
+            MarketEvent::Trade(trade) => {
+                assert_eq!(trade.size, 200.0);
+                assert_eq!(trade.time.timestamp_millis(), 1_718_990_001_500);
+                assert_eq!(trade.exchange.as_deref(), Some("NSDQ"));
+            }
+            other => panic!("expected a trade, got {:?}", other),
+        }
+
+        assert!(
+            MarketEvent::from_push(&json!({"type": 102}), &json!({"price": 1}), received).is_none()
+        );
+    }
+
+    #[test]
+    fn test_failed_rows_keep_newest() {
+        let mut rows: Vec<i32> = (1..=5).collect();
+        keep_newest(&mut rows, 10);
+        assert_eq!(rows, vec![1, 2, 3, 4, 5]);
+        keep_newest(&mut rows, 3);
+        assert_eq!(rows, vec![3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_migrations() {
+        let config = SqlSinkConfig::new("postgres://localhost/market")
+            .schema("market")
+            .timescale(true);
+        let statements = config.migrations().unwrap();
+        assert_eq!(statements[0], "CREATE SCHEMA IF NOT EXISTS market");
+        assert!(statements[1].starts_with("CREATE TABLE IF NOT EXISTS market.webull_quotes ("));
+        assert!(statements
+            .iter()
+            .any(|s| s.contains("create_hypertable('market.webull_trades'")));
+
+        let bad = config.tables("quotes; DROP TABLE x", "trades");
+        assert!(bad.migrations().is_err());
+
+        // A pool of its own doesn't skip the identifier check
+        let pool = PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/market")
+            .unwrap();
+        assert!(SqlSink::with_pool(pool, bad).is_err());
+    }
+}
@@ -0,0 +1,204 @@
+// Local history of account snapshots, stored as JSON lines
+
+use crate::{
+    error::{Result, WebullError},
+    fx::FxRateCache,
+    models::{AccountDetail, Order, Position},
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::ops::RangeBounds;
+use std::path::{Path, PathBuf};
+
+/// Point-in-time copy of the account, its positions and open orders
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSnapshot {
+    pub taken_at: DateTime<Utc>,
+    /// Net liquidation value at the time of the snapshot
+    pub account_value: Option<f64>,
+    pub account: AccountDetail,
+    pub positions: Vec<Position>,
+    pub open_orders: Vec<Order>,
+    /// At-the-money implied volatility by ticker ID, for IV rank history
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub implied_volatility: BTreeMap<String, f64>,
+}
+
+impl AccountSnapshot {
+    /// Build a snapshot stamped with the current time
+    pub fn new(account: AccountDetail, positions: Vec<Position>, open_orders: Vec<Order>) -> Self {
+        Self {
+            taken_at: Utc::now(),
+            account_value: account.net_liquidation,
+            account,
+            positions,
+            open_orders,
+            implied_volatility: BTreeMap::new(),
+        }
+    }
+
+    /// UTC date the snapshot was taken
+    pub fn date(&self) -> NaiveDate {
+        self.taken_at.date_naive()
+    }
+
+    /// Account value converted to `currency` at the rate on the snapshot date
+    ///
+    /// Accounts without a currency are taken to be in USD. `None` if the
+    /// rate is missing from the cache.
+    pub fn account_value_in(&self, currency: &str, rates: &FxRateCache) -> Option<f64> {
+        let from = self.account.currency.as_deref().unwrap_or("USD");
+        rates.convert(self.account_value?, from, currency, self.date())
+    }
+}
+
+/// Append-only file of account snapshots, one JSON document per line
+#[derive(Debug)]
+pub struct SnapshotStore {
+    path: PathBuf,
+    // Serializes appends from concurrent snapshot jobs
+    write_lock: Mutex<()>,
+}
+
+impl SnapshotStore {
+    /// Open (or lazily create) a store at the given file path
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Get the backing file path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a snapshot to the store
+    pub fn append(&self, snapshot: &AccountSnapshot) -> Result<()> {
+        let line = serde_json::to_string(snapshot)
+            .map_err(|e| WebullError::SerializationError(e.to_string()))?;
+
+        let _guard = self.write_lock.lock();
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+
+    /// Load snapshots whose UTC date falls within `range`, oldest first
+    pub fn load<R: RangeBounds<NaiveDate>>(&self, range: R) -> Result<Vec<AccountSnapshot>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = fs::File::open(&self.path)?;
+        let mut snapshots = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let snapshot: AccountSnapshot = serde_json::from_str(&line)?;
+            if range.contains(&snapshot.date()) {
+                snapshots.push(snapshot);
+            }
+        }
+
+        snapshots.sort_by_key(|s| s.taken_at);
+        Ok(snapshots)
+    }
+
+    /// Implied volatility recorded for a ticker on dates within `range`, oldest first
+    pub fn iv_history<R: RangeBounds<NaiveDate>>(
+        &self,
+        ticker_id: &str,
+        range: R,
+    ) -> Result<Vec<(NaiveDate, f64)>> {
+        Ok(self
+            .load(range)?
+            .iter()
+            .filter_map(|s| {
+                s.implied_volatility
+                    .get(ticker_id)
+                    .map(|&iv| (s.date(), iv))
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn snapshot(day: u32, value: f64) -> AccountSnapshot {
+        let account: AccountDetail = serde_json::from_value(json!({
+            "secAccountId": 12345678,
+            "netLiquidation": value.to_string()
+        }))
+        .unwrap();
+        let position: Position = serde_json::from_value(json!({
+            "position": "10",
+            "costPrice": "150.25",
+            "cost": "1502.50",
+            "marketValue": "1600.00",
+            "lastPrice": "160.00",
+            "unrealizedProfitLoss": "97.50"
+        }))
+        .unwrap();
+
+        let mut snapshot = AccountSnapshot::new(account, vec![position], Vec::new());
+        snapshot.taken_at = Utc.with_ymd_and_hms(2024, 6, day, 20, 0, 0).unwrap();
+        snapshot
+    }
+
+    #[test]
+    fn test_append_and_load_range() {
+        let dir = std::env::temp_dir().join(format!("webull_snapshots_{}", uuid::Uuid::new_v4()));
+        let store = SnapshotStore::open(dir.join("snapshots.jsonl"));
+        assert!(store.load(..).unwrap().is_empty());
+
+        store.append(&snapshot(5, 10_500.0)).unwrap();
+        store.append(&snapshot(3, 10_000.0)).unwrap();
+        store.append(&snapshot(4, 10_250.0)).unwrap();
+
+        let all = store.load(..).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].account_value, Some(10_000.0));
+        assert_eq!(all[0].positions[0].quantity, 10.0);
+
+        let from = NaiveDate::from_ymd_opt(2024, 6, 4).unwrap();
+        let to = NaiveDate::from_ymd_opt(2024, 6, 4).unwrap();
+        let range = store.load(from..=to).unwrap();
+        assert_eq!(range.len(), 1);
+        assert_eq!(range[0].account_value, Some(10_250.0));
+        assert!(store.iv_history("913256135", ..).unwrap().is_empty());
+
+        let mut with_iv = snapshot(6, 10_600.0);
+        with_iv
+            .implied_volatility
+            .insert("913256135".to_string(), 0.31);
+        store.append(&with_iv).unwrap();
+        let history = store.iv_history("913256135", ..).unwrap();
+        assert_eq!(
+            history,
+            vec![(NaiveDate::from_ymd_opt(2024, 6, 6).unwrap(), 0.31)]
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
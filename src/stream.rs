@@ -1,11 +1,19 @@
+use crate::delta::interval_seconds;
+use crate::endpoints::Environment;
 use crate::error::{Result, WebullError};
+use crate::models::{Bar, BarInterval, Quote, TradeTick};
+use crate::order_book::DepthUpdate;
+use chrono::{DateTime, Utc};
 use log::{debug, error, info, warn};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tokio::time::{sleep, Duration};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Duration, Instant};
 
 /// Callback for handling price updates
 pub type PriceCallback = Arc<dyn Fn(Value, Value) + Send + Sync>;
@@ -13,6 +21,15 @@ pub type PriceCallback = Arc<dyn Fn(Value, Value) + Send + Sync>;
 /// Callback for handling order updates  
 pub type OrderCallback = Arc<dyn Fn(Value, Value) + Send + Sync>;
 
+/// Callback for handling depth-of-book updates (ticker ID, parsed update)
+pub type DepthCallback = Arc<dyn Fn(String, DepthUpdate) + Send + Sync>;
+
+/// Callback for tickers whose feed has gone quiet
+pub type StaleCallback = Arc<dyn Fn(StaleData) + Send + Sync>;
+
+/// Per-ticker channels handed out by `StreamConn::subscribe`
+type TickerChannels = Arc<RwLock<HashMap<String, mpsc::Sender<MarketEvent>>>>;
+
 /// Stream connection configuration
 #[derive(Debug, Clone)]
 pub struct StreamConfig {
@@ -20,8 +37,14 @@ pub struct StreamConfig {
     pub port: u16,
     pub use_ssl: bool,
     pub client_id: String,
+    /// MQTT ping interval; the connection drops if pings go unanswered
     pub keep_alive: Duration,
     pub debug: bool,
+    /// Buffered events per ticker channel; pushes beyond this are dropped
+    pub channel_capacity: usize,
+    /// Report a subscribed ticker as stale after this long without a
+    /// message; `None` turns the check off
+    pub stale_after: Option<Duration>,
 }
 
 impl Default for StreamConfig {
@@ -33,6 +56,26 @@ impl Default for StreamConfig {
             client_id: format!("rust_client_{}", uuid::Uuid::new_v4()),
             keep_alive: Duration::from_secs(30),
             debug: false,
+            channel_capacity: 1024,
+            stale_after: None,
+        }
+    }
+}
+
+impl StreamConfig {
+    /// Default settings connecting to `environment`'s streaming host
+    pub fn for_environment(environment: &Environment) -> Self {
+        let host = environment.stream_url();
+        let use_ssl = host.starts_with("wss://");
+        let port = url::Url::parse(&host)
+            .ok()
+            .and_then(|url| url.port_or_known_default())
+            .unwrap_or(443);
+        Self {
+            host,
+            port,
+            use_ssl,
+            ..Self::default()
         }
     }
 }
@@ -43,11 +86,70 @@ pub struct StreamConn {
     client: Option<AsyncClient>,
     price_callback: Option<PriceCallback>,
     order_callback: Option<OrderCallback>,
+    depth_callback: Option<DepthCallback>,
+    stale_callback: Option<StaleCallback>,
     total_volume: Arc<RwLock<HashMap<String, i64>>>,
     subscriptions: Arc<RwLock<Vec<String>>>,
+    ticker_channels: TickerChannels,
+    ticker_topics: HashMap<String, Vec<i32>>,
+    staleness: Arc<Mutex<Staleness>>,
+    last_heartbeat: Arc<RwLock<Option<Instant>>>,
+    watchdog: Option<JoinHandle<()>>,
     is_connected: Arc<RwLock<bool>>,
 }
 
+/// Where the event loop sends each incoming message
+struct Routes {
+    price_callback: Option<PriceCallback>,
+    order_callback: Option<OrderCallback>,
+    depth_callback: Option<DepthCallback>,
+    total_volume: Arc<RwLock<HashMap<String, i64>>>,
+    ticker_channels: TickerChannels,
+    staleness: Arc<Mutex<Staleness>>,
+}
+
+/// When each subscribed ticker last had a message
+#[derive(Debug, Default)]
+struct Staleness {
+    last_message: HashMap<String, Instant>,
+    // Already reported, until the next message arrives
+    reported: HashSet<String>,
+}
+
+impl Staleness {
+    /// Start watching `ticker_id`, counting silence from `now`
+    fn watch(&mut self, ticker_id: &str, now: Instant) {
+        self.last_message
+            .entry(ticker_id.to_string())
+            .or_insert(now);
+    }
+
+    fn forget(&mut self, ticker_id: &str) {
+        self.last_message.remove(ticker_id);
+        self.reported.remove(ticker_id);
+    }
+
+    fn touch(&mut self, ticker_id: &str, now: Instant) {
+        if let Some(last) = self.last_message.get_mut(ticker_id) {
+            *last = now;
+            self.reported.remove(ticker_id);
+        }
+    }
+
+    /// Tickers silent for at least `stale_after`, each reported once
+    fn check(&mut self, now: Instant, stale_after: Duration) -> Vec<(String, Duration)> {
+        let mut stale = Vec::new();
+        for (ticker_id, last) in &self.last_message {
+            let silent_for = now.saturating_duration_since(*last);
+            if silent_for >= stale_after && self.reported.insert(ticker_id.clone()) {
+                stale.push((ticker_id.clone(), silent_for));
+            }
+        }
+        stale.sort();
+        stale
+    }
+}
+
 impl StreamConn {
     /// Create a new streaming connection
     pub fn new(config: Option<StreamConfig>) -> Self {
@@ -56,8 +158,15 @@ impl StreamConn {
             client: None,
             price_callback: None,
             order_callback: None,
+            depth_callback: None,
+            stale_callback: None,
             total_volume: Arc::new(RwLock::new(HashMap::new())),
             subscriptions: Arc::new(RwLock::new(Vec::new())),
+            ticker_channels: Arc::new(RwLock::new(HashMap::new())),
+            ticker_topics: HashMap::new(),
+            staleness: Arc::new(Mutex::new(Staleness::default())),
+            last_heartbeat: Arc::new(RwLock::new(None)),
+            watchdog: None,
             is_connected: Arc::new(RwLock::new(false)),
         }
     }
@@ -78,6 +187,29 @@ impl StreamConn {
         self.order_callback = Some(Arc::new(callback));
     }
 
+    /// Set depth-of-book update callback
+    ///
+    /// Book messages are parsed into `DepthUpdate`s which can be fed into an `OrderBook`.
+    pub fn set_depth_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(String, DepthUpdate) + Send + Sync + 'static,
+    {
+        self.depth_callback = Some(Arc::new(callback));
+    }
+
+    /// Set the callback for tickers that stop sending messages
+    ///
+    /// Needs `StreamConfig::stale_after`. Each silence is reported once; the
+    /// ticker is reported again if it recovers and then goes quiet again.
+    /// Tickers with a channel from `subscribe` also get a
+    /// `MarketEvent::StaleData` on it.
+    pub fn set_stale_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(StaleData) + Send + Sync + 'static,
+    {
+        self.stale_callback = Some(Arc::new(callback));
+    }
+
     /// Connect to the streaming service
     pub async fn connect(&mut self, access_token: &str, did: &str) -> Result<()> {
         let mut mqtt_options = MqttOptions::new(
@@ -101,10 +233,16 @@ impl StreamConn {
 
         // Spawn event loop handler
         let is_connected = Arc::clone(&self.is_connected);
-        let price_callback = self.price_callback.clone();
-        let order_callback = self.order_callback.clone();
+        let routes = Routes {
+            price_callback: self.price_callback.clone(),
+            order_callback: self.order_callback.clone(),
+            depth_callback: self.depth_callback.clone(),
+            total_volume: Arc::clone(&self.total_volume),
+            ticker_channels: Arc::clone(&self.ticker_channels),
+            staleness: Arc::clone(&self.staleness),
+        };
         let debug = self.config.debug;
-        let total_volume = Arc::clone(&self.total_volume);
+        let last_heartbeat = Arc::clone(&self.last_heartbeat);
 
         tokio::spawn(async move {
             loop {
@@ -113,6 +251,9 @@ impl StreamConn {
                         if debug {
                             debug!("MQTT Event: {:?}", event);
                         }
+                        if let Event::Incoming(_) = event {
+                            *last_heartbeat.write() = Some(Instant::now());
+                        }
 
                         match event {
                             Event::Incoming(Packet::ConnAck(_)) => {
@@ -123,9 +264,7 @@ impl StreamConn {
                                 Self::handle_message(
                                     &publish.topic,
                                     &publish.payload,
-                                    &price_callback,
-                                    &order_callback,
-                                    &total_volume,
+                                    &routes,
                                     debug,
                                 );
                             }
@@ -145,6 +284,13 @@ impl StreamConn {
             }
         });
 
+        if let Some(stale_after) = self.config.stale_after {
+            if let Some(watchdog) = self.watchdog.take() {
+                watchdog.abort();
+            }
+            self.watchdog = Some(self.spawn_watchdog(stale_after));
+        }
+
         // Wait for connection
         let mut attempts = 0;
         while !*self.is_connected.read() && attempts < 10 {
@@ -193,15 +339,37 @@ impl StreamConn {
         }
     }
 
+    /// Check for quiet tickers every quarter of `stale_after`
+    fn spawn_watchdog(&self, stale_after: Duration) -> JoinHandle<()> {
+        let staleness = Arc::clone(&self.staleness);
+        let channels = Arc::clone(&self.ticker_channels);
+        let callback = self.stale_callback.clone();
+        let period = (stale_after / 4).max(Duration::from_millis(100));
+
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(period);
+            loop {
+                ticks.tick().await;
+                let stale = staleness.lock().check(Instant::now(), stale_after);
+                for (ticker_id, silent_for) in stale {
+                    warn!("No stream data for {} in {:?}", ticker_id, silent_for);
+                    let data = StaleData {
+                        ticker_id: ticker_id.clone(),
+                        silent_for,
+                    };
+                    if let Some(sender) = channels.read().get(&ticker_id) {
+                        let _ = sender.try_send(MarketEvent::StaleData(data.clone()));
+                    }
+                    if let Some(callback) = &callback {
+                        callback(data);
+                    }
+                }
+            }
+        })
+    }
+
     /// Handle incoming messages
-    fn handle_message(
-        topic: &str,
-        payload: &[u8],
-        price_callback: &Option<PriceCallback>,
-        order_callback: &Option<OrderCallback>,
-        total_volume: &Arc<RwLock<HashMap<String, i64>>>,
-        debug: bool,
-    ) {
+    fn handle_message(topic: &str, payload: &[u8], routes: &Routes, debug: bool) {
         // Try to parse the message
         let topic_json = match serde_json::from_str::<Value>(topic) {
             Ok(v) => v,
@@ -229,7 +397,7 @@ impl StreamConn {
 
         // Check if it's an order message (from platpush)
         if topic.contains("platpush") {
-            if let Some(callback) = order_callback {
+            if let Some(callback) = &routes.order_callback {
                 callback(topic_json, payload_json);
             }
         }
@@ -238,16 +406,66 @@ impl StreamConn {
             // Update total volume if applicable
             if let Some(ticker_id) = topic_json.get("tickerId").and_then(|v| v.as_str()) {
                 if let Some(volume) = payload_json.get("volume").and_then(|v| v.as_i64()) {
-                    total_volume.write().insert(ticker_id.to_string(), volume);
+                    routes
+                        .total_volume
+                        .write()
+                        .insert(ticker_id.to_string(), volume);
+                }
+            }
+
+            // Route depth-of-book updates to the depth callback
+            let ticker_id = match topic_json.get("tickerId") {
+                Some(Value::String(s)) => s.clone(),
+                Some(Value::Number(n)) => n.to_string(),
+                _ => String::new(),
+            };
+            routes.staleness.lock().touch(&ticker_id, Instant::now());
+            if let Some(callback) = &routes.depth_callback {
+                if let Some(update) = DepthUpdate::from_payload(&payload_json) {
+                    callback(ticker_id.clone(), update);
                 }
             }
 
-            if let Some(callback) = price_callback {
+            Self::route_to_channel(
+                &routes.ticker_channels,
+                &ticker_id,
+                &topic_json,
+                &payload_json,
+            );
+
+            if let Some(callback) = &routes.price_callback {
                 callback(topic_json, payload_json);
             }
         }
     }
 
+    /// Send a price push to its ticker's channel, if it has one
+    fn route_to_channel(
+        channels: &TickerChannels,
+        ticker_id: &str,
+        topic: &Value,
+        payload: &Value,
+    ) {
+        let sender = match channels.read().get(ticker_id) {
+            Some(sender) => sender.clone(),
+            None => return,
+        };
+        let event = match MarketEvent::from_push(topic, payload, Utc::now()) {
+            Some(event) => event,
+            None => return,
+        };
+        match sender.try_send(event) {
+            Ok(()) => {}
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                warn!("Channel for {} is full, dropping an event", ticker_id);
+            }
+            // The receiver was dropped; stop routing to it
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                channels.write().remove(ticker_id);
+            }
+        }
+    }
+
     /// Subscribe to ticker updates
     pub async fn subscribe_ticker(&mut self, ticker_id: &str, topics: Vec<i32>) -> Result<()> {
         if let Some(client) = &self.client {
@@ -260,6 +478,7 @@ impl StreamConn {
                     .map_err(|e| WebullError::MqttError(e.to_string()))?;
 
                 self.subscriptions.write().push(topic.clone());
+                self.staleness.lock().watch(ticker_id, Instant::now());
 
                 if self.config.debug {
                     debug!("Subscribed to: {}", topic);
@@ -309,12 +528,64 @@ impl StreamConn {
                     debug!("Unsubscribed from: {}", topic);
                 }
             }
+            let prefix = format!("{{\"tickerId\":\"{}\",", ticker_id);
+            if !self
+                .subscriptions
+                .read()
+                .iter()
+                .any(|t| t.starts_with(&prefix))
+            {
+                self.staleness.lock().forget(ticker_id);
+            }
             Ok(())
         } else {
             Err(WebullError::WebSocketError("Not connected".to_string()))
         }
     }
 
+    /// Subscribe to a ticker and get its events on a dedicated channel
+    ///
+    /// Price pushes for the ticker are normalized into `MarketEvent`s. The
+    /// price callback, if set, still sees every push. Subscribing to the
+    /// same ticker again replaces the previous channel.
+    pub async fn subscribe(
+        &mut self,
+        ticker_id: &str,
+        topics: Vec<i32>,
+    ) -> Result<mpsc::Receiver<MarketEvent>> {
+        self.subscribe_ticker(ticker_id, topics.clone()).await?;
+        let (sender, receiver) = mpsc::channel(self.config.channel_capacity.max(1));
+        self.ticker_channels
+            .write()
+            .insert(ticker_id.to_string(), sender);
+        self.ticker_topics
+            .entry(ticker_id.to_string())
+            .or_default()
+            .extend(topics);
+        Ok(receiver)
+    }
+
+    /// Drop a ticker's channel and unsubscribe from everything `subscribe`
+    /// subscribed it to
+    pub async fn unsubscribe(&mut self, ticker_id: &str) -> Result<()> {
+        self.ticker_channels.write().remove(ticker_id);
+        match self.ticker_topics.remove(ticker_id) {
+            Some(mut topics) => {
+                topics.sort_unstable();
+                topics.dedup();
+                self.unsubscribe_ticker(ticker_id, topics).await
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Tickers with a channel from `subscribe`
+    pub fn subscribed_tickers(&self) -> Vec<String> {
+        let mut tickers: Vec<String> = self.ticker_channels.read().keys().cloned().collect();
+        tickers.sort();
+        tickers
+    }
+
     /// Unsubscribe from all topics
     pub async fn unsubscribe_all(&mut self) -> Result<()> {
         if let Some(client) = &self.client {
@@ -326,6 +597,9 @@ impl StreamConn {
                     .map_err(|e| WebullError::MqttError(e.to_string()))?;
             }
             self.subscriptions.write().clear();
+            self.ticker_channels.write().clear();
+            self.ticker_topics.clear();
+            *self.staleness.lock() = Staleness::default();
             Ok(())
         } else {
             Err(WebullError::WebSocketError("Not connected".to_string()))
@@ -336,6 +610,9 @@ impl StreamConn {
     pub async fn disconnect(&mut self) -> Result<()> {
         if self.client.is_some() {
             self.unsubscribe_all().await?;
+            if let Some(watchdog) = self.watchdog.take() {
+                watchdog.abort();
+            }
             if let Some(client) = self.client.take() {
                 client
                     .disconnect()
@@ -359,6 +636,11 @@ impl StreamConn {
         self.subscriptions.read().clone()
     }
 
+    /// When the last packet (data or ping response) arrived
+    pub fn last_heartbeat(&self) -> Option<Instant> {
+        *self.last_heartbeat.read()
+    }
+
     /// Get total volume for a ticker
     pub fn get_total_volume(&self, ticker_id: &str) -> Option<i64> {
         self.total_volume.read().get(ticker_id).copied()
@@ -398,6 +680,335 @@ impl TopicTypes {
     }
 }
 
+/// A quote update reduced to price, top of book and volume
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteEvent {
+    pub ticker_id: i64,
+    pub time: DateTime<Utc>,
+    pub price: f64,
+    pub bid: Option<f64>,
+    pub ask: Option<f64>,
+    /// Day volume so far
+    pub volume: Option<f64>,
+}
+
+/// A single trade
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeEvent {
+    pub ticker_id: i64,
+    pub time: DateTime<Utc>,
+    pub price: f64,
+    pub size: f64,
+    pub exchange: Option<String>,
+    pub condition: Option<String>,
+}
+
+/// A subscribed ticker that has gone quiet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaleData {
+    pub ticker_id: String,
+    /// Time since the last message, or since subscribing if none came
+    pub silent_for: Duration,
+}
+
+/// A normalized market data event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MarketEvent {
+    Quote(QuoteEvent),
+    Trade(TradeEvent),
+    /// No message for the ticker within `StreamConfig::stale_after`
+    StaleData(StaleData),
+}
+
+fn number(data: &Value, keys: &[&str]) -> Option<f64> {
+    keys.iter().find_map(|key| match data.get(*key) {
+        Some(Value::String(s)) => s.parse::<f64>().ok(),
+        Some(Value::Number(n)) => n.as_f64(),
+        _ => None,
+    })
+}
+
+fn text(data: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| match data.get(*key) {
+        Some(Value::String(s)) if !s.is_empty() => Some(s.clone()),
+        Some(Value::Number(n)) => Some(n.to_string()),
+        _ => None,
+    })
+}
+
+/// Top-of-book price from a `bidList`/`askList` array or a flat field
+fn top_of_book(data: &Value, list: &str, flat: &str) -> Option<f64> {
+    data.get(list)
+        .and_then(|levels| levels.get(0))
+        .and_then(|level| number(level, &["price"]))
+        .or_else(|| number(data, &[flat]))
+}
+
+impl MarketEvent {
+    /// Normalize a price stream push (see `StreamConn::set_price_callback`)
+    ///
+    /// Trade topics become `Trade` events and everything else with a price
+    /// becomes a `Quote`. The push's own trade time is used when present,
+    /// `received` otherwise.
+    pub fn from_push(topic: &Value, data: &Value, received: DateTime<Utc>) -> Option<Self> {
+        let ticker_id =
+            number(topic, &["tickerId"]).or_else(|| number(data, &["tickerId"]))? as i64;
+        let time = number(data, &["tradeTime", "tradeStamp"])
+            .and_then(|millis| DateTime::from_timestamp_millis(millis as i64))
+            .unwrap_or(received);
+
+        let topic_type = number(topic, &["type"]).map(|t| t as i32);
+        if topic_type == Some(TopicTypes::TICKER_TRADE) {
+            return Some(MarketEvent::Trade(TradeEvent {
+                ticker_id,
+                time,
+                price: number(data, &["deal", "price", "tradePrice"])?,
+                size: number(data, &["volume", "size", "tradeVolume"]).unwrap_or(0.0),
+                exchange: text(data, &["exchange", "trdEx"]),
+                condition: text(data, &["tradeCondition", "trdCond"]),
+            }));
+        }
+
+        Some(MarketEvent::Quote(QuoteEvent {
+            ticker_id,
+            time,
+            price: number(data, &["price", "close", "pPrice"])?,
+            bid: top_of_book(data, "bidList", "bid"),
+            ask: top_of_book(data, "askList", "ask"),
+            volume: number(data, &["volume"]),
+        }))
+    }
+
+    /// A polled quote, stamped `at`; `None` without a numeric ticker ID
+    pub fn from_quote(quote: &Quote, at: DateTime<Utc>) -> Option<Self> {
+        Some(MarketEvent::Quote(QuoteEvent {
+            ticker_id: quote.ticker_id.as_deref()?.parse().ok()?,
+            time: at,
+            price: quote.latest_price(),
+            bid: None,
+            ask: None,
+            volume: quote.volume,
+        }))
+    }
+
+    /// A trade from `get_ticks`; `None` if its time is out of range
+    pub fn from_tick(ticker_id: i64, tick: &TradeTick) -> Option<Self> {
+        Some(MarketEvent::Trade(TradeEvent {
+            ticker_id,
+            time: DateTime::from_timestamp_millis(tick.time)?,
+            price: tick.price,
+            size: tick.size,
+            exchange: tick.exchange.clone(),
+            condition: tick.condition.clone(),
+        }))
+    }
+}
+
+/// A bar closed by a `BarAggregator`
+#[derive(Debug, Clone)]
+pub struct StreamBar {
+    pub ticker_id: String,
+    pub interval: BarInterval,
+    pub bar: Bar,
+}
+
+/// Bar still taking prices
+#[derive(Debug, Clone)]
+struct OpenBar {
+    bar: Bar,
+    notional: f64,
+}
+
+#[derive(Debug, Default)]
+struct AggregatorState {
+    open: HashMap<(String, BarInterval), OpenBar>,
+    // Last day volume seen on quotes, to turn it into per-push volume
+    day_volume: HashMap<String, f64>,
+    // Tickers with trade pushes; their quote volume is ignored
+    trades_seen: std::collections::HashSet<String>,
+}
+
+/// Builds live candles from streamed quotes and trades
+///
+/// Feed it from `StreamConn::set_price_callback` and read completed bars
+/// from the receiver returned by `new`. Bars are aligned to the interval in
+/// UTC, so it is meant for intraday intervals. A bar is closed by the first
+/// push after its end; call `flush` on a timer to close bars for quiet
+/// tickers too.
+///
+/// Trades carry their own size; quote pushes only count volume (from the
+/// change in day volume) for tickers that send no trades.
+///
+/// ```no_run
+/// use webull_unofficial::models::BarInterval;
+/// use webull_unofficial::stream::{BarAggregator, StreamConn};
+///
+/// # async fn run(mut stream: StreamConn) {
+/// let (aggregator, mut bars) = BarAggregator::new(&[BarInterval::M1, BarInterval::M5]);
+/// stream.set_price_callback(aggregator.price_callback());
+/// while let Some(closed) = bars.recv().await {
+///     println!("{} {} close {}", closed.ticker_id, closed.interval, closed.bar.close);
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BarAggregator {
+    intervals: Vec<BarInterval>,
+    state: Arc<Mutex<AggregatorState>>,
+    sender: mpsc::UnboundedSender<StreamBar>,
+}
+
+impl BarAggregator {
+    pub fn new(intervals: &[BarInterval]) -> (Self, mpsc::UnboundedReceiver<StreamBar>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let aggregator = Self {
+            intervals: intervals.to_vec(),
+            state: Arc::new(Mutex::new(AggregatorState::default())),
+            sender,
+        };
+        (aggregator, receiver)
+    }
+
+    /// Callback for `StreamConn::set_price_callback`
+    pub fn price_callback(&self) -> impl Fn(Value, Value) + Send + Sync + 'static {
+        let aggregator = self.clone();
+        move |topic, data| aggregator.on_push(&topic, &data)
+    }
+
+    /// Fold one price push into the open bars
+    pub fn on_push(&self, topic: &Value, data: &Value) {
+        let number = |value: &Value, key: &str| match value.get(key) {
+            Some(Value::String(s)) => s.parse::<f64>().ok(),
+            Some(Value::Number(n)) => n.as_f64(),
+            _ => None,
+        };
+        let ticker_id = match topic.get("tickerId").or_else(|| data.get("tickerId")) {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Number(n)) => n.to_string(),
+            _ => return,
+        };
+        let time = number(data, "tradeTime")
+            .or_else(|| number(data, "tradeStamp"))
+            .map(|millis| millis as i64)
+            .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+
+        if number(topic, "type").map(|t| t as i32) == Some(TopicTypes::TICKER_TRADE) {
+            let price = match number(data, "deal").or_else(|| number(data, "price")) {
+                Some(price) => price,
+                None => return,
+            };
+            let size = number(data, "volume").unwrap_or(0.0);
+            self.on_trade(&ticker_id, time, price, size);
+        } else if let Some(price) = number(data, "price").or_else(|| number(data, "close")) {
+            self.on_quote(&ticker_id, time, price, number(data, "volume"));
+        }
+    }
+
+    /// Fold a trade of `size` at `price` (time in Unix milliseconds)
+    pub fn on_trade(&self, ticker_id: &str, time: i64, price: f64, size: f64) {
+        let mut state = self.state.lock();
+        state.trades_seen.insert(ticker_id.to_string());
+        self.apply(&mut state, ticker_id, time, price, size);
+    }
+
+    /// Fold a quote with the day volume so far (time in Unix milliseconds)
+    pub fn on_quote(&self, ticker_id: &str, time: i64, price: f64, day_volume: Option<f64>) {
+        let mut state = self.state.lock();
+        let mut size = 0.0;
+        if let Some(day_volume) = day_volume {
+            let previous = state.day_volume.insert(ticker_id.to_string(), day_volume);
+            if !state.trades_seen.contains(ticker_id) {
+                // A drop means a new session started
+                size = previous.map_or(0.0, |p| (day_volume - p).max(0.0));
+            }
+        }
+        self.apply(&mut state, ticker_id, time, price, size);
+    }
+
+    /// Close every bar that ended at or before `now` (Unix milliseconds)
+    pub fn flush_until(&self, now: i64) {
+        let mut state = self.state.lock();
+        let now = now.div_euclid(1000);
+        let ended: Vec<(String, BarInterval)> = state
+            .open
+            .iter()
+            .filter(|((_, interval), open)| open.bar.timestamp + interval_seconds(*interval) <= now)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in ended {
+            if let Some(open) = state.open.remove(&key) {
+                self.emit(key.0, key.1, open);
+            }
+        }
+    }
+
+    /// Close every bar that has ended by now
+    pub fn flush(&self) {
+        self.flush_until(chrono::Utc::now().timestamp_millis());
+    }
+
+    fn apply(
+        &self,
+        state: &mut AggregatorState,
+        ticker_id: &str,
+        time: i64,
+        price: f64,
+        size: f64,
+    ) {
+        let seconds = time.div_euclid(1000);
+        for &interval in &self.intervals {
+            let length = interval_seconds(interval);
+            let start = seconds - seconds.rem_euclid(length);
+            let key = (ticker_id.to_string(), interval);
+
+            match state.open.get_mut(&key) {
+                // Late push for a bar already closed
+                Some(open) if start < open.bar.timestamp => continue,
+                Some(open) if start == open.bar.timestamp => {
+                    open.bar.high = open.bar.high.max(price);
+                    open.bar.low = open.bar.low.min(price);
+                    open.bar.close = price;
+                    open.bar.volume += size;
+                    open.notional += price * size;
+                    if open.bar.volume > 0.0 {
+                        open.bar.vwap = open.notional / open.bar.volume;
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+
+            let fresh = OpenBar {
+                bar: Bar {
+                    timestamp: start,
+                    open: price,
+                    high: price,
+                    low: price,
+                    close: price,
+                    volume: size,
+                    vwap: price,
+                },
+                notional: price * size,
+            };
+            if let Some(closed) = state.open.insert(key, fresh) {
+                self.emit(ticker_id.to_string(), interval, closed);
+            }
+        }
+    }
+
+    fn emit(&self, ticker_id: String, interval: BarInterval, open: OpenBar) {
+        // Nobody listening any more is not an error for the stream
+        let _ = self.sender.send(StreamBar {
+            ticker_id,
+            interval,
+            bar: open.bar,
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -417,4 +1028,112 @@ mod tests {
         let basic_topics = TopicTypes::basic();
         assert_eq!(basic_topics.len(), 3);
     }
+
+    #[test]
+    fn test_bar_aggregator_closes_bars() {
+        let (aggregator, mut bars) = BarAggregator::new(&[BarInterval::M1, BarInterval::M5]);
+        let trade = |millis: i64, price: f64, size: f64| {
+            aggregator.on_push(
+                &serde_json::json!({"tickerId": "913256135", "type": TopicTypes::TICKER_TRADE}),
+                &serde_json::json!({"tradeTime": millis, "deal": price, "volume": size}),
+            )
+        };
+        // 2024-01-02 14:30:00 UTC
+        let base = 1_704_205_800_000;
+        trade(base + 1_000, 10.0, 100.0);
+        trade(base + 20_000, 12.0, 100.0);
+        trade(base + 50_000, 9.0, 200.0);
+        assert!(bars.try_recv().is_err());
+
+        trade(base + 61_000, 11.0, 50.0);
+        let closed = bars.try_recv().unwrap();
+        assert_eq!(closed.interval, BarInterval::M1);
+        assert_eq!(closed.bar.timestamp, base / 1000);
+        assert_eq!(
+            (
+                closed.bar.open,
+                closed.bar.high,
+                closed.bar.low,
+                closed.bar.close
+            ),
+            (10.0, 12.0, 9.0, 9.0)
+        );
+        assert_eq!(closed.bar.volume, 400.0);
+        assert_eq!(closed.bar.vwap, 10.0);
+
+        // A late trade misses the closed 1m bar but lands in the open 5m one
+        trade(base + 30_000, 99.0, 1.0);
+        aggregator.flush_until(base + 300_000);
+        let mut rest: Vec<StreamBar> = std::iter::from_fn(|| bars.try_recv().ok()).collect();
+        rest.sort_by_key(|b| b.bar.timestamp);
+        assert_eq!(rest.len(), 2);
+        assert_eq!(rest[1].interval, BarInterval::M1);
+        assert_eq!(rest[1].bar.high, 11.0);
+        assert_eq!(rest[0].interval, BarInterval::M5);
+        assert_eq!(rest[0].bar.volume, 451.0);
+        assert_eq!(rest[0].bar.high, 99.0);
+
+        // Quote volume comes from the change in day volume
+        aggregator.on_quote("1", base, 5.0, Some(1_000.0));
+        aggregator.on_quote("1", base + 1_000, 6.0, Some(1_300.0));
+        aggregator.flush_until(base + 300_000);
+        let quoted: Vec<StreamBar> = std::iter::from_fn(|| bars.try_recv().ok()).collect();
+        assert!(quoted.iter().all(|b| b.bar.volume == 300.0));
+    }
+
+    #[tokio::test]
+    async fn test_pushes_route_to_ticker_channels() {
+        let channels: TickerChannels = Arc::new(RwLock::new(HashMap::new()));
+        let (sender, mut receiver) = mpsc::channel(1);
+        channels.write().insert("913256135".to_string(), sender);
+
+        let topic = serde_json::json!({"tickerId": 913256135, "type": TopicTypes::TICKER_QUOTE});
+        let payload = serde_json::json!({"price": "187.5", "tradeTime": 1_704_205_800_000_i64});
+        StreamConn::route_to_channel(&channels, "913256135", &topic, &payload);
+        // Over capacity: dropped rather than blocking the event loop
+        StreamConn::route_to_channel(&channels, "913256135", &topic, &payload);
+        StreamConn::route_to_channel(&channels, "1", &topic, &payload);
+
+        match receiver.recv().await {
+            Some(MarketEvent::Quote(quote)) => {
+                assert_eq!(quote.ticker_id, 913256135);
+                assert_eq!(quote.price, 187.5);
+            }
+            other => panic!("expected a quote, got {:?}", other),
+        }
+        assert!(receiver.try_recv().is_err());
+
+        drop(receiver);
+        StreamConn::route_to_channel(&channels, "913256135", &topic, &payload);
+        assert!(channels.read().is_empty());
+    }
+
+    #[test]
+    fn test_staleness_reports_once_until_data_returns() {
+        let start = Instant::now();
+        let stale_after = Duration::from_secs(10);
+        let mut staleness = Staleness::default();
+        staleness.watch("1", start);
+        staleness.watch("2", start);
+        // Unwatched tickers are ignored
+        staleness.touch("3", start);
+
+        staleness.touch("2", start + Duration::from_secs(8));
+        let stale = staleness.check(start + Duration::from_secs(12), stale_after);
+        assert_eq!(stale, vec![("1".to_string(), Duration::from_secs(12))]);
+        assert!(staleness
+            .check(start + Duration::from_secs(13), stale_after)
+            .is_empty());
+
+        staleness.touch("1", start + Duration::from_secs(14));
+        let stale = staleness.check(start + Duration::from_secs(25), stale_after);
+        assert_eq!(stale.len(), 2);
+
+        staleness.forget("1");
+        staleness.touch("2", start + Duration::from_secs(30));
+        assert!(staleness
+            .check(start + Duration::from_secs(60), stale_after)
+            .iter()
+            .all(|(ticker_id, _)| ticker_id == "2"));
+    }
 }
@@ -0,0 +1,308 @@
+// Request spans and metrics for the `tracing` and `metrics` features, and
+// redaction of secrets
+
+use crate::error::{Result, WebullError};
+use crate::rate_limit::EndpointClass;
+use reqwest::header::HeaderMap;
+use reqwest::{Request, Response};
+use serde_json::Value;
+use std::future::Future;
+
+/// Counter of HTTP requests, labelled `endpoint`, `class` and `status`
+/// (`error` when no response arrived)
+pub const REQUESTS_TOTAL: &str = "webull_requests_total";
+/// Histogram of request latency in seconds, labelled `endpoint` and `class`
+pub const REQUEST_DURATION_SECONDS: &str = "webull_request_duration_seconds";
+/// Counter of errors, labelled `kind` (the `WebullError` variant)
+pub const ERRORS_TOTAL: &str = "webull_errors_total";
+/// Counter of access token refreshes, labelled `result` (`ok` or `error`)
+pub const TOKEN_REFRESHES_TOTAL: &str = "webull_token_refreshes_total";
+
+/// Headers and query parameters whose values are never logged
+const SECRETS: &[&str] = &[
+    "access_token",
+    "refresh_token",
+    "t_token",
+    "accesstoken",
+    "refreshtoken",
+    "tradetoken",
+    "token",
+    "pwd",
+    "password",
+    "tradepwd",
+    "did",
+    "deviceid",
+    "x-s",
+];
+
+const REDACTED: &str = "[redacted]";
+
+pub(crate) fn is_secret(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SECRETS.contains(&name.as_str())
+}
+
+/// `url` with the values of secret query parameters replaced
+pub fn redact_url(url: &url::Url) -> String {
+    if url.query().is_none() {
+        return url.to_string();
+    }
+    let mut redacted = url.clone();
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| {
+            let value = if is_secret(&key) {
+                REDACTED.to_string()
+            } else {
+                value.into_owned()
+            };
+            (key.into_owned(), value)
+        })
+        .collect();
+    redacted.query_pairs_mut().clear().extend_pairs(pairs);
+    redacted.to_string()
+}
+
+/// Header names and values with tokens and device IDs replaced
+pub fn redact_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if is_secret(name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                value.to_str().unwrap_or("[binary]").to_string()
+            };
+            (name.as_str().to_string(), value)
+        })
+        .collect()
+}
+
+/// URL path with numeric segments (ticker and order IDs) collapsed, to keep
+/// metric label cardinality bounded
+pub fn endpoint_label(url: &url::Url) -> String {
+    let path = url
+        .path()
+        .split('/')
+        .map(|segment| {
+            if !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()) {
+                "{id}"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    format!("{}{}", url.host_str().unwrap_or_default(), path)
+}
+
+/// Register descriptions for the crate's metrics with the installed
+/// recorder
+#[cfg(feature = "metrics")]
+pub fn describe_metrics() {
+    metrics::describe_counter!(REQUESTS_TOTAL, "HTTP requests sent to Webull");
+    metrics::describe_histogram!(
+        REQUEST_DURATION_SECONDS,
+        metrics::Unit::Seconds,
+        "Latency of HTTP requests to Webull, including retries"
+    );
+    metrics::describe_counter!(ERRORS_TOTAL, "Errors returned by the client");
+    metrics::describe_counter!(TOKEN_REFRESHES_TOTAL, "Access token refreshes");
+}
+
+/// Count an error by kind
+pub(crate) fn record_error(error: &WebullError) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!(ERRORS_TOTAL, "kind" => error.kind()).increment(1);
+    #[cfg(not(feature = "metrics"))]
+    let _ = error;
+}
+
+/// Count a token refresh
+pub(crate) fn record_token_refresh(ok: bool) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!(TOKEN_REFRESHES_TOTAL, "result" => if ok { "ok" } else { "error" })
+        .increment(1);
+    #[cfg(not(feature = "metrics"))]
+    let _ = ok;
+}
+
+/// A JSON body with the values of secret fields replaced, at any depth
+pub fn redact_body(body: &Value) -> Value {
+    match body {
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| {
+                    let value = if is_secret(key) {
+                        Value::String(REDACTED.to_string())
+                    } else {
+                        redact_body(value)
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact_body).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Span and metrics for one HTTP request, including its retries
+///
+/// Without the `tracing` and `metrics` features this does nothing.
+pub(crate) struct RequestSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(any(feature = "tracing", feature = "metrics"))]
+    started: std::time::Instant,
+    #[cfg(feature = "metrics")]
+    endpoint: String,
+    #[cfg(feature = "metrics")]
+    class: EndpointClass,
+}
+
+impl RequestSpan {
+    pub(crate) fn start(request: &Request, class: EndpointClass) -> Self {
+        #[cfg(feature = "tracing")]
+        let span = {
+            let reqid = request
+                .headers()
+                .get("reqid")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default();
+            let span = tracing::info_span!(
+                "webull_request",
+                method = %request.method(),
+                endpoint = %redact_url(request.url()),
+                class = ?class,
+                reqid = %reqid,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            );
+            tracing::trace!(
+                parent: &span,
+                headers = ?redact_headers(request.headers()),
+                "sending request"
+            );
+            span
+        };
+        #[cfg(not(any(feature = "tracing", feature = "metrics")))]
+        let _ = (request, class);
+
+        Self {
+            #[cfg(feature = "tracing")]
+            span,
+            #[cfg(any(feature = "tracing", feature = "metrics"))]
+            started: std::time::Instant::now(),
+            #[cfg(feature = "metrics")]
+            endpoint: endpoint_label(request.url()),
+            #[cfg(feature = "metrics")]
+            class,
+        }
+    }
+
+    /// Run `send` inside the span and record how it went
+    pub(crate) async fn run<F>(self, send: F) -> Result<Response>
+    where
+        F: Future<Output = Result<Response>>,
+    {
+        #[cfg(feature = "tracing")]
+        let result = tracing::Instrument::instrument(send, self.span.clone()).await;
+        #[cfg(not(feature = "tracing"))]
+        let result = send.await;
+
+        self.finish(&result);
+        result
+    }
+
+    fn finish(&self, result: &Result<Response>) {
+        if let Err(e) = result {
+            record_error(e);
+        }
+
+        #[cfg(feature = "tracing")]
+        {
+            let latency_ms = self.started.elapsed().as_millis() as u64;
+            self.span.record("latency_ms", latency_ms);
+            match result {
+                Ok(response) => {
+                    self.span.record("status", response.status().as_u16());
+                    tracing::debug!(parent: &self.span, "request finished");
+                }
+                Err(e) => tracing::warn!(parent: &self.span, error = %e, "request failed"),
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        {
+            let class = format!("{:?}", self.class);
+            let status = match result {
+                Ok(response) => response.status().as_u16().to_string(),
+                Err(_) => "error".to_string(),
+            };
+            metrics::counter!(
+                REQUESTS_TOTAL,
+                "endpoint" => self.endpoint.clone(),
+                "class" => class.clone(),
+                "status" => status
+            )
+            .increment(1);
+            metrics::histogram!(
+                REQUEST_DURATION_SECONDS,
+                "endpoint" => self.endpoint.clone(),
+                "class" => class
+            )
+            .record(self.started.elapsed().as_secs_f64());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_secrets_are_redacted() {
+        let url = url::Url::parse(
+            "https://u1suser.webullfintech.com/api/user?access_token=abc&regionId=6&pwd=x",
+        )
+        .unwrap();
+        let redacted = redact_url(&url);
+        assert!(!redacted.contains("abc"));
+        assert!(redacted.contains("regionId=6"));
+        assert!(redacted.contains("pwd=%5Bredacted%5D"));
+
+        let bare = url::Url::parse("https://quotes-gw.webullfintech.com/api/quote").unwrap();
+        assert_eq!(redact_url(&bare), bare.to_string());
+
+        let mut headers = HeaderMap::new();
+        headers.insert("access_token", HeaderValue::from_static("secret"));
+        headers.insert("t_token", HeaderValue::from_static("trade"));
+        headers.insert("reqid", HeaderValue::from_static("r1"));
+        let headers = redact_headers(&headers);
+        assert!(headers.iter().all(|(_, v)| v != "secret" && v != "trade"));
+        assert!(headers.contains(&("reqid".to_string(), "r1".to_string())));
+    }
+
+    #[test]
+    fn test_endpoint_labels_collapse_ids() {
+        let url = url::Url::parse(
+            "https://quotes-gw.webullfintech.com/api/quote/charts/query/913256135?type=m1",
+        )
+        .unwrap();
+        assert_eq!(
+            endpoint_label(&url),
+            "quotes-gw.webullfintech.com/api/quote/charts/query/{id}"
+        );
+        assert_eq!(WebullError::DeadlineExceeded.kind(), "DeadlineExceeded");
+        assert_eq!(
+            WebullError::InvalidTradePin {
+                remaining_attempts: None
+            }
+            .kind(),
+            "InvalidTradePin"
+        );
+    }
+}
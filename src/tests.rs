@@ -33,6 +33,41 @@ mod tests {
         assert_eq!(get_account_type("username").unwrap(), 2);
     }
 
+    #[test]
+    fn test_account_identifier_normalizes_logins() {
+        let phone = |login: &str| AccountIdentifier::parse(login).unwrap().account();
+        assert_eq!(phone("+1-2345678901"), "+1-2345678901");
+        assert_eq!(phone("+86 138 1234 5678"), "+86-13812345678");
+        assert_eq!(phone("+1 (234) 567-8901"), "+1-2345678901");
+        assert_eq!(phone("0044 20 7946 0958"), "+44-2079460958");
+        assert_eq!(phone("+8613812345678"), "+86-13812345678");
+        assert_eq!(phone("+85261234567"), "+852-61234567");
+        assert_eq!(phone("+1555-123-4567"), "+1-5551234567");
+        assert_eq!(phone("+44207946 0958"), "+44-2079460958");
+        assert_eq!(
+            AccountIdentifier::parse(" +852-6123 4567 ").unwrap(),
+            AccountIdentifier::Phone {
+                country_code: "852".to_string(),
+                number: "61234567".to_string()
+            }
+        );
+
+        let email: AccountIdentifier = " Jane.Doe@Example.COM".parse().unwrap();
+        assert_eq!(
+            email,
+            AccountIdentifier::Email("jane.doe@example.com".to_string())
+        );
+        assert_eq!(email.account_type(), 2);
+
+        assert_eq!(
+            AccountIdentifier::parse("007bond").unwrap(),
+            AccountIdentifier::Username("007bond".to_string())
+        );
+        for bad in ["+1-12", "+1-234-abc-5678", "+", "a@b"] {
+            assert!(AccountIdentifier::parse(bad).is_err(), "{}", bad);
+        }
+    }
+
     #[test]
     fn test_email_validation() {
         assert!(validate_email("test@example.com"));
@@ -203,6 +238,8 @@ mod tests {
             outside_regular_trading_hour: false,
             serial_id: None,
             combo_type: None,
+            expire_date: None,
+            short_sale: false,
         };
 
         let json = serde_json::to_value(&order).unwrap();
@@ -218,16 +255,1795 @@ mod tests {
 
     #[test]
     fn test_screener_request() {
-        // Test simplified screener request
-        let request = ScreenerRequest {
-            region_id: 6,
-            plate_id: 1,
-            rank_id: 0,
-        };
+        let request = ScreenerRequestBuilder::new()
+            .price(Some(1.5), None)
+            .volume(None, Some(1_000_000.0))
+            .sectors(&["Technology", "Energy"])
+            .top_gainers()
+            .limit(50)
+            .build()
+            .unwrap();
 
         let json = serde_json::to_value(&request).unwrap();
-        assert_eq!(json["regionId"], 6);
-        assert_eq!(json["plateId"], 1);
-        assert_eq!(json["rankId"], 0);
+        assert_eq!(json["fetch"], 50);
+        assert_eq!(
+            json["rules"]["wlas.screener.rule.region"],
+            "securities.region.name.6"
+        );
+        assert_eq!(json["rules"]["wlas.screener.rule.price"], "gte=1.5");
+        assert_eq!(json["rules"]["wlas.screener.rule.volume"], "lte=1000000");
+        assert_eq!(json["rules"]["wlas.screener.rule.changeRatio"], "gte=0");
+        assert_eq!(
+            json["rules"]["wlas.screener.rule.sector"],
+            "Technology,Energy"
+        );
+        assert_eq!(json["sort"]["rule"], "wlas.screener.rule.changeRatio");
+        assert_eq!(json["sort"]["desc"], true);
+        assert_eq!(json["attach"]["hkexPrivilege"], false);
+
+        assert!(ScreenerRequestBuilder::new()
+            .pe_ratio(Some(30.0), Some(10.0))
+            .build()
+            .is_err());
+
+        let response = serde_json::json!({
+            "data": {"items": [
+                {"ticker": {"tickerId": 913256135, "disSymbol": "AAPL", "name": "Apple Inc"}},
+                {"values": {}}
+            ]}
+        });
+        let tickers = ScreenerRequest::parse_response(&response);
+        assert_eq!(tickers.len(), 1);
+        assert_eq!(tickers[0].symbol, "AAPL");
+    }
+
+    #[test]
+    fn test_watchlist_deserialization() {
+        let json = serde_json::json!({
+            "id": 12345,
+            "name": "Tech",
+            "tickerList": [
+                {"tickerId": 913256135, "disSymbol": "AAPL", "name": "Apple Inc"}
+            ]
+        });
+
+        let watchlist: Watchlist = serde_json::from_value(json).unwrap();
+        assert_eq!(watchlist.id, Some("12345".to_string()));
+        assert_eq!(watchlist.name, "Tech");
+        assert_eq!(watchlist.tickers.len(), 1);
+        assert_eq!(watchlist.tickers[0].symbol, "AAPL");
+    }
+
+    #[test]
+    fn test_alert_rule_builder() {
+        let rule = AlertRuleBuilder::new()
+            .price_above(200.0)
+            .price_below(150.0)
+            .percent_change_down(5.0)
+            .volume_above(1_000_000.0)
+            .frequency(2)
+            .build()
+            .unwrap();
+
+        assert_eq!(rule.rules.len(), 4);
+        assert_eq!(rule.frequency, 2);
+
+        let json = serde_json::to_value(&rule.rules[0]).unwrap();
+        assert_eq!(json["field"], "price");
+        assert_eq!(json["type"], "above");
+        assert_eq!(json["value"], "200");
+        assert_eq!(json["active"], "on");
+        assert_eq!(rule.rules[2].value, "-5");
+
+        assert!(AlertRuleBuilder::new().build().is_err());
+        assert!(AlertRuleBuilder::new()
+            .price_above(1.0)
+            .frequency(9)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_minute_volume_profile_weights() {
+        let buckets: Vec<VolumeBucket> = serde_json::from_value(serde_json::json!([
+            {"time": "09:30", "volume": "300"},
+            {"time": "10:00", "volume": 100},
+            {"time": "10:30", "avgVolume": "100"}
+        ]))
+        .unwrap();
+
+        let profile = MinuteVolumeProfile {
+            ticker_id: "913256135".to_string(),
+            days: 20,
+            buckets,
+        };
+
+        assert_eq!(profile.total_volume(), 500.0);
+        assert_eq!(profile.weights(), vec![0.6, 0.2, 0.2]);
+    }
+
+    #[tokio::test]
+    async fn test_health_check_without_login() {
+        let client = WebullClient::new_live(Some(6)).unwrap();
+        let status = client.health_check().await;
+
+        assert!(!status.authenticated);
+        assert!(!status.is_ready());
+        assert!(!status.is_trading_ready());
+        assert_eq!(status.error, Some("Not logged in".to_string()));
+        assert!(status.latency_ms.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_health_check_reads_the_probe_body() {
+        use crate::fixtures::{FixtureMode, FixtureStore};
+
+        let dir = std::env::temp_dir().join(format!("webull_health_{}", uuid::Uuid::new_v4()));
+        let mut client = crate::LiveWebullClient::new(None).unwrap();
+        client.access_token = Some("expired".to_string());
+        let request = reqwest::Client::new()
+            .get(client.endpoints.account_id())
+            .build()
+            .unwrap();
+        let body = serde_json::json!({
+            "success": false,
+            "code": "auth.token.expire",
+            "msg": "Token expired"
+        });
+        FixtureStore::new(&dir, FixtureMode::Record)
+            .record(&request, 200, body.to_string().as_bytes())
+            .unwrap();
+        client.set_fixture_mode(&dir, FixtureMode::Replay);
+
+        let status = client.health_check().await;
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(!status.authenticated);
+        assert!(status.error.is_some());
+        assert!(status.latency_ms.is_some());
+    }
+
+    #[test]
+    fn test_history_order_parsing_live_and_paper() {
+        let ticker = serde_json::json!({
+            "tickerId": 913256135, "disSymbol": "AAPL", "name": "Apple Inc"
+        });
+
+        // Live history groups orders under a combo
+        let live = serde_json::json!([{
+            "comboId": "c1",
+            "orders": [{
+                "orderId": 111,
+                "ticker": ticker,
+                "action": "BUY",
+                "orderType": "LMT",
+                "status": "Partially Filled",
+                "statusCode": "PartialFilled",
+                "timeInForce": "GTC",
+                "totalQuantity": "10",
+                "filledQuantity": "4",
+                "lmtPrice": "150.25",
+                "createTime0": 1609459200000i64
+            }]
+        }]);
+        let orders = HistoryOrder::from_response(&live);
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].order_id, "111");
+        assert_eq!(orders[0].combo_id, Some("c1".to_string()));
+        assert_eq!(orders[0].status, OrderStatus::PartialFilled);
+        assert_eq!(orders[0].time_in_force, TimeInForce::GoodTillCancel);
+        assert_eq!(orders[0].limit_price, Some(150.25));
+        assert!(orders[0]
+            .placed_time
+            .as_ref()
+            .unwrap()
+            .starts_with("2021-01-01"));
+        assert!(orders[0].status.is_open());
+
+        let open: Order = orders[0].clone().into();
+        assert_eq!(open.filled_quantity, 4.0);
+        assert_eq!(open.create_time, open.placed_time);
+
+        // Combo group IDs may also be numeric
+        let mut numeric = live.clone();
+        numeric[0]["comboId"] = serde_json::json!(987);
+        let orders = HistoryOrder::from_response(&numeric);
+        assert_eq!(orders[0].combo_id, Some("987".to_string()));
+
+        // Paper history is a flat array
+        let paper = serde_json::json!([{
+            "orderId": "222",
+            "ticker": ticker,
+            "action": "SELL",
+            "orderType": "STP LMT",
+            "status": "Cancelled",
+            "totalQuantity": 5,
+            "filledQuantity": 0,
+            "auxPrice": "140"
+        }, {
+            "orderId": "333"
+        }]);
+        let orders = HistoryOrder::from_response(&paper);
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].order_type, OrderType::StopLimit);
+        assert_eq!(orders[0].status, OrderStatus::Cancelled);
+        assert_eq!(orders[0].stop_price, Some(140.0));
+        assert!(!orders[0].status.is_open());
+
+        let order: Order = orders[0].clone().into();
+        assert_eq!(order.order_id, "222");
+        assert_eq!(order.ticker_id(), Some(913256135));
+
+        // Statuses the parser doesn't know are kept as reported, never open
+        let unknown = serde_json::json!([{
+            "orderId": "444",
+            "ticker": ticker,
+            "action": "BUY",
+            "orderType": "LMT",
+            "status": "Pending Cancel",
+            "totalQuantity": 1,
+            "lmtPrice": "100"
+        }]);
+        let orders = HistoryOrder::from_response(&unknown);
+        assert_eq!(
+            orders[0].status,
+            OrderStatus::Unknown("Pending Cancel".to_string())
+        );
+        assert!(!orders[0].status.is_open());
+        let status: OrderStatus = serde_json::from_value(serde_json::json!("Inactive")).unwrap();
+        assert_eq!(status, OrderStatus::Unknown("Inactive".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_without_session() {
+        use crate::shutdown::ShutdownPolicy;
+
+        let mut client = WebullClient::new_paper(Some(6)).unwrap();
+        let policy = ShutdownPolicy::flatten().logout(false);
+        assert!(policy.cancel_open_orders);
+
+        let report = client.shutdown(policy, &mut []).await;
+
+        // No paper account is resolved before login, so fetching orders fails
+        assert!(report.cancelled_orders.is_empty());
+        assert_eq!(report.errors.len(), 1);
+        assert!(!report.logged_out);
+        assert!(!report.is_clean());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_reports_refused_logout() {
+        use crate::fixtures::{FixtureMode, FixtureStore};
+        use crate::shutdown::ShutdownPolicy;
+
+        let dir = std::env::temp_dir().join(format!("webull_logout_{}", uuid::Uuid::new_v4()));
+        let mut live = crate::LiveWebullClient::new(None).unwrap();
+        live.set_retry_policy(crate::RetryPolicy::none());
+        let request = reqwest::Client::new()
+            .post(live.endpoints.logout())
+            .build()
+            .unwrap();
+        FixtureStore::new(&dir, FixtureMode::Record)
+            .record(&request, 400, b"{}")
+            .unwrap();
+        live.set_fixture_mode(&dir, FixtureMode::Replay);
+
+        let mut client = WebullClient::Live(live);
+        let report = client.shutdown(ShutdownPolicy::new(), &mut []).await;
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(!report.logged_out);
+        assert_eq!(report.errors, vec!["Logout was not accepted".to_string()]);
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn test_activity_parsing() {
+        let dividend = Activity::from_value(&serde_json::json!({
+            "id": 987,
+            "type": "CASH_DIVIDEND",
+            "date": "2024-03-15",
+            "ticker": {"disSymbol": "MSFT"},
+            "amount": "12.34",
+            "currency": "USD"
+        }));
+        assert_eq!(dividend.id, Some("987".to_string()));
+        assert_eq!(dividend.activity_type, ActivityType::Dividend);
+        assert_eq!(dividend.symbol, Some("MSFT".to_string()));
+        assert_eq!(dividend.amount, Some(12.34));
+        assert_eq!(dividend.date, chrono::NaiveDate::from_ymd_opt(2024, 3, 15));
+
+        let deposit = Activity::from_value(&serde_json::json!({
+            "type": "ACH_DEPOSIT",
+            "createTime": 1609459200000i64,
+            "amount": 1000
+        }));
+        assert_eq!(deposit.activity_type, ActivityType::Transfer);
+        assert_eq!(deposit.date, chrono::NaiveDate::from_ymd_opt(2021, 1, 1));
+
+        assert_eq!(ActivityType::from_raw("BUY"), ActivityType::Trade);
+        assert_eq!(
+            ActivityType::from_raw("MARGIN_INTEREST"),
+            ActivityType::Interest
+        );
+        assert_eq!(ActivityType::from_raw("REG_FEE"), ActivityType::Fee);
+        assert_eq!(ActivityType::from_raw("SPLIT"), ActivityType::Other);
+    }
+
+    #[test]
+    fn test_good_till_date_orders() {
+        let expire = chrono::Utc::now().date_naive() + chrono::Duration::days(30);
+        let order = PlaceOrderRequest::limit(150.0)
+            .ticker_id(913256135)
+            .buy()
+            .quantity(1.0)
+            .good_till_date(expire)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&order).unwrap();
+        assert_eq!(json["timeInForce"], "GTD");
+        assert_eq!(json["expireDate"], expire.format("%Y-%m-%d").to_string());
+
+        // Non-GTD orders don't send the field
+        let day = PlaceOrderRequest::market()
+            .ticker_id(913256135)
+            .buy()
+            .quantity(1.0)
+            .build()
+            .unwrap();
+        assert!(serde_json::to_value(&day)
+            .unwrap()
+            .get("expireDate")
+            .is_none());
+
+        let today = chrono::NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+        let gtd = TimeInForce::GoodTillDate;
+        assert!(validate_expiration(&gtd, None, today).is_err());
+        assert!(validate_expiration(&gtd, Some(today), today).is_err());
+        assert!(validate_expiration(&gtd, today.succ_opt(), today).is_ok());
+        assert!(validate_expiration(
+            &gtd,
+            Some(today + chrono::Duration::days(GTD_MAX_DAYS + 1)),
+            today
+        )
+        .is_err());
+        assert!(validate_expiration(&TimeInForce::Day, today.succ_opt(), today).is_err());
+    }
+
+    #[test]
+    fn test_price_band_check() {
+        let response = serde_json::json!({
+            "forward": false,
+            "checkResultList": [{
+                "code": "OAUTH_PRICE_OUT_OF_RANGE",
+                "lowerLimit": "95.50",
+                "upperLimit": "105.50"
+            }]
+        });
+        let band = PriceBand::from_check_response(&response).unwrap();
+        assert_eq!(band.lower, 95.5);
+        assert_eq!(band.upper, 105.5);
+
+        assert!(band.check(100.0).is_ok());
+        match band.check(110.0) {
+            Err(WebullError::PriceOutOfBand {
+                price,
+                lower,
+                upper,
+            }) => {
+                assert_eq!(price, 110.0);
+                assert_eq!((lower, upper), (95.5, 105.5));
+            }
+            other => panic!("expected PriceOutOfBand, got {:?}", other),
+        }
+
+        let flat = serde_json::json!({"data": {"minPrice": 1.0, "maxPrice": 2.0}});
+        assert!(PriceBand::from_check_response(&flat).unwrap().contains(1.5));
+        assert!(PriceBand::from_check_response(&serde_json::json!({"forward": true})).is_none());
+    }
+
+    #[test]
+    fn test_options_chain_parsing_and_filters() {
+        let response = serde_json::json!({
+            "expireDateList": [{
+                "from": {"date": "2024-06-21", "days": 18, "weekly": 0},
+                "data": [
+                    {
+                        "tickerId": 1041211111, "symbol": "AAPL240621C00190000",
+                        "strikePrice": "190", "direction": "call",
+                        "bidList": [{"price": "5.00", "volume": "12"}],
+                        "askList": [{"price": "5.50", "volume": "8"}],
+                        "close": "5.20", "volume": "1523", "openInterest": "10234",
+                        "impVol": "0.2531", "delta": "0.5412", "gamma": "0.0321",
+                        "theta": "-0.1123", "vega": "0.2211"
+                    },
+                    {
+                        "tickerId": "1041211112", "symbol": "AAPL240621P00190000",
+                        "strikePrice": "190", "direction": "put"
+                    },
+                    {
+                        "tickerId": 1041211113, "symbol": "AAPL240621C00220000",
+                        "strikePrice": "220", "direction": "call"
+                    }
+                ]
+            }, {
+                "from": {"date": "2024-06-28", "days": 25, "weekly": 1},
+                "data": [{
+                    "strikePrice": "195",
+                    "call": {"tickerId": 1041211114, "strikePrice": "195"},
+                    "put": {"tickerId": 1041211115, "strikePrice": "195"}
+                }]
+            }]
+        });
+
+        let contracts = OptionContract::from_response(&response);
+        assert_eq!(contracts.len(), 5);
+
+        let call = &contracts[0];
+        assert_eq!(call.option_type, "CALL");
+        assert_eq!(call.expiration_date, "2024-06-21");
+        assert_eq!(call.mid(), Some(5.25));
+        assert_eq!(call.open_interest, Some(10234.0));
+        assert_eq!(call.delta, Some(0.5412));
+        assert_eq!(call.theta, Some(-0.1123));
+        assert_eq!(contracts[4].option_type, "PUT");
+        assert_eq!(contracts[4].expiration_date, "2024-06-28");
+
+        let expirations = OptionExpiration::from_response(&response);
+        assert_eq!(expirations.len(), 2);
+        assert_eq!(expirations[0].days, Some(18));
+        assert!(expirations[1].weekly);
+
+        let unordered = serde_json::json!({"expireDateList": [
+            {"from": {"date": "2024-07-19", "days": 46}},
+            {"from": {"date": "2024-06-21", "days": 18}},
+            {"from": {"date": "2024-07-19", "days": 46}}
+        ]});
+        let dates: Vec<_> = OptionExpiration::from_response(&unordered)
+            .into_iter()
+            .map(|e| e.date)
+            .collect();
+        assert_eq!(dates, vec!["2024-06-21", "2024-07-19"]);
+
+        let query = OptionsRequestBuilder::new()
+            .ticker("AAPL")
+            .expiration("2024-06-21")
+            .calls_only()
+            .strike_range(180.0, 200.0)
+            .build()
+            .unwrap();
+        let filtered: Vec<_> = contracts.iter().filter(|c| query.matches(c)).collect();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].ticker_id, 1041211111);
+
+        assert!(OptionsRequestBuilder::new()
+            .ticker("AAPL")
+            .strike_range(200.0, 100.0)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_option_quote_parsing() {
+        let value = serde_json::json!({
+            "tickerId": 1041211111,
+            "symbol": "AAPL240621C00190000",
+            "strikePrice": "190.00",
+            "expireDate": "2024-06-21",
+            "direction": "call",
+            "bidList": [{"price": "4.00", "volume": "25"}],
+            "askList": [{"price": "4.50", "volume": "40"}],
+            "close": "4.25",
+            "change": "0.35",
+            "changeRatio": "0.0897",
+            "delta": "0.4821",
+            "rho": "0.0412",
+            "unPrice": "188.32"
+        });
+
+        let quote = OptionQuote::from_value(&value).unwrap();
+        assert_eq!(quote.contract.ticker_id, 1041211111);
+        assert_eq!(quote.contract.option_type, "CALL");
+        assert_eq!(quote.contract.expiration_date, "2024-06-21");
+        assert_eq!(quote.mid(), Some(4.25));
+        assert_eq!(quote.bid_size, Some(25.0));
+        assert_eq!(quote.ask_size, Some(40.0));
+        assert_eq!(quote.rho, Some(0.0412));
+        assert_eq!(quote.underlying_price, Some(188.32));
+
+        assert!(OptionQuote::from_value(&serde_json::json!({"symbol": "X"})).is_none());
+    }
+
+    #[test]
+    fn test_top_options_parsing() {
+        let response = serde_json::json!({
+            "data": [{
+                "belongTicker": {"tickerId": 913243251, "disSymbol": "SPY"},
+                "derivative": {
+                    "tickerId": 1041500001,
+                    "symbol": "SPY240603C00530000",
+                    "strikePrice": "530",
+                    "expireDate": "2024-06-03",
+                    "direction": "call",
+                    "volume": "412033",
+                    "openInterest": "20311"
+                }
+            }, {
+                "belongTicker": {"tickerId": 913243251, "disSymbol": "SPY"}
+            }]
+        });
+
+        let top = TopOption::from_response(&response);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].underlying_ticker_id, Some(913243251));
+        assert_eq!(top[0].underlying_symbol.as_deref(), Some("SPY"));
+        assert_eq!(top[0].contract.volume, Some(412033.0));
+        assert_eq!(top[0].contract.open_interest, Some(20311.0));
+        assert_eq!(TopOptionsRank::OpenInterest.as_str(), "position");
+    }
+
+    #[test]
+    fn test_quotes_batch_parsing() {
+        let response = serde_json::json!([
+            {
+                "tickerId": 913256135, "close": "189.50", "change": "1.20",
+                "changeRatio": "0.0064", "preClose": "188.30", "open": "188.90",
+                "high": "190.10", "low": "188.20", "volume": "51234567"
+            },
+            {
+                "tickerId": "913243251", "close": "530.12", "change": "-2.01",
+                "changeRatio": "-0.0038", "preClose": "532.13", "open": "531.00",
+                "high": "532.50", "low": "529.80", "volume": "61234567"
+            },
+            {"tickerId": 1, "status": "halted"}
+        ]);
+
+        let quotes = Quote::from_batch_response(&response);
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(quotes[0].ticker_id.as_deref(), Some("913256135"));
+        assert_eq!(quotes[1].ticker_id.as_deref(), Some("913243251"));
+        assert_eq!(quotes[1].close, 530.12);
+
+        let endpoints = crate::endpoints::Endpoints::new();
+        assert!(endpoints
+            .quotes_batch(&["913256135", "913243251"])
+            .contains("ids=913256135,913243251"));
+    }
+
+    #[test]
+    fn test_api_error_code_mapping() {
+        use serde_json::json;
+
+        let pin = WebullError::from_api_body(
+            417,
+            &json!({"code": "trade.pwd.invalid", "msg": "Wrong PIN", "data": {"remainTimes": 2}}),
+        );
+        assert!(matches!(
+            pin,
+            Some(WebullError::InvalidTradePin {
+                remaining_attempts: Some(2)
+            })
+        ));
+
+        let funds = WebullError::from_api_body(
+            200,
+            &json!({"success": false, "code": "trade.webull.INSUFFICIENT_BUYING_POWER", "msg": "x"}),
+        );
+        assert!(matches!(funds, Some(WebullError::InsufficientFunds)));
+
+        let limited = WebullError::from_api_body(429, &json!({}));
+        assert!(matches!(limited, Some(WebullError::RateLimitExceeded)));
+
+        let rejected = WebullError::from_api_body(
+            400,
+            &json!({"code": "order.price.tick", "msg": "Bad tick size"}),
+        )
+        .unwrap();
+        assert_eq!(
+            rejected.rejection_reason(),
+            Some(crate::RejectionReason::InvalidTickSize)
+        );
+        assert!(matches!(
+            funds.as_ref().and_then(WebullError::rejection_reason),
+            Some(crate::RejectionReason::InsufficientBuyingPower)
+        ));
+
+        let unknown = WebullError::from_api_body(
+            400,
+            &json!({"code": "order.strange.failure", "msg": "Something odd"}),
+        );
+        match unknown {
+            Some(WebullError::ApiErrorCode { code, message }) => {
+                assert_eq!(code, "order.strange.failure");
+                assert_eq!(message, "Something odd");
+            }
+            other => panic!("unexpected: {:?}", other),
+        }
+
+        // Codes added to a catalogue decode too
+        let mut catalogue = crate::RejectionCatalogue::default();
+        catalogue.insert(
+            "order.strange.failure",
+            crate::RejectionReason::AccountRestricted,
+        );
+        let registered = WebullError::from_api_body_with(
+            400,
+            &json!({"code": "order.strange.failure", "msg": "Something odd"}),
+            &catalogue,
+        );
+        assert!(matches!(
+            registered,
+            Some(WebullError::OrderRejected {
+                reason: crate::RejectionReason::AccountRestricted,
+                ..
+            })
+        ));
+
+        // Successful bodies are left alone
+        assert!(
+            WebullError::from_api_body(200, &json!({"code": "200", "success": false})).is_none()
+        );
+        assert!(WebullError::from_api_body(200, &json!({"data": [], "msg": "ok"})).is_none());
+        assert!(WebullError::from_api_body(200, &json!([{"tickerId": 1}])).is_none());
+    }
+
+    #[test]
+    fn test_bar_interval_from_str() {
+        assert_eq!("5m".parse::<BarInterval>().unwrap(), BarInterval::M5);
+        assert_eq!("m5".parse::<BarInterval>().unwrap(), BarInterval::M5);
+        assert_eq!("1h".parse::<BarInterval>().unwrap(), BarInterval::H1);
+        assert_eq!("1d".parse::<BarInterval>().unwrap(), BarInterval::D1);
+        assert_eq!("1M".parse::<BarInterval>().unwrap(), BarInterval::Month1);
+        assert!("10s".parse::<BarInterval>().is_err());
+
+        // Spellings accepted before the enum existed
+        for (alias, interval) in [
+            ("3m", BarInterval::M3),
+            ("m3", BarInterval::M3),
+            ("120m", BarInterval::H2),
+            ("m120", BarInterval::H2),
+            ("2h", BarInterval::H2),
+            ("240m", BarInterval::H4),
+            ("4h", BarInterval::H4),
+            ("d5", BarInterval::D5),
+        ] {
+            assert_eq!(alias.parse::<BarInterval>().unwrap(), interval);
+            assert!(parse_interval(alias).is_ok());
+        }
+
+        assert_eq!(BarInterval::D1.to_string(), "d1");
+        assert_eq!(BarInterval::Month1.as_str(), "mo1");
+    }
+
+    #[test]
+    fn test_security_event_parsing() {
+        let response = serde_json::json!({
+            "data": [
+                {
+                    "id": 101,
+                    "type": "NEW_DEVICE_LOGIN",
+                    "title": "New device login",
+                    "deviceName": "Pixel 8",
+                    "loginIp": "203.0.113.7",
+                    "createTime": 1717243200000i64
+                },
+                {"messageId": "102", "msgType": "SECURITY", "title": "Password changed"},
+                {"id": 103, "msgType": "SECURITY", "type": "MFA_ENABLED", "title": "2FA on"},
+                {"title": "missing id"}
+            ]
+        });
+
+        let events = SecurityEvent::from_list_response(&response);
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].id, "101");
+        assert_eq!(events[0].kind, SecurityEventKind::NewDeviceLogin);
+        assert_eq!(events[0].ip.as_deref(), Some("203.0.113.7"));
+        assert!(events[0]
+            .occurred_at
+            .as_deref()
+            .unwrap()
+            .starts_with("2024-06-01"));
+        assert_eq!(events[1].kind, SecurityEventKind::PasswordChange);
+        assert_eq!(
+            events[2].kind,
+            SecurityEventKind::Unknown("MFA_ENABLED".to_string())
+        );
+    }
+
+    #[test]
+    fn test_shortability_and_short_orders() {
+        let response = serde_json::json!({
+            "data": {
+                "tickerId": 913256135,
+                "assetType": "STOCK",
+                "shortable": true,
+                "etb": "Y",
+                "shortableShares": "150000",
+                "borrowFeeRate": "0.0025"
+            }
+        });
+        let shortability = Shortability::from_permission_response("913256135", &response).unwrap();
+        assert!(shortability.shortable);
+        assert_eq!(shortability.asset_type.as_deref(), Some("STOCK"));
+        assert_eq!(shortability.easy_to_borrow, Some(true));
+        assert_eq!(shortability.available_shares, Some(150000.0));
+        assert_eq!(shortability.fee_rate, Some(0.0025));
+
+        let exhausted = serde_json::json!({"shortable": true, "shortableShares": 0});
+        assert!(
+            !Shortability::from_permission_response("1", &exhausted)
+                .unwrap()
+                .shortable
+        );
+        // No flag at all is not the same as "not shortable"
+        let silent = serde_json::json!({"data": {"tickerId": 1, "assetType": "STOCK"}});
+        assert!(matches!(
+            Shortability::from_permission_response("1", &silent),
+            Err(WebullError::ParseError(_))
+        ));
+
+        let order = PlaceOrderRequest::market()
+            .ticker_id(913256135)
+            .sell_short()
+            .quantity(10.0)
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&order).unwrap();
+        assert_eq!(json["action"], "SELL");
+        assert_eq!(json["shortSupport"], true);
+
+        // Plain sells don't carry the flag
+        let sell = PlaceOrderRequest::market()
+            .ticker_id(913256135)
+            .sell()
+            .quantity(10.0)
+            .build()
+            .unwrap();
+        assert!(serde_json::to_value(&sell)
+            .unwrap()
+            .get("shortSupport")
+            .is_none());
+
+        let margin: AccountDetail =
+            serde_json::from_value(serde_json::json!({"secAccountId": 1, "accountType": "MARGIN"}))
+                .unwrap();
+        assert!(margin.supports_short_selling());
+        let cash: AccountDetail =
+            serde_json::from_value(serde_json::json!({"secAccountId": 1, "accountType": "CASH"}))
+                .unwrap();
+        assert!(!cash.supports_short_selling());
+    }
+
+    #[test]
+    fn test_earnings_event_optional_fields() {
+        let response = serde_json::json!({
+            "data": [
+                {
+                    "ticker": {"tickerId": 913256135, "disSymbol": "AAPL"},
+                    "values": {
+                        "releaseDate": "2024-08-01",
+                        "fiscalPeriod": "Q3 2024",
+                        "epsEstimate": "1.35",
+                        "whisperEps": "1.40"
+                    },
+                    "guidance": {"period": "Q4 2024", "epsLow": 1.50, "epsHigh": 1.60}
+                },
+                {
+                    "ticker": {"tickerId": 913256135, "disSymbol": "AAPL"},
+                    "values": {
+                        "releaseDate": "2024-05-02",
+                        "epsEstimate": "1.50",
+                        "epsActual": "1.53",
+                        "priceReaction": "5.98"
+                    }
+                }
+            ]
+        });
+
+        let events = EarningsEvent::from_list_response(&response);
+        assert_eq!(events.len(), 2);
+
+        let upcoming = &events[0];
+        assert!(!upcoming.is_reported());
+        assert_eq!(upcoming.symbol.as_deref(), Some("AAPL"));
+        assert_eq!(upcoming.whisper_eps, Some(1.40));
+        let guidance = upcoming.guidance.as_ref().unwrap();
+        assert_eq!(guidance.eps_high, Some(1.60));
+        assert_eq!(guidance.revenue_low, None);
+
+        let reported = &events[1];
+        assert!(reported.is_reported());
+        assert_eq!(reported.whisper_eps, None);
+        assert!(reported.guidance.is_none());
+        assert_eq!(reported.price_reaction, Some(5.98));
+    }
+
+    #[test]
+    fn test_earnings_calendar_entries() {
+        // Calendar entries carry the release time after the date
+        let response = serde_json::json!({
+            "data": [
+                {
+                    "ticker": {"tickerId": 913323997, "disSymbol": "NVDA"},
+                    "values": {"releaseDate": "2024-08-28 16:20:00", "epsEstimate": 0.64}
+                },
+                {"ticker": {"tickerId": 1}, "values": {}}
+            ]
+        });
+        let events = EarningsEvent::from_list_response(&response);
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0].release_day(),
+            chrono::NaiveDate::from_ymd_opt(2024, 8, 28)
+        );
+        assert_eq!(events[0].eps_estimate, Some(0.64));
+        assert_eq!(events[1].release_day(), None);
+    }
+
+    #[test]
+    fn test_account_list_detects_advisors_accounts() {
+        let response = serde_json::json!({
+            "data": [
+                {"secAccountId": 12345678, "accountType": "MARGIN", "brokerId": 8, "status": "NORMAL"},
+                {"secAccountId": "87654321", "accountType": "ROBO_ADVISOR", "brokerId": 8}
+            ]
+        });
+        let accounts = LinkedAccount::from_list_response(&response);
+        assert_eq!(accounts.len(), 2);
+        assert_eq!(accounts[0].account_id, "12345678");
+        assert_eq!(accounts[0].kind, AccountKind::Brokerage);
+        assert!(accounts[1].is_managed());
+
+        let portfolio = ManagedPortfolio::from_value(
+            "87654321",
+            &serde_json::json!({
+                "netLiquidation": "10500.25",
+                "accountMembers": [{"key": "cashBalance", "value": "120.50"}],
+                "positions": []
+            }),
+        );
+        assert_eq!(portfolio.net_liquidation, Some(10500.25));
+        assert_eq!(portfolio.cash_balance, Some(120.50));
+        assert!(portfolio.positions.is_empty());
+    }
+
+    #[test]
+    fn test_live_account_computed_fields() {
+        let mut account: AccountDetail = serde_json::from_value(serde_json::json!({
+            "secAccountId": 12345678,
+            "accountType": "CASH",
+            "netLiquidation": "5250.00",
+            "accountMembers": [
+                {"key": "totalMarketValue", "value": "4000.00"},
+                {"key": "cashBalance", "value": "1250.00"},
+                {"key": "dayBuyingPower", "value": "1000.00"},
+                {"key": "unsettledFunds", "value": "250.00"}
+            ]
+        }))
+        .unwrap();
+        account.compute_member_fields();
+
+        assert_eq!(account.total_market_value, Some(4000.0));
+        assert_eq!(account.cash_balance, Some(1250.0));
+        assert_eq!(account.total_cash, Some(1250.0));
+        assert_eq!(account.buying_power, Some(1000.0));
+        assert_eq!(account.unsettled_funds, Some(250.0));
+        assert_eq!(account.settled_funds, Some(1000.0));
+    }
+
+    #[test]
+    fn test_drip_settings_round_trip() {
+        let response = serde_json::json!({
+            "data": {
+                "dripStatus": "ON",
+                "tickers": [
+                    {"ticker": {"tickerId": 913256135, "disSymbol": "AAPL"}, "dripStatus": "OFF"},
+                    {"tickerId": "913243251", "enabled": true},
+                    {"dripStatus": "ON"}
+                ]
+            }
+        });
+        let mut settings = DripSettings::from_value(&response).unwrap();
+        assert!(settings.enabled);
+        assert_eq!(settings.holdings.len(), 2);
+        assert_eq!(settings.holdings[0].symbol.as_deref(), Some("AAPL"));
+        assert!(!settings.is_enabled_for(913256135));
+        assert!(settings.is_enabled_for(913243251));
+        // Holdings without an override follow the account setting
+        assert!(settings.is_enabled_for(1));
+
+        settings.set_holding(913256135, true).set_holding(1, false);
+        let request = settings.to_request();
+        assert_eq!(request["dripStatus"], "ON");
+        assert_eq!(request["tickers"][0]["dripStatus"], "ON");
+        assert_eq!(request["tickers"][2]["tickerId"], 1);
+        assert_eq!(request["tickers"][2]["dripStatus"], "OFF");
+
+        // An unknown status is an error, not "off"
+        let unknown = serde_json::json!({"data": {"dripStatus": "PENDING"}});
+        assert!(matches!(
+            DripSettings::from_value(&unknown),
+            Err(WebullError::ParseError(_))
+        ));
+        let unknown_holding = serde_json::json!({
+            "dripStatus": "OFF",
+            "tickers": [{"tickerId": 1, "dripStatus": 2}]
+        });
+        assert!(DripSettings::from_value(&unknown_holding).is_err());
+        assert!(DripSettings::from_value(&serde_json::json!({"data": {}})).is_err());
+    }
+
+    #[test]
+    fn test_financial_statement_parsing() {
+        let response = serde_json::json!({
+            "data": [
+                {
+                    "fiscalYear": 2023,
+                    "fiscalPeriod": 4,
+                    "reportEndDate": "2023-09-30T00:00:00.000+0000",
+                    "items": [
+                        {"title": "Operating Cash Flow", "value": "21598000000"},
+                        {"title": "Capital Expenditure", "value": -2163000000.0},
+                        {"title": "Notes", "value": null}
+                    ]
+                },
+                {
+                    "fiscalYear": 2024,
+                    "fiscalPeriod": 1,
+                    "reportEndDate": "2023-12-30",
+                    "operatingCashFlow": 39895000000.0,
+                    "capitalExpenditure": "-2392000000",
+                    "freeCashFlow": 37503000000.0
+                }
+            ]
+        });
+        let statement = FinancialStatement::from_response(
+            StatementType::CashFlow,
+            ReportPeriod::Quarterly,
+            &response,
+        );
+        assert_eq!(statement.periods.len(), 2);
+
+        let latest = statement.latest().unwrap();
+        assert_eq!(latest.fiscal_year, Some(2024));
+        assert_eq!(latest.free_cash_flow(), Some(37_503_000_000.0));
+        assert!(!latest.items.contains_key("fiscalyear"));
+
+        let previous = &statement.periods[1];
+        assert_eq!(previous.end_date.as_deref(), Some("2023-09-30"));
+        assert_eq!(previous.get("operating cash flow"), Some(21_598_000_000.0));
+        assert_eq!(previous.items.len(), 2);
+        assert_eq!(previous.free_cash_flow(), Some(19_435_000_000.0));
+        assert_eq!(previous.revenue(), None);
+
+        assert_eq!(ReportPeriod::Annual.query_values(), (102, "0"));
+        assert_eq!(StatementType::BalanceSheet.as_path(), "balancesheet");
+    }
+
+    #[test]
+    fn test_fundamentals_parsing() {
+        let response = serde_json::json!({
+            "data": {
+                "tickerId": "913256135",
+                "ticker": {"disSymbol": "AAPL", "name": "Apple Inc"},
+                "sector": {"id": 7, "name": "Technology"},
+                "industryName": "Consumer Electronics",
+                "marketValue": "2950000000000",
+                "peTtm": "30.12",
+                "eps": 6.43,
+                "beta": "1.29",
+                "fiftyTwoWkHigh": "199.62",
+                "fiftyTwoWkLow": "164.08",
+                "totalShares": "15441900000",
+                "outstandingShares": "15408100000",
+                "floatShares": 15390000000.0,
+                "dividend": "0.96",
+                "yield": "0.0051",
+                "exDividendDate": "2024-02-09",
+                "nextEarningDay": "2024-05-02T20:30:00.000+0000"
+            }
+        });
+        let fundamental = Fundamental::from_value(&response);
+        assert_eq!(fundamental.ticker_id, Some(913256135));
+        assert_eq!(fundamental.symbol.as_deref(), Some("AAPL"));
+        assert_eq!(fundamental.sector.as_deref(), Some("Technology"));
+        assert_eq!(
+            fundamental.industry.as_deref(),
+            Some("Consumer Electronics")
+        );
+        assert_eq!(fundamental.pe_ratio, Some(30.12));
+        assert_eq!(fundamental.shares_outstanding, Some(15_408_100_000.0));
+        assert_eq!(fundamental.dividend_yield, Some(0.0051));
+        assert_eq!(
+            fundamental.next_earnings_date.as_deref(),
+            Some("2024-05-02")
+        );
+        assert_eq!(fundamental.dividend_pay_date, None);
+        let position = fundamental.range_position(181.85).unwrap();
+        assert!((position - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_extended_hours_quote_fields() {
+        let quote: Quote = serde_json::from_value(serde_json::json!({
+            "tickerId": 913256135, "close": "189.50", "change": "1.20",
+            "changeRatio": "0.0064", "preClose": "188.30", "open": "188.90",
+            "high": "190.10", "low": "188.20", "volume": "51234567",
+            "pPrice": "190.75", "pChange": "1.25", "pChRatio": "0.0066"
+        }))
+        .unwrap();
+        assert_eq!(quote.p_price, Some(190.75));
+        assert_eq!(quote.p_change, Some(1.25));
+        assert_eq!(quote.p_change_ratio, Some(0.0066));
+        assert_eq!(quote.latest_price(), 190.75);
+
+        let regular = Quote {
+            p_price: None,
+            ..quote
+        };
+        assert_eq!(regular.latest_price(), 189.50);
+    }
+
+    #[test]
+    fn test_trade_ticks_parse_oldest_first() {
+        let response = serde_json::json!([{
+            "tickerId": 913256135,
+            "data": [
+                {"tradeTime": 1718990401500i64, "price": "189.51", "volume": "100", "trdEx": "NSDQ"},
+                {"tradeTime": 1718990400000i64, "price": "189.50", "volume": "25", "tradeCondition": "I"},
+                {"tradeTime": 1718990402000i64, "volume": "10"}
+            ]
+        }]);
+        let ticks = TradeTick::from_response(&response);
+        assert_eq!(ticks.len(), 2);
+        assert_eq!(ticks[0].price, 189.50);
+        assert_eq!(ticks[0].size, 25.0);
+        assert_eq!(ticks[0].condition.as_deref(), Some("I"));
+        assert_eq!(ticks[1].exchange.as_deref(), Some("NSDQ"));
+
+        let wrapped = serde_json::json!({"data": [{"time": 1, "price": 2.5}]});
+        assert_eq!(TradeTick::from_response(&wrapped)[0].size, 0.0);
+    }
+
+    #[cfg(feature = "sandbox")]
+    #[test]
+    fn test_custom_environment_rebases_every_host() {
+        use crate::endpoints::{Endpoints, Environment};
+        use crate::stream::StreamConfig;
+        use crate::{ClientConfig, LiveWebullClient};
+
+        let environment = Environment::Custom("http://localhost:8080/".to_string());
+        let endpoints = Endpoints::for_environment(&environment);
+        assert_eq!(
+            endpoints.quotes_extended("913256135"),
+            "http://localhost:8080/api/quotes/ticker/getTickerRealTime?tickerId=913256135&includeSecu=1&includeQuote=1&more=1"
+        );
+        assert_eq!(
+            endpoints.base_paper_url,
+            "http://localhost:8080/webull-paper-center/api"
+        );
+        assert_eq!(endpoints.base_trade_url, "http://localhost:8080/api/trade");
+
+        let client = LiveWebullClient::with_config(
+            None,
+            ClientConfig::new().environment(environment.clone()),
+        )
+        .unwrap();
+        assert!(client
+            .endpoints
+            .login()
+            .starts_with("http://localhost:8080/api/"));
+
+        let stream = StreamConfig::for_environment(&environment);
+        assert_eq!(stream.host, "ws://localhost:8080/mqtt");
+        assert_eq!(stream.port, 8080);
+        assert!(!stream.use_ssl);
+    }
+
+    #[test]
+    fn test_etf_profile_and_holdings_parse() {
+        let holdings = serde_json::json!({"data": [
+            {"symbol": "MSFT", "name": "Microsoft", "tickerId": 913323997, "ratio": "6.9"},
+            {"symbol": "AAPL", "tickerId": 913256135, "ratio": "7.1", "shares": "170000000"},
+            {"name": "Cash"}
+        ]});
+        let holdings = EtfHolding::from_response(&holdings);
+        assert_eq!(holdings.len(), 2);
+        assert_eq!(holdings[0].symbol, "AAPL");
+        assert!((holdings[0].weight - 0.071).abs() < 1e-9);
+        assert_eq!(holdings[0].shares, Some(170_000_000.0));
+
+        let profile = serde_json::json!({"data": {
+            "name": "SPDR S&P 500 ETF Trust",
+            "expenseRatio": "0.0945",
+            "totalAssets": 5.2e11,
+            "holdingsCount": 503,
+            "topHoldings": [{"symbol": "NVDA", "weight": 7.2}]
+        }});
+        let profile = EtfProfile::from_response("913243251", &profile);
+        assert_eq!(profile.name.as_deref(), Some("SPDR S&P 500 ETF Trust"));
+        assert!((profile.expense_ratio.unwrap() - 0.000945).abs() < 1e-12);
+        assert_eq!(profile.aum, Some(5.2e11));
+        assert_eq!(profile.holdings_count, Some(503));
+        assert_eq!(profile.top_holdings[0].symbol, "NVDA");
+    }
+
+    #[test]
+    fn test_ipo_calendar_parse_soonest_first() {
+        let response = serde_json::json!({"data": [
+            {"symbol": "LATE", "priceRange": "$14.00 - $16.00", "shares": "5000000"},
+            {"symbol": "SOON", "tickerId": 950000001, "ipoPrice": 21.5, "ipoDate": "2024-06-20"},
+            {"symbol": "NEXT", "minPrice": "9", "maxPrice": "11", "ipoDate": "2024-06-27 00:00:00"},
+            {"name": "No symbol"}
+        ]});
+        let listings = IpoListing::from_response(&response);
+        let symbols: Vec<&str> = listings.iter().map(|l| l.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["SOON", "NEXT", "LATE"]);
+        assert_eq!(listings[0].price_low, Some(21.5));
+        assert_eq!(listings[0].price_high, Some(21.5));
+        assert_eq!(listings[1].price_high, Some(11.0));
+        assert_eq!(listings[2].price_low, Some(14.0));
+        assert_eq!(listings[2].price_high, Some(16.0));
+        assert_eq!(listings[2].shares, Some(5_000_000.0));
+        assert!(listings[2].expected_date.is_none());
+
+        let order: IpoOrder = serde_json::from_value(serde_json::json!({
+            "orderId": 123, "tickerId": 950000001, "disSymbol": "SOON",
+            "statusStr": "Pending", "totalQuantity": "100", "lmtPrice": "21.50"
+        }))
+        .unwrap();
+        assert_eq!(order.order_id.as_deref(), Some("123"));
+        assert_eq!(order.symbol.as_deref(), Some("SOON"));
+        assert_eq!(order.quantity, Some(100.0));
+    }
+
+    #[test]
+    fn test_endpoints_builder_overrides_and_redirects() {
+        let endpoints = crate::endpoints::Endpoints::builder()
+            .redirect_host("quotes-gw.webullfintech.com", "http://127.0.0.1:9000/")
+            .user_url("https://user-proxy.example.com/api")
+            .build()
+            .unwrap();
+        assert_eq!(
+            endpoints.trade_ticks("913256135", 5),
+            "http://127.0.0.1:9000/api/stock/tradeTick/getTradeTick?tickerId=913256135&count=5"
+        );
+        assert_eq!(
+            endpoints.base_user_url,
+            "https://user-proxy.example.com/api"
+        );
+        assert_eq!(
+            endpoints.base_new_trade_url,
+            "https://trade.webullfintech.com/api"
+        );
+
+        let config = crate::ClientConfig::new().endpoints(endpoints.clone());
+        let client = WebullClient::new_paper_with_config(None, config).unwrap();
+        assert_eq!(client.endpoints().base_user_url, endpoints.base_user_url);
+
+        assert!(crate::endpoints::Endpoints::builder()
+            .quote_url("not a url")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_client_builder_applies_options() {
+        let dir = std::env::temp_dir().join(format!("webull-builder-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let client = WebullClient::builder()
+            .paper()
+            .region(2)
+            .timeout(42)
+            .user_agent("scanner/1.0")
+            .header("x-source", "tests")
+            .did_path(&dir)
+            .rate_limit(3.0)
+            .build()
+            .unwrap();
+        assert!(client.is_paper());
+        assert!(dir.join("did.bin").exists());
+        match &client {
+            WebullClient::Paper(paper) => {
+                let live = &paper.base_client;
+                assert_eq!(live.timeout, 42);
+                assert_eq!(live.region_code, 2);
+                assert!(live.rate_limiter.is_some());
+                assert_eq!(live.headers["User-Agent"], "scanner/1.0");
+                assert_eq!(live.headers["x-source"], "tests");
+            }
+            WebullClient::Live(_) => unreachable!(),
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let bad = WebullClient::builder().header("bad header", "x").build();
+        assert!(matches!(bad, Err(WebullError::InvalidParameter(_))));
+        assert!(WebullClient::builder().proxy("::nope").build().is_err());
+    }
+
+    #[test]
+    fn test_client_builder_http_client_and_tls_options() {
+        let http = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(5))
+            .build()
+            .unwrap();
+        let client = WebullClient::builder()
+            .http_client(http.clone())
+            .build()
+            .unwrap();
+        assert!(!client.is_paper());
+
+        let conflicting = WebullClient::builder()
+            .http_client(http.clone())
+            .proxy("http://proxy.internal:3128")
+            .build();
+        assert!(matches!(conflicting, Err(WebullError::InvalidParameter(_))));
+
+        assert!(WebullClient::builder()
+            .root_certificate_pem(b"not a certificate")
+            .is_err());
+        let proxied = WebullClient::builder()
+            .proxy("http://proxy.internal:3128")
+            .proxy_auth("user", "secret")
+            .build();
+        assert!(proxied.is_ok());
+
+        assert!(crate::LiveWebullClient::with_http_client(None, http).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_class_timeouts_overrides_and_deadline() {
+        use crate::rate_limit::EndpointClass;
+        use std::time::Duration;
+
+        let config = crate::ClientConfig::new()
+            .timeout(20)
+            .class_timeout(EndpointClass::MarketData, 3)
+            .class_timeout(EndpointClass::Account, 60);
+        let client = crate::LiveWebullClient::with_config(None, config).unwrap();
+        assert_eq!(
+            client.request_timeout(EndpointClass::MarketData).unwrap(),
+            Duration::from_secs(3)
+        );
+        assert_eq!(
+            client.request_timeout(EndpointClass::Trading).unwrap(),
+            Duration::from_secs(20)
+        );
+        let quick = client.with_timeout(Duration::from_millis(500));
+        assert_eq!(
+            quick.request_timeout(EndpointClass::Account).unwrap(),
+            Duration::from_millis(500)
+        );
+
+        let bounded = client.with_deadline(Duration::from_secs(1));
+        assert!(bounded.request_timeout(EndpointClass::Account).unwrap() <= Duration::from_secs(1));
+
+        let expired = client.with_deadline(Duration::ZERO);
+        let result = expired
+            .get_bars_range("913256135", BarInterval::D1, 0, 86_400)
+            .await;
+        assert!(matches!(result, Err(WebullError::DeadlineExceeded)));
+    }
+
+    #[test]
+    fn test_explicit_did_skips_disk() {
+        let client = crate::LiveWebullClient::with_did(None, "0123456789abcdef").unwrap();
+        assert_eq!(client.get_did(), "0123456789abcdef");
+
+        let store = std::sync::Arc::new(crate::MemoryDidStore::new());
+        let mut client = WebullClient::builder()
+            .did("fedcba9876543210")
+            .build_live()
+            .unwrap();
+        assert_eq!(client.headers["did"], "fedcba9876543210");
+        client.did_store = store.clone();
+        client.replace_did("aaaabbbbccccdddd").unwrap();
+        assert_eq!(client.get_did(), "aaaabbbbccccdddd");
+        assert_eq!(
+            crate::DidStore::load(store.as_ref()).unwrap().as_deref(),
+            Some("aaaabbbbccccdddd")
+        );
+
+        // set_did writes through the same store rather than to did.bin
+        client.set_did("1111222233334444", None).unwrap();
+        assert_eq!(client.headers["did"], "1111222233334444");
+        assert_eq!(
+            crate::DidStore::load(store.as_ref()).unwrap().as_deref(),
+            Some("1111222233334444")
+        );
+        // A device ID that can't be sent as a header is refused, not saved
+        assert!(matches!(
+            client.replace_did("bad\nid"),
+            Err(WebullError::DeviceIdError(_))
+        ));
+        assert_eq!(client.get_did(), "1111222233334444");
+        assert_eq!(
+            crate::DidStore::load(store.as_ref()).unwrap().as_deref(),
+            Some("1111222233334444")
+        );
+        assert!(matches!(
+            crate::LiveWebullClient::with_did(None, "bad\nid"),
+            Err(WebullError::DeviceIdError(_))
+        ));
+    }
+
+    #[test]
+    fn test_captcha_challenge_from_login_error() {
+        let body = serde_json::json!({
+            "code": "user.check.slider.pic.fail",
+            "msg": "Please complete the verification",
+            "data": {"captchaToken": "tok-1"}
+        });
+        match WebullError::from_api_body(417, &body) {
+            Some(WebullError::CaptchaRequired(challenge)) => {
+                assert_eq!(challenge.kind, CaptchaKind::Slider);
+                assert_eq!(challenge.token.as_deref(), Some("tok-1"));
+            }
+            other => panic!("expected a captcha challenge, got {:?}", other),
+        }
+
+        let payload = CaptchaPayload::from_response(&serde_json::json!({"data": {
+            "captchaId": "c-9", "backgroundImage": "iVBORw0K", "sliderImage": "iVBORw1K", "y": 42
+        }}))
+        .unwrap();
+        assert_eq!(payload.captcha_id, "c-9");
+        assert_eq!(payload.slider_y, Some(42));
+        assert!(CaptchaPayload::from_response(&serde_json::json!({"data": {}})).is_none());
+
+        let verified = serde_json::json!({"data": {"ticket": "t-1"}});
+        assert_eq!(
+            CaptchaPayload::ticket_from_response(&verified).unwrap(),
+            "t-1"
+        );
+        assert!(matches!(
+            CaptchaPayload::ticket_from_response(&serde_json::json!({"success": false})),
+            Err(WebullError::AuthenticationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_mfa_options_and_response() {
+        let options = MfaOptions::default();
+        assert_eq!(options.code_type.code(), 5);
+        let phone = AccountIdentifier::parse("+1-2345678901").unwrap();
+        assert_eq!(phone.mfa_channel(), MfaChannel::Sms);
+        assert_eq!(phone.account_type(), 1);
+        let email = AccountIdentifier::parse("test@example.com").unwrap();
+        assert_eq!(email.mfa_channel(), MfaChannel::Email);
+        assert_eq!(MfaCodeType::Other(8).code(), 8);
+
+        let sent = MfaResponse::from_response(
+            true,
+            &serde_json::json!({"data": {"expireTime": 300, "interval": "60"}}),
+        );
+        assert!(sent.success);
+        assert_eq!(sent.expires_in, Some(300));
+        assert_eq!(sent.retry_after, Some(60));
+
+        let checked =
+            MfaResponse::from_response(true, &serde_json::json!({"data": {"result": false}}));
+        assert!(!checked.success);
+        assert!(MfaResponse::from_response(true, &serde_json::Value::Null).success);
+    }
+
+    #[test]
+    fn test_login_outcome_from_challenge() {
+        let mfa = serde_json::json!({"code": "user.login.need.verification", "data": {"codeTypes": [5, "7"]}});
+        match LoginOutcome::from_challenge(&mfa) {
+            Some(LoginOutcome::MfaRequired { code_types }) => {
+                assert_eq!(code_types, vec![MfaCodeType::Login, MfaCodeType::Other(7)])
+            }
+            other => panic!("expected MFA, got {:?}", other),
+        }
+
+        let question = serde_json::json!({
+            "code": "user.login.need.question",
+            "data": {"questionId": 1001, "questionName": "First pet?"}
+        });
+        match LoginOutcome::from_challenge(&question) {
+            Some(LoginOutcome::SecurityQuestionRequired { question }) => {
+                assert_eq!(question.question_id, "1001");
+                assert_eq!(question.question, "First pet?");
+            }
+            other => panic!("expected a security question, got {:?}", other),
+        }
+
+        let captcha = serde_json::json!({"code": "user.check.slider.pic.fail", "msg": "verify"});
+        let outcome = LoginOutcome::from_challenge(&captcha).unwrap();
+        assert!(matches!(
+            outcome.into_success(),
+            Err(WebullError::CaptchaRequired(_))
+        ));
+        assert!(
+            LoginOutcome::from_challenge(&serde_json::json!({"code": "phone.illegal"})).is_none()
+        );
+        assert!(LoginOutcome::from_challenge(&serde_json::json!({"accessToken": "t"})).is_none());
+    }
+
+    #[test]
+    fn test_pre_market_quote_without_session_fields() {
+        let quote: Quote = serde_json::from_value(serde_json::json!({
+            "tickerId": 913256135, "close": "189.50", "pPrice": "190.75",
+            "faTradeTime": "2024-06-04T08:15:00.000+0000", "turnoverRate": "0.0031"
+        }))
+        .unwrap();
+        assert_eq!(quote.close, 189.50);
+        assert_eq!(quote.open, None);
+        assert_eq!(quote.day_range(), None);
+        assert_eq!(quote.latest_price(), 190.75);
+        assert_eq!(quote.get_f64("turnoverRate"), Some(0.0031));
+        assert_eq!(
+            quote.get("faTradeTime").and_then(|v| v.as_str()),
+            Some("2024-06-04T08:15:00.000+0000")
+        );
+        assert!(serde_json::to_value(&quote).unwrap().get("raw").is_none());
+
+        let built = Quote::builder(101.0)
+            .ticker_id(913256135)
+            .pre_close(100.0)
+            .session(100.5, 102.0, 99.5)
+            .build();
+        assert_eq!(built.change_ratio, Some(0.01));
+        assert_eq!(built.day_range(), Some((99.5, 102.0)));
+        assert_eq!(built.get_f64("preClose"), Some(100.0));
+    }
+
+    #[test]
+    fn test_ticker_filter_and_exact_match() {
+        let tickers: Vec<Ticker> = serde_json::from_value(serde_json::json!([
+            {"tickerId": 1, "disSymbol": "SPYG", "name": "SPDR Growth ETF", "template": "etf", "regionId": 6},
+            {"tickerId": 2, "disSymbol": "SPY", "name": "SPDR S&P 500 ETF", "template": "etf",
+             "regionId": 6, "derivativeSupport": 1},
+            {"tickerId": 3, "disSymbol": "SPOT", "name": "Spotify", "template": "stock",
+             "regionId": 6, "derivativeSupport": 1},
+            {"tickerId": 4, "disSymbol": "BRK.B", "name": "Berkshire Hathaway B", "template": "stock", "regionId": 6},
+            {"tickerId": 5, "disSymbol": "SPY", "name": "SPDR S&P 500 ETF (HK)", "template": "etf", "regionId": 2},
+            {"tickerId": 6, "disSymbol": "BTCUSD", "name": "Bitcoin", "template": "crypto", "regionId": 6}
+        ]))
+        .unwrap();
+        assert_eq!(tickers[5].instrument_type(), InstrumentType::Crypto);
+
+        let ids = |filter: TickerFilter| -> Vec<i64> {
+            tickers
+                .iter()
+                .filter(|t| filter.matches(t))
+                .map(|t| t.ticker_id)
+                .collect()
+        };
+        assert_eq!(ids(TickerFilter::new().stocks()), vec![3, 4]);
+        assert_eq!(ids(TickerFilter::new().etfs().options_enabled()), vec![2]);
+        assert_eq!(ids(TickerFilter::new().stocks().crypto()), vec![3, 4, 6]);
+        assert_eq!(ids(TickerFilter::new().etfs().region(2)), vec![5]);
+        assert_eq!(ids(TickerFilter::new()).len(), 6);
+
+        assert_eq!(
+            Ticker::exact_match(&tickers, "spy", 6).unwrap().ticker_id,
+            2
+        );
+        assert_eq!(
+            Ticker::exact_match(&tickers, "SPY", 2).unwrap().ticker_id,
+            5
+        );
+        assert_eq!(
+            Ticker::exact_match(&tickers, "BRK-B", 6).unwrap().ticker_id,
+            4
+        );
+        assert!(Ticker::exact_match(&tickers, "SP", 6).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_options_by_expiration_rejects_bad_dates() {
+        let client = WebullClient::new_live(Some(6)).unwrap();
+        let result = client
+            .get_options_by_expiration("913256135", "06/21/2024")
+            .await;
+        assert!(matches!(result, Err(WebullError::InvalidParameter(_))));
+    }
+
+    #[tokio::test]
+    async fn test_retry_backoff_releases_concurrency_slot() {
+        let endpoints = crate::endpoints::Endpoints::builder()
+            .redirect_host("quotes-gw.webullfintech.com", "http://127.0.0.1:1/")
+            .build()
+            .unwrap();
+        let mut client = crate::LiveWebullClient::new(None)
+            .unwrap()
+            .with_endpoints(endpoints);
+        client.set_max_in_flight(1);
+        client.set_retry_policy(
+            crate::RetryPolicy::new()
+                .max_retries(1)
+                .base_delay(std::time::Duration::from_millis(500))
+                .jitter(false),
+        );
+        let limiter = client.concurrency_limiter().unwrap().clone();
+
+        let failing = tokio::spawn(async move { client.get_quotes("913256135").await });
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        // The refused request is sleeping before its retry, without a slot
+        assert!(!failing.is_finished());
+        assert_eq!(limiter.in_flight(), 0);
+        assert!(limiter.try_acquire().is_some());
+
+        assert!(failing.await.unwrap().is_err());
+        assert_eq!(limiter.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_quote_probe_keeps_host_usable() {
+        // A quote host that accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let mut open = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                open.push(socket);
+            }
+        });
+        let endpoints = crate::endpoints::Endpoints::builder()
+            .redirect_host(
+                "quotes-gw.webullfintech.com",
+                format!("http://127.0.0.1:{}/", port),
+            )
+            .build()
+            .unwrap();
+        let mut client = crate::LiveWebullClient::with_did(None, "0123456789abcdef")
+            .unwrap()
+            .with_endpoints(endpoints);
+        client.quote_breakers = std::sync::Arc::new(crate::CircuitBreakers::new(
+            1,
+            std::time::Duration::ZERO,
+        ));
+        let host = "127.0.0.1".to_string();
+        client.quote_breakers.record_failure(&host);
+
+        // The half-open probe is cancelled mid-request
+        let probe = tokio::time::timeout(
+            std::time::Duration::from_millis(200),
+            client.get_quotes("913256135"),
+        )
+        .await;
+        assert!(probe.is_err());
+        assert!(client.quote_breakers.allow(&host).is_some());
+    }
+
+    #[test]
+    fn test_unusable_rate_limits_are_rejected() {
+        for rate in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                WebullClient::builder().rate_limit(rate).build(),
+                Err(WebullError::InvalidParameter(_))
+            ));
+            assert!(matches!(
+                crate::LiveWebullClient::with_config(
+                    None,
+                    crate::ClientConfig::new().rate_limit(rate)
+                ),
+                Err(WebullError::InvalidParameter(_))
+            ));
+        }
+
+        let mut client = WebullClient::new_paper(None).unwrap();
+        assert!(client.set_rate_limit(0.0).is_err());
+        assert!(client.set_rate_limit(2.0).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_wait_holds_no_concurrency_slot() {
+        let endpoints = crate::endpoints::Endpoints::builder()
+            .redirect_host("quotes-gw.webullfintech.com", "http://127.0.0.1:1/")
+            .build()
+            .unwrap();
+        let mut client = crate::LiveWebullClient::new(None)
+            .unwrap()
+            .with_endpoints(endpoints);
+        client.set_max_in_flight(1);
+        client.set_rate_limit(1.0).unwrap();
+        client.set_retry_policy(crate::RetryPolicy::new().max_retries(0));
+        let limiter = client.concurrency_limiter().unwrap().clone();
+
+        // The first request spends the only token
+        assert!(client
+            .get_ticker_minute_volume("913256135", 5)
+            .await
+            .is_err());
+
+        let throttled =
+            tokio::spawn(async move { client.get_ticker_minute_volume("913256135", 5).await });
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        assert!(!throttled.is_finished());
+        assert_eq!(limiter.in_flight(), 0);
+
+        assert!(throttled.await.unwrap().is_err());
+        assert_eq!(limiter.in_flight(), 0);
+    }
+
+    #[test]
+    fn test_regional_hosts_are_opt_in() {
+        let client = WebullClient::builder()
+            .region(2)
+            .did("0123456789abcdef")
+            .build_live()
+            .unwrap();
+        assert_eq!(client.headers["lzone"], "dc_core_r001");
+        assert_eq!(
+            client.endpoints.base_fintech_gw_url,
+            "https://quotes-gw.webullfintech.com/api"
+        );
+        assert_eq!(client.region_profile().currency, "HKD");
+
+        let client = WebullClient::builder()
+            .region(2)
+            .did("0123456789abcdef")
+            .config(crate::ClientConfig::new().regional_hosts(true))
+            .build_live()
+            .unwrap();
+        assert_eq!(client.headers["lzone"], "dc_core_r002");
+        assert_eq!(client.headers["hl"], "zh-hant");
+        assert_eq!(
+            client.endpoints.base_fintech_gw_url,
+            "https://quotes-gw.webull.hk/api"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reset_day_cancels_open_orders_on_every_page() {
+        use crate::fixtures::{FixtureMode, FixtureStore};
+
+        let dir = std::env::temp_dir().join(format!("webull_reset_{}", uuid::Uuid::new_v4()));
+        let mut client = crate::PaperWebullClient::new(Some(6)).unwrap();
+        client.paper_account_id = Some("5000".to_string());
+        let endpoints = client.base_client.endpoints.clone();
+
+        let store = FixtureStore::new(&dir, FixtureMode::Record);
+        let http = reqwest::Client::new();
+        let record = |request: reqwest::RequestBuilder, body: serde_json::Value| {
+            store
+                .record(&request.build().unwrap(), 200, body.to_string().as_bytes())
+                .unwrap()
+        };
+        let order = |id: usize, status: &str| {
+            serde_json::json!({
+                "orderId": id.to_string(),
+                "ticker": {"tickerId": 913256135, "disSymbol": "AAPL", "name": "Apple Inc"},
+                "action": "BUY",
+                "orderType": "LMT",
+                "status": status,
+                "totalQuantity": 1,
+                "lmtPrice": "100",
+                "createTime0": 1717243200000i64 - id as i64 * 1000
+            })
+        };
+
+        // A full first page, then a page holding a partial fill and a fill
+        let first: Vec<_> = (0..100).map(|id| order(id, "Working")).collect();
+        record(
+            http.get(endpoints.paper_orders_with_status("5000", 100, "Working", None)),
+            serde_json::json!(first),
+        );
+        record(
+            http.get(endpoints.paper_orders_with_status(
+                "5000",
+                100,
+                "Working",
+                Some(1717243200000 - 99_000),
+            )),
+            serde_json::json!([order(100, "Partially Filled"), order(101, "Filled")]),
+        );
+        for id in 0..=100 {
+            record(
+                http.post(endpoints.paper_cancel_order("5000", &id.to_string())),
+                serde_json::json!({}),
+            );
+        }
+        client
+            .base_client
+            .set_fixture_mode(&dir, FixtureMode::Replay);
+
+        let reset = client.reset_day(false).await.unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(reset.cancelled_orders.len(), 101);
+        assert!(reset.cancelled_orders.contains(&"100".to_string()));
+        assert!(!reset.cancelled_orders.contains(&"101".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_reset_day_leaves_option_positions_open() {
+        use crate::fixtures::{FixtureMode, FixtureStore};
+
+        let dir = std::env::temp_dir().join(format!("webull_flatten_{}", uuid::Uuid::new_v4()));
+        let mut client = crate::PaperWebullClient::new(Some(6)).unwrap();
+        client.paper_account_id = Some("5000".to_string());
+        let endpoints = client.base_client.endpoints.clone();
+
+        let store = FixtureStore::new(&dir, FixtureMode::Record);
+        let http = reqwest::Client::new();
+        let record = |request: reqwest::RequestBuilder, body: serde_json::Value| {
+            store
+                .record(&request.build().unwrap(), 200, body.to_string().as_bytes())
+                .unwrap()
+        };
+        record(
+            http.get(endpoints.paper_orders_with_status("5000", 100, "Working", None)),
+            serde_json::json!([]),
+        );
+        record(
+            http.get(endpoints.paper_account("5000")),
+            serde_json::json!({"positions": [{
+                "ticker": {"tickerId": 1041234567, "disSymbol": "AAPL240621C00190000", "name": "AAPL Call"},
+                "position": "2",
+                "costPrice": "1.50",
+                "cost": "300",
+                "marketValue": "250",
+                "lastPrice": "1.25",
+                "assetType": "OPTION"
+            }]}),
+        );
+        client
+            .base_client
+            .set_fixture_mode(&dir, FixtureMode::Replay);
+
+        let reset = client.reset_day(true).await.unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(reset.closing_orders.is_empty());
+        assert_eq!(reset.skipped_positions, vec![1041234567]);
+    }
+
+    #[tokio::test]
+    async fn test_activities_keep_identical_rows_across_pages() {
+        use crate::fixtures::{FixtureMode, FixtureStore};
+
+        let dir = std::env::temp_dir().join(format!("webull_activities_{}", uuid::Uuid::new_v4()));
+        let mut client = crate::LiveWebullClient::with_did(None, "0123456789abcdef").unwrap();
+        client.account_id = Some("5000".to_string());
+        let url = client.endpoints.account_activities("5000");
+
+        let store = FixtureStore::new(&dir, FixtureMode::Record);
+        let http = reqwest::Client::new();
+        let record = |page: usize, body: serde_json::Value| {
+            let request = http
+                .post(&url)
+                .json(&serde_json::json!({"pageIndex": page, "pageSize": 500}))
+                .build()
+                .unwrap();
+            store
+                .record(&request, 200, body.to_string().as_bytes())
+                .unwrap()
+        };
+        let fee = serde_json::json!({"type": "FEE", "date": "2024-06-03", "amount": "-0.02"});
+        let trade = |id: usize| serde_json::json!({"id": id.to_string(), "type": "TRADE", "date": "2024-06-03"});
+
+        // Two identical fees straddle the page boundary, and the second page
+        // repeats one ID from the first
+        let mut first: Vec<_> = (0..499).map(trade).collect();
+        first.push(fee.clone());
+        record(1, serde_json::json!(first));
+        record(2, serde_json::json!([fee, trade(0), trade(500)]));
+        client.set_fixture_mode(&dir, FixtureMode::Replay);
+
+        let activities = client
+            .get_account_activities(None, None, None)
+            .await
+            .unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(activities.len(), 502);
+        assert_eq!(
+            activities
+                .iter()
+                .filter(|a| a.activity_type == ActivityType::Fee)
+                .count(),
+            2
+        );
+        assert_eq!(
+            activities
+                .iter()
+                .filter(|a| a.id.as_deref() == Some("0"))
+                .count(),
+            1
+        );
     }
 }
@@ -0,0 +1,482 @@
+// Building blocks lifted from the examples: account allocation, order
+// confirmation checks and an end-to-end smoke test
+
+use crate::{
+    format::RoundingPolicy,
+    models::{AccountDetail, OrderAction, OrderType, PlaceOrderRequest},
+    unified_client::WebullClient,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Direction of a P&L figure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Trend {
+    Up,
+    Down,
+    Flat,
+}
+
+impl Trend {
+    pub fn of(value: f64) -> Self {
+        if value > 0.0 {
+            Trend::Up
+        } else if value < 0.0 {
+            Trend::Down
+        } else {
+            Trend::Flat
+        }
+    }
+}
+
+/// How an account's value splits between cash and positions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSummary {
+    /// Net liquidation, or cash plus market value when it isn't reported
+    pub total_value: f64,
+    pub cash: f64,
+    pub invested: f64,
+    /// Share of total value held as cash, 0-1
+    pub cash_weight: f64,
+    /// Share of total value held in positions, 0-1
+    pub invested_weight: f64,
+    pub unrealized_pnl: Option<f64>,
+    /// Unrealized P&L as a fraction of cost
+    pub unrealized_pnl_rate: Option<f64>,
+    pub buying_power: Option<f64>,
+    pub unsettled_funds: Option<f64>,
+}
+
+impl AccountSummary {
+    pub fn from_account(account: &AccountDetail) -> Self {
+        let cash = account.total_cash.unwrap_or(0.0);
+        let invested = account.total_market_value.unwrap_or(0.0);
+        let total_value = account.net_liquidation.unwrap_or(cash + invested);
+        let weight = |value: f64| {
+            if total_value > 0.0 {
+                value / total_value
+            } else {
+                0.0
+            }
+        };
+        Self {
+            total_value,
+            cash,
+            invested,
+            cash_weight: weight(cash),
+            invested_weight: weight(invested),
+            unrealized_pnl: account.unrealized_profit_loss,
+            unrealized_pnl_rate: account.unrealized_profit_loss_rate,
+            buying_power: account.buying_power,
+            unsettled_funds: account.unsettled_funds,
+        }
+    }
+
+    pub fn trend(&self) -> Trend {
+        Trend::of(self.unrealized_pnl.unwrap_or(0.0))
+    }
+
+    /// Plain-text allocation summary, one figure per line
+    pub fn render(&self, policy: &RoundingPolicy) -> String {
+        let percent = |value: f64| policy.percent(value).trim_start_matches('+').to_string();
+        let mut out = format!(
+            "Total Value: {}\nCash: {} ({})\nInvested: {} ({})\n",
+            policy.money(self.total_value),
+            policy.money(self.cash),
+            percent(self.cash_weight),
+            policy.money(self.invested),
+            percent(self.invested_weight),
+        );
+        if let Some(pnl) = self.unrealized_pnl {
+            out.push_str(&format!("Unrealized P&L: {}", policy.money(pnl)));
+            if let Some(rate) = self.unrealized_pnl_rate {
+                out.push_str(&format!(" ({})", policy.percent(rate)));
+            }
+            out.push('\n');
+        }
+        if let Some(buying_power) = self.buying_power {
+            out.push_str(&format!("Buying Power: {}\n", policy.money(buying_power)));
+        }
+        out
+    }
+}
+
+/// Something about an order worth a second look before sending it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum OrderWarning {
+    /// The limit price is this fraction away from the last price
+    LimitFarFromMarket(f64),
+    /// A stop already on the wrong side of the last price, which
+    /// triggers as soon as the order is accepted
+    StopAlreadyTriggered,
+    /// Estimated order value over the configured ceiling
+    LargeNotional(f64),
+    /// No last price to check against
+    NoMarketPrice,
+}
+
+impl fmt::Display for OrderWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderWarning::LimitFarFromMarket(distance) => write!(
+                f,
+                "limit price is {:.1}% away from the last price",
+                distance * 100.0
+            ),
+            OrderWarning::StopAlreadyTriggered => {
+                write!(f, "stop price is already through the last price")
+            }
+            OrderWarning::LargeNotional(value) => {
+                write!(f, "estimated value ${:.2} is over the limit", value)
+            }
+            OrderWarning::NoMarketPrice => write!(f, "no last price to check against"),
+        }
+    }
+}
+
+/// An order summary to show before asking for confirmation
+///
+/// ```
+/// use webull_unofficial::models::PlaceOrderRequest;
+/// use webull_unofficial::toolkit::OrderConfirmation;
+///
+/// let order = PlaceOrderRequest::limit(150.0)
+///     .ticker_id(913256135)
+///     .buy()
+///     .quantity(10.0)
+///     .build()
+///     .unwrap();
+/// let confirmation = OrderConfirmation::new(&order, "AAPL", Some(152.0));
+/// assert_eq!(confirmation.estimated_total, Some(1500.0));
+/// assert!(confirmation.warnings.is_empty());
+/// assert_eq!(
+///     confirmation.prompt(),
+///     "Place this LIMIT order to BUY 10 shares of AAPL at $150.00"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderConfirmation {
+    pub symbol: String,
+    pub action: OrderAction,
+    pub order_type: OrderType,
+    pub quantity: f64,
+    pub last_price: Option<f64>,
+    pub limit_price: Option<f64>,
+    pub stop_price: Option<f64>,
+    /// Quantity times the limit price, or the stop or last price for
+    /// orders without one
+    pub estimated_total: Option<f64>,
+    pub warnings: Vec<OrderWarning>,
+}
+
+impl OrderConfirmation {
+    /// Limit prices further than this from the last price are flagged
+    pub const LIMIT_DISTANCE: f64 = 0.10;
+
+    pub fn new(order: &PlaceOrderRequest, symbol: &str, last_price: Option<f64>) -> Self {
+        let last_price = last_price.filter(|price| *price > 0.0);
+        let reference = match order.order_type {
+            OrderType::Limit | OrderType::StopLimit => order.limit_price,
+            OrderType::Stop => order.stop_price,
+            OrderType::Market => last_price,
+        };
+
+        let mut warnings = Vec::new();
+        match last_price {
+            None => warnings.push(OrderWarning::NoMarketPrice),
+            Some(last) => {
+                if let Some(limit) = order.limit_price {
+                    let distance = (limit - last).abs() / last;
+                    if distance > Self::LIMIT_DISTANCE {
+                        warnings.push(OrderWarning::LimitFarFromMarket(distance));
+                    }
+                }
+                if let Some(stop) = order.stop_price {
+                    let triggered = match order.action {
+                        OrderAction::Sell => stop >= last,
+                        OrderAction::Buy => stop <= last,
+                    };
+                    if triggered {
+                        warnings.push(OrderWarning::StopAlreadyTriggered);
+                    }
+                }
+            }
+        }
+
+        Self {
+            symbol: symbol.to_string(),
+            action: order.action.clone(),
+            order_type: order.order_type.clone(),
+            quantity: order.quantity,
+            last_price,
+            limit_price: order.limit_price,
+            stop_price: order.stop_price,
+            estimated_total: reference.map(|price| price * order.quantity),
+            warnings,
+        }
+    }
+
+    /// Flag orders whose estimated value is over `max`
+    pub fn max_notional(mut self, max: f64) -> Self {
+        if let Some(total) = self.estimated_total.filter(|total| *total > max) {
+            self.warnings.push(OrderWarning::LargeNotional(total));
+        }
+        self
+    }
+
+    /// One-line question to put to the user
+    pub fn prompt(&self) -> String {
+        let kind = match self.order_type {
+            OrderType::Market => "MARKET",
+            OrderType::Limit => "LIMIT",
+            OrderType::Stop => "STOP",
+            OrderType::StopLimit => "STOP LIMIT",
+        };
+        let mut prompt = format!(
+            "Place this {} order to {} {} shares of {}",
+            kind,
+            action_name(&self.action),
+            self.quantity,
+            self.symbol
+        );
+        match (self.stop_price, self.limit_price) {
+            (Some(stop), Some(limit)) => {
+                prompt.push_str(&format!(" at ${:.2}, stop ${:.2}", limit, stop))
+            }
+            (Some(price), None) | (None, Some(price)) => {
+                prompt.push_str(&format!(" at ${:.2}", price))
+            }
+            (None, None) => {}
+        }
+        prompt
+    }
+
+    /// Whether a typed answer to `prompt` means yes
+    pub fn is_affirmative(response: &str) -> bool {
+        matches!(response.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+    }
+}
+
+fn action_name(action: &OrderAction) -> &'static str {
+    match action {
+        OrderAction::Buy => "BUY",
+        OrderAction::Sell => "SELL",
+    }
+}
+
+impl fmt::Display for OrderConfirmation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Action: {} {} shares of {}",
+            action_name(&self.action),
+            self.quantity,
+            self.symbol
+        )?;
+        if let Some(last) = self.last_price {
+            writeln!(f, "Current Price: ${:.2}", last)?;
+        }
+        if let Some(limit) = self.limit_price {
+            writeln!(f, "Limit Price: ${:.2}", limit)?;
+        }
+        if let Some(stop) = self.stop_price {
+            writeln!(f, "Stop Price: ${:.2}", stop)?;
+        }
+        if let Some(total) = self.estimated_total {
+            writeln!(f, "Estimated Total: ${:.2}", total)?;
+        }
+        for warning in &self.warnings {
+            writeln!(f, "Warning: {}", warning)?;
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of one smoke test step
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SmokeStep {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// Results of a `SmokeTest` run, in step order
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SmokeTestReport {
+    pub steps: Vec<SmokeStep>,
+}
+
+impl SmokeTestReport {
+    fn record(&mut self, name: impl Into<String>, outcome: Result<String, String>) {
+        let (passed, detail) = match outcome {
+            Ok(detail) => (true, detail),
+            Err(detail) => (false, detail),
+        };
+        self.steps.push(SmokeStep {
+            name: name.into(),
+            passed,
+            detail,
+        });
+    }
+
+    pub fn passed(&self) -> bool {
+        self.steps.iter().all(|step| step.passed)
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &SmokeStep> {
+        self.steps.iter().filter(|step| !step.passed)
+    }
+}
+
+impl fmt::Display for SmokeTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            let mark = if step.passed { "ok" } else { "FAILED" };
+            writeln!(f, "{:<6} {}: {}", mark, step.name, step.detail)?;
+        }
+        Ok(())
+    }
+}
+
+/// End-to-end check that a logged-in client can read the account, fetch
+/// quotes and, when enabled, place and cancel an order
+#[derive(Debug, Clone)]
+pub struct SmokeTest {
+    symbols: Vec<String>,
+    test_order: Option<PlaceOrderRequest>,
+}
+
+impl Default for SmokeTest {
+    fn default() -> Self {
+        Self::new(&["AAPL", "MSFT", "GOOGL"])
+    }
+}
+
+impl SmokeTest {
+    pub fn new<S: AsRef<str>>(symbols: &[S]) -> Self {
+        Self {
+            symbols: symbols.iter().map(|s| s.as_ref().to_string()).collect(),
+            test_order: None,
+        }
+    }
+
+    /// Also place `order`, then cancel it
+    ///
+    /// Use a paper client or a limit far from the market; a market order
+    /// on a live client fills before it can be cancelled.
+    pub fn with_order(mut self, order: PlaceOrderRequest) -> Self {
+        self.test_order = Some(order);
+        self
+    }
+
+    /// Run every step, carrying on past failures
+    pub async fn run(&self, client: &WebullClient) -> SmokeTestReport {
+        let mut report = SmokeTestReport::default();
+
+        let account = client.get_account().await.map_err(|e| e.to_string());
+        report.record(
+            "account",
+            account.map(|account| {
+                let summary = AccountSummary::from_account(&account);
+                format!("total value ${:.2}", summary.total_value)
+            }),
+        );
+
+        for symbol in &self.symbols {
+            let outcome = match client.find_ticker(symbol).await {
+                Ok(tickers) => match tickers.first() {
+                    Some(ticker) => client
+                        .get_quotes(&ticker.ticker_id.to_string())
+                        .await
+                        .map(|quote| match quote.change_ratio {
+                            Some(ratio) => format!("${:.2} ({:+.2}%)", quote.close, ratio * 100.0),
+                            None => format!("${:.2}", quote.close),
+                        })
+                        .map_err(|e| e.to_string()),
+                    None => Err("ticker not found".to_string()),
+                },
+                Err(e) => Err(e.to_string()),
+            };
+            report.record(format!("quote {}", symbol), outcome);
+        }
+
+        if let Some(order) = &self.test_order {
+            match client.place_order(order).await {
+                Ok(order_id) => {
+                    report.record("place order", Ok(format!("order {}", order_id)));
+                    let cancelled = match client.cancel_order(&order_id).await {
+                        Ok(true) => Ok("cancelled".to_string()),
+                        Ok(false) => Err("cancel was not accepted".to_string()),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    report.record("cancel order", cancelled);
+                }
+                Err(e) => report.record("place order", Err(e.to_string())),
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_account_summary_weights() {
+        let mut account: AccountDetail = serde_json::from_value(json!({
+            "secAccountId": 12345,
+            "unrealizedProfitLoss": "-120.5"
+        }))
+        .unwrap();
+        account.total_cash = Some(2500.0);
+        account.total_market_value = Some(7500.0);
+        let summary = AccountSummary::from_account(&account);
+        assert_eq!(summary.total_value, 10_000.0);
+        assert_eq!(summary.cash_weight, 0.25);
+        assert_eq!(summary.invested_weight, 0.75);
+        assert_eq!(summary.trend(), Trend::Down);
+        assert!(summary
+            .render(&RoundingPolicy::default())
+            .contains("Cash: $2,500.00 (25.00%)"));
+    }
+
+    #[test]
+    fn test_order_confirmation_warnings() {
+        let stop = PlaceOrderRequest::stop(155.0)
+            .ticker_id(913256135)
+            .sell()
+            .quantity(5.0)
+            .build()
+            .unwrap();
+        let confirmation = OrderConfirmation::new(&stop, "AAPL", Some(150.0));
+        assert_eq!(
+            confirmation.warnings,
+            vec![OrderWarning::StopAlreadyTriggered]
+        );
+        assert_eq!(confirmation.estimated_total, Some(775.0));
+
+        let market = PlaceOrderRequest::market()
+            .ticker_id(913256135)
+            .buy()
+            .quantity(100.0)
+            .build()
+            .unwrap();
+        let confirmation =
+            OrderConfirmation::new(&market, "AAPL", Some(150.0)).max_notional(10_000.0);
+        assert_eq!(
+            confirmation.warnings,
+            vec![OrderWarning::LargeNotional(15_000.0)]
+        );
+        assert!(OrderConfirmation::new(&market, "AAPL", None)
+            .warnings
+            .contains(&OrderWarning::NoMarketPrice));
+
+        assert!(OrderConfirmation::is_affirmative(" Yes\n"));
+        assert!(!OrderConfirmation::is_affirmative("n"));
+    }
+}
@@ -0,0 +1,163 @@
+// Backend-agnostic trading interface
+
+use crate::{
+    client::{LiveWebullClient, PaperWebullClient, WebullClient},
+    error::Result,
+    models::*,
+};
+use async_trait::async_trait;
+
+/// Common trading operations shared by live and paper clients
+///
+/// Write strategies against `impl WebullTrader` (or `Box<dyn WebullTrader>`)
+/// to run them unchanged on either backend:
+///
+/// ```no_run
+/// use webull_unofficial::{WebullTrader, Result};
+///
+/// async fn exposure(trader: &impl WebullTrader) -> Result<f64> {
+///     let positions = trader.get_positions().await?;
+///     Ok(positions.iter().map(|p| p.market_value).sum())
+/// }
+/// ```
+#[async_trait]
+pub trait WebullTrader: Send + Sync {
+    /// Login to the account
+    async fn login(
+        &mut self,
+        username: &str,
+        password: &str,
+        device_name: Option<&str>,
+        mfa: Option<&str>,
+        question_id: Option<&str>,
+        question_answer: Option<&str>,
+    ) -> Result<LoginOutcome>;
+
+    /// Logout
+    async fn logout(&mut self) -> Result<bool>;
+
+    /// Get trade token
+    async fn get_trade_token(&mut self, password: &str) -> Result<String>;
+
+    /// Get account details
+    async fn get_account(&self) -> Result<AccountDetail>;
+
+    /// Get positions
+    async fn get_positions(&self) -> Result<Vec<Position>>;
+
+    /// Get current orders
+    async fn get_orders(&self, page_size: Option<i32>) -> Result<Vec<Order>>;
+
+    /// Get history orders
+    async fn get_history_orders(&self, status: &str, count: i32) -> Result<Vec<HistoryOrder>>;
+
+    /// Place an order, returning the order ID
+    async fn place_order(&self, order: &PlaceOrderRequest) -> Result<String>;
+
+    /// Cancel an order
+    async fn cancel_order(&self, order_id: &str) -> Result<bool>;
+
+    /// Get quotes
+    async fn get_quotes(&self, ticker_id: &str) -> Result<Quote>;
+
+    /// Check if this backend trades a paper account
+    fn is_paper(&self) -> bool;
+}
+
+// Forwards every trait method to the inherent method of the same name
+macro_rules! impl_webull_trader {
+    ($client:ty) => {
+        #[async_trait]
+        impl WebullTrader for $client {
+            async fn login(
+                &mut self,
+                username: &str,
+                password: &str,
+                device_name: Option<&str>,
+                mfa: Option<&str>,
+                question_id: Option<&str>,
+                question_answer: Option<&str>,
+            ) -> Result<LoginOutcome> {
+                <$client>::login(
+                    self,
+                    username,
+                    password,
+                    device_name,
+                    mfa,
+                    question_id,
+                    question_answer,
+                )
+                .await
+            }
+
+            async fn logout(&mut self) -> Result<bool> {
+                <$client>::logout(self).await
+            }
+
+            async fn get_trade_token(&mut self, password: &str) -> Result<String> {
+                <$client>::get_trade_token(self, password).await
+            }
+
+            async fn get_account(&self) -> Result<AccountDetail> {
+                <$client>::get_account(self).await
+            }
+
+            async fn get_positions(&self) -> Result<Vec<Position>> {
+                <$client>::get_positions(self).await
+            }
+
+            async fn get_orders(&self, page_size: Option<i32>) -> Result<Vec<Order>> {
+                <$client>::get_orders(self, page_size).await
+            }
+
+            async fn get_history_orders(
+                &self,
+                status: &str,
+                count: i32,
+            ) -> Result<Vec<HistoryOrder>> {
+                <$client>::get_history_orders(self, status, count).await
+            }
+
+            async fn place_order(&self, order: &PlaceOrderRequest) -> Result<String> {
+                <$client>::place_order(self, order).await
+            }
+
+            async fn cancel_order(&self, order_id: &str) -> Result<bool> {
+                <$client>::cancel_order(self, order_id).await
+            }
+
+            async fn get_quotes(&self, ticker_id: &str) -> Result<Quote> {
+                <$client>::get_quotes(self, ticker_id).await
+            }
+
+            fn is_paper(&self) -> bool {
+                <$client>::is_paper(self)
+            }
+        }
+    };
+}
+
+impl_webull_trader!(LiveWebullClient);
+impl_webull_trader!(PaperWebullClient);
+impl_webull_trader!(WebullClient);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_trader<T: WebullTrader>() {}
+
+    #[test]
+    fn test_clients_implement_trader() {
+        assert_trader::<LiveWebullClient>();
+        assert_trader::<PaperWebullClient>();
+        assert_trader::<WebullClient>();
+
+        let boxed: Vec<Box<dyn WebullTrader>> = vec![
+            Box::new(LiveWebullClient::new(Some(6)).unwrap()),
+            Box::new(PaperWebullClient::new(Some(6)).unwrap()),
+        ];
+        assert!(!boxed[0].is_paper());
+        assert!(boxed[1].is_paper());
+    }
+}
@@ -1,11 +1,35 @@
 use crate::{
+    analytics::{PortfolioAnalytics, PortfolioReport},
+    audit::CapturedPayload,
     builders::*,
+    chains::OptionChainDownload,
+    client::WebullClientBuilder,
+    config::ClientConfig,
+    delta::{NewsDelta, QuoteDelta},
+    endpoints::Endpoints,
     error::{Result, WebullError},
+    fixtures::FixtureMode,
+    fx::FxRateCache,
     live_client::LiveWebullClient,
+    middleware::Middleware,
     models::*,
+    options::PlaceOptionSpreadRequest,
     paper_client::PaperWebullClient,
+    quality::QuoteValidator,
+    quote_history::QuoteRecorder,
+    rate_limit::EndpointClass,
+    region::{Region, RegionProfile},
+    retry::RetryPolicy,
+    risk::{RiskPosition, Scenario, ScenarioTable},
+    shutdown::{ShutdownPolicy, ShutdownReport},
+    snapshots::{AccountSnapshot, SnapshotStore},
+    stream::StreamConn,
+    volatility::{atm_implied_volatility, HistoricalVolatility, IvRank},
+    watchlists::{WatchlistBundle, WatchlistDiff},
 };
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
 use serde_json::Value;
+use std::collections::BTreeMap;
 
 /// Unified Webull client that can work with both live and paper trading
 #[derive(Debug, Clone)]
@@ -15,6 +39,11 @@ pub enum WebullClient {
 }
 
 impl WebullClient {
+    /// Configure a client before creating it
+    pub fn builder() -> WebullClientBuilder {
+        WebullClientBuilder::new()
+    }
+
     /// Create a new live trading client
     pub fn new_live(region_code: Option<i32>) -> Result<Self> {
         Ok(WebullClient::Live(LiveWebullClient::new(region_code)?))
@@ -25,6 +54,172 @@ impl WebullClient {
         Ok(WebullClient::Paper(PaperWebullClient::new(region_code)?))
     }
 
+    /// Create a new live trading client with the given configuration
+    pub fn new_live_with_config(region_code: Option<i32>, config: ClientConfig) -> Result<Self> {
+        Ok(WebullClient::Live(LiveWebullClient::with_config(
+            region_code,
+            config,
+        )?))
+    }
+
+    /// Create a new paper trading client with the given configuration
+    pub fn new_paper_with_config(region_code: Option<i32>, config: ClientConfig) -> Result<Self> {
+        Ok(WebullClient::Paper(PaperWebullClient::with_config(
+            region_code,
+            config,
+        )?))
+    }
+
+    /// Region the client was created for
+    pub fn region(&self) -> Region {
+        match self {
+            WebullClient::Live(client) => client.region(),
+            WebullClient::Paper(client) => client.base_client.region(),
+        }
+    }
+
+    /// Hosts, headers and currency defaults the client uses
+    pub fn region_profile(&self) -> RegionProfile {
+        match self {
+            WebullClient::Live(client) => client.region_profile(),
+            WebullClient::Paper(client) => client.base_client.region_profile(),
+        }
+    }
+
+    /// Use `endpoints` for every request, e.g. to go through an API proxy
+    ///
+    /// ```
+    /// use webull_unofficial::{endpoints::Endpoints, MemoryDidStore, WebullClient};
+    ///
+    /// let endpoints = Endpoints::builder()
+    ///     .fintech_gw_url("https://proxy.internal/api")
+    ///     .build()
+    ///     .unwrap();
+    /// let client = WebullClient::builder()
+    ///     .did_store(MemoryDidStore::new())
+    ///     .build()
+    ///     .unwrap()
+    ///     .with_endpoints(endpoints);
+    /// assert_eq!(client.endpoints().base_fintech_gw_url, "https://proxy.internal/api");
+    /// ```
+    pub fn with_endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.set_endpoints(endpoints);
+        self
+    }
+
+    /// Replace the base URLs requests are sent to
+    pub fn set_endpoints(&mut self, endpoints: Endpoints) {
+        match self {
+            WebullClient::Live(client) => client.set_endpoints(endpoints),
+            WebullClient::Paper(client) => client.base_client.set_endpoints(endpoints),
+        }
+    }
+
+    /// Base URLs requests are sent to
+    pub fn endpoints(&self) -> &Endpoints {
+        match self {
+            WebullClient::Live(client) => &client.endpoints,
+            WebullClient::Paper(client) => &client.base_client.endpoints,
+        }
+    }
+
+    /// Set the request timeout in seconds for one endpoint class
+    pub fn set_class_timeout(&mut self, class: EndpointClass, seconds: u64) {
+        match self {
+            WebullClient::Live(client) => client.set_class_timeout(class, seconds),
+            WebullClient::Paper(client) => client.base_client.set_class_timeout(class, seconds),
+        }
+    }
+
+    /// A copy of this client whose requests all use `timeout`
+    pub fn with_timeout(&self, timeout: std::time::Duration) -> Self {
+        match self {
+            WebullClient::Live(client) => WebullClient::Live(client.with_timeout(timeout)),
+            WebullClient::Paper(client) => {
+                let mut client = client.clone();
+                client.base_client = client.base_client.with_timeout(timeout);
+                WebullClient::Paper(client)
+            }
+        }
+    }
+
+    /// A copy of this client that fails with `DeadlineExceeded` once
+    /// `budget` has passed, across every request it makes
+    pub fn with_deadline(&self, budget: std::time::Duration) -> Self {
+        match self {
+            WebullClient::Live(client) => WebullClient::Live(client.with_deadline(budget)),
+            WebullClient::Paper(client) => {
+                let mut client = client.clone();
+                client.base_client = client.base_client.with_deadline(budget);
+                WebullClient::Paper(client)
+            }
+        }
+    }
+
+    /// Limit requests per second for each endpoint class
+    pub fn set_rate_limit(&mut self, requests_per_sec: f64) -> Result<()> {
+        match self {
+            WebullClient::Live(client) => client.set_rate_limit(requests_per_sec),
+            WebullClient::Paper(client) => client.base_client.set_rate_limit(requests_per_sec),
+        }
+    }
+
+    /// Allow at most `max_in_flight` requests at once, orders first
+    pub fn set_max_in_flight(&mut self, max_in_flight: usize) {
+        match self {
+            WebullClient::Live(client) => client.set_max_in_flight(max_in_flight),
+            WebullClient::Paper(client) => client.base_client.set_max_in_flight(max_in_flight),
+        }
+    }
+
+    /// Set the retry policy for transient failures
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        match self {
+            WebullClient::Live(client) => client.set_retry_policy(policy),
+            WebullClient::Paper(client) => client.base_client.set_retry_policy(policy),
+        }
+    }
+
+    /// Record raw responses into `dir`, or replay them from it
+    pub fn set_fixture_mode(&mut self, dir: impl Into<std::path::PathBuf>, mode: FixtureMode) {
+        match self {
+            WebullClient::Live(client) => client.set_fixture_mode(dir, mode),
+            WebullClient::Paper(client) => client.base_client.set_fixture_mode(dir, mode),
+        }
+    }
+
+    /// Capture every trading request body as sent, with credentials redacted
+    pub fn set_payload_hook(&mut self, hook: impl Fn(&CapturedPayload) + Send + Sync + 'static) {
+        match self {
+            WebullClient::Live(client) => client.set_payload_hook(hook),
+            WebullClient::Paper(client) => client.base_client.set_payload_hook(hook),
+        }
+    }
+
+    /// Run `middleware` on every request and response
+    pub fn add_middleware(&mut self, middleware: impl Middleware + 'static) {
+        match self {
+            WebullClient::Live(client) => client.add_middleware(middleware),
+            WebullClient::Paper(client) => client.base_client.add_middleware(middleware),
+        }
+    }
+
+    /// Remove all middleware
+    pub fn clear_middleware(&mut self) {
+        match self {
+            WebullClient::Live(client) => client.clear_middleware(),
+            WebullClient::Paper(client) => client.base_client.clear_middleware(),
+        }
+    }
+
+    /// Validate every quote fetched by this client
+    pub fn set_quote_validator(&mut self, validator: std::sync::Arc<QuoteValidator>) {
+        match self {
+            WebullClient::Live(client) => client.set_quote_validator(validator),
+            WebullClient::Paper(client) => client.base_client.set_quote_validator(validator),
+        }
+    }
+
     /// Check if this is a paper trading client
     pub fn is_paper(&self) -> bool {
         matches!(self, WebullClient::Paper(_))
@@ -39,7 +234,7 @@ impl WebullClient {
         mfa: Option<&str>,
         question_id: Option<&str>,
         question_answer: Option<&str>,
-    ) -> Result<LoginResponse> {
+    ) -> Result<LoginOutcome> {
         match self {
             WebullClient::Live(client) => {
                 client
@@ -76,19 +271,72 @@ impl WebullClient {
         }
     }
 
-    /// Get MFA code
-    pub async fn get_mfa(&self, username: &str) -> Result<bool> {
+    /// Get MFA code, sent wherever the username points
+    pub async fn get_mfa(&self, username: &str) -> Result<MfaResponse> {
+        self.get_mfa_with(username, MfaOptions::default()).await
+    }
+
+    /// Get MFA code for a chosen purpose
+    pub async fn get_mfa_with(&self, username: &str, options: MfaOptions) -> Result<MfaResponse> {
         match self {
-            WebullClient::Live(client) => client.get_mfa(username).await,
-            WebullClient::Paper(client) => client.base_client.get_mfa(username).await,
+            WebullClient::Live(client) => client.get_mfa_with(username, options).await,
+            WebullClient::Paper(client) => client.base_client.get_mfa_with(username, options).await,
         }
     }
 
     /// Check MFA code
-    pub async fn check_mfa(&self, username: &str, mfa: &str) -> Result<bool> {
+    pub async fn check_mfa(&self, username: &str, mfa: &str) -> Result<MfaResponse> {
+        self.check_mfa_with(username, mfa, MfaOptions::default())
+            .await
+    }
+
+    /// Check MFA code requested with `get_mfa_with`; pass the same options
+    pub async fn check_mfa_with(
+        &self,
+        username: &str,
+        mfa: &str,
+        options: MfaOptions,
+    ) -> Result<MfaResponse> {
         match self {
-            WebullClient::Live(client) => client.check_mfa(username, mfa).await,
-            WebullClient::Paper(client) => client.base_client.check_mfa(username, mfa).await,
+            WebullClient::Live(client) => client.check_mfa_with(username, mfa, options).await,
+            WebullClient::Paper(client) => {
+                client
+                    .base_client
+                    .check_mfa_with(username, mfa, options)
+                    .await
+            }
+        }
+    }
+
+    /// Fetch the captcha for a login refused with `CaptchaRequired`
+    pub async fn get_captcha(
+        &self,
+        username: &str,
+        challenge: &LoginChallenge,
+    ) -> Result<CaptchaPayload> {
+        match self {
+            WebullClient::Live(client) => client.get_captcha(username, challenge).await,
+            WebullClient::Paper(client) => {
+                client.base_client.get_captcha(username, challenge).await
+            }
+        }
+    }
+
+    /// Submit a captcha solution; the next `login` carries the proof
+    pub async fn submit_captcha(
+        &mut self,
+        username: &str,
+        captcha: &CaptchaPayload,
+        x: i32,
+    ) -> Result<()> {
+        match self {
+            WebullClient::Live(client) => client.submit_captcha(username, captcha, x).await,
+            WebullClient::Paper(client) => {
+                client
+                    .base_client
+                    .submit_captcha(username, captcha, x)
+                    .await
+            }
         }
     }
 
@@ -115,6 +363,38 @@ impl WebullClient {
         }
     }
 
+    /// Account ID, if already known, without a request
+    pub fn get_account_id_str(&self) -> Option<String> {
+        match self {
+            WebullClient::Live(client) => client.get_account_id_str().map(String::from),
+            WebullClient::Paper(client) => client.get_account_id_str(),
+        }
+    }
+
+    /// When the access token expires (Unix seconds), if the login said
+    pub fn token_expires_at(&self) -> Option<i64> {
+        match self {
+            WebullClient::Live(client) => client.token_expires_at(),
+            WebullClient::Paper(client) => client.base_client.token_expires_at(),
+        }
+    }
+
+    /// Get every account on the login, including Webull Advisors accounts
+    pub async fn get_accounts(&mut self) -> Result<Vec<LinkedAccount>> {
+        match self {
+            WebullClient::Live(client) => client.get_accounts().await,
+            WebullClient::Paper(client) => client.get_accounts().await,
+        }
+    }
+
+    /// Get the read-only holdings of a Webull Advisors account
+    pub async fn get_managed_portfolio(&self, account_id: &str) -> Result<ManagedPortfolio> {
+        match self {
+            WebullClient::Live(client) => client.get_managed_portfolio(account_id).await,
+            WebullClient::Paper(client) => client.get_managed_portfolio(account_id).await,
+        }
+    }
+
     /// Get trade token
     pub async fn get_trade_token(&mut self, password: &str) -> Result<String> {
         match self {
@@ -147,14 +427,145 @@ impl WebullClient {
         }
     }
 
+    /// Enable local account snapshots, stored as JSON lines at `path`
+    pub fn set_snapshot_store(&mut self, path: impl Into<std::path::PathBuf>) {
+        match self {
+            WebullClient::Live(client) => client.set_snapshot_store(path),
+            WebullClient::Paper(client) => client.base_client.set_snapshot_store(path),
+        }
+    }
+
+    fn snapshot_store(&self) -> Result<&SnapshotStore> {
+        let store = match self {
+            WebullClient::Live(client) => client.snapshot_store.as_deref(),
+            WebullClient::Paper(client) => client.base_client.snapshot_store.as_deref(),
+        };
+        store.ok_or_else(|| {
+            WebullError::InvalidRequest("Snapshot store is not configured".to_string())
+        })
+    }
+
+    /// Capture account value, positions and open orders, appending to the local store
+    pub async fn take_snapshot(&self) -> Result<AccountSnapshot> {
+        self.take_snapshot_with_iv(&[]).await
+    }
+
+    /// Take a snapshot that also records at-the-money IV for `ticker_ids`
+    ///
+    /// The recorded IV builds the history used by `get_iv_rank`.
+    pub async fn take_snapshot_with_iv(&self, ticker_ids: &[&str]) -> Result<AccountSnapshot> {
+        let store = self.snapshot_store()?;
+        let mut snapshot = AccountSnapshot::new(
+            self.get_account().await?,
+            self.get_positions().await?,
+            self.get_orders(None).await?,
+        );
+        for ticker_id in ticker_ids {
+            match self.get_atm_implied_volatility(ticker_id).await {
+                Ok(Some(iv)) => {
+                    snapshot
+                        .implied_volatility
+                        .insert(ticker_id.to_string(), iv);
+                }
+                Ok(None) => log::warn!("No implied volatility available for {}", ticker_id),
+                Err(e) => log::warn!("Implied volatility for {} failed: {}", ticker_id, e),
+            }
+        }
+        store.append(&snapshot)?;
+        Ok(snapshot)
+    }
+
+    /// Current front-month, at-the-money implied volatility
+    pub async fn get_atm_implied_volatility(&self, ticker_id: &str) -> Result<Option<f64>> {
+        let quote = self.get_quotes(ticker_id).await?;
+        let contracts = self.get_options(ticker_id).await?;
+        Ok(atm_implied_volatility(&contracts, quote.close))
+    }
+
+    /// 20, 30 and 60 day historical volatility from daily bars
+    pub async fn get_historical_volatility(&self, ticker_id: &str) -> Result<HistoricalVolatility> {
+        let bars = self.get_bars(ticker_id, BarInterval::D1, 70, None).await?;
+        Ok(HistoricalVolatility::from_bars(&bars))
+    }
+
+    /// Rank current at-the-money IV against the past year of snapshots
+    ///
+    /// `None` until snapshots taken with `take_snapshot_with_iv` have recorded
+    /// IV for this ticker.
+    pub async fn get_iv_rank(&self, ticker_id: &str) -> Result<Option<IvRank>> {
+        let since = chrono::Utc::now().date_naive() - chrono::Duration::days(365);
+        let history: Vec<f64> = self
+            .snapshot_store()?
+            .iv_history(ticker_id, since..)?
+            .into_iter()
+            .map(|(_, iv)| iv)
+            .collect();
+        Ok(self
+            .get_atm_implied_volatility(ticker_id)
+            .await?
+            .and_then(|current| IvRank::compute(&history, current)))
+    }
+
+    /// Take a snapshot once a day at `at` (UTC)
+    ///
+    /// Runs until the future is dropped; failures are logged and retried the next day.
+    pub async fn run_daily_snapshots(&self, at: chrono::NaiveTime) -> Result<()> {
+        self.snapshot_store()?;
+        loop {
+            let now = chrono::Utc::now();
+            let mut next = now.date_naive().and_time(at).and_utc();
+            if next <= now {
+                next += chrono::Duration::days(1);
+            }
+            let wait = (next - now).to_std().unwrap_or_default();
+            tokio::time::sleep(wait).await;
+
+            if let Err(e) = self.take_snapshot().await {
+                log::warn!("Account snapshot failed: {}", e);
+            }
+        }
+    }
+
+    /// Load locally stored snapshots taken on dates within `range` (UTC)
+    pub fn get_local_snapshots<R: std::ops::RangeBounds<chrono::NaiveDate>>(
+        &self,
+        range: R,
+    ) -> Result<Vec<AccountSnapshot>> {
+        self.snapshot_store()?.load(range)
+    }
+
     /// Get historical orders
-    pub async fn get_history_orders(&self, status: &str, count: i32) -> Result<Value> {
+    pub async fn get_history_orders(&self, status: &str, count: i32) -> Result<Vec<HistoryOrder>> {
         match self {
             WebullClient::Live(client) => client.get_history_orders(status, count).await,
             WebullClient::Paper(client) => client.get_history_orders(status, count).await,
         }
     }
 
+    /// Stream the full order history, following pagination transparently
+    pub fn history_orders_paginated<'a>(
+        &'a self,
+        status: &'a str,
+        page_size: i32,
+    ) -> BoxStream<'a, Result<HistoryOrder>> {
+        match self {
+            WebullClient::Live(client) => {
+                client.history_orders_paginated(status, page_size).boxed()
+            }
+            WebullClient::Paper(client) => {
+                client.history_orders_paginated(status, page_size).boxed()
+            }
+        }
+    }
+
+    /// Get historical orders as raw JSON
+    pub async fn get_history_orders_raw(&self, status: &str, count: i32) -> Result<Value> {
+        match self {
+            WebullClient::Live(client) => client.get_history_orders_raw(status, count).await,
+            WebullClient::Paper(client) => client.get_history_orders_raw(status, count).await,
+        }
+    }
+
     /// Place an order
     pub async fn place_order(&self, order: &PlaceOrderRequest) -> Result<String> {
         match self {
@@ -163,6 +574,36 @@ impl WebullClient {
         }
     }
 
+    /// Get borrow availability and fee rate for shorting a ticker
+    pub async fn get_shortability(&self, ticker_id: &str) -> Result<Shortability> {
+        match self {
+            WebullClient::Live(client) => client.get_shortability(ticker_id).await,
+            WebullClient::Paper(client) => client.base_client.get_shortability(ticker_id).await,
+        }
+    }
+
+    /// Get the acceptable limit price range for an order
+    pub async fn get_price_band(&self, order: &PlaceOrderRequest) -> Result<Option<PriceBand>> {
+        match self {
+            WebullClient::Live(client) => client.get_price_band(order).await,
+            WebullClient::Paper(client) => client.base_client.get_price_band(order).await,
+        }
+    }
+
+    /// Validate an order's limit price against the price band
+    pub async fn validate_price_band(&self, order: &PlaceOrderRequest) -> Result<()> {
+        match self {
+            WebullClient::Live(client) => client.validate_price_band(order).await,
+            WebullClient::Paper(client) => client.base_client.validate_price_band(order).await,
+        }
+    }
+
+    /// Place an order after validating its limit price against the price band
+    pub async fn place_order_checked(&self, order: &PlaceOrderRequest) -> Result<String> {
+        self.validate_price_band(order).await?;
+        self.place_order(order).await
+    }
+
     /// Cancel an order
     pub async fn cancel_order(&self, order_id: &str) -> Result<bool> {
         match self {
@@ -171,6 +612,53 @@ impl WebullClient {
         }
     }
 
+    /// Scenario P&L for the account's equity positions
+    ///
+    /// Option positions aren't reported with greeks; add them with
+    /// `RiskPosition::option` and call `ScenarioTable::simulate` directly.
+    pub async fn simulate_scenarios(&self, scenarios: &[Scenario]) -> Result<ScenarioTable> {
+        let positions: Vec<RiskPosition> = self
+            .get_positions()
+            .await?
+            .iter()
+            .filter_map(RiskPosition::from_position)
+            .collect();
+        Ok(ScenarioTable::simulate(&positions, scenarios))
+    }
+
+    /// Build a P&L and exposure report for the account
+    ///
+    /// Realized P&L covers the full filled order history. Daily returns come
+    /// from locally stored snapshots and are empty without a snapshot store.
+    pub async fn portfolio_report(
+        &self,
+        analytics: &PortfolioAnalytics,
+    ) -> Result<PortfolioReport> {
+        let positions = self.get_positions().await?;
+        let orders: Vec<HistoryOrder> = self
+            .history_orders_paginated("Filled", 100)
+            .try_collect()
+            .await?;
+        let snapshots = match self.snapshot_store() {
+            Ok(store) => store.load(..)?,
+            Err(_) => Vec::new(),
+        };
+        analytics.report(&positions, &orders, &snapshots)
+    }
+
+    /// Cancel every open order, or only those for `ticker_id`, concurrently
+    ///
+    /// Returns each order's outcome keyed by order ID.
+    pub async fn cancel_all_orders(
+        &self,
+        ticker_id: Option<i64>,
+    ) -> Result<BTreeMap<String, Result<bool>>> {
+        match self {
+            WebullClient::Live(client) => client.cancel_all_orders(ticker_id).await,
+            WebullClient::Paper(client) => client.cancel_all_orders(ticker_id).await,
+        }
+    }
+
     /// Get quotes for a ticker
     pub async fn get_quotes(&self, ticker_id: &str) -> Result<Quote> {
         match self {
@@ -179,11 +667,81 @@ impl WebullClient {
         }
     }
 
+    /// Record bid/ask snapshots for tickers into `dir`
+    pub fn record_quotes(
+        &self,
+        ticker_ids: &[&str],
+        dir: impl Into<std::path::PathBuf>,
+    ) -> QuoteRecorder<'_> {
+        match self {
+            WebullClient::Live(client) => client.record_quotes(ticker_ids, dir),
+            WebullClient::Paper(client) => client.record_quotes(ticker_ids, dir),
+        }
+    }
+
+    /// Get a quote by symbol, resolving the ticker ID through the symbol cache
+    pub async fn get_quote_by_symbol(&self, symbol: &str) -> Result<Quote> {
+        match self {
+            WebullClient::Live(client) => client.get_quote_by_symbol(symbol).await,
+            WebullClient::Paper(client) => client.base_client.get_quote_by_symbol(symbol).await,
+        }
+    }
+
+    /// Get a quote including pre-market and after-hours prices
+    pub async fn get_quote_extended(&self, ticker_id: &str) -> Result<Quote> {
+        match self {
+            WebullClient::Live(client) => client.get_quote_extended(ticker_id).await,
+            WebullClient::Paper(client) => client.get_quote_extended(ticker_id).await,
+        }
+    }
+
+    /// Get only the quote fields that changed since a checkpoint
+    pub async fn get_quote_delta(&self, ticker_id: &str, since: Option<i64>) -> Result<QuoteDelta> {
+        match self {
+            WebullClient::Live(client) => client.get_quote_delta(ticker_id, since).await,
+            WebullClient::Paper(client) => client.get_quote_delta(ticker_id, since).await,
+        }
+    }
+
+    /// Get headlines for a ticker ID published after the headline `since`
+    pub async fn get_news_delta(
+        &self,
+        ticker_id: &str,
+        since: Option<i64>,
+        count: i32,
+    ) -> Result<NewsDelta> {
+        match self {
+            WebullClient::Live(client) => client.get_news_delta(ticker_id, since, count).await,
+            WebullClient::Paper(client) => client.get_news_delta(ticker_id, since, count).await,
+        }
+    }
+
+    /// Get bars starting at or after `since` (Unix seconds), oldest first
+    pub async fn get_bars_since(
+        &self,
+        ticker_id: &str,
+        interval: BarInterval,
+        since: i64,
+    ) -> Result<Vec<Bar>> {
+        match self {
+            WebullClient::Live(client) => client.get_bars_since(ticker_id, interval, since).await,
+            WebullClient::Paper(client) => client.get_bars_since(ticker_id, interval, since).await,
+        }
+    }
+
+    /// Get quotes for several tickers in one request
+    pub async fn get_quotes_batch(&self, ticker_ids: &[&str]) -> Result<Vec<Quote>> {
+        match self {
+            WebullClient::Live(client) => client.get_quotes_batch(ticker_ids).await,
+            WebullClient::Paper(client) => client.base_client.get_quotes_batch(ticker_ids).await,
+        }
+    }
+
     /// Get historical bars
     pub async fn get_bars(
         &self,
         ticker_id: &str,
-        interval: &str,
+        interval: BarInterval,
         count: i32,
         timestamp: Option<i64>,
     ) -> Result<Vec<Bar>> {
@@ -197,6 +755,24 @@ impl WebullClient {
         }
     }
 
+    /// Get every bar between `start` and `end` (Unix seconds), oldest first
+    pub async fn get_bars_range(
+        &self,
+        ticker_id: &str,
+        interval: BarInterval,
+        start: i64,
+        end: i64,
+    ) -> Result<Vec<Bar>> {
+        match self {
+            WebullClient::Live(client) => {
+                client.get_bars_range(ticker_id, interval, start, end).await
+            }
+            WebullClient::Paper(client) => {
+                client.get_bars_range(ticker_id, interval, start, end).await
+            }
+        }
+    }
+
     /// Find ticker by keyword
     pub async fn find_ticker(&self, keyword: &str) -> Result<Vec<Ticker>> {
         match self {
@@ -205,6 +781,80 @@ impl WebullClient {
         }
     }
 
+    /// Find tickers matching `keyword` that pass `filter`
+    pub async fn find_ticker_with(
+        &self,
+        keyword: &str,
+        filter: &TickerFilter,
+    ) -> Result<Vec<Ticker>> {
+        match self {
+            WebullClient::Live(client) => client.find_ticker_with(keyword, filter).await,
+            WebullClient::Paper(client) => client.find_ticker_with(keyword, filter).await,
+        }
+    }
+
+    /// The ticker listed under exactly `symbol`, preferring the client's region
+    pub async fn find_ticker_exact(&self, symbol: &str) -> Result<Ticker> {
+        match self {
+            WebullClient::Live(client) => client.find_ticker_exact(symbol).await,
+            WebullClient::Paper(client) => client.find_ticker_exact(symbol).await,
+        }
+    }
+
+    /// Find a ticker by its ISIN
+    pub async fn find_ticker_by_isin(&self, isin: &str) -> Result<Vec<Ticker>> {
+        match self {
+            WebullClient::Live(client) => client.find_ticker_by_isin(isin).await,
+            WebullClient::Paper(client) => client.find_ticker_by_isin(isin).await,
+        }
+    }
+
+    /// Find a ticker by its CUSIP
+    pub async fn find_ticker_by_cusip(&self, cusip: &str) -> Result<Vec<Ticker>> {
+        match self {
+            WebullClient::Live(client) => client.find_ticker_by_cusip(cusip).await,
+            WebullClient::Paper(client) => client.find_ticker_by_cusip(cusip).await,
+        }
+    }
+
+    /// Write the instrument universe for a region to a local file
+    pub async fn dump_instrument_universe(
+        &self,
+        region_code: i32,
+        types: &[InstrumentType],
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<usize> {
+        match self {
+            WebullClient::Live(client) => {
+                client
+                    .dump_instrument_universe(region_code, types, path)
+                    .await
+            }
+            WebullClient::Paper(client) => {
+                client
+                    .base_client
+                    .dump_instrument_universe(region_code, types, path)
+                    .await
+            }
+        }
+    }
+
+    /// Resolve a symbol to its ticker ID, using the cache when possible
+    pub async fn resolve(&self, symbol: &str) -> Result<i64> {
+        match self {
+            WebullClient::Live(client) => client.resolve(symbol).await,
+            WebullClient::Paper(client) => client.base_client.resolve(symbol).await,
+        }
+    }
+
+    /// Persist the symbol cache to a JSON file, loading any entries already there
+    pub fn set_symbol_cache_path(&mut self, path: impl Into<std::path::PathBuf>) -> Result<()> {
+        match self {
+            WebullClient::Live(client) => client.set_symbol_cache_path(path),
+            WebullClient::Paper(client) => client.base_client.set_symbol_cache_path(path),
+        }
+    }
+
     /// Get options chain
     pub async fn get_options(&self, ticker: &str) -> Result<Vec<OptionContract>> {
         match self {
@@ -213,6 +863,70 @@ impl WebullClient {
         }
     }
 
+    /// Get the option chain filtered by expiration, direction and strike range
+    pub async fn get_options_chain(&self, query: &OptionsQuery) -> Result<Vec<OptionContract>> {
+        match self {
+            WebullClient::Live(client) => client.get_options_chain(query).await,
+            WebullClient::Paper(client) => client.base_client.get_options_chain(query).await,
+        }
+    }
+
+    /// Get the option chain for one expiration date (`YYYY-MM-DD`)
+    pub async fn get_options_by_expiration(
+        &self,
+        ticker: &str,
+        date: &str,
+    ) -> Result<Vec<OptionContract>> {
+        match self {
+            WebullClient::Live(client) => client.get_options_by_expiration(ticker, date).await,
+            WebullClient::Paper(client) => {
+                client
+                    .base_client
+                    .get_options_by_expiration(ticker, date)
+                    .await
+            }
+        }
+    }
+
+    /// Get the available option expiration dates for a ticker, soonest first
+    pub async fn get_option_expirations(&self, ticker: &str) -> Result<Vec<OptionExpiration>> {
+        match self {
+            WebullClient::Live(client) => client.get_option_expirations(ticker).await,
+            WebullClient::Paper(client) => client.base_client.get_option_expirations(ticker).await,
+        }
+    }
+
+    /// Get the available option expiration dates for a ticker
+    #[deprecated(note = "use get_option_expirations")]
+    pub async fn get_options_expiration_dates(
+        &self,
+        ticker: &str,
+    ) -> Result<Vec<OptionExpiration>> {
+        self.get_option_expirations(ticker).await
+    }
+
+    /// Get a real-time quote for a single option contract
+    pub async fn get_option_quote(&self, option_ticker_id: &str) -> Result<OptionQuote> {
+        match self {
+            WebullClient::Live(client) => client.get_option_quote(option_ticker_id).await,
+            WebullClient::Paper(client) => {
+                client.base_client.get_option_quote(option_ticker_id).await
+            }
+        }
+    }
+
+    /// Get the most active option contracts market-wide by volume or open interest
+    pub async fn get_top_options(
+        &self,
+        rank: TopOptionsRank,
+        count: i32,
+    ) -> Result<Vec<TopOption>> {
+        match self {
+            WebullClient::Live(client) => client.get_top_options(rank, count).await,
+            WebullClient::Paper(client) => client.base_client.get_top_options(rank, count).await,
+        }
+    }
+
     /// Get news for a ticker
     pub async fn get_news(&self, ticker: &str, last_id: i64, count: i32) -> Result<Vec<News>> {
         match self {
@@ -221,6 +935,19 @@ impl WebullClient {
         }
     }
 
+    /// Get news for a ticker ID without resolving a symbol
+    pub async fn get_ticker_news(
+        &self,
+        ticker_id: &str,
+        last_id: i64,
+        count: i32,
+    ) -> Result<Vec<News>> {
+        match self {
+            WebullClient::Live(client) => client.get_ticker_news(ticker_id, last_id, count).await,
+            WebullClient::Paper(client) => client.get_ticker_news(ticker_id, last_id, count).await,
+        }
+    }
+
     /// Get bars with builder (new fluent API)
     pub fn get_bars_with(&self) -> BarsRequestBuilderWithClient<'_> {
         BarsRequestBuilderWithClient::new(self)
@@ -278,6 +1005,81 @@ impl WebullClient {
         }
     }
 
+    /// Download option chains for many underlyings, keeping contracts that
+    /// expire within `dte_range` days
+    pub fn download_option_chains(
+        &self,
+        symbols: &[&str],
+        dte_range: std::ops::RangeInclusive<i64>,
+    ) -> OptionChainDownload<'_> {
+        match self {
+            WebullClient::Live(client) => client.download_option_chains(symbols, dte_range),
+            WebullClient::Paper(client) => client.download_option_chains(symbols, dte_range),
+        }
+    }
+
+    /// Get the exchange rate from one currency to another on a date
+    pub async fn get_fx_rate(&self, from: &str, to: &str, date: chrono::NaiveDate) -> Result<f64> {
+        match self {
+            WebullClient::Live(client) => client.get_fx_rate(from, to, date).await,
+            WebullClient::Paper(client) => client.get_fx_rate(from, to, date).await,
+        }
+    }
+
+    /// Cache of FX rates fetched so far, for converting snapshots and lots
+    pub fn fx_rates(&self) -> std::sync::Arc<FxRateCache> {
+        match self {
+            WebullClient::Live(client) => client.fx_rates(),
+            WebullClient::Paper(client) => client.base_client.fx_rates(),
+        }
+    }
+
+    /// Get every earnings release for a ticker, newest first
+    pub async fn get_ticker_earnings(&self, ticker_id: &str) -> Result<Vec<EarningsEvent>> {
+        match self {
+            WebullClient::Live(client) => client.get_ticker_earnings(ticker_id).await,
+            WebullClient::Paper(client) => client.get_ticker_earnings(ticker_id).await,
+        }
+    }
+
+    /// Get a ticker's income statement, balance sheet or cash flow statement
+    pub async fn get_financials(
+        &self,
+        ticker_id: &str,
+        statement: StatementType,
+        period: ReportPeriod,
+    ) -> Result<FinancialStatement> {
+        match self {
+            WebullClient::Live(client) => client.get_financials(ticker_id, statement, period).await,
+            WebullClient::Paper(client) => {
+                client.get_financials(ticker_id, statement, period).await
+            }
+        }
+    }
+
+    /// Get earnings releases scheduled between two dates (inclusive), earliest first
+    pub async fn get_earnings_calendar(
+        &self,
+        start_date: chrono::NaiveDate,
+        end_date: chrono::NaiveDate,
+    ) -> Result<Vec<EarningsEvent>> {
+        match self {
+            WebullClient::Live(client) => client.get_earnings_calendar(start_date, end_date).await,
+            WebullClient::Paper(client) => client.get_earnings_calendar(start_date, end_date).await,
+        }
+    }
+
+    /// Get the next earnings release with whisper EPS, guidance and past reactions
+    pub async fn get_ticker_earnings_whisper(
+        &self,
+        ticker_id: &str,
+    ) -> Result<Option<EarningsEvent>> {
+        match self {
+            WebullClient::Live(client) => client.get_ticker_earnings_whisper(ticker_id).await,
+            WebullClient::Paper(client) => client.get_ticker_earnings_whisper(ticker_id).await,
+        }
+    }
+
     /// Run screener
     pub async fn screener(&self, request: &ScreenerRequest) -> Result<Vec<Ticker>> {
         match self {
@@ -285,4 +1087,290 @@ impl WebullClient {
             WebullClient::Paper(client) => client.base_client.screener(request).await,
         }
     }
+
+    /// Get all watchlists
+    pub async fn get_watchlists(&self) -> Result<Vec<Watchlist>> {
+        match self {
+            WebullClient::Live(client) => client.get_watchlists().await,
+            WebullClient::Paper(client) => client.base_client.get_watchlists().await,
+        }
+    }
+
+    /// Create a new watchlist
+    pub async fn create_watchlist(&self, name: &str) -> Result<String> {
+        match self {
+            WebullClient::Live(client) => client.create_watchlist(name).await,
+            WebullClient::Paper(client) => client.base_client.create_watchlist(name).await,
+        }
+    }
+
+    /// Add tickers to a watchlist
+    pub async fn add_to_watchlist(&self, watchlist_id: &str, ticker_ids: &[i64]) -> Result<bool> {
+        match self {
+            WebullClient::Live(client) => client.add_to_watchlist(watchlist_id, ticker_ids).await,
+            WebullClient::Paper(client) => {
+                client
+                    .base_client
+                    .add_to_watchlist(watchlist_id, ticker_ids)
+                    .await
+            }
+        }
+    }
+
+    /// Remove tickers from a watchlist
+    pub async fn remove_from_watchlist(
+        &self,
+        watchlist_id: &str,
+        ticker_ids: &[i64],
+    ) -> Result<bool> {
+        match self {
+            WebullClient::Live(client) => {
+                client.remove_from_watchlist(watchlist_id, ticker_ids).await
+            }
+            WebullClient::Paper(client) => {
+                client
+                    .base_client
+                    .remove_from_watchlist(watchlist_id, ticker_ids)
+                    .await
+            }
+        }
+    }
+
+    /// Delete a watchlist
+    pub async fn delete_watchlist(&self, watchlist_id: &str) -> Result<bool> {
+        match self {
+            WebullClient::Live(client) => client.delete_watchlist(watchlist_id).await,
+            WebullClient::Paper(client) => client.base_client.delete_watchlist(watchlist_id).await,
+        }
+    }
+
+    /// Export every watchlist in the portable format
+    pub async fn export_watchlists(&self) -> Result<WatchlistBundle> {
+        match self {
+            WebullClient::Live(client) => client.export_watchlists().await,
+            WebullClient::Paper(client) => client.base_client.export_watchlists().await,
+        }
+    }
+
+    /// Make the named watchlist hold exactly `symbols`, creating it if needed
+    pub async fn sync_watchlist<S: AsRef<str>>(
+        &self,
+        name: &str,
+        symbols: &[S],
+    ) -> Result<WatchlistDiff> {
+        match self {
+            WebullClient::Live(client) => client.sync_watchlist(name, symbols).await,
+            WebullClient::Paper(client) => client.base_client.sync_watchlist(name, symbols).await,
+        }
+    }
+
+    /// Sync every watchlist in a bundle
+    pub async fn import_watchlists(&self, bundle: &WatchlistBundle) -> Result<Vec<WatchlistDiff>> {
+        match self {
+            WebullClient::Live(client) => client.import_watchlists(bundle).await,
+            WebullClient::Paper(client) => client.base_client.import_watchlists(bundle).await,
+        }
+    }
+
+    /// List price alerts
+    pub async fn list_alerts(&self) -> Result<Vec<Alert>> {
+        match self {
+            WebullClient::Live(client) => client.list_alerts().await,
+            WebullClient::Paper(client) => client.base_client.list_alerts().await,
+        }
+    }
+
+    /// Create a price alert for a ticker
+    pub async fn create_alert(&self, ticker_id: i64, rule: &AlertRule) -> Result<bool> {
+        match self {
+            WebullClient::Live(client) => client.create_alert(ticker_id, rule).await,
+            WebullClient::Paper(client) => client.base_client.create_alert(ticker_id, rule).await,
+        }
+    }
+
+    /// Delete all alerts set on a ticker
+    pub async fn delete_alert(&self, ticker_id: &str) -> Result<bool> {
+        match self {
+            WebullClient::Live(client) => client.delete_alert(ticker_id).await,
+            WebullClient::Paper(client) => client.base_client.delete_alert(ticker_id).await,
+        }
+    }
+
+    /// Get the intraday volume profile for a ticker
+    pub async fn get_ticker_minute_volume(
+        &self,
+        ticker_id: &str,
+        days: i32,
+    ) -> Result<MinuteVolumeProfile> {
+        match self {
+            WebullClient::Live(client) => client.get_ticker_minute_volume(ticker_id, days).await,
+            WebullClient::Paper(client) => {
+                client
+                    .base_client
+                    .get_ticker_minute_volume(ticker_id, days)
+                    .await
+            }
+        }
+    }
+
+    /// Get the most recent trades (time & sales), oldest first
+    pub async fn get_ticks(&self, ticker_id: &str, count: i32) -> Result<Vec<TradeTick>> {
+        match self {
+            WebullClient::Live(client) => client.get_ticks(ticker_id, count).await,
+            WebullClient::Paper(client) => client.base_client.get_ticks(ticker_id, count).await,
+        }
+    }
+
+    /// Get an ETF's constituents, heaviest first
+    pub async fn get_etf_holdings(&self, ticker_id: &str) -> Result<Vec<EtfHolding>> {
+        match self {
+            WebullClient::Live(client) => client.get_etf_holdings(ticker_id).await,
+            WebullClient::Paper(client) => client.base_client.get_etf_holdings(ticker_id).await,
+        }
+    }
+
+    /// Get an ETF's fund details: expense ratio, assets under management
+    /// and top holdings
+    pub async fn get_etf_profile(&self, ticker_id: &str) -> Result<EtfProfile> {
+        match self {
+            WebullClient::Live(client) => client.get_etf_profile(ticker_id).await,
+            WebullClient::Paper(client) => client.base_client.get_etf_profile(ticker_id).await,
+        }
+    }
+
+    /// Get the IPO calendar for a region (6 = US), soonest first
+    pub async fn get_ipo_calendar(&self, region_code: i32) -> Result<Vec<IpoListing>> {
+        match self {
+            WebullClient::Live(client) => client.get_ipo_calendar(region_code).await,
+            WebullClient::Paper(client) => client.base_client.get_ipo_calendar(region_code).await,
+        }
+    }
+
+    /// Check session health for readiness probes
+    pub async fn health_check(&self) -> HealthStatus {
+        match self {
+            WebullClient::Live(client) => client.health_check().await,
+            WebullClient::Paper(client) => client.health_check().await,
+        }
+    }
+
+    /// Shut the client down safely (e.g. on SIGTERM)
+    ///
+    /// Steps run in order: close streams, cancel open orders, log out. Failures are
+    /// collected in the report rather than aborting the remaining steps.
+    pub async fn shutdown(
+        &mut self,
+        policy: ShutdownPolicy,
+        streams: &mut [StreamConn],
+    ) -> ShutdownReport {
+        let mut report = ShutdownReport::default();
+
+        if policy.close_streams {
+            for stream in streams.iter_mut() {
+                match stream.disconnect().await {
+                    Ok(()) => report.streams_closed += 1,
+                    Err(e) => report
+                        .errors
+                        .push(format!("Stream disconnect failed: {}", e)),
+                }
+            }
+        }
+
+        if policy.cancel_open_orders {
+            match self.cancel_all_orders(None).await {
+                Ok(results) => {
+                    for (order_id, result) in results {
+                        match result {
+                            Ok(true) => report.cancelled_orders.push(order_id),
+                            Ok(false) => report
+                                .failed_cancellations
+                                .push((order_id, "Cancel rejected".to_string())),
+                            Err(e) => report.failed_cancellations.push((order_id, e.to_string())),
+                        }
+                    }
+                }
+                Err(e) => report
+                    .errors
+                    .push(format!("Failed to fetch open orders: {}", e)),
+            }
+        }
+
+        if policy.logout {
+            match self.logout().await {
+                Ok(true) => report.logged_out = true,
+                Ok(false) => report.errors.push("Logout was not accepted".to_string()),
+                Err(e) => report.errors.push(format!("Logout failed: {}", e)),
+            }
+        }
+
+        report
+    }
+
+    /// Place a multi-leg option order (not available for paper accounts)
+    pub async fn place_option_spread(&self, order: &PlaceOptionSpreadRequest) -> Result<String> {
+        match self {
+            WebullClient::Live(client) => client.place_option_spread(order).await,
+            WebullClient::Paper(_) => Err(WebullError::InvalidRequest(
+                "Option spreads are not available for paper accounts".to_string(),
+            )),
+        }
+    }
+
+    /// Get dividend reinvestment settings (not available for paper accounts)
+    pub async fn get_drip_settings(&self) -> Result<DripSettings> {
+        match self {
+            WebullClient::Live(client) => client.get_drip_settings().await,
+            WebullClient::Paper(_) => Err(WebullError::InvalidRequest(
+                "Dividend reinvestment is not available for paper accounts".to_string(),
+            )),
+        }
+    }
+
+    /// Update dividend reinvestment settings (not available for paper accounts)
+    pub async fn update_drip_settings(&self, settings: &DripSettings) -> Result<bool> {
+        match self {
+            WebullClient::Live(client) => client.update_drip_settings(settings).await,
+            WebullClient::Paper(_) => Err(WebullError::InvalidRequest(
+                "Dividend reinvestment is not available for paper accounts".to_string(),
+            )),
+        }
+    }
+
+    /// Get account activities (not available for paper accounts)
+    pub async fn get_account_activities(
+        &self,
+        start: Option<chrono::NaiveDate>,
+        end: Option<chrono::NaiveDate>,
+        activity_type: Option<ActivityType>,
+    ) -> Result<Vec<Activity>> {
+        match self {
+            WebullClient::Live(client) => {
+                client
+                    .get_account_activities(start, end, activity_type)
+                    .await
+            }
+            WebullClient::Paper(_) => Err(WebullError::InvalidRequest(
+                "Account activities are not available for paper accounts".to_string(),
+            )),
+        }
+    }
+
+    /// Get recent account security events (logins, password changes)
+    pub async fn get_security_events(&self, count: i32) -> Result<Vec<SecurityEvent>> {
+        match self {
+            WebullClient::Live(client) => client.get_security_events(count).await,
+            WebullClient::Paper(client) => client.base_client.get_security_events(count).await,
+        }
+    }
+
+    /// Poll the security feed every `poll` and yield events as they appear
+    pub fn watch_security_events(
+        &self,
+        poll: std::time::Duration,
+    ) -> BoxStream<'_, Result<SecurityEvent>> {
+        match self {
+            WebullClient::Live(client) => client.watch_security_events(poll).boxed(),
+            WebullClient::Paper(client) => client.base_client.watch_security_events(poll).boxed(),
+        }
+    }
 }
@@ -0,0 +1,117 @@
+// Offline copy of the instrument universe, stored as JSON lines
+
+use crate::{error::Result, models::Instrument, resolver::SymbolResolver};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Instrument static data loaded from a `dump_instrument_universe` file
+#[derive(Debug, Clone, Default)]
+pub struct InstrumentUniverse {
+    instruments: Vec<Instrument>,
+}
+
+impl InstrumentUniverse {
+    pub fn new(instruments: Vec<Instrument>) -> Self {
+        Self { instruments }
+    }
+
+    /// Read a universe file written by `save`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(path)?;
+        let mut instruments = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            instruments.push(serde_json::from_str(&line)?);
+        }
+        Ok(Self { instruments })
+    }
+
+    /// Write the universe to a file, one instrument per line
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        let mut writer = BufWriter::new(File::create(path)?);
+        for instrument in &self.instruments {
+            serde_json::to_writer(&mut writer, instrument)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// All instruments
+    pub fn instruments(&self) -> &[Instrument] {
+        &self.instruments
+    }
+
+    /// Look up an instrument by symbol (case-insensitive)
+    pub fn find(&self, symbol: &str) -> Option<&Instrument> {
+        self.instruments
+            .iter()
+            .find(|i| i.symbol.eq_ignore_ascii_case(symbol))
+    }
+
+    /// Seed a resolver so symbol lookups never hit the network
+    pub fn seed(&self, resolver: &SymbolResolver) -> Result<()> {
+        resolver.extend(
+            self.instruments
+                .iter()
+                .map(|i| (i.symbol.as_str(), i.ticker_id)),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.instruments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instruments.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::InstrumentType;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_save_and_seed() {
+        let response = json!({
+            "data": [
+                {"ticker": {"tickerId": 913256135, "disSymbol": "AAPL", "name": "Apple Inc",
+                            "disExchangeCode": "NASDAQ", "template": "stock"}},
+                {"tickerId": "913243251", "symbol": "SPY", "name": "SPDR S&P 500 ETF",
+                 "template": "etf"},
+                {"ticker": {"name": "missing id"}}
+            ]
+        });
+        let instruments = Instrument::from_list_response(&response);
+        assert_eq!(instruments.len(), 2);
+        assert_eq!(instruments[0].instrument_type, InstrumentType::Stock);
+        assert_eq!(instruments[1].ticker_id, 913243251);
+        assert_eq!(instruments[1].instrument_type, InstrumentType::Etf);
+
+        let dir = std::env::temp_dir().join(format!("webull_universe_{}", uuid::Uuid::new_v4()));
+        let path = dir.join("universe.jsonl");
+        InstrumentUniverse::new(instruments).save(&path).unwrap();
+
+        let universe = InstrumentUniverse::load(&path).unwrap();
+        assert_eq!(universe.len(), 2);
+        assert_eq!(universe.find("spy").unwrap().symbol, "SPY");
+
+        let resolver = SymbolResolver::new();
+        universe.seed(&resolver).unwrap();
+        assert_eq!(resolver.get("aapl"), Some(913256135));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
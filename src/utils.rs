@@ -1,57 +1,27 @@
+use crate::did::{load_or_create, DidStore, FileDidStore};
 use crate::error::{Result, WebullError};
+use crate::models::{AccountIdentifier, BarInterval};
 use base64::{engine::general_purpose, Engine as _};
-use std::fs::{self, File};
-use std::io::{Read, Write};
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use uuid::Uuid;
 
-/// Generate or load a device ID from file
+/// Generate or load a device ID from `did.bin` in `path` (the working
+/// directory by default)
 pub fn get_did(path: Option<&Path>) -> Result<String> {
-    let filename = if let Some(p) = path {
-        p.join("did.bin")
-    } else {
-        PathBuf::from("did.bin")
+    let store = match path {
+        Some(dir) => FileDidStore::new(dir),
+        None => FileDidStore::default(),
     };
-
-    if filename.exists() {
-        let mut file = File::open(&filename)?;
-        let mut contents = Vec::new();
-        file.read_to_end(&mut contents)?;
-
-        // Try to deserialize with bincode
-        match bincode::deserialize::<String>(&contents) {
-            Ok(did) => Ok(did),
-            Err(_) => {
-                // Fallback: treat as raw string
-                String::from_utf8(contents).map_err(|e| WebullError::DeviceIdError(e.to_string()))
-            }
-        }
-    } else {
-        let did = Uuid::new_v4().to_string().replace("-", "");
-        save_did(&did, Some(&filename))?;
-        Ok(did)
-    }
+    load_or_create(&store)
 }
 
-/// Save device ID to file
+/// Save device ID to file (`did.bin` in the working directory by default)
 pub fn save_did(did: &str, path: Option<&Path>) -> Result<()> {
-    let filename = if let Some(p) = path {
-        p.to_path_buf()
-    } else {
-        PathBuf::from("did.bin")
+    let store = match path {
+        Some(file) => FileDidStore::at(file),
+        None => FileDidStore::default(),
     };
-
-    // Ensure parent directory exists
-    if let Some(parent) = filename.parent() {
-        fs::create_dir_all(parent)?;
-    }
-
-    let serialized =
-        bincode::serialize(did).map_err(|e| WebullError::SerializationError(e.to_string()))?;
-
-    let mut file = File::create(&filename)?;
-    file.write_all(&serialized)?;
-    Ok(())
+    store.save(did)
 }
 
 /// Hash password with Webull's salt
@@ -62,24 +32,7 @@ pub fn hash_password(password: &str) -> String {
 
 /// Determine account type from username
 pub fn get_account_type(username: &str) -> Result<i32> {
-    // Check if it's an email
-    if username.contains('@') {
-        if validate_email(username) {
-            return Ok(2); // Email account
-        } else {
-            return Err(WebullError::InvalidParameter(
-                "Invalid email format".to_string(),
-            ));
-        }
-    }
-
-    // Check if it's a phone number
-    if username.starts_with('+') {
-        return Ok(1); // Phone account
-    }
-
-    // Default to email type if no clear indicator
-    Ok(2)
+    AccountIdentifier::parse(username).map(|identifier| identifier.account_type())
 }
 
 /// Simple email validation
@@ -108,20 +61,12 @@ pub fn timestamp_to_string(timestamp: i64) -> String {
 }
 
 /// Parse time interval string (e.g., "1m", "5m", "1h", "1d")
+///
+/// Accepts the same spellings as `BarInterval::from_str` and returns the
+/// input unchanged; parse into a `BarInterval` for the API code.
 pub fn parse_interval(interval: &str) -> Result<String> {
-    let valid_intervals = vec![
-        "1m", "3m", "5m", "15m", "30m", "60m", "120m", "240m", "1h", "2h", "4h", "1d", "1w", "1M",
-        "d1", "d5", "m1", "m5", "m15", "m30", "m60", "m120", "m240", "h1", "h2", "h4", "w1", "mo1",
-    ];
-
-    if valid_intervals.contains(&interval) {
-        Ok(interval.to_string())
-    } else {
-        Err(WebullError::InvalidParameter(format!(
-            "Invalid interval: {}",
-            interval
-        )))
-    }
+    interval.parse::<BarInterval>()?;
+    Ok(interval.to_string())
 }
 
 /// Format a float to a string with specified decimal places
@@ -156,6 +101,114 @@ pub fn base64_decode(encoded: &str) -> Result<Vec<u8>> {
         .map_err(|e| WebullError::SerializationError(e.to_string()))
 }
 
+/// Check an ISIN's format and check digit, e.g. `US0378331005`
+pub fn is_valid_isin(isin: &str) -> bool {
+    let isin = isin.trim();
+    if isin.len() != 12
+        || !isin.is_ascii()
+        || !isin[..2].chars().all(|c| c.is_ascii_uppercase())
+        || !isin
+            .chars()
+            .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase())
+        || !isin[11..].chars().all(|c| c.is_ascii_digit())
+    {
+        return false;
+    }
+
+    // Letters expand to two digits (A = 10), then Luhn over the result
+    let digits: Vec<u32> = isin
+        .chars()
+        .flat_map(|c| {
+            let value = c.to_digit(36).unwrap_or(0);
+            if value >= 10 {
+                vec![value / 10, value % 10]
+            } else {
+                vec![value]
+            }
+        })
+        .collect();
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                doubled / 10 + doubled % 10
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+/// Check a CUSIP's format and check digit, e.g. `037833100`
+pub fn is_valid_cusip(cusip: &str) -> bool {
+    let cusip = cusip.trim();
+    if cusip.len() != 9 || !cusip.is_ascii() {
+        return false;
+    }
+
+    let mut sum = 0;
+    for (i, c) in cusip[..8].chars().enumerate() {
+        let value = match c {
+            '0'..='9' | 'A'..='Z' => c.to_digit(36).unwrap_or(0),
+            '*' => 36,
+            '@' => 37,
+            '#' => 38,
+            _ => return false,
+        };
+        let value = if i % 2 == 1 { value * 2 } else { value };
+        sum += value / 10 + value % 10;
+    }
+    let check = (10 - sum % 10) % 10;
+    cusip[8..].parse::<u32>() == Ok(check)
+}
+
+/// Read a JSON response body, surfacing Webull error bodies as typed errors
+pub(crate) async fn read_json<T: serde::de::DeserializeOwned>(
+    response: reqwest::Response,
+) -> Result<T> {
+    let status = response.status();
+    let text = response.text().await?;
+    parse_json(status.as_u16(), text)
+}
+
+/// `read_json` for a body that has already been read
+pub(crate) fn parse_json<T: serde::de::DeserializeOwned>(status: u16, text: String) -> Result<T> {
+    let value: serde_json::Value = match serde_json::from_str(&text) {
+        Ok(value) => value,
+        Err(_) if !(200..300).contains(&status) => {
+            return Err(WebullError::ApiErrorCode {
+                code: status.to_string(),
+                message: text,
+            })
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    if let Some(error) = WebullError::from_api_body(status, &value) {
+        crate::telemetry::record_error(&error);
+        return Err(error);
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Check a response that carries no data, surfacing Webull error bodies
+///
+/// Returns `Ok(false)` for failures without a recognizable error body.
+pub(crate) async fn check_success(response: reqwest::Response) -> Result<bool> {
+    let status = response.status();
+    let text = response.text().await?;
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(&text) {
+        if let Some(error) = WebullError::from_api_body(status.as_u16(), &value) {
+            return Err(error);
+        }
+    }
+    Ok(status.is_success())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -195,4 +248,19 @@ mod tests {
         assert_eq!(format_price(123.456789, 2), "123.46");
         assert_eq!(format_price(0.001234, 4), "0.0012");
     }
+
+    #[test]
+    fn test_security_identifiers() {
+        assert!(is_valid_isin("US0378331005"));
+        assert!(is_valid_isin("GB0002634946"));
+        assert!(!is_valid_isin("US0378331006"));
+        assert!(!is_valid_isin("us0378331005"));
+        assert!(!is_valid_isin("US037833100"));
+        assert!(!is_valid_isin("Aé037833100"));
+
+        assert!(is_valid_cusip("037833100"));
+        assert!(is_valid_cusip("38259P508"));
+        assert!(!is_valid_cusip("037833101"));
+        assert!(!is_valid_cusip("0378331"));
+    }
 }
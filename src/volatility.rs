@@ -0,0 +1,218 @@
+// Historical volatility and implied volatility rank
+
+use crate::models::{Bar, OptionContract};
+use serde::{Deserialize, Serialize};
+
+/// Trading days used to annualize daily volatility
+const TRADING_DAYS: f64 = 252.0;
+
+/// Annualized close-to-close volatility over the last `window` daily bars
+///
+/// Uses log returns and the sample standard deviation. Bars may be in any
+/// order; `None` if there are fewer than `window + 1` usable closes.
+pub fn historical_volatility(bars: &[Bar], window: usize) -> Option<f64> {
+    if window < 2 {
+        return None;
+    }
+    let mut closes: Vec<(i64, f64)> = bars
+        .iter()
+        .filter(|b| b.close > 0.0)
+        .map(|b| (b.timestamp, b.close))
+        .collect();
+    closes.sort_by_key(|(ts, _)| *ts);
+    closes.dedup_by_key(|(ts, _)| *ts);
+    if closes.len() < window + 1 {
+        return None;
+    }
+
+    let recent = &closes[closes.len() - window - 1..];
+    let returns: Vec<f64> = recent
+        .windows(2)
+        .map(|pair| (pair[1].1 / pair[0].1).ln())
+        .collect();
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+    let variance =
+        returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+    Some(variance.sqrt() * TRADING_DAYS.sqrt())
+}
+
+/// Standard historical volatility windows
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoricalVolatility {
+    pub hv20: Option<f64>,
+    pub hv30: Option<f64>,
+    pub hv60: Option<f64>,
+}
+
+impl HistoricalVolatility {
+    /// Compute 20, 30 and 60 day volatility from daily bars
+    pub fn from_bars(bars: &[Bar]) -> Self {
+        Self {
+            hv20: historical_volatility(bars, 20),
+            hv30: historical_volatility(bars, 30),
+            hv60: historical_volatility(bars, 60),
+        }
+    }
+}
+
+/// Implied volatility of the front-month, at-the-money contracts
+///
+/// Averages the IV of every contract in the nearest expiration whose strike
+/// is closest to `underlying_price` (usually the call and the put).
+pub fn atm_implied_volatility(contracts: &[OptionContract], underlying_price: f64) -> Option<f64> {
+    let front = contracts
+        .iter()
+        .filter(|c| c.implied_volatility.is_some())
+        .map(|c| c.expiration_date.as_str())
+        .min()?;
+    let in_front = || {
+        contracts
+            .iter()
+            .filter(move |c| c.expiration_date == front)
+            .filter(|c| c.implied_volatility.is_some())
+    };
+
+    let distance = |c: &OptionContract| (c.strike_price - underlying_price).abs();
+    let nearest = in_front().map(distance).fold(f64::INFINITY, f64::min);
+    let ivs: Vec<f64> = in_front()
+        .filter(|c| distance(c) <= nearest + f64::EPSILON)
+        .filter_map(|c| c.implied_volatility)
+        .collect();
+    Some(ivs.iter().sum::<f64>() / ivs.len() as f64)
+}
+
+/// Where implied volatility sits relative to its own history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VolatilityRegime {
+    /// IV rank below 30: options are cheap, favour debit strategies
+    Low,
+    Normal,
+    /// IV rank above 70: options are rich, favour credit strategies
+    High,
+}
+
+/// IV rank and percentile of the current IV against stored history
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IvRank {
+    pub current: f64,
+    pub low: f64,
+    pub high: f64,
+    /// Position of `current` between the historical low and high, 0-100
+    pub rank: f64,
+    /// Share of historical observations below `current`, 0-100
+    pub percentile: f64,
+}
+
+impl IvRank {
+    /// Rank `current` against `history`; `None` if there is no history
+    pub fn compute(history: &[f64], current: f64) -> Option<Self> {
+        let values: Vec<f64> = history.iter().copied().filter(|v| v.is_finite()).collect();
+        if values.is_empty() {
+            return None;
+        }
+        let low = values.iter().copied().fold(current, f64::min);
+        let high = values.iter().copied().fold(current, f64::max);
+        let rank = if high > low {
+            (current - low) / (high - low) * 100.0
+        } else {
+            50.0
+        };
+        let below = values.iter().filter(|&&v| v < current).count();
+
+        Some(Self {
+            current,
+            low,
+            high,
+            rank,
+            percentile: below as f64 / values.len() as f64 * 100.0,
+        })
+    }
+
+    pub fn regime(&self) -> VolatilityRegime {
+        if self.rank < 30.0 {
+            VolatilityRegime::Low
+        } else if self.rank > 70.0 {
+            VolatilityRegime::High
+        } else {
+            VolatilityRegime::Normal
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn daily(closes: &[f64]) -> Vec<Bar> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Bar {
+                timestamp: i as i64 * 86_400,
+                open: close,
+                high: close,
+                low: close,
+                close,
+                volume: 0.0,
+                vwap: close,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_historical_volatility() {
+        // Constant growth has no variance
+        let steady: Vec<f64> = (0..30).map(|i| 100.0 * 1.01f64.powi(i)).collect();
+        assert!(historical_volatility(&daily(&steady), 20).unwrap() < 1e-9);
+
+        // Alternating +/-1% moves: daily stdev ~1%, annualized ~16%
+        let choppy: Vec<f64> = (0..61)
+            .map(|i| if i % 2 == 0 { 100.0 } else { 101.0 })
+            .collect();
+        let hv = HistoricalVolatility::from_bars(&daily(&choppy));
+        let hv20 = hv.hv20.unwrap();
+        assert!((hv20 - 0.16).abs() < 0.01, "hv20 = {}", hv20);
+        assert!(hv.hv60.is_some());
+        assert_eq!(historical_volatility(&daily(&choppy[..20]), 20), None);
+    }
+
+    #[test]
+    fn test_atm_implied_volatility_uses_front_month() {
+        let contract = |exp: &str, strike: f64, kind: &str, iv: f64| -> OptionContract {
+            serde_json::from_value(json!({
+                "tickerId": 1,
+                "symbol": "AAPL",
+                "strikePrice": strike,
+                "expirationDate": exp,
+                "optionType": kind,
+                "impliedVolatility": iv
+            }))
+            .unwrap()
+        };
+        let chain = vec![
+            contract("2024-07-19", 150.0, "CALL", 0.30),
+            contract("2024-07-19", 150.0, "PUT", 0.32),
+            contract("2024-07-19", 155.0, "CALL", 0.28),
+            contract("2024-08-16", 150.0, "CALL", 0.40),
+        ];
+        let iv = atm_implied_volatility(&chain, 151.0).unwrap();
+        assert!((iv - 0.31).abs() < 1e-9);
+        assert_eq!(atm_implied_volatility(&[], 151.0), None);
+    }
+
+    #[test]
+    fn test_iv_rank_and_percentile() {
+        let history = [0.20, 0.25, 0.30, 0.35, 0.40];
+        let rank = IvRank::compute(&history, 0.38).unwrap();
+        assert!((rank.rank - 90.0).abs() < 1e-9);
+        assert!((rank.percentile - 80.0).abs() < 1e-9);
+        assert_eq!(rank.regime(), VolatilityRegime::High);
+
+        let low = IvRank::compute(&history, 0.22).unwrap();
+        assert_eq!(low.regime(), VolatilityRegime::Low);
+        assert!(IvRank::compute(&[], 0.3).is_none());
+    }
+}
@@ -0,0 +1,331 @@
+// Portable watchlist export/import and server-side reconciliation
+
+use crate::error::{Result, WebullError};
+use crate::models::Watchlist;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Version written to JSON exports
+const FORMAT_VERSION: u32 = 1;
+
+/// A watchlist reduced to its name and symbols
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortableWatchlist {
+    pub name: String,
+    pub symbols: Vec<String>,
+}
+
+impl PortableWatchlist {
+    pub fn new<S: AsRef<str>>(name: impl Into<String>, symbols: &[S]) -> Self {
+        let mut list = Self {
+            name: name.into(),
+            symbols: Vec::new(),
+        };
+        for symbol in symbols {
+            list.push(symbol.as_ref());
+        }
+        list
+    }
+
+    /// Add a symbol, uppercased, unless it's blank or already listed
+    pub fn push(&mut self, symbol: &str) {
+        let symbol = symbol.trim().to_ascii_uppercase();
+        if !symbol.is_empty() && !self.symbols.contains(&symbol) {
+            self.symbols.push(symbol);
+        }
+    }
+}
+
+impl From<&Watchlist> for PortableWatchlist {
+    fn from(watchlist: &Watchlist) -> Self {
+        let symbols: Vec<&str> = watchlist
+            .tickers
+            .iter()
+            .map(|item| item.symbol.as_str())
+            .collect();
+        Self::new(watchlist.name.clone(), &symbols)
+    }
+}
+
+/// A set of watchlists in a format that survives moving between accounts
+///
+/// JSON exports look like
+/// `{"version": 1, "watchlists": [{"name": "Tech", "symbols": ["AAPL"]}]}`.
+/// CSV exports have a `watchlist,symbol` header and one row per symbol, in
+/// list order, for editing in a spreadsheet.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchlistBundle {
+    #[serde(default = "format_version")]
+    pub version: u32,
+    pub watchlists: Vec<PortableWatchlist>,
+}
+
+fn format_version() -> u32 {
+    FORMAT_VERSION
+}
+
+impl WatchlistBundle {
+    pub fn new(watchlists: Vec<PortableWatchlist>) -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            watchlists,
+        }
+    }
+
+    /// Bundle server-side watchlists
+    pub fn from_watchlists(watchlists: &[Watchlist]) -> Self {
+        Self::new(watchlists.iter().map(PortableWatchlist::from).collect())
+    }
+
+    /// Watchlist by name, case-insensitively
+    pub fn get(&self, name: &str) -> Option<&PortableWatchlist> {
+        self.watchlists
+            .iter()
+            .find(|list| list.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("watchlist,symbol\n");
+        for list in &self.watchlists {
+            for symbol in &list.symbols {
+                csv.push_str(&csv_field(&list.name));
+                csv.push(',');
+                csv.push_str(&csv_field(symbol));
+                csv.push('\n');
+            }
+        }
+        csv
+    }
+
+    /// Parse a `watchlist,symbol` CSV; the header row is optional
+    pub fn from_csv(csv: &str) -> Result<Self> {
+        let mut bundle = Self::new(Vec::new());
+        for (number, line) in csv.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields = split_csv_line(line);
+            let (name, symbol) = match fields.as_slice() {
+                [name, symbol, ..] => (name.trim(), symbol.trim()),
+                _ => {
+                    return Err(WebullError::ParseError(format!(
+                        "Watchlist CSV line {} needs a name and a symbol",
+                        number + 1
+                    )))
+                }
+            };
+            if number == 0
+                && name.eq_ignore_ascii_case("watchlist")
+                && symbol.eq_ignore_ascii_case("symbol")
+            {
+                continue;
+            }
+            match bundle.watchlists.iter_mut().find(|list| list.name == name) {
+                Some(list) => list.push(symbol),
+                None => bundle
+                    .watchlists
+                    .push(PortableWatchlist::new(name, &[symbol])),
+            }
+        }
+        Ok(bundle)
+    }
+
+    /// Write to a file, as CSV when the extension is `.csv` and JSON otherwise
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let contents = if is_csv(path) {
+            self.to_csv()
+        } else {
+            self.to_json()?
+        };
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Read a file written by `save` (or by hand), by its extension
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        if is_csv(path) {
+            Self::from_csv(&contents)
+        } else {
+            Self::from_json(&contents)
+        }
+    }
+}
+
+fn is_csv(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Changes needed to bring a server-side watchlist in line with a local one
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchlistDiff {
+    pub watchlist_id: Option<String>,
+    /// Whether the watchlist had to be created
+    pub created: bool,
+    /// Symbols to add, in local order
+    pub add: Vec<String>,
+    /// Ticker IDs and symbols to remove
+    pub remove: Vec<(i64, String)>,
+}
+
+impl WatchlistDiff {
+    /// Compare a server-side watchlist (or none) with the symbols it should hold
+    pub fn between(current: Option<&Watchlist>, desired: &PortableWatchlist) -> Self {
+        let held: Vec<String> = current
+            .map(|list| {
+                list.tickers
+                    .iter()
+                    .map(|item| item.symbol.to_ascii_uppercase())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let add = desired
+            .symbols
+            .iter()
+            .filter(|symbol| !held.contains(symbol))
+            .cloned()
+            .collect();
+        let remove = current
+            .map(|list| {
+                list.tickers
+                    .iter()
+                    .filter(|item| !desired.symbols.contains(&item.symbol.to_ascii_uppercase()))
+                    .map(|item| (item.ticker_id, item.symbol.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            watchlist_id: current.and_then(|list| list.id.clone()),
+            created: current.is_none(),
+            add,
+            remove,
+        }
+    }
+
+    /// Whether the lists already match
+    pub fn is_empty(&self) -> bool {
+        !self.created && self.add.is_empty() && self.remove.is_empty()
+    }
+
+    /// ID of the server-side list to change, `None` if it must be created
+    ///
+    /// A list that matched by name but came back without an ID can't be
+    /// edited; that is an error rather than a reason to create a second list
+    /// with the same name.
+    pub(crate) fn existing_id(&self) -> Result<Option<&str>> {
+        match (&self.watchlist_id, self.created) {
+            (Some(id), _) => Ok(Some(id)),
+            (None, true) => Ok(None),
+            (None, false) => Err(WebullError::ApiError(
+                "Watchlist was listed without an ID".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_csv_and_json_round_trip() {
+        let bundle = WatchlistBundle::new(vec![
+            PortableWatchlist::new("Tech, large cap", &["aapl", "MSFT", "AAPL"]),
+            PortableWatchlist::new("ETFs", &["SPY"]),
+        ]);
+        assert_eq!(bundle.watchlists[0].symbols, vec!["AAPL", "MSFT"]);
+
+        let csv = bundle.to_csv();
+        assert_eq!(
+            csv,
+            "watchlist,symbol\n\"Tech, large cap\",AAPL\n\"Tech, large cap\",MSFT\nETFs,SPY\n"
+        );
+        assert_eq!(WatchlistBundle::from_csv(&csv).unwrap(), bundle);
+        assert_eq!(
+            WatchlistBundle::from_json(&bundle.to_json().unwrap()).unwrap(),
+            bundle
+        );
+
+        // No header and no version
+        let bundle = WatchlistBundle::from_csv("Growth,tsla\n\nGrowth,nvda\n").unwrap();
+        assert_eq!(bundle.get("growth").unwrap().symbols, vec!["TSLA", "NVDA"]);
+        assert!(WatchlistBundle::from_json(r#"{"watchlists": []}"#).is_ok());
+        assert!(WatchlistBundle::from_csv("just-a-name\n").is_err());
+    }
+
+    #[test]
+    fn test_diff_against_server_list() {
+        let server: Watchlist = serde_json::from_value(json!({
+            "id": 42,
+            "name": "Tech",
+            "tickerList": [
+                {"tickerId": 913256135, "disSymbol": "AAPL"},
+                {"tickerId": 913303964, "disSymbol": "INTC"}
+            ]
+        }))
+        .unwrap();
+        let desired = PortableWatchlist::new("Tech", &["AAPL", "NVDA"]);
+
+        let diff = WatchlistDiff::between(Some(&server), &desired);
+        assert_eq!(diff.watchlist_id.as_deref(), Some("42"));
+        assert!(!diff.created);
+        assert_eq!(diff.add, vec!["NVDA"]);
+        assert_eq!(diff.remove, vec![(913303964, "INTC".to_string())]);
+
+        assert_eq!(diff.existing_id().unwrap(), Some("42"));
+
+        // Matched by name but missing its ID: never create a duplicate
+        let unnamed: Watchlist =
+            serde_json::from_value(json!({"name": "Tech", "tickerList": []})).unwrap();
+        let diff = WatchlistDiff::between(Some(&unnamed), &desired);
+        assert!(!diff.created);
+        assert!(diff.existing_id().is_err());
+
+        let diff = WatchlistDiff::between(None, &desired);
+        assert_eq!(diff.existing_id().unwrap(), None);
+        assert!(diff.created);
+        assert_eq!(diff.add.len(), 2);
+        assert!(!diff.is_empty());
+    }
+}
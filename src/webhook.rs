@@ -0,0 +1,373 @@
+// Forwarding of account events to webhooks and async callbacks
+
+use crate::{
+    error::{Result, WebullError},
+    events::{AccountEvent, AccountEventKind},
+};
+use futures::future::BoxFuture;
+use futures::stream::{Stream, StreamExt};
+use log::warn;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Async callback receiving forwarded events
+pub type EventHandler = Arc<dyn Fn(AccountEvent) -> BoxFuture<'static, Result<()>> + Send + Sync>;
+
+/// Which events get forwarded
+pub type EventFilter = Arc<dyn Fn(&AccountEvent) -> bool + Send + Sync>;
+
+/// Body sent to a webhook
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookFormat {
+    /// The `AccountEvent` as JSON
+    Json,
+    /// `{"text": ...}` for Slack incoming webhooks
+    Slack,
+    /// `{"content": ...}` for Discord webhooks
+    Discord,
+}
+
+impl WebhookFormat {
+    /// Request body for `event`
+    pub fn body(self, event: &AccountEvent) -> Value {
+        match self {
+            WebhookFormat::Json => serde_json::to_value(event).unwrap_or(Value::Null),
+            WebhookFormat::Slack => json!({ "text": summary(event) }),
+            WebhookFormat::Discord => json!({ "content": summary(event) }),
+        }
+    }
+}
+
+/// One-line description of an event for chat messages
+pub fn summary(event: &AccountEvent) -> String {
+    match &event.kind {
+        AccountEventKind::Order(order) => match order.filled_quantity {
+            Some(filled) => format!(
+                "Order {} {:?} ({} filled)",
+                order.order_id, order.status, filled
+            ),
+            None => format!("Order {} {:?}", order.order_id, order.status),
+        },
+        AccountEventKind::Position(change) => format!(
+            "Position {}: {} -> {}",
+            change.symbol, change.previous_quantity, change.quantity
+        ),
+        AccountEventKind::Notification(notification) => {
+            format!("Notification: {}", notification.title)
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Webhook {
+    url: String,
+    format: WebhookFormat,
+}
+
+impl Webhook {
+    /// Host of the URL, which is all that's safe to show: a Slack or
+    /// Discord webhook URL carries its secret in the path
+    fn host(&self) -> String {
+        url::Url::parse(&self.url)
+            .ok()
+            .and_then(|u| u.host_str().map(String::from))
+            .unwrap_or_else(|| "<invalid url>".to_string())
+    }
+}
+
+impl std::fmt::Debug for Webhook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Webhook")
+            .field("host", &self.host())
+            .field("format", &self.format)
+            .finish()
+    }
+}
+
+/// Forwards account events to HTTP webhooks and async callbacks
+///
+/// Order and position events are forwarded by default; use `filter` to
+/// change that. Each event goes to every target, and a failing target
+/// doesn't stop the others.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use webull_unofficial::events::AccountEvents;
+/// use webull_unofficial::webhook::{WebhookBridge, WebhookFormat};
+/// use webull_unofficial::WebullClient;
+///
+/// # async fn run(client: WebullClient) {
+/// let bridge = WebhookBridge::new()
+///     .webhook_as("https://hooks.slack.com/services/T000/B000/XXXX", WebhookFormat::Slack)
+///     .callback(|event| async move {
+///         println!("#{} {:?}", event.sequence, event.kind);
+///         Ok(())
+///     });
+/// bridge
+///     .run(client.account_events(AccountEvents::new(Duration::from_secs(30))))
+///     .await;
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct WebhookBridge {
+    client: Client,
+    headers: HeaderMap,
+    timeout: Duration,
+    webhooks: Vec<Webhook>,
+    handlers: Vec<EventHandler>,
+    filter: EventFilter,
+}
+
+impl std::fmt::Debug for WebhookBridge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookBridge")
+            .field("webhooks", &self.webhooks)
+            .field("handlers", &self.handlers.len())
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl Default for WebhookBridge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WebhookBridge {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            headers: HeaderMap::new(),
+            timeout: Duration::from_secs(10),
+            webhooks: Vec::new(),
+            handlers: Vec::new(),
+            filter: Arc::new(|event: &AccountEvent| {
+                matches!(
+                    event.kind,
+                    AccountEventKind::Order(_) | AccountEventKind::Position(_)
+                )
+            }),
+        }
+    }
+
+    /// Send webhooks with this client, e.g. one sharing a proxy
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// POST each event to `url` as JSON
+    pub fn webhook(self, url: impl Into<String>) -> Self {
+        self.webhook_as(url, WebhookFormat::Json)
+    }
+
+    /// POST each event to `url` in `format`
+    pub fn webhook_as(mut self, url: impl Into<String>, format: WebhookFormat) -> Self {
+        self.webhooks.push(Webhook {
+            url: url.into(),
+            format,
+        });
+        self
+    }
+
+    /// Header sent with every webhook request, e.g. an authorization token
+    pub fn header(mut self, name: &str, value: &str) -> Result<Self> {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| WebullError::InvalidParameter(e.to_string()))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|e| WebullError::InvalidParameter(e.to_string()))?;
+        self.headers.insert(name, value);
+        Ok(self)
+    }
+
+    /// Timeout per webhook request (10 seconds by default)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Call `handler` with each event
+    pub fn callback<F, Fut>(mut self, handler: F) -> Self
+    where
+        F: Fn(AccountEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.handlers
+            .push(Arc::new(move |event| Box::pin(handler(event))));
+        self
+    }
+
+    /// Forward only events for which `filter` returns true
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&AccountEvent) -> bool + Send + Sync + 'static,
+    {
+        self.filter = Arc::new(filter);
+        self
+    }
+
+    /// Send one event to every target
+    ///
+    /// Returns the first failure after trying them all; filtered-out events
+    /// succeed without being sent.
+    pub async fn forward(&self, event: &AccountEvent) -> Result<()> {
+        if !(self.filter)(event) {
+            return Ok(());
+        }
+
+        let mut first_error = None;
+        for webhook in &self.webhooks {
+            if let Err(e) = self.post(webhook, event).await {
+                warn!("Webhook to {} failed: {}", webhook.host(), e);
+                first_error.get_or_insert(e);
+            }
+        }
+        for handler in &self.handlers {
+            if let Err(e) = handler(event.clone()).await {
+                warn!("Event callback failed: {}", e);
+                first_error.get_or_insert(e);
+            }
+        }
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Forward every event from `events` until it ends
+    ///
+    /// Errors from the feed and from targets are logged and skipped.
+    pub async fn run<S>(&self, events: S)
+    where
+        S: Stream<Item = Result<AccountEvent>>,
+    {
+        futures::pin_mut!(events);
+        while let Some(event) = events.next().await {
+            match event {
+                // Failures are already logged per target
+                Ok(event) => {
+                    let _ = self.forward(&event).await;
+                }
+                Err(e) => warn!("Account event feed error: {}", e),
+            }
+        }
+    }
+
+    async fn post(&self, webhook: &Webhook, event: &AccountEvent) -> Result<()> {
+        let response = self
+            .client
+            .post(&webhook.url)
+            .headers(self.headers.clone())
+            .json(&webhook.format.body(event))
+            .timeout(self.timeout)
+            .send()
+            .await
+            .map_err(|e| e.without_url())?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(WebullError::ApiErrorCode {
+                code: response.status().as_u16().to_string(),
+                message: response.text().await.unwrap_or_default(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{OrderUpdate, PositionChange};
+    use crate::models::{OrderStatus, SecurityEvent};
+    use chrono::Utc;
+    use parking_lot::Mutex;
+
+    fn event(sequence: u64, kind: AccountEventKind) -> AccountEvent {
+        AccountEvent {
+            sequence,
+            at: Utc::now(),
+            kind,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bridge_forwards_orders_and_positions() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let bridge = WebhookBridge::new().callback(move |event| {
+            let sink = Arc::clone(&sink);
+            async move {
+                sink.lock().push(event.sequence);
+                Ok(())
+            }
+        });
+
+        let order = event(
+            1,
+            AccountEventKind::Order(OrderUpdate {
+                order_id: "42".to_string(),
+                status: OrderStatus::Filled,
+                filled_quantity: Some(10.0),
+                raw: Value::Null,
+            }),
+        );
+        let position = event(
+            2,
+            AccountEventKind::Position(PositionChange {
+                symbol: "AAPL".to_string(),
+                ticker_id: Some(913256135),
+                previous_quantity: 0.0,
+                quantity: 10.0,
+            }),
+        );
+        let notification = event(
+            3,
+            AccountEventKind::Notification(
+                SecurityEvent::from_value(&json!({"id": "n1", "title": "New login"})).unwrap(),
+            ),
+        );
+        bridge
+            .run(futures::stream::iter(vec![
+                Ok(order.clone()),
+                Err(WebullError::Unknown("poll failed".to_string())),
+                Ok(position.clone()),
+                Ok(notification),
+            ]))
+            .await;
+        assert_eq!(*seen.lock(), vec![1, 2]);
+
+        assert_eq!(
+            WebhookFormat::Slack.body(&order),
+            json!({"text": "Order 42 Filled (10 filled)"})
+        );
+        assert_eq!(
+            WebhookFormat::Discord.body(&position),
+            json!({"content": "Position AAPL: 0 -> 10"})
+        );
+        assert_eq!(WebhookFormat::Json.body(&order)["sequence"], 1);
+
+        let failing = WebhookBridge::new()
+            .callback(|_| async { Err(WebullError::Unknown("down".to_string())) });
+        assert!(failing.forward(&order).await.is_err());
+        assert!(WebhookBridge::new().header("bad header", "x").is_err());
+    }
+
+    #[test]
+    fn test_debug_hides_webhook_secrets() {
+        let bridge = WebhookBridge::new()
+            .webhook_as(
+                "https://hooks.slack.com/services/T000/B000/XXXX",
+                WebhookFormat::Slack,
+            )
+            .webhook("not a url/secret");
+        let debug = format!("{:?}", bridge);
+        assert!(debug.contains("hooks.slack.com"));
+        assert!(!debug.contains("XXXX"));
+        assert!(!debug.contains("secret"));
+    }
+}
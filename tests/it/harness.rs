@@ -0,0 +1,87 @@
+use std::env;
+use webull_unofficial::{error::Result, WebullClient};
+
+/// Credentials and settings read from the environment
+pub struct Settings {
+    pub username: String,
+    pub password: String,
+    pub region: i32,
+    pub mfa: Option<String>,
+    pub symbol: String,
+}
+
+impl Settings {
+    /// `None` (after printing why) when the suite isn't configured
+    pub fn from_env() -> Option<Self> {
+        dotenv::dotenv().ok();
+        let (username, password) = match (env::var("WEBULL_USERNAME"), env::var("WEBULL_PASSWORD"))
+        {
+            (Ok(username), Ok(password)) => (username, password),
+            _ => {
+                eprintln!("skipping: WEBULL_USERNAME and WEBULL_PASSWORD not set");
+                return None;
+            }
+        };
+        Some(Self {
+            username,
+            password,
+            region: env::var("WEBULL_REGION")
+                .ok()
+                .and_then(|r| r.parse().ok())
+                .unwrap_or(6),
+            mfa: env::var("WEBULL_MFA").ok(),
+            symbol: env::var("WEBULL_IT_SYMBOL").unwrap_or_else(|_| "AAPL".to_string()),
+        })
+    }
+
+    /// Log in a live or paper client
+    pub async fn login(&self, paper: bool) -> Result<WebullClient> {
+        let mut client = if paper {
+            WebullClient::new_paper(Some(self.region))?
+        } else {
+            WebullClient::new_live(Some(self.region))?
+        };
+        client
+            .login(
+                &self.username,
+                &self.password,
+                None,
+                self.mfa.as_deref(),
+                None,
+                None,
+            )
+            .await?
+            .into_success()?;
+        Ok(client)
+    }
+
+    /// Ticker ID for the configured symbol
+    pub async fn ticker_id(&self, client: &WebullClient) -> Result<String> {
+        let tickers = client.find_ticker(&self.symbol).await?;
+        let ticker = tickers
+            .iter()
+            .find(|t| t.symbol.eq_ignore_ascii_case(&self.symbol))
+            .or_else(|| tickers.first())
+            .expect("symbol search returned no tickers");
+        Ok(ticker.ticker_id.to_string())
+    }
+}
+
+/// Orders placed by a test, cancelled by `cleanup`
+#[derive(Default)]
+pub struct PlacedOrders(Vec<String>);
+
+impl PlacedOrders {
+    pub fn push(&mut self, order_id: String) {
+        self.0.push(order_id);
+    }
+
+    /// Cancel every recorded order, reporting (not failing on) errors
+    pub async fn cleanup(self, client: &WebullClient) {
+        for order_id in self.0 {
+            if let Err(e) = client.cancel_order(&order_id).await {
+                eprintln!("cleanup: failed to cancel order {}: {}", order_id, e);
+            }
+        }
+    }
+}
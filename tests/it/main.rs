@@ -0,0 +1,16 @@
+// End-to-end tests against the real Webull API
+//
+// Every test is ignored by default and skips itself unless credentials are
+// set, so `cargo test` never touches the network. To run them:
+//
+//     WEBULL_USERNAME=... WEBULL_PASSWORD=... \
+//         cargo test --test it -- --ignored --test-threads=1
+//
+// Optional settings: WEBULL_REGION (default 6), WEBULL_MFA for accounts
+// that need a code, and WEBULL_IT_SYMBOL (default AAPL). Orders are placed
+// in the paper account only, far from the market, and cancelled again
+// whether or not the test passes.
+
+mod harness;
+mod market_data;
+mod paper_orders;
@@ -0,0 +1,39 @@
+use crate::harness::Settings;
+use webull_unofficial::models::BarInterval;
+
+#[tokio::test]
+#[ignore = "needs WEBULL_USERNAME and WEBULL_PASSWORD"]
+async fn login_then_market_data() {
+    let settings = match Settings::from_env() {
+        Some(settings) => settings,
+        None => return,
+    };
+    let mut client = settings.login(false).await.expect("login");
+    client.get_account_id().await.expect("account ID");
+
+    let account = client.get_account().await.expect("account");
+    assert!(account.net_liquidation.is_some());
+
+    let ticker_id = settings.ticker_id(&client).await.expect("ticker");
+    let quote = client.get_quotes(&ticker_id).await.expect("quote");
+    assert!(quote.close > 0.0);
+    let by_symbol = client
+        .get_quote_by_symbol(&settings.symbol)
+        .await
+        .expect("quote by symbol");
+    assert_eq!(by_symbol.ticker_id, quote.ticker_id);
+
+    let bars = client
+        .get_bars(&ticker_id, BarInterval::D1, 5, None)
+        .await
+        .expect("bars");
+    assert!(!bars.is_empty());
+    assert!(bars.iter().all(|b| b.high >= b.low));
+
+    // Parsing fails if the live permission response lacks a shortable flag
+    let shortability = client
+        .get_shortability(&ticker_id)
+        .await
+        .expect("shortability");
+    assert_eq!(shortability.ticker_id, ticker_id);
+}
@@ -0,0 +1,51 @@
+use crate::harness::{PlacedOrders, Settings};
+use webull_unofficial::models::*;
+
+#[tokio::test]
+#[ignore = "needs WEBULL_USERNAME and WEBULL_PASSWORD"]
+async fn paper_order_then_cancel() {
+    let settings = match Settings::from_env() {
+        Some(settings) => settings,
+        None => return,
+    };
+    let client = settings.login(true).await.expect("login");
+    let ticker_id = settings.ticker_id(&client).await.expect("ticker");
+    let quote = client.get_quotes(&ticker_id).await.expect("quote");
+
+    // Half the last price keeps the order from filling before it's cancelled
+    let limit_price = (quote.close * 0.5 * 100.0).round() / 100.0;
+    let order = PlaceOrderRequest {
+        ticker_id: ticker_id.parse().expect("numeric ticker ID"),
+        action: OrderAction::Buy,
+        order_type: OrderType::Limit,
+        time_in_force: TimeInForce::Day,
+        quantity: 1.0,
+        limit_price: Some(limit_price),
+        stop_price: None,
+        outside_regular_trading_hour: false,
+        serial_id: None,
+        combo_type: None,
+        expire_date: None,
+        short_sale: false,
+    };
+
+    // Nothing is asserted until cleanup has run, so a failed run never
+    // leaves orders open on the account
+    let mut placed = PlacedOrders::default();
+    let result = async {
+        let order_id = client.place_order(&order).await?;
+        placed.push(order_id.clone());
+
+        let open = client.get_orders(Some(20)).await?;
+        let listed = open.iter().any(|o| o.order_id == order_id);
+        let cancelled = client.cancel_order(&order_id).await?;
+        Ok::<_, webull_unofficial::WebullError>((order_id, listed, cancelled))
+    }
+    .await;
+
+    // Cancelling twice is harmless
+    placed.cleanup(&client).await;
+    let (order_id, listed, cancelled) = result.expect("order round trip");
+    assert!(listed, "order {} not in open orders", order_id);
+    assert!(cancelled, "cancel reported failure");
+}